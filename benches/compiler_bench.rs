@@ -505,13 +505,7 @@ fn bench_kpi_parse_latency(c: &mut Criterion) {
 fn bench_kpi_full_compile_latency(c: &mut Criterion) {
     let mut group = c.benchmark_group("kpi/full_compile_latency");
     let registry = create_loaded_registry();
-    let opts = codegen::CodegenOptions {
-        release: false,
-        include_paths: vec![],
-        provenance: None,
-        experimental: false,
-        bind_overrides: std::collections::HashMap::new(),
-    };
+    let opts = codegen::CodegenOptions::default();
 
     for (name, source) in scenarios() {
         group.bench_with_input(BenchmarkId::from_parameter(name), source, |b, source| {
@@ -525,13 +519,7 @@ fn bench_kpi_full_compile_latency(c: &mut Criterion) {
 // KPI: phase-level latency on a non-trivial program.
 fn bench_kpi_phase_latency(c: &mut Criterion) {
     let registry = create_loaded_registry();
-    let opts = codegen::CodegenOptions {
-        release: false,
-        include_paths: vec![],
-        provenance: None,
-        experimental: false,
-        bind_overrides: std::collections::HashMap::new(),
-    };
+    let opts = codegen::CodegenOptions::default();
     let source = COMPLEX_PIPELINE;
     bench_parse_phase(c, source);
     bench_resolve_phase(c, source, &registry);
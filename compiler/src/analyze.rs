@@ -14,7 +14,7 @@
 //                produce `Diagnostic` entries.
 // Side effects: none.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
 use chumsky::span::Span as _;
 
@@ -26,15 +26,21 @@ use crate::diag::{DiagCode, DiagLevel, Diagnostic};
 use crate::graph::*;
 use crate::hir::{HirSwitchSource, HirTaskBody};
 use crate::id::CallId;
-use crate::registry::{ActorMeta, ParamKind, ParamType, PipitType, PortShape, TokenCount};
+use crate::registry::{
+    ActorMeta, ParamKind, ParamType, PipitType, PortShape, TokenCount, TypeExpr,
+};
 use crate::subgraph_index::{
-    build_global_node_index, build_subgraph_indices, find_node, subgraph_key, subgraphs_of,
-    GraphQueryCtx, SubgraphIndex,
+    build_global_node_index, build_subgraph_indices, find_node, identify_back_edges, subgraph_key,
+    subgraphs_of, GraphQueryCtx, SubgraphIndex,
 };
 use crate::thir::ThirContext;
 
 const SHAPE_WORKLIST_MIN_EDGES: usize = 24;
 
+/// Shape inference step budget per subgraph node (dense passes or worklist
+/// pops). Exceeding `nodes * budget` means propagation is not converging.
+const SHAPE_INFERENCE_STEPS_PER_NODE: usize = 16;
+
 // ── Public types ────────────────────────────────────────────────────────────
 
 /// Result of static analysis.
@@ -54,6 +60,8 @@ pub struct AnalyzedProgram {
     pub inter_task_buffers: HashMap<String, u64>,
     /// Total memory required (bytes).
     pub total_memory: u64,
+    /// Resolved `SCRATCH()` element counts for actor nodes that declare one.
+    pub scratch_sizes: HashMap<NodeId, u32>,
     /// Inferred shape constraints from SDF edge propagation (§13.3.3).
     /// For actors with unresolved SHAPE dims, this stores the shapes inferred
     /// from connected edges. NodeId → inferred ShapeConstraint.
@@ -68,6 +76,13 @@ pub struct AnalyzedProgram {
     pub node_port_rates: HashMap<NodeId, NodePortRates>,
     /// Bind contracts inferred from graph analysis (§5.5).
     pub bind_contracts: HashMap<String, BindContract>,
+    /// Shared array families whose element buffers can share one ring buffer.
+    /// Sorted by family name.
+    pub family_reuse: Vec<FamilyReuse>,
+    /// Longest dependency chain per (task_name, subgraph_label), source to
+    /// sink, with feedback back-edges removed. Absent for subgraphs that stay
+    /// cyclic (a loop without delay).
+    pub critical_path: HashMap<(String, String), Vec<NodeId>>,
 }
 
 /// Concrete input/output token rates for a node.
@@ -87,23 +102,77 @@ pub struct BindContract {
     /// Deterministic ID from graph lineage (§5.5.3). 16-char hex string derived
     /// from SHA-256 of (direction, adjacent actor CallIds, transport).
     pub stable_id: String,
+    /// Author-controlled wire version from the `version=N` endpoint argument.
+    /// `None` when the bind is unversioned.
+    pub version: Option<u16>,
+    /// OUT binds: estimated delay (ms) added by cross-clock shared buffers
+    /// upstream of the writer task. `None` when no cross-clock buffer feeds it.
+    pub latency_ms: Option<f64>,
+}
+
+/// Buffer reuse opportunity for a shared array family (`name[*]`).
+///
+/// All element buffers are written by a single scatter firing and drained by
+/// one gather firing in each reader task, so their live ranges coincide and
+/// the N element ring buffers can be coalesced into one ring of N-token frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FamilyReuse {
+    pub family_name: String,
+    pub element_count: u32,
+    pub writer_task: String,
+    /// Reader tasks (sorted), identical for every element.
+    pub reader_tasks: Vec<String>,
+    /// Total bytes with one ring buffer per element (payload + headers).
+    pub separate_bytes: u64,
+    /// Total bytes with a single coalesced ring buffer.
+    pub coalesced_bytes: u64,
+}
+
+impl FamilyReuse {
+    pub fn saved_bytes(&self) -> u64 {
+        self.separate_bytes - self.coalesced_bytes
+    }
+}
+
+/// Ring buffer payload size used when analysis could not size a buffer.
+pub const DEFAULT_INTER_TASK_BUFFER_BYTES: u64 = 1024;
+
+/// Fixed per-ring-buffer overhead in bytes, mirroring `pipit::RingBuffer`:
+/// one cache line for the head cursor, one per reader tail, and one for the
+/// writer-cached tail (padded by the 64-byte alignment of the class).
+pub fn ring_buffer_header_bytes(readers: usize) -> u64 {
+    64 * (2 + readers.max(1) as u64)
 }
 
 // ── Public entry point ──────────────────────────────────────────────────────
 
 /// Run all static analysis checks on a built SDF program graph.
 pub fn analyze(thir: &ThirContext, graph: &ProgramGraph) -> AnalysisResult {
+    analyze_with_shape_budget(thir, graph, SHAPE_INFERENCE_STEPS_PER_NODE)
+}
+
+fn analyze_with_shape_budget(
+    thir: &ThirContext,
+    graph: &ProgramGraph,
+    shape_steps_per_node: usize,
+) -> AnalysisResult {
     let mut ctx = AnalyzeCtx::new(thir, graph);
+    ctx.shape_steps_per_node = shape_steps_per_node;
     ctx.check_types();
     ctx.record_span_derived_dims();
     ctx.infer_shapes_from_edges();
     ctx.check_shape_constraints();
     ctx.check_dimension_param_order();
     ctx.precompute_node_port_rates();
+    ctx.check_buffer_token_cost();
     ctx.solve_balance_equations();
     ctx.check_feedback_delays();
     ctx.check_cross_clock_rates();
+    ctx.check_source_termination();
+    ctx.check_modal_buffer_writes();
     ctx.compute_buffer_sizes();
+    ctx.analyze_family_reuse();
+    ctx.compute_critical_paths();
     ctx.infer_bind_contracts();
     ctx.validate_bind_endpoints();
     ctx.check_memory_pool();
@@ -121,6 +190,7 @@ struct AnalyzeCtx<'a> {
     repetition_vectors: HashMap<(String, String), HashMap<NodeId, u32>>,
     inter_buffers: HashMap<String, u64>,
     total_memory: u64,
+    scratch_sizes: HashMap<NodeId, u32>,
     inferred_shapes: HashMap<NodeId, ShapeConstraint>,
     span_derived_dims: HashMap<NodeId, HashMap<String, u32>>,
     subgraph_indices: HashMap<usize, SubgraphIndex>,
@@ -128,8 +198,11 @@ struct AnalyzeCtx<'a> {
     global_node_index: HashMap<NodeId, (usize, usize)>,
     rv_by_task: HashMap<String, HashMap<NodeId, u32>>,
     bind_contracts: HashMap<String, BindContract>,
+    family_reuse: Vec<FamilyReuse>,
+    critical_path: HashMap<(String, String), Vec<NodeId>>,
     node_port_rates: HashMap<NodeId, NodePortRates>,
     all_subgraphs: Vec<(&'a str, &'a str, &'a Subgraph)>,
+    shape_steps_per_node: usize,
 }
 
 struct BalanceGraph {
@@ -219,6 +292,7 @@ impl<'a> AnalyzeCtx<'a> {
             repetition_vectors: HashMap::new(),
             inter_buffers: HashMap::new(),
             total_memory: 0,
+            scratch_sizes: HashMap::new(),
             inferred_shapes: HashMap::new(),
             span_derived_dims: HashMap::new(),
             subgraph_indices,
@@ -226,8 +300,11 @@ impl<'a> AnalyzeCtx<'a> {
             global_node_index,
             rv_by_task: HashMap::new(),
             bind_contracts: HashMap::new(),
+            family_reuse: Vec::new(),
+            critical_path: HashMap::new(),
             node_port_rates: HashMap::new(),
             all_subgraphs,
+            shape_steps_per_node: SHAPE_INFERENCE_STEPS_PER_NODE,
         }
     }
 
@@ -258,10 +335,13 @@ impl<'a> AnalyzeCtx<'a> {
                 repetition_vectors: self.repetition_vectors,
                 inter_task_buffers: self.inter_buffers,
                 total_memory: self.total_memory,
+                scratch_sizes: self.scratch_sizes,
                 inferred_shapes: self.inferred_shapes,
                 span_derived_dims: self.span_derived_dims,
                 node_port_rates: self.node_port_rates,
                 bind_contracts: self.bind_contracts,
+                family_reuse: self.family_reuse,
+                critical_path: self.critical_path,
             },
             diagnostics: self.diagnostics,
         }
@@ -413,7 +493,8 @@ impl<'a> AnalyzeCtx<'a> {
     }
 
     /// Get production rate of a node (out_count). Passthrough nodes return 1.
-    /// Uses explicit shape constraint first, then inferred shape as fallback.
+    /// A `RATE_CHANGE(up, down)` actor produces `up`; otherwise the explicit
+    /// shape constraint is used first, then the inferred shape as fallback.
     fn production_rate(&self, node: &Node) -> Option<u32> {
         match &node.kind {
             NodeKind::Actor {
@@ -423,6 +504,13 @@ impl<'a> AnalyzeCtx<'a> {
                 ..
             } => {
                 let meta = self.actor_meta(name)?;
+                if let Some(rate) = meta
+                    .rate_change
+                    .as_ref()
+                    .and_then(|rc| self.thir.resolve_rate_factor(&rc.up, meta, args))
+                {
+                    return Some(rate);
+                }
                 // Try explicit shape constraint first, then inferred
                 let result =
                     self.resolve_port_rate(&meta.out_shape, meta, args, shape_constraint.as_ref());
@@ -445,7 +533,8 @@ impl<'a> AnalyzeCtx<'a> {
     }
 
     /// Get consumption rate of a node (in_count). Passthrough nodes return 1.
-    /// Uses explicit shape constraint first, then inferred shape as fallback.
+    /// A `RATE_CHANGE(up, down)` actor consumes `down`; otherwise the explicit
+    /// shape constraint is used first, then the inferred shape as fallback.
     fn consumption_rate(&self, node: &Node) -> Option<u32> {
         match &node.kind {
             NodeKind::Actor {
@@ -455,6 +544,13 @@ impl<'a> AnalyzeCtx<'a> {
                 ..
             } => {
                 let meta = self.actor_meta(name)?;
+                if let Some(rate) = meta
+                    .rate_change
+                    .as_ref()
+                    .and_then(|rc| self.thir.resolve_rate_factor(&rc.down, meta, args))
+                {
+                    return Some(rate);
+                }
                 let result =
                     self.resolve_port_rate(&meta.in_shape, meta, args, shape_constraint.as_ref());
                 if result.is_some() {
@@ -513,9 +609,30 @@ impl<'a> AnalyzeCtx<'a> {
     ///
     /// Returns:
     /// - (`set mem` value, Some(span)) when explicitly configured.
+    /// - (`N%` of `--platform-memory`, Some(span)) for a percentage.
     /// - (64MB, None) when omitted (spec default).
-    fn get_mem_limit(&self) -> (u64, Option<Span>) {
-        (self.thir.mem_bytes, self.thir.mem_span)
+    ///
+    /// Errs with a message when a `set mem` percentage cannot be resolved
+    /// against the platform budget.
+    fn get_mem_limit(&self) -> Result<(u64, Option<Span>), String> {
+        if let Some(pct) = self.thir.mem_percent {
+            match self.thir.platform_mem_bytes {
+                None => {
+                    return Err(format!(
+                        "`set mem = {}%` needs a platform memory budget, but no --platform-memory was given",
+                        pct
+                    ))
+                }
+                Some(budget) if pct > 100.0 => {
+                    return Err(format!(
+                        "`set mem = {}%` exceeds the platform memory budget of {} bytes",
+                        pct, budget
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        Ok((self.thir.mem_bytes, self.thir.mem_span))
     }
 
     // ── Phase 0: Shape inference from SDF edges (§13.3.3) ────────────────
@@ -527,33 +644,47 @@ impl<'a> AnalyzeCtx<'a> {
 
     fn infer_shapes_from_edges(&mut self) {
         let subs = std::mem::take(&mut self.all_subgraphs);
-        for &(_, _, sub) in &subs {
-            self.infer_shapes_in_subgraph(sub);
+        for &(task_name, label, sub) in &subs {
+            if !self.infer_shapes_in_subgraph(sub) {
+                self.report_shape_non_convergence(task_name, label, sub);
+            }
         }
         self.all_subgraphs = subs;
     }
 
-    fn infer_shapes_in_subgraph(&mut self, sub: &Subgraph) {
+    /// Propagate shapes to a fixpoint. Returns false if the step budget ran
+    /// out first (propagations kept changing each other's results).
+    fn infer_shapes_in_subgraph(&mut self, sub: &Subgraph) -> bool {
         if sub.nodes.is_empty() || sub.edges.is_empty() {
-            return;
+            return true;
         }
         if self.should_use_dense_shape_inference(sub) {
-            self.infer_shapes_in_subgraph_dense(sub);
-            return;
+            return self.infer_shapes_in_subgraph_dense(sub);
         }
 
         let adjacency = ShapeAdjacency::from_subgraph(sub);
         let mut worklist = ShapeWorklist::seeded(sub);
+        let mut steps_left = self.shape_step_limit(sub);
         while let Some(node_id) = worklist.pop() {
+            if steps_left == 0 {
+                return false;
+            }
+            steps_left -= 1;
             self.propagate_shapes_forward(sub, node_id, &adjacency, &mut worklist);
             self.propagate_shapes_reverse(sub, node_id, &adjacency, &mut worklist);
         }
+        true
     }
 
-    fn infer_shapes_in_subgraph_dense(&mut self, sub: &Subgraph) {
+    fn infer_shapes_in_subgraph_dense(&mut self, sub: &Subgraph) -> bool {
         // Dense fallback for small subgraphs: avoids worklist bookkeeping overhead.
+        let mut steps_left = self.shape_step_limit(sub);
         let mut changed = true;
         while changed {
+            if steps_left == 0 {
+                return false;
+            }
+            steps_left -= 1;
             changed = false;
             for edge_idx in 0..sub.edges.len() {
                 if self.propagate_shape_for_edge_dense(sub, edge_idx) {
@@ -561,6 +692,26 @@ impl<'a> AnalyzeCtx<'a> {
                 }
             }
         }
+        true
+    }
+
+    fn shape_step_limit(&self, sub: &Subgraph) -> usize {
+        sub.nodes.len().saturating_mul(self.shape_steps_per_node)
+    }
+
+    fn report_shape_non_convergence(&mut self, task_name: &str, label: &str, sub: &Subgraph) {
+        let span = sub.nodes[0].span;
+        self.error_with_hint(
+            codes::E0314,
+            span,
+            format!(
+                "internal error: shape inference did not converge in task '{}' ({}) within {} steps",
+                task_name,
+                label,
+                self.shape_step_limit(sub)
+            ),
+            "edge shape propagations keep overriding each other; add explicit shape constraints (e.g. `actor()[N]`) and please report this program".to_string(),
+        );
     }
 
     fn should_use_dense_shape_inference(&self, sub: &Subgraph) -> bool {
@@ -1109,8 +1260,15 @@ impl<'a> AnalyzeCtx<'a> {
         let mut pending: Vec<(DiagCode, Span, String, String)> = Vec::new();
 
         // ── 2. Unresolved frame dim check (E0300) ──
+        // A dim whose PARAM declares a default is resolved by that default.
         if shape_constraint.is_none() && args.is_empty() {
-            if let Some(&(first_sym, _)) = symbolic_dims.first() {
+            let first_undefaulted = symbolic_dims.iter().find(|(sym, _)| {
+                !meta
+                    .params
+                    .iter()
+                    .any(|p| p.name == *sym && p.default.is_some())
+            });
+            if let Some(&(first_sym, _)) = first_undefaulted {
                 if self.production_rate(node).is_none() && self.consumption_rate(node).is_none() {
                     pending.push((
                         codes::E0300,
@@ -1413,10 +1571,18 @@ impl<'a> AnalyzeCtx<'a> {
                         ),
                     )
                     .with_code(codes::E0303)
-                    .with_hint(format!(
-                        "insert a conversion actor between {} and {} (e.g. c2r, mag)",
-                        src_name, tgt_name
-                    ))
+                    .with_hint(if st == PipitType::Bool || tt == PipitType::Bool {
+                        format!(
+                            "bool never converts implicitly; insert an actor between {} and {} \
+                             that maps {} to {}",
+                            src_name, tgt_name, st, tt
+                        )
+                    } else {
+                        format!(
+                            "insert a conversion actor between {} and {} (e.g. c2r, mag)",
+                            src_name, tgt_name
+                        )
+                    })
                     .with_related(src_node.span, format!("{} produces {}", src_name, st))
                     .with_related(tgt_node.span, format!("{} expects {}", tgt_name, tt));
                     d = d.with_cause(
@@ -1433,6 +1599,116 @@ impl<'a> AnalyzeCtx<'a> {
         }
     }
 
+    /// `(sink, consumer)` pairs where the output of an actor with a concrete
+    /// `void` out type flows into another actor through passthrough nodes.
+    fn void_fed_actors(&self, sub: &Subgraph) -> Vec<(NodeId, NodeId)> {
+        let mut pairs = Vec::new();
+        for node in &sub.nodes {
+            let NodeKind::Actor { name, call_id, .. } = &node.kind else {
+                continue;
+            };
+            let is_void = self
+                .thir
+                .concrete_actor(name, *call_id)
+                .is_some_and(|m| m.out_type.as_concrete() == Some(PipitType::Void));
+            if !is_void {
+                continue;
+            }
+            let mut stack = vec![node.id];
+            let mut visited = HashSet::new();
+            while let Some(current) = stack.pop() {
+                if !visited.insert(current) {
+                    continue;
+                }
+                for edge in sub.edges.iter().filter(|e| e.source == current) {
+                    let Some(target) = self.node_in_subgraph(sub, edge.target) else {
+                        continue;
+                    };
+                    match target.kind {
+                        NodeKind::Actor { .. } => pairs.push((node.id, target.id)),
+                        NodeKind::Fork { .. } | NodeKind::Probe { .. } => stack.push(target.id),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    // ── Phase 1b: Buffer cost heuristic (W0315) ─────────────────────────
+    //
+    // An actor that consumes far more tokens per firing than its producer
+    // supplies forces the edge to accumulate many producer firings, at least
+    // lcm(in, out) tokens. Only checked when `set warn_buffer_tokens` is given.
+
+    fn check_buffer_token_cost(&mut self) {
+        let Some(threshold) = self.thir.warn_buffer_tokens else {
+            return;
+        };
+        let subs = std::mem::take(&mut self.all_subgraphs);
+        for &(_, _, sub) in &subs {
+            for node in &sub.nodes {
+                let NodeKind::Actor { name, .. } = &node.kind else {
+                    continue;
+                };
+                let Some(in_rate) = self.node_port_rates.get(&node.id).and_then(|r| r.in_rate)
+                else {
+                    continue;
+                };
+                let Some((producer, out_rate)) = self.upstream_producer_rate(node.id, sub) else {
+                    continue;
+                };
+                if out_rate == 0 || in_rate <= out_rate {
+                    continue;
+                }
+                let tokens = lcm(in_rate as u64, out_rate as u64);
+                if tokens <= threshold {
+                    continue;
+                }
+                let type_size = self
+                    .trace_type_backward_concrete(producer.id, sub)
+                    .map(type_size_bytes)
+                    .unwrap_or(4);
+                let msg = format!(
+                    "actor '{}' consumes {} tokens per firing but its producer '{}' supplies {}; \
+                     the edge buffers at least {} tokens ({} bytes)",
+                    name,
+                    in_rate,
+                    node_display_name(producer),
+                    out_rate,
+                    tokens,
+                    tokens * type_size,
+                );
+                let hint = format!(
+                    "the edge exceeds warn_buffer_tokens = {}; have the producer emit \
+                     larger frames or give '{}' a smaller input frame",
+                    threshold, name
+                );
+                self.warning_with_hint(codes::W0315, node.span, msg, hint);
+            }
+        }
+        self.all_subgraphs = subs;
+    }
+
+    /// Nearest upstream node with a known production rate, skipping forks
+    /// and probes, together with that rate.
+    fn upstream_producer_rate<'s>(
+        &self,
+        node_id: NodeId,
+        sub: &'s Subgraph,
+    ) -> Option<(&'s Node, u32)> {
+        let mut current = self.first_incoming_edge_in_subgraph(sub, node_id)?.source;
+        let mut visited = HashSet::new();
+        while visited.insert(current) {
+            let node = self.node_in_subgraph(sub, current)?;
+            if !matches!(node.kind, NodeKind::Fork { .. } | NodeKind::Probe { .. }) {
+                return self.cached_production_rate(node).map(|rate| (node, rate));
+            }
+            current = self.first_incoming_edge_in_subgraph(sub, current)?.source;
+        }
+        None
+    }
+
     // ── Phase 2: SDF balance equation solving ───────────────────────────
 
     fn solve_balance_equations(&mut self) {
@@ -1444,7 +1720,8 @@ impl<'a> AnalyzeCtx<'a> {
     }
 
     fn solve_subgraph_balance(&mut self, task_name: &str, label: &str, sub: &Subgraph) {
-        if sub.nodes.is_empty() {
+        // A sink's zero output rate leaves no ratio to its consumers (E0103).
+        if sub.nodes.is_empty() || !self.void_fed_actors(sub).is_empty() {
             return;
         }
 
@@ -1652,6 +1929,34 @@ impl<'a> AnalyzeCtx<'a> {
             });
             if !has_delay {
                 let cycle_desc = self.format_cycle_path(cycle);
+                let allowed = cycle.iter().find_map(|&nid| {
+                    self.find_node_in_any_subgraph(nid).filter(|node| {
+                        matches!(
+                            &node.kind,
+                            NodeKind::Actor {
+                                allow_combinational_loop: true,
+                                ..
+                            }
+                        )
+                    })
+                });
+                if let Some(node) = allowed {
+                    let span = node.span;
+                    self.warning_with_hint(
+                        codes::W0314,
+                        span,
+                        format!(
+                            "feedback loop at '{}' has no delay (allowed by @allow_combinational_loop)",
+                            cycle_desc
+                        ),
+                        format!(
+                            "the loop is broken at the output of '{}', which is read one \
+                             PASS cycle late (zero-initialized)",
+                            node_display_name(node)
+                        ),
+                    );
+                    continue;
+                }
                 let span = self
                     .find_node_in_any_subgraph(cycle[0])
                     .map(|n| n.span)
@@ -1727,7 +2032,13 @@ impl<'a> AnalyzeCtx<'a> {
                             edge.reader_task,
                             reader_rate,
                         );
-                        self.error(codes::E0306, span, msg);
+                        let hint = resample_hint(
+                            &edge.buffer_name,
+                            &edge.reader_task,
+                            writer_rate,
+                            reader_rate,
+                        );
+                        self.error_with_hint(codes::E0306, span, msg, hint);
                     }
                 }
             }
@@ -1742,6 +2053,149 @@ impl<'a> AnalyzeCtx<'a> {
             .copied()
     }
 
+    // ── Phase 4b: Source termination (W0312) ────────────────────────────
+    //
+    // Classifies each task by the sources its data originates from: bounded
+    // (`TERMINATES` actors such as `binread`, which stop at end of input) or
+    // unbounded (sources that produce forever). A task fed across shared
+    // buffers by both kinds keeps its unbounded side running after the
+    // bounded data ends.
+
+    fn check_source_termination(&mut self) {
+        // task → {(bounded, source actor, task owning the source)}
+        let mut origins: BTreeMap<&'a str, BTreeSet<(bool, &'a str, &'a str)>> = BTreeMap::new();
+        for &(task_name, _, sub) in &self.all_subgraphs {
+            let task_origins = origins.entry(task_name).or_default();
+            for node in &sub.nodes {
+                let NodeKind::Actor { name, .. } = &node.kind else {
+                    continue;
+                };
+                let Some(meta) = self.actor_meta(name) else {
+                    continue;
+                };
+                if meta.in_type == TypeExpr::Concrete(PipitType::Void) {
+                    task_origins.insert((meta.terminates, name.as_str(), task_name));
+                }
+            }
+        }
+
+        // Propagate origins downstream through shared buffers to a fixed point.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for edge in &self.graph.inter_task_edges {
+                let upstream = origins
+                    .get(edge.writer_task.as_str())
+                    .cloned()
+                    .unwrap_or_default();
+                let downstream = origins.entry(edge.reader_task.as_str()).or_default();
+                for origin in upstream {
+                    changed |= downstream.insert(origin);
+                }
+            }
+        }
+
+        for (task_name, task_origins) in &origins {
+            let mixed = task_origins
+                .iter()
+                .filter(|o| o.0)
+                .flat_map(|b| task_origins.iter().filter(|o| !o.0).map(move |u| (b, u)))
+                .find(|(b, u)| b.2 != *task_name || u.2 != *task_name);
+            let Some(((_, bounded, bounded_task), (_, unbounded, unbounded_task))) = mixed else {
+                continue;
+            };
+            let span = self
+                .graph
+                .inter_task_edges
+                .iter()
+                .filter(|e| e.reader_task == *task_name)
+                .min_by(|a, b| a.buffer_name.cmp(&b.buffer_name))
+                .and_then(|e| self.find_node_in_any_subgraph(e.reader_node))
+                .map(|n| n.span)
+                .unwrap_or(Span::new((), 0..0));
+            self.warning_with_hint(
+                codes::W0312,
+                span,
+                format!(
+                    "task '{}' mixes bounded source '{}' (task '{}') with unbounded source '{}' (task '{}')",
+                    task_name, bounded, bounded_task, unbounded, unbounded_task
+                ),
+                format!(
+                    "once '{}' reaches end of input, '{}' keeps producing with nothing to pair against; \
+                     feed this task from bounded sources only",
+                    bounded, unbounded
+                ),
+            );
+        }
+    }
+
+    // ── Phase 4c: Modal write symmetry (W0313) ──────────────────────────
+    //
+    // Each mode of a modal task runs alone in a tick. A shared buffer written
+    // by some modes but not others is left unfed while a non-writing mode is
+    // active, so its consumer sees no fresh data.
+
+    fn check_modal_buffer_writes(&mut self) {
+        for hir_task in &self.thir.hir.tasks {
+            let HirTaskBody::Modal(modal) = &hir_task.body else {
+                continue;
+            };
+            let Some(TaskGraph::Modal { modes, .. }) = self.graph.tasks.get(&hir_task.name) else {
+                continue;
+            };
+
+            // mode → {buffer → first write span}
+            let writes: Vec<(&str, BTreeMap<&str, Span>)> = modes
+                .iter()
+                .map(|(mode_name, sub)| {
+                    let mut bufs = BTreeMap::new();
+                    for node in &sub.nodes {
+                        if let NodeKind::BufferWrite { buffer_name } = &node.kind {
+                            bufs.entry(buffer_name.as_str()).or_insert(node.span);
+                        }
+                    }
+                    (mode_name.as_str(), bufs)
+                })
+                .collect();
+            let all: BTreeSet<&str> = writes.iter().flat_map(|(_, b)| b.keys().copied()).collect();
+
+            for (mode_name, bufs) in &writes {
+                for &buffer in all.iter().filter(|b| !bufs.contains_key(*b)) {
+                    let (writer_mode, write_span) = writes
+                        .iter()
+                        .find_map(|(m, b)| b.get(buffer).map(|s| (*m, *s)))
+                        .expect("buffer comes from some mode");
+                    let span = modal
+                        .modes
+                        .iter()
+                        .find(|(m, _)| m == mode_name)
+                        .map(|(_, p)| p.span)
+                        .unwrap_or(modal.span);
+                    self.diagnostics.push(
+                        Diagnostic::new(
+                            DiagLevel::Warning,
+                            span,
+                            format!(
+                                "mode '{}' of task '{}' does not write shared buffer '{}'",
+                                mode_name, hir_task.name, buffer
+                            ),
+                        )
+                        .with_code(codes::W0313)
+                        .with_related(
+                            write_span,
+                            format!("'{}' is written here in mode '{}'", buffer, writer_mode),
+                        )
+                        .with_hint(format!(
+                            "while '{}' is active, readers of '{}' get no new data; \
+                             write '-> {}' in every mode if they should keep being fed",
+                            mode_name, buffer, buffer
+                        )),
+                    );
+                }
+            }
+        }
+    }
+
     // ── Bind contract inference (§5.5) ──────────────────────────────────
     //
     // Infers direction (in/out) and data contract (dtype/shape/rate) for each
@@ -1753,7 +2207,8 @@ impl<'a> AnalyzeCtx<'a> {
 
         for bind_name in &bind_names {
             let has_writer = self.graph_has_buffer_node(bind_name, true);
-            let has_reader = self.graph_has_buffer_node(bind_name, false);
+            let has_reader = self.graph_has_buffer_node(bind_name, false)
+                || self.switch_bind_task(bind_name).is_some();
 
             let direction = if has_writer {
                 // Spec §5.5.1 first-match rule: -> name exists → Out
@@ -1772,7 +2227,7 @@ impl<'a> AnalyzeCtx<'a> {
                     span,
                     format!(
                         "bind '{}' is not referenced by any pipe \
-                         (expected @{0} or -> {0})",
+                         (expected @{0}, -> {0} or switch(@{0}, ...))",
                         bind_name
                     ),
                 );
@@ -1792,11 +2247,41 @@ impl<'a> AnalyzeCtx<'a> {
                 .unwrap_or("");
             let call_ids = self.collect_bind_call_ids(bind_name, direction);
             contract.stable_id = compute_stable_id(direction, &call_ids, transport);
+            contract.version = self.bind_contract_version(bind_name);
 
             self.bind_contracts.insert(bind_name.clone(), contract);
         }
     }
 
+    /// Read the optional `version=N` endpoint argument of a bind.
+    ///
+    /// Emits E0713 unless the value is an integer literal in `0..=65535`
+    /// (the width of the PPKT header field that carries it).
+    fn bind_contract_version(&mut self, bind_name: &str) -> Option<u16> {
+        let ep = self.thir.bind_info(bind_name)?.endpoint.clone();
+        let scalar = ep.args.iter().find_map(|a| match a {
+            BindArg::Named(ident, scalar) if ident.name == "version" => Some(scalar),
+            _ => None,
+        })?;
+        match scalar {
+            Scalar::Number(val, _, true) if (0.0..=u16::MAX as f64).contains(val) => {
+                Some(*val as u16)
+            }
+            _ => {
+                self.error_with_hint(
+                    codes::E0713,
+                    ep.span,
+                    format!(
+                        "bind '{}': 'version' must be a non-negative integer literal",
+                        bind_name
+                    ),
+                    "use an integer value in 0..=65535, e.g. version=1".to_string(),
+                );
+                None
+            }
+        }
+    }
+
     /// Validate SHM bind endpoint arguments (slots, slot_bytes, name).
     ///
     /// Preconditions: called after `infer_bind_contracts()` so binds are available.
@@ -1806,50 +2291,122 @@ impl<'a> AnalyzeCtx<'a> {
             .thir
             .binds()
             .iter()
-            .filter(|b| b.endpoint.transport.name == "shm")
+            .filter(|b| matches!(b.endpoint.transport.name.as_str(), "shm" | "tcp"))
             .map(|b| (b.name.clone(), b.endpoint.clone()))
             .collect();
 
         for (name, ep) in &binds {
-            let span = ep.span;
+            if ep.transport.name == "tcp" {
+                self.validate_tcp_endpoint(name, ep);
+            } else {
+                self.validate_shm_endpoint(name, ep);
+            }
+        }
+    }
 
-            // Check positional name arg
-            let has_positional = ep.args.iter().any(|a| matches!(a, BindArg::Positional(_)));
-            if !has_positional {
-                self.error(
-                    codes::E0724,
+    fn validate_shm_endpoint(&mut self, name: &str, ep: &BindEndpoint) {
+        let span = ep.span;
+
+        // Check positional name arg
+        let has_positional = ep.args.iter().any(|a| matches!(a, BindArg::Positional(_)));
+        if !has_positional {
+            self.error(
+                codes::E0724,
+                span,
+                format!("shm bind '{}': missing required name argument", name),
+            );
+        }
+
+        // Check named arg: slots
+        self.validate_shm_int_arg(name, &ep.args, "slots", span, codes::E0720, codes::E0722);
+
+        // Check named arg: slot_bytes
+        self.validate_shm_int_arg(
+            name,
+            &ep.args,
+            "slot_bytes",
+            span,
+            codes::E0721,
+            codes::E0723,
+        );
+
+        // Check slot_bytes alignment (must be multiple of 8)
+        if let Some(slot_bytes_val) = self.find_named_number(&ep.args, "slot_bytes") {
+            let v = slot_bytes_val as u64;
+            if v > 0 && !v.is_multiple_of(8) {
+                self.error_with_hint(
+                    codes::E0726,
                     span,
-                    format!("shm bind '{}': missing required name argument", name),
+                    format!(
+                        "shm bind '{}': slot_bytes={} is not a multiple of 8",
+                        name, v
+                    ),
+                    "slot_bytes must be 8-byte aligned for atomic field access".to_string(),
                 );
             }
+        }
+    }
 
-            // Check named arg: slots
-            self.validate_shm_int_arg(name, &ep.args, "slots", span, codes::E0720, codes::E0722);
+    /// Validate a `tcp("host:port", backlog=N, reconnect=MS)` endpoint.
+    ///
+    /// The address may be omitted (activated later via `--bind` / rebind);
+    /// a literal address must carry a port. `backlog` and `reconnect` are
+    /// optional.
+    fn validate_tcp_endpoint(&mut self, name: &str, ep: &BindEndpoint) {
+        let span = ep.span;
+
+        let address = ep.args.iter().find_map(|a| match a {
+            BindArg::Positional(Scalar::StringLit(addr, addr_span)) => Some((addr, *addr_span)),
+            _ => None,
+        });
+        if let Some((addr, addr_span)) = address {
+            let port = addr
+                .rsplit_once(':')
+                .filter(|(host, _)| !host.is_empty())
+                .and_then(|(_, port)| port.parse::<u16>().ok())
+                .filter(|&p| p > 0);
+            if port.is_none() {
+                self.error_with_hint(
+                    codes::E0730,
+                    addr_span,
+                    format!(
+                        "tcp bind '{}': address '{}' has no valid port",
+                        name, addr
+                    ),
+                    "tcp endpoints are 'host:port' with a port in 1..=65535, e.g. tcp(\"127.0.0.1:9100\")"
+                        .to_string(),
+                );
+            }
+        }
 
-            // Check named arg: slot_bytes
-            self.validate_shm_int_arg(
-                name,
-                &ep.args,
-                "slot_bytes",
-                span,
-                codes::E0721,
-                codes::E0723,
-            );
+        // `backlog` is the listen() queue length for IN binds
+        if let Some(scalar) = find_named_scalar(&ep.args, "backlog") {
+            if !matches!(scalar, Scalar::Number(v, _, true) if *v > 0.0) {
+                self.error_with_hint(
+                    codes::E0731,
+                    span,
+                    format!(
+                        "tcp bind '{}': 'backlog' must be a positive integer literal",
+                        name
+                    ),
+                    "use an integer value like backlog=4".to_string(),
+                );
+            }
+        }
 
-            // Check slot_bytes alignment (must be multiple of 8)
-            if let Some(slot_bytes_val) = self.find_named_number(&ep.args, "slot_bytes") {
-                let v = slot_bytes_val as u64;
-                if v > 0 && !v.is_multiple_of(8) {
-                    self.error_with_hint(
-                        codes::E0726,
-                        span,
-                        format!(
-                            "shm bind '{}': slot_bytes={} is not a multiple of 8",
-                            name, v
-                        ),
-                        "slot_bytes must be 8-byte aligned for atomic field access".to_string(),
-                    );
-                }
+        // `reconnect` is the retry interval in milliseconds; 0 disables retries
+        if let Some(scalar) = find_named_scalar(&ep.args, "reconnect") {
+            if !matches!(scalar, Scalar::Number(v, _, true) if *v >= 0.0) {
+                self.error_with_hint(
+                    codes::E0732,
+                    span,
+                    format!(
+                        "tcp bind '{}': 'reconnect' must be a non-negative integer literal (milliseconds)",
+                        name
+                    ),
+                    "use reconnect=1000 to retry every second, or reconnect=0 to disable retries"
+                        .to_string(),
+                );
             }
         }
     }
@@ -1938,6 +2495,23 @@ impl<'a> AnalyzeCtx<'a> {
 
     /// Check whether the post-expansion graph contains a BufferWrite or
     /// BufferRead node matching the given buffer name.
+    /// First modal task (by name) whose switch reads `@bind_name`.
+    fn switch_bind_task(&self, bind_name: &str) -> Option<String> {
+        let mut tasks: Vec<&String> = self
+            .thir
+            .hir
+            .tasks
+            .iter()
+            .filter(|t| {
+                matches!(&t.body, HirTaskBody::Modal(m)
+                    if matches!(&m.switch, HirSwitchSource::Bind(n, _) if n == bind_name))
+            })
+            .map(|t| &t.name)
+            .collect();
+        tasks.sort();
+        tasks.first().map(|t| t.to_string())
+    }
+
     fn graph_has_buffer_node(&self, buffer_name: &str, is_write: bool) -> bool {
         for task_graph in self.graph.tasks.values() {
             for sub in subgraphs_of(task_graph) {
@@ -2003,9 +2577,11 @@ impl<'a> AnalyzeCtx<'a> {
     }
 
     /// Infer contract for an OUT bind (pipeline writes, external reads).
-    fn infer_out_bind_contract(&self, bind_name: &str) -> BindContract {
+    fn infer_out_bind_contract(&mut self, bind_name: &str) -> BindContract {
         let dtype = self.infer_bind_type_from_writer(bind_name);
         let shape = self.infer_bind_shape_from_writer(bind_name);
+        let (dtype, shape) =
+            self.apply_declared_bind_type(bind_name, "its writer produces", dtype, shape);
         let rate_hz = self.infer_bind_rate_from_writer(bind_name);
 
         BindContract {
@@ -2014,23 +2590,79 @@ impl<'a> AnalyzeCtx<'a> {
             shape: shape.unwrap_or_default(),
             rate_hz,
             stable_id: String::new(), // filled by infer_bind_contracts after CallId extraction
+            version: None,
+            latency_ms: self.infer_bind_latency_ms(bind_name),
         }
     }
 
-    /// Trace backward from BufferWrite to the nearest Actor, using
-    /// concrete_actor (lowered → registry) for polymorphic type resolution.
-    fn trace_type_backward_concrete(&self, node_id: NodeId, sub: &Subgraph) -> Option<PipitType> {
-        let mut current = node_id;
-        let mut visited = Vec::new();
-        loop {
-            if visited.contains(&current) {
-                return None;
-            }
-            visited.push(current);
-            let node = self.node_in_subgraph(sub, current)?;
-            if let NodeKind::Actor { name, call_id, .. } = &node.kind {
-                return self
-                    .thir
+    /// Estimate the delay an OUT bind's data picks up in cross-clock shared
+    /// buffers upstream of its writer task: each such buffer contributes its
+    /// depth in tokens over the rate its writer fills it. The worst path wins.
+    fn infer_bind_latency_ms(&self, bind_name: &str) -> Option<f64> {
+        let buf_info = self.thir.resolved.buffers.get(bind_name)?;
+        let mut visiting = Vec::new();
+        self.upstream_buffer_latency_ms(&buf_info.writer_task, &mut visiting)
+    }
+
+    fn upstream_buffer_latency_ms(
+        &self,
+        task_name: &str,
+        visiting: &mut Vec<String>,
+    ) -> Option<f64> {
+        if visiting.iter().any(|t| t == task_name) {
+            return None;
+        }
+        let (reader_freq, _) = self.get_task_freq(task_name)?;
+        visiting.push(task_name.to_string());
+        let mut worst: Option<f64> = None;
+        for edge in &self.graph.inter_task_edges {
+            if edge.reader_task != task_name {
+                continue;
+            }
+            let upstream = self.upstream_buffer_latency_ms(&edge.writer_task, visiting);
+            let own = self.cross_clock_buffer_latency_ms(edge, reader_freq);
+            let total = match (own, upstream) {
+                (None, None) => continue,
+                (a, b) => a.unwrap_or(0.0) + b.unwrap_or(0.0),
+            };
+            worst = Some(worst.map_or(total, |w| w.max(total)));
+        }
+        visiting.pop();
+        worst
+    }
+
+    /// Buffer depth over write rate, in ms, for an inter-task edge whose
+    /// writer runs on a different clock than its reader.
+    fn cross_clock_buffer_latency_ms(&self, edge: &InterTaskEdge, reader_freq: f64) -> Option<f64> {
+        let (writer_freq, _) = self.get_task_freq(&edge.writer_task)?;
+        if (writer_freq - reader_freq).abs() <= 0.001 {
+            return None;
+        }
+        let bytes = *self.inter_buffers.get(&edge.buffer_name)?;
+        let type_size = self
+            .infer_buffer_type(&edge.buffer_name)
+            .map(type_size_bytes)
+            .unwrap_or(4);
+        let depth_tokens = (bytes / type_size) as f64;
+        let pw = self.get_rv_for_node(&edge.writer_task, edge.writer_node)?;
+        let tokens_per_sec = pw as f64 * writer_freq;
+        (tokens_per_sec > 0.0).then(|| depth_tokens / tokens_per_sec * 1000.0)
+    }
+
+    /// Trace backward from BufferWrite to the nearest Actor, using
+    /// concrete_actor (lowered → registry) for polymorphic type resolution.
+    fn trace_type_backward_concrete(&self, node_id: NodeId, sub: &Subgraph) -> Option<PipitType> {
+        let mut current = node_id;
+        let mut visited = Vec::new();
+        loop {
+            if visited.contains(&current) {
+                return None;
+            }
+            visited.push(current);
+            let node = self.node_in_subgraph(sub, current)?;
+            if let NodeKind::Actor { name, call_id, .. } = &node.kind {
+                return self
+                    .thir
                     .concrete_actor(name, *call_id)
                     .and_then(|m| m.out_type.as_concrete());
             }
@@ -2057,10 +2689,23 @@ impl<'a> AnalyzeCtx<'a> {
     }
 
     /// Infer contract for an IN bind (external writes, pipeline reads).
+    ///
+    /// A switch reading the bind consumes one int32 token per tick of its
+    /// task; that reader only fills in what pipe readers leave unresolved.
     fn infer_in_bind_contract(&mut self, bind_name: &str) -> BindContract {
-        let dtype = self.infer_bind_type_from_readers(bind_name);
+        let switch_task = self.switch_bind_task(bind_name);
+        let dtype = self
+            .infer_bind_type_from_readers(bind_name)
+            .or(switch_task.as_ref().map(|_| PipitType::Int32));
         let shape = self.infer_bind_shape_from_readers(bind_name);
-        let rate_hz = self.infer_bind_rate_from_readers(bind_name);
+        let (dtype, shape) =
+            self.apply_declared_bind_type(bind_name, "its readers use", dtype, shape);
+        let rate_hz = self.infer_bind_rate_from_readers(bind_name).or_else(|| {
+            switch_task
+                .as_deref()
+                .and_then(|t| self.thir.task_info(t))
+                .map(|t| t.freq_hz)
+        });
 
         BindContract {
             direction: BindDirection::In,
@@ -2068,7 +2713,60 @@ impl<'a> AnalyzeCtx<'a> {
             shape: shape.unwrap_or_default(),
             rate_hz,
             stable_id: String::new(), // filled by infer_bind_contracts after CallId extraction
+            version: None,
+            latency_ms: None,
+        }
+    }
+
+    /// Override a traced bind type/shape with the bind's declared wire type
+    /// (`bind iq: cfloat[1024] = ...`). The declaration wins; a traced value
+    /// that disagrees with it is an E0312 conflict. `traced_by` names the
+    /// traced end in the message ("its readers use", "its writer produces").
+    fn apply_declared_bind_type(
+        &mut self,
+        bind_name: &str,
+        traced_by: &str,
+        traced_dtype: Option<PipitType>,
+        traced_shape: Option<Vec<u32>>,
+    ) -> (Option<PipitType>, Option<Vec<u32>>) {
+        let Some(bind) = self.thir.bind_info(bind_name) else {
+            return (traced_dtype, traced_shape);
+        };
+        let Some(ty) = bind.ty.clone() else {
+            return (traced_dtype, traced_shape);
+        };
+        let Some(declared_dtype) = crate::type_infer::parse_type_name(&ty.dtype.name) else {
+            return (traced_dtype, traced_shape);
+        };
+        let declared_shape: Option<Vec<u32>> = ty
+            .shape
+            .as_ref()
+            .and_then(|sc| sc.dims.iter().map(|d| self.resolve_shape_dim(d)).collect());
+
+        if let Some(traced) = traced_dtype.filter(|t| *t != declared_dtype) {
+            self.error(
+                codes::E0312,
+                ty.dtype.span,
+                format!(
+                    "bind '{}' declares type {} but {} {}",
+                    bind_name, declared_dtype, traced_by, traced
+                ),
+            );
+        }
+        if let (Some(declared), Some(traced)) = (&declared_shape, &traced_shape) {
+            if declared != traced {
+                let span = ty.shape.as_ref().map_or(ty.span, |sc| sc.span);
+                self.error(
+                    codes::E0312,
+                    span,
+                    format!(
+                        "bind '{}' declares shape {:?} but {} {:?}",
+                        bind_name, declared, traced_by, traced
+                    ),
+                );
+            }
         }
+        (Some(declared_dtype), declared_shape.or(traced_shape))
     }
 
     /// Trace forward from a node to the nearest downstream Actor and return
@@ -2286,25 +2984,207 @@ impl<'a> AnalyzeCtx<'a> {
             let pw = self
                 .get_rv_for_node(&edge.writer_task, edge.writer_node)
                 .unwrap_or(1);
-            let buffer_bytes = 2 * pw as u64 * type_size;
+            let buffer_bytes = self.thir.buffering.depth() * pw as u64 * type_size;
             self.inter_buffers
                 .insert(edge.buffer_name.clone(), buffer_bytes);
             total += buffer_bytes;
         }
 
+        // Per-instance actor scratch (`SCRATCH(type, count)`)
+        let subs = std::mem::take(&mut self.all_subgraphs);
+        for &(_, _, sub) in &subs {
+            for node in &sub.nodes {
+                total += self.scratch_bytes(node);
+            }
+        }
+        self.all_subgraphs = subs;
+
         self.total_memory = total;
     }
 
+    /// Resolve an actor node's scratch count and return its size in bytes.
+    /// Records the count for codegen; E0300 if a symbolic count is unresolved.
+    fn scratch_bytes(&mut self, node: &Node) -> u64 {
+        let NodeKind::Actor {
+            name,
+            call_id,
+            args,
+            ..
+        } = &node.kind
+        else {
+            return 0;
+        };
+        let Some(meta) = self.thir.concrete_actor(name, *call_id) else {
+            return 0;
+        };
+        let Some(scratch) = &meta.scratch else {
+            return 0;
+        };
+        let count = match &scratch.count {
+            TokenCount::Literal(n) => Some(*n),
+            TokenCount::Symbolic(sym) => self
+                .resolve_symbolic_dim_from_args(sym, meta, args)
+                .or_else(|| {
+                    self.span_derived_dims
+                        .get(&node.id)
+                        .and_then(|m| m.get(sym.as_str()))
+                        .copied()
+                }),
+        };
+        let Some(count) = count.filter(|&n| n > 0) else {
+            let sym = match &scratch.count {
+                TokenCount::Symbolic(sym) => sym.clone(),
+                TokenCount::Literal(n) => n.to_string(),
+            };
+            self.error_with_hint(
+                codes::E0300,
+                node.span,
+                format!("cannot resolve scratch size '{}' of actor '{}'", sym, name),
+                format!("pass '{}' explicitly as a positive constant", sym),
+            );
+            return 0;
+        };
+        self.scratch_sizes.insert(node.id, count);
+        count as u64 * type_size_bytes(scratch.elem_type)
+    }
+
+    // ── Phase 5b: Family buffer reuse ───────────────────────────────────
+    //
+    // A family element buffer `name__i` is live from the firing that writes it
+    // to the firing that drains it. When every element is written by the same
+    // ScatterWrite node and read only by GatherRead nodes (one per reader task),
+    // all element live ranges coincide and one ring buffer slot per frame can
+    // be reused for the whole family.
+
+    fn analyze_family_reuse(&mut self) {
+        // family → [(task, element_count)] for scatter writers and gather readers
+        let mut scatters: HashMap<&str, Vec<(&str, u32)>> = HashMap::new();
+        let mut gathers: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut element_refs: HashSet<&str> = HashSet::new();
+        for (task_name, task_graph) in &self.graph.tasks {
+            for sub in subgraphs_of(task_graph) {
+                for node in &sub.nodes {
+                    match &node.kind {
+                        NodeKind::ScatterWrite {
+                            family_name,
+                            element_count,
+                        } => scatters
+                            .entry(family_name.as_str())
+                            .or_default()
+                            .push((task_name.as_str(), *element_count)),
+                        NodeKind::GatherRead { family_name, .. } => gathers
+                            .entry(family_name.as_str())
+                            .or_default()
+                            .push(task_name.as_str()),
+                        NodeKind::BufferRead { buffer_name }
+                        | NodeKind::BufferWrite { buffer_name } => {
+                            element_refs.insert(buffer_name.as_str());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        for (family, writers) in scatters {
+            let [(writer_task, element_count)] = writers[..] else {
+                continue;
+            };
+            let Some(gather_tasks) = gathers.get(family) else {
+                continue;
+            };
+            let mut reader_tasks: Vec<String> =
+                gather_tasks.iter().map(|t| t.to_string()).collect();
+            reader_tasks.sort();
+            if element_count < 2 || reader_tasks.windows(2).any(|w| w[0] == w[1]) {
+                continue;
+            }
+
+            let mut payload_total = 0u64;
+            let mut eligible = true;
+            for i in 0..element_count {
+                let elem = format!("{}__{}", family, i);
+                let Some(info) = self.thir.resolved.buffers.get(&elem) else {
+                    eligible = false;
+                    break;
+                };
+                let mut readers: Vec<&str> = info.readers.iter().map(|(t, _)| t.as_str()).collect();
+                readers.sort();
+                readers.dedup();
+                if element_refs.contains(elem.as_str())
+                    || info.writer_task != writer_task
+                    || readers != reader_tasks
+                {
+                    eligible = false;
+                    break;
+                }
+                payload_total += self
+                    .inter_buffers
+                    .get(&elem)
+                    .copied()
+                    .unwrap_or(DEFAULT_INTER_TASK_BUFFER_BYTES);
+            }
+            if !eligible {
+                continue;
+            }
+
+            let header = ring_buffer_header_bytes(reader_tasks.len());
+            result.push(FamilyReuse {
+                family_name: family.to_string(),
+                element_count,
+                writer_task: writer_task.to_string(),
+                reader_tasks,
+                separate_bytes: payload_total + element_count as u64 * header,
+                coalesced_bytes: payload_total + header,
+            });
+        }
+        result.sort_by(|a, b| a.family_name.cmp(&b.family_name));
+        self.family_reuse = result;
+    }
+
+    // ── Phase 5c: Critical path ─────────────────────────────────────────
+    //
+    // Longest node chain through each subgraph, for latency budgeting. The
+    // scheduler's back-edges (delay outputs in feedback loops) are dropped
+    // first so the traversal runs over a DAG.
+
+    fn compute_critical_paths(&mut self) {
+        for &(task_name, label, sub) in &self.all_subgraphs {
+            let back_edges = identify_back_edges(sub, &self.graph.cycles);
+            if let Some(path) = longest_path(sub, &back_edges) {
+                self.critical_path
+                    .insert((task_name.to_string(), label.to_string()), path);
+            }
+        }
+    }
+
     // ── Phase 6: Memory pool check ──────────────────────────────────────
 
     fn check_memory_pool(&mut self) {
-        let (limit, span_opt) = self.get_mem_limit();
+        let (limit, span_opt) = match self.get_mem_limit() {
+            Ok(limit) => limit,
+            Err(msg) => {
+                let span = self.thir.mem_span.unwrap_or(self.thir.program_span);
+                self.error_with_hint(
+                    codes::E0315,
+                    span,
+                    msg,
+                    "pass --platform-memory <SIZE> and keep the percentage within 0-100%, or give `set mem` an absolute size".to_string(),
+                );
+                return;
+            }
+        };
         if self.total_memory > limit {
             let span = span_opt.unwrap_or(self.thir.program_span);
-            let limit_src = if span_opt.is_some() {
-                "set mem"
+            let limit_src = if self.thir.mem_from_cli {
+                "--max-memory".to_string()
+            } else if let Some(pct) = self.thir.mem_percent {
+                format!("set mem = {}% of --platform-memory", pct)
+            } else if span_opt.is_some() {
+                "set mem".to_string()
             } else {
-                "default mem (64MB)"
+                "default mem (64MB)".to_string()
             };
             self.error(
                 codes::E0307,
@@ -2394,8 +3274,8 @@ impl<'a> AnalyzeCtx<'a> {
                 HirTaskBody::Modal(m) => m,
                 _ => continue,
             };
-            let ctrl_buffer_name = match &modal.switch {
-                HirSwitchSource::Buffer(name, _) => name,
+            let (ctrl_buffer_name, switch_span) = match &modal.switch {
+                HirSwitchSource::Buffer(name, span) => (name, *span),
                 HirSwitchSource::Param(name, span) => {
                     let Some(param) = self.thir.param_info(name) else {
                         // Undefined param is already reported by resolve.
@@ -2416,11 +3296,69 @@ impl<'a> AnalyzeCtx<'a> {
                     }
                     continue;
                 }
+                HirSwitchSource::Bind(name, span) => {
+                    // Undefined binds are reported by resolve, unreferenced
+                    // ones never get a contract.
+                    let Some(contract) = self.bind_contracts.get(name) else {
+                        continue;
+                    };
+                    if contract.direction != BindDirection::In {
+                        self.error_with_hint(
+                            codes::E0310,
+                            *span,
+                            format!(
+                                "switch bind '@{}' in task '{}' is written by the pipeline; \
+                                 switch ctrl must be an IN bind",
+                                name, hir_task.name
+                            ),
+                            format!("remove the `-> {}` sink or switch on a separate bind", name),
+                        );
+                    } else if let Some(dtype) = contract.dtype.filter(|t| *t != PipitType::Int32) {
+                        self.error_with_hint(
+                            codes::E0310,
+                            *span,
+                            format!(
+                                "switch bind '@{}' in task '{}' has type {}, \
+                                 but switch ctrl must be int32",
+                                name, hir_task.name, dtype
+                            ),
+                            format!("read '@{}' only through int32 actors", name),
+                        );
+                    }
+                    continue;
+                }
             };
             let control_sub = match self.graph.tasks.get(&hir_task.name) {
                 Some(TaskGraph::Modal { control, .. }) => control,
                 _ => continue,
             };
+            // A ctrl buffer this task writes itself must come from the control
+            // subgraph; one written only by its own modes never selects a mode.
+            // Buffers written by another task are external ctrl sources.
+            let written_in_control = control_sub.nodes.iter().any(|node| {
+                matches!(&node.kind, NodeKind::BufferWrite { buffer_name } if buffer_name == ctrl_buffer_name)
+            });
+            let self_written = self
+                .thir
+                .resolved
+                .buffers
+                .get(ctrl_buffer_name)
+                .is_some_and(|info| info.writer_task == hir_task.name);
+            if !written_in_control && self_written {
+                self.error_with_hint(
+                    codes::E0313,
+                    switch_span,
+                    format!(
+                        "switch ctrl buffer '{}' of task '{}' is not written in the control block",
+                        ctrl_buffer_name, hir_task.name
+                    ),
+                    format!(
+                        "write the ctrl signal from the control block, e.g. `control {{ ... | detect() -> {} }}`",
+                        ctrl_buffer_name
+                    ),
+                );
+                continue;
+            }
             // Find the BufferWrite node for the ctrl buffer in the control subgraph
             for node in &control_sub.nodes {
                 if let NodeKind::BufferWrite { buffer_name } = &node.kind {
@@ -2476,8 +3414,66 @@ fn find_buffer_write_in_task(task_graph: &TaskGraph, buffer_name: &str) -> Optio
     None
 }
 
-/// Display name for a node (for error messages).
-fn node_display_name(node: &Node) -> String {
+/// Longest chain of nodes in `sub` ignoring `back_edges`, or `None` when the
+/// remaining graph still has a cycle. Ties go to the lowest NodeId so the
+/// result is deterministic.
+fn longest_path(sub: &Subgraph, back_edges: &HashSet<(NodeId, NodeId)>) -> Option<Vec<NodeId>> {
+    let mut preds: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    let mut succs: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    let mut in_degree: HashMap<NodeId, usize> = sub.nodes.iter().map(|n| (n.id, 0)).collect();
+    for edge in &sub.edges {
+        if back_edges.contains(&(edge.source, edge.target)) {
+            continue;
+        }
+        preds.entry(edge.target).or_default().push(edge.source);
+        succs.entry(edge.source).or_default().push(edge.target);
+        *in_degree.entry(edge.target).or_insert(0) += 1;
+    }
+
+    // Kahn's algorithm; `best` holds (chain length, predecessor on the chain).
+    let mut ready: BTreeSet<u32> = in_degree
+        .iter()
+        .filter(|(_, &d)| d == 0)
+        .map(|(id, _)| id.0)
+        .collect();
+    let mut best: HashMap<NodeId, (usize, Option<NodeId>)> = HashMap::new();
+    while let Some(raw) = ready.pop_first() {
+        let id = NodeId(raw);
+        let entry = preds
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter_map(|p| best.get(p).map(|&(len, _)| (len, *p)))
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1 .0.cmp(&a.1 .0)))
+            .map_or((1, None), |(len, p)| (len + 1, Some(p)));
+        best.insert(id, entry);
+        for &next in succs.get(&id).into_iter().flatten() {
+            let d = in_degree
+                .get_mut(&next)
+                .expect("edge target is a subgraph node");
+            *d -= 1;
+            if *d == 0 {
+                ready.insert(next.0);
+            }
+        }
+    }
+    if best.len() != in_degree.len() {
+        return None;
+    }
+
+    let (&end, _) = best
+        .iter()
+        .max_by(|a, b| (a.1).0.cmp(&(b.1).0).then(b.0 .0.cmp(&a.0 .0)))?;
+    let mut path = vec![end];
+    while let Some(prev) = best[path.last().unwrap()].1 {
+        path.push(prev);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Display name for a node (for error messages and graph labels).
+pub(crate) fn node_display_name(node: &Node) -> String {
     match &node.kind {
         NodeKind::Actor { name, .. } => name.clone(),
         NodeKind::Fork { tap_name } => format!(":{}", tap_name),
@@ -2489,8 +3485,16 @@ fn node_display_name(node: &Node) -> String {
     }
 }
 
+/// Value of the named endpoint argument `name`, if present.
+fn find_named_scalar<'a>(args: &'a [BindArg], name: &str) -> Option<&'a Scalar> {
+    args.iter().find_map(|a| match a {
+        BindArg::Named(ident, scalar) if ident.name == name => Some(scalar),
+        _ => None,
+    })
+}
+
 /// Size in bytes for a PipitType.
-fn type_size_bytes(t: PipitType) -> u64 {
+pub(crate) fn type_size_bytes(t: PipitType) -> u64 {
     match t {
         PipitType::Int8 => 1,
         PipitType::Int16 => 2,
@@ -2499,10 +3503,71 @@ fn type_size_bytes(t: PipitType) -> u64 {
         PipitType::Double => 8,
         PipitType::Cfloat => 8,
         PipitType::Cdouble => 16,
+        PipitType::Bool => 1,
         PipitType::Void => 0,
     }
 }
 
+/// Largest decimation/interpolation factor suggested by E0306 hints.
+const MAX_RESAMPLE_FACTOR: u64 = 1000;
+
+/// Suggest a reader-side resampler that reconciles a cross-clock rate mismatch.
+///
+/// The reader must consume `writer_rate / reader_rate` times as many tokens
+/// per firing; that ratio is reduced to `P/Q` (nearest rational when it is not
+/// exact) and mapped to the standard `interpolate(Q)` and/or `decimate(P)`
+/// rate-change actors.
+fn resample_hint(buffer: &str, reader_task: &str, writer_rate: f64, reader_rate: f64) -> String {
+    let ratio = writer_rate / reader_rate;
+    let (p, q) = nearest_ratio(ratio, MAX_RESAMPLE_FACTOR);
+    let place = format!("after '@{}' in task '{}'", buffer, reader_task);
+    let fix = match (p, q) {
+        (p, 1) => format!("insert decimate({}) {}", p, place),
+        (1, q) => format!("insert interpolate({}) {}", q, place),
+        (p, q) => format!("insert interpolate({}) | decimate({}) {}", q, p, place),
+    };
+    let exact = ((p as f64 / q as f64) - ratio).abs() <= ratio * 1e-9;
+    if exact {
+        fix
+    } else {
+        format!(
+            "rates differ by a factor of {:.6}; nearest ratio is {}/{}: {}",
+            ratio, p, q, fix
+        )
+    }
+}
+
+/// Best rational approximation `p/q` of a positive `x` with `p, q <= max`
+/// (continued-fraction convergents).
+fn nearest_ratio(x: f64, max: u64) -> (u64, u64) {
+    if x < 1.0 {
+        let (p, q) = nearest_ratio(1.0 / x, max);
+        return (q, p);
+    }
+    let (mut h0, mut h1) = (0u64, 1u64);
+    let (mut k0, mut k1) = (1u64, 0u64);
+    let mut v = x;
+    loop {
+        let a = v.floor() as u64;
+        let h2 = a.saturating_mul(h1).saturating_add(h0);
+        let k2 = a.saturating_mul(k1).saturating_add(k0);
+        if h2 > max || k2 > max {
+            break;
+        }
+        (h0, h1, k0, k1) = (h1, h2, k1, k2);
+        let frac = v - a as f64;
+        if frac < 1e-9 {
+            break;
+        }
+        v = 1.0 / frac;
+    }
+    if k1 == 0 {
+        // Even the integer part exceeds the cap.
+        return (max, 1);
+    }
+    reduce_ratio(h1, k1)
+}
+
 /// GCD for u64.
 fn gcd(a: u64, b: u64) -> u64 {
     if b == 0 {
@@ -2652,6 +3717,36 @@ mod tests {
 
     /// Parse, resolve, build HIR, graph, ThirContext, and analyze.
     fn analyze_source(source: &str, registry: &Registry) -> AnalysisResult {
+        analyze_source_with_shape_budget(source, registry, SHAPE_INFERENCE_STEPS_PER_NODE)
+    }
+
+    fn analyze_source_with_shape_budget(
+        source: &str,
+        registry: &Registry,
+        shape_steps_per_node: usize,
+    ) -> AnalysisResult {
+        analyze_source_configured(source, registry, shape_steps_per_node, None)
+    }
+
+    fn analyze_source_with_platform_memory(
+        source: &str,
+        registry: &Registry,
+        platform_memory: Option<u64>,
+    ) -> AnalysisResult {
+        analyze_source_configured(
+            source,
+            registry,
+            SHAPE_INFERENCE_STEPS_PER_NODE,
+            platform_memory,
+        )
+    }
+
+    fn analyze_source_configured(
+        source: &str,
+        registry: &Registry,
+        shape_steps_per_node: usize,
+        platform_memory: Option<u64>,
+    ) -> AnalysisResult {
         let parse_result = crate::parser::parse(source);
         assert!(
             parse_result.errors.is_empty(),
@@ -2691,7 +3786,7 @@ mod tests {
             "graph errors: {:#?}",
             graph_result.diagnostics
         );
-        let thir = crate::thir::build_thir_context(
+        let mut thir = crate::thir::build_thir_context(
             &hir_program,
             &resolve_result.resolved,
             &type_result.typed,
@@ -2699,7 +3794,10 @@ mod tests {
             registry,
             &graph_result.graph,
         );
-        analyze(&thir, &graph_result.graph)
+        if let Some(bytes) = platform_memory {
+            thir.set_platform_memory(bytes);
+        }
+        analyze_with_shape_budget(&thir, &graph_result.graph, shape_steps_per_node)
     }
 
     fn analyze_ok(source: &str, registry: &Registry) -> AnalysisResult {
@@ -2842,6 +3940,54 @@ mod tests {
         );
     }
 
+    const BOOL_ACTORS: &str = r#"
+#include <pipit.h>
+ACTOR(is_positive, IN(float, 1), OUT(bool, 1)) {
+    out[0] = in[0] > 0.0f; return ACTOR_OK;
+}
+ACTOR(count_true, IN(bool, 1), OUT(int32, 1)) {
+    out[0] = in[0] ? 1 : 0; return ACTOR_OK;
+}"#;
+
+    #[test]
+    fn type_check_bool_is_distinct() {
+        let reg = test_registry_with_extra_header(BOOL_ACTORS);
+        analyze_ok(
+            "clock 1kHz t {\n    constant(0.0) | is_positive() | count_true() | stdout()\n}",
+            &reg,
+        );
+        // bool does not widen to float, and int32 does not narrow to bool.
+        for source in [
+            "clock 1kHz t {\n    constant(0.0) | is_positive() | fft(256) | stdout()\n}",
+            "clock 1kHz t {\n    constant(0.0) | detect() | count_true() | stdout()\n}",
+        ] {
+            let result = analyze_source(source, &reg);
+            let err = result
+                .diagnostics
+                .iter()
+                .find(|d| d.code == Some(codes::E0303))
+                .unwrap_or_else(|| panic!("{}: {:#?}", source, result.diagnostics));
+            assert!(
+                err.hint.as_deref().unwrap().contains("bool never converts"),
+                "{:#?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn bool_buffer_is_one_byte_per_token() {
+        let reg = test_registry_with_extra_header(BOOL_ACTORS);
+        let result = analyze_ok(
+            concat!(
+                "clock 1kHz a { constant(0.0) | is_positive() -> flag }\n",
+                "clock 1kHz b { @flag | count_true() | stdout() }\n",
+            ),
+            &reg,
+        );
+        assert_eq!(result.analysis.inter_task_buffers["flag"], 2);
+    }
+
     #[test]
     fn polymorphic_stdout_accepts_cfloat_from_fft() {
         let reg = test_registry();
@@ -2854,6 +4000,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn void_actor_through_tap_skips_balance() {
+        let reg = test_registry();
+        // Used to divide by zero in the balance solver; type_infer reports
+        // the tap read as E0103 before analysis runs in the pipeline.
+        let result = analyze_source(
+            concat!(
+                "clock 1kHz t {\n",
+                "    constant(0.0) | stdout() | :s\n",
+                "    :s | mag() | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        assert!(result.analysis.repetition_vectors.is_empty());
+    }
+
+    #[test]
+    fn void_tap_into_actor_left_to_e0103() {
+        let reg = test_registry();
+        // A void sink feeding an actor through a tap is E0103's job
+        // (type_infer); analysis must not add its own error or solve
+        // the void edge.
+        let source = concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | stdout() | :t\n",
+            "    :t | mul(2.0) | stdout()\n",
+            "}\n",
+        );
+        let result = analyze_source(source, &reg);
+        assert!(result.analysis.repetition_vectors.is_empty());
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .all(|d| d.level != DiagLevel::Error),
+            "{:#?}",
+            result.diagnostics
+        );
+
+        let program = crate::parser::parse(source).program.unwrap();
+        let mut resolved = resolve::resolve(&program, &reg);
+        let hir = crate::hir::build_hir(&program, &resolved.resolved, &mut resolved.id_alloc);
+        let typed = crate::type_infer::type_infer(&hir, &resolved.resolved, &reg);
+        assert!(typed
+            .diagnostics
+            .iter()
+            .any(|d| d.code == Some(codes::E0103)));
+    }
+
+    #[test]
+    fn void_sink_at_pipeline_end_ok() {
+        let reg = test_registry();
+        let result = analyze_ok(
+            "clock 1kHz t {\n    constant(0.0) | :s | stdout()\n    :s | mag() | stdout()\n}",
+            &reg,
+        );
+        assert!(!result.analysis.repetition_vectors.is_empty());
+    }
+
     #[test]
     fn polymorphic_fir_rate_resolution() {
         let reg = test_registry();
@@ -2910,6 +4116,59 @@ mod tests {
         );
     }
 
+    // ── Phase 1b: Buffer cost heuristic tests ───────────────────────────
+
+    fn w0315(result: &AnalysisResult) -> Vec<&Diagnostic> {
+        result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::W0315))
+            .collect()
+    }
+
+    #[test]
+    fn buffer_token_cost_warns_above_threshold() {
+        let reg = test_registry();
+        // abs emits 1 float per firing; fft(4096) needs 4096 of them.
+        let result = analyze_ok(
+            concat!(
+                "set warn_buffer_tokens = 1024\n",
+                "clock 1kHz t { constant(0.0) | abs() | fft(4096) | c2r() | stdout() }\n",
+            ),
+            &reg,
+        );
+        let warnings = w0315(&result);
+        assert_eq!(warnings.len(), 1, "got: {:#?}", result.diagnostics);
+        assert_eq!(warnings[0].level, DiagLevel::Warning);
+        assert!(
+            warnings[0]
+                .message
+                .contains("actor 'fft' consumes 4096 tokens")
+                && warnings[0].message.contains("producer 'abs' supplies 1")
+                && warnings[0].message.contains("4096 tokens (16384 bytes)"),
+            "got: {}",
+            warnings[0].message
+        );
+    }
+
+    #[test]
+    fn buffer_token_cost_is_off_by_default_and_respects_threshold() {
+        let reg = test_registry();
+        let pipeline = "clock 1kHz t { constant(0.0) | abs() | fft(4096) | c2r() | stdout() }\n";
+        assert!(w0315(&analyze_ok(pipeline, &reg)).is_empty());
+        let result = analyze_ok(&format!("set warn_buffer_tokens = 4096\n{pipeline}"), &reg);
+        assert!(w0315(&result).is_empty(), "at threshold is not above it");
+        // Equal frame sizes on both sides never accumulate.
+        let result = analyze_ok(
+            concat!(
+                "set warn_buffer_tokens = 16\n",
+                "clock 1kHz t { constant(0.0) | fft(4096) | c2r() | stdout() }\n",
+            ),
+            &reg,
+        );
+        assert!(w0315(&result).is_empty(), "got: {:#?}", result.diagnostics);
+    }
+
     // ── Phase 2: SDF balance equation tests ─────────────────────────────
 
     #[test]
@@ -2959,6 +4218,37 @@ mod tests {
         assert_eq!(rv[&stdout_id], 256);
     }
 
+    #[test]
+    fn balance_rate_change_interpolate_decimate() {
+        let reg = test_registry();
+        // interpolate(3): RATE_CHANGE(3, 1). decimate(2): RATE_CHANGE(1, 2).
+        let source =
+            "clock 3kHz t {\n    constant(0.0) | interpolate(3) | decimate(2) | stdout()\n}";
+        let (result, graph) = analyze_with_graph(source, &reg);
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert!(errors.is_empty(), "unexpected errors: {:#?}", errors);
+        let rv = result
+            .analysis
+            .repetition_vectors
+            .get(&("t".to_string(), "pipeline".to_string()))
+            .expect("rv missing");
+        // constant→interpolate: rv[constant]×1 = rv[interpolate]×1
+        // interpolate→decimate: rv[interpolate]×3 = rv[decimate]×2
+        // decimate→stdout:      rv[decimate]×1 = rv[stdout]×1
+        let constant_id = find_actor_id(&graph, "t", "constant");
+        let interp_id = find_actor_id(&graph, "t", "interpolate");
+        let decim_id = find_actor_id(&graph, "t", "decimate");
+        let stdout_id = find_actor_id(&graph, "t", "stdout");
+        assert_eq!(rv[&constant_id], 2);
+        assert_eq!(rv[&interp_id], 2);
+        assert_eq!(rv[&decim_id], 3);
+        assert_eq!(rv[&stdout_id], 3);
+    }
+
     #[test]
     fn balance_fir_symbolic() {
         let reg = test_registry();
@@ -2990,6 +4280,111 @@ mod tests {
         assert_eq!(rv[&stdout_id], 1);
     }
 
+    #[test]
+    fn balance_tap_gather_splits_consumption() {
+        let reg = test_registry();
+        // mean(4) consumes 4 tokens per firing, split evenly across the two
+        // gathered taps: rv[mean]×2 = rv[constant]×1 on each incoming edge.
+        let source = concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | :a | stdout()\n",
+            "    constant(1.0) | :b | stdout()\n",
+            "    gather(:a, :b) | mean(4) | stdout()\n",
+            "}",
+        );
+        let (result, graph) = analyze_with_graph(source, &reg);
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert!(errors.is_empty(), "unexpected errors: {:#?}", errors);
+        let rv = result
+            .analysis
+            .repetition_vectors
+            .get(&("t".to_string(), "pipeline".to_string()))
+            .expect("rv missing");
+        let mean_id = find_actor_id(&graph, "t", "mean");
+        assert_eq!(rv[&mean_id], 1);
+        let crate::graph::TaskGraph::Pipeline(sub) = &graph.tasks["t"] else {
+            panic!("expected pipeline task");
+        };
+        let sources: Vec<NodeId> = sub
+            .edges
+            .iter()
+            .filter(|e| e.target == mean_id)
+            .map(|e| e.source)
+            .collect();
+        assert_eq!(sources.len(), 2);
+        for node in sub.nodes.iter().filter(
+            |n| matches!(&n.kind, crate::graph::NodeKind::Actor { name, .. } if name == "constant"),
+        ) {
+            assert_eq!(rv[&node.id], 2);
+        }
+    }
+
+    fn critical_path_names(
+        result: &AnalysisResult,
+        graph: &crate::graph::ProgramGraph,
+        task: &str,
+    ) -> Vec<String> {
+        let crate::graph::TaskGraph::Pipeline(sub) = &graph.tasks[task] else {
+            panic!("expected pipeline task");
+        };
+        result.analysis.critical_path[&(task.to_string(), "pipeline".to_string())]
+            .iter()
+            .map(|id| node_display_name(find_node(sub, *id).expect("path node in subgraph")))
+            .collect()
+    }
+
+    #[test]
+    fn critical_path_takes_longer_diamond_branch() {
+        let reg = test_registry();
+        // constant fans out through :a into a two-actor branch and a
+        // one-actor branch that rejoin at add.
+        let source = concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | :a | mul(2.0) | mul(3.0) | :l | stdout()\n",
+            "    :a | abs() | :r | stdout()\n",
+            "    gather(:l, :r) | add() | stdout()\n",
+            "}",
+        );
+        let (result, graph) = analyze_with_graph(source, &reg);
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert!(errors.is_empty(), "unexpected errors: {:#?}", errors);
+        assert_eq!(
+            critical_path_names(&result, &graph, "t"),
+            ["constant", ":a", "mul", "mul", ":l", "add", "stdout"]
+        );
+    }
+
+    #[test]
+    fn critical_path_skips_feedback_back_edge() {
+        let reg = test_registry();
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let path = manifest_dir
+            .parent()
+            .expect("missing workspace root")
+            .join("examples/feedback.pdl");
+        let source = std::fs::read_to_string(path).expect("failed to read feedback.pdl");
+        let (result, graph) = analyze_with_graph(&source, &reg);
+        let names = critical_path_names(&result, &graph, "iir");
+        // The chain starts at the source; delay's output edge back into add
+        // is not followed.
+        assert_eq!(names.first().map(String::as_str), Some("constant"));
+        let unique: HashSet<&String> = names.iter().collect();
+        assert_eq!(
+            unique.len(),
+            names.len(),
+            "path revisits a node: {:?}",
+            names
+        );
+    }
+
     #[test]
     fn balance_with_fork() {
         let reg = test_registry();
@@ -3049,6 +4444,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn allow_combinational_loop_downgrades_only_annotated_cycle() {
+        let reg = test_registry();
+        let result = analyze_source(
+            concat!(
+                "clock 1kHz t {\n",
+                "    constant(0.0) | add(:fb) @allow_combinational_loop | :out | stdout()\n",
+                "    :out | mul(0.5) | :fb\n",
+                "    constant(0.0) | add(:fb2) | :out2 | stdout()\n",
+                "    :out2 | mul(0.5) | :fb2\n",
+                "}",
+            ),
+            &reg,
+        );
+        let warnings: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::W0314))
+            .collect();
+        assert_eq!(warnings.len(), 1, "{:#?}", result.diagnostics);
+        assert_eq!(warnings[0].level, DiagLevel::Warning);
+        assert!(
+            warnings[0].message.contains(":fb"),
+            "{}",
+            warnings[0].message
+        );
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::E0305))
+            .collect();
+        assert_eq!(errors.len(), 1, "{:#?}", result.diagnostics);
+        assert!(errors[0].message.contains(":fb2"), "{}", errors[0].message);
+    }
+
     // ── Phase 4: Cross-clock rate matching tests ──────────────────────
 
     #[test]
@@ -3086,6 +4516,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cross_clock_rate_mismatch_suggests_decimation_factor() {
+        let reg = test_registry();
+        let result = analyze_source(
+            concat!(
+                "set mem = 64MB\n",
+                "clock 10kHz fast { constant(0.0) -> sig }\n",
+                "clock 1kHz slow { @sig | stdout() }\n",
+            ),
+            &reg,
+        );
+        let diag = result
+            .diagnostics
+            .iter()
+            .find(|d| d.code == Some(codes::E0306))
+            .expect("expected E0306");
+        assert_eq!(
+            diag.hint.as_deref(),
+            Some("insert decimate(10) after '@sig' in task 'slow'")
+        );
+    }
+
+    #[test]
+    fn cross_clock_rate_mismatch_suggests_interpolation_and_rational_pair() {
+        let reg = test_registry();
+        let hint_for = |writer: &str, reader: &str| {
+            let result = analyze_source(
+                &format!(
+                    "set mem = 64MB\nclock {} w {{ constant(0.0) -> sig }}\nclock {} r {{ @sig | stdout() }}\n",
+                    writer, reader
+                ),
+                &reg,
+            );
+            result
+                .diagnostics
+                .iter()
+                .find(|d| d.code == Some(codes::E0306))
+                .and_then(|d| d.hint.clone())
+                .expect("expected E0306 with hint")
+        };
+        assert_eq!(
+            hint_for("1kHz", "4kHz"),
+            "insert interpolate(4) after '@sig' in task 'r'"
+        );
+        assert_eq!(
+            hint_for("44.1kHz", "48kHz"),
+            "insert interpolate(160) | decimate(147) after '@sig' in task 'r'"
+        );
+    }
+
+    #[test]
+    fn nearest_ratio_approximates_non_integer_rates() {
+        assert_eq!(nearest_ratio(10.0, MAX_RESAMPLE_FACTOR), (10, 1));
+        assert_eq!(nearest_ratio(0.25, MAX_RESAMPLE_FACTOR), (1, 4));
+        assert_eq!(
+            nearest_ratio(48000.0 / 44100.0, MAX_RESAMPLE_FACTOR),
+            (160, 147)
+        );
+        assert_eq!(nearest_ratio(std::f64::consts::PI, 100), (22, 7));
+    }
+
     #[test]
     fn cross_clock_rate_mismatch_modal_writer_is_error() {
         let reg = test_registry();
@@ -3110,6 +4601,198 @@ mod tests {
         );
     }
 
+    // ── Phase 4b: Source termination tests ──────────────────────────────
+
+    #[test]
+    fn mixed_bounded_unbounded_sources_warns() {
+        let reg = test_registry();
+        let result = analyze_ok(
+            concat!(
+                "clock 1kHz replay { binread(\"in.bin\", \"float\") -> samples }\n",
+                "clock 1kHz gen { constant(0.0) -> offset }\n",
+                "clock 1kHz mix {\n",
+                "    @samples | stdout()\n",
+                "    @offset | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        let warnings: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::W0312))
+            .collect();
+        assert_eq!(warnings.len(), 1, "got: {:#?}", result.diagnostics);
+        assert_eq!(
+            warnings[0].message,
+            "task 'mix' mixes bounded source 'binread' (task 'replay') \
+             with unbounded source 'constant' (task 'gen')"
+        );
+    }
+
+    #[test]
+    fn bounded_source_alone_does_not_warn() {
+        let reg = test_registry();
+        let result = analyze_ok(
+            concat!(
+                "clock 1kHz replay { binread(\"in.bin\", \"float\") -> samples }\n",
+                "clock 1kHz sink { @samples | stdout() }\n",
+                "clock 1kHz local {\n",
+                "    binread(\"in.bin\", \"float\") | stdout()\n",
+                "    constant(0.0) | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .all(|d| d.code != Some(codes::W0312)),
+            "got: {:#?}",
+            result.diagnostics
+        );
+    }
+
+    #[test]
+    fn switch_ctrl_not_written_in_control_block_errors() {
+        let reg = test_registry();
+        let source = concat!(
+            "clock 1kHz rx {\n",
+            "    control { constant(0.0) | stdout() }\n",
+            "    mode a { constant(0) -> ctrl }\n",
+            "    mode b { constant(0) -> ctrl }\n",
+            "    switch(ctrl, a, b)\n",
+            "}\n",
+        );
+        let result = analyze_source(source, &reg);
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::E0313))
+            .collect();
+        assert_eq!(errors.len(), 1, "got: {:#?}", result.diagnostics);
+        assert_eq!(
+            errors[0].message,
+            "switch ctrl buffer 'ctrl' of task 'rx' is not written in the control block"
+        );
+        let switch_ctrl = source.find("switch(ctrl").unwrap() + "switch(".len();
+        assert_eq!(errors[0].span.start, switch_ctrl);
+    }
+
+    #[test]
+    fn switch_ctrl_from_another_task_is_not_required_in_control_block() {
+        let reg = test_registry();
+        let result = analyze_ok(
+            concat!(
+                "clock 1kHz producer { constant(0) -> ctrl }\n",
+                "clock 1kHz rx {\n",
+                "    control { constant(0.0) | stdout() }\n",
+                "    mode a { constant(1.0) | stdout() }\n",
+                "    mode b { constant(2.0) | stdout() }\n",
+                "    switch(ctrl, a, b)\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        assert!(!has_error_code(&result, codes::E0313));
+    }
+
+    #[test]
+    fn modal_asymmetric_buffer_write_warns() {
+        let reg = test_registry();
+        let source = concat!(
+            "clock 1kHz rx {\n",
+            "    control { constant(0) -> ctrl }\n",
+            "    mode a { constant(1.0) -> out }\n",
+            "    mode b { constant(2.0) | stdout() }\n",
+            "    switch(ctrl, a, b)\n",
+            "}\n",
+            "clock 1kHz sink { @out | stdout() }\n",
+        );
+        let result = analyze_ok(source, &reg);
+        let warnings: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::W0313))
+            .collect();
+        assert_eq!(warnings.len(), 1, "got: {:#?}", result.diagnostics);
+        assert_eq!(
+            warnings[0].message,
+            "mode 'b' of task 'rx' does not write shared buffer 'out'"
+        );
+        let mode_b = source.find("mode b").unwrap();
+        assert_eq!(warnings[0].span.start, mode_b);
+        assert_eq!(warnings[0].related_spans.len(), 1);
+    }
+
+    #[test]
+    fn modal_symmetric_buffer_writes_do_not_warn() {
+        let reg = test_registry();
+        let result = analyze_ok(
+            concat!(
+                "clock 1kHz rx {\n",
+                "    control { constant(0) -> ctrl }\n",
+                "    mode a { constant(1.0) -> out }\n",
+                "    mode b { constant(2.0) -> out }\n",
+                "    switch(ctrl, a, b)\n",
+                "}\n",
+                "clock 1kHz sink { @out | stdout() }\n",
+            ),
+            &reg,
+        );
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .all(|d| d.code != Some(codes::W0313)),
+            "got: {:#?}",
+            result.diagnostics
+        );
+    }
+
+    // ── Phase 5b: Family buffer reuse tests ─────────────────────────────
+
+    #[test]
+    fn family_reuse_scatter_gather_coalescable() {
+        let reg = test_registry();
+        // 4 element buffers, unsized by analysis → 1024B payload each.
+        // Headers: 64 × (2 + 1 reader) = 192B per ring buffer.
+        // separate = 4×1024 + 4×192 = 4864, coalesced = 4×1024 + 192 = 4288
+        let result = analyze_ok(
+            concat!(
+                "shared sig[4]\n",
+                "clock 1kHz writer { constant(0.0) -> sig[*] }\n",
+                "clock 1kHz reader { @sig[*] | stdout<float>() }\n",
+            ),
+            &reg,
+        );
+        let reuse = &result.analysis.family_reuse;
+        assert_eq!(reuse.len(), 1, "expected one reusable family: {:?}", reuse);
+        assert_eq!(reuse[0].family_name, "sig");
+        assert_eq!(reuse[0].writer_task, "writer");
+        assert_eq!(reuse[0].reader_tasks, vec!["reader".to_string()]);
+        assert_eq!(reuse[0].separate_bytes, 4864);
+        assert_eq!(reuse[0].coalesced_bytes, 4288);
+        assert_eq!(reuse[0].saved_bytes(), 576);
+    }
+
+    #[test]
+    fn family_reuse_rejects_element_access() {
+        let reg = test_registry();
+        // sig[1] is also read on its own: live ranges no longer coincide.
+        let result = analyze_ok(
+            concat!(
+                "shared sig[2]\n",
+                "clock 1kHz writer { constant(0.0) -> sig[*] }\n",
+                "clock 1kHz reader { @sig[*] | stdout<float>() }\n",
+                "clock 1kHz tap { @sig[1] | stdout<float>() }\n",
+            ),
+            &reg,
+        );
+        assert!(result.analysis.family_reuse.is_empty());
+    }
+
     // ── Phase 5/6: Buffer size and memory pool tests ────────────────────
 
     #[test]
@@ -3133,6 +4816,30 @@ mod tests {
         assert_eq!(result.analysis.total_memory, 8);
     }
 
+    #[test]
+    fn buffer_size_follows_buffering_mode() {
+        let reg = test_registry();
+        // buffer_bytes = depth × 1 × 4 for single (1) and triple (3)
+        for (mode, bytes) in [("single", 4), ("double", 8), ("triple", 12)] {
+            let result = analyze_ok(
+                &format!(
+                    "set buffering = {}\n\
+                     clock 1kHz a {{ constant(0.0) -> sig }}\n\
+                     clock 1kHz b {{ @sig | stdout() }}\n",
+                    mode
+                ),
+                &reg,
+            );
+            assert_eq!(
+                *result.analysis.inter_task_buffers.get("sig").unwrap(),
+                bytes,
+                "set buffering = {}",
+                mode
+            );
+            assert_eq!(result.analysis.total_memory, bytes);
+        }
+    }
+
     #[test]
     fn memory_pool_exceeded_error() {
         let reg = test_registry();
@@ -3167,6 +4874,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn memory_pool_percent_of_platform_budget() {
+        let reg = test_registry();
+        // sig buffer = 2 × 1 × 4 = 8 bytes
+        let src = |pct: &str| {
+            format!(
+                "set mem = {}\nclock 1kHz a {{ constant(0.0) -> sig }}\nclock 1kHz b {{ @sig | stdout() }}\n",
+                pct
+            )
+        };
+
+        // 0% leaves no room for the 8-byte buffer.
+        let zero = analyze_source_with_platform_memory(&src("0%"), &reg, Some(1024));
+        assert!(
+            has_error(
+                &zero,
+                "available 0 bytes (set mem = 0% of --platform-memory)"
+            ),
+            "expected E0307 against a 0% budget, got: {:#?}",
+            zero.diagnostics
+        );
+
+        // 100% is the whole budget; 1% of 1KB (10 bytes) still fits.
+        let full = analyze_source_with_platform_memory(&src("100%"), &reg, Some(8));
+        assert!(!full.diagnostics.iter().any(|d| d.level == DiagLevel::Error));
+        let one = analyze_source_with_platform_memory(&src("1%"), &reg, Some(1024));
+        assert!(!one.diagnostics.iter().any(|d| d.level == DiagLevel::Error));
+
+        // Over 100% is rejected rather than exceeding the platform budget.
+        let over = analyze_source_with_platform_memory(&src("150%"), &reg, Some(1024));
+        let diag = over
+            .diagnostics
+            .iter()
+            .find(|d| d.code == Some(codes::E0315))
+            .expect("expected E0315 for >100%");
+        assert!(diag
+            .message
+            .contains("exceeds the platform memory budget of 1024 bytes"));
+
+        // A percentage without --platform-memory cannot be resolved.
+        let missing = analyze_source_with_platform_memory(&src("50%"), &reg, None);
+        let diag = missing
+            .diagnostics
+            .iter()
+            .find(|d| d.code == Some(codes::E0315))
+            .expect("expected E0315 without --platform-memory");
+        assert!(diag.message.contains("no --platform-memory was given"));
+
+        // Absolute sizes ignore the platform budget.
+        let abs = analyze_source_with_platform_memory(&src("1KB"), &reg, Some(0));
+        assert!(!abs.diagnostics.iter().any(|d| d.level == DiagLevel::Error));
+    }
+
     // ── Phase 7: Param type tests ───────────────────────────────────────
 
     #[test]
@@ -3240,6 +5000,41 @@ mod tests {
         assert!(!rv.is_empty());
     }
 
+    #[test]
+    fn dimension_inference_from_const_arithmetic_shape() {
+        // fft()[N*2] / fft()[N/2]: the folded value propagates downstream
+        let reg = test_registry();
+        for (dim, expected) in [("N*2", 512), ("N/2", 128)] {
+            let source = format!(
+                "const N = 256\nclock 1kHz t {{\n    constant(0.0) | fft()[{}] | mag() | stdout()\n}}",
+                dim
+            );
+            let (result, graph) = analyze_with_graph(&source, &reg);
+            assert!(
+                !result
+                    .diagnostics
+                    .iter()
+                    .any(|d| d.level == DiagLevel::Error),
+                "unexpected errors for [{}]: {:#?}",
+                dim,
+                result.diagnostics
+            );
+            let mag_id = find_actor_id(&graph, "t", "mag");
+            let inferred = result
+                .analysis
+                .inferred_shapes
+                .get(&mag_id)
+                .expect("expected inferred shape for mag()");
+            assert!(
+                matches!(inferred.dims[..], [ShapeDim::Literal(n, _)] if n == expected),
+                "[{}]: expected inferred dim {}, got {:?}",
+                dim,
+                expected,
+                inferred.dims
+            );
+        }
+    }
+
     // ── Phase 9: SDF edge shape inference (§13.3.3) ───────────────────
 
     #[test]
@@ -3269,6 +5064,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shape_inference_budget_exhaustion_reports_e0314() {
+        // A zero step budget cannot reach a fixpoint: the guard must report
+        // instead of looping (dense path, < SHAPE_WORKLIST_MIN_EDGES edges).
+        let reg = test_registry();
+        let source = "clock 1kHz t {\n    constant(0.0) | fft()[256] | mag() | stdout()\n}";
+        let result = analyze_source_with_shape_budget(source, &reg, 0);
+        assert!(has_error_code(&result, codes::E0314));
+        assert!(has_error(
+            &result,
+            "shape inference did not converge in task 't' (pipeline)"
+        ));
+    }
+
+    #[test]
+    fn shape_inference_budget_guards_worklist_path() {
+        let reg = test_registry();
+        let stages = " | fft() | mag()".repeat(12);
+        let source = format!(
+            "clock 1kHz t {{\n    constant(0.0) | fft()[256] | mag(){} | stdout()\n}}",
+            stages
+        );
+        let converged = analyze_ok(&source, &reg);
+        assert!(!has_error_code(&converged, codes::E0314));
+        let exhausted = analyze_source_with_shape_budget(&source, &reg, 0);
+        assert!(has_error_code(&exhausted, codes::E0314));
+    }
+
     #[test]
     fn sdf_edge_inference_through_fork() {
         // fft(256) | :raw | mag(): N inferred through fork node
@@ -3337,6 +5160,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn defaulted_dimension_is_resolved() {
+        // N = 256 fills the omitted argument, so no E0300; an explicit
+        // shape constraint still takes precedence over the default.
+        let reg = test_registry_with_extra_header(
+            "ACTOR(blk_fft, IN(float, N), OUT(cfloat, N), PARAM(int, N = 256)) {\n\
+             return ACTOR_OK;\n}};\n",
+        );
+        for (call, frame) in [("blk_fft()", 256), ("blk_fft()[64]", 64)] {
+            let source = format!(
+                "clock 1kHz t {{\n    constant(0.0) | {} | mag() | stdout()\n}}",
+                call
+            );
+            let (result, graph) = analyze_with_graph(&source, &reg);
+            assert!(
+                result
+                    .diagnostics
+                    .iter()
+                    .all(|d| d.level != DiagLevel::Error),
+                "unexpected errors for {}: {:#?}",
+                call,
+                result.diagnostics
+            );
+            let rv =
+                &result.analysis.repetition_vectors[&("t".to_string(), "pipeline".to_string())];
+            assert_eq!(rv[&find_actor_id(&graph, "t", "stdout")], frame, "{}", call);
+        }
+    }
+
     #[test]
     fn conflicting_shape_constraint_error() {
         // fft(256) outputs [256], but mag()[128] has explicit [128] → conflict
@@ -3534,6 +5386,34 @@ ACTOR(float_src, IN(void, 0), OUT(float, 1), PARAM(float, value)) {
         );
     }
 
+    #[test]
+    fn ctrl_type_bool_error() {
+        let reg = test_registry_with_extra_header(BOOL_ACTORS);
+        let result = analyze_source(
+            concat!(
+                "clock 1kHz t {\n",
+                "    control {\n",
+                "        constant(0.0) | is_positive() -> ctrl\n",
+                "    }\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(ctrl, a, b) default a\n",
+                "}",
+            ),
+            &reg,
+        );
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.code == Some(codes::E0310)
+                    && d.message
+                        .contains("has type bool, but switch ctrl must be int32")),
+            "{:#?}",
+            result.diagnostics
+        );
+    }
+
     #[test]
     fn switch_param_ctrl_type_int32_ok() {
         let reg = test_registry();
@@ -3588,6 +5468,59 @@ ACTOR(float_src, IN(void, 0), OUT(float, 1), PARAM(float, value)) {
         );
     }
 
+    #[test]
+    fn switch_bind_ctrl_infers_int32_in_contract() {
+        let reg = test_registry();
+        let result = analyze_ok(
+            concat!(
+                "bind sel = udp(\"127.0.0.1:9100\")\n",
+                "clock 1kHz t {\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(@sel, a, b)\n",
+                "}",
+            ),
+            &reg,
+        );
+        let contract = &result.analysis.bind_contracts["sel"];
+        assert_eq!(contract.direction, BindDirection::In);
+        assert_eq!(contract.dtype, Some(PipitType::Int32));
+        assert_eq!(contract.rate_hz, Some(1000.0));
+    }
+
+    #[test]
+    fn switch_bind_ctrl_type_not_int32_error() {
+        let reg = test_registry_with_extra_header(
+            r#"
+#include <pipit.h>
+ACTOR(float_sink, IN(float, 1), OUT(void, 0)) {
+    (void)in; (void)out; return ACTOR_OK;
+}};"#,
+        );
+        let result = analyze_source(
+            concat!(
+                "bind sel = udp(\"127.0.0.1:9100\")\n",
+                "clock 1kHz mon {\n    @sel | float_sink()\n}\n",
+                "clock 1kHz t {\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(@sel, a, b)\n",
+                "}",
+            ),
+            &reg,
+        );
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.code == Some(codes::E0310)
+                    && d.message.contains("switch bind '@sel'")
+                    && d.message.contains("float")),
+            "should error when switch(@bind,...) is read as float: {:#?}",
+            result.diagnostics
+        );
+    }
+
     // ── v0.3.1 span-derived dimension tests ─────────────────────────────
 
     #[test]
@@ -3618,6 +5551,34 @@ ACTOR(float_src, IN(void, 0), OUT(float, 1), PARAM(float, value)) {
         );
     }
 
+    #[test]
+    fn span_derived_dim_uses_slice_length() {
+        let reg = test_registry();
+        let source = concat!(
+            "const coeff = [0.1, 0.2, 0.4, 0.2, 0.1, 0.0, 0.0]\n",
+            "clock 1kHz t {\n    constant(0.0) | fir(coeff[1:4]) | stdout()\n}",
+        );
+        let (result, graph) = analyze_with_graph(source, &reg);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .all(|d| d.level != DiagLevel::Error),
+            "unexpected errors: {:#?}",
+            result.diagnostics
+        );
+        let fir_id = find_actor_id(&graph, "t", "fir");
+        assert_eq!(
+            result
+                .analysis
+                .span_derived_dims
+                .get(&fir_id)
+                .and_then(|m| m.get("N")),
+            Some(&3),
+            "fir(coeff[1:4]) should store N=3, not the full array length"
+        );
+    }
+
     #[test]
     fn span_derived_dim_not_stored_when_explicit_arg() {
         let reg = test_registry();
@@ -3941,7 +5902,7 @@ clock 48kHz audio {
         // binwrite() has concrete IN(float, 1) → dtype = Float
         let source = r#"bind iq = udp("127.0.0.1:9100")
 clock 48kHz audio {
-    @iq | binwrite("/dev/null")
+    @iq | binwrite("/dev/null", "float")
 }
 "#;
         let result = analyze_ok(source, &reg);
@@ -3953,6 +5914,74 @@ clock 48kHz audio {
         assert_eq!(contract.dtype, Some(PipitType::Float));
     }
 
+    #[test]
+    fn bind_in_declared_type_sets_contract() {
+        let reg = test_registry();
+        // stdout<T>() cannot pin the type; the declaration supplies it.
+        let source = r#"const N = 1
+bind iq: cfloat[N] = udp("127.0.0.1:9100")
+clock 48kHz audio {
+    @iq | stdout()
+}
+"#;
+        let result = analyze_ok(source, &reg);
+        let contract = result
+            .analysis
+            .bind_contracts
+            .get("iq")
+            .expect("contract for 'iq'");
+        assert_eq!(contract.dtype, Some(PipitType::Cfloat));
+        assert_eq!(contract.shape, vec![1]);
+    }
+
+    #[test]
+    fn bind_in_declared_type_conflicts_with_reader_e0312() {
+        let reg = test_registry();
+        let source = r#"bind iq: cfloat = udp("127.0.0.1:9100")
+clock 48kHz audio {
+    @iq | binwrite("/dev/null", "float")
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(
+            has_error(&result, "declares type cfloat but its readers use float"),
+            "expected E0312 for declared/traced dtype mismatch: {:#?}",
+            result.diagnostics
+        );
+    }
+
+    #[test]
+    fn bind_in_declared_shape_conflicts_with_reader_e0312() {
+        let reg = test_registry();
+        let source = r#"bind iq: cfloat[128] = udp("127.0.0.1:9100")
+clock 48kHz audio {
+    @iq | mag(256) | stdout()
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(
+            has_error(&result, "declares shape [128] but its readers use [256]"),
+            "expected E0312 for declared/traced shape mismatch: {:#?}",
+            result.diagnostics
+        );
+    }
+
+    #[test]
+    fn bind_out_declared_type_conflicts_with_writer_e0312() {
+        let reg = test_registry();
+        let source = r#"bind iq: float = udp("127.0.0.1:9100")
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(
+            has_error(&result, "declares type float but its writer produces int32"),
+            "expected E0312 for declared/writer dtype mismatch: {:#?}",
+            result.diagnostics
+        );
+    }
+
     #[test]
     fn bind_in_contract_rate() {
         let reg = test_registry();
@@ -3976,6 +6005,37 @@ clock 48kHz audio {
         );
     }
 
+    #[test]
+    fn bind_version_recorded_in_contract() {
+        let reg = test_registry();
+        let source = r#"bind iq = udp("127.0.0.1:9100", version=3)
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_ok(source, &reg);
+        assert_eq!(result.analysis.bind_contracts["iq"].version, Some(3));
+    }
+
+    #[test]
+    fn bind_version_must_be_non_negative_integer() {
+        let reg = test_registry();
+        for version in ["1.5", "-1", "70000", "\"v2\""] {
+            let source = format!(
+                "bind iq = udp(\"127.0.0.1:9100\", version={})\n\
+                 clock 48kHz audio {{\n    constant(0) -> iq\n}}\n",
+                version
+            );
+            let result = analyze_source(&source, &reg);
+            assert!(
+                has_error_code(&result, codes::E0713),
+                "expected E0713 for version={}, got: {:?}",
+                version,
+                result.diagnostics
+            );
+        }
+    }
+
     #[test]
     fn stable_id_deterministic() {
         let reg = test_registry();
@@ -4197,4 +6257,75 @@ clock 48kHz audio {
             "SHM validation should not apply to UDP binds"
         );
     }
+
+    #[test]
+    fn tcp_endpoint_valid() {
+        let reg = test_registry();
+        let source = r#"bind iq = tcp("127.0.0.1:9100", backlog=4, reconnect=500)
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_source(source, &reg);
+        for code in [codes::E0730, codes::E0731, codes::E0732] {
+            assert!(!has_error_code(&result, code), "unexpected {:?}", code);
+        }
+    }
+
+    #[test]
+    fn tcp_endpoint_missing_port() {
+        let reg = test_registry();
+        let source = r#"bind iq = tcp("localhost")
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(
+            has_error_code(&result, codes::E0730),
+            "expected E0730 for tcp address without port"
+        );
+    }
+
+    #[test]
+    fn tcp_endpoint_port_out_of_range() {
+        let reg = test_registry();
+        let source = r#"bind iq = tcp("127.0.0.1:70000")
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(has_error_code(&result, codes::E0730));
+    }
+
+    #[test]
+    fn tcp_endpoint_backlog_zero() {
+        let reg = test_registry();
+        let source = r#"bind iq = tcp("127.0.0.1:9100", backlog=0)
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(
+            has_error_code(&result, codes::E0731),
+            "expected E0731 for backlog=0"
+        );
+    }
+
+    #[test]
+    fn tcp_endpoint_reconnect_non_integer() {
+        let reg = test_registry();
+        let source = r#"bind iq = tcp("127.0.0.1:9100", reconnect=1.5)
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+        let result = analyze_source(source, &reg);
+        assert!(
+            has_error_code(&result, codes::E0732),
+            "expected E0732 for fractional reconnect"
+        );
+    }
 }
@@ -40,6 +40,7 @@ pub enum StatementKind {
     Task(Box<TaskStmt>),
     Bind(BindStmt),
     Shared(SharedDecl),
+    Include(IncludeStmt),
 }
 
 // ── set_stmt: 'set' IDENT '=' set_value ──
@@ -55,9 +56,15 @@ pub struct SetStmt {
 pub enum SetValue {
     Number(f64, Span),
     Size(u64, Span),
+    /// Percentage (e.g. `50%`); `set mem` resolves it against
+    /// `--platform-memory`.
+    Percent(f64, Span),
     Freq(f64, Span),
     StringLit(String, Span),
     Ident(Ident),
+    /// `{ key: value, ... }` (e.g. `set affinity = { a: 2, b: 3 }`); entries
+    /// hold simple values only.
+    Map(Vec<(Ident, SetValue)>, Span),
 }
 
 // ── const_stmt: 'const' IDENT '=' value ──
@@ -85,12 +92,42 @@ pub struct DefineStmt {
     pub body: PipelineBody,
 }
 
-// ── bind_stmt: 'bind' IDENT '=' bind_endpoint ──
+// ── bind_stmt: ('@' 'when' '(' build_mode ')')? 'bind' IDENT (':' bind_type)? '=' bind_endpoint ──
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BindStmt {
     pub name: Ident,
+    /// Declared wire type (`bind iq: cfloat[1024] = ...`); overrides the
+    /// contract traced from readers.
+    pub ty: Option<BindType>,
     pub endpoint: BindEndpoint,
+    /// `@when(mode)` guard; the bind only exists in builds of that mode.
+    pub when: Option<BuildMode>,
+}
+
+/// bind_type: pipit type keyword with an optional shape, e.g. `cfloat[1024]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindType {
+    pub dtype: Ident,
+    pub shape: Option<ShapeConstraint>,
+    pub span: Span,
+}
+
+/// Build mode named by a `@when(...)` guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildMode {
+    Debug,
+    Release,
+}
+
+impl BuildMode {
+    /// Whether a statement guarded by this mode is active in the given build.
+    pub fn is_active(self, release: bool) -> bool {
+        match self {
+            BuildMode::Debug => !release,
+            BuildMode::Release => release,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -184,6 +221,8 @@ pub struct SwitchStmt {
 pub enum SwitchSource {
     Buffer(Ident),
     Param(Ident),
+    /// `@name` — an IN bind written by an external controller.
+    Bind(Ident),
 }
 
 // ── pipeline_body: (pipe_expr NL)* ──
@@ -212,6 +251,15 @@ pub enum PipeSource {
     TapRef(Ident),
     /// `name(args)` — actor call
     ActorCall(ActorCall),
+    /// `gather(:a, :b, ...)` — fan several taps into the next actor's input
+    TapGather(TapGather),
+}
+
+/// `gather(:a, :b, ...)`: the listed taps, in concatenation order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TapGather {
+    pub taps: Vec<Ident>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -231,7 +279,7 @@ pub struct Sink {
     pub span: Span,
 }
 
-// ── actor_call: IDENT ('<' type_arg (',' type_arg)* '>')? '(' args? ')' shape_constraint? ──
+// ── actor_call: IDENT ('<' type_arg (',' type_arg)* '>')? '(' args? ')' shape_constraint? annotation* ──
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ActorCall {
@@ -242,6 +290,15 @@ pub struct ActorCall {
     pub args: Vec<Arg>,
     /// Optional shape constraint: `actor(...)[d0, d1, ...]` (v0.2.0).
     pub shape_constraint: Option<ShapeConstraint>,
+    /// Optional atomic group annotation: `actor(...) @atomic_group(name)`.
+    /// Members of a group fire contiguously in declared order.
+    pub atomic_group: Option<Ident>,
+    /// `@persist` annotation: a modal `delay` keeps its state across mode switches.
+    pub persist: bool,
+    /// `@allow_combinational_loop` annotation: a feedback cycle through this
+    /// call may have no `delay`.
+    pub allow_combinational_loop: bool,
+    /// Span of the call itself (excludes any trailing annotation).
     pub span: Span,
 }
 
@@ -259,6 +316,116 @@ pub enum ShapeDim {
     Literal(u32, Span),
     /// Const reference dimension (resolved later).
     ConstRef(Ident),
+    /// Integer arithmetic over dimensions: `N*2`, `N+1` (folded once consts resolve).
+    BinOp(Box<ShapeDim>, ShapeOp, Box<ShapeDim>, Span),
+}
+
+/// Operator in a shape dimension expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeOp {
+    Add,
+    Sub,
+    Mul,
+    /// Truncating integer division.
+    Div,
+}
+
+/// Why a `ShapeDim` could not be folded to a size.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapeFoldError {
+    /// A const reference has no integer value.
+    Unresolved(Ident),
+    /// The right-hand side of `/` evaluated to 0.
+    DivisionByZero(Span),
+    /// A subexpression evaluated to a negative value.
+    Negative(i64, Span),
+    /// A subexpression exceeded `u32::MAX`.
+    Overflow(Span),
+}
+
+impl ShapeDim {
+    pub fn span(&self) -> Span {
+        match self {
+            ShapeDim::Literal(_, span) | ShapeDim::BinOp(_, _, _, span) => *span,
+            ShapeDim::ConstRef(ident) => ident.span,
+        }
+    }
+
+    /// Const references in this dimension, left to right.
+    pub fn const_refs(&self) -> Vec<&Ident> {
+        match self {
+            ShapeDim::Literal(..) => Vec::new(),
+            ShapeDim::ConstRef(ident) => vec![ident],
+            ShapeDim::BinOp(lhs, _, rhs, _) => {
+                let mut refs = lhs.const_refs();
+                refs.extend(rhs.const_refs());
+                refs
+            }
+        }
+    }
+
+    /// Evaluate to a concrete size, looking const references up with `lookup`.
+    pub fn fold(&self, lookup: &dyn Fn(&str) -> Option<u32>) -> Result<u32, ShapeFoldError> {
+        match self {
+            ShapeDim::Literal(n, _) => Ok(*n),
+            ShapeDim::ConstRef(ident) => {
+                lookup(&ident.name).ok_or_else(|| ShapeFoldError::Unresolved(ident.clone()))
+            }
+            ShapeDim::BinOp(lhs, op, rhs, span) => {
+                let l = i64::from(lhs.fold(lookup)?);
+                let r = i64::from(rhs.fold(lookup)?);
+                let value = match op {
+                    ShapeOp::Add => l + r,
+                    ShapeOp::Sub => l - r,
+                    ShapeOp::Mul => l * r,
+                    ShapeOp::Div if r == 0 => {
+                        return Err(ShapeFoldError::DivisionByZero(rhs.span()))
+                    }
+                    ShapeOp::Div => l / r,
+                };
+                if value < 0 {
+                    return Err(ShapeFoldError::Negative(value, *span));
+                }
+                u32::try_from(value).map_err(|_| ShapeFoldError::Overflow(*span))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ShapeDim {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeDim::Literal(n, _) => write!(f, "{}", n),
+            ShapeDim::ConstRef(ident) => write!(f, "{}", ident.name),
+            ShapeDim::BinOp(lhs, op, rhs, _) => {
+                // Parenthesize a looser-binding operand so the text re-parses
+                // to the same tree.
+                let prec = |d: &ShapeDim| match d {
+                    ShapeDim::BinOp(_, ShapeOp::Add | ShapeOp::Sub, _, _) => 1,
+                    ShapeDim::BinOp(..) => 2,
+                    _ => 3,
+                };
+                let own = prec(self);
+                let symbol = match op {
+                    ShapeOp::Add => "+",
+                    ShapeOp::Sub => "-",
+                    ShapeOp::Mul => "*",
+                    ShapeOp::Div => "/",
+                };
+                if prec(lhs) < own {
+                    write!(f, "({})", lhs)?;
+                } else {
+                    write!(f, "{}", lhs)?;
+                }
+                write!(f, "{}", symbol)?;
+                if prec(rhs) <= own {
+                    write!(f, "({})", rhs)
+                } else {
+                    write!(f, "{}", rhs)
+                }
+            }
+        }
+    }
 }
 
 // ── arg ──
@@ -273,6 +440,27 @@ pub enum Arg {
     ConstRef(Ident),
     /// `:name` — tap reference as additional actor input (for feedback loops).
     TapRef(Ident),
+    /// `name[start:end]` — half-open slice of a const array.
+    ConstSlice(ConstSlice),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstSlice {
+    pub name: Ident,
+    pub start: u32,
+    pub end: u32,
+    /// Span of the whole `name[start:end]` expression.
+    pub span: Span,
+}
+
+impl ConstSlice {
+    pub fn len(&self) -> u32 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 // ── value, scalar, array ──
@@ -327,6 +515,14 @@ pub struct SharedDecl {
     pub span: Span,
 }
 
+/// `include "file.pdl"` — composes another source file into this program.
+/// Resolved before parsing the combined source (see `crate::include`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludeStmt {
+    pub path: String,
+    pub path_span: Span,
+}
+
 // ── Spawn clause (v0.4.8) ──
 
 /// Spawn range bound — non-negative integer (unlike ShapeDim which requires > 0).
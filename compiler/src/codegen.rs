@@ -14,7 +14,7 @@ use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::path::PathBuf;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use crate::ast::BindDirection;
 use crate::diag::{codes, DiagLevel, Diagnostic};
@@ -39,20 +39,160 @@ pub struct CodegenResult {
 #[derive(Debug)]
 pub struct GeneratedCode {
     pub cpp_source: String,
+    /// Every buffer the generated code declares, in emission order
+    /// (`--trace-allocations`).
+    pub allocations: Vec<BufferAllocation>,
 }
 
-#[derive(Debug, Clone)]
+/// A buffer declared by the generated C++ — the codegen-side ground truth
+/// of memory usage, as opposed to the analysis estimate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferAllocation {
+    /// Owning task; `None` for inter-task ring buffers at file scope.
+    pub task: Option<String>,
+    pub kind: AllocationKind,
+    /// C++ variable name.
+    pub name: String,
+    pub cpp_type: &'static str,
+    /// Payload element count.
+    pub elements: u64,
+    /// Total bytes, including the ring buffer header for `Ring`.
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllocationKind {
+    /// Intra-task edge buffer (`alignas(64) static T name[N]`; the trailing
+    /// `pipit::EDGE_GUARD_TOKENS` of debug builds are not counted).
+    Edge,
+    /// Feedback back-edge buffer, initialized once per task run.
+    Feedback,
+    /// Inter-task `pipit::RingBuffer` (one per shared buffer or coalesced family).
+    Ring,
+    /// Per-actor-instance `SCRATCH()` array.
+    Scratch,
+}
+
+impl std::fmt::Display for AllocationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            AllocationKind::Edge => "edge",
+            AllocationKind::Feedback => "feedback",
+            AllocationKind::Ring => "ring",
+            AllocationKind::Scratch => "scratch",
+        })
+    }
+}
+
+/// Render the `--trace-allocations` report: one line per buffer, grouped by
+/// task (inter-task ring buffers under `shared`), with per-group and grand
+/// totals in bytes.
+pub fn format_allocation_report(allocations: &[BufferAllocation]) -> String {
+    let mut groups: Vec<(Option<&str>, Vec<&BufferAllocation>)> = Vec::new();
+    for alloc in allocations {
+        let task = alloc.task.as_deref();
+        match groups.iter_mut().find(|(t, _)| *t == task) {
+            Some((_, group)) => group.push(alloc),
+            None => groups.push((task, vec![alloc])),
+        }
+    }
+
+    let mut out = String::from("allocations:\n");
+    let mut total = 0;
+    for (task, group) in &groups {
+        let _ = match task {
+            Some(name) => writeln!(out, "  task {}:", name),
+            None => writeln!(out, "  shared:"),
+        };
+        let mut group_total = 0;
+        for alloc in group {
+            let _ = writeln!(
+                out,
+                "    {:<8} {} {}[{}] = {} bytes",
+                alloc.kind, alloc.name, alloc.cpp_type, alloc.elements, alloc.bytes
+            );
+            group_total += alloc.bytes;
+        }
+        let _ = writeln!(out, "    subtotal: {} bytes", group_total);
+        total += group_total;
+    }
+    let _ = writeln!(out, "  total: {} bytes", total);
+    out
+}
+
+/// Size in bytes of a LIR C++ element type.
+fn cpp_type_size(cpp_type: &str) -> u64 {
+    match cpp_type {
+        "int8_t" | "bool" => 1,
+        "int16_t" => 2,
+        "int32_t" | "float" => 4,
+        "double" | "cfloat" => 8,
+        "cdouble" => 16,
+        _ => 0,
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct CodegenOptions {
     pub release: bool,
+    /// Actor header directories (`-I`). Machine-specific paths; header
+    /// content is covered by the registry fingerprint instead.
+    #[serde(skip)]
     pub include_paths: Vec<PathBuf>,
     /// Provenance metadata to stamp in generated C++ header comment.
     /// None in unit tests; Some in production builds.
+    #[serde(skip)]
     pub provenance: Option<crate::pipeline::Provenance>,
     /// Enable experimental codegen features (e.g., block pool allocator).
     /// No behavioral change currently — reserved for Phase C gating.
     pub experimental: bool,
+    /// Coalesce eligible shared array families into one ring buffer each
+    /// (see `LirProgram::coalesced_families`).
+    pub reuse_family_buffers: bool,
+    /// Omit the standard includes the generated code uses directly; all of
+    /// them (`<cstdio>`, `<cstring>`) already come through `pipit.h`.
+    pub minimal_includes: bool,
+    /// Run all tasks from one cooperative loop instead of a thread per task
+    /// (`--runtime single-thread`).
+    pub single_thread: bool,
+    /// Emit `#pragma omp parallel for` on repetition loops of eligible
+    /// actors (`LirSubgraph::parallel_nodes`), for builds with `-fopenmp`.
+    pub openmp: bool,
+    /// Bracket every actor call with `pipit_now_ns()` samples accumulated
+    /// per node and ranked at shutdown (`--profile-actors`). Costs two clock
+    /// reads per firing; rejected together with `release`.
+    pub profile_actors: bool,
+    /// Keep the generated glue buildable with `-fno-exceptions`: param
+    /// setters parse with `pipit::parse_param` instead of `std::sto*`.
+    pub no_exceptions: bool,
+    /// Build an embeddable library (`--library`): omit `main()` and signal
+    /// handling, and emit the `extern "C"` entry points declared by
+    /// `--emit header` (`pipit_start`, `pipit_stop`, bind accessors).
+    pub library: bool,
     /// Compile-time bind endpoint overrides: name → endpoint spec string.
+    #[serde(serialize_with = "serialize_sorted")]
     pub bind_overrides: std::collections::HashMap<String, String>,
+    /// Memory pool limit in bytes from `--max-memory`; supersedes `set mem`.
+    pub max_memory: Option<u64>,
+    /// Platform memory budget in bytes from `--platform-memory`; resolves
+    /// `set mem = N%`.
+    pub platform_memory: Option<u64>,
+}
+
+impl CodegenOptions {
+    /// Canonical compact JSON of every option that shapes the generated C++
+    /// (all fields except `provenance` and `include_paths`), for content
+    /// hashing.
+    pub fn canonical_json(&self) -> String {
+        serde_json::to_string(self).expect("codegen options serialization should not fail")
+    }
+}
+
+fn serialize_sorted<S: serde::Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serde::Serialize::serialize(&map.iter().collect::<BTreeMap<_, _>>(), serializer)
 }
 
 // ── Public entry point ──────────────────────────────────────────────────────
@@ -73,6 +213,74 @@ pub fn codegen_from_lir(
     ctx.build_result()
 }
 
+/// Generate the C header of a `--library` build (`--emit header`).
+///
+/// Declares `pipit_start()` / `pipit_stop()` and one accessor per bind with
+/// an inferred dtype — `pipit_read_<bind>` for OUT binds, `pipit_write_<bind>`
+/// for IN binds. Element types follow `BindContract::dtype`; each call moves
+/// one frame of `PIPIT_BIND_<NAME>_TOKENS` elements (the product of `shape`).
+pub fn generate_library_header(lir: &LirProgram) -> String {
+    let mut out = String::new();
+    out.push_str("// Generated by pcc (Pipit Compiler Collection)\n");
+    out.push_str("// C interface of a pipeline built with `pcc --emit cpp --library`.\n");
+    out.push_str("#ifndef PIPIT_PROGRAM_H\n#define PIPIT_PROGRAM_H\n\n");
+    out.push_str("#include <stdint.h>\n\n");
+    out.push_str(&library_c_types(lir));
+
+    for bind in &lir.binds {
+        if library_accessor_signature(bind).is_some() {
+            let _ = writeln!(
+                out,
+                "#define PIPIT_BIND_{}_TOKENS {}",
+                bind.name.to_uppercase(),
+                bind_frame_tokens(bind)
+            );
+        }
+    }
+
+    out.push_str("\n#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+    out.push_str("/* Start the task threads. Returns 0, or -1 if already running. */\n");
+    out.push_str("int pipit_start(void);\n");
+    out.push_str(
+        "/* Stop and join the task threads. Returns the exit code, or -1 if not running. */\n",
+    );
+    out.push_str("int pipit_stop(void);\n");
+
+    for bind in &lir.binds {
+        let Some(contract) = &bind.contract else {
+            continue;
+        };
+        out.push('\n');
+        let (Some(dtype), Some(signature)) = (contract.dtype, library_accessor_signature(bind))
+        else {
+            let _ = writeln!(
+                out,
+                "/* bind '{}': dtype not inferred; no accessor */",
+                bind.name
+            );
+            continue;
+        };
+        let frame = if contract.shape.is_empty() {
+            dtype.to_string()
+        } else {
+            let dims: Vec<String> = contract.shape.iter().map(|d| d.to_string()).collect();
+            format!("{}[{}]", dtype, dims.join("]["))
+        };
+        let effect = match contract.direction {
+            BindDirection::Out => "pops one frame; returns 1, or 0 if none is queued.",
+            BindDirection::In => "pushes one frame; returns 1, or 0 if the port is full.",
+        };
+        let _ = writeln!(
+            out,
+            "/* bind '{}' ({}, {}): {} */\n{};",
+            bind.name, contract.direction, frame, effect, signature
+        );
+    }
+
+    out.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* PIPIT_PROGRAM_H */\n");
+    out
+}
+
 // ── Internal context ────────────────────────────────────────────────────────
 
 struct CodegenCtx<'a> {
@@ -88,6 +296,11 @@ struct CodegenCtx<'a> {
     lowered_shm_binds: HashSet<String>,
     /// Precomputed task name → LIR task index for O(1) lookup.
     task_index: HashMap<&'a str, usize>,
+    /// Buffers declared so far (see `BufferAllocation`).
+    allocations: Vec<BufferAllocation>,
+    /// Declared token count of each guarded edge buffer, by variable name.
+    /// Empty in release builds, which emit no guards or bounds asserts.
+    guarded_edges: HashMap<String, u32>,
 }
 
 impl<'a> CodegenCtx<'a> {
@@ -103,6 +316,24 @@ impl<'a> CodegenCtx<'a> {
             .enumerate()
             .map(|(i, t)| (t.name.as_str(), i))
             .collect();
+        let mut guarded_edges = HashMap::new();
+        if !options.release {
+            for task in &lir.tasks {
+                let subgraphs: Vec<&LirSubgraph> = match &task.body {
+                    LirTaskBody::Pipeline(sg) => vec![sg],
+                    LirTaskBody::Modal(modal) => {
+                        let mut sgs = vec![&modal.control];
+                        sgs.extend(modal.modes.iter().map(|(_, sg)| sg));
+                        sgs
+                    }
+                };
+                for eb in subgraphs.into_iter().flat_map(|sg| &sg.edge_buffers) {
+                    if !eb.is_feedback && eb.alias_of.is_none() {
+                        guarded_edges.insert(eb.var_name.clone(), eb.tokens);
+                    }
+                }
+            }
+        }
         // Estimate output size: ~200 bytes per task + 2K base
         let estimated_size = 2048 + lir.tasks.len() * 200;
         CodegenCtx {
@@ -115,6 +346,8 @@ impl<'a> CodegenCtx<'a> {
             lowered_binds: HashSet::new(),
             lowered_shm_binds: HashSet::new(),
             task_index,
+            allocations: Vec::new(),
+            guarded_edges,
         }
     }
 
@@ -135,6 +368,7 @@ impl<'a> CodegenCtx<'a> {
         CodegenResult {
             generated: GeneratedCode {
                 cpp_source: self.out,
+                allocations: self.allocations,
             },
             diagnostics: self.diagnostics,
         }
@@ -146,38 +380,83 @@ impl<'a> CodegenCtx<'a> {
         self.emit_preamble();
         self.emit_const_storage();
         self.emit_param_storage();
+        self.emit_param_control();
         self.emit_shared_buffers();
         self.emit_stop_flag();
         self.emit_stats_storage();
         self.emit_bind_storage();
         self.emit_bind_io_adapters();
         self.emit_task_functions();
-        self.emit_main();
+        if self.options.library {
+            self.emit_library_entry_points();
+        } else {
+            self.emit_main();
+        }
+    }
+
+    /// Record a declared buffer for the allocation report.
+    fn record_allocation(
+        &mut self,
+        task: Option<&str>,
+        kind: AllocationKind,
+        name: String,
+        cpp_type: &'static str,
+        elements: u64,
+        readers: usize,
+    ) {
+        let mut bytes = elements * cpp_type_size(cpp_type);
+        if kind == AllocationKind::Ring {
+            bytes += crate::analyze::ring_buffer_header_bytes(readers);
+        }
+        self.allocations.push(BufferAllocation {
+            task: task.map(str::to_string),
+            kind,
+            name,
+            cpp_type,
+            elements,
+            bytes,
+        });
     }
 
     // ── Phase 1: Preamble ───────────────────────────────────────────────
 
     fn emit_preamble(&mut self) {
         if let Some(ref prov) = self.options.provenance {
-            let _ = writeln!(
+            let _ = write!(
                 self.out,
                 "// pcc provenance: source_hash={} registry_fingerprint={} version={}",
                 prov.source_hash_hex(),
                 prov.registry_fingerprint_hex(),
                 prov.compiler_version,
             );
+            if let Some(epoch) = prov.build_epoch {
+                let _ = write!(self.out, " build_epoch={}", epoch);
+            }
+            self.out.push('\n');
         }
         self.out
             .push_str("// Generated by pcc (Pipit Compiler Collection)\n");
         self.out.push_str("#include <pipit.h>\n");
         self.out.push_str("#include <pipit_shell.h>\n");
-        if !self.lir.binds.is_empty() {
-            self.out.push_str("#include <pipit_bind_io.h>\n");
+        if self.options.library {
+            // Binds are served in-process by LibraryBindPort; no transports.
+            self.out.push_str("#include <pipit_library.h>\n");
+        } else {
+            if !self.lir.binds.is_empty() {
+                self.out.push_str("#include <pipit_bind_io.h>\n");
+            }
+            if self.lir.binds.iter().any(|b| b.transport == "shm") {
+                self.out.push_str("#include <pipit_shm.h>\n");
+            }
+            if self.lir.binds.iter().any(|b| b.transport == "tcp") {
+                self.out.push_str("#include <pipit_tcp.h>\n");
+            }
         }
-        if self.lir.binds.iter().any(|b| b.transport == "shm") {
-            self.out.push_str("#include <pipit_shm.h>\n");
+        // Emitted code calls only <cstdio> and <cstring> directly, and
+        // pipit.h already includes both.
+        if !self.options.minimal_includes {
+            self.out.push_str("#include <cstdio>\n");
         }
-        self.out.push_str("#include <cstdio>\n");
         self.out.push('\n');
 
         for path in &self.options.include_paths {
@@ -242,6 +521,45 @@ impl<'a> CodegenCtx<'a> {
         self.out.push('\n');
     }
 
+    /// Emit `pipit_set_param(name, value)`: the host-side setter that
+    /// dispatches on param name and stores the coerced value atomically.
+    fn emit_param_control(&mut self) {
+        let lir = self.lir;
+        let mut sorted_params: Vec<&_> = lir.params.iter().collect();
+        sorted_params.sort_by_key(|p| &p.name);
+        self.out.push_str(
+            "// Host control: set a runtime param by name; returns a pipit::ParamStatus.\n",
+        );
+        if sorted_params.is_empty() {
+            self.out.push_str(
+                "int pipit_set_param(const char*, double) { return pipit::PARAM_UNKNOWN; }\n\n",
+            );
+            return;
+        }
+        self.out
+            .push_str("int pipit_set_param(const char* name, double value) {\n");
+        for p in sorted_params {
+            let _ = writeln!(
+                self.out,
+                "    if (std::strcmp(name, \"{}\") == 0) {{",
+                p.name
+            );
+            let _ = writeln!(self.out, "        {} _v{{}};", p.cpp_type);
+            self.out.push_str(
+                "        if (!pipit::coerce_param(value, &_v)) return pipit::PARAM_INVALID;\n",
+            );
+            let _ = writeln!(
+                self.out,
+                "        _param_{}_write.store(_v, std::memory_order_release);",
+                p.name
+            );
+            self.out.push_str("        return pipit::PARAM_OK;\n");
+            self.out.push_str("    }\n");
+        }
+        self.out.push_str("    return pipit::PARAM_UNKNOWN;\n");
+        self.out.push_str("}\n\n");
+    }
+
     // ── Phase 4: Shared (inter-task) buffers ────────────────────────────
 
     fn emit_shared_buffers(&mut self) {
@@ -249,20 +567,99 @@ impl<'a> CodegenCtx<'a> {
         if lir.inter_task_buffers.is_empty() {
             return;
         }
+        let coalesced = self.coalesced_elements();
         for buf in &lir.inter_task_buffers {
             // Skip dead ring buffers (bind-only OUT with no local readers)
             if buf.skip_writes && buf.reader_tasks.is_empty() {
                 continue;
             }
+            if coalesced.contains(buf.name.as_str()) {
+                continue;
+            }
             let _ = writeln!(
                 self.out,
                 "static pipit::RingBuffer<{}, {}, {}> _ringbuf_{};",
                 buf.cpp_type, buf.capacity_tokens, buf.reader_count, buf.name
             );
+            self.record_allocation(
+                None,
+                AllocationKind::Ring,
+                format!("_ringbuf_{}", buf.name),
+                buf.cpp_type,
+                buf.capacity_tokens as u64,
+                buf.reader_count,
+            );
+            self.emit_buffer_peak_decl(&buf.name);
+            self.emit_buffer_stats_decl(&buf.name);
+        }
+        if self.options.reuse_family_buffers {
+            for fam in &lir.coalesced_families {
+                let _ = writeln!(
+                    self.out,
+                    "static pipit::RingBuffer<{}, {}, {}> _ringbuf_{}; // reuses {}",
+                    fam.cpp_type,
+                    fam.capacity_tokens,
+                    fam.reader_count,
+                    fam.family_name,
+                    fam.elements.join(", ")
+                );
+                self.record_allocation(
+                    None,
+                    AllocationKind::Ring,
+                    format!("_ringbuf_{}", fam.family_name),
+                    fam.cpp_type,
+                    fam.capacity_tokens as u64,
+                    fam.reader_count,
+                );
+                self.emit_buffer_peak_decl(&fam.family_name);
+                self.emit_buffer_stats_decl(&fam.family_name);
+            }
         }
         self.out.push('\n');
     }
 
+    /// Debug builds track each ring buffer's high-water mark. The counter is
+    /// written only by the buffer's writer task and read after all tasks join.
+    fn emit_buffer_peak_decl(&mut self, buffer: &str) {
+        if !self.options.release {
+            let _ = writeln!(self.out, "static std::size_t _peak_{} = 0;", buffer);
+        }
+    }
+
+    /// Underrun/overrun counters, bumped on each failed ring read or write
+    /// while `--stats` is on and reported at shutdown.
+    fn emit_buffer_stats_decl(&mut self, buffer: &str) {
+        let _ = writeln!(self.out, "static pipit::BufferStats _bufstats_{};", buffer);
+    }
+
+    /// Count one failed `read` or `write` on `buffer` (see `emit_buffer_stats_decl`).
+    fn buffer_fail_count(buffer: &str, op: &str) -> String {
+        format!("if (_stats) _bufstats_{}.record_{}_fail();", buffer, op)
+    }
+
+    /// Update `_peak_<buffer>` after a successful write (debug builds only).
+    fn emit_buffer_peak_update(&mut self, buffer: &str, indent: &str) {
+        if !self.options.release {
+            let _ = writeln!(
+                self.out,
+                "{}if (std::size_t _fill = _ringbuf_{}.fill(); _fill > _peak_{}) _peak_{} = _fill;",
+                indent, buffer, buffer, buffer
+            );
+        }
+    }
+
+    /// Element buffers replaced by coalesced family ring buffers.
+    fn coalesced_elements(&self) -> HashSet<&'a str> {
+        if !self.options.reuse_family_buffers {
+            return HashSet::new();
+        }
+        self.lir
+            .coalesced_families
+            .iter()
+            .flat_map(|f| f.elements.iter().map(String::as_str))
+            .collect()
+    }
+
     // ── Phase 5: Stop flag ──────────────────────────────────────────────
 
     fn emit_stop_flag(&mut self) {
@@ -272,6 +669,16 @@ impl<'a> CodegenCtx<'a> {
             .push_str("static std::atomic<int> _exit_code{0};\n");
         self.out
             .push_str("static std::atomic<bool> _start{false};\n\n");
+
+        // `set tick_alignment`: the first aligned task past the start barrier
+        // fixes the epoch; the others reuse it so their ticks share a phase.
+        if self.lir.tasks.iter().any(|t| t.tick_aligned) {
+            self.out
+                .push_str("static std::chrono::steady_clock::time_point _tick_epoch() {\n");
+            self.out
+                .push_str("    static const auto epoch = std::chrono::steady_clock::now();\n");
+            self.out.push_str("    return epoch;\n}\n\n");
+        }
     }
 
     // ── Phase 5b: Statistics and probe storage ────────────────────────────
@@ -298,6 +705,20 @@ impl<'a> CodegenCtx<'a> {
                 );
             }
         }
+        if self.options.profile_actors {
+            for task in &lir.tasks {
+                let mut seen = HashSet::new();
+                for actor in collect_lir_actor_firings(&task.body) {
+                    if seen.insert(actor.node_id) {
+                        let _ = writeln!(
+                            self.out,
+                            "static pipit::ActorProfile _prof_{};",
+                            actor.node_id.0
+                        );
+                    }
+                }
+            }
+        }
         self.out.push('\n');
     }
 
@@ -455,6 +876,11 @@ impl<'a> CodegenCtx<'a> {
         if self.lir.binds.is_empty() {
             return;
         }
+        if self.options.library {
+            self.emit_library_bind_ports();
+            self.emit_apply_pending_rebinds();
+            return;
+        }
 
         struct BindAdapterInfo {
             name: String,
@@ -463,6 +889,10 @@ impl<'a> CodegenCtx<'a> {
             chan_id: u16,
             rate_hz: f64,
             transport: String,
+            version: Option<u16>,
+            // tcp only: listen() backlog and reconnect interval (ms)
+            backlog: i64,
+            reconnect_ms: i64,
         }
 
         struct ShmAdapterInfo {
@@ -493,7 +923,7 @@ impl<'a> CodegenCtx<'a> {
             // Transport guard
             let is_shm = bind.transport == "shm";
             match bind.transport.as_str() {
-                "udp" | "unix_dgram" | "shm" => {}
+                "udp" | "unix_dgram" | "tcp" | "shm" => {}
                 other => {
                     diags.push(
                         Diagnostic::new(
@@ -631,6 +1061,9 @@ impl<'a> CodegenCtx<'a> {
                     chan_id,
                     rate_hz,
                     transport: bind.transport.clone(),
+                    version: contract.version,
+                    backlog: Self::extract_named_int(&bind.args, "backlog").unwrap_or(1),
+                    reconnect_ms: Self::extract_named_int(&bind.args, "reconnect").unwrap_or(1000),
                 });
             }
         }
@@ -642,9 +1075,33 @@ impl<'a> CodegenCtx<'a> {
         if !adapters.is_empty() {
             self.out.push_str("// ── Bind I/O adapters (datagram) ──\n");
             for adapter in &adapters {
+                // Versioned binds stamp the PPKT contract_version field
+                let version_arg = adapter
+                    .version
+                    .map(|v| format!(", {}", v))
+                    .unwrap_or_default();
+                if adapter.transport == "tcp" {
+                    // Stream transport: OUT connects as a client, IN listens
+                    let _ = writeln!(
+                        self.out,
+                        "static pipit::TcpIoAdapter _bind_io_{}(\"{}\", {}, {}, {}, {:?}, &_bind_state_{}, {}, {}{});",
+                        adapter.name,
+                        escape_cpp_string(&adapter.name),
+                        adapter.is_out,
+                        adapter.ppkt_dtype,
+                        adapter.chan_id,
+                        adapter.rate_hz,
+                        adapter.name,
+                        adapter.backlog,
+                        adapter.reconnect_ms,
+                        version_arg,
+                    );
+                    self.lowered_binds.insert(adapter.name.clone());
+                    continue;
+                }
                 let _ = writeln!(
                     self.out,
-                    "static pipit::BindIoAdapter _bind_io_{}(\"{}\", {}, {}, {}, {:.1}, \"{}\", &_bind_state_{});",
+                    "static pipit::BindIoAdapter _bind_io_{}(\"{}\", {}, {}, {}, {:?}, \"{}\", &_bind_state_{}{});",
                     adapter.name,
                     escape_cpp_string(&adapter.name),
                     adapter.is_out,
@@ -653,6 +1110,7 @@ impl<'a> CodegenCtx<'a> {
                     adapter.rate_hz,
                     escape_cpp_string(&adapter.transport),
                     adapter.name,
+                    version_arg,
                 );
                 self.lowered_binds.insert(adapter.name.clone());
             }
@@ -683,7 +1141,7 @@ impl<'a> CodegenCtx<'a> {
 
                 let _ = writeln!(
                     self.out,
-                    "static pipit::shm::ShmIoAdapter _shm_io_{}(\"{}\", {}, {}, {:.1}, {}, {}, \"{}\", {}ULL, {}, {}, {}, &_bind_state_{});",
+                    "static pipit::shm::ShmIoAdapter _shm_io_{}(\"{}\", {}, {}, {:?}, {}, {}, \"{}\", {}ULL, {}, {}, {}, &_bind_state_{});",
                     adapter.name,
                     escape_cpp_string(&adapter.name),
                     adapter.is_out,
@@ -781,6 +1239,43 @@ impl<'a> CodegenCtx<'a> {
         self.out.push_str("}\n\n");
     }
 
+    // ── Phase 5c: Library bind ports (`--library`) ──────────────────────
+
+    /// Serve every bind in-process for a library build. The host is the
+    /// transport, so neither the transport guard nor an endpoint applies;
+    /// only the dtype must be known to type the port.
+    fn emit_library_bind_ports(&mut self) {
+        self.out.push_str("// ── Bind ports (library) ──\n");
+        for bind in &self.lir.binds {
+            let Some(contract) = &bind.contract else {
+                continue;
+            };
+            let Some((cpp_type, _)) = contract.dtype.and_then(library_bind_types) else {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagLevel::Warning,
+                        codegen_span(),
+                        format!(
+                            "bind '{}': dtype could not be inferred; no I/O adapter emitted",
+                            bind.name
+                        ),
+                    )
+                    .with_code(codes::W0711),
+                );
+                continue;
+            };
+            let _ = writeln!(
+                self.out,
+                "static pipit::LibraryBindPort<{}, {}> _bind_io_{};",
+                cpp_type,
+                library_port_capacity(self.lir, bind),
+                bind.name
+            );
+            self.lowered_binds.insert(bind.name.clone());
+        }
+        self.out.push('\n');
+    }
+
     // ── Phase 6: Task functions ─────────────────────────────────────────
 
     fn emit_task_functions(&mut self) {
@@ -802,18 +1297,36 @@ impl<'a> CodegenCtx<'a> {
         let Some(meta) = self.schedule.tasks.get(task_name) else {
             return;
         };
+        let single_thread = self.options.single_thread;
+        if single_thread {
+            // Next timer deadline, read by the cooperative loop in shell_main.
+            let _ = writeln!(self.out, "static int64_t _deadline_{} = 0;\n", task_name);
+        }
         let _ = writeln!(self.out, "void task_{}() {{", task_name);
         self.emit_task_prologue(task_name, meta, task_graph);
         self.emit_edge_buffer_declarations(task_name);
+        self.emit_scratch_declarations(task_name);
         let tick_hoisted_actors = self.emit_tick_hoisted_actor_declarations(
             task_name,
             task_graph,
             &meta.schedule,
             "    ",
         );
-        self.out
-            .push_str("    while (!_stop.load(std::memory_order_acquire)) {\n");
-        self.out.push_str("        _timer.wait();\n");
+        self.emit_startup_firings(task_name, &tick_hoisted_actors);
+        if single_thread {
+            // One tick per call; `continue` (drop policy) ends the tick.
+            self.out.push_str("    do {\n");
+            self.out.push_str("        _timer.wait();\n");
+            let _ = writeln!(
+                self.out,
+                "        _deadline_{} = _timer.deadline_ns();",
+                task_name
+            );
+        } else {
+            self.out
+                .push_str("    while (!_stop.load(std::memory_order_acquire)) {\n");
+            self.out.push_str("        _timer.wait();\n");
+        }
 
         let policy = self.emit_task_overrun_policy(task_name);
         let indent =
@@ -832,7 +1345,11 @@ impl<'a> CodegenCtx<'a> {
         if policy == "backlog" {
             self.out.push_str("        }\n");
         }
-        self.out.push_str("    }\n");
+        if single_thread {
+            self.out.push_str("    } while (false);\n");
+        } else {
+            self.out.push_str("    }\n");
+        }
         self.out.push_str("}\n\n");
     }
 
@@ -855,15 +1372,113 @@ impl<'a> CodegenCtx<'a> {
                 if eb.is_feedback || eb.alias_of.is_some() {
                     continue;
                 }
-                let _ = writeln!(
-                    self.out,
-                    "    alignas(64) static {} {}[{}];",
-                    eb.cpp_type, eb.var_name, eb.tokens
+                if self.options.release {
+                    let _ = writeln!(
+                        self.out,
+                        "    alignas(64) static {} {}[{}];",
+                        eb.cpp_type, eb.var_name, eb.tokens
+                    );
+                } else {
+                    // Debug: trailing guard tokens catch writes past the end.
+                    let _ = writeln!(
+                        self.out,
+                        "    alignas(64) static {} {}[{} + pipit::EDGE_GUARD_TOKENS];",
+                        eb.cpp_type, eb.var_name, eb.tokens
+                    );
+                    let _ = writeln!(self.out, "    pipit::edge_guard_fill({});", eb.var_name);
+                }
+                self.record_allocation(
+                    Some(task_name),
+                    AllocationKind::Edge,
+                    eb.var_name.clone(),
+                    eb.cpp_type,
+                    eb.tokens as u64,
+                    0,
                 );
             }
         }
     }
 
+    /// Emit one zero-initialized static array per actor node declaring
+    /// `SCRATCH()`, so state persists across firings but not across instances.
+    fn emit_scratch_declarations(&mut self, task_name: &str) {
+        let Some(lir_task) = self.lir_task(task_name) else {
+            return;
+        };
+        let subgraphs: Vec<&LirSubgraph> = match &lir_task.body {
+            LirTaskBody::Pipeline(sg) => vec![sg],
+            LirTaskBody::Modal(modal) => {
+                let mut sgs = vec![&modal.control];
+                sgs.extend(modal.modes.iter().map(|(_, sg)| sg));
+                sgs
+            }
+        };
+        for sg in subgraphs {
+            for group in &sg.firings {
+                let firings: Vec<&LirFiring> = match group {
+                    LirFiringGroup::Single(f) => vec![f],
+                    LirFiringGroup::Fused(chain) => chain.body.iter().collect(),
+                };
+                for firing in firings {
+                    let LirFiringKind::Actor(actor) = &firing.kind else {
+                        continue;
+                    };
+                    let Some(LirActorArg::Scratch {
+                        var_name,
+                        cpp_type,
+                        elements,
+                    }) = actor.params.last()
+                    else {
+                        continue;
+                    };
+                    let _ = writeln!(
+                        self.out,
+                        "    static {} {}[{}] = {{}};",
+                        cpp_type, var_name, elements
+                    );
+                    self.record_allocation(
+                        Some(task_name),
+                        AllocationKind::Scratch,
+                        var_name.clone(),
+                        cpp_type,
+                        *elements as u64,
+                        0,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Fire the constant `PURE` prefix of each subgraph once, before the tick
+    /// loop; its output buffers keep their values for every later tick.
+    fn emit_startup_firings(&mut self, task_name: &str, tick_hoisted: &HashMap<NodeId, String>) {
+        let Some(lir_task) = self.lir_task(task_name) else {
+            return;
+        };
+        let subgraphs: Vec<&LirSubgraph> = match &lir_task.body {
+            LirTaskBody::Pipeline(sg) => vec![sg],
+            LirTaskBody::Modal(modal) => {
+                let mut sgs = vec![&modal.control];
+                sgs.extend(modal.modes.iter().map(|(_, sg)| sg));
+                sgs
+            }
+        };
+        for sg in subgraphs {
+            if sg.startup_nodes.is_empty() {
+                continue;
+            }
+            self.out
+                .push_str("    // constant PURE prefix: evaluated once, reused every tick\n");
+            for group in &sg.firings {
+                if let LirFiringGroup::Single(firing) = group {
+                    if is_startup_firing(sg, firing) {
+                        self.emit_lir_single_firing(task_name, firing, "    ", tick_hoisted);
+                    }
+                }
+            }
+        }
+    }
+
     fn emit_tick_hoisted_actor_declarations(
         &mut self,
         task_name: &str,
@@ -874,16 +1489,25 @@ impl<'a> CodegenCtx<'a> {
         // LIR path: emit tick-level hoisted declarations from pre-resolved LIR data
         if let Some(lir_task) = self.lir_task(task_name) {
             let hoisted_list = collect_lir_tick_hoistable_actors(&lir_task.body);
+            let storage = if self.options.single_thread {
+                "static "
+            } else {
+                ""
+            };
             let mut hoisted = HashMap::new();
             for (var_name, cpp_name, params) in &hoisted_list {
                 let params_str = format_lir_actor_args(params);
                 if params_str.is_empty() {
-                    let _ = writeln!(self.out, "{}auto {} = {}{{}};", indent, var_name, cpp_name);
+                    let _ = writeln!(
+                        self.out,
+                        "{}{}auto {} = {}{{}};",
+                        indent, storage, var_name, cpp_name
+                    );
                 } else {
                     let _ = writeln!(
                         self.out,
-                        "{}auto {} = {}{{{}}};",
-                        indent, var_name, cpp_name, params_str
+                        "{}{}auto {} = {}{{{}}};",
+                        indent, storage, var_name, cpp_name, params_str
                     );
                 }
                 if let Some(id_str) = var_name.strip_prefix("_actor_") {
@@ -899,10 +1523,17 @@ impl<'a> CodegenCtx<'a> {
     }
 
     fn emit_task_prologue(&mut self, task_name: &str, meta: &TaskMeta, task_graph: &TaskGraph) {
-        // Wait for all task threads to be created before starting timer.
-        self.out.push_str(
-            "    while (!_start.load(std::memory_order_acquire)) { std::this_thread::yield(); }\n",
-        );
+        // Single-thread runtime: the task function runs one tick per call, so
+        // its state is function-static and initialized on the first call.
+        let storage = if self.options.single_thread {
+            "static "
+        } else {
+            // Wait for all task threads to be created before starting timer.
+            self.out.push_str(
+                "    while (!_start.load(std::memory_order_acquire)) { std::this_thread::yield(); }\n",
+            );
+            ""
+        };
 
         // Timer (measure_latency enabled only when stats are active;
         // spin_ns from `set timer_spin`, default 10us; `auto` = adaptive).
@@ -910,23 +1541,30 @@ impl<'a> CodegenCtx<'a> {
             LirTimerSpin::Fixed(ns) => ns,
             LirTimerSpin::Adaptive => -1,
         };
+        let epoch = if self.lir_task(task_name).is_some_and(|t| t.tick_aligned) {
+            ", _tick_epoch()"
+        } else {
+            ""
+        };
         let _ = writeln!(
             self.out,
-            "    pipit::Timer _timer({:.1}, _stats, {});",
+            "    {}pipit::Timer _timer({:?}, _stats, {}{});",
+            storage,
             meta.freq_hz / meta.k_factor as f64,
-            spin_ns
+            spin_ns,
+            epoch
         );
         let _ = writeln!(
             self.out,
-            "    pipit::detail::set_actor_task_rate_hz({:.1});",
+            "    pipit::detail::set_actor_task_rate_hz({:?});",
             meta.freq_hz
         );
-        self.out.push_str("    uint64_t _iter_idx = 0;\n");
+        let _ = writeln!(self.out, "    {}uint64_t _iter_idx = 0;", storage);
 
         // Feedback back-edge buffers (persist across K-loop iterations).
         self.emit_feedback_buffers(task_name, task_graph, &meta.schedule);
         if matches!(&meta.schedule, TaskSchedule::Modal { .. }) {
-            self.out.push_str("    int32_t _active_mode = -1;\n");
+            let _ = writeln!(self.out, "    {}int32_t _active_mode = -1;", storage);
         }
     }
 
@@ -977,7 +1615,11 @@ impl<'a> CodegenCtx<'a> {
         for group in &sub.firings {
             let firing = match group {
                 LirFiringGroup::Single(f) => f,
-                LirFiringGroup::Fused(chain) => match chain.body.first() {
+                LirFiringGroup::Fused(chain) => match chain
+                    .body
+                    .iter()
+                    .find(|f| matches!(f.kind, LirFiringKind::Actor(_)))
+                {
                     Some(f) => f,
                     None => continue,
                 },
@@ -1011,6 +1653,9 @@ impl<'a> CodegenCtx<'a> {
         } else {
             "        "
         };
+        if self.options.single_thread {
+            self.emit_cooperative_iteration_guard(task_name, indent);
+        }
         // Per-iteration rebind apply (spec §5.11: MUST apply at iteration boundary)
         if !self.lir.binds.is_empty() {
             let _ = writeln!(self.out, "{}_apply_pending_rebinds();", indent);
@@ -1194,11 +1839,24 @@ impl<'a> CodegenCtx<'a> {
         _task_schedule: &TaskSchedule,
     ) {
         if let Some(lir_task) = self.lir_task(task_name) {
+            let storage = if self.options.single_thread {
+                "static "
+            } else {
+                ""
+            };
             for fb in &lir_task.feedback_buffers {
                 let _ = writeln!(
                     self.out,
-                    "    {} {}[{}] = {{{}}};",
-                    fb.cpp_type, fb.var_name, fb.tokens, fb.init_val
+                    "    {}{} {}[{}] = {{{}}};",
+                    storage, fb.cpp_type, fb.var_name, fb.tokens, fb.init_val
+                );
+                self.record_allocation(
+                    Some(task_name),
+                    AllocationKind::Feedback,
+                    fb.var_name.clone(),
+                    fb.cpp_type,
+                    fb.tokens as u64,
+                    0,
                 );
             }
         }
@@ -1221,9 +1879,18 @@ impl<'a> CodegenCtx<'a> {
     // ── Phase 7: main() ─────────────────────────────────────────────────
 
     fn emit_main(&mut self) {
-        let lir = self.lir;
-
         self.out.push_str("int main(int argc, char* argv[]) {\n");
+        self.emit_program_desc();
+        self.out
+            .push_str("    return pipit::shell_main(argc, argv, _desc);\n");
+        self.out.push_str("}\n");
+    }
+
+    /// Emit the descriptor tables and a local `pipit::ProgramDesc _desc`
+    /// (function-body indentation), shared by `main()` and the library
+    /// `_make_program_desc()`.
+    fn emit_program_desc(&mut self) {
+        let lir = self.lir;
 
         // Param descriptors
         if !lir.params.is_empty() {
@@ -1232,11 +1899,19 @@ impl<'a> CodegenCtx<'a> {
             let mut sorted_params: Vec<&_> = lir.params.iter().collect();
             sorted_params.sort_by_key(|p| &p.name);
             for p in &sorted_params {
-                let _ = writeln!(
-                    self.out,
-                    "        {{\"{}\", [](const char* v) -> bool {{ try {{ _param_{}_write.store({}(v), std::memory_order_release); return true; }} catch (...) {{ return false; }} }}}},",
-                    p.name, p.name, p.cli_converter
-                );
+                if self.options.no_exceptions {
+                    let _ = writeln!(
+                        self.out,
+                        "        {{\"{}\", [](const char* v) -> bool {{ {} _v{{}}; if (!pipit::parse_param(v, &_v)) return false; _param_{}_write.store(_v, std::memory_order_release); return true; }}}},",
+                        p.name, p.cpp_type, p.name
+                    );
+                } else {
+                    let _ = writeln!(
+                        self.out,
+                        "        {{\"{}\", [](const char* v) -> bool {{ try {{ _param_{}_write.store({}(v), std::memory_order_release); return true; }} catch (...) {{ return false; }} }}}},",
+                        p.name, p.name, p.cli_converter
+                    );
+                }
             }
             self.out.push_str("    };\n");
         }
@@ -1245,28 +1920,58 @@ impl<'a> CodegenCtx<'a> {
         self.out
             .push_str("    static const pipit::TaskDesc _task_descs[] = {\n");
         for task in &lir.tasks {
-            let _ = writeln!(
-                self.out,
-                "        {{\"{}\", task_{}, &_stats_{}}},",
-                task.name, task.name, task.name
-            );
+            if self.options.single_thread {
+                let _ = writeln!(
+                    self.out,
+                    "        {{\"{}\", task_{}, &_stats_{}, &_deadline_{}}},",
+                    task.name, task.name, task.name, task.name
+                );
+            } else if let Some(cpu) = task.cpu {
+                // `set affinity`: the runtime pins this task's thread.
+                let _ = writeln!(
+                    self.out,
+                    "        {{\"{}\", task_{}, &_stats_{}, nullptr, {}}},",
+                    task.name, task.name, task.name, cpu
+                );
+            } else {
+                let _ = writeln!(
+                    self.out,
+                    "        {{\"{}\", task_{}, &_stats_{}}},",
+                    task.name, task.name, task.name
+                );
+            }
         }
         self.out.push_str("    };\n");
 
         // Buffer descriptors (skip dead ring buffers: bind-only OUT with no readers)
-        let active_bufs: Vec<&_> = lir
+        let coalesced = self.coalesced_elements();
+        let mut active_bufs: Vec<(&str, &str, u32)> = lir
             .inter_task_buffers
             .iter()
             .filter(|b| !(b.skip_writes && b.reader_tasks.is_empty()))
+            .filter(|b| !coalesced.contains(b.name.as_str()))
+            .map(|b| (b.name.as_str(), b.cpp_type, b.capacity_tokens))
             .collect();
+        if self.options.reuse_family_buffers {
+            active_bufs.extend(
+                lir.coalesced_families
+                    .iter()
+                    .map(|f| (f.family_name.as_str(), f.cpp_type, f.capacity_tokens)),
+            );
+        }
         if !active_bufs.is_empty() {
             self.out
                 .push_str("    static const pipit::BufferStatsDesc _buffer_descs[] = {\n");
-            for buf in &active_bufs {
+            for (name, cpp_type, capacity) in &active_bufs {
+                let peak = if self.options.release {
+                    "nullptr, 0".to_string()
+                } else {
+                    format!("&_peak_{}, {}", name, capacity)
+                };
                 let _ = writeln!(
                     self.out,
-                    "        {{\"{}\", []() -> size_t {{ return _ringbuf_{}.available(); }}, sizeof({})}},",
-                    buf.name, buf.name, buf.cpp_type
+                    "        {{\"{}\", []() -> size_t {{ return _ringbuf_{}.available(); }}, sizeof({}), {}, &_bufstats_{}}},",
+                    name, name, cpp_type, peak, name
                 );
             }
             self.out.push_str("    };\n");
@@ -1336,7 +2041,7 @@ impl<'a> CodegenCtx<'a> {
                 let endpoint = self.effective_endpoint(bind);
                 let _ = writeln!(
                     self.out,
-                    "        {{\"{}\", \"{}\", \"{}\", {}, {}, {}, {:.1}, \"{}\", &_bind_state_{}}},",
+                    "        {{\"{}\", \"{}\", \"{}\", {}, {}, {}, {:?}, \"{}\", &_bind_state_{}}},",
                     bind.stable_id,
                     bind.name,
                     dir,
@@ -1351,6 +2056,29 @@ impl<'a> CodegenCtx<'a> {
             self.out.push_str("    };\n");
         }
 
+        let mut profile_descs = Vec::new();
+        if self.options.profile_actors {
+            for task in &lir.tasks {
+                let mut seen = HashSet::new();
+                for actor in collect_lir_actor_firings(&task.body) {
+                    if seen.insert(actor.node_id) {
+                        profile_descs.push(format!(
+                            "        {{\"{}\", \"{}\", {}, &_prof_{}}},",
+                            task.name, actor.actor_name, actor.node_id.0, actor.node_id.0
+                        ));
+                    }
+                }
+            }
+        }
+        if !profile_descs.is_empty() {
+            self.out
+                .push_str("    static const pipit::ActorProfileDesc _actor_profile_descs[] = {\n");
+            for line in &profile_descs {
+                let _ = writeln!(self.out, "{}", line);
+            }
+            self.out.push_str("    };\n");
+        }
+
         // ProgramDesc initialization
         self.out.push_str("    pipit::ProgramDesc _desc{};\n");
         self.out.push_str(
@@ -1387,18 +2115,79 @@ impl<'a> CodegenCtx<'a> {
             self.out.push_str("    _desc.binds = _bind_descs;\n");
         }
 
+        if !profile_descs.is_empty() {
+            self.out
+                .push_str("    _desc.actor_profiles = _actor_profile_descs;\n");
+        }
+
         let policy = self.get_overrun_policy().to_string();
         let _ = writeln!(self.out, "    _desc.overrun_policy = \"{}\";", policy);
+        if self.options.single_thread {
+            self.out.push_str("    _desc.single_thread = true;\n");
+        }
         let _ = writeln!(
             self.out,
             "    _desc.mem_allocated = {};",
             lir.directives.mem_bytes
         );
         let _ = writeln!(self.out, "    _desc.mem_used = {};", lir.total_memory);
+    }
+
+    // ── Phase 7: Library entry points (`--library`) ─────────────────────
+
+    /// Replace `main()` with the C interface declared by `--emit header`:
+    /// `pipit_start()` / `pipit_stop()` drive the task threads through
+    /// `pipit::LibraryRuntime`, and each lowered bind gets an accessor
+    /// moving one frame through its `LibraryBindPort`.
+    fn emit_library_entry_points(&mut self) {
+        let lir = self.lir;
 
         self.out
-            .push_str("    return pipit::shell_main(argc, argv, _desc);\n");
-        self.out.push_str("}\n");
+            .push_str("static pipit::ProgramDesc _make_program_desc() {\n");
+        self.emit_program_desc();
+        self.out.push_str("    return _desc;\n}\n\n");
+        self.out
+            .push_str("static const pipit::ProgramDesc _program_desc = _make_program_desc();\n");
+        self.out
+            .push_str("static pipit::LibraryRuntime _library_runtime;\n\n");
+
+        self.out.push_str(&library_c_types(lir));
+        self.out.push_str(
+            "extern \"C\" int pipit_start(void) {\n    return _library_runtime.start(_program_desc) ? 0 : -1;\n}\n\n",
+        );
+        self.out.push_str(
+            "extern \"C\" int pipit_stop(void) {\n    return _library_runtime.stop(_program_desc);\n}\n",
+        );
+
+        for bind in &lir.binds {
+            if !self.lowered_binds.contains(&bind.name) {
+                continue;
+            }
+            let (Some(contract), Some(signature)) =
+                (&bind.contract, library_accessor_signature(bind))
+            else {
+                continue;
+            };
+            let Some((cpp_type, _)) = contract.dtype.and_then(library_bind_types) else {
+                continue;
+            };
+            let tokens = bind_frame_tokens(bind);
+            let body = match contract.direction {
+                BindDirection::Out => format!(
+                    "_bind_io_{}.pop(reinterpret_cast<{}*>(out), {})",
+                    bind.name, cpp_type, tokens
+                ),
+                BindDirection::In => format!(
+                    "_bind_io_{}.push(reinterpret_cast<const {}*>(in), {})",
+                    bind.name, cpp_type, tokens
+                ),
+            };
+            let _ = writeln!(
+                self.out,
+                "\nextern \"C\" {} {{\n    return {} ? 1 : 0;\n}}",
+                signature, body
+            );
+        }
     }
 
     // ── Helpers ─────────────────────────────────────────────────────────
@@ -1417,9 +2206,15 @@ impl<'a> CodegenCtx<'a> {
         indent: &str,
         tick_hoisted: &HashMap<NodeId, String>,
     ) {
-        // Emit firing groups
+        // Emit firing groups (startup firings already ran before the loop)
         for group in &lir_sg.firings {
             match group {
+                LirFiringGroup::Single(firing) if is_startup_firing(lir_sg, firing) => {}
+                LirFiringGroup::Single(firing)
+                    if self.options.openmp && is_parallel_firing(lir_sg, firing) =>
+                {
+                    self.emit_lir_parallel_firing(task_name, firing, indent, tick_hoisted);
+                }
                 LirFiringGroup::Single(firing) => {
                     self.emit_lir_single_firing(task_name, firing, indent, tick_hoisted);
                 }
@@ -1481,22 +2276,7 @@ impl<'a> CodegenCtx<'a> {
                 self.emit_lir_probe(probe, ind, 1);
             }
             LirFiringKind::BufferRead(io) => {
-                let use_recv = self
-                    .lir
-                    .binds
-                    .iter()
-                    .find(|b| b.name == io.buffer_name)
-                    .and_then(|b| {
-                        let is_in =
-                            b.contract.as_ref().map(|c| c.direction) == Some(BindDirection::In);
-                        if is_in && self.lowered_binds.contains(&b.name) {
-                            Some(())
-                        } else {
-                            None
-                        }
-                    })
-                    .is_some();
-                if use_recv {
+                if self.is_lowered_bind(&io.buffer_name, BindDirection::In) {
                     let prefix = if self.lowered_shm_binds.contains(&io.buffer_name) {
                         "_shm_io_"
                     } else {
@@ -1512,22 +2292,7 @@ impl<'a> CodegenCtx<'a> {
                 }
             }
             LirFiringKind::BufferWrite(io) => {
-                let use_send = self
-                    .lir
-                    .binds
-                    .iter()
-                    .find(|b| b.name == io.buffer_name)
-                    .and_then(|b| {
-                        let is_out =
-                            b.contract.as_ref().map(|c| c.direction) == Some(BindDirection::Out);
-                        if is_out && self.lowered_binds.contains(&b.name) {
-                            Some(())
-                        } else {
-                            None
-                        }
-                    })
-                    .is_some();
-                if use_send {
+                if self.is_lowered_bind(&io.buffer_name, BindDirection::Out) {
                     let prefix = if self.lowered_shm_binds.contains(&io.buffer_name) {
                         "_shm_io_"
                     } else {
@@ -1556,6 +2321,84 @@ impl<'a> CodegenCtx<'a> {
         }
     }
 
+    /// True if `name` is a bind of `direction` lowered to a transport
+    /// endpoint (`recv`/`send` instead of a plain ring buffer read/write).
+    fn is_lowered_bind(&self, name: &str, direction: BindDirection) -> bool {
+        self.lowered_binds.contains(name)
+            && self.lir.binds.iter().any(|b| {
+                b.name == name && b.contract.as_ref().map(|c| c.direction) == Some(direction)
+            })
+    }
+
+    /// Emit an eligible actor repetition loop as an OpenMP parallel for.
+    ///
+    /// `return` may not leave an OpenMP structured block, so failures are
+    /// OR-reduced into a flag and reported after the loop.
+    fn emit_lir_parallel_firing(
+        &mut self,
+        task_name: &str,
+        firing: &LirFiring,
+        indent: &str,
+        tick_hoisted: &HashMap<NodeId, String>,
+    ) {
+        let LirFiringKind::Actor(actor) = &firing.kind else {
+            return;
+        };
+        let mut hoisted_var = tick_hoisted.get(&actor.node_id).cloned();
+        if hoisted_var.is_none() {
+            if let Some(h) = &actor.hoisted {
+                self.emit_lir_hoisted_decl(h, indent);
+                hoisted_var = Some(h.var_name.clone());
+            }
+        }
+
+        let err_var = format!("_omp_err_{}", actor.node_id.0);
+        let _ = writeln!(self.out, "{}bool {} = false;", indent, err_var);
+        // Profile builds time the whole parallel loop as `repetition` firings.
+        if self.options.profile_actors {
+            let _ = writeln!(
+                self.out,
+                "{}const uint64_t _prof_t0_{} = pipit_now_ns();",
+                indent, actor.node_id.0
+            );
+        }
+        let _ = writeln!(
+            self.out,
+            "{}#pragma omp parallel for reduction(||: {})",
+            indent, err_var
+        );
+        let _ = writeln!(
+            self.out,
+            "{}for (int _r = 0; _r < {}; ++_r) {{",
+            indent, firing.repetition
+        );
+        let body_indent = self.indent_plus4(indent);
+        self.emit_edge_bounds_assert(actor, &body_indent, firing.repetition);
+        let call_expr = self.lir_actor_call_expr(
+            actor,
+            &body_indent,
+            firing.repetition,
+            hoisted_var.as_deref(),
+        );
+        let _ = writeln!(
+            self.out,
+            "{}if ({} != ACTOR_OK) {} = true;",
+            body_indent, call_expr, err_var
+        );
+        let _ = writeln!(self.out, "{}}}", indent);
+        if self.options.profile_actors {
+            let _ = writeln!(
+                self.out,
+                "{}_prof_{}.record(pipit_now_ns() - _prof_t0_{}, {});",
+                indent, actor.node_id.0, actor.node_id.0, firing.repetition
+            );
+        }
+        let _ = writeln!(self.out, "{}if ({}) {{", indent, err_var);
+        self.emit_actor_error_exit(task_name, &actor.actor_name, indent);
+        let _ = writeln!(self.out, "{}}}", indent);
+        self.emit_edge_guard_check(actor, indent);
+    }
+
     /// Emit a fused actor chain from LIR.
     fn emit_lir_fused_chain(
         &mut self,
@@ -1588,10 +2431,16 @@ impl<'a> CodegenCtx<'a> {
             self.emit_lir_hoisted_decl(hoisted, indent);
         }
 
-        // Emit fork comments before loop
+        // Emit fork comments and the anchor's block read before the loop
         for firing in &chain.body {
-            if let LirFiringKind::Fork(fork) = &firing.kind {
-                let _ = writeln!(self.out, "{}// fork: {} (zero-copy)", indent, fork.tap_name);
+            match &firing.kind {
+                LirFiringKind::Fork(fork) => {
+                    let _ = writeln!(self.out, "{}// fork: {} (zero-copy)", indent, fork.tap_name);
+                }
+                LirFiringKind::BufferRead(_) => {
+                    self.emit_lir_single_firing(task_name, firing, indent, tick_hoisted);
+                }
+                _ => {}
             }
         }
 
@@ -1616,7 +2465,7 @@ impl<'a> CodegenCtx<'a> {
                         .or_else(|| actor.hoisted.as_ref().map(|h| h.var_name.as_str()));
                     self.emit_lir_actor_call(task_name, actor, ind, chain.repetition, hoisted_var);
                 }
-                LirFiringKind::Fork(_) => {} // Already emitted above
+                LirFiringKind::Fork(_) | LirFiringKind::BufferRead(_) => {} // Emitted above
                 LirFiringKind::Probe(probe) => {
                     self.emit_lir_probe(probe, ind, chain.repetition);
                 }
@@ -1654,28 +2503,116 @@ impl<'a> CodegenCtx<'a> {
         rep: u32,
         hoisted_var: Option<&str>,
     ) {
-        let in_ptr = self.build_lir_input_ptr(actor, indent, rep);
-        let out_ptr = build_lir_output_ptr(actor, rep);
+        self.emit_edge_bounds_assert(actor, indent, rep);
+        let call_expr = self.lir_actor_call_expr(actor, indent, rep, hoisted_var);
+        if self.options.profile_actors {
+            let id = actor.node_id.0;
+            let _ = writeln!(
+                self.out,
+                "{}const uint64_t _prof_t0_{} = pipit_now_ns();",
+                indent, id
+            );
+            let _ = writeln!(
+                self.out,
+                "{}const int _prof_rc_{} = {};",
+                indent, id, call_expr
+            );
+            let _ = writeln!(
+                self.out,
+                "{}_prof_{}.record(pipit_now_ns() - _prof_t0_{});",
+                indent, id, id
+            );
+            let _ = writeln!(self.out, "{}if (_prof_rc_{} != ACTOR_OK) {{", indent, id);
+        } else {
+            let _ = writeln!(self.out, "{}if ({} != ACTOR_OK) {{", indent, call_expr);
+        }
+        self.emit_actor_error_exit(task_name, &actor.actor_name, indent);
+        let _ = writeln!(self.out, "{}}}", indent);
+        self.emit_edge_guard_check(actor, indent);
+    }
+
+    /// Debug builds: the declared edge buffer an actor firing writes, with
+    /// its token count, when that buffer carries a guard.
+    fn guarded_output<'b>(&self, actor: &'b LirActorFiring) -> Option<(&'b str, u32)> {
+        if actor.void_output {
+            return None;
+        }
+        let var = &actor.outputs.first()?.buffer_var;
+        let declared = *self.guarded_edges.get(var)?;
+        Some((var.as_str(), declared))
+    }
 
-        let call_expr = if let Some(var_name) = hoisted_var {
-            format!("{}.operator()({}, {})", var_name, in_ptr, out_ptr)
+    /// Debug builds: assert the firing's output window — `_r * stride +
+    /// out_count` in a repetition loop — fits the declared edge buffer.
+    fn emit_edge_bounds_assert(&mut self, actor: &LirActorFiring, indent: &str, rep: u32) {
+        let Some((var, declared)) = self.guarded_output(actor) else {
+            return;
+        };
+        let (_, out_count) = firing_token_counts(actor, rep);
+        let end = if rep > 1 {
+            format!("_r * {} + {}", lir_output_stride(actor, rep), out_count)
         } else {
-            let params = format_lir_actor_args(&actor.params);
-            if params.is_empty() {
-                format!("{}{{}}({}, {})", actor.cpp_name, in_ptr, out_ptr)
-            } else {
-                format!(
-                    "{}{{{}}}.operator()({}, {})",
-                    actor.cpp_name, params, in_ptr, out_ptr
-                )
-            }
+            out_count.to_string()
+        };
+        let _ = writeln!(
+            self.out,
+            "{}assert({} <= {} && \"edge buffer overflow: {}\");",
+            indent, end, declared, var
+        );
+    }
+
+    /// Debug builds: assert the output buffer's guard tokens survived the
+    /// firing.
+    fn emit_edge_guard_check(&mut self, actor: &LirActorFiring, indent: &str) {
+        let Some((var, _)) = self.guarded_output(actor) else {
+            return;
+        };
+        let _ = writeln!(
+            self.out,
+            "{}assert(pipit::edge_guard_intact({}) && \"edge buffer guard overwritten: {}\");",
+            indent, var, var
+        );
+    }
+
+    /// Build the call expression for one actor firing. May emit a local
+    /// input buffer declaration (multi-input actors) at `indent`.
+    fn lir_actor_call_expr(
+        &mut self,
+        actor: &LirActorFiring,
+        indent: &str,
+        rep: u32,
+        hoisted_var: Option<&str>,
+    ) -> String {
+        let in_ptr = self.build_lir_input_ptr(actor, indent, rep);
+        let out_ptr = build_lir_output_ptr(actor, rep);
+        let (in_count, out_count) = firing_token_counts(actor, rep);
+        let counts = format!("<{}, {}>", in_count, out_count);
+
+        let instance = match hoisted_var {
+            Some(var_name) => var_name.to_string(),
+            None => format!(
+                "{}{{{}}}",
+                actor.cpp_name,
+                format_lir_actor_args(&actor.params)
+            ),
         };
+        if actor.inline {
+            format!(
+                "pipit::fire_inline{}({}, {}, {})",
+                counts, instance, in_ptr, out_ptr
+            )
+        } else {
+            format!("{}.operator(){}({}, {})", instance, counts, in_ptr, out_ptr)
+        }
+    }
 
-        let _ = writeln!(self.out, "{}if ({} != ACTOR_OK) {{", indent, call_expr);
+    /// Emit the body of an `ACTOR_ERROR` branch: report, stop the program,
+    /// and leave the task tick. `indent` is that of the enclosing `if`.
+    fn emit_actor_error_exit(&mut self, task_name: &str, actor_name: &str, indent: &str) {
         let _ = writeln!(
             self.out,
             "{}    fprintf(stderr, \"runtime error: actor '{}' in task '{}' returned ACTOR_ERROR\\n\");",
-            indent, actor.actor_name, task_name
+            indent, actor_name, task_name
         );
         let _ = writeln!(
             self.out,
@@ -1688,7 +2625,6 @@ impl<'a> CodegenCtx<'a> {
             indent
         );
         let _ = writeln!(self.out, "{}    return;", indent);
-        let _ = writeln!(self.out, "{}}}", indent);
     }
 
     /// Build input pointer expression from LIR actor data.
@@ -1775,12 +2711,27 @@ impl<'a> CodegenCtx<'a> {
         if io.reader_count == 1 {
             let _ = writeln!(self.out, "{}// SPSC: single-reader fast path", indent);
         }
+        if self.options.single_thread {
+            let op = format!(
+                "_ringbuf_{}.read({}, {}, {})",
+                io.buffer_name, reader_idx, io.edge_var, io.total_tokens
+            );
+            let fail_count = Self::buffer_fail_count(&io.buffer_name, "read");
+            self.emit_cooperative_ring_op(&op, &fail_count, indent);
+            return;
+        }
         let _ = writeln!(self.out, "{}while (true) {{", indent);
         let _ = writeln!(
             self.out,
             "{}    if (_ringbuf_{}.read({}, {}, {})) break;",
             indent, io.buffer_name, reader_idx, io.edge_var, io.total_tokens
         );
+        let _ = writeln!(
+            self.out,
+            "{}    {}",
+            indent,
+            Self::buffer_fail_count(&io.buffer_name, "read")
+        );
         let _ = writeln!(
             self.out,
             "{}    if (_stop.load(std::memory_order_acquire)) return;",
@@ -1828,12 +2779,28 @@ impl<'a> CodegenCtx<'a> {
         if io.reader_count == 1 {
             let _ = writeln!(self.out, "{}// SPSC: single-reader fast path", indent);
         }
+        if self.options.single_thread {
+            let op = format!(
+                "_ringbuf_{}.write({}, {})",
+                io.buffer_name, io.edge_var, io.total_tokens
+            );
+            let fail_count = Self::buffer_fail_count(&io.buffer_name, "write");
+            self.emit_cooperative_ring_op(&op, &fail_count, indent);
+            self.emit_buffer_peak_update(&io.buffer_name, indent);
+            return;
+        }
         let _ = writeln!(self.out, "{}while (true) {{", indent);
         let _ = writeln!(
             self.out,
             "{}    if (_ringbuf_{}.write({}, {})) break;",
             indent, io.buffer_name, io.edge_var, io.total_tokens
         );
+        let _ = writeln!(
+            self.out,
+            "{}    {}",
+            indent,
+            Self::buffer_fail_count(&io.buffer_name, "write")
+        );
         let _ = writeln!(
             self.out,
             "{}    if (_stop.load(std::memory_order_acquire)) return;",
@@ -1873,137 +2840,393 @@ impl<'a> CodegenCtx<'a> {
         let _ = writeln!(self.out, "{}        return;", indent);
         let _ = writeln!(self.out, "{}    }}", indent);
         let _ = writeln!(self.out, "{}}}", indent);
+        self.emit_buffer_peak_update(&io.buffer_name, indent);
     }
 
     /// Emit gather read: N sequential spin-wait reads from element ring buffers
-    /// into a contiguous output edge variable.
+    /// into a contiguous output edge variable. With family buffer reuse, the
+    /// whole frame is read from the coalesced family ring buffer at once.
     fn emit_lir_gather_read(&mut self, task_name: &str, io: &LirGatherIo, indent: &str) {
-        let timeout_ms = self.lir.directives.wait_timeout_ms;
         let _ = writeln!(
             self.out,
             "{}// gather @{}[*] — {} elements",
             indent, io.family_name, io.element_count
         );
-        for elem in &io.elements {
-            if elem.reader_count == 1 {
-                let _ = writeln!(self.out, "{}// SPSC: single-reader fast path", indent);
-            }
-            let _ = writeln!(self.out, "{}while (true) {{", indent);
-            let _ = writeln!(
-                self.out,
-                "{}    if (_ringbuf_{}.read({}, {} + {}, {})) break;",
+        if let (true, Some(reader_idx)) =
+            (self.options.reuse_family_buffers, io.coalesced_reader_idx)
+        {
+            let reader_count = io.elements.first().map(|e| e.reader_count).unwrap_or(1);
+            let wait_var = io
+                .elements
+                .first()
+                .map(|e| format!("_wr_{}_{}", e.src_node_id.0, e.peer_node_id.0))
+                .unwrap_or_else(|| "_wr_family".to_string());
+            let _ = writeln!(self.out, "{}// family buffer reuse: coalesced read", indent);
+            self.emit_ring_read_loop(
+                task_name,
+                &io.family_name,
+                reader_idx,
+                reader_count,
+                &io.output_edge_var,
+                io.total_output_tokens,
+                &wait_var,
                 indent,
-                elem.buffer_name,
-                elem.reader_idx,
-                io.output_edge_var,
-                elem.offset,
-                elem.tokens
-            );
-            let _ = writeln!(
-                self.out,
-                "{}    if (_stop.load(std::memory_order_acquire)) return;",
-                indent
-            );
-            let wr_var = format!("_wr_{}_{}", elem.src_node_id.0, elem.peer_node_id.0);
-            let _ = writeln!(
-                self.out,
-                "{}    auto {} = _ringbuf_{}.wait_readable({}, {}, _stop, std::chrono::milliseconds({}));",
-                indent, wr_var, elem.buffer_name, elem.reader_idx, elem.tokens, timeout_ms
-            );
-            let _ = writeln!(
-                self.out,
-                "{}    if ({} == pipit::WaitResult::stopped) return;",
-                indent, wr_var
-            );
-            let _ = writeln!(
-                self.out,
-                "{}    if ({} == pipit::WaitResult::timeout) {{",
-                indent, wr_var
-            );
-            let _ = writeln!(
-                self.out,
-                "{}        std::fprintf(stderr, \"runtime error: task '{}' timeout waiting for {} token(s) from shared buffer '{}'\\n\");",
-                indent, task_name, elem.tokens, elem.buffer_name
             );
-            let _ = writeln!(
-                self.out,
-                "{}        _exit_code.store(1, std::memory_order_release);",
-                indent
-            );
-            let _ = writeln!(
-                self.out,
-                "{}        _stop.store(true, std::memory_order_release);",
-                indent
+            return;
+        }
+        for elem in &io.elements {
+            self.emit_ring_read_loop(
+                task_name,
+                &elem.buffer_name,
+                elem.reader_idx,
+                elem.reader_count,
+                &format!("{} + {}", io.output_edge_var, elem.offset),
+                elem.tokens,
+                &format!("_wr_{}_{}", elem.src_node_id.0, elem.peer_node_id.0),
+                indent,
             );
-            let _ = writeln!(self.out, "{}        return;", indent);
-            let _ = writeln!(self.out, "{}    }}", indent);
-            let _ = writeln!(self.out, "{}}}", indent);
         }
     }
 
-    /// Emit scatter write: N sequential spin-wait writes of slices from a contiguous
-    /// input edge variable to element ring buffers.
-    fn emit_lir_scatter_write(&mut self, task_name: &str, io: &LirScatterIo, indent: &str) {
+    /// Emit one spin-wait read loop on `_ringbuf_<buffer>` into `dst`.
+    #[allow(clippy::too_many_arguments)]
+    fn emit_ring_read_loop(
+        &mut self,
+        task_name: &str,
+        buffer: &str,
+        reader_idx: usize,
+        reader_count: usize,
+        dst: &str,
+        tokens: u32,
+        wr_var: &str,
+        indent: &str,
+    ) {
         let timeout_ms = self.lir.directives.wait_timeout_ms;
+        if reader_count == 1 {
+            let _ = writeln!(self.out, "{}// SPSC: single-reader fast path", indent);
+        }
+        if self.options.single_thread {
+            let op = format!(
+                "_ringbuf_{}.read({}, {}, {})",
+                buffer, reader_idx, dst, tokens
+            );
+            let fail_count = Self::buffer_fail_count(buffer, "read");
+            self.emit_cooperative_ring_op(&op, &fail_count, indent);
+            return;
+        }
+        let _ = writeln!(self.out, "{}while (true) {{", indent);
         let _ = writeln!(
             self.out,
-            "{}// scatter ->{}[*] — {} elements",
-            indent, io.family_name, io.element_count
+            "{}    if (_ringbuf_{}.read({}, {}, {})) break;",
+            indent, buffer, reader_idx, dst, tokens
         );
-        for elem in &io.elements {
-            if elem.skip {
-                continue;
-            }
-            if elem.reader_count == 1 {
-                let _ = writeln!(self.out, "{}// SPSC: single-reader fast path", indent);
-            }
-            let _ = writeln!(self.out, "{}while (true) {{", indent);
-            let _ = writeln!(
-                self.out,
-                "{}    if (_ringbuf_{}.write({} + {}, {})) break;",
-                indent, elem.buffer_name, io.input_edge_var, elem.offset, elem.tokens
-            );
-            let _ = writeln!(
-                self.out,
-                "{}    if (_stop.load(std::memory_order_acquire)) return;",
-                indent
-            );
-            let ww_var = format!("_ww_{}_{}", elem.src_node_id.0, elem.peer_node_id.0);
-            let _ = writeln!(
-                self.out,
-                "{}    auto {} = _ringbuf_{}.wait_writable({}, _stop, std::chrono::milliseconds({}));",
-                indent, ww_var, elem.buffer_name, elem.tokens, timeout_ms
-            );
-            let _ = writeln!(
-                self.out,
-                "{}    if ({} == pipit::WaitResult::stopped) return;",
-                indent, ww_var
-            );
-            let _ = writeln!(
-                self.out,
-                "{}    if ({} == pipit::WaitResult::timeout) {{",
-                indent, ww_var
-            );
-            let _ = writeln!(
-                self.out,
-                "{}        std::fprintf(stderr, \"runtime error: task '{}' timeout waiting to write {} token(s) to shared buffer '{}'\\n\");",
-                indent, task_name, elem.tokens, elem.buffer_name
-            );
-            let _ = writeln!(
-                self.out,
-                "{}        _exit_code.store(1, std::memory_order_release);",
-                indent
-            );
-            let _ = writeln!(
-                self.out,
-                "{}        _stop.store(true, std::memory_order_release);",
-                indent
-            );
-            let _ = writeln!(self.out, "{}        return;", indent);
-            let _ = writeln!(self.out, "{}    }}", indent);
-            let _ = writeln!(self.out, "{}}}", indent);
-        }
-    }
+        let _ = writeln!(
+            self.out,
+            "{}    {}",
+            indent,
+            Self::buffer_fail_count(buffer, "read")
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    if (_stop.load(std::memory_order_acquire)) return;",
+            indent
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    auto {} = _ringbuf_{}.wait_readable({}, {}, _stop, std::chrono::milliseconds({}));",
+            indent, wr_var, buffer, reader_idx, tokens, timeout_ms
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    if ({} == pipit::WaitResult::stopped) return;",
+            indent, wr_var
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    if ({} == pipit::WaitResult::timeout) {{",
+            indent, wr_var
+        );
+        let _ = writeln!(
+            self.out,
+            "{}        std::fprintf(stderr, \"runtime error: task '{}' timeout waiting for {} token(s) from shared buffer '{}'\\n\");",
+            indent, task_name, tokens, buffer
+        );
+        let _ = writeln!(
+            self.out,
+            "{}        _exit_code.store(1, std::memory_order_release);",
+            indent
+        );
+        let _ = writeln!(
+            self.out,
+            "{}        _stop.store(true, std::memory_order_release);",
+            indent
+        );
+        let _ = writeln!(self.out, "{}        return;", indent);
+        let _ = writeln!(self.out, "{}    }}", indent);
+        let _ = writeln!(self.out, "{}}}", indent);
+    }
+
+    /// Emit scatter write: N sequential spin-wait writes of slices from a contiguous
+    /// input edge variable to element ring buffers. With family buffer reuse, the
+    /// whole frame is written to the coalesced family ring buffer at once.
+    fn emit_lir_scatter_write(&mut self, task_name: &str, io: &LirScatterIo, indent: &str) {
+        let _ = writeln!(
+            self.out,
+            "{}// scatter ->{}[*] — {} elements",
+            indent, io.family_name, io.element_count
+        );
+        if self.options.reuse_family_buffers && io.coalesced {
+            let reader_count = io.elements.first().map(|e| e.reader_count).unwrap_or(1);
+            let wait_var = io
+                .elements
+                .first()
+                .map(|e| format!("_ww_{}_{}", e.src_node_id.0, e.peer_node_id.0))
+                .unwrap_or_else(|| "_ww_family".to_string());
+            let _ = writeln!(
+                self.out,
+                "{}// family buffer reuse: coalesced write",
+                indent
+            );
+            self.emit_ring_write_loop(
+                task_name,
+                &io.family_name,
+                reader_count,
+                &io.input_edge_var,
+                io.total_input_tokens,
+                &wait_var,
+                indent,
+            );
+            return;
+        }
+        for elem in &io.elements {
+            if elem.skip {
+                continue;
+            }
+            self.emit_ring_write_loop(
+                task_name,
+                &elem.buffer_name,
+                elem.reader_count,
+                &format!("{} + {}", io.input_edge_var, elem.offset),
+                elem.tokens,
+                &format!("_ww_{}_{}", elem.src_node_id.0, elem.peer_node_id.0),
+                indent,
+            );
+        }
+    }
+
+    /// Emit one spin-wait write loop from `src` to `_ringbuf_<buffer>`.
+    #[allow(clippy::too_many_arguments)]
+    fn emit_ring_write_loop(
+        &mut self,
+        task_name: &str,
+        buffer: &str,
+        reader_count: usize,
+        src: &str,
+        tokens: u32,
+        ww_var: &str,
+        indent: &str,
+    ) {
+        let timeout_ms = self.lir.directives.wait_timeout_ms;
+        if reader_count == 1 {
+            let _ = writeln!(self.out, "{}// SPSC: single-reader fast path", indent);
+        }
+        if self.options.single_thread {
+            let op = format!("_ringbuf_{}.write({}, {})", buffer, src, tokens);
+            let fail_count = Self::buffer_fail_count(buffer, "write");
+            self.emit_cooperative_ring_op(&op, &fail_count, indent);
+            self.emit_buffer_peak_update(buffer, indent);
+            return;
+        }
+        let _ = writeln!(self.out, "{}while (true) {{", indent);
+        let _ = writeln!(
+            self.out,
+            "{}    if (_ringbuf_{}.write({}, {})) break;",
+            indent, buffer, src, tokens
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    {}",
+            indent,
+            Self::buffer_fail_count(buffer, "write")
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    if (_stop.load(std::memory_order_acquire)) return;",
+            indent
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    auto {} = _ringbuf_{}.wait_writable({}, _stop, std::chrono::milliseconds({}));",
+            indent, ww_var, buffer, tokens, timeout_ms
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    if ({} == pipit::WaitResult::stopped) return;",
+            indent, ww_var
+        );
+        let _ = writeln!(
+            self.out,
+            "{}    if ({} == pipit::WaitResult::timeout) {{",
+            indent, ww_var
+        );
+        let _ = writeln!(
+            self.out,
+            "{}        std::fprintf(stderr, \"runtime error: task '{}' timeout waiting to write {} token(s) to shared buffer '{}'\\n\");",
+            indent, task_name, tokens, buffer
+        );
+        let _ = writeln!(
+            self.out,
+            "{}        _exit_code.store(1, std::memory_order_release);",
+            indent
+        );
+        let _ = writeln!(
+            self.out,
+            "{}        _stop.store(true, std::memory_order_release);",
+            indent
+        );
+        let _ = writeln!(self.out, "{}        return;", indent);
+        let _ = writeln!(self.out, "{}    }}", indent);
+        let _ = writeln!(self.out, "{}}}", indent);
+        self.emit_buffer_peak_update(buffer, indent);
+    }
+
+    /// Emit the single-thread iteration guard: every shared ring buffer read
+    /// and write of one iteration is checked before the first firing, and the
+    /// tick ends if any cannot complete yet. Bailing out mid-iteration would
+    /// drop the output of firings that already consumed their input, and
+    /// leave gather/scatter element rings out of step with each other.
+    ///
+    /// Modal tasks check the ring ops of every mode, since the active mode
+    /// is only known after the ctrl token has been consumed.
+    fn emit_cooperative_iteration_guard(&mut self, task_name: &str, indent: &str) {
+        let Some(lir_task) = self.lir_task(task_name) else {
+            return;
+        };
+        let mut demands = Vec::new();
+        match &lir_task.body {
+            LirTaskBody::Pipeline(sg) => self.collect_ring_demands(sg, &mut demands),
+            LirTaskBody::Modal(modal) => {
+                self.collect_ring_demands(&modal.control, &mut demands);
+                if let LirCtrlSource::RingBuffer { name, reader_idx } = &modal.ctrl_source {
+                    add_ring_demand(&mut demands, name, Some(*reader_idx), 1);
+                }
+                // Only one mode runs per iteration: take the largest demand.
+                let mut mode_max: Vec<RingDemand> = Vec::new();
+                for (_, sg) in &modal.modes {
+                    let mut mode_demands = Vec::new();
+                    self.collect_ring_demands(sg, &mut mode_demands);
+                    for d in mode_demands {
+                        match mode_max
+                            .iter_mut()
+                            .find(|m| m.buffer == d.buffer && m.reader_idx == d.reader_idx)
+                        {
+                            Some(m) => m.tokens = m.tokens.max(d.tokens),
+                            None => mode_max.push(d),
+                        }
+                    }
+                }
+                for d in mode_max {
+                    add_ring_demand(&mut demands, &d.buffer, d.reader_idx, d.tokens);
+                }
+            }
+        }
+        if demands.is_empty() {
+            return;
+        }
+        let _ = writeln!(
+            self.out,
+            "{}// single-thread: run the iteration only if every ring op can complete",
+            indent
+        );
+        let _ = writeln!(self.out, "{}bool _ring_ready = true;", indent);
+        for demand in &demands {
+            let (check, op) = match demand.reader_idx {
+                Some(idx) => (format!("available({})", idx), "read"),
+                None => ("writable()".to_string(), "write"),
+            };
+            let _ = writeln!(
+                self.out,
+                "{}if (_ringbuf_{}.{} < {}) {{ {} _ring_ready = false; }}",
+                indent,
+                demand.buffer,
+                check,
+                demand.tokens,
+                Self::buffer_fail_count(&demand.buffer, op)
+            );
+        }
+        let _ = writeln!(
+            self.out,
+            "{}if (!_ring_ready) return; // peer task not caught up, yield",
+            indent
+        );
+    }
+
+    /// Collect the ring buffer reads and writes one iteration of `sg` performs,
+    /// summed per buffer (and reader).
+    fn collect_ring_demands(&self, sg: &LirSubgraph, demands: &mut Vec<RingDemand>) {
+        let firings = sg.firings.iter().flat_map(|group| match group {
+            LirFiringGroup::Single(firing) => std::slice::from_ref(firing),
+            LirFiringGroup::Fused(chain) => chain.body.as_slice(),
+        });
+        for firing in firings {
+            match &firing.kind {
+                LirFiringKind::BufferRead(io) => {
+                    if !self.is_lowered_bind(&io.buffer_name, BindDirection::In) {
+                        let idx = io.reader_idx.unwrap_or(0);
+                        add_ring_demand(demands, &io.buffer_name, Some(idx), io.total_tokens);
+                    }
+                }
+                LirFiringKind::BufferWrite(io) => {
+                    if !io.skip {
+                        add_ring_demand(demands, &io.buffer_name, None, io.total_tokens);
+                    }
+                }
+                LirFiringKind::GatherRead(io) => match io.coalesced_reader_idx {
+                    Some(idx) if self.options.reuse_family_buffers => {
+                        add_ring_demand(
+                            demands,
+                            &io.family_name,
+                            Some(idx),
+                            io.total_output_tokens,
+                        );
+                    }
+                    _ => {
+                        for elem in &io.elements {
+                            add_ring_demand(
+                                demands,
+                                &elem.buffer_name,
+                                Some(elem.reader_idx),
+                                elem.tokens,
+                            );
+                        }
+                    }
+                },
+                LirFiringKind::ScatterWrite(io) => {
+                    if self.options.reuse_family_buffers && io.coalesced {
+                        add_ring_demand(demands, &io.family_name, None, io.total_input_tokens);
+                    } else {
+                        for elem in io.elements.iter().filter(|e| !e.skip) {
+                            add_ring_demand(demands, &elem.buffer_name, None, elem.tokens);
+                        }
+                    }
+                }
+                LirFiringKind::Actor(_) | LirFiringKind::Fork(_) | LirFiringKind::Probe(_) => {}
+            }
+        }
+    }
+
+    /// Emit a non-blocking ring buffer operation for the single-thread runtime.
+    ///
+    /// The iteration guard has already checked that the operation fits, so the
+    /// `return` here is a backstop; blocking would starve the peer task
+    /// sharing the thread.
+    fn emit_cooperative_ring_op(&mut self, op: &str, fail_count: &str, indent: &str) {
+        let _ = writeln!(
+            self.out,
+            "{}if (!{}) {{ {} return; }} // single-thread: peer task not caught up, yield",
+            indent, op, fail_count
+        );
+    }
 
     /// Emit probe observation from LIR data.
     fn emit_lir_probe(&mut self, probe: &LirProbeFiring, indent: &str, rep: u32) {
@@ -2063,6 +3286,13 @@ impl<'a> CodegenCtx<'a> {
             }
             LirCtrlSource::RingBuffer { name, reader_idx } => {
                 let _ = writeln!(self.out, "{}int32_t _ctrl_buf[1];", indent);
+                if self.options.single_thread {
+                    let op = format!("_ringbuf_{}.read({}, _ctrl_buf, 1)", name, reader_idx);
+                    let fail_count = Self::buffer_fail_count(name, "read");
+                    self.emit_cooperative_ring_op(&op, &fail_count, indent);
+                    let _ = writeln!(self.out, "{}int32_t _ctrl = _ctrl_buf[0];", indent);
+                    return;
+                }
                 let _ = writeln!(
                     self.out,
                     "{}if (!_ringbuf_{}.read({}, _ctrl_buf, 1)) {{",
@@ -2087,6 +3317,29 @@ impl<'a> CodegenCtx<'a> {
                 let _ = writeln!(self.out, "{}}}", indent);
                 let _ = writeln!(self.out, "{}int32_t _ctrl = _ctrl_buf[0];", indent);
             }
+            LirCtrlSource::Bind { name } => {
+                // Keep the current mode (the first one before any write) on
+                // ticks where the external controller sent nothing.
+                let _ = writeln!(
+                    self.out,
+                    "{}int32_t _ctrl = _active_mode != -1 ? _active_mode : 0;",
+                    indent
+                );
+                if !self.lowered_binds.contains(name) {
+                    return;
+                }
+                let prefix = if self.lowered_shm_binds.contains(name) {
+                    "_shm_io_"
+                } else {
+                    "_bind_io_"
+                };
+                let _ = writeln!(self.out, "{}int32_t _ctrl_buf[1];", indent);
+                let _ = writeln!(
+                    self.out,
+                    "{}if ({}{}.recv(_ctrl_buf, 1)) _ctrl = _ctrl_buf[0];",
+                    indent, prefix, name
+                );
+            }
         }
     }
 
@@ -2114,15 +3367,57 @@ impl<'a> CodegenCtx<'a> {
 
 // ── Free helpers ────────────────────────────────────────────────────────────
 
-/// Collect all hoisted actor declarations from a LIR task body (free function
-/// to avoid borrow conflicts with &mut self emission methods).
-/// Collect tick-hoistable actors (above K-loop) from all subgraphs.
-/// Returns (var_name, cpp_name, params) tuples for declaration emission.
-fn collect_lir_tick_hoistable_actors(
-    body: &LirTaskBody,
-) -> Vec<(String, String, Vec<LirActorArg>)> {
-    let mut result = Vec::new();
-    let mut seen = std::collections::HashSet::new();
+/// Tokens one single-thread iteration reads from (`reader_idx` is `Some`) or
+/// writes to (`None`) a shared ring buffer.
+struct RingDemand {
+    buffer: String,
+    reader_idx: Option<usize>,
+    tokens: u32,
+}
+
+fn add_ring_demand(
+    demands: &mut Vec<RingDemand>,
+    buffer: &str,
+    reader_idx: Option<usize>,
+    tokens: u32,
+) {
+    match demands
+        .iter_mut()
+        .find(|d| d.buffer == buffer && d.reader_idx == reader_idx)
+    {
+        Some(d) => d.tokens += tokens,
+        None => demands.push(RingDemand {
+            buffer: buffer.to_string(),
+            reader_idx,
+            tokens,
+        }),
+    }
+}
+
+/// True if `firing` is an actor firing hoisted out of the tick loop.
+fn is_startup_firing(sg: &LirSubgraph, firing: &LirFiring) -> bool {
+    matches!(&firing.kind, LirFiringKind::Actor(actor) if sg.startup_nodes.contains(&actor.node_id))
+}
+
+/// True if `firing` is an actor repetition loop eligible for `--openmp`.
+fn is_parallel_firing(sg: &LirSubgraph, firing: &LirFiring) -> bool {
+    matches!(&firing.kind, LirFiringKind::Actor(actor) if sg.parallel_nodes.contains(&actor.node_id))
+}
+
+/// Collect all actor firings of a LIR task body, across subgraphs and fused
+/// chains, in emission order.
+fn collect_lir_actor_firings(body: &LirTaskBody) -> Vec<&LirActorFiring> {
+    collect_lir_firings(body)
+        .into_iter()
+        .filter_map(|firing| match &firing.kind {
+            LirFiringKind::Actor(actor) => Some(actor),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collect every firing (fused chains flattened) from a LIR task body.
+fn collect_lir_firings(body: &LirTaskBody) -> Vec<&LirFiring> {
     let subgraphs: Vec<&LirSubgraph> = match body {
         LirTaskBody::Pipeline(sg) => vec![sg],
         LirTaskBody::Modal(modal) => {
@@ -2133,53 +3428,58 @@ fn collect_lir_tick_hoistable_actors(
             sgs
         }
     };
+    let mut result = Vec::new();
     for sg in subgraphs {
         for group in &sg.firings {
-            let actors: Vec<&LirActorFiring> = match group {
-                LirFiringGroup::Single(firing) => {
-                    if let LirFiringKind::Actor(actor) = &firing.kind {
-                        vec![actor]
-                    } else {
-                        vec![]
-                    }
-                }
-                LirFiringGroup::Fused(chain) => chain
-                    .body
-                    .iter()
-                    .filter_map(|f| {
-                        if let LirFiringKind::Actor(actor) = &f.kind {
-                            Some(actor)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect(),
+            let firings: &[LirFiring] = match group {
+                LirFiringGroup::Single(firing) => std::slice::from_ref(firing),
+                LirFiringGroup::Fused(chain) => &chain.body,
             };
-            for actor in actors {
-                if actor.tick_hoistable && seen.insert(actor.node_id) {
-                    result.push((
-                        format!("_actor_{}", actor.node_id.0),
-                        actor.cpp_name.clone(),
-                        actor.params.clone(),
-                    ));
-                }
-            }
+            result.extend(firings);
         }
     }
     result
 }
 
+/// Collect all hoisted actor declarations from a LIR task body (free function
+/// to avoid borrow conflicts with &mut self emission methods).
+/// Collect tick-hoistable actors (above K-loop) from all subgraphs.
+/// Returns (var_name, cpp_name, params) tuples for declaration emission.
+fn collect_lir_tick_hoistable_actors(
+    body: &LirTaskBody,
+) -> Vec<(String, String, Vec<LirActorArg>)> {
+    let mut seen = std::collections::HashSet::new();
+    collect_lir_actor_firings(body)
+        .into_iter()
+        .filter(|actor| actor.tick_hoistable && seen.insert(actor.node_id))
+        .map(|actor| {
+            (
+                format!("_actor_{}", actor.node_id.0),
+                actor.cpp_name.clone(),
+                actor.params.clone(),
+            )
+        })
+        .collect()
+}
+
 /// Format a single LIR actor arg to C++ string.
 fn format_lir_actor_arg(arg: &LirActorArg) -> String {
     match arg {
         LirActorArg::Literal(s) => s.clone(),
         LirActorArg::ParamRef(name) => format!("_param_{}_val", name),
         LirActorArg::ConstScalar(literal) => literal.clone(),
-        LirActorArg::ConstSpan { name, len } => {
-            format!("std::span<const float>(_const_{}, {})", name, len)
-        }
+        LirActorArg::ConstSpan {
+            name,
+            offset: 0,
+            len,
+        } => format!("std::span<const float>(_const_{}, {})", name, len),
+        LirActorArg::ConstSpan { name, offset, len } => format!(
+            "std::span<const float>(_const_{} + {}, {})",
+            name, offset, len
+        ),
         LirActorArg::ConstArrayLen(len) => format!("{}", len),
         LirActorArg::DimValue(val) => format!("{}", val),
+        LirActorArg::Scratch { var_name, .. } => var_name.clone(),
     }
 }
 
@@ -2192,32 +3492,146 @@ fn format_lir_actor_args(args: &[LirActorArg]) -> String {
 }
 
 /// Build output pointer expression from LIR actor data (free function to avoid borrow issues).
+/// Per-firing input/output token counts passed as the actor's `IN_COUNT` /
+/// `OUT_COUNT` template arguments. Falls back to edge tokens per firing,
+/// like the buffer strides, when analysis left a rate unresolved.
+fn firing_token_counts(actor: &LirActorFiring, rep: u32) -> (u32, u32) {
+    let rep = rep.max(1);
+    let in_count = if actor.in_type == "void" || actor.inputs.is_empty() {
+        0
+    } else {
+        let per_firing: u32 = actor.inputs.iter().map(|i| i.tokens / rep).sum();
+        actor.in_rate.unwrap_or(per_firing)
+    };
+    let out_count = if actor.void_output || actor.outputs.is_empty() {
+        0
+    } else {
+        actor
+            .out_rate
+            .unwrap_or_else(|| actor.outputs[0].tokens / rep)
+    };
+    (in_count, out_count)
+}
+
 fn build_lir_output_ptr(actor: &LirActorFiring, rep: u32) -> String {
     if actor.void_output || actor.outputs.is_empty() {
         return "nullptr".to_string();
     }
     let output = &actor.outputs[0];
     if rep > 1 {
-        let stride = actor.out_rate.unwrap_or_else(|| output.tokens / rep);
-        format!("&{}[_r * {}]", output.buffer_var, stride)
+        format!(
+            "&{}[_r * {}]",
+            output.buffer_var,
+            lir_output_stride(actor, rep)
+        )
     } else {
         output.buffer_var.clone()
     }
 }
 
-fn escape_cpp_string(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+/// Output tokens between consecutive firings of a repetition loop.
+fn lir_output_stride(actor: &LirActorFiring, rep: u32) -> u32 {
+    actor
+        .out_rate
+        .unwrap_or_else(|| actor.outputs[0].tokens / rep)
 }
 
-/// A dummy span for codegen-phase diagnostics (no source location).
-fn codegen_span() -> crate::ast::Span {
-    use chumsky::span::Span as _;
-    crate::ast::Span::new((), 0..0)
+/// Frames of headroom in a `LibraryBindPort` between host calls.
+const LIBRARY_PORT_FRAMES: u32 = 64;
+
+/// Tokens in one bind frame: the product of the contract shape, 1 if scalar.
+fn bind_frame_tokens(bind: &LirBind) -> u32 {
+    bind.contract
+        .as_ref()
+        .map(|c| c.shape.iter().product::<u32>())
+        .unwrap_or(1)
+        .max(1)
 }
 
-// ── Tests ───────────────────────────────────────────────────────────────────
-// Unit tests: verify structural and semantic properties of generated C++ strings
-// (firing order, buffer layout, probe stripping) without a C++ compiler.
+/// Ring capacity of a library bind port: `LIBRARY_PORT_FRAMES` times the
+/// larger of one frame and the most tokens a task moves through the bind
+/// per tick (send/recv size × K-factor).
+fn library_port_capacity(lir: &LirProgram, bind: &LirBind) -> u32 {
+    let largest_io = lir
+        .tasks
+        .iter()
+        .flat_map(|task| {
+            collect_lir_firings(&task.body)
+                .into_iter()
+                .filter_map(move |firing| match &firing.kind {
+                    LirFiringKind::BufferRead(io) | LirFiringKind::BufferWrite(io)
+                        if io.buffer_name == bind.name =>
+                    {
+                        Some(io.total_tokens * task.k_factor.max(1))
+                    }
+                    _ => None,
+                })
+        })
+        .max()
+        .unwrap_or(0);
+    largest_io.max(bind_frame_tokens(bind)) * LIBRARY_PORT_FRAMES
+}
+
+/// (C++ element type, C element type) of a library bind accessor.
+fn library_bind_types(dtype: PipitType) -> Option<(&'static str, &'static str)> {
+    match dtype {
+        PipitType::Int8 => Some(("int8_t", "int8_t")),
+        PipitType::Int16 => Some(("int16_t", "int16_t")),
+        PipitType::Int32 => Some(("int32_t", "int32_t")),
+        PipitType::Float => Some(("float", "float")),
+        PipitType::Double => Some(("double", "double")),
+        PipitType::Cfloat => Some(("cfloat", "pipit_cfloat")),
+        PipitType::Cdouble => Some(("cdouble", "pipit_cdouble")),
+        PipitType::Bool => Some(("bool", "uint8_t")),
+        PipitType::Void => None,
+    }
+}
+
+/// C declaration of a bind accessor (without trailing `;`), or `None` when
+/// the bind has no contract or no inferred dtype.
+fn library_accessor_signature(bind: &LirBind) -> Option<String> {
+    let contract = bind.contract.as_ref()?;
+    let (_, c_type) = contract.dtype.and_then(library_bind_types)?;
+    Some(match contract.direction {
+        BindDirection::Out => format!("int pipit_read_{}({}* out)", bind.name, c_type),
+        BindDirection::In => format!("int pipit_write_{}(const {}* in)", bind.name, c_type),
+    })
+}
+
+/// C layouts of the complex element types used by any bind accessor
+/// (layout-compatible with `std::complex`); shared by header and source.
+fn library_c_types(lir: &LirProgram) -> String {
+    let dtypes: Vec<PipitType> = lir
+        .binds
+        .iter()
+        .filter_map(|b| b.contract.as_ref().and_then(|c| c.dtype))
+        .collect();
+    let mut out = String::new();
+    if dtypes.contains(&PipitType::Cfloat) {
+        out.push_str("typedef struct { float re; float im; } pipit_cfloat;\n");
+    }
+    if dtypes.contains(&PipitType::Cdouble) {
+        out.push_str("typedef struct { double re; double im; } pipit_cdouble;\n");
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn escape_cpp_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A dummy span for codegen-phase diagnostics (no source location).
+fn codegen_span() -> crate::ast::Span {
+    use chumsky::span::Span as _;
+    crate::ast::Span::new((), 0..0)
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+// Unit tests: verify structural and semantic properties of generated C++ strings
+// (firing order, buffer layout, probe stripping) without a C++ compiler.
 // Complements compiler/tests/codegen_compile.rs (compilation + runtime checks).
 
 #[cfg(test)]
@@ -2340,17 +3754,7 @@ mod tests {
     }
 
     fn codegen_source(source: &str, registry: &Registry) -> CodegenResult {
-        codegen_source_with_options(
-            source,
-            registry,
-            CodegenOptions {
-                release: false,
-                include_paths: vec![],
-                provenance: None,
-                experimental: false,
-                bind_overrides: std::collections::HashMap::new(),
-            },
-        )
+        codegen_source_with_options(source, registry, CodegenOptions::default())
     }
 
     fn codegen_ok(source: &str, registry: &Registry) -> String {
@@ -2402,6 +3806,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn actor_calls_pass_resolved_token_counts() {
+        // fir(coeff) consumes 3 tokens per firing, produces 1; decimate(4)
+        // consumes 4; the void-output sink produces 0.
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "const coeff = [0.1, 0.2, 0.4]\n\
+             clock 1kHz t { constant(0.0) | fir(coeff) | decimate(4) | stdout() }",
+            &reg,
+        );
+        for call in [
+            "_actor_1.operator()<3, 1>(&_e0_1[_r * 3], &_e1_2[_r * 1])",
+            "_actor_2.operator()<4, 1>(_e1_2, _e2_3)",
+            "_actor_3.operator()<1, 0>(_e2_3, nullptr)",
+        ] {
+            assert!(cpp.contains(call), "expected '{}' in:\n{}", call, cpp);
+        }
+    }
+
     // ── Param storage tests ─────────────────────────────────────────────
 
     #[test]
@@ -2527,7 +3950,7 @@ mod tests {
             cpp
         );
         let pos_c2r_call = cpp
-            .find("_actor_2.operator()(&_e1_2[_r * 256], &_e2_3[_r * 256])")
+            .find("_actor_2.operator()<256, 256>(&_e1_2[_r * 256], &_e2_3[_r * 256])")
             .expect("expected c2r call in rep=5 loop");
         let pos_fir_loop = cpp
             .find("for (int _r = 0; _r < 256; ++_r)")
@@ -2563,6 +3986,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn persistent_modal_delay_not_reset_on_mode_change() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "param sel = 0\n",
+                "clock 1kHz t {\n",
+                "    control {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode a {\n",
+                "        constant(0.0) | add(:fb) | :out | stdout()\n",
+                "        :out | delay(1, 0.0) @persist | :fb\n",
+                "    }\n",
+                "    mode b {\n",
+                "        constant(0.0) | add(:fb2) | :out2 | stdout()\n",
+                "        :out2 | delay(1, 0.0) | :fb2\n",
+                "    }\n",
+                "    switch($sel, a, b)\n",
+                "}\n"
+            ),
+            &reg,
+        );
+        let transition = cpp
+            .split("if (_active_mode != -1 && _ctrl != _active_mode) {")
+            .nth(1)
+            .and_then(|rest| rest.split("_active_mode = _ctrl;").next())
+            .expect("mode transition block");
+        let fb_vars: Vec<&str> = cpp
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("float _fb_"))
+            .filter_map(|rest| rest.split('[').next())
+            .collect();
+        assert_eq!(fb_vars.len(), 2, "two feedback buffers: {}", cpp);
+        let reset_count = fb_vars
+            .iter()
+            .filter(|v| transition.contains(&format!("_fb_{}[0] = 0;", v)))
+            .count();
+        assert_eq!(
+            reset_count, 1,
+            "only the non-persistent delay should be reset on mode change: {}",
+            transition
+        );
+    }
+
     #[test]
     fn fork_passthrough_chain_fused_into_single_r_loop() {
         let reg = test_registry();
@@ -2584,6 +4050,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn buffer_read_anchors_fused_chain() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "clock 1kHz a { constant(-4.0, 8) -> sig }\n",
+                "clock 1kHz b { @sig | abs() | decimate(8) | stdout() }\n",
+            ),
+            &reg,
+        );
+        // Block read first, then the loop consumes its buffer per iteration.
+        let pos_read = cpp
+            .find("_ringbuf_sig.read(0, _e2_3, 8)")
+            .expect("expected block read of 8 tokens");
+        let pos_loop = cpp
+            .find("for (int _r = 0; _r < 8; ++_r)")
+            .expect("expected fused rep=8 loop");
+        assert!(
+            pos_read < pos_loop,
+            "read should precede the loop:\n{}",
+            cpp
+        );
+        assert!(
+            cpp[pos_loop..].starts_with(
+                "for (int _r = 0; _r < 8; ++_r) {\n            assert(_r * 1 + 1 <= 8 && \"edge buffer overflow: _e3_4\");\n            if (pipit::fire_inline<1, 1>(_actor_3, &_e2_3[_r * 1], &_e3_4[_r * 1])"
+            ),
+            "abs should consume the read buffer at _r, got:\n{}",
+            cpp
+        );
+    }
+
     #[test]
     fn probe_passthrough_fusion_uses_per_firing_slice() {
         let reg = test_registry();
@@ -2705,86 +4202,902 @@ mod tests {
         );
     }
 
-    // ── timer_spin tests ─────────────────────────────────────────────────
-
+    // ── timer_spin tests ─────────────────────────────────────────────────
+
+    #[test]
+    fn timer_spin_default() {
+        let reg = test_registry();
+        let cpp = codegen_ok("clock 1kHz t { constant(0.0) | stdout() }", &reg);
+        // default timer_spin = 10000 (10us)
+        assert!(
+            cpp.contains("pipit::Timer _timer(1000.0, _stats, 10000);"),
+            "default spin should be 10000ns: {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn timer_spin_explicit() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "set timer_spin = 5000\nclock 1kHz t { constant(0.0) | stdout() }",
+            &reg,
+        );
+        assert!(
+            cpp.contains("pipit::Timer _timer(1000.0, _stats, 5000);"),
+            "explicit spin should be 5000ns: {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn timer_spin_auto() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "set timer_spin = auto\nclock 1kHz t { constant(0.0) | stdout() }",
+            &reg,
+        );
+        // auto → sentinel -1 for adaptive EWMA mode
+        assert!(
+            cpp.contains("pipit::Timer _timer(1000.0, _stats, -1);"),
+            "auto spin should emit sentinel -1: {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn timer_sub_hz_keeps_full_precision() {
+        let reg = test_registry();
+        // 0.5Hz → 2s period; the default 10us spin budget is kept rather
+        // than shrinking with the rate.
+        let cpp = codegen_ok("clock 0.5Hz t { constant(0.0) | stdout() }", &reg);
+        assert!(
+            cpp.contains("pipit::Timer _timer(0.5, _stats, 10000);"),
+            "0.5Hz timer should keep its rate and default spin: {}",
+            cpp
+        );
+        assert!(
+            cpp.contains("set_actor_task_rate_hz(0.5);"),
+            "task rate should be 0.5Hz: {}",
+            cpp
+        );
+
+        // Rates below 0.1Hz must not be rounded to zero (infinite period).
+        let cpp = codegen_ok(
+            "set timer_spin = auto\nclock 0.04Hz t { constant(0.0) | stdout() }",
+            &reg,
+        );
+        assert!(
+            cpp.contains("pipit::Timer _timer(0.04, _stats, -1);"),
+            "0.04Hz timer should keep full precision: {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn scratch_allocated_per_actor_instance() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "clock 1kHz t { constant(1.0) | sample_delay(4) | sample_delay(4) | stdout() }",
+            &reg,
+        );
+        let decls: Vec<&str> = cpp
+            .lines()
+            .filter(|l| l.trim_start().starts_with("static float _scratch_"))
+            .collect();
+        assert_eq!(decls.len(), 2, "one scratch array per instance: {}", cpp);
+        for decl in &decls {
+            assert!(decl.ends_with("[4] = {};"), "scratch sized by N: {}", decl);
+            let var = decl.trim_start()["static float ".len()..]
+                .split('[')
+                .next()
+                .unwrap();
+            assert!(
+                cpp.contains(&format!("Actor_sample_delay{{4, {}}}", var)),
+                "scratch passed as the last member: {}",
+                cpp
+            );
+        }
+        assert!(
+            cpp.contains("_desc.mem_used = 32;"),
+            "scratch counted in total_memory (2 x 4 floats): {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn pure_constant_prefix_hoisted_out_of_tick_loop() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "param gain = 3.0\n\
+             clock 1kHz t {\n\
+                 constant(5.0) | mul(2.0) | stdout()\n\
+                 constant(1.0) | mul($gain) | stdout()\n\
+             }",
+            &reg,
+        );
+        let body = &cpp[cpp.find("void task_t()").unwrap()..];
+        let tick_loop = body.find("while (!_stop").unwrap();
+        let (before, inside) = body.split_at(tick_loop);
+        assert!(
+            before.contains("_actor_1.operator()<1, 1>(_e0_1, _e1_2)"),
+            "constant-argument mul runs once before the loop: {}",
+            body
+        );
+        assert!(
+            !inside.contains("_e0_1, _e1_2"),
+            "hoisted prefix does not fire per tick: {}",
+            body
+        );
+        assert!(
+            inside.contains("_actor_2.operator()<1, 0>(_e1_2, nullptr)"),
+            "impure sink still fires every tick: {}",
+            body
+        );
+        assert!(
+            inside.contains("_param_gain_val"),
+            "runtime-param actor stays in the tick loop: {}",
+            body
+        );
+    }
+
+    #[test]
+    fn aliased_actor_call_emits_canonical_struct() {
+        let mut reg = test_registry();
+        let mut mul = reg.lookup("mul").unwrap().clone();
+        mul.aliases.push("scale".to_string());
+        reg.insert(mul);
+        let cpp = codegen_ok(
+            "clock 1kHz t { constant(1.0) | scale(2.0) | stdout() }",
+            &reg,
+        );
+        assert!(
+            cpp.contains("Actor_mul<float>"),
+            "alias should instantiate the renamed actor: {}",
+            cpp
+        );
+        assert!(
+            !cpp.contains("Actor_scale"),
+            "no struct for the alias: {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn tick_alignment_shares_epoch_between_commensurate_tasks() {
+        let reg = test_registry();
+        let src = "clock 1kHz a { constant(0.0) | stdout() }\n\
+                   clock 1kHz b { constant(0.0) | stdout() }\n\
+                   clock 700Hz c { constant(0.0) | stdout() }";
+        let cpp = codegen_ok(&format!("set tick_alignment = true\n{}", src), &reg);
+        assert!(
+            cpp.contains("static std::chrono::steady_clock::time_point _tick_epoch()"),
+            "aligned program should define the shared epoch: {}",
+            cpp
+        );
+        let task_body = |name: &str| {
+            let start = cpp
+                .find(&format!("void task_{}()", name))
+                .unwrap_or_else(|| panic!("missing task_{}", name));
+            let end = cpp[start..].find("\n}\n").unwrap() + start;
+            cpp[start..end].to_string()
+        };
+        for name in ["a", "b"] {
+            assert!(
+                task_body(name)
+                    .contains("pipit::Timer _timer(1000.0, _stats, 10000, _tick_epoch());"),
+                "task {} should start from the shared epoch: {}",
+                name,
+                cpp
+            );
+        }
+        assert!(
+            task_body("c").contains("pipit::Timer _timer(700.0, _stats, 10000);"),
+            "700Hz is not commensurate with 1kHz and keeps its own start: {}",
+            cpp
+        );
+
+        let unaligned = codegen_ok(src, &reg);
+        assert!(
+            !unaligned.contains("_tick_epoch"),
+            "tick alignment is opt-in: {}",
+            unaligned
+        );
+    }
+
+    // ── Fork test ───────────────────────────────────────────────────────
+
+    #[test]
+    fn fork_zero_copy() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "clock 1kHz t { constant(0.0) | :raw | stdout()\n:raw | stdout() }",
+            &reg,
+        );
+        // Fork should NOT use memcpy — downstream actors share the upstream buffer
+        assert!(
+            !cpp.contains("memcpy"),
+            "fork should be zero-copy (no memcpy): {}",
+            cpp
+        );
+        assert!(cpp.contains("// fork:"), "fork comment expected: {}", cpp);
+    }
+
+    // ── Shared buffer test ──────────────────────────────────────────────
+
+    #[test]
+    fn shared_buffer_declaration() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "set mem = 64MB\n",
+                "clock 1kHz a { constant(0.0) -> sig }\n",
+                "clock 1kHz b { @sig | stdout() }\n",
+            ),
+            &reg,
+        );
+        assert!(
+            cpp.contains("pipit::RingBuffer<float"),
+            "should declare ring buffer: {}",
+            cpp
+        );
+        assert!(cpp.contains("_ringbuf_sig"), "should name buffer: {}", cpp);
+    }
+
+    #[test]
+    fn shared_buffer_depth_follows_buffering_mode() {
+        let reg = test_registry();
+        for (mode, tokens) in [("single", 1), ("double", 2), ("triple", 3)] {
+            let cpp = codegen_ok(
+                &format!(
+                    "set buffering = {}\n\
+                     clock 1kHz a {{ constant(0.0) -> sig }}\n\
+                     clock 1kHz b {{ @sig | stdout() }}\n",
+                    mode
+                ),
+                &reg,
+            );
+            let decl = format!(
+                "static pipit::RingBuffer<float, {}, 1> _ringbuf_sig;",
+                tokens
+            );
+            assert!(
+                cpp.contains(&decl),
+                "{}: expected {}, got:\n{}",
+                mode,
+                decl,
+                cpp
+            );
+        }
+    }
+
+    #[test]
+    fn buffer_high_water_mark_debug_only() {
+        let reg = test_registry();
+        let source = concat!(
+            "set mem = 64MB\n",
+            "clock 1kHz a { constant(0.0) -> sig }\n",
+            "clock 1kHz b { @sig | stdout() }\n",
+        );
+
+        let debug_cpp = codegen_ok(source, &reg);
+        assert!(
+            debug_cpp.contains("static std::size_t _peak_sig = 0;"),
+            "debug build should declare peak counter: {}",
+            debug_cpp
+        );
+        assert!(
+            debug_cpp.contains("_ringbuf_sig.fill(); _fill > _peak_sig) _peak_sig = _fill;"),
+            "debug build should track peak after writes: {}",
+            debug_cpp
+        );
+        assert!(
+            debug_cpp.contains("sizeof(float), &_peak_sig, "),
+            "debug buffer stats should report peak: {}",
+            debug_cpp
+        );
+
+        let release_cpp = codegen_source_with_options(
+            source,
+            &reg,
+            CodegenOptions {
+                release: true,
+                ..Default::default()
+            },
+        )
+        .generated
+        .cpp_source;
+        assert!(
+            !release_cpp.contains("_peak_") && !release_cpp.contains(".fill()"),
+            "release build should not track peaks: {}",
+            release_cpp
+        );
+        assert!(release_cpp.contains("_ringbuf_sig.available(); }, sizeof(float), nullptr, 0, "));
+    }
+
+    #[test]
+    fn buffer_fail_counters_count_ring_retries() {
+        let reg = test_registry();
+        let source = concat!(
+            "set mem = 64MB\n",
+            "clock 1kHz a { constant(0.0) -> sig }\n",
+            "clock 1kHz b { @sig | stdout() }\n",
+        );
+
+        let cpp = codegen_ok(source, &reg);
+        assert!(
+            cpp.contains("static pipit::BufferStats _bufstats_sig;"),
+            "should declare fail counters: {}",
+            cpp
+        );
+        for (op, count) in [
+            (
+                "_ringbuf_sig.write(",
+                "if (_stats) _bufstats_sig.record_write_fail();",
+            ),
+            (
+                "_ringbuf_sig.read(0, ",
+                "if (_stats) _bufstats_sig.record_read_fail();",
+            ),
+        ] {
+            let retry = cpp.find(op).expect("ring op in retry loop");
+            let next = cpp[retry..].lines().nth(1).unwrap_or_default();
+            assert_eq!(next.trim(), count, "failed op should be counted: {}", cpp);
+        }
+        assert!(
+            cpp.contains("sizeof(float), &_peak_sig, 2, &_bufstats_sig},"),
+            "buffer stats should report fail counters: {}",
+            cpp
+        );
+
+        let single = codegen_source_with_options(
+            source,
+            &reg,
+            CodegenOptions {
+                single_thread: true,
+                ..Default::default()
+            },
+        )
+        .generated
+        .cpp_source;
+        assert!(
+            single.contains(
+                ")) { if (_stats) _bufstats_sig.record_read_fail(); return; } // single-thread"
+            ),
+            "single-thread yield should count the underrun: {}",
+            single
+        );
+    }
+
+    #[test]
+    fn edge_buffer_guards_debug_only() {
+        let reg = test_registry();
+        let source = "clock 1kHz t {\n    constant(0.0)[4] | fft(2) | mag() | stdout()\n}\n";
+
+        let debug_cpp = codegen_ok(source, &reg);
+        for expected in [
+            "alignas(64) static float _e0_1[4 + pipit::EDGE_GUARD_TOKENS];",
+            "pipit::edge_guard_fill(_e0_1);",
+            "assert(4 <= 4 && \"edge buffer overflow: _e0_1\");",
+            "assert(_r * 2 + 2 <= 4 && \"edge buffer overflow: _e1_2\");",
+            "assert(pipit::edge_guard_intact(_e1_2) && \"edge buffer guard overwritten: _e1_2\");",
+        ] {
+            assert!(
+                debug_cpp.contains(expected),
+                "debug build should contain '{}': {}",
+                expected,
+                debug_cpp
+            );
+        }
+
+        let release_cpp = codegen_source_with_options(
+            source,
+            &reg,
+            CodegenOptions {
+                release: true,
+                ..Default::default()
+            },
+        )
+        .generated
+        .cpp_source;
+        assert!(
+            !release_cpp.contains("EDGE_GUARD")
+                && !release_cpp.contains("edge_guard_")
+                && !release_cpp.contains("assert("),
+            "release build should not guard edge buffers: {}",
+            release_cpp
+        );
+        assert!(release_cpp.contains("alignas(64) static float _e0_1[4];"));
+    }
+
+    // ── Allocation report tests ─────────────────────────────────────────
+
+    #[test]
+    fn allocation_report_total_matches_declared_buffers() {
+        let reg = test_registry();
+        let result = codegen_source_with_options(
+            concat!(
+                "clock 1kHz iir {\n",
+                "    constant(0.0)[4] | add(:fb) | mul(2.0) | :out -> sig\n",
+                "    :out | delay(1, 0.0) | :fb\n",
+                "}\n",
+                "clock 1kHz sink { @sig | fft(4) | mag() | stdout() }\n",
+            ),
+            &reg,
+            CodegenOptions::default(),
+        );
+        let generated = &result.generated;
+
+        // Independently sum every array and ring buffer declared in the C++.
+        let size_of = |t: &str| match t {
+            "float" => 4,
+            "cfloat" => 8,
+            other => panic!("unexpected element type {}", other),
+        };
+        let mut declared = 0u64;
+        for line in generated.cpp_source.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("static pipit::RingBuffer<") {
+                let args: Vec<&str> = rest.split('>').next().unwrap().split(", ").collect();
+                let tokens: u64 = args[1].parse().unwrap();
+                let readers: usize = args[2].parse().unwrap();
+                declared +=
+                    tokens * size_of(args[0]) + crate::analyze::ring_buffer_header_bytes(readers);
+            } else if line.contains("_e") || line.contains("_fb_") {
+                let decl = line.strip_prefix("alignas(64) static ").unwrap_or(line);
+                let Some((head, rest)) = decl.split_once('[') else {
+                    continue;
+                };
+                let mut words = head.split(' ');
+                let (Some(ty), Some(_name), None) = (words.next(), words.next(), words.next())
+                else {
+                    continue;
+                };
+                if !rest.ends_with("];") && !rest.contains("] = {") {
+                    continue;
+                }
+                // Debug edge buffers append `+ pipit::EDGE_GUARD_TOKENS`.
+                let payload = rest.split(']').next().unwrap();
+                let count: u64 = payload.split(" + ").next().unwrap().parse().unwrap();
+                declared += count * size_of(ty);
+            }
+        }
+
+        let kinds: HashSet<AllocationKind> = generated.allocations.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds.len(),
+            3,
+            "edge, feedback and ring expected: {:?}",
+            kinds
+        );
+        let report = format_allocation_report(&generated.allocations);
+        assert!(
+            report.contains(&format!("  total: {} bytes\n", declared)),
+            "report total should equal declared buffers ({} bytes):\n{}\n{}",
+            declared,
+            report,
+            generated.cpp_source
+        );
+        assert!(report.contains("  task iir:\n") && report.contains("  shared:\n"));
+    }
+
+    // ── Preamble tests ──────────────────────────────────────────────────
+
+    #[test]
+    fn minimal_includes_omits_cstdio_for_probe_free_release() {
+        let reg = test_registry();
+        let source = "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}";
+        let options = |minimal_includes| CodegenOptions {
+            release: true,
+            minimal_includes,
+            ..Default::default()
+        };
+
+        let full = codegen_source_with_options(source, &reg, options(false))
+            .generated
+            .cpp_source;
+        assert!(full.contains("#include <cstdio>\n"));
+
+        let minimal = codegen_source_with_options(source, &reg, options(true))
+            .generated
+            .cpp_source;
+        assert!(
+            !minimal.contains("#include <cstdio>"),
+            "minimal preamble should not include <cstdio>: {}",
+            minimal
+        );
+        assert!(minimal.starts_with("// Generated by pcc"));
+        assert!(minimal.contains("#include <pipit_shell.h>\n"));
+        assert_eq!(
+            full.replace("#include <cstdio>\n", ""),
+            minimal,
+            "only the preamble should differ"
+        );
+    }
+
+    #[test]
+    fn duplicate_pure_actor_fires_once() {
+        let reg = test_registry();
+        // Both fft(256) read the same tap buffer: the second reuses the first
+        // result, and the probe on its branch observes the shared buffer.
+        let cpp = codegen_ok(
+            concat!(
+                "clock 1kHz t {\n",
+                "    constant(0.0) | :raw | fft(256) | c2r() | stdout()\n",
+                "    :raw | fft(256) | ?spec | mag() | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        assert_eq!(count_occurrences(&cpp, "Actor_fft{"), 1, "got:\n{}", cpp);
+        assert_eq!(
+            count_occurrences(&cpp, "(_e0_1, _e2_3)"),
+            1,
+            "got:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("(_e2_3)[_pi]"),
+            "probe reads the shared buffer:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("Actor_mag") && cpp.contains("(_e2_3, _e"),
+            "mag reads fft's output:\n{}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn cse_keeps_runtime_param_and_distinct_arg_firings() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "param g = 2.0\n",
+                "clock 1kHz t {\n",
+                "    constant(0.0) | :raw | mul($g) | stdout()\n",
+                "    :raw | mul($g) | stdout()\n",
+                "    :raw | mean(4) | stdout()\n",
+                "    :raw | mean(2) | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        assert_eq!(
+            count_occurrences(&cpp, "Actor_mul<float>{_param_g_val"),
+            2,
+            "runtime-param actors are never merged:\n{}",
+            cpp
+        );
+        assert_eq!(
+            count_occurrences(&cpp, "Actor_mean<float>{"),
+            2,
+            "got:\n{}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn openmp_pragma_only_on_eligible_repetition_loops() {
+        let reg = test_registry();
+        // abs in t: PURE, unfused, 256 reps → parallel. In u, the fused
+        // abs|sqrt chain and the stdout loop (not PURE) stay sequential.
+        let source = concat!(
+            "param g = 1.0\n",
+            "clock 1kHz t {\n    constant($g) | fft(256) | c2r() | abs() | mean(256) | stdout()\n}\n",
+            "clock 1kHz u {\n    constant($g) | fft(256) | c2r() | abs() | sqrt() | stdout()\n}\n",
+        );
+        let options = |openmp| CodegenOptions {
+            openmp,
+            ..Default::default()
+        };
+        let cpp = codegen_source_with_options(source, &reg, options(true))
+            .generated
+            .cpp_source;
+        assert_eq!(
+            count_occurrences(&cpp, "#pragma omp parallel for"),
+            1,
+            "only the abs loop qualifies:\n{}",
+            cpp
+        );
+        let pragma = cpp.find("#pragma omp parallel for").unwrap();
+        let after = &cpp[pragma..];
+        assert!(
+            after.starts_with("#pragma omp parallel for reduction(||: _omp_err_3)\n"),
+            "got:\n{}",
+            after
+        );
+        let body = &after[..after.find("\n        }\n").unwrap()];
+        assert!(
+            body.contains("for (int _r = 0; _r < 256; ++_r)"),
+            "{}",
+            body
+        );
+        assert!(
+            body.contains("pipit::fire_inline<1, 1>(_actor_3, &_e2_3[_r * 1]"),
+            "{}",
+            body
+        );
+        assert!(
+            !body.contains("return;"),
+            "no return inside the parallel loop"
+        );
+
+        let serial = codegen_source_with_options(source, &reg, options(false))
+            .generated
+            .cpp_source;
+        assert!(!serial.contains("#pragma omp"), "pragma requires --openmp");
+    }
+
+    #[test]
+    fn inline_actor_fires_through_fire_inline() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            "clock 1kHz t {\n    constant(1.0) | abs() | stdout()\n}\n",
+            &reg,
+        );
+        assert!(
+            cpp.contains("pipit::fire_inline<1, 1>(_actor_1, _e0_1, _e1_2)"),
+            "INLINE actor should use the forced-inline wrapper:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("_actor_0.operator()<0, 1>("),
+            "other actors keep the direct call:\n{}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn defaulted_params_are_passed_explicitly() {
+        let mut reg = test_registry();
+        let mut meta = reg.lookup("fft").unwrap().clone();
+        meta.name = "blk".to_string();
+        meta.params[0].default = Some("16".to_string());
+        reg.insert(meta);
+        let cpp = codegen_ok(
+            "clock 1kHz t {\n    constant(0.0) | blk() | mag() | stdout()\n}\n",
+            &reg,
+        );
+        assert!(
+            cpp.contains("Actor_blk{16}"),
+            "omitted N should be initialized from its default:\n{}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn profile_actors_wraps_each_firing_with_timing() {
+        let reg = test_registry();
+        let source = "clock 1kHz t {\n    constant(1.0) | abs() | stdout()\n}\n";
+        let options = |profile_actors| CodegenOptions {
+            profile_actors,
+            ..Default::default()
+        };
+        let cpp = codegen_source_with_options(source, &reg, options(true))
+            .generated
+            .cpp_source;
+        for id in 0..3 {
+            assert!(
+                cpp.contains(&format!("static pipit::ActorProfile _prof_{};", id)),
+                "missing accumulator for node {}:\n{}",
+                id,
+                cpp
+            );
+            let t0 = format!("const uint64_t _prof_t0_{} = pipit_now_ns();", id);
+            let record = format!("_prof_{}.record(pipit_now_ns() - _prof_t0_{});", id, id);
+            let check = format!("if (_prof_rc_{} != ACTOR_OK) {{", id);
+            let start = cpp.find(&t0).unwrap_or_else(|| panic!("missing {}", t0));
+            let rest = &cpp[start..];
+            let rec = rest.find(&record).expect("record after sample");
+            assert!(
+                rest[..rec].contains(&format!("const int _prof_rc_{} = ", id)),
+                "call is bracketed by the samples:\n{}",
+                rest
+            );
+            assert!(rest[rec..].contains(&check), "error check after record");
+        }
+        assert!(cpp.contains("{\"t\", \"abs\", 1, &_prof_1},"), "{}", cpp);
+        assert!(cpp.contains("_desc.actor_profiles = _actor_profile_descs;"));
+
+        let plain = codegen_source_with_options(source, &reg, options(false))
+            .generated
+            .cpp_source;
+        assert!(
+            !plain.contains("_prof_"),
+            "wrappers require --profile-actors"
+        );
+    }
+
     #[test]
-    fn timer_spin_default() {
+    fn no_exceptions_param_setters_parse_without_try() {
         let reg = test_registry();
-        let cpp = codegen_ok("clock 1kHz t { constant(0.0) | stdout() }", &reg);
-        // default timer_spin = 10000 (10us)
+        let source = "param g = 0.5\nclock 1kHz t {\n    constant(1.0) | mul($g) | stdout()\n}\n";
+        let options = |no_exceptions| CodegenOptions {
+            no_exceptions,
+            ..Default::default()
+        };
+        let cpp = codegen_source_with_options(source, &reg, options(true))
+            .generated
+            .cpp_source;
         assert!(
-            cpp.contains("pipit::Timer _timer(1000.0, _stats, 10000);"),
-            "default spin should be 10000ns: {}",
+            cpp.contains(
+                "{\"g\", [](const char* v) -> bool { float _v{}; if (!pipit::parse_param(v, &_v)) return false; _param_g_write.store(_v, std::memory_order_release); return true; }},"
+            ),
+            "{}",
             cpp
         );
+        for construct in [
+            "try",
+            "catch",
+            "throw",
+            "std::sto",
+            "dynamic_cast",
+            "typeid",
+        ] {
+            assert!(
+                !cpp.contains(construct),
+                "'{}' in -fno-exceptions/-fno-rtti glue:\n{}",
+                construct,
+                cpp
+            );
+        }
+
+        let default = codegen_source_with_options(source, &reg, options(false))
+            .generated
+            .cpp_source;
+        assert!(default.contains("try { _param_g_write.store(std::stof(v)"));
     }
 
     #[test]
-    fn timer_spin_explicit() {
+    fn set_param_dispatches_to_each_declared_param() {
         let reg = test_registry();
         let cpp = codegen_ok(
-            "set timer_spin = 5000\nclock 1kHz t { constant(0.0) | stdout() }",
+            concat!(
+                "param gain = 2.0\n",
+                "param sel = 1\n",
+                "clock 1kHz t {\n",
+                "    constant(1.0) | mul($gain) | stdout()\n",
+                "}\n",
+            ),
             &reg,
         );
+        let start = cpp
+            .find("int pipit_set_param(const char* name, double value) {")
+            .expect("pipit_set_param should be emitted");
+        let body = &cpp[start..start + cpp[start..].find("\n}\n").unwrap()];
+        for (name, cpp_type) in [("gain", "float"), ("sel", "int")] {
+            let branch = format!(
+                concat!(
+                    "    if (std::strcmp(name, \"{name}\") == 0) {{\n",
+                    "        {ty} _v{{}};\n",
+                    "        if (!pipit::coerce_param(value, &_v)) return pipit::PARAM_INVALID;\n",
+                    "        _param_{name}_write.store(_v, std::memory_order_release);\n",
+                    "        return pipit::PARAM_OK;\n",
+                    "    }}\n",
+                ),
+                name = name,
+                ty = cpp_type
+            );
+            assert!(
+                body.contains(&branch),
+                "missing branch for '{}':\n{}",
+                name,
+                body
+            );
+        }
         assert!(
-            cpp.contains("pipit::Timer _timer(1000.0, _stats, 5000);"),
-            "explicit spin should be 5000ns: {}",
-            cpp
+            body.ends_with("    return pipit::PARAM_UNKNOWN;"),
+            "{}",
+            body
         );
+
+        let no_params = codegen_ok("clock 1kHz t { constant(0.0) | stdout() }", &reg);
+        assert!(no_params
+            .contains("int pipit_set_param(const char*, double) { return pipit::PARAM_UNKNOWN; }"));
     }
 
     #[test]
-    fn timer_spin_auto() {
+    fn single_thread_runtime_emits_one_tick_per_call() {
         let reg = test_registry();
-        let cpp = codegen_ok(
-            "set timer_spin = auto\nclock 1kHz t { constant(0.0) | stdout() }",
-            &reg,
+        let source = concat!(
+            "clock 1kHz producer {\n    constant(1.0) | mul(2.0) -> sig\n}\n",
+            "clock 1kHz consumer {\n    @sig | stdout()\n}\n",
         );
-        // auto → sentinel -1 for adaptive EWMA mode
+        let options = CodegenOptions {
+            single_thread: true,
+            ..Default::default()
+        };
+        let cpp = codegen_source_with_options(source, &reg, options)
+            .generated
+            .cpp_source;
+
+        for task in ["producer", "consumer"] {
+            assert!(cpp.contains(&format!("static int64_t _deadline_{} = 0;", task)));
+            assert!(cpp.contains(&format!("_deadline_{} = _timer.deadline_ns();", task)));
+            assert!(cpp.contains(&format!(
+                "{{\"{}\", task_{}, &_stats_{}, &_deadline_{}}},",
+                task, task, task, task
+            )));
+        }
+        assert!(cpp.contains("static pipit::Timer _timer("));
+        assert!(cpp.contains("    do {\n") && cpp.contains("    } while (false);\n"));
+        assert!(cpp.contains("_desc.single_thread = true;"));
         assert!(
-            cpp.contains("pipit::Timer _timer(1000.0, _stats, -1);"),
-            "auto spin should emit sentinel -1: {}",
+            cpp.contains("if (!_ringbuf_sig.write(") && cpp.contains("if (!_ringbuf_sig.read(0, "),
+            "ring buffer access should yield instead of blocking: {}",
             cpp
         );
+        assert!(!cpp.contains("wait_readable") && !cpp.contains("wait_writable"));
+        assert!(!cpp.contains("std::this_thread::yield"));
     }
 
-    // ── Fork test ───────────────────────────────────────────────────────
-
     #[test]
-    fn fork_zero_copy() {
+    fn single_thread_checks_every_ring_op_before_first_firing() {
         let reg = test_registry();
-        let cpp = codegen_ok(
-            "clock 1kHz t { constant(0.0) | :raw | stdout()\n:raw | stdout() }",
-            &reg,
-        );
-        // Fork should NOT use memcpy — downstream actors share the upstream buffer
-        assert!(
-            !cpp.contains("memcpy"),
-            "fork should be zero-copy (no memcpy): {}",
-            cpp
+        let source = concat!(
+            "shared sig[3]\n",
+            "clock 1kHz writer { sine(100.0, 1.0, 3) -> sig[*] }\n",
+            "clock 1kHz reader { @sig[*] | stdout<float>() }\n",
         );
-        assert!(cpp.contains("// fork:"), "fork comment expected: {}", cpp);
+        let options = CodegenOptions {
+            single_thread: true,
+            ..Default::default()
+        };
+        let cpp = codegen_source_with_options(source, &reg, options)
+            .generated
+            .cpp_source;
+
+        let task_body = |task: &str| {
+            let start = cpp.find(&format!("void task_{}() {{", task)).unwrap();
+            let end = start + cpp[start..].find("} while (false);").unwrap();
+            cpp[start..end].to_string()
+        };
+        let writer = task_body("writer");
+        let guard = writer
+            .find("if (!_ring_ready) return;")
+            .expect("writer guard");
+        for elem in 0..3 {
+            assert!(
+                writer.contains(&format!("if (_ringbuf_sig__{}.writable() < 1)", elem)),
+                "writer should check space on every element: {}",
+                writer
+            );
+        }
+        assert!(guard < writer.find("actor 'sine'").unwrap());
+        assert!(guard < writer.find("_ringbuf_sig__0.write(").unwrap());
+
+        let reader = task_body("reader");
+        let guard = reader
+            .find("if (!_ring_ready) return;")
+            .expect("reader guard");
+        for elem in 0..3 {
+            assert!(
+                reader.contains(&format!("if (_ringbuf_sig__{}.available(0) < 1)", elem)),
+                "reader should check tokens on every element: {}",
+                reader
+            );
+        }
+        assert!(guard < reader.find("_ringbuf_sig__0.read(").unwrap());
     }
 
-    // ── Shared buffer test ──────────────────────────────────────────────
-
     #[test]
-    fn shared_buffer_declaration() {
-        let reg = test_registry();
-        let cpp = codegen_ok(
-            concat!(
-                "set mem = 64MB\n",
-                "clock 1kHz a { constant(0.0) -> sig }\n",
-                "clock 1kHz b { @sig | stdout() }\n",
-            ),
-            &reg,
-        );
-        assert!(
-            cpp.contains("pipit::RingBuffer<float"),
-            "should declare ring buffer: {}",
-            cpp
-        );
-        assert!(cpp.contains("_ringbuf_sig"), "should name buffer: {}", cpp);
+    fn runtime_includes_headers_emitted_code_uses() {
+        let include_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("runtime/libpipit/include");
+        let mut runtime_text = String::new();
+        for header in ["pipit.h", "pipit_shell.h"] {
+            runtime_text.push_str(&std::fs::read_to_string(include_dir.join(header)).unwrap());
+        }
+        // `--minimal-includes` relies on these arriving through pipit.h.
+        for header in ["<cstdio>", "<cstring>"] {
+            assert!(
+                runtime_text.contains(&format!("#include {}\n", header)),
+                "{} is not included by pipit.h or pipit_shell.h",
+                header
+            );
+        }
     }
 
     // ── Main function test ──────────────────────────────────────────────
@@ -2839,10 +5152,7 @@ mod tests {
             &reg,
             CodegenOptions {
                 release: true,
-                include_paths: vec![],
-                provenance: None,
-                experimental: false,
-                bind_overrides: std::collections::HashMap::new(),
+                ..Default::default()
             },
         );
         let errors: Vec<_> = release_result
@@ -2973,6 +5283,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn switch_bind_source_keeps_mode_without_data() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "bind sel = udp(\"127.0.0.1:9300\")\n",
+                "clock 1kHz t {\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(@sel, a, b)\n",
+                "}\n"
+            ),
+            &reg,
+        );
+        assert!(
+            cpp.contains("int32_t _ctrl = _active_mode != -1 ? _active_mode : 0;"),
+            "switch(@bind, ...) should default to the current mode: {}",
+            cpp
+        );
+        assert!(
+            cpp.contains("if (_bind_io_sel.recv(_ctrl_buf, 1)) _ctrl = _ctrl_buf[0];"),
+            "switch(@bind, ...) should only update ctrl on received data: {}",
+            cpp
+        );
+    }
+
+    #[test]
+    fn switch_param_source_reloads_atomic_every_iteration() {
+        // K = 10: the param must be re-read inside the K loop, not once per
+        // tick, and before `_ctrl` is computed, so live updates switch modes.
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "set tick_rate = 1kHz\n",
+                "param sel = 0\n",
+                "clock 10kHz t {\n",
+                "    control {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch($sel, a, b)\n",
+                "}\n"
+            ),
+            &reg,
+        );
+        let task = &cpp[cpp.find("void task_t()").expect("task function")..];
+        let k_loop = task
+            .find("for (int _k = 0; _k < 10; ++_k) {")
+            .expect("K loop");
+        let load = task
+            .find("int _param_sel_val = _param_sel_write.load(std::memory_order_acquire);")
+            .expect("atomic param load");
+        let ctrl = task
+            .find("int32_t _ctrl = static_cast<int32_t>(_param_sel_val);")
+            .expect("ctrl from param");
+        assert!(
+            k_loop < load && load < ctrl,
+            "param must be loaded per iteration before ctrl: {}",
+            task
+        );
+        assert_eq!(
+            task.matches("_param_sel_write.load(").count(),
+            1,
+            "exactly one atomic load per iteration: {}",
+            task
+        );
+    }
+
     #[test]
     fn switch_external_buffer_source_reads_ring_buffer_for_ctrl() {
         let reg = test_registry();
@@ -3161,13 +5538,7 @@ mod tests {
             &analysis_result.analysis,
             &schedule_result.schedule,
         );
-        let options = CodegenOptions {
-            release: false,
-            include_paths: vec![],
-            provenance: None,
-            experimental: false,
-            bind_overrides: std::collections::HashMap::new(),
-        };
+        let options = CodegenOptions::default();
         let mut ctx = CodegenCtx::new(
             &graph_result.graph,
             &schedule_result.schedule,
@@ -3251,6 +5622,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fir_const_slice_emits_offset_span() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "const coeff = [0.1, 0.2, 0.4, 0.2, 0.1, 0.0, 0.0]\n",
+                "clock 1kHz t { constant(0.0) | fir(coeff[2:5]) | stdout() }",
+            ),
+            &reg,
+        );
+        assert!(
+            cpp.contains("Actor_fir<float>{std::span<const float>(_const_coeff + 2, 3), 3}"),
+            "fir should take a 3-element span at offset 2, got:\n{}",
+            cpp
+        );
+    }
+
     #[test]
     fn fir_span_derived_not_overridden_by_edge_inference() {
         let reg = test_registry();
@@ -3315,7 +5703,7 @@ mod tests {
         );
         // The hoisted actor should be called via .operator() inside the loop
         assert!(
-            cpp.contains("_actor_") && cpp.contains(".operator()("),
+            cpp.contains("_actor_") && cpp.contains(".operator()<"),
             "hoisted actor should be called via .operator() inside the loop, got:\n{}",
             cpp
         );
@@ -3363,6 +5751,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tap_gather_concatenates_in_list_order() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "clock 1kHz t {\n",
+                "    constant(0.0) | :a | stdout()\n",
+                "    constant(1.0) | :b | stdout()\n",
+                "    gather(:b, :a) | mean(4) | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+        );
+        // Node ids: constant(0.0)=0 -> fork(:a)=1, constant(1.0)=3 -> fork(:b)=4.
+        let b_pos = cpp
+            .find("_in_6[0] = (_e3_4)[0];")
+            .unwrap_or_else(|| panic!("':b' should fill the first input slots:\n{}", cpp));
+        let a_pos = cpp
+            .find("_in_6[2] = (_e0_1)[0];")
+            .unwrap_or_else(|| panic!("':a' should follow ':b':\n{}", cpp));
+        assert!(b_pos < a_pos);
+    }
+
+    #[test]
+    fn set_affinity_pins_mapped_tasks() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            concat!(
+                "set affinity = { rx: 2 }\n",
+                "clock 1kHz rx {\n    constant(0.0) | stdout()\n}\n",
+                "clock 1kHz tx {\n    constant(0.0) | stdout()\n}\n",
+            ),
+            &reg,
+        );
+        assert!(
+            cpp.contains("{\"rx\", task_rx, &_stats_rx, nullptr, 2},"),
+            "rx should carry its core:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("{\"tx\", task_tx, &_stats_tx},"),
+            "unpinned tx keeps OS placement:\n{}",
+            cpp
+        );
+    }
+
     #[test]
     fn provenance_comment_emitted() {
         let reg = test_registry();
@@ -3370,16 +5804,16 @@ mod tests {
             source_hash: [0xab; 32],
             registry_fingerprint: [0xcd; 32],
             compiler_version: "0.1.2-test",
+            runtime_abi_version: 1,
+            build_epoch: None,
+            memory: None,
         };
         let result = codegen_source_with_options(
             "clock 1kHz t { constant(0.0) | stdout() }",
             &reg,
             CodegenOptions {
-                release: false,
-                include_paths: vec![],
                 provenance: Some(prov),
-                experimental: false,
-                bind_overrides: std::collections::HashMap::new(),
+                ..Default::default()
             },
         );
         let cpp = result.generated.cpp_source;
@@ -3440,6 +5874,23 @@ clock 48kHz audio {
         );
     }
 
+    #[test]
+    fn bind_version_stamped_in_adapter_header() {
+        let reg = test_registry();
+        let cpp = codegen_ok(
+            r#"bind iq = udp("127.0.0.1:9100", chan=10, version=7)
+clock 48kHz audio {
+    constant(0) -> iq
+}"#,
+            &reg,
+        );
+        assert!(
+            cpp.contains("\"udp\", &_bind_state_iq, 7);"),
+            "versioned bind should pass contract_version to the adapter, got:\n{}",
+            cpp
+        );
+    }
+
     #[test]
     fn bind_out_emits_send_call() {
         let reg = test_registry();
@@ -3463,7 +5914,7 @@ clock 48kHz audio {
         let cpp = codegen_ok(
             r#"bind iq = udp("127.0.0.1:9100", chan=10)
 clock 48kHz audio {
-    @iq | binwrite("/dev/null")
+    @iq | binwrite("/dev/null", "float")
 }"#,
             &reg,
         );
@@ -3553,6 +6004,53 @@ clock 48kHz audio {
         );
     }
 
+    #[test]
+    fn library_mode_replaces_main_with_c_entry_points() {
+        let reg = test_registry();
+        let source = r#"bind iq = udp("127.0.0.1:9100", chan=10)
+bind rf = udp("127.0.0.1:9200", chan=20)
+clock 48kHz tx {
+    constant(0) -> iq
+}
+clock 48kHz rx {
+    @rf | binwrite("/dev/null", "float")
+}"#;
+        let options = CodegenOptions {
+            library: true,
+            ..Default::default()
+        };
+        let cpp = codegen_source_with_options(source, &reg, options)
+            .generated
+            .cpp_source;
+        assert!(!cpp.contains("int main("), "{}", cpp);
+        assert!(!cpp.contains("shell_main"), "{}", cpp);
+        assert!(cpp.contains("#include <pipit_library.h>"), "{}", cpp);
+        assert!(!cpp.contains("BindIoAdapter"), "{}", cpp);
+        assert!(
+            cpp.contains("static pipit::LibraryBindPort<int32_t, "),
+            "{}",
+            cpp
+        );
+        assert!(cpp.contains("_bind_io_iq.send("), "{}", cpp);
+        assert!(cpp.contains("_bind_io_rf.recv("), "{}", cpp);
+        assert!(
+            cpp.contains("extern \"C\" int pipit_start(void)"),
+            "{}",
+            cpp
+        );
+        assert!(cpp.contains("extern \"C\" int pipit_stop(void)"), "{}", cpp);
+        assert!(
+            cpp.contains("extern \"C\" int pipit_read_iq(int32_t* out)"),
+            "{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("extern \"C\" int pipit_write_rf(const float* in)"),
+            "{}",
+            cpp
+        );
+    }
+
     #[test]
     fn no_bind_preamble_unchanged() {
         let reg = test_registry();
@@ -3660,6 +6158,71 @@ clock 48kHz audio {
         );
     }
 
+    #[test]
+    fn bind_tcp_transport_emits_adapter() {
+        let reg = test_registry();
+        let result = codegen_source(
+            r#"bind iq = tcp("127.0.0.1:9100", backlog=4, reconnect=250)
+clock 48kHz audio {
+    constant(0) -> iq
+}"#,
+            &reg,
+        );
+        let cpp = &result.generated.cpp_source;
+        assert!(
+            !result
+                .diagnostics
+                .iter()
+                .any(|d| d.code.as_ref().map(|c| c.0) == Some("E0710")),
+            "tcp transport should NOT produce E0710 diagnostic"
+        );
+        assert!(
+            cpp.contains("#include <pipit_tcp.h>"),
+            "tcp bind should include pipit_tcp.h"
+        );
+        assert!(
+            cpp.contains("static pipit::TcpIoAdapter _bind_io_iq(\"iq\", true,"),
+            "tcp OUT bind should emit TcpIoAdapter instance, got:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("&_bind_state_iq, 4, 250);"),
+            "tcp adapter should carry backlog and reconnect, got:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("_bind_io_iq.send("),
+            "tcp bind should reuse the datagram send path"
+        );
+    }
+
+    #[test]
+    fn bind_tcp_defaults() {
+        let reg = test_registry();
+        let result = codegen_source(
+            r#"bind iq = tcp("127.0.0.1:9100")
+clock 48kHz audio {
+    @iq | binwrite("/dev/null", "float")
+}"#,
+            &reg,
+        );
+        let cpp = &result.generated.cpp_source;
+        assert!(
+            cpp.contains("static pipit::TcpIoAdapter _bind_io_iq(\"iq\", false,"),
+            "tcp IN bind should emit TcpIoAdapter instance, got:\n{}",
+            cpp
+        );
+        assert!(
+            cpp.contains("&_bind_state_iq, 1, 1000);"),
+            "tcp adapter should default backlog=1 and reconnect=1000, got:\n{}",
+            cpp
+        );
+        assert!(
+            !cpp.contains("#include <pipit_shm.h>"),
+            "tcp bind should not include pipit_shm.h"
+        );
+    }
+
     #[test]
     fn bind_no_endpoint_warning() {
         let reg = test_registry();
@@ -3700,4 +6263,38 @@ clock 48kHz audio {
             cpp
         );
     }
+
+    #[test]
+    fn family_buffer_reuse_coalesces_ring_buffers() {
+        let reg = test_registry();
+        let source = concat!(
+            "shared sig[3]\n",
+            "clock 1kHz writer { constant(0.0) -> sig[*] }\n",
+            "clock 1kHz reader { @sig[*] | stdout<float>() }\n",
+        );
+        let result = codegen_source_with_options(
+            source,
+            &reg,
+            CodegenOptions {
+                reuse_family_buffers: true,
+                ..Default::default()
+            },
+        );
+        let cpp = result.generated.cpp_source;
+        assert!(
+            cpp.contains("pipit::RingBuffer<float, 768, 1> _ringbuf_sig;"),
+            "family should share one ring buffer, got:\n{}",
+            cpp
+        );
+        assert!(
+            !cpp.contains("_ringbuf_sig__"),
+            "element ring buffers should be replaced, got:\n{}",
+            cpp
+        );
+        assert!(cpp.contains("_ringbuf_sig.write(") && cpp.contains("_ringbuf_sig.read(0, "));
+
+        // Default mode keeps one ring buffer per element.
+        let cpp = codegen_ok(source, &reg);
+        assert!(cpp.contains("_ringbuf_sig__0") && !cpp.contains("_ringbuf_sig;"));
+    }
 }
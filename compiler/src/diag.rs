@@ -278,13 +278,27 @@ pub mod codes {
     pub const E0033: DiagCode = DiagCode("E0033"); // star-writer conflicts with element-writer
     pub const E0034: DiagCode = DiagCode("E0034"); // duplicate shared array name
     pub const E0035: DiagCode = DiagCode("E0035"); // buffer index const not a non-negative integer
+    pub const E0036: DiagCode = DiagCode("E0036"); // include file not found or unreadable
+    pub const E0037: DiagCode = DiagCode("E0037"); // include cycle
+    pub const E0038: DiagCode = DiagCode("E0038"); // duplicate probe name
+    pub const E0039: DiagCode = DiagCode("E0039"); // invalid const array slice
+    pub const E0040: DiagCode = DiagCode("E0040"); // undefined bind in switch source
+    pub const E0041: DiagCode = DiagCode("E0041"); // unknown `set buffering` mode
+    pub const E0042: DiagCode = DiagCode("E0042"); // actor called with wrong argument count
+    pub const E0043: DiagCode = DiagCode("E0043"); // invalid tap gather
+    pub const E0044: DiagCode = DiagCode("E0044"); // invalid `set affinity` entry
+    pub const E0045: DiagCode = DiagCode("E0045"); // shape dimension arithmetic out of range
+    pub const E0046: DiagCode = DiagCode("E0046"); // invalid `set tick_alignment` value
     pub const W0001: DiagCode = DiagCode("W0001"); // define shadows actor
     pub const W0002: DiagCode = DiagCode("W0002"); // deprecated switch default clause
+    pub const W0003: DiagCode = DiagCode("W0003"); // const or param declared but never referenced
 
     // ── Type infer (E0100-E0199) ─────────────────────────────────────
     pub const E0100: DiagCode = DiagCode("E0100"); // unknown type
     pub const E0101: DiagCode = DiagCode("E0101"); // ambiguous polymorphic call (upstream context)
     pub const E0102: DiagCode = DiagCode("E0102"); // ambiguous polymorphic call (no context)
+    pub const E0103: DiagCode = DiagCode("E0103"); // actor placed after void-output sink
+    pub const E0104: DiagCode = DiagCode("E0104"); // gathered taps carry different wire types
 
     // ── Lower (E0200-E0299) ──────────────────────────────────────────
     pub const E0200: DiagCode = DiagCode("E0200"); // L1 type consistency
@@ -308,12 +322,23 @@ pub mod codes {
     pub const E0309: DiagCode = DiagCode("E0309"); // switch param non-int32 default
     pub const E0310: DiagCode = DiagCode("E0310"); // ctrl buffer type mismatch
     pub const E0311: DiagCode = DiagCode("E0311"); // bind target not referenced in any task
-    pub const E0312: DiagCode = DiagCode("E0312"); // bind contract conflict (readers disagree on type/shape/rate)
+    pub const E0312: DiagCode = DiagCode("E0312"); // bind contract conflict (readers disagree, or contradict a declared bind type)
+    pub const E0313: DiagCode = DiagCode("E0313"); // switch ctrl buffer not written in control block
+    pub const E0314: DiagCode = DiagCode("E0314"); // shape inference did not converge
+    pub const E0315: DiagCode = DiagCode("E0315"); // `set mem` percentage unresolvable (no --platform-memory, or over 100%)
     pub const W0300: DiagCode = DiagCode("W0300"); // inferred dim param ordering
+    pub const W0312: DiagCode = DiagCode("W0312"); // bounded and unbounded sources mixed
+    pub const W0313: DiagCode = DiagCode("W0313"); // modal task: mode does not write a shared buffer other modes write
+    pub const W0314: DiagCode = DiagCode("W0314"); // delay-free feedback loop allowed by @allow_combinational_loop
+    pub const W0315: DiagCode = DiagCode("W0315"); // actor input outpaces its producer past warn_buffer_tokens
 
     // ── Schedule (E0400-E0499, W0400-W0499) ──────────────────────────
     pub const E0400: DiagCode = DiagCode("E0400"); // unresolvable cycle
+    pub const E0401: DiagCode = DiagCode("E0401"); // atomic group spans subgraphs
+    pub const E0402: DiagCode = DiagCode("E0402"); // atomic group not a connected chain
+    pub const E0403: DiagCode = DiagCode("E0403"); // @persist outside a modal delay
     pub const W0400: DiagCode = DiagCode("W0400"); // unsustainable tick rate
+    pub const W0401: DiagCode = DiagCode("W0401"); // batched latency exceeds max_latency_ms
 
     // ── Graph (E0500-E0599) ──────────────────────────────────────────
     pub const E0500: DiagCode = DiagCode("E0500"); // tap not found in graph
@@ -331,6 +356,7 @@ pub mod codes {
     pub const E0710: DiagCode = DiagCode("E0710"); // bind: unsupported transport
     pub const E0711: DiagCode = DiagCode("E0711"); // bind: unsupported dtype for PPKT
     pub const E0712: DiagCode = DiagCode("E0712"); // bind: unresolved endpoint argument
+    pub const E0713: DiagCode = DiagCode("E0713"); // bind: `version` must be a non-negative integer literal
     pub const E0720: DiagCode = DiagCode("E0720"); // shm: missing required `slots` argument
     pub const E0721: DiagCode = DiagCode("E0721"); // shm: missing required `slot_bytes` argument
     pub const E0722: DiagCode = DiagCode("E0722"); // shm: `slots` must be > 0
@@ -338,6 +364,9 @@ pub mod codes {
     pub const E0724: DiagCode = DiagCode("E0724"); // shm: missing required name (positional arg)
     pub const E0725: DiagCode = DiagCode("E0725"); // shm: `slots`/`slot_bytes` must be integer literals
     pub const E0726: DiagCode = DiagCode("E0726"); // shm: `slot_bytes` must be a multiple of 8
+    pub const E0730: DiagCode = DiagCode("E0730"); // tcp: endpoint address has no port
+    pub const E0731: DiagCode = DiagCode("E0731"); // tcp: `backlog` must be a positive integer literal
+    pub const E0732: DiagCode = DiagCode("E0732"); // tcp: `reconnect` must be a non-negative integer literal
     pub const W0710: DiagCode = DiagCode("W0710"); // bind: no endpoint address (placeholder)
     pub const W0711: DiagCode = DiagCode("W0711"); // bind: dtype unresolved, no I/O adapter
 
@@ -345,14 +374,451 @@ pub mod codes {
     pub const ALL_CODES: &[DiagCode] = &[
         E0001, E0002, E0003, E0004, E0005, E0006, E0007, E0008, E0009, E0010, E0011, E0012, E0013,
         E0014, E0015, E0016, E0017, E0018, E0019, E0020, E0021, E0022, E0023, E0024, E0025, E0026,
-        E0027, E0028, E0029, E0030, E0031, E0032, E0033, E0034, E0035, W0001, W0002, E0100, E0101,
-        E0102, E0200, E0201, E0202, E0203, E0204, E0205, E0206, E0300, E0301, E0302, E0303, E0304,
-        E0305, E0306, E0307, E0308, E0309, E0310, E0311, E0312, W0300, E0400, W0400, E0500, E0600,
-        E0601, E0602, E0603, E0700, E0710, E0711, E0712, E0720, E0721, E0722, E0723, E0724, E0725,
-        E0726, W0710, W0711,
+        E0027, E0028, E0029, E0030, E0031, E0032, E0033, E0034, E0035, E0036, E0037, E0038, E0039,
+        E0040, E0041, E0042, E0043, E0044, E0045, E0046, W0001, W0002, W0003, E0100, E0101, E0102,
+        E0103, E0104, E0200, E0201, E0202, E0203, E0204, E0205, E0206, E0300, E0301, E0302, E0303,
+        E0304, E0305, E0306, E0307, E0308, E0309, E0310, E0311, E0312, E0313, E0314, E0315, W0300,
+        W0312, W0313, W0314, W0315, E0400, E0401, E0402, E0403, W0400, W0401, E0500, E0600, E0601,
+        E0602, E0603, E0700, E0710, E0711, E0712, E0713, E0720, E0721, E0722, E0723, E0724, E0725,
+        E0726, E0730, E0731, E0732, W0710, W0711,
     ];
 }
 
+// ── Code explanations (`pcc --explain`) ──────────────────────────────
+
+/// Long-form description of a diagnostic code, printed by `pcc --explain`.
+#[derive(Debug)]
+pub struct Explanation {
+    pub code: DiagCode,
+    /// One-line summary.
+    pub title: &'static str,
+    /// What the diagnostic means and how to fix it (one paragraph).
+    pub text: &'static str,
+    /// Minimal program that triggers the diagnostic, if one exists.
+    pub example: Option<&'static str>,
+}
+
+/// Explanations, in code order. Examples use the standard actor library
+/// (`std_actors.h`) unless their first line says otherwise.
+pub const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: codes::E0300,
+        title: "unresolved frame dimension",
+        text: "An actor's port shape uses a symbolic dimension (e.g. `N` in \
+               `IN(float, SHAPE(N))`) that nothing determines: no argument sets it, \
+               no `[...]` shape constraint fixes it, and no neighbour's shape can be \
+               propagated into it. Add a shape constraint or pass the dimension as an \
+               argument on one actor of the chain; the others infer it from there.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | fft() | mag() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0301,
+        title: "conflicting frame constraint from upstream",
+        text: "An actor's explicit shape constraint disagrees with the shape its \
+               upstream actor produces. Frame sizes flow along the pipe, so a `[256]` \
+               frame cannot feed an actor constrained to `[128]`. Drop one of the \
+               constraints, or insert an actor that changes the frame size.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | fft()[256] | mag()[128] | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0302,
+        title: "conflicting dimension (span-derived vs edge-inferred)",
+        text: "A dimension is fixed twice with different values: once by the length \
+               of an array argument (a coefficient list of 3 elements sets `N = 3`) \
+               and once by a shape constraint or a neighbour's shape. Make the array \
+               length and the constraint agree, or remove the constraint.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | fir([1.0, 2.0, 3.0])[4] | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0303,
+        title: "type mismatch at pipe",
+        text: "The producer's output type cannot reach the consumer's input type. \
+               Numeric types widen implicitly along the chain int8 < int16 < int32 < \
+               float < double and float < cfloat < cdouble, but never narrow, and \
+               `bool` never converts. Insert an actor that converts explicitly.",
+        example: Some(concat!(
+            "# with ACTOR(is_positive, IN(float, 1), OUT(bool, 1), ...) in scope\n",
+            "clock 1kHz t {\n",
+            "    constant(0.0) | is_positive() | fft(256) | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0304,
+        title: "SDF balance equation unsolvable",
+        text: "Every pipe is a synchronous-dataflow edge: per task tick, the tokens \
+               the producer writes (its repetition count times its output rate) must \
+               equal the tokens the consumer reads. When two paths from the same \
+               source rejoin with different rate changes, no repetition counts satisfy \
+               both edges. Below, `add` gets every sample through the tap `:a` but only \
+               one in four through `decimate(4)`. Resample so both paths carry the \
+               same rate.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | :a | decimate(4) | add(:a) | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0305,
+        title: "feedback loop with no delay",
+        text: "A tap feeds back into an actor upstream of itself, and no `delay` \
+               sits on the loop, so each firing would need its own output as input. \
+               Put a `delay(1, 0.0)` on the feedback path, or mark an actor of the \
+               loop `@allow_combinational_loop` if it does not read the fed-back value \
+               before writing (see W0314).",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | add(:fb) | :fb | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0306,
+        title: "shared buffer rate mismatch",
+        text: "The writer task of a shared buffer produces tokens at a different \
+               rate (clock times tokens per tick) than a reader task consumes them, so \
+               the ring buffer would eventually overflow or run dry. Change a clock, \
+               or resample on the reader side; the hint names the `interpolate`/`decimate` \
+               factors that reconcile the rates.",
+        example: Some(concat!(
+            "clock 1kHz a {\n",
+            "    constant(0.0) -> sig\n",
+            "}\n",
+            "clock 1kHz b {\n",
+            "    @sig | decimate(4) | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0307,
+        title: "shared memory pool exceeded",
+        text: "The shared buffers of the program need more bytes than the memory \
+               pool allows. The pool is 64MB unless `set mem` says otherwise. Raise \
+               `set mem`, or shrink frames or buffer depths.",
+        example: Some(concat!(
+            "set mem = 1KB\n",
+            "clock 1kHz a {\n",
+            "    constant(0.0)[256] -> frames\n",
+            "}\n",
+            "clock 1kHz b {\n",
+            "    @frames | fft()[256] | mag() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0308,
+        title: "param type mismatch",
+        text: "A runtime `param` is passed to an actor whose RUNTIME_PARAM has a \
+               different type than the param's default value suggests. Params keep \
+               their type for the whole run, so give the param a default of the type \
+               the actor expects.",
+        example: Some(concat!(
+            "param path = 1.0\n",
+            "clock 1kHz t {\n",
+            "    binread($path, \"f32\") | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0309,
+        title: "switch param has a non-int32 default",
+        text: "`switch($param, ...)` selects a mode by index, so the param must be an \
+               int32. Give it an integer default such as `param sel = 0`.",
+        example: Some(concat!(
+            "param sel = 0.5\n",
+            "clock 1kHz t {\n",
+            "    control {\n",
+            "        constant(0.0) | stdout()\n",
+            "    }\n",
+            "    mode a {\n",
+            "        constant(0.0) | stdout()\n",
+            "    }\n",
+            "    mode b {\n",
+            "        constant(1.0) | stdout()\n",
+            "    }\n",
+            "    switch($sel, a, b)\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0310,
+        title: "control buffer type mismatch",
+        text: "The buffer (or bind) a `switch` reads must carry int32 mode indices. \
+               Feed it from an actor that outputs int32, such as `detect()`; a \
+               `switch(@bind, ...)` bind must also be an IN bind.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    control {\n",
+            "        constant(0.0) | sample_delay(1) -> ctrl\n",
+            "    }\n",
+            "    mode a {\n",
+            "        constant(0.0) | stdout()\n",
+            "    }\n",
+            "    mode b {\n",
+            "        constant(1.0) | stdout()\n",
+            "    }\n",
+            "    switch(ctrl, a, b)\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0311,
+        title: "bind target not referenced in any task",
+        text: "A `bind` declares an external endpoint that no pipe reads (`@name`) or \
+               writes (`-> name`), so its type and rate cannot be inferred. Use it in \
+               a task or remove the declaration.",
+        example: Some(concat!(
+            "bind iq = udp(\"127.0.0.1:9100\")\n",
+            "clock 1kHz t {\n",
+            "    constant(0.0) | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0312,
+        title: "bind contract conflict",
+        text: "An IN bind's wire contract (type, shape, and rate) is inferred from the \
+               pipes that read it. When two readers need different contracts, the \
+               endpoint cannot serve both. Read the bind once and fan out through a \
+               tap, or convert in one of the readers. A declared bind type \
+               (`bind iq: cfloat[256] = ...`) replaces inference, and pipes that \
+               disagree with it are reported the same way.",
+        example: Some(concat!(
+            "bind iq = udp(\"127.0.0.1:9100\")\n",
+            "clock 1kHz a {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+            "clock 1kHz b {\n",
+            "    @iq | mag()[1] | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0313,
+        title: "switch ctrl buffer not written in the control block",
+        text: "A modal task switches on a buffer that only its own modes write. Modes \
+               run after the switch has chosen one of them, so the value could never \
+               select a mode. Write the ctrl buffer from the task's `control` block.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    control {\n",
+            "        constant(0.0) | stdout()\n",
+            "    }\n",
+            "    mode a {\n",
+            "        constant(0.0) | detect() -> ctrl\n",
+            "    }\n",
+            "    mode b {\n",
+            "        constant(1.0) | detect() -> ctrl\n",
+            "    }\n",
+            "    switch(ctrl, a, b)\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0314,
+        title: "shape inference did not converge",
+        text: "Internal: edge shape inference ran out of its step budget (16 steps \
+               per subgraph node) without reaching a fixpoint. Add explicit shape \
+               constraints to the actors of the reported task and please report the \
+               program.",
+        example: None,
+    },
+    Explanation {
+        code: codes::E0315,
+        title: "`set mem` percentage cannot be resolved",
+        text: "`set mem = N%` sizes the memory pool relative to the target's memory, \
+               which pcc only knows from `--platform-memory`. Pass that flag, use an \
+               absolute size such as `set mem = 16MB`, or keep the percentage at or \
+               below 100%.",
+        example: Some(concat!(
+            "set mem = 50%\n",
+            "clock 1kHz t {\n",
+            "    constant(0.0) | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::W0312,
+        title: "bounded and unbounded sources mixed",
+        text: "A task reads shared buffers fed by a source that terminates (such as \
+               `binread`) and by one that runs forever. When the bounded source ends, \
+               the task stops receiving part of its input while the rest keeps coming. \
+               Usually intended only for test setups.",
+        example: Some(concat!(
+            "clock 1kHz replay {\n",
+            "    binread(\"in.bin\", \"float\") -> samples\n",
+            "}\n",
+            "clock 1kHz gen {\n",
+            "    constant(0.0) -> offset\n",
+            "}\n",
+            "clock 1kHz mix {\n",
+            "    @samples | stdout()\n",
+            "    @offset | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::W0313,
+        title: "mode does not write a buffer other modes write",
+        text: "One mode of a modal task writes a shared buffer and another does not, \
+               so readers of that buffer stall while the silent mode is active. Write \
+               the buffer from every mode, or move the write into the control block.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    control {\n",
+            "        constant(0.0) | detect() -> ctrl\n",
+            "    }\n",
+            "    mode a {\n",
+            "        constant(0.0) -> out\n",
+            "    }\n",
+            "    mode b {\n",
+            "        constant(1.0) | stdout()\n",
+            "    }\n",
+            "    switch(ctrl, a, b)\n",
+            "}\n",
+            "clock 1kHz sink {\n",
+            "    @out | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::W0314,
+        title: "delay-free feedback loop allowed",
+        text: "A feedback loop has no `delay`, which is normally E0305, but an actor \
+               in it carries `@allow_combinational_loop`. The generated code runs the \
+               loop in one pass; this is correct only if that actor writes before it \
+               reads the fed-back value.",
+        example: Some(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | add(:fb) @allow_combinational_loop | :fb | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::W0315,
+        title: "actor input outpaces its producer",
+        text: "With `set warn_buffer_tokens = N`, an actor that consumes more tokens \
+               per firing than its upstream producer supplies is flagged when the edge \
+               between them must hold more than N tokens (at least lcm of the two \
+               rates). Raise the producer's output frame or lower the consumer's input \
+               frame to keep the buffer small.",
+        example: Some(concat!(
+            "set warn_buffer_tokens = 1024\n",
+            "clock 1kHz t {\n",
+            "    constant(0.0) | abs() | fft(4096) | c2r() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0720,
+        title: "shm bind: missing `slots`",
+        text: "A `shm(...)` endpoint needs the ring size up front: \
+               `shm(\"name\", slots=<int>, slot_bytes=<int>)`. Add `slots`, the number \
+               of slots in the shared ring.",
+        example: Some(concat!(
+            "bind iq = shm(\"rx.iq\", slot_bytes=4096)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0721,
+        title: "shm bind: missing `slot_bytes`",
+        text: "A `shm(...)` endpoint needs the size of each ring slot in bytes. Add \
+               `slot_bytes`, large enough for one frame of the bound signal.",
+        example: Some(concat!(
+            "bind iq = shm(\"rx.iq\", slots=1024)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0722,
+        title: "shm bind: `slots` must be > 0",
+        text: "A shared-memory ring with no slots cannot carry data. Use a positive \
+               slot count; a few frames of headroom absorb scheduling jitter between \
+               the two processes.",
+        example: Some(concat!(
+            "bind iq = shm(\"rx.iq\", slots=0, slot_bytes=4096)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0723,
+        title: "shm bind: `slot_bytes` must be > 0",
+        text: "Each ring slot holds one frame of the bound signal, so it needs a \
+               positive size (a multiple of 8, see E0726).",
+        example: Some(concat!(
+            "bind iq = shm(\"rx.iq\", slots=1024, slot_bytes=0)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0724,
+        title: "shm bind: missing name",
+        text: "The first positional argument of `shm(...)` names the shared-memory \
+               object both processes open, e.g. `shm(\"rx.iq\", ...)`. Add it.",
+        example: Some(concat!(
+            "bind iq = shm(slots=1024, slot_bytes=4096)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0725,
+        title: "shm bind: `slots`/`slot_bytes` must be integer literals",
+        text: "The ring geometry is part of the wire contract both processes check at \
+               attach time, so it must be written as integer literals; const \
+               references and fractional values are rejected.",
+        example: Some(concat!(
+            "const SLOTS = 1024\n",
+            "bind iq = shm(\"rx.iq\", slots=SLOTS, slot_bytes=4096)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+    Explanation {
+        code: codes::E0726,
+        title: "shm bind: `slot_bytes` must be a multiple of 8",
+        text: "Slots are accessed with 8-byte atomic fields, so each slot must start \
+               on an 8-byte boundary. Round `slot_bytes` up to a multiple of 8.",
+        example: Some(concat!(
+            "bind iq = shm(\"rx.iq\", slots=1024, slot_bytes=100)\n",
+            "clock 1kHz t {\n",
+            "    @iq | detect() | stdout()\n",
+            "}\n",
+        )),
+    },
+];
+
+/// Look up the explanation for a code such as `E0304` (case-insensitive).
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    EXPLANATIONS
+        .iter()
+        .find(|e| e.code.0.eq_ignore_ascii_case(code.trim()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,11 +921,36 @@ mod tests {
 
     #[test]
     fn code_count() {
-        // 25 resolve errors + 10 spawn/shared errors (E0026-E0035) + 2 resolve warnings
-        // + 3 type_infer + 7 lower + 13 analyze errors + 1 analyze warning
-        // + 1 schedule error + 1 schedule warning + 1 graph + 4 pipeline
-        // + 1 usage (E0700) + 3 codegen errors (E0710-E0712) + 7 shm errors (E0720-E0726)
-        // + 2 codegen warnings (W0710-W0711)
-        assert_eq!(codes::ALL_CODES.len(), 81);
+        // 25 resolve errors + 10 spawn/shared errors (E0026-E0035) + 2 include errors
+        // (E0036-E0037) + 1 duplicate probe (E0038) + 1 const slice (E0039)
+        // + 1 switch bind source (E0040) + 1 buffering mode (E0041) + 1 arity (E0042)
+        // + 1 tap gather (E0043) + 1 affinity (E0044) + 1 shape arithmetic (E0045)
+        // + 1 tick alignment (E0046)
+        // + 3 resolve warnings
+        // + 5 type_infer + 7 lower + 14 analyze errors + 3 analyze warnings
+        // + 4 schedule errors + 2 schedule warnings + 1 graph + 4 pipeline
+        // + 1 usage (E0700) + 4 codegen/bind errors (E0710-E0713) + 7 shm errors (E0720-E0726)
+        // + 3 tcp errors (E0730-E0732) + 2 codegen warnings (W0710-W0711)
+        assert_eq!(codes::ALL_CODES.len(), 110);
+    }
+
+    #[test]
+    fn explanations_cover_analysis_and_shm_codes() {
+        let mut seen = std::collections::HashSet::new();
+        for e in EXPLANATIONS {
+            assert!(
+                codes::ALL_CODES.contains(&e.code),
+                "{} is not assigned",
+                e.code
+            );
+            assert!(seen.insert(e.code), "{} explained twice", e.code);
+        }
+        for code in codes::ALL_CODES {
+            if code.0.starts_with("E03") || code.0.starts_with("E072") {
+                assert!(explain(code.0).is_some(), "{} has no explanation", code);
+            }
+        }
+        assert_eq!(explain(" e0304").map(|e| e.code), Some(codes::E0304));
+        assert!(explain("E9999").is_none());
     }
 }
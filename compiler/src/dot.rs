@@ -1,20 +1,34 @@
-// dot.rs — Graphviz DOT output for Pipit SDF graphs
+// dot.rs — Graphviz DOT, Mermaid flowchart, and JSON output for Pipit SDF graphs
 //
-// Transforms a ProgramGraph into DOT format suitable for rendering
-// with `dot`, `neato`, or other Graphviz layout engines.
+// Transforms a ProgramGraph into DOT format (per-task clusters, or a
+// whole-program task call graph) suitable for rendering
+// with `dot`, `neato`, or other Graphviz layout engines, into a
+// Mermaid `flowchart` for Markdown-based docs, or into structured JSON
+// for external visualizers.
 //
 // Preconditions: `graph` is a fully constructed ProgramGraph.
-// Postconditions: returns a valid DOT string representing the graph.
+// Postconditions: returns a valid DOT (or Mermaid, or JSON) representation of the graph.
 // Failure modes: none (pure string formatting).
 // Side effects: none.
 
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 
+use crate::analyze::{node_display_name, NodePortRates};
 use crate::graph::*;
 
 /// Emit the program graph as a Graphviz DOT string.
 pub fn emit_dot(graph: &ProgramGraph) -> String {
+    render_dot(graph, false)
+}
+
+/// Emit the program graph as DOT with forks and probes collapsed into
+/// direct edges labeled with the tap/probe names they passed through.
+pub fn emit_dot_collapsed(graph: &ProgramGraph) -> String {
+    render_dot(graph, true)
+}
+
+fn render_dot(graph: &ProgramGraph, collapse: bool) -> String {
     let mut buf = String::new();
     writeln!(buf, "digraph pipit {{").unwrap();
     writeln!(buf, "    rankdir=LR;").unwrap();
@@ -36,7 +50,15 @@ pub fn emit_dot(graph: &ProgramGraph) -> String {
                 writeln!(buf, "        label=\"task: {task_name}\";").unwrap();
                 writeln!(buf, "        style=rounded;").unwrap();
                 writeln!(buf, "        color=gray50;").unwrap();
-                write_subgraph_contents(&mut buf, &sanitized, "", sub, &cycle_edges, "        ");
+                write_subgraph_contents(
+                    &mut buf,
+                    &sanitized,
+                    "",
+                    sub,
+                    &cycle_edges,
+                    "        ",
+                    collapse,
+                );
                 writeln!(buf, "    }}").unwrap();
             }
             TaskGraph::Modal { control, modes } => {
@@ -59,6 +81,7 @@ pub fn emit_dot(graph: &ProgramGraph) -> String {
                     control,
                     &cycle_edges,
                     "            ",
+                    collapse,
                 );
                 writeln!(buf, "        }}").unwrap();
 
@@ -78,6 +101,7 @@ pub fn emit_dot(graph: &ProgramGraph) -> String {
                         sub,
                         &cycle_edges,
                         "            ",
+                        collapse,
                     );
                     writeln!(buf, "        }}").unwrap();
                 }
@@ -115,6 +139,352 @@ pub fn emit_dot(graph: &ProgramGraph) -> String {
     buf
 }
 
+// ── Call graph ──────────────────────────────────────────────────────────────
+
+/// Emit the whole-program task graph as a Graphviz DOT string.
+///
+/// Nodes are tasks; each inter-task edge becomes an arrow from the writer
+/// task to the reader task labeled with the buffer name and, when
+/// `buffer_bytes` knows it, the buffer size. `binds` lists `(name,
+/// transport)` pairs; tasks writing or reading a bind connect to an external
+/// node drawn with a distinct shape. Nodes and edges are sorted so the
+/// output is byte-stable across runs.
+pub fn emit_callgraph(
+    graph: &ProgramGraph,
+    buffer_bytes: Option<&HashMap<String, u64>>,
+    binds: &[(&str, &str)],
+) -> String {
+    let mut buf = String::new();
+    writeln!(buf, "digraph pipit_callgraph {{").unwrap();
+    writeln!(buf, "    rankdir=LR;").unwrap();
+    writeln!(buf, "    node [fontname=\"Helvetica\", fontsize=10];").unwrap();
+    writeln!(buf, "    edge [fontname=\"Helvetica\", fontsize=9];").unwrap();
+
+    let mut task_names: Vec<&String> = graph.tasks.keys().collect();
+    task_names.sort();
+    writeln!(buf).unwrap();
+    for task_name in &task_names {
+        writeln!(
+            buf,
+            "    task_{} [label=\"{}\", shape=box, style=rounded];",
+            sanitize(task_name),
+            task_name
+        )
+        .unwrap();
+    }
+
+    let mut bind_list: Vec<&(&str, &str)> = binds.iter().collect();
+    bind_list.sort();
+    if !bind_list.is_empty() {
+        writeln!(buf).unwrap();
+        for (name, transport) in &bind_list {
+            writeln!(
+                buf,
+                "    bind_{} [label=\"{}\\n{}\", shape=cds, style=dashed];",
+                sanitize(name),
+                name,
+                transport
+            )
+            .unwrap();
+        }
+    }
+
+    let label = |buffer: &str| match buffer_bytes.and_then(|b| b.get(buffer)) {
+        Some(bytes) => format!("{buffer}\\n{bytes} B"),
+        None => buffer.to_string(),
+    };
+
+    // (source node, target node, buffer) — deduplicated and sorted
+    let mut edges: Vec<(String, String, &str)> = graph
+        .inter_task_edges
+        .iter()
+        .map(|ite| {
+            (
+                format!("task_{}", sanitize(&ite.writer_task)),
+                format!("task_{}", sanitize(&ite.reader_task)),
+                ite.buffer_name.as_str(),
+            )
+        })
+        .collect();
+    let bind_names: HashSet<&str> = binds.iter().map(|(name, _)| *name).collect();
+    for task_name in &task_names {
+        let task_id = format!("task_{}", sanitize(task_name));
+        for sub in crate::subgraph_index::subgraphs_of(&graph.tasks[*task_name]) {
+            for node in &sub.nodes {
+                match &node.kind {
+                    NodeKind::BufferWrite { buffer_name }
+                        if bind_names.contains(buffer_name.as_str()) =>
+                    {
+                        let bind_id = format!("bind_{}", sanitize(buffer_name));
+                        edges.push((task_id.clone(), bind_id, buffer_name));
+                    }
+                    NodeKind::BufferRead { buffer_name }
+                        if bind_names.contains(buffer_name.as_str()) =>
+                    {
+                        let bind_id = format!("bind_{}", sanitize(buffer_name));
+                        edges.push((bind_id, task_id.clone(), buffer_name));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    edges.sort();
+    edges.dedup();
+    if !edges.is_empty() {
+        writeln!(buf).unwrap();
+        for (src, tgt, buffer) in &edges {
+            writeln!(buf, "    {src} -> {tgt} [label=\"{}\"];", label(buffer)).unwrap();
+        }
+    }
+
+    writeln!(buf, "}}").unwrap();
+    buf
+}
+
+// ── Mermaid ─────────────────────────────────────────────────────────────────
+
+/// Emit the program graph as a Mermaid `flowchart TD` string.
+///
+/// One `subgraph` block per task (nested blocks for modal control/modes).
+/// Forks render as stadiums `([ ])`, probes as hexagons `{{ }}`.
+/// When `rates` is given, intra-task edges are labeled with the tokens
+/// produced per source firing. Inter-task edges are dotted arrows labeled
+/// with the buffer name.
+pub fn emit_mermaid(
+    graph: &ProgramGraph,
+    rates: Option<&HashMap<NodeId, NodePortRates>>,
+) -> String {
+    let mut buf = String::new();
+    writeln!(buf, "flowchart TD").unwrap();
+
+    let mut task_names: Vec<&String> = graph.tasks.keys().collect();
+    task_names.sort();
+
+    for task_name in &task_names {
+        let sanitized = sanitize(task_name);
+        writeln!(
+            buf,
+            "    subgraph task_{sanitized}[\"task: {}\"]",
+            mermaid_escape(task_name)
+        )
+        .unwrap();
+        match &graph.tasks[*task_name] {
+            TaskGraph::Pipeline(sub) => {
+                write_mermaid_subgraph(&mut buf, &sanitized, "", sub, rates, "        ");
+            }
+            TaskGraph::Modal { control, modes } => {
+                writeln!(
+                    buf,
+                    "        subgraph task_{sanitized}_control[\"control\"]"
+                )
+                .unwrap();
+                write_mermaid_subgraph(
+                    &mut buf,
+                    &sanitized,
+                    "control",
+                    control,
+                    rates,
+                    "            ",
+                );
+                writeln!(buf, "        end").unwrap();
+                for (mode_name, sub) in modes {
+                    let mode_san = sanitize(mode_name);
+                    writeln!(
+                        buf,
+                        "        subgraph task_{sanitized}_{mode_san}[\"mode: {}\"]",
+                        mermaid_escape(mode_name)
+                    )
+                    .unwrap();
+                    write_mermaid_subgraph(
+                        &mut buf,
+                        &sanitized,
+                        &mode_san,
+                        sub,
+                        rates,
+                        "            ",
+                    );
+                    writeln!(buf, "        end").unwrap();
+                }
+            }
+        }
+        writeln!(buf, "    end").unwrap();
+    }
+
+    for ite in &graph.inter_task_edges {
+        let writer_prefix = find_node_prefix(
+            &graph.tasks[&ite.writer_task],
+            &sanitize(&ite.writer_task),
+            ite.writer_node,
+        );
+        let reader_prefix = find_node_prefix(
+            &graph.tasks[&ite.reader_task],
+            &sanitize(&ite.reader_task),
+            ite.reader_node,
+        );
+        writeln!(
+            buf,
+            "    {}_n{} -.->|\"{}\"| {}_n{}",
+            writer_prefix,
+            ite.writer_node.0,
+            mermaid_escape(&ite.buffer_name),
+            reader_prefix,
+            ite.reader_node.0,
+        )
+        .unwrap();
+    }
+    buf
+}
+
+/// Write the nodes and edges of one subgraph as Mermaid statements.
+fn write_mermaid_subgraph(
+    buf: &mut String,
+    task: &str,
+    prefix: &str,
+    sub: &Subgraph,
+    rates: Option<&HashMap<NodeId, NodePortRates>>,
+    indent: &str,
+) {
+    for node in &sub.nodes {
+        let id = dot_node_id(task, prefix, node.id);
+        let label = mermaid_escape(&node_label(&node.kind));
+        let shape = match node.kind {
+            NodeKind::Fork { .. } => format!("([\"{label}\"])"),
+            NodeKind::Probe { .. } => format!("{{{{\"{label}\"}}}}"),
+            _ => format!("[\"{label}\"]"),
+        };
+        writeln!(buf, "{indent}{id}{shape}").unwrap();
+    }
+    for edge in &sub.edges {
+        let src = dot_node_id(task, prefix, edge.source);
+        let tgt = dot_node_id(task, prefix, edge.target);
+        match rates.and_then(|r| edge_rate(r, edge)) {
+            Some(rate) => writeln!(buf, "{indent}{src} -->|{rate}| {tgt}").unwrap(),
+            None => writeln!(buf, "{indent}{src} --> {tgt}").unwrap(),
+        }
+    }
+}
+
+/// Tokens carried per source firing: the source's output rate, falling back
+/// to the target's input rate (forks and probes have no rates of their own).
+fn edge_rate(rates: &HashMap<NodeId, NodePortRates>, edge: &Edge) -> Option<u32> {
+    rates
+        .get(&edge.source)
+        .and_then(|r| r.out_rate)
+        .or_else(|| rates.get(&edge.target).and_then(|r| r.in_rate))
+}
+
+/// Escape a quoted Mermaid label (`"` would end the string).
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────────
+
+/// Serialize the program graph for `--emit graph-json`.
+///
+/// Tasks are keyed by name; each lists its subgraphs (`pipeline`, or
+/// `control` and `mode:<name>`) with nodes and edges sorted by node id.
+/// `rates` supplies each node's `in_rate` / `out_rate` (null when unknown).
+/// Cycles and inter-task edges are sorted the same way, so the output is
+/// byte-stable across runs.
+pub fn emit_graph_json(
+    graph: &ProgramGraph,
+    rates: Option<&HashMap<NodeId, NodePortRates>>,
+) -> serde_json::Value {
+    let mut task_names: Vec<&String> = graph.tasks.keys().collect();
+    task_names.sort();
+    let mut tasks = serde_json::Map::new();
+    for task_name in task_names {
+        let subgraphs = match &graph.tasks[task_name] {
+            TaskGraph::Pipeline(sub) => vec![subgraph_json("pipeline", sub, rates)],
+            TaskGraph::Modal { control, modes } => {
+                let mut subgraphs = vec![subgraph_json("control", control, rates)];
+                for (mode_name, sub) in modes {
+                    subgraphs.push(subgraph_json(&format!("mode:{mode_name}"), sub, rates));
+                }
+                subgraphs
+            }
+        };
+        tasks.insert(
+            task_name.clone(),
+            serde_json::json!({ "subgraphs": subgraphs }),
+        );
+    }
+
+    let mut cycles: Vec<Vec<u32>> = graph
+        .cycles
+        .iter()
+        .map(|cycle| cycle.iter().map(|id| id.0).collect())
+        .collect();
+    cycles.sort();
+
+    let mut inter_task: Vec<&InterTaskEdge> = graph.inter_task_edges.iter().collect();
+    inter_task.sort_by_key(|e| (e.writer_node.0, e.reader_node.0));
+    let inter_task_edges: Vec<serde_json::Value> = inter_task
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "buffer": e.buffer_name,
+                "writer_task": e.writer_task,
+                "writer_node": e.writer_node.0,
+                "reader_task": e.reader_task,
+                "reader_node": e.reader_node.0,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "tasks": tasks,
+        "cycles": cycles,
+        "inter_task_edges": inter_task_edges,
+    })
+}
+
+fn subgraph_json(
+    label: &str,
+    sub: &Subgraph,
+    rates: Option<&HashMap<NodeId, NodePortRates>>,
+) -> serde_json::Value {
+    let mut nodes: Vec<&Node> = sub.nodes.iter().collect();
+    nodes.sort_by_key(|n| n.id.0);
+    let nodes: Vec<serde_json::Value> = nodes
+        .iter()
+        .map(|node| {
+            let (kind, name) = crate::graph_query::kind_and_name(&node.kind);
+            let rate = rates.and_then(|r| r.get(&node.id));
+            serde_json::json!({
+                "id": node.id.0,
+                "kind": kind,
+                "name": name,
+                "label": node_display_name(node),
+                "span": span_json(node.span),
+                "in_rate": rate.and_then(|r| r.in_rate),
+                "out_rate": rate.and_then(|r| r.out_rate),
+            })
+        })
+        .collect();
+
+    let mut edges: Vec<&Edge> = sub.edges.iter().collect();
+    edges.sort_by_key(|e| (e.source.0, e.target.0));
+    let edges: Vec<serde_json::Value> = edges
+        .iter()
+        .map(|edge| {
+            serde_json::json!({
+                "source": edge.source.0,
+                "target": edge.target.0,
+                "span": span_json(edge.span),
+            })
+        })
+        .collect();
+
+    serde_json::json!({ "label": label, "nodes": nodes, "edges": edges })
+}
+
+fn span_json(span: crate::ast::Span) -> serde_json::Value {
+    serde_json::json!({ "start": span.start, "end": span.end })
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────────
 
 /// Sanitize a name to valid DOT identifier characters.
@@ -140,7 +510,7 @@ fn dot_node_id(task: &str, prefix: &str, node: NodeId) -> String {
 }
 
 /// Return the node label for a given NodeKind.
-fn node_label(kind: &NodeKind) -> String {
+pub fn node_label(kind: &NodeKind) -> String {
     match kind {
         NodeKind::Actor { name, .. } => name.clone(),
         NodeKind::Fork { tap_name } => format!(":{tap_name}"),
@@ -180,7 +550,13 @@ fn write_subgraph_contents(
     sub: &Subgraph,
     cycle_edges: &HashSet<(u32, u32)>,
     indent: &str,
+    collapse: bool,
 ) {
+    if collapse {
+        write_collapsed_contents(buf, task, prefix, sub, cycle_edges, indent);
+        return;
+    }
+
     // Identify probe nodes and build their bypass mapping.
     let probe_ids: HashSet<u32> = sub
         .nodes
@@ -229,7 +605,8 @@ fn write_subgraph_contents(
         let src = dot_node_id(task, prefix, edge.source);
         let tgt = dot_node_id(task, prefix, edge.target);
         if cycle_edges.contains(&(edge.source.0, edge.target.0)) {
-            writeln!(buf, "{indent}{src} -> {tgt} [style=bold, color=blue];").unwrap();
+            let attrs = cycle_edge_attrs(sub, edge.target);
+            writeln!(buf, "{indent}{src} -> {tgt} [{attrs}];").unwrap();
         } else {
             writeln!(buf, "{indent}{src} -> {tgt};").unwrap();
         }
@@ -260,6 +637,63 @@ fn write_subgraph_contents(
     }
 }
 
+/// Write a subgraph with forks and probes collapsed (`--collapse-passthrough`).
+///
+/// A collapsed edge is drawn as a cycle edge when a cycle edge leaves its
+/// source and another enters its target.
+fn write_collapsed_contents(
+    buf: &mut String,
+    task: &str,
+    prefix: &str,
+    sub: &Subgraph,
+    cycle_edges: &HashSet<(u32, u32)>,
+    indent: &str,
+) {
+    for node in sub.nodes.iter().filter(|n| !is_passthrough(&n.kind)) {
+        let id = dot_node_id(task, prefix, node.id);
+        let attrs = node_attrs(&node.kind);
+        writeln!(buf, "{indent}{id} [{attrs}];").unwrap();
+    }
+    writeln!(buf).unwrap();
+
+    for edge in collapse_passthrough(sub) {
+        let src = dot_node_id(task, prefix, edge.source);
+        let tgt = dot_node_id(task, prefix, edge.target);
+        let on_cycle = cycle_edges.iter().any(|&(s, _)| s == edge.source.0)
+            && cycle_edges.iter().any(|&(_, t)| t == edge.target.0);
+        let mut attrs = Vec::new();
+        if !edge.via.is_empty() {
+            attrs.push(format!("label=\"{}\"", edge.via.join(" ")));
+            if on_cycle {
+                attrs.push(CYCLE_EDGE_STYLE.to_string());
+            }
+        } else if on_cycle {
+            attrs.push(cycle_edge_attrs(sub, edge.target));
+        }
+        if attrs.is_empty() {
+            writeln!(buf, "{indent}{src} -> {tgt};").unwrap();
+        } else {
+            writeln!(buf, "{indent}{src} -> {tgt} [{}];", attrs.join(", ")).unwrap();
+        }
+    }
+}
+
+/// DOT style for an edge on a feedback cycle.
+const CYCLE_EDGE_STYLE: &str = "style=bold, color=red";
+
+/// Attributes for a cycle edge: red, plus `label="delay"` where it enters
+/// the `delay` actor that closes the loop.
+fn cycle_edge_attrs(sub: &Subgraph, target: NodeId) -> String {
+    let enters_delay = sub.nodes.iter().any(|n| {
+        n.id == target && matches!(&n.kind, NodeKind::Actor { name, .. } if name == "delay")
+    });
+    if enters_delay {
+        format!("{CYCLE_EDGE_STYLE}, label=\"delay\"")
+    } else {
+        CYCLE_EDGE_STYLE.to_string()
+    }
+}
+
 /// Collect cycle edges that belong to a specific subgraph.
 fn cycle_edges_for_subgraph(sub: &Subgraph, all_cycles: &[Vec<NodeId>]) -> HashSet<(u32, u32)> {
     let node_ids: HashSet<u32> = sub.nodes.iter().map(|n| n.id.0).collect();
@@ -332,6 +766,14 @@ mod tests {
     }
 
     fn build_and_emit(source: &str, registry: &Registry) -> String {
+        build_and_emit_with(source, registry, emit_dot)
+    }
+
+    fn build_and_emit_with(
+        source: &str,
+        registry: &Registry,
+        emit: fn(&ProgramGraph) -> String,
+    ) -> String {
         let parse_result = crate::parser::parse(source);
         assert!(
             parse_result.errors.is_empty(),
@@ -363,7 +805,7 @@ mod tests {
             "graph errors: {:?}",
             graph_result.diagnostics
         );
-        emit_dot(&graph_result.graph)
+        emit(&graph_result.graph)
     }
 
     #[test]
@@ -430,6 +872,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn collapsed_dot_hides_forks_and_probes() {
+        let reg = test_registry();
+        let dot = build_and_emit_with(
+            "clock 1kHz t {\n    constant(0.0) | :raw | ?p | stdout()\n    :raw | stdout()\n}",
+            &reg,
+            emit_dot_collapsed,
+        );
+        assert!(!dot.contains("shape=diamond"), "fork drawn:\n{dot}");
+        assert!(!dot.contains("shape=circle"), "probe drawn:\n{dot}");
+        assert!(
+            dot.contains("t_n0 -> t_n3 [label=\":raw ?p\"];"),
+            "tap/probe names should label the collapsed edge:\n{dot}"
+        );
+        assert!(
+            dot.contains("[label=\":raw\"];"),
+            "second tap consumer keeps the tap label:\n{dot}"
+        );
+    }
+
+    #[test]
+    fn feedback_cycle_edges_are_red() {
+        let reg = test_registry();
+        let source = concat!(
+            "clock 1kHz iir {\n",
+            "    constant(0.0) | add(:fb) | mul(0.5) | :out | stdout()\n",
+            "    :out | delay(1, 0.0) | :fb\n",
+            "}",
+        );
+        let dot = build_and_emit(source, &reg);
+        let red: Vec<&str> = dot.lines().filter(|l| l.contains("color=red")).collect();
+        assert!(!red.is_empty(), "no red cycle edge:\n{dot}");
+        assert_eq!(
+            red.iter().filter(|l| l.contains("label=\"delay\"")).count(),
+            1,
+            "exactly the edge into delay is labeled:\n{dot}"
+        );
+        assert!(
+            dot.lines()
+                .filter(|l| l.contains("->") && l.contains("iir_n"))
+                .any(|l| !l.contains("color=red")),
+            "edges off the cycle stay unstyled:\n{dot}"
+        );
+
+        let collapsed = build_and_emit_with(source, &reg, emit_dot_collapsed);
+        assert!(
+            collapsed.contains("style=bold, color=red"),
+            "collapsed view keeps the cycle highlight:\n{collapsed}"
+        );
+    }
+
     #[test]
     fn modal_nested_clusters() {
         let reg = test_registry();
@@ -536,4 +1029,166 @@ mod tests {
         let dot2 = build_and_emit(source, &reg);
         assert_eq!(dot1, dot2, "DOT output is not deterministic");
     }
+
+    #[test]
+    fn mermaid_flowchart_structure() {
+        let reg = test_registry();
+        let mermaid = build_and_emit_with(
+            concat!(
+                "clock 1kHz writer {\n",
+                "    constant(0.0) | :raw | ?p | fft(256) -> sig\n",
+                "    :raw | stdout()\n",
+                "}\n",
+                "clock 1kHz reader {\n",
+                "    @sig | c2r() | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+            |g| emit_mermaid(g, None),
+        );
+        assert!(mermaid.starts_with("flowchart TD\n"), "got:\n{mermaid}");
+        assert!(mermaid.contains("subgraph task_writer[\"task: writer\"]"));
+        assert!(mermaid.contains("subgraph task_reader[\"task: reader\"]"));
+        assert_eq!(mermaid.matches("\n    end\n").count(), 2);
+        assert!(mermaid.contains("([\":raw\"])"), "fork stadium:\n{mermaid}");
+        assert!(mermaid.contains("{{\"?p\"}}"), "probe hexagon:\n{mermaid}");
+        assert!(mermaid.contains("[\"fft\"]"));
+        assert!(
+            mermaid
+                .lines()
+                .any(|l| l.contains(" -.->|\"sig\"| reader_n")),
+            "dotted inter-task edge:\n{mermaid}"
+        );
+        assert!(!mermaid.contains("-->|"), "no rates without analysis");
+    }
+
+    #[test]
+    fn graph_json_lists_sorted_nodes_edges_and_rates() {
+        let reg = test_registry();
+        let json = build_and_emit_with(
+            "clock 1kHz t {\n    constant(0.0) | fft(256) | ?spec | c2r() -> sig\n}\nclock 1kHz r {\n    @sig | stdout()\n}",
+            &reg,
+            |g| {
+                let TaskGraph::Pipeline(sub) = &g.tasks["t"] else {
+                    unreachable!()
+                };
+                let mut rates = HashMap::new();
+                for node in &sub.nodes {
+                    if matches!(&node.kind, NodeKind::Actor { name, .. } if name == "fft") {
+                        let r = NodePortRates {
+                            in_rate: Some(256),
+                            out_rate: Some(1),
+                        };
+                        rates.insert(node.id, r);
+                    }
+                }
+                emit_graph_json(g, Some(&rates)).to_string()
+            },
+        );
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let sub = &json["tasks"]["t"]["subgraphs"][0];
+        assert_eq!(sub["label"], "pipeline");
+        let nodes = sub["nodes"].as_array().unwrap();
+        let ids: Vec<u64> = nodes.iter().map(|n| n["id"].as_u64().unwrap()).collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]), "nodes sorted: {ids:?}");
+        let fft = nodes.iter().find(|n| n["name"] == "fft").unwrap();
+        assert_eq!(fft["kind"], "actor");
+        assert_eq!(fft["in_rate"], 256);
+        assert_eq!(fft["out_rate"], 1);
+        assert!(fft["span"]["end"].as_u64() > fft["span"]["start"].as_u64());
+        let probe = nodes.iter().find(|n| n["kind"] == "probe").unwrap();
+        assert_eq!(probe["label"], "?spec");
+        assert!(probe["in_rate"].is_null());
+        let edges = sub["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), nodes.len() - 1);
+        let inter = json["inter_task_edges"].as_array().unwrap();
+        assert_eq!(inter.len(), 1);
+        assert_eq!(inter[0]["buffer"], "sig");
+        assert_eq!(inter[0]["reader_task"], "r");
+        assert_eq!(json["cycles"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn callgraph_connects_tasks_and_binds() {
+        let reg = test_registry();
+        let dot = build_and_emit_with(
+            concat!(
+                "clock 1kHz writer {\n",
+                "    constant(0.0) | fft(256) | c2r() -> sig\n",
+                "}\n",
+                "clock 1kHz reader {\n",
+                "    @sig | stdout()\n",
+                "}\n",
+                "clock 1kHz out {\n",
+                "    constant(0.0) -> tx\n",
+                "}\n",
+                "clock 1kHz idle {\n",
+                "    constant(0.0) | stdout()\n",
+                "}\n",
+            ),
+            &reg,
+            |g| {
+                let bytes = HashMap::from([("sig".to_string(), 2048)]);
+                emit_callgraph(g, Some(&bytes), &[("tx", "udp")])
+            },
+        );
+        assert!(dot.starts_with("digraph pipit_callgraph {"), "got:\n{dot}");
+        let tasks: Vec<&str> = dot
+            .lines()
+            .filter(|l| l.contains("shape=box"))
+            .map(str::trim)
+            .collect();
+        assert_eq!(
+            tasks,
+            [
+                "task_idle [label=\"idle\", shape=box, style=rounded];",
+                "task_out [label=\"out\", shape=box, style=rounded];",
+                "task_reader [label=\"reader\", shape=box, style=rounded];",
+                "task_writer [label=\"writer\", shape=box, style=rounded];",
+            ],
+            "tasks sorted by name"
+        );
+        assert!(
+            dot.contains("bind_tx [label=\"tx\\nudp\", shape=cds, style=dashed];"),
+            "bind drawn as external node:\n{dot}"
+        );
+        assert!(
+            dot.contains("task_writer -> task_reader [label=\"sig\\n2048 B\"];"),
+            "buffer edge labeled with bytes:\n{dot}"
+        );
+        assert!(
+            dot.contains("task_out -> bind_tx [label=\"tx\"];"),
+            "writer connects to bind:\n{dot}"
+        );
+        assert!(!dot.contains("-> task_idle"), "idle task has no edges");
+    }
+
+    #[test]
+    fn mermaid_edges_labeled_with_rates() {
+        let reg = test_registry();
+        let mermaid = build_and_emit_with(
+            "clock 1kHz t {\n    constant(0.0) | ?p | fft(256) | c2r() | stdout()\n}",
+            &reg,
+            |g| {
+                // fft produces 256 tokens per firing; the probe has no rates
+                // of its own, so its edge falls back to fft's input rate.
+                let TaskGraph::Pipeline(sub) = &g.tasks["t"] else {
+                    unreachable!()
+                };
+                let mut rates = HashMap::new();
+                for node in &sub.nodes {
+                    if matches!(&node.kind, NodeKind::Actor { name, .. } if name == "fft") {
+                        let r = NodePortRates {
+                            in_rate: Some(256),
+                            out_rate: Some(256),
+                        };
+                        rates.insert(node.id, r);
+                    }
+                }
+                emit_mermaid(g, Some(&rates))
+            },
+        );
+        assert_eq!(mermaid.matches("-->|256|").count(), 2, "got:\n{mermaid}");
+        assert_eq!(mermaid.matches(" --> ").count(), 2, "got:\n{mermaid}");
+    }
 }
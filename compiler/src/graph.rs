@@ -12,7 +12,7 @@
 // Failure modes: none (define recursion handled in HIR pass).
 // Side effects: none.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 use crate::ast::*;
@@ -48,6 +48,13 @@ pub enum NodeKind {
         shape_constraint: Option<ShapeConstraint>,
         /// Stable call-site identifier (ADR-021).
         call_id: CallId,
+        /// Atomic group from `@atomic_group(name)`, if annotated.
+        atomic_group: Option<String>,
+        /// `@persist`: a modal `delay` whose feedback state survives mode switches.
+        persist: bool,
+        /// `@allow_combinational_loop`: a zero-delay cycle through this node is
+        /// intentional (E0305 is reported as W0314).
+        allow_combinational_loop: bool,
     },
     /// A fork node created by a tap declaration (`:name`).
     Fork { tap_name: String },
@@ -201,6 +208,152 @@ impl fmt::Display for ProgramGraph {
     }
 }
 
+// ── Passthrough collapse ────────────────────────────────────────────────────
+
+/// A direct edge between two non-passthrough nodes in a collapsed view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollapsedEdge {
+    pub source: NodeId,
+    pub target: NodeId,
+    /// Labels (`:tap`, `?probe`) of the passthrough nodes crossed, in order.
+    pub via: Vec<String>,
+}
+
+/// Whether a node is zero-copy in codegen (a tap fork or a probe).
+pub fn is_passthrough(kind: &NodeKind) -> bool {
+    matches!(kind, NodeKind::Fork { .. } | NodeKind::Probe { .. })
+}
+
+/// Replace every path `A -> (fork|probe)* -> B` by a direct edge `A -> B`
+/// annotated with the crossed passthrough names, mirroring the buffer
+/// aliasing LIR applies to those nodes. Paths that end in a passthrough
+/// node (e.g. a trailing probe) are dropped.
+pub fn collapse_passthrough(sub: &Subgraph) -> Vec<CollapsedEdge> {
+    let kinds: HashMap<NodeId, &NodeKind> = sub.nodes.iter().map(|n| (n.id, &n.kind)).collect();
+    let mut outgoing: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for edge in &sub.edges {
+        outgoing.entry(edge.source).or_default().push(edge.target);
+    }
+
+    let mut collapsed = Vec::new();
+    for node in &sub.nodes {
+        if is_passthrough(&node.kind) {
+            continue;
+        }
+        // DFS through passthrough nodes: (current node, labels crossed so far).
+        let mut stack: Vec<(NodeId, Vec<String>)> = outgoing
+            .get(&node.id)
+            .into_iter()
+            .flatten()
+            .rev()
+            .map(|&t| (t, Vec::new()))
+            .collect();
+        let mut visited = HashSet::new();
+        while let Some((id, mut via)) = stack.pop() {
+            let Some(kind) = kinds.get(&id) else {
+                continue;
+            };
+            match kind {
+                NodeKind::Fork { tap_name } => via.push(format!(":{}", tap_name)),
+                NodeKind::Probe { probe_name } => via.push(format!("?{}", probe_name)),
+                _ => {
+                    collapsed.push(CollapsedEdge {
+                        source: node.id,
+                        target: id,
+                        via,
+                    });
+                    continue;
+                }
+            }
+            if !visited.insert(id) {
+                continue;
+            }
+            for &next in outgoing.get(&id).into_iter().flatten().rev() {
+                stack.push((next, via.clone()));
+            }
+        }
+    }
+    collapsed
+}
+
+/// Render the program graph with passthrough nodes collapsed: one line per
+/// remaining node and per direct edge, for the `--collapse-passthrough`
+/// text dump.
+pub fn fmt_collapsed(graph: &ProgramGraph) -> String {
+    use std::fmt::Write;
+
+    fn write_sub(out: &mut String, indent: &str, sub: &Subgraph) {
+        let edges = collapse_passthrough(sub);
+        let nodes: Vec<&Node> = sub
+            .nodes
+            .iter()
+            .filter(|n| !is_passthrough(&n.kind))
+            .collect();
+        let label = |id: NodeId| {
+            let kind = &sub.nodes.iter().find(|n| n.id == id).unwrap().kind;
+            match kind {
+                NodeKind::Actor { name, .. } => name.clone(),
+                NodeKind::BufferRead { buffer_name } => format!("@{}", buffer_name),
+                NodeKind::BufferWrite { buffer_name } => format!("-> {}", buffer_name),
+                NodeKind::GatherRead { family_name, .. } => format!("@{}[*]", family_name),
+                NodeKind::ScatterWrite { family_name, .. } => format!("-> {}[*]", family_name),
+                NodeKind::Fork { tap_name } => format!(":{}", tap_name),
+                NodeKind::Probe { probe_name } => format!("?{}", probe_name),
+            }
+        };
+        let _ = writeln!(out, "{} nodes, {} edges", nodes.len(), edges.len());
+        for e in &edges {
+            let _ = write!(
+                out,
+                "{}  n{} {} -> n{} {}",
+                indent,
+                e.source.0,
+                label(e.source),
+                e.target.0,
+                label(e.target)
+            );
+            if !e.via.is_empty() {
+                let _ = write!(out, " [{}]", e.via.join(" "));
+            }
+            out.push('\n');
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "ProgramGraph ({} tasks, passthrough collapsed)",
+        graph.tasks.len()
+    );
+    let mut names: Vec<&String> = graph.tasks.keys().collect();
+    names.sort();
+    for name in names {
+        match &graph.tasks[name] {
+            TaskGraph::Pipeline(sub) => {
+                let _ = write!(out, "  task '{}': ", name);
+                write_sub(&mut out, "  ", sub);
+            }
+            TaskGraph::Modal { control, modes } => {
+                let _ = writeln!(out, "  task '{}' (modal):", name);
+                let _ = write!(out, "    control: ");
+                write_sub(&mut out, "    ", control);
+                for (mode_name, sub) in modes {
+                    let _ = write!(out, "    mode '{}': ", mode_name);
+                    write_sub(&mut out, "    ", sub);
+                }
+            }
+        }
+    }
+    for ite in &graph.inter_task_edges {
+        let _ = writeln!(
+            out,
+            "  inter-task '{}': {}.n{} -> {}.n{}",
+            ite.buffer_name, ite.writer_task, ite.writer_node.0, ite.reader_task, ite.reader_node.0
+        );
+    }
+    out
+}
+
 // ── Internal builder ────────────────────────────────────────────────────────
 
 struct GraphBuilder<'a> {
@@ -380,6 +533,16 @@ impl<'a> GraphBuilder<'a> {
                 // Look up the fork node for this tap
                 ctx.taps.get(name).copied()
             }
+            HirPipeSource::TapGather(_) => None,
+        };
+        // gather(:a, :b, ...): one edge per tap into the first actor, in
+        // list order, so its input buffer concatenates the taps.
+        let mut gathered: Vec<(NodeId, Span)> = match &expr.source {
+            HirPipeSource::TapGather(taps) => taps
+                .iter()
+                .filter_map(|(name, span)| ctx.taps.get(name).map(|&fork| (fork, *span)))
+                .collect(),
+            _ => Vec::new(),
         };
 
         // Elements
@@ -387,6 +550,9 @@ impl<'a> GraphBuilder<'a> {
             match elem {
                 HirPipeElem::ActorCall(call) => {
                     let id = self.build_actor_node(call, ctx);
+                    for (fork, span) in gathered.drain(..) {
+                        ctx.add_edge(fork, id, span);
+                    }
                     if let Some(prev) = prev_node {
                         ctx.add_edge(prev, id, expr.span);
                     }
@@ -469,6 +635,9 @@ impl<'a> GraphBuilder<'a> {
                 args: call.args.clone(),
                 shape_constraint: call.shape_constraint.clone(),
                 call_id: call.call_id,
+                atomic_group: call.atomic_group.as_ref().map(|(g, _)| g.clone()),
+                persist: call.persist,
+                allow_combinational_loop: call.allow_combinational_loop,
             },
             call.call_span,
         );
@@ -877,6 +1046,46 @@ mod tests {
         assert_eq!(edges_from_fork, 2);
     }
 
+    #[test]
+    fn collapse_passthrough_leaves_actor_topology() {
+        let reg = test_registry();
+        let graph = build_ok(
+            "clock 1kHz t {\n    constant(0.0) | :raw | ?p | mul(2.0) | stdout()\n    :raw | :again | stdout()\n    :again | stdout()\n}",
+            &reg,
+        );
+        let sub = get_pipeline_subgraph(&graph, "t");
+        let name = |id: NodeId| match &sub.nodes.iter().find(|n| n.id == id).unwrap().kind {
+            NodeKind::Actor { name, .. } => name.clone(),
+            other => panic!("collapsed edge touches passthrough node {:?}", other),
+        };
+        let mut edges: Vec<(String, String, String)> = collapse_passthrough(sub)
+            .into_iter()
+            .map(|e| (name(e.source), name(e.target), e.via.join(" ")))
+            .collect();
+        edges.sort();
+        let expected = [
+            ("constant", "mul", ":raw ?p"),
+            ("constant", "stdout", ":raw :again"),
+            ("constant", "stdout", ":raw :again"),
+            ("mul", "stdout", ""),
+        ];
+        assert_eq!(
+            edges,
+            expected
+                .iter()
+                .map(|(a, b, v)| (a.to_string(), b.to_string(), v.to_string()))
+                .collect::<Vec<_>>()
+        );
+
+        let text = fmt_collapsed(&graph);
+        assert!(
+            text.contains("task 't': 5 nodes, 4 edges"),
+            "text dump: {}",
+            text
+        );
+        assert!(text.contains("[:raw ?p]"), "text dump: {}", text);
+    }
+
     #[test]
     fn tap_multiple_consumers() {
         let reg = test_registry();
@@ -1271,6 +1480,9 @@ mod tests {
                         args: vec![],
                         shape_constraint: None,
                         call_id: CallId(0),
+                        atomic_group: None,
+                        persist: false,
+                        allow_combinational_loop: false,
                     },
                     span: sp(0, 1),
                 },
@@ -1282,6 +1494,9 @@ mod tests {
                         args: vec![],
                         shape_constraint: None,
                         call_id: CallId(0),
+                        atomic_group: None,
+                        persist: false,
+                        allow_combinational_loop: false,
                     },
                     span: sp(2, 3),
                 },
@@ -1293,6 +1508,9 @@ mod tests {
                         args: vec![],
                         shape_constraint: None,
                         call_id: CallId(0),
+                        atomic_group: None,
+                        persist: false,
+                        allow_combinational_loop: false,
                     },
                     span: sp(4, 5),
                 },
@@ -0,0 +1,556 @@
+// graph_query.rs — Structural assertions over the analyzed program graph
+//
+// Parses and evaluates the small query language of `pcc graph-query`, e.g.
+// `count(actor=fft, task=audio) == 1 && !exists_cycle`. A query is one or
+// more clauses joined by `&&`; each clause passes or fails on its own.
+//
+//   clause  := '!'? 'exists_cycle' filters? | measure CMP INT
+//   measure := 'count' filters | 'max_repetition' filters
+//   filters := '(' (filter (',' filter)*)? ')'
+//   filter  := ('actor' | 'kind' | 'task' | 'name') '=' IDENT
+//   CMP     := '==' | '!=' | '<' | '<=' | '>' | '>='
+//
+// Preconditions: the graph has been built and analyzed without errors.
+// Postconditions: one `ClauseResult` per clause, in query order.
+// Failure modes: malformed queries are rejected by `parse_query`.
+// Side effects: none.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::analyze::AnalyzedProgram;
+use crate::graph::{NodeId, NodeKind, ProgramGraph, Subgraph, TaskGraph};
+
+/// Node kinds accepted by `kind=`.
+pub const NODE_KINDS: &[&str] = &[
+    "actor",
+    "fork",
+    "probe",
+    "buffer_read",
+    "buffer_write",
+    "gather",
+    "scatter",
+];
+
+/// A parsed query: clauses joined by `&&`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub clauses: Vec<Clause>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clause {
+    /// `exists_cycle(...)`, or its negation with `!`.
+    ExistsCycle { negated: bool, filters: Vec<Filter> },
+    /// `measure(...) CMP value`.
+    Compare {
+        measure: Measure,
+        filters: Vec<Filter>,
+        op: CmpOp,
+        value: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Measure {
+    /// Number of matching nodes.
+    Count,
+    /// Largest repetition count among matching nodes (0 if none match).
+    MaxRepetition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// Actor node with this actor name.
+    Actor(String),
+    /// Node kind, one of `NODE_KINDS`.
+    Kind(String),
+    /// Node in this task.
+    Task(String),
+    /// Actor, tap, probe, buffer, or family name.
+    Name(String),
+}
+
+/// Outcome of one clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseResult {
+    /// The clause in canonical form.
+    pub clause: String,
+    pub passed: bool,
+    /// The measured value (`count`/`max_repetition`) or cycle count.
+    pub actual: u64,
+}
+
+impl CmpOp {
+    fn holds(self, lhs: u64, rhs: u64) -> bool {
+        match self {
+            CmpOp::Eq => lhs == rhs,
+            CmpOp::Ne => lhs != rhs,
+            CmpOp::Lt => lhs < rhs,
+            CmpOp::Le => lhs <= rhs,
+            CmpOp::Gt => lhs > rhs,
+            CmpOp::Ge => lhs >= rhs,
+        }
+    }
+}
+
+impl fmt::Display for CmpOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CmpOp::Eq => "==",
+            CmpOp::Ne => "!=",
+            CmpOp::Lt => "<",
+            CmpOp::Le => "<=",
+            CmpOp::Gt => ">",
+            CmpOp::Ge => ">=",
+        })
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Actor(v) => write!(f, "actor={}", v),
+            Filter::Kind(v) => write!(f, "kind={}", v),
+            Filter::Task(v) => write!(f, "task={}", v),
+            Filter::Name(v) => write!(f, "name={}", v),
+        }
+    }
+}
+
+fn fmt_filters(f: &mut fmt::Formatter<'_>, filters: &[Filter]) -> fmt::Result {
+    let parts: Vec<String> = filters.iter().map(|x| x.to_string()).collect();
+    write!(f, "({})", parts.join(", "))
+}
+
+impl fmt::Display for Clause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Clause::ExistsCycle { negated, filters } => {
+                if *negated {
+                    f.write_str("!")?;
+                }
+                f.write_str("exists_cycle")?;
+                if !filters.is_empty() {
+                    fmt_filters(f, filters)?;
+                }
+                Ok(())
+            }
+            Clause::Compare {
+                measure,
+                filters,
+                op,
+                value,
+            } => {
+                f.write_str(match measure {
+                    Measure::Count => "count",
+                    Measure::MaxRepetition => "max_repetition",
+                })?;
+                fmt_filters(f, filters)?;
+                write!(f, " {} {}", op, value)
+            }
+        }
+    }
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Int(u64),
+    Punct(&'static str),
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tok::Ident(s) => write!(f, "'{}'", s),
+            Tok::Int(n) => write!(f, "'{}'", n),
+            Tok::Punct(p) => write!(f, "'{}'", p),
+        }
+    }
+}
+
+const PUNCTS: &[&str] = &[
+    "&&", "==", "!=", "<=", ">=", "(", ")", ",", "=", "<", ">", "!",
+];
+
+fn tokenize(text: &str) -> Result<Vec<Tok>, String> {
+    let mut toks = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        if c.is_ascii_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if word.bytes().all(|b| b.is_ascii_digit()) {
+                let n = word
+                    .parse()
+                    .map_err(|_| format!("integer '{}' out of range", word))?;
+                toks.push(Tok::Int(n));
+            } else {
+                toks.push(Tok::Ident(word.to_string()));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+        let Some(p) = PUNCTS.iter().find(|p| rest.starts_with(**p)) else {
+            return Err(format!("unexpected character '{}'", c));
+        };
+        toks.push(Tok::Punct(p));
+        rest = &rest[p.len()..];
+    }
+    Ok(toks)
+}
+
+struct Parser {
+    toks: Vec<Tok>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.toks.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let tok = self.toks.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn eat(&mut self, punct: &'static str) -> bool {
+        if self.peek() == Some(&Tok::Punct(punct)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &'static str) -> Result<(), String> {
+        if self.eat(punct) {
+            return Ok(());
+        }
+        Err(match self.peek() {
+            Some(tok) => format!("expected '{}', found {}", punct, tok),
+            None => format!("expected '{}' at end of query", punct),
+        })
+    }
+
+    fn ident(&mut self, what: &str) -> Result<String, String> {
+        match self.next() {
+            Some(Tok::Ident(s)) => Ok(s),
+            Some(tok) => Err(format!("expected {}, found {}", what, tok)),
+            None => Err(format!("expected {} at end of query", what)),
+        }
+    }
+
+    fn clause(&mut self) -> Result<Clause, String> {
+        let negated = self.eat("!");
+        let head = self.ident("a predicate")?;
+        match head.as_str() {
+            "exists_cycle" => {
+                let filters = if self.peek() == Some(&Tok::Punct("(")) {
+                    self.filters()?
+                } else {
+                    Vec::new()
+                };
+                Ok(Clause::ExistsCycle { negated, filters })
+            }
+            "count" | "max_repetition" if !negated => {
+                let measure = if head == "count" {
+                    Measure::Count
+                } else {
+                    Measure::MaxRepetition
+                };
+                let filters = self.filters()?;
+                let op = self.cmp_op()?;
+                let value = match self.next() {
+                    Some(Tok::Int(n)) => n,
+                    Some(tok) => return Err(format!("expected an integer, found {}", tok)),
+                    None => return Err("expected an integer at end of query".to_string()),
+                };
+                Ok(Clause::Compare {
+                    measure,
+                    filters,
+                    op,
+                    value,
+                })
+            }
+            "count" | "max_repetition" => Err(format!(
+                "'!' applies only to exists_cycle; negate '{}' with the comparison",
+                head
+            )),
+            other => Err(format!(
+                "unknown predicate '{}' (expected count, max_repetition, or exists_cycle)",
+                other
+            )),
+        }
+    }
+
+    fn filters(&mut self) -> Result<Vec<Filter>, String> {
+        self.expect("(")?;
+        let mut filters = Vec::new();
+        if self.eat(")") {
+            return Ok(filters);
+        }
+        loop {
+            filters.push(self.filter()?);
+            if self.eat(")") {
+                return Ok(filters);
+            }
+            if !self.eat(",") {
+                return Err(match self.peek() {
+                    Some(tok) => format!("expected ',' or ')', found {}", tok),
+                    None => "expected ',' or ')' at end of query".to_string(),
+                });
+            }
+        }
+    }
+
+    fn filter(&mut self) -> Result<Filter, String> {
+        let key = self.ident("a filter key")?;
+        self.expect("=")?;
+        let value = self.ident("a filter value")?;
+        match key.as_str() {
+            "actor" => Ok(Filter::Actor(value)),
+            "kind" if NODE_KINDS.contains(&value.as_str()) => Ok(Filter::Kind(value)),
+            "kind" => Err(format!(
+                "unknown node kind '{}' (expected one of: {})",
+                value,
+                NODE_KINDS.join(", ")
+            )),
+            "task" => Ok(Filter::Task(value)),
+            "name" => Ok(Filter::Name(value)),
+            other => Err(format!(
+                "unknown filter '{}' (expected actor, kind, task, or name)",
+                other
+            )),
+        }
+    }
+
+    fn cmp_op(&mut self) -> Result<CmpOp, String> {
+        let op = match self.next() {
+            Some(Tok::Punct("==")) => CmpOp::Eq,
+            Some(Tok::Punct("!=")) => CmpOp::Ne,
+            Some(Tok::Punct("<")) => CmpOp::Lt,
+            Some(Tok::Punct("<=")) => CmpOp::Le,
+            Some(Tok::Punct(">")) => CmpOp::Gt,
+            Some(Tok::Punct(">=")) => CmpOp::Ge,
+            Some(tok) => return Err(format!("expected a comparison, found {}", tok)),
+            None => return Err("expected a comparison at end of query".to_string()),
+        };
+        Ok(op)
+    }
+}
+
+/// Parse a query string.
+pub fn parse_query(text: &str) -> Result<Query, String> {
+    let mut parser = Parser {
+        toks: tokenize(text)?,
+        pos: 0,
+    };
+    let mut clauses = vec![parser.clause()?];
+    while parser.eat("&&") {
+        clauses.push(parser.clause()?);
+    }
+    if let Some(tok) = parser.peek() {
+        return Err(format!("unexpected {} after clause", tok));
+    }
+    Ok(Query { clauses })
+}
+
+// ── Evaluation ──────────────────────────────────────────────────────────────
+
+/// A node with the task/subgraph it lives in.
+struct NodeRef<'a> {
+    task: &'a str,
+    label: &'a str,
+    id: NodeId,
+    kind: &'a NodeKind,
+}
+
+fn collect_nodes(graph: &ProgramGraph) -> Vec<NodeRef<'_>> {
+    fn push<'a>(out: &mut Vec<NodeRef<'a>>, task: &'a str, label: &'a str, sub: &'a Subgraph) {
+        out.extend(sub.nodes.iter().map(|n| NodeRef {
+            task,
+            label,
+            id: n.id,
+            kind: &n.kind,
+        }));
+    }
+    let mut out = Vec::new();
+    let mut tasks: Vec<_> = graph.tasks.iter().collect();
+    tasks.sort_by(|a, b| a.0.cmp(b.0));
+    for (task, tg) in tasks {
+        match tg {
+            TaskGraph::Pipeline(sub) => push(&mut out, task, "pipeline", sub),
+            TaskGraph::Modal { control, modes } => {
+                push(&mut out, task, "control", control);
+                for (mode, sub) in modes {
+                    push(&mut out, task, mode, sub);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// The `kind=` label and display name of a node.
+pub(crate) fn kind_and_name(kind: &NodeKind) -> (&'static str, &str) {
+    match kind {
+        NodeKind::Actor { name, .. } => ("actor", name),
+        NodeKind::Fork { tap_name } => ("fork", tap_name),
+        NodeKind::Probe { probe_name } => ("probe", probe_name),
+        NodeKind::BufferRead { buffer_name } => ("buffer_read", buffer_name),
+        NodeKind::BufferWrite { buffer_name } => ("buffer_write", buffer_name),
+        NodeKind::GatherRead { family_name, .. } => ("gather", family_name),
+        NodeKind::ScatterWrite { family_name, .. } => ("scatter", family_name),
+    }
+}
+
+fn matches(node: &NodeRef<'_>, filters: &[Filter]) -> bool {
+    let (kind, name) = kind_and_name(node.kind);
+    filters.iter().all(|f| match f {
+        Filter::Actor(v) => kind == "actor" && name == v,
+        Filter::Kind(v) => kind == v,
+        Filter::Task(v) => node.task == v,
+        Filter::Name(v) => name == v,
+    })
+}
+
+/// Evaluate every clause of `query`.
+pub fn evaluate(
+    query: &Query,
+    graph: &ProgramGraph,
+    analysis: &AnalyzedProgram,
+) -> Vec<ClauseResult> {
+    let nodes = collect_nodes(graph);
+    query
+        .clauses
+        .iter()
+        .map(|clause| {
+            let (passed, actual) = match clause {
+                Clause::ExistsCycle { negated, filters } => {
+                    let in_scope: HashSet<NodeId> = nodes
+                        .iter()
+                        .filter(|n| matches(n, filters))
+                        .map(|n| n.id)
+                        .collect();
+                    let cycles = graph
+                        .cycles
+                        .iter()
+                        .filter(|cycle| cycle.iter().any(|id| in_scope.contains(id)))
+                        .count() as u64;
+                    ((cycles > 0) != *negated, cycles)
+                }
+                Clause::Compare {
+                    measure,
+                    filters,
+                    op,
+                    value,
+                } => {
+                    let matched = nodes.iter().filter(|n| matches(n, filters));
+                    let actual = match measure {
+                        Measure::Count => matched.count() as u64,
+                        Measure::MaxRepetition => matched
+                            .filter_map(|n| {
+                                analysis
+                                    .repetition_vectors
+                                    .get(&(n.task.to_string(), n.label.to_string()))
+                                    .and_then(|rv| rv.get(&n.id))
+                            })
+                            .map(|&r| r as u64)
+                            .max()
+                            .unwrap_or(0),
+                    };
+                    (op.holds(actual, *value), actual)
+                }
+            };
+            ClauseResult {
+                clause: clause.to_string(),
+                passed,
+                actual,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_conjunction_round_trips() {
+        let q = parse_query("count(actor=fft,task=audio)==1 && !exists_cycle").unwrap();
+        assert_eq!(q.clauses.len(), 2);
+        assert_eq!(
+            q.clauses[0].to_string(),
+            "count(actor=fft, task=audio) == 1"
+        );
+        assert_eq!(q.clauses[1].to_string(), "!exists_cycle");
+    }
+
+    #[test]
+    fn parse_max_repetition_and_filtered_cycle() {
+        let q = parse_query("max_repetition(kind=actor) <= 64 && exists_cycle(task=t)").unwrap();
+        assert_eq!(
+            q.clauses[0],
+            Clause::Compare {
+                measure: Measure::MaxRepetition,
+                filters: vec![Filter::Kind("actor".to_string())],
+                op: CmpOp::Le,
+                value: 64,
+            }
+        );
+        assert_eq!(
+            q.clauses[1],
+            Clause::ExistsCycle {
+                negated: false,
+                filters: vec![Filter::Task("t".to_string())],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors_are_descriptive() {
+        let err = |q: &str| parse_query(q).unwrap_err();
+        assert_eq!(
+            err("size(actor=fft) == 1"),
+            "unknown predicate 'size' (expected count, max_repetition, or exists_cycle)"
+        );
+        assert_eq!(
+            err("count(actor=fft)"),
+            "expected a comparison at end of query"
+        );
+        assert!(err("count(kind=node) == 1").starts_with("unknown node kind 'node'"));
+        assert_eq!(
+            err("count(colour=red) == 1"),
+            "unknown filter 'colour' (expected actor, kind, task, or name)"
+        );
+        assert_eq!(
+            err("count(actor=fft"),
+            "expected ',' or ')' at end of query"
+        );
+        assert_eq!(err("count() == 1 1"), "unexpected '1' after clause");
+        assert_eq!(err("count() == $"), "unexpected character '$'");
+        assert!(err("!count() == 1").starts_with("'!' applies only to exists_cycle"));
+    }
+}
@@ -14,7 +14,9 @@
 
 use std::collections::HashMap;
 
-use crate::ast::{Arg, BindEndpoint, BufferIndex, Scalar, SetValue, ShapeConstraint, Span, Value};
+use crate::ast::{
+    Arg, BindEndpoint, BindType, BufferIndex, Scalar, SetValue, ShapeConstraint, Span, Value,
+};
 use crate::id::{CallId, DefId, TaskId};
 
 // ── Program ─────────────────────────────────────────────────────────────────
@@ -74,6 +76,7 @@ pub struct HirModal {
 pub enum HirSwitchSource {
     Buffer(String, Span),
     Param(String, Span),
+    Bind(String, Span),
 }
 
 // ── Pipe expression ─────────────────────────────────────────────────────────
@@ -101,6 +104,9 @@ pub enum HirPipeSource {
         span: Span,
     },
     TapRef(String, Span),
+    /// `gather(:a, :b, ...)`: taps fanned into the first element's input,
+    /// in concatenation order.
+    TapGather(Vec<(String, Span)>),
 }
 
 #[derive(Debug, Clone)]
@@ -135,6 +141,13 @@ pub struct HirActorCall {
     pub type_args: Vec<(String, Span)>,
     /// Optional shape constraint: `actor(...)[d0, d1, ...]`.
     pub shape_constraint: Option<ShapeConstraint>,
+    /// Optional `@atomic_group(name)` annotation with the group name's span.
+    pub atomic_group: Option<(String, Span)>,
+    /// `@persist` annotation (persistent modal `delay`).
+    pub persist: bool,
+    /// `@allow_combinational_loop`: zero-delay cycles through this call warn
+    /// instead of failing.
+    pub allow_combinational_loop: bool,
 }
 
 // ── Top-level declarations ──────────────────────────────────────────────────
@@ -172,6 +185,8 @@ pub struct HirSetDirective {
 pub struct HirBind {
     pub name: String,
     pub name_span: Span,
+    /// Declared wire type, if any (`bind iq: cfloat[1024] = ...`).
+    pub ty: Option<BindType>,
     pub endpoint: BindEndpoint,
 }
 
@@ -225,6 +240,7 @@ fn fmt_task(f: &mut fmt::Formatter<'_>, task: &HirTask) -> fmt::Result {
             let ctrl = match &modal.switch {
                 HirSwitchSource::Buffer(name, _) => format!("buffer:{}", name),
                 HirSwitchSource::Param(name, _) => format!("param:{}", name),
+                HirSwitchSource::Bind(name, _) => format!("bind:{}", name),
             };
             writeln!(
                 f,
@@ -266,6 +282,10 @@ fn fmt_pipe_source(f: &mut fmt::Formatter<'_>, source: &HirPipeSource) -> fmt::R
             write!(f, "gather_read({}[*])", family_name)
         }
         HirPipeSource::TapRef(name, _) => write!(f, "^{}", name),
+        HirPipeSource::TapGather(taps) => {
+            let names: Vec<String> = taps.iter().map(|(n, _)| format!("^{}", n)).collect();
+            write!(f, "gather({})", names.join(", "))
+        }
     }
 }
 
@@ -306,6 +326,7 @@ fn fmt_actor_call(f: &mut fmt::Formatter<'_>, call: &HirActorCall) -> fmt::Resul
             match dim {
                 crate::ast::ShapeDim::Literal(n, _) => write!(f, "{}", n)?,
                 crate::ast::ShapeDim::ConstRef(ident) => write!(f, ":{}", ident.name)?,
+                crate::ast::ShapeDim::BinOp(..) => write!(f, "{}", dim)?,
             }
         }
         write!(f, "]")?;
@@ -319,6 +340,7 @@ fn fmt_arg(f: &mut fmt::Formatter<'_>, arg: &Arg) -> fmt::Result {
         Arg::ParamRef(ident) => write!(f, "${}", ident.name),
         Arg::ConstRef(ident) => write!(f, ":{}", ident.name),
         Arg::TapRef(ident) => write!(f, "^{}", ident.name),
+        Arg::ConstSlice(slice) => write!(f, ":{}[{}:{}]", slice.name.name, slice.start, slice.end),
     }
 }
 
@@ -346,9 +368,17 @@ fn fmt_set_value(val: &SetValue) -> String {
     match val {
         SetValue::Number(n, _) => format_number(*n),
         SetValue::Size(bytes, _) => format!("{}B", bytes),
+        SetValue::Percent(pct, _) => format!("{}%", format_number(*pct)),
         SetValue::Freq(hz, _) => format!("{}Hz", hz),
         SetValue::StringLit(s, _) => format!("\"{}\"", s),
         SetValue::Ident(ident) => ident.name.clone(),
+        SetValue::Map(entries, _) => {
+            let parts: Vec<String> = entries
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k.name, fmt_set_value(v)))
+                .collect();
+            format!("{{ {} }}", parts.join(", "))
+        }
     }
 }
 
@@ -361,6 +391,264 @@ fn format_number(n: f64) -> String {
     }
 }
 
+// ── Canonical source printer ────────────────────────────────────────────────
+
+impl HirProgram {
+    /// Render the program as canonical `.pdl` source.
+    ///
+    /// Shows exactly what graph building sees: defines are inlined, spawn
+    /// clauses are expanded into one task per index, and element buffers
+    /// (`name__i`) are printed back as `name[i]`. The output re-parses to
+    /// an equivalent program.
+    pub fn to_pdl(&self, resolved: &ResolvedProgram) -> String {
+        let mut out = String::new();
+        for d in &self.set_directives {
+            out.push_str(&format!("set {} = {}\n", d.name, pdl_set_value(&d.value)));
+        }
+        for c in &self.consts {
+            out.push_str(&format!("const {} = {}\n", c.name, pdl_value(&c.value)));
+        }
+        for p in &self.params {
+            out.push_str(&format!(
+                "param {} = {}\n",
+                p.name,
+                pdl_scalar(&p.default_value)
+            ));
+        }
+        let mut shared: Vec<_> = resolved.shared_arrays.values().collect();
+        shared.sort_by(|a, b| a.name.cmp(&b.name));
+        for arr in shared {
+            out.push_str(&format!("shared {}[{}]\n", arr.name, arr.size));
+        }
+        for b in &self.binds {
+            let args: Vec<String> = b
+                .endpoint
+                .args
+                .iter()
+                .map(|arg| match arg {
+                    crate::ast::BindArg::Positional(v) => pdl_scalar(v),
+                    crate::ast::BindArg::Named(name, v) => {
+                        format!("{}={}", name.name, pdl_scalar(v))
+                    }
+                })
+                .collect();
+            let ty = match &b.ty {
+                Some(ty) => match &ty.shape {
+                    Some(sc) => {
+                        let dims: Vec<String> = sc.dims.iter().map(|dim| dim.to_string()).collect();
+                        format!(": {}[{}]", ty.dtype.name, dims.join(", "))
+                    }
+                    None => format!(": {}", ty.dtype.name),
+                },
+                None => String::new(),
+            };
+            out.push_str(&format!(
+                "bind {}{} = {}({})\n",
+                b.name,
+                ty,
+                b.endpoint.transport.name,
+                args.join(", ")
+            ));
+        }
+
+        for task in &self.tasks {
+            out.push_str(&format!(
+                "\nclock {} {} {{\n",
+                pdl_freq(task.freq_hz),
+                task.name
+            ));
+            match &task.body {
+                HirTaskBody::Pipeline(pipeline) => {
+                    pdl_pipeline(&mut out, pipeline, "    ", resolved);
+                }
+                HirTaskBody::Modal(modal) => {
+                    out.push_str("    control {\n");
+                    pdl_pipeline(&mut out, &modal.control, "        ", resolved);
+                    out.push_str("    }\n");
+                    for (mode_name, pipeline) in &modal.modes {
+                        out.push_str(&format!("    mode {} {{\n", mode_name));
+                        pdl_pipeline(&mut out, pipeline, "        ", resolved);
+                        out.push_str("    }\n");
+                    }
+                    let source = match &modal.switch {
+                        HirSwitchSource::Buffer(name, _) => name.clone(),
+                        HirSwitchSource::Param(name, _) => format!("${}", name),
+                        HirSwitchSource::Bind(name, _) => format!("@{}", name),
+                    };
+                    let modes: Vec<&str> = modal.modes.iter().map(|(m, _)| m.as_str()).collect();
+                    out.push_str(&format!("    switch({}, {})\n", source, modes.join(", ")));
+                }
+            }
+            out.push_str("}\n");
+        }
+        out
+    }
+}
+
+fn pdl_pipeline(
+    out: &mut String,
+    pipeline: &HirPipeline,
+    indent: &str,
+    resolved: &ResolvedProgram,
+) {
+    for pipe in &pipeline.pipes {
+        out.push_str(indent);
+        match &pipe.source {
+            HirPipeSource::ActorCall(call) => out.push_str(&pdl_actor_call(call)),
+            HirPipeSource::BufferRead(name, _) => {
+                out.push_str(&format!("@{}", pdl_buffer_name(name, resolved)));
+            }
+            HirPipeSource::GatherRead { family_name, .. } => {
+                out.push_str(&format!("@{}[*]", family_name));
+            }
+            HirPipeSource::TapRef(name, _) => out.push_str(&format!(":{}", name)),
+            HirPipeSource::TapGather(taps) => {
+                let names: Vec<String> = taps.iter().map(|(n, _)| format!(":{}", n)).collect();
+                out.push_str(&format!("gather({})", names.join(", ")));
+            }
+        }
+        for elem in &pipe.elements {
+            out.push_str(" | ");
+            match elem {
+                HirPipeElem::ActorCall(call) => out.push_str(&pdl_actor_call(call)),
+                HirPipeElem::Tap(name, _) => out.push_str(&format!(":{}", name)),
+                HirPipeElem::Probe(name, _) => out.push_str(&format!("?{}", name)),
+            }
+        }
+        if let Some(ref sink) = pipe.sink {
+            match sink.scatter {
+                Some(_) => out.push_str(&format!(" -> {}[*]", sink.buffer_name)),
+                None => out.push_str(&format!(
+                    " -> {}",
+                    pdl_buffer_name(&sink.buffer_name, resolved)
+                )),
+            }
+        }
+        out.push('\n');
+    }
+}
+
+/// Map a resolved element buffer name (`name__i`) back to `name[i]`.
+fn pdl_buffer_name(name: &str, resolved: &ResolvedProgram) -> String {
+    if let Some((family, idx)) = name.rsplit_once("__") {
+        if resolved.shared_arrays.contains_key(family) && idx.parse::<u32>().is_ok() {
+            return format!("{}[{}]", family, idx);
+        }
+    }
+    name.to_string()
+}
+
+fn pdl_actor_call(call: &HirActorCall) -> String {
+    let mut s = call.name.clone();
+    if !call.type_args.is_empty() {
+        let tys: Vec<&str> = call.type_args.iter().map(|(t, _)| t.as_str()).collect();
+        s.push_str(&format!("<{}>", tys.join(", ")));
+    }
+    let args: Vec<String> = call
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Value(val) => pdl_value(val),
+            Arg::ParamRef(ident) => format!("${}", ident.name),
+            Arg::ConstRef(ident) => ident.name.clone(),
+            Arg::TapRef(ident) => format!(":{}", ident.name),
+            Arg::ConstSlice(slice) => {
+                format!("{}[{}:{}]", slice.name.name, slice.start, slice.end)
+            }
+        })
+        .collect();
+    s.push_str(&format!("({})", args.join(", ")));
+    if let Some(ref sc) = call.shape_constraint {
+        let dims: Vec<String> = sc.dims.iter().map(|dim| dim.to_string()).collect();
+        s.push_str(&format!("[{}]", dims.join(", ")));
+    }
+    if let Some((ref group, _)) = call.atomic_group {
+        s.push_str(&format!(" @atomic_group({})", group));
+    }
+    if call.persist {
+        s.push_str(" @persist");
+    }
+    if call.allow_combinational_loop {
+        s.push_str(" @allow_combinational_loop");
+    }
+    s
+}
+
+fn pdl_value(value: &Value) -> String {
+    match value {
+        Value::Scalar(s) => pdl_scalar(s),
+        Value::Array(elems, _) => {
+            let items: Vec<String> = elems.iter().map(pdl_scalar).collect();
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
+
+/// Like `fmt_scalar`, but keeps float literals (`0.0`) distinct from integer
+/// literals (`0`) since that distinction drives type inference.
+fn pdl_scalar(scalar: &Scalar) -> String {
+    match scalar {
+        Scalar::Number(n, _, is_int_literal) => pdl_number(*n, *is_int_literal),
+        Scalar::Freq(hz, _) => pdl_freq(*hz),
+        Scalar::Size(bytes, _) => pdl_size(*bytes),
+        Scalar::StringLit(s, _) => pdl_string(s),
+        Scalar::Ident(ident) => ident.name.clone(),
+    }
+}
+
+fn pdl_set_value(val: &SetValue) -> String {
+    match val {
+        SetValue::Number(n, _) => format_number(*n),
+        SetValue::Size(bytes, _) => pdl_size(*bytes),
+        SetValue::Percent(pct, _) => format!("{}%", format_number(*pct)),
+        SetValue::Freq(hz, _) => pdl_freq(*hz),
+        SetValue::StringLit(s, _) => pdl_string(s),
+        SetValue::Ident(ident) => ident.name.clone(),
+        SetValue::Map(entries, _) => {
+            let parts: Vec<String> = entries
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k.name, pdl_set_value(v)))
+                .collect();
+            format!("{{ {} }}", parts.join(", "))
+        }
+    }
+}
+
+fn pdl_number(n: f64, is_int_literal: bool) -> String {
+    let s = format_number(n);
+    if is_int_literal || s.contains(['.', 'e', 'E']) || !n.is_finite() {
+        s
+    } else {
+        format!("{}.0", s)
+    }
+}
+
+/// Largest unit that expresses the frequency exactly (`48kHz`, `10MHz`).
+fn pdl_freq(hz: f64) -> String {
+    for (scale, unit) in [(1e9, "GHz"), (1e6, "MHz"), (1e3, "kHz")] {
+        let v = hz / scale;
+        if v.abs() >= 1.0 && v.fract() == 0.0 {
+            return format!("{}{}", format_number(v), unit);
+        }
+    }
+    format!("{}Hz", format_number(hz))
+}
+
+/// Largest binary unit that expresses the size exactly. Sizes that are not
+/// a multiple of 1KB have no size literal and are printed as plain numbers.
+fn pdl_size(bytes: u64) -> String {
+    for (scale, unit) in [(1u64 << 30, "GB"), (1 << 20, "MB"), (1 << 10, "KB")] {
+        if bytes >= scale && bytes.is_multiple_of(scale) {
+            return format!("{}{}", bytes / scale, unit);
+        }
+    }
+    bytes.to_string()
+}
+
+fn pdl_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 // ── HIR Verification ────────────────────────────────────────────────────────
 
 use std::collections::HashSet;
@@ -546,10 +834,14 @@ impl<'a> HirBuilder<'a> {
                         span: stmt.span,
                     });
                 }
-                StatementKind::Define(_) | StatementKind::Bind(_) | StatementKind::Shared(_) => {
+                StatementKind::Define(_)
+                | StatementKind::Bind(_)
+                | StatementKind::Shared(_)
+                | StatementKind::Include(_) => {
                     // Defines: consumed during expansion, not emitted to HIR.
                     // Binds: collected separately below from resolved.binds.
                     // Shared: consumed during resolve, not emitted to HIR.
+                    // Includes: composed into the source before parsing.
                 }
             }
         }
@@ -563,6 +855,7 @@ impl<'a> HirBuilder<'a> {
             .map(|(name, entry)| HirBind {
                 name: name.clone(),
                 name_span: entry.name_span,
+                ty: entry.ty.clone(),
                 endpoint: entry.endpoint.clone(),
             })
             .collect();
@@ -630,6 +923,9 @@ impl<'a> HirBuilder<'a> {
                     SwitchSource::Param(ident) => {
                         HirSwitchSource::Param(ident.name.clone(), ident.span)
                     }
+                    SwitchSource::Bind(ident) => {
+                        HirSwitchSource::Bind(ident.name.clone(), ident.span)
+                    }
                 };
                 HirTaskBody::Modal(HirModal {
                     control,
@@ -796,6 +1092,13 @@ impl<'a> HirBuilder<'a> {
             PipeSource::TapRef(ident) => {
                 ExpandedSource::Single(HirPipeSource::TapRef(ident.name.clone(), ident.span))
             }
+            PipeSource::TapGather(gather) => ExpandedSource::Single(HirPipeSource::TapGather(
+                gather
+                    .taps
+                    .iter()
+                    .map(|t| (t.name.clone(), t.span))
+                    .collect(),
+            )),
         }
     }
 
@@ -844,17 +1147,25 @@ impl<'a> HirBuilder<'a> {
             id
         };
 
+        let mut args = call.args.clone();
+        if let Some(defaults) = self.resolved.default_args.get(&call.span) {
+            args.extend(defaults.iter().cloned());
+        }
+
         HirActorCall {
             name: call.name.name.clone(),
             call_id,
             call_span: call.span,
-            args: call.args.clone(),
+            args,
             type_args: call
                 .type_args
                 .iter()
                 .map(|i| (i.name.clone(), i.span))
                 .collect(),
             shape_constraint: call.shape_constraint.clone(),
+            atomic_group: call.atomic_group.as_ref().map(|g| (g.name.clone(), g.span)),
+            persist: call.persist,
+            allow_combinational_loop: call.allow_combinational_loop,
         }
     }
 
@@ -945,6 +1256,9 @@ fn substitute_actor_call(call: &ActorCall, arg_map: &HashMap<String, Arg>) -> Ac
             .map(|arg| substitute_arg(arg, arg_map))
             .collect(),
         shape_constraint: call.shape_constraint.clone(),
+        atomic_group: call.atomic_group.clone(),
+        persist: call.persist,
+        allow_combinational_loop: call.allow_combinational_loop,
         span: call.span,
     }
 }
@@ -1078,6 +1392,76 @@ mod tests {
         assert_eq!(hir.set_directives.len(), 2);
     }
 
+    #[test]
+    fn to_pdl_canonical_source() {
+        let source = r#"
+            set mem = 64MB
+            const coeffs = [1.0, 2.0, 3.0]
+            param gain = 1.0
+            define amplify(g) {
+                scale(g) | mul($gain)
+            }
+            clock 48kHz main {
+                constant(0.0) | amplify(2) | :t | stdout()
+                :t | fir(coeffs)[4] -> out
+            }
+        "#;
+        let registry = crate::registry::Registry::empty();
+        let (hir, resolved) = build_hir_from_source(source, &registry);
+        let pdl = hir.to_pdl(&resolved);
+        assert_eq!(
+            pdl,
+            concat!(
+                "set mem = 64MB\n",
+                "const coeffs = [1.0, 2.0, 3.0]\n",
+                "param gain = 1.0\n",
+                "\n",
+                "clock 48kHz main {\n",
+                "    constant(0.0) | scale(2) | mul($gain) | :t | stdout()\n",
+                "    :t | fir(coeffs)[4] -> out\n",
+                "}\n",
+            )
+        );
+
+        // Canonical output is a fixed point.
+        let (hir2, resolved2) = build_hir_from_source(&pdl, &registry);
+        assert_eq!(hir2.to_pdl(&resolved2), pdl);
+    }
+
+    #[test]
+    fn to_pdl_keeps_bind_types() {
+        let source = r#"
+            bind iq: cfloat[4] = udp("127.0.0.1:9000")
+            bind level: float = udp("127.0.0.1:9001")
+            bind raw = udp("127.0.0.1:9002")
+            clock 1kHz main {
+                @iq | mag() | stdout()
+            }
+        "#;
+        let registry = crate::registry::Registry::empty();
+        let (hir, resolved) = build_hir_from_source(source, &registry);
+        let pdl = hir.to_pdl(&resolved);
+        assert!(
+            pdl.contains("bind iq: cfloat[4] = udp(\"127.0.0.1:9000\")\n"),
+            "{}",
+            pdl
+        );
+        assert!(
+            pdl.contains("bind level: float = udp(\"127.0.0.1:9001\")\n"),
+            "{}",
+            pdl
+        );
+        assert!(
+            pdl.contains("bind raw = udp(\"127.0.0.1:9002\")\n"),
+            "{}",
+            pdl
+        );
+
+        // Typed binds round-trip through the canonical form.
+        let (hir2, resolved2) = build_hir_from_source(&pdl, &registry);
+        assert_eq!(hir2.to_pdl(&resolved2), pdl);
+    }
+
     #[test]
     fn hir_no_defines_in_output() {
         let source = r#"
@@ -1151,6 +1535,9 @@ mod tests {
                     args: Vec::new(),
                     type_args: Vec::new(),
                     shape_constraint: None,
+                    atomic_group: None,
+                    persist: false,
+                    allow_combinational_loop: false,
                 }));
             }
         }
@@ -1188,6 +1575,9 @@ mod tests {
                     args: Vec::new(),
                     type_args: Vec::new(),
                     shape_constraint: None,
+                    atomic_group: None,
+                    persist: false,
+                    allow_combinational_loop: false,
                 }));
             }
         }
@@ -0,0 +1,373 @@
+// Include composition: `include "file.pdl"` → one composed source text.
+//
+// Reads every file reachable through `include` directives and concatenates
+// them, each included file ahead of the file that includes it, into a single
+// source that the parser sees as one program. Several root files (multiple
+// source paths on the command line) compose in order into the same text.
+// Offsets in the composed text map back to their file through
+// `ComposedSource::locate`.
+//
+// Preconditions: the root source texts have been read by the caller.
+// Postconditions: every reachable file appears exactly once (a file included
+//   from several places, or also given as a root, is composed once);
+//   `include` statements remain in the composed text and are ignored by
+//   later passes.
+// Failure modes: unreadable include files (E0036) and include cycles (E0037)
+//   produce diagnostics whose spans point into the composed text.
+// Side effects: reads included files from the filesystem.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use chumsky::span::Span as _;
+
+use crate::ast::{Span, StatementKind};
+use crate::diag::{codes, DiagLevel, Diagnostic};
+
+/// One file's slice of the composed source.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    /// Byte offset of the file's first byte in `ComposedSource::text`.
+    pub start: usize,
+    pub len: usize,
+    /// A source given by the caller rather than reached through `include`.
+    pub root: bool,
+}
+
+/// The root sources plus everything they include, as one text.
+#[derive(Debug, Clone)]
+pub struct ComposedSource {
+    pub text: String,
+    /// Files in composition order (each root after the files it includes).
+    pub files: Vec<SourceFile>,
+}
+
+impl ComposedSource {
+    /// A single file with no includes resolved.
+    pub fn single(path: impl Into<PathBuf>, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let len = text.len();
+        ComposedSource {
+            text,
+            files: vec![SourceFile {
+                path: path.into(),
+                start: 0,
+                len,
+                root: true,
+            }],
+        }
+    }
+
+    /// The file containing composed `offset`, with that file's text.
+    pub fn locate(&self, offset: usize) -> (&SourceFile, &str) {
+        let file = self
+            .files
+            .iter()
+            .find(|f| offset < f.start + f.len)
+            .or(self.files.last())
+            .expect("composed source has at least one file");
+        (file, &self.text[file.start..file.start + file.len])
+    }
+
+    /// Paths of the included files (excluding the roots), for depfiles.
+    pub fn included_paths(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .filter(|f| !f.root)
+            .map(|f| f.path.as_path())
+    }
+}
+
+/// Result of include composition.
+pub struct IncludeResult {
+    pub source: ComposedSource,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Compose `root_text` with the files it includes.
+///
+/// An include path is looked up relative to the including file's directory,
+/// then in each of `search_dirs`.
+pub fn compose_includes(
+    root_path: &Path,
+    root_text: &str,
+    search_dirs: &[PathBuf],
+) -> IncludeResult {
+    compose_sources(
+        &[(root_path.to_path_buf(), root_text.to_string())],
+        search_dirs,
+    )
+}
+
+/// Compose several root sources, in order, each with the files it includes,
+/// into one program text. A root already composed (given twice, or included
+/// by an earlier root) is skipped.
+pub fn compose_sources(roots: &[(PathBuf, String)], search_dirs: &[PathBuf]) -> IncludeResult {
+    let mut composer = Composer {
+        search_dirs,
+        text: String::new(),
+        files: Vec::new(),
+        seen: HashSet::new(),
+        stack: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+    for (root_path, root_text) in roots {
+        let root_key = canonical_key(root_path);
+        if !composer.seen.insert(root_key.clone()) {
+            continue;
+        }
+        composer.compose_file(root_path.clone(), root_key, root_text.clone(), true);
+    }
+    IncludeResult {
+        source: ComposedSource {
+            text: composer.text,
+            files: composer.files,
+        },
+        diagnostics: composer.diagnostics,
+    }
+}
+
+struct Composer<'a> {
+    search_dirs: &'a [PathBuf],
+    text: String,
+    files: Vec<SourceFile>,
+    /// Canonical paths already composed (or in progress).
+    seen: HashSet<PathBuf>,
+    /// Canonical paths of the files being composed, outermost first.
+    stack: Vec<PathBuf>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Composer<'_> {
+    /// Compose `path`'s includes, then append `path` itself. Diagnostics on
+    /// its own directives are shifted once its final offset is known.
+    fn compose_file(&mut self, path: PathBuf, key: PathBuf, text: String, root: bool) {
+        self.stack.push(key);
+        let mut local_diags = Vec::new();
+        // A file that fails to parse contributes no includes; the parse of
+        // the composed text reports its errors.
+        if let Some(program) = crate::parser::parse(&text).program {
+            for stmt in &program.statements {
+                let StatementKind::Include(inc) = &stmt.kind else {
+                    continue;
+                };
+                self.include(&path, &inc.path, inc.path_span, &mut local_diags);
+            }
+        }
+        self.stack.pop();
+
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+        }
+        let start = self.text.len();
+        self.text.push_str(&text);
+        self.files.push(SourceFile {
+            path,
+            start,
+            len: text.len(),
+            root,
+        });
+        for mut diag in local_diags {
+            diag.span = Span::new((), diag.span.start + start..diag.span.end + start);
+            self.diagnostics.push(diag);
+        }
+    }
+
+    fn include(&mut self, from: &Path, target: &str, span: Span, diags: &mut Vec<Diagnostic>) {
+        let Some(found) = self.find(from, target) else {
+            diags.push(
+                Diagnostic::new(
+                    DiagLevel::Error,
+                    span,
+                    format!("include file '{}' not found", target),
+                )
+                .with_code(codes::E0036)
+                .with_hint(
+                    "include paths are relative to the including file, then each -I directory",
+                ),
+            );
+            return;
+        };
+        let key = canonical_key(&found);
+        if let Some(pos) = self.stack.iter().position(|p| *p == key) {
+            let chain: Vec<String> = self.stack[pos..]
+                .iter()
+                .chain(std::iter::once(&key))
+                .map(|p| p.display().to_string())
+                .collect();
+            diags.push(
+                Diagnostic::new(
+                    DiagLevel::Error,
+                    span,
+                    format!("include cycle: {}", chain.join(" -> ")),
+                )
+                .with_code(codes::E0037),
+            );
+            return;
+        }
+        if !self.seen.insert(key.clone()) {
+            return; // already composed via another include
+        }
+        match std::fs::read_to_string(&found) {
+            Ok(text) => self.compose_file(found, key, text, false),
+            Err(e) => diags.push(
+                Diagnostic::new(
+                    DiagLevel::Error,
+                    span,
+                    format!("cannot read include file '{}': {}", found.display(), e),
+                )
+                .with_code(codes::E0036),
+            ),
+        }
+    }
+
+    fn find(&self, from: &Path, target: &str) -> Option<PathBuf> {
+        let base = from.parent().unwrap_or(Path::new(""));
+        std::iter::once(base.to_path_buf())
+            .chain(self.search_dirs.iter().filter(|d| d.is_dir()).cloned())
+            .map(|dir| dir.join(target))
+            .find(|p| p.is_file())
+    }
+}
+
+fn canonical_key(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pcc_include_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn included_file_precedes_includer() {
+        let dir = temp_dir("order");
+        std::fs::write(dir.join("common.pdl"), "const n = 4\n").unwrap();
+        let root = dir.join("main.pdl");
+        let main = "include \"common.pdl\"\nclock 1kHz t { constant(0.0) | stdout() }\n";
+        let result = compose_includes(&root, main, &[]);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.diagnostics.is_empty(), "{:#?}", result.diagnostics);
+        assert_eq!(result.source.text, format!("const n = 4\n{}", main));
+        assert_eq!(result.source.files.len(), 2);
+        let (file, text) = result
+            .source
+            .locate(result.source.text.find("clock").unwrap());
+        assert_eq!(file.path, root);
+        assert_eq!(text, main);
+        assert_eq!(result.source.included_paths().count(), 1);
+    }
+
+    #[test]
+    fn diamond_include_composed_once() {
+        let dir = temp_dir("diamond");
+        std::fs::write(dir.join("base.pdl"), "const n = 4\n").unwrap();
+        std::fs::write(dir.join("a.pdl"), "include \"base.pdl\"\n").unwrap();
+        std::fs::write(dir.join("b.pdl"), "include \"base.pdl\"\n").unwrap();
+        let main = "include \"a.pdl\"\ninclude \"b.pdl\"\n";
+        let result = compose_includes(&dir.join("main.pdl"), main, &[]);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.diagnostics.is_empty(), "{:#?}", result.diagnostics);
+        assert_eq!(result.source.text.matches("const n").count(), 1);
+        assert_eq!(result.source.files.len(), 4);
+    }
+
+    #[test]
+    fn search_dirs_used_after_includer_dir() {
+        let dir = temp_dir("search");
+        let lib = dir.join("lib");
+        std::fs::create_dir_all(&lib).unwrap();
+        std::fs::write(lib.join("common.pdl"), "const n = 4\n").unwrap();
+        let result = compose_includes(&dir.join("main.pdl"), "include \"common.pdl\"\n", &[lib]);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(result.diagnostics.is_empty(), "{:#?}", result.diagnostics);
+        assert!(result.source.text.starts_with("const n = 4\n"));
+    }
+
+    #[test]
+    fn include_cycle_reported() {
+        let dir = temp_dir("cycle");
+        std::fs::write(dir.join("a.pdl"), "include \"b.pdl\"\n").unwrap();
+        std::fs::write(dir.join("b.pdl"), "include \"a.pdl\"\n").unwrap();
+        let main = "include \"a.pdl\"\n";
+        let result = compose_includes(&dir.join("main.pdl"), main, &[]);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(result.diagnostics.len(), 1, "{:#?}", result.diagnostics);
+        let diag = &result.diagnostics[0];
+        assert_eq!(diag.code, Some(codes::E0037));
+        assert!(
+            diag.message.starts_with("include cycle: ")
+                && diag.message.contains("a.pdl -> ")
+                && diag.message.ends_with("a.pdl"),
+            "got: {}",
+            diag.message
+        );
+        // The span points at the directive in b.pdl.
+        let (file, _) = result.source.locate(diag.span.start);
+        assert!(file.path.ends_with("b.pdl"), "got {:?}", file.path);
+        assert_eq!(
+            &result.source.text[diag.span.start..diag.span.end],
+            "\"a.pdl\""
+        );
+    }
+
+    #[test]
+    fn multiple_roots_compose_in_order() {
+        let dir = temp_dir("roots");
+        std::fs::write(dir.join("common.pdl"), "const n = 4\n").unwrap();
+        let lib = dir.join("lib.pdl");
+        let lib_text = "include \"common.pdl\"\nclock 1kHz a { constant(0.0) -> sig }";
+        let main = dir.join("main.pdl");
+        let main_text = "include \"common.pdl\"\nclock 1kHz b { @sig | stdout() }\n";
+        let result = compose_sources(
+            &[
+                (lib.clone(), lib_text.to_string()),
+                (main.clone(), main_text.to_string()),
+                (lib.clone(), lib_text.to_string()),
+            ],
+            &[],
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.diagnostics.is_empty(), "{:#?}", result.diagnostics);
+        assert_eq!(
+            result.source.text,
+            format!("const n = 4\n{}\n{}", lib_text, main_text)
+        );
+        let roots: Vec<_> = result
+            .source
+            .files
+            .iter()
+            .filter(|f| f.root)
+            .map(|f| &f.path)
+            .collect();
+        assert_eq!(roots, vec![&lib, &main]);
+        assert_eq!(result.source.included_paths().count(), 1);
+        let (file, _) = result
+            .source
+            .locate(result.source.text.find("clock 1kHz b").unwrap());
+        assert_eq!(file.path, main);
+    }
+
+    #[test]
+    fn missing_include_reported() {
+        let main = "include \"nope.pdl\"\n";
+        let result = compose_includes(Path::new("/nonexistent/main.pdl"), main, &[]);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].code, Some(codes::E0036));
+        assert_eq!(
+            result.diagnostics[0].message,
+            "include file 'nope.pdl' not found"
+        );
+        assert_eq!(result.source.text, main);
+    }
+}
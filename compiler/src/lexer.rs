@@ -65,6 +65,8 @@ pub enum Token {
     Bind,
     #[token("shared")]
     Shared,
+    #[token("include")]
+    Include,
 
     // ── Symbols ──
     #[token("|")]
@@ -103,6 +105,12 @@ pub enum Token {
     DotDot,
     #[token("*")]
     Star,
+    #[token("+")]
+    Plus,
+    #[token("-")]
+    Minus,
+    #[token("/")]
+    Slash,
 
     // ── Literals ──
     //
@@ -116,8 +124,13 @@ pub enum Token {
     #[regex(r"[0-9]+(KB|MB|GB)", parse_size)]
     Size(u64),
 
-    /// Numeric literal (int, float, exponent, negative).
+    /// Percentage literal (e.g. `50%`). Value stored as the percentage.
+    #[regex(r"[0-9]+(\.[0-9]+)?%", parse_percent)]
+    Percent(f64),
+
+    /// Numeric literal (int, float, exponent, negative, `0x` hex, `0b` binary).
     #[regex(r"-?[0-9]+(\.[0-9]+)?([eE][+-]?[0-9]+)?", parse_number)]
+    #[regex(r"-?0[xb][0-9a-zA-Z_]*", parse_radix_number)]
     Number(f64),
 
     /// String literal with `\"` and `\\` escapes.
@@ -153,6 +166,7 @@ impl fmt::Display for Token {
             Token::Delay => write!(f, "delay"),
             Token::Bind => write!(f, "bind"),
             Token::Shared => write!(f, "shared"),
+            Token::Include => write!(f, "include"),
             Token::Pipe => write!(f, "|"),
             Token::Arrow => write!(f, "->"),
             Token::At => write!(f, "@"),
@@ -171,8 +185,12 @@ impl fmt::Display for Token {
             Token::Gt => write!(f, ">"),
             Token::DotDot => write!(f, ".."),
             Token::Star => write!(f, "*"),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Slash => write!(f, "/"),
             Token::Freq(v) => write!(f, "{v}Hz"),
             Token::Size(v) => write!(f, "{v}B"),
+            Token::Percent(v) => write!(f, "{v}%"),
             Token::Number(v) => write!(f, "{v}"),
             Token::StringLit(s) => write!(f, "\"{s}\""),
             Token::Ident => write!(f, "<ident>"),
@@ -187,6 +205,20 @@ fn parse_number(lex: &mut logos::Lexer<'_, Token>) -> Option<f64> {
     lex.slice().parse().ok()
 }
 
+/// `0x`/`0b` integer literal. The regex takes any trailing alphanumerics so
+/// that a bad digit (`0xG`) is one lex error rather than `0` followed by an
+/// identifier.
+fn parse_radix_number(lex: &mut logos::Lexer<'_, Token>) -> Option<f64> {
+    let slice = lex.slice();
+    let (negative, body) = match slice.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, slice),
+    };
+    let radix = if body.starts_with("0x") { 16 } else { 2 };
+    let value = u64::from_str_radix(&body[2..], radix).ok()? as f64;
+    Some(if negative { -value } else { value })
+}
+
 fn parse_freq(lex: &mut logos::Lexer<'_, Token>) -> Option<f64> {
     let slice = lex.slice();
     let unit_start = slice.find(|c: char| c.is_alphabetic())?;
@@ -216,6 +248,10 @@ fn parse_size(lex: &mut logos::Lexer<'_, Token>) -> Option<u64> {
     num.checked_mul(multiplier)
 }
 
+fn parse_percent(lex: &mut logos::Lexer<'_, Token>) -> Option<f64> {
+    lex.slice().strip_suffix('%')?.parse().ok()
+}
+
 fn parse_string(lex: &mut logos::Lexer<'_, Token>) -> Option<String> {
     let slice = lex.slice();
     let inner = &slice[1..slice.len() - 1]; // strip quotes
@@ -257,10 +293,18 @@ pub fn lex(source: &str) -> LexResult {
         };
         match result {
             Ok(token) => tokens.push((token, span)),
-            Err(()) => errors.push(LexError {
-                span,
-                message: format!("unexpected character: {:?}", &source[span.start..span.end]),
-            }),
+            Err(()) => {
+                let text = &source[span.start..span.end];
+                let digits = text.trim_start_matches('-');
+                let message = if digits.starts_with("0x") {
+                    format!("invalid hexadecimal literal: {:?}", text)
+                } else if digits.starts_with("0b") {
+                    format!("invalid binary literal: {:?}", text)
+                } else {
+                    format!("unexpected character: {:?}", text)
+                };
+                errors.push(LexError { span, message });
+            }
         }
     }
 
@@ -295,8 +339,9 @@ mod tests {
 
     #[test]
     fn keywords() {
-        let tokens =
-            lex_ok("set const param define clock mode control switch default delay bind shared");
+        let tokens = lex_ok(
+            "set const param define clock mode control switch default delay bind shared include",
+        );
         assert_eq!(
             tokens,
             vec![
@@ -312,6 +357,7 @@ mod tests {
                 Token::Delay,
                 Token::Bind,
                 Token::Shared,
+                Token::Include,
             ]
         );
     }
@@ -351,6 +397,27 @@ mod tests {
         assert_eq!(tokens, vec![Token::LBracket, Token::Star, Token::RBracket]);
     }
 
+    #[test]
+    fn shape_arithmetic_tokens() {
+        let tokens = lex_ok("N*2 + 1 - N/2");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident,
+                Token::Star,
+                Token::Number(2.0),
+                Token::Plus,
+                Token::Number(1.0),
+                Token::Minus,
+                Token::Ident,
+                Token::Slash,
+                Token::Number(2.0),
+            ]
+        );
+        // Without spaces, `-1` stays a negative number literal.
+        assert_eq!(lex_ok("N-1"), vec![Token::Ident, Token::Number(-1.0)]);
+    }
+
     #[test]
     fn spawn_clause_tokens() {
         let tokens = lex_ok("[ch=0..CH]");
@@ -428,6 +495,30 @@ mod tests {
         assert_eq!(tokens, vec![Token::Number(-2.5e10)]);
     }
 
+    #[test]
+    fn number_hex_and_binary() {
+        let tokens = lex_ok("0x10 0xFF 0b1010 -0x8");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Number(16.0),
+                Token::Number(255.0),
+                Token::Number(10.0),
+                Token::Number(-8.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn number_invalid_hex_digit() {
+        let (tokens, errors) = lex_all("0xG 0b102");
+        assert!(tokens.is_empty(), "got tokens: {:?}", tokens);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "invalid hexadecimal literal: \"0xG\"");
+        assert_eq!(errors[0].span, Span { start: 0, end: 3 });
+        assert_eq!(errors[1].message, "invalid binary literal: \"0b102\"");
+    }
+
     // ── Frequency literals ──
 
     #[test]
@@ -454,6 +545,12 @@ mod tests {
         assert_eq!(tokens, vec![Token::Freq(2_400_000_000.0)]);
     }
 
+    #[test]
+    fn freq_fractional() {
+        assert_eq!(lex_ok("0.5Hz"), vec![Token::Freq(0.5)]);
+        assert_eq!(lex_ok("1.25kHz"), vec![Token::Freq(1_250.0)]);
+    }
+
     // ── Size literals ──
 
     #[test]
@@ -474,6 +571,14 @@ mod tests {
         assert_eq!(tokens, vec![Token::Size(1024 * 1024 * 1024)]);
     }
 
+    // ── Percentage literals ──
+
+    #[test]
+    fn percent_literals() {
+        assert_eq!(lex_ok("50%"), vec![Token::Percent(50.0)]);
+        assert_eq!(lex_ok("12.5%"), vec![Token::Percent(12.5)]);
+    }
+
     // ── String literals ──
 
     #[test]
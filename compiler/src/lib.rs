@@ -8,8 +8,10 @@ pub mod codegen;
 pub mod diag;
 pub mod dot;
 pub mod graph;
+pub mod graph_query;
 pub mod hir;
 pub mod id;
+pub mod include;
 pub mod lexer;
 pub mod lir;
 pub mod lower;
@@ -17,6 +19,7 @@ pub mod parser;
 pub mod pass;
 pub mod pipeline;
 pub mod registry;
+pub mod report;
 pub mod resolve;
 pub mod schedule;
 pub mod spawn;
@@ -24,3 +27,4 @@ pub mod subgraph_index;
 pub mod thir;
 pub mod timing;
 pub mod type_infer;
+pub mod yaml;
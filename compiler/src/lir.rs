@@ -6,9 +6,9 @@
 //!
 //! See ADR-025 for design rationale.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::analyze::{AnalyzedProgram, BindContract};
 use crate::ast::BindDirection;
@@ -16,6 +16,7 @@ use crate::ast::{Arg, BindArg, Scalar, SetValue, ShapeConstraint, Value};
 use crate::graph::{Edge, NodeId, NodeKind, ProgramGraph, Subgraph, TaskGraph};
 use crate::hir::{HirSwitchSource, HirTaskBody};
 use crate::registry::{ActorMeta, ParamKind, ParamType, PipitType, TokenCount};
+use crate::resolve::Buffering;
 use crate::schedule::{FiringEntry, ScheduledProgram, SubgraphSchedule, TaskSchedule};
 use crate::subgraph_index::{
     build_subgraph_indices, identify_back_edges, subgraphs_of, GraphQueryCtx, SubgraphIndex,
@@ -30,6 +31,8 @@ pub struct LirProgram {
     pub directives: LirDirectives,
     pub binds: Vec<LirBind>,
     pub inter_task_buffers: Vec<LirInterTaskBuffer>,
+    /// Shared array families eligible for a single coalesced ring buffer.
+    pub coalesced_families: Vec<LirCoalescedFamily>,
     pub tasks: Vec<LirTask>,
     pub probes: Vec<LirProbe>,
     pub total_memory: u64,
@@ -66,8 +69,13 @@ pub struct LirParam {
 pub struct LirDirectives {
     pub mem_bytes: u64,
     pub overrun_policy: String,
+    /// `set buffering`: writer frames per inter-task ring (already folded
+    /// into each buffer's `capacity_tokens`).
+    pub buffering: Buffering,
     pub timer_spin: LirTimerSpin,
     pub wait_timeout_ms: u64,
+    /// `set tick_alignment = true`: commensurate tasks share a timer epoch.
+    pub tick_alignment: bool,
 }
 
 /// Timer spin mode — resolved from `set timer_spin` directive.
@@ -117,6 +125,20 @@ pub struct LirInterTaskBuffer {
     pub memory_kind: MemoryKind,
 }
 
+/// A shared array family whose element ring buffers can be replaced by one
+/// ring buffer of whole frames (`_ringbuf_<family>`). Codegen applies it only
+/// when `CodegenOptions::reuse_family_buffers` is set.
+pub struct LirCoalescedFamily {
+    pub family_name: String,
+    pub cpp_type: &'static str,
+    /// Sum of the element ring buffer capacities.
+    pub capacity_tokens: u32,
+    pub reader_count: usize,
+    pub reader_tasks: Vec<String>,
+    /// Element buffer names replaced by the coalesced buffer.
+    pub elements: Vec<String>,
+}
+
 // ── Tasks ──────────────────────────────────────────────────────────────────
 
 pub struct LirTask {
@@ -126,6 +148,10 @@ pub struct LirTask {
     pub body: LirTaskBody,
     pub used_params: Vec<LirUsedParam>,
     pub feedback_buffers: Vec<LirFeedbackBuffer>,
+    /// Timer starts from the shared tick epoch (`set tick_alignment`).
+    pub tick_aligned: bool,
+    /// CPU core the task thread is pinned to (`set affinity`).
+    pub cpu: Option<u32>,
 }
 
 pub struct LirUsedParam {
@@ -147,9 +173,20 @@ pub struct LirModalBody {
 }
 
 pub enum LirCtrlSource {
-    Param { name: String },
-    EdgeBuffer { var_name: String },
-    RingBuffer { name: String, reader_idx: usize },
+    Param {
+        name: String,
+    },
+    EdgeBuffer {
+        var_name: String,
+    },
+    RingBuffer {
+        name: String,
+        reader_idx: usize,
+    },
+    /// IN bind; the last received mode persists across ticks.
+    Bind {
+        name: String,
+    },
 }
 
 pub struct LirFeedbackReset {
@@ -171,8 +208,19 @@ pub struct LirSubgraph {
     /// Invariant: sorted by (src_node_id, tgt_node_id) for deterministic output.
     pub edge_buffers: Vec<LirEdgeBuffer>,
     pub firings: Vec<LirFiringGroup>,
+    /// Actor firings evaluated once before the tick loop: `PURE` actors with
+    /// constant arguments whose inputs come only from other such firings.
+    /// Their output buffers never change, so codegen skips them per tick.
+    pub startup_nodes: Vec<NodeId>,
+    /// Unfused `PURE` actor firings whose repetition loop may run its
+    /// iterations in parallel (`--openmp`): no scratch, not on a feedback
+    /// cycle, and at least `OPENMP_MIN_REPETITION` iterations.
+    pub parallel_nodes: Vec<NodeId>,
 }
 
+/// Smallest repetition count worth a `#pragma omp parallel for`.
+pub const OPENMP_MIN_REPETITION: u32 = 64;
+
 pub struct LirEdgeBuffer {
     pub var_name: String,
     pub cpp_type: &'static str,
@@ -227,10 +275,12 @@ pub struct LirActorFiring {
     pub void_output: bool,
     /// True if actor can be hoisted above K-loop (no ParamRef args).
     pub tick_hoistable: bool,
+    /// Actor declared `INLINE`: fire through `pipit::fire_inline`.
+    pub inline: bool,
 }
 
 /// Structured actor argument — resolved by LIR builder, formatted by codegen.
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum LirActorArg {
     /// Scalar literal: "256", "3.14f", "0".
     Literal(String),
@@ -238,12 +288,20 @@ pub enum LirActorArg {
     ParamRef(String),
     /// Const scalar reference: emits resolved literal value.
     ConstScalar(String),
-    /// Const array as span: emits `std::span<const {elem_type}>(_const_{name}, {len})`.
-    ConstSpan { name: String, len: u32 },
+    /// Const array (or `name[start:end]` slice) as span: emits
+    /// `std::span<const {elem_type}>(_const_{name} + {offset}, {len})`.
+    ConstSpan { name: String, offset: u32, len: u32 },
     /// Const array length: emits the numeric count.
     ConstArrayLen(u32),
     /// Resolved dimension value from shape/span/schedule inference.
     DimValue(u32),
+    /// Per-node `SCRATCH()` array, always the last arg: emits the array
+    /// name (decays to a pointer); codegen declares it at task scope.
+    Scratch {
+        var_name: String,
+        cpp_type: &'static str,
+        elements: u32,
+    },
 }
 
 pub struct LirEdgeRef {
@@ -296,6 +354,9 @@ pub struct LirGatherIo {
     /// Total output tokens = element_count * tokens_per_element.
     pub total_output_tokens: u32,
     pub elements: Vec<LirGatherElement>,
+    /// Reader index on the coalesced family ring buffer, if the family is
+    /// eligible for buffer reuse.
+    pub coalesced_reader_idx: Option<usize>,
 }
 
 pub struct LirGatherElement {
@@ -321,6 +382,8 @@ pub struct LirScatterIo {
     /// Total input tokens = element_count * tokens_per_element.
     pub total_input_tokens: u32,
     pub elements: Vec<LirScatterElement>,
+    /// True if the family is eligible for a coalesced ring buffer.
+    pub coalesced: bool,
 }
 
 pub struct LirScatterElement {
@@ -389,15 +452,52 @@ impl LirBind {
 
 // ── Interface manifest ───────────────────────────────────────────────────────
 
+/// Serialization of the interface manifest (`--interface-format`). Both
+/// formats carry the same keys in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ManifestFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
 /// Top-level interface manifest (§5.5.5, emitted by `--emit interface`).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceManifest {
     pub schema: u32,
     pub binds: Vec<InterfaceBindEntry>,
+    /// Analysis-derived sizing, absent in manifests written before it existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sizing: Option<InterfaceSizing>,
+}
+
+/// Repetition vectors and shared buffer sizes, so deployment tools need not
+/// re-derive them from the bind contracts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceSizing {
+    /// Per task, keyed by task name.
+    pub tasks: BTreeMap<String, InterfaceTaskSizing>,
+    /// Inter-task shared buffer sizes in bytes, keyed by buffer name.
+    pub inter_task_buffers: BTreeMap<String, u64>,
+}
+
+/// Sizing for one task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceTaskSizing {
+    /// Repetition vector per subgraph label (`pipeline`, `control`, or the
+    /// mode name), sorted by node id.
+    pub repetition_vectors: BTreeMap<String, Vec<InterfaceRepetition>>,
+}
+
+/// One repetition vector entry: node `node_id` fires `count` times per PASS cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceRepetition {
+    pub node_id: u32,
+    pub count: u32,
 }
 
 /// A single bind in the interface manifest.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceBindEntry {
     pub stable_id: String,
     pub name: String,
@@ -405,20 +505,27 @@ pub struct InterfaceBindEntry {
     pub dtype: Option<String>,
     pub shape: Vec<u32>,
     pub rate_hz: Option<f64>,
+    /// Author-declared wire version (`version=N`), absent when unversioned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u16>,
+    /// Estimated delay (ms) from cross-clock shared buffers upstream of an
+    /// OUT bind, absent when none feed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<f64>,
     pub endpoint: InterfaceEndpoint,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub endpoint_override: Option<String>,
 }
 
 /// Endpoint description in the interface manifest.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceEndpoint {
     pub transport: String,
     pub args: Vec<InterfaceArg>,
 }
 
 /// A single endpoint argument, preserving order and positional/named distinction.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind")]
 pub enum InterfaceArg {
     #[serde(rename = "positional")]
@@ -428,7 +535,7 @@ pub enum InterfaceArg {
 }
 
 /// A scalar value in the interface manifest.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum InterfaceValue {
     String(String),
@@ -436,6 +543,168 @@ pub enum InterfaceValue {
     Float(f64),
 }
 
+/// One difference between a baseline interface manifest and the current one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterfaceChange {
+    Added {
+        name: String,
+    },
+    Removed {
+        name: String,
+    },
+    StableIdChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+    DirectionChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+    DtypeChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    ShapeChanged {
+        name: String,
+        old: Vec<u32>,
+        new: Vec<u32>,
+    },
+    RateChanged {
+        name: String,
+        old: Option<f64>,
+        new: Option<f64>,
+    },
+    VersionChanged {
+        name: String,
+        old: Option<u16>,
+        new: Option<u16>,
+    },
+}
+
+impl InterfaceChange {
+    /// Whether an external client built against the baseline may stop working.
+    /// Only a newly added bind is compatible.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(self, InterfaceChange::Added { .. })
+    }
+}
+
+impl std::fmt::Display for InterfaceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn opt<T: std::fmt::Display>(v: &Option<T>) -> String {
+            v.as_ref().map_or("unknown".to_string(), |v| v.to_string())
+        }
+        match self {
+            InterfaceChange::Added { name } => write!(f, "bind '{}' added", name),
+            InterfaceChange::Removed { name } => write!(f, "bind '{}' removed", name),
+            InterfaceChange::StableIdChanged { name, old, new } => {
+                write!(f, "bind '{}' stable_id changed: {} -> {}", name, old, new)
+            }
+            InterfaceChange::DirectionChanged { name, old, new } => {
+                write!(f, "bind '{}' direction changed: {} -> {}", name, old, new)
+            }
+            InterfaceChange::DtypeChanged { name, old, new } => write!(
+                f,
+                "bind '{}' dtype changed: {} -> {}",
+                name,
+                opt(old),
+                opt(new)
+            ),
+            InterfaceChange::ShapeChanged { name, old, new } => {
+                write!(f, "bind '{}' shape changed: {:?} -> {:?}", name, old, new)
+            }
+            InterfaceChange::RateChanged { name, old, new } => write!(
+                f,
+                "bind '{}' rate_hz changed: {} -> {}",
+                name,
+                opt(old),
+                opt(new)
+            ),
+            InterfaceChange::VersionChanged { name, old, new } => write!(
+                f,
+                "bind '{}' version changed: {} -> {}",
+                name,
+                opt(old),
+                opt(new)
+            ),
+        }
+    }
+}
+
+/// Compare two interface manifests bind-by-name.
+///
+/// Changes are reported in baseline order (removals and field changes),
+/// followed by binds only present in `current`. Endpoint transport and
+/// arguments are deployment details and are not compared.
+pub fn diff_interface_manifests(
+    baseline: &InterfaceManifest,
+    current: &InterfaceManifest,
+) -> Vec<InterfaceChange> {
+    let mut changes = Vec::new();
+    for old in &baseline.binds {
+        let Some(new) = current.binds.iter().find(|b| b.name == old.name) else {
+            changes.push(InterfaceChange::Removed {
+                name: old.name.clone(),
+            });
+            continue;
+        };
+        let name = old.name.clone();
+        if old.stable_id != new.stable_id {
+            changes.push(InterfaceChange::StableIdChanged {
+                name: name.clone(),
+                old: old.stable_id.clone(),
+                new: new.stable_id.clone(),
+            });
+        }
+        if old.direction != new.direction {
+            changes.push(InterfaceChange::DirectionChanged {
+                name: name.clone(),
+                old: old.direction.clone(),
+                new: new.direction.clone(),
+            });
+        }
+        if old.dtype != new.dtype {
+            changes.push(InterfaceChange::DtypeChanged {
+                name: name.clone(),
+                old: old.dtype.clone(),
+                new: new.dtype.clone(),
+            });
+        }
+        if old.shape != new.shape {
+            changes.push(InterfaceChange::ShapeChanged {
+                name: name.clone(),
+                old: old.shape.clone(),
+                new: new.shape.clone(),
+            });
+        }
+        if old.rate_hz != new.rate_hz {
+            changes.push(InterfaceChange::RateChanged {
+                name: name.clone(),
+                old: old.rate_hz,
+                new: new.rate_hz,
+            });
+        }
+        if old.version != new.version {
+            changes.push(InterfaceChange::VersionChanged {
+                name,
+                old: old.version,
+                new: new.version,
+            });
+        }
+    }
+    for new in &current.binds {
+        if !baseline.binds.iter().any(|b| b.name == new.name) {
+            changes.push(InterfaceChange::Added {
+                name: new.name.clone(),
+            });
+        }
+    }
+    changes
+}
+
 // ── Verification ─────────────────────────────────────────────────────────────
 
 /// Machine-checkable evidence for LIR postconditions (R1-R2).
@@ -549,23 +818,42 @@ pub(crate) fn fmt_bind_value(v: &LirBindValue) -> String {
 }
 
 impl LirProgram {
-    /// Generate a JSON interface manifest for all binds.
+    /// Generate the interface manifest for all binds, serialized as `format`.
     pub fn generate_interface_manifest(
         &self,
         bind_overrides: &std::collections::HashMap<String, String>,
+        analysis: &AnalyzedProgram,
+        format: ManifestFormat,
     ) -> String {
+        let manifest = self.interface_manifest(bind_overrides, analysis);
+        match format {
+            ManifestFormat::Json => {
+                serde_json::to_string_pretty(&manifest).expect("interface manifest serialization")
+            }
+            ManifestFormat::Yaml => crate::yaml::to_string(&manifest),
+        }
+    }
+
+    /// Build the interface manifest for all binds, with sizing from `analysis`.
+    pub fn interface_manifest(
+        &self,
+        bind_overrides: &std::collections::HashMap<String, String>,
+        analysis: &AnalyzedProgram,
+    ) -> InterfaceManifest {
         let binds = self
             .binds
             .iter()
             .map(|b| {
-                let (direction, dtype, shape, rate_hz) = match &b.contract {
+                let (direction, dtype, shape, rate_hz, version, latency_ms) = match &b.contract {
                     Some(c) => (
                         c.direction.to_string(),
                         c.dtype.map(|t| t.to_string()),
                         c.shape.clone(),
                         c.rate_hz,
+                        c.version,
+                        c.latency_ms,
                     ),
-                    None => ("unknown".to_string(), None, Vec::new(), None),
+                    None => ("unknown".to_string(), None, Vec::new(), None, None, None),
                 };
                 InterfaceBindEntry {
                     stable_id: b.stable_id.clone(),
@@ -574,6 +862,8 @@ impl LirProgram {
                     dtype,
                     shape,
                     rate_hz,
+                    version,
+                    latency_ms,
                     endpoint: InterfaceEndpoint {
                         transport: b.transport.clone(),
                         args: b.args.iter().map(lir_bind_arg_to_interface).collect(),
@@ -583,8 +873,40 @@ impl LirProgram {
             })
             .collect();
 
-        let manifest = InterfaceManifest { schema: 1, binds };
-        serde_json::to_string_pretty(&manifest).expect("interface manifest serialization")
+        InterfaceManifest {
+            schema: 1,
+            binds,
+            sizing: Some(interface_sizing(analysis)),
+        }
+    }
+}
+
+fn interface_sizing(analysis: &AnalyzedProgram) -> InterfaceSizing {
+    let mut tasks: BTreeMap<String, InterfaceTaskSizing> = BTreeMap::new();
+    for ((task, label), counts) in &analysis.repetition_vectors {
+        let mut entries: Vec<InterfaceRepetition> = counts
+            .iter()
+            .map(|(id, &count)| InterfaceRepetition {
+                node_id: id.0,
+                count,
+            })
+            .collect();
+        entries.sort_unstable_by_key(|e| e.node_id);
+        tasks
+            .entry(task.clone())
+            .or_insert_with(|| InterfaceTaskSizing {
+                repetition_vectors: BTreeMap::new(),
+            })
+            .repetition_vectors
+            .insert(label.clone(), entries);
+    }
+    InterfaceSizing {
+        tasks,
+        inter_task_buffers: analysis
+            .inter_task_buffers
+            .iter()
+            .map(|(name, &bytes)| (name.clone(), bytes))
+            .collect(),
     }
 }
 
@@ -662,9 +984,10 @@ impl std::fmt::Display for LirProgram {
         };
         writeln!(
             f,
-            "  directives: mem={}, overrun={}, timer={}, wait_timeout={}ms",
+            "  directives: mem={}, overrun={}, buffering={}, timer={}, wait_timeout={}ms",
             self.directives.mem_bytes,
             self.directives.overrun_policy,
+            self.directives.buffering,
             timer,
             self.directives.wait_timeout_ms
         )?;
@@ -720,6 +1043,20 @@ impl std::fmt::Display for LirProgram {
             )?;
         }
 
+        // Coalescable families
+        for fam in &self.coalesced_families {
+            writeln!(
+                f,
+                "  family-reuse {}: {}[{}] readers={} [{}] <- {}",
+                fam.family_name,
+                fam.cpp_type,
+                fam.capacity_tokens,
+                fam.reader_count,
+                fam.reader_tasks.join(", "),
+                fam.elements.join(", "),
+            )?;
+        }
+
         // Tasks
         for task in &self.tasks {
             fmt_lir_task(f, task, "  ")?;
@@ -784,6 +1121,7 @@ fn fmt_ctrl_source(src: &LirCtrlSource) -> String {
     match src {
         LirCtrlSource::Param { name } => format!("param({})", name),
         LirCtrlSource::EdgeBuffer { var_name } => format!("edge({})", var_name),
+        LirCtrlSource::Bind { name } => format!("bind({})", name),
         LirCtrlSource::RingBuffer { name, reader_idx } => {
             format!("ring({}, reader={})", name, reader_idx)
         }
@@ -812,6 +1150,16 @@ fn fmt_lir_subgraph(
         writeln!(f, "{}edge_buffers: {}", indent, bufs.join(", "))?;
     }
 
+    if !sub.startup_nodes.is_empty() {
+        let nodes: Vec<String> = sub.startup_nodes.iter().map(|n| n.0.to_string()).collect();
+        writeln!(f, "{}startup (once): [{}]", indent, nodes.join(", "))?;
+    }
+
+    if !sub.parallel_nodes.is_empty() {
+        let nodes: Vec<String> = sub.parallel_nodes.iter().map(|n| n.0.to_string()).collect();
+        writeln!(f, "{}parallel (openmp): [{}]", indent, nodes.join(", "))?;
+    }
+
     // Firings
     writeln!(f, "{}firings:", indent)?;
     for group in &sub.firings {
@@ -931,9 +1279,17 @@ fn fmt_args(args: &[LirActorArg]) -> String {
             LirActorArg::Literal(lit) => lit.clone(),
             LirActorArg::ParamRef(name) => format!("${}", name),
             LirActorArg::ConstScalar(lit) => lit.clone(),
-            LirActorArg::ConstSpan { name, len } => format!(":{}[{}]", name, len),
+            LirActorArg::ConstSpan {
+                name,
+                offset: 0,
+                len,
+            } => format!(":{}[{}]", name, len),
+            LirActorArg::ConstSpan { name, offset, len } => {
+                format!(":{}[{}:{}]", name, offset, offset + len)
+            }
             LirActorArg::ConstArrayLen(n) => format!("len({})", n),
             LirActorArg::DimValue(n) => format!("dim({})", n),
+            LirActorArg::Scratch { var_name, .. } => format!("&{}", var_name),
         })
         .collect::<Vec<_>>()
         .join(", ")
@@ -998,6 +1354,7 @@ impl<'a> LirBuilder<'a> {
             directives: self.build_directives(),
             binds: self.build_binds(),
             inter_task_buffers: self.build_inter_task_buffers(),
+            coalesced_families: self.build_coalesced_families(),
             tasks: self.build_tasks(),
             probes: self.build_probes(),
             total_memory: self.analysis.total_memory,
@@ -1063,8 +1420,13 @@ impl<'a> LirBuilder<'a> {
         LirDirectives {
             mem_bytes: self.thir.mem_bytes,
             overrun_policy: self.thir.overrun_policy.clone(),
+            buffering: self.thir.buffering,
             timer_spin,
             wait_timeout_ms: self.thir.wait_timeout_ms,
+            tick_alignment: matches!(
+                self.thir.set_directive("tick_alignment").map(|d| &d.value),
+                Some(SetValue::Ident(ident)) if ident.name == "true"
+            ),
         }
     }
 
@@ -1114,6 +1476,38 @@ impl<'a> LirBuilder<'a> {
             .collect()
     }
 
+    fn build_coalesced_families(&self) -> Vec<LirCoalescedFamily> {
+        self.analysis
+            .family_reuse
+            .iter()
+            .map(|fam| {
+                let elements: Vec<String> = (0..fam.element_count)
+                    .map(|i| format!("{}__{}", fam.family_name, i))
+                    .collect();
+                let wire_type = self.infer_buffer_wire_type(&elements[0]);
+                let capacity_tokens = elements
+                    .iter()
+                    .map(|e| self.inter_task_buffer_capacity(e, wire_type))
+                    .sum();
+                LirCoalescedFamily {
+                    family_name: fam.family_name.clone(),
+                    cpp_type: pipit_type_to_cpp(wire_type),
+                    capacity_tokens,
+                    reader_count: fam.reader_tasks.len().max(1),
+                    reader_tasks: fam.reader_tasks.clone(),
+                    elements,
+                }
+            })
+            .collect()
+    }
+
+    fn family_reuse(&self, family_name: &str) -> Option<&crate::analyze::FamilyReuse> {
+        self.analysis
+            .family_reuse
+            .iter()
+            .find(|f| f.family_name == family_name)
+    }
+
     // ── Probes ─────────────────────────────────────────────────────────
 
     fn build_probes(&self) -> Vec<LirProbe> {
@@ -1163,10 +1557,14 @@ impl<'a> LirBuilder<'a> {
     fn build_tasks(&self) -> Vec<LirTask> {
         let mut task_names: Vec<&String> = self.schedule.tasks.keys().collect();
         task_names.sort();
-        task_names
+        let mut tasks: Vec<LirTask> = task_names
             .into_iter()
             .filter_map(|name| self.build_task(name))
-            .collect()
+            .collect();
+        if self.build_directives().tick_alignment {
+            mark_tick_aligned_tasks(&mut tasks);
+        }
+        tasks
     }
 
     fn build_task(&self, task_name: &str) -> Option<LirTask> {
@@ -1220,6 +1618,8 @@ impl<'a> LirBuilder<'a> {
             body,
             used_params,
             feedback_buffers,
+            tick_aligned: false,
+            cpu: self.thir.affinity.get(task_name).copied(),
         })
     }
 
@@ -1319,6 +1719,14 @@ impl<'a> LirBuilder<'a> {
         buffers
     }
 
+    fn is_persistent_delay(&self, sub: &Subgraph, node_id: NodeId) -> bool {
+        let gq = self.gqctx();
+        matches!(
+            gq.node_in_subgraph(sub, node_id).map(|n| &n.kind),
+            Some(NodeKind::Actor { name, persist: true, .. }) if name == "delay"
+        )
+    }
+
     fn delay_init_value(&self, sub: &Subgraph, node_id: NodeId) -> String {
         let gq = self.gqctx();
         if let Some(node) = gq.node_in_subgraph(sub, node_id) {
@@ -1344,6 +1752,10 @@ impl<'a> LirBuilder<'a> {
         for (_mode_name, sub) in mode_subs {
             let back_edges = identify_back_edges(sub, &self.graph.cycles);
             for (src, tgt) in &back_edges {
+                if self.is_persistent_delay(sub, *src) {
+                    // `@persist`: state carries over from the previous activation.
+                    continue;
+                }
                 let tokens = sub
                     .edges
                     .iter()
@@ -1383,6 +1795,7 @@ impl<'a> LirBuilder<'a> {
 
         match switch_source {
             Some(HirSwitchSource::Param(name, _)) => LirCtrlSource::Param { name },
+            Some(HirSwitchSource::Bind(name, _)) => LirCtrlSource::Bind { name },
             Some(HirSwitchSource::Buffer(name, _)) => {
                 // Check if the buffer is read via a BufferRead in the ctrl subgraph
                 if let Some(ctrl_var) = self.find_ctrl_output_for_buffer(ctrl_sub, &name) {
@@ -1453,19 +1866,230 @@ impl<'a> LirBuilder<'a> {
     ) -> LirSubgraph {
         let back_edges = identify_back_edges(sub, &self.graph.cycles);
         let adj = EdgeAdjacency::build(sub);
-        let aliases = build_passthrough_aliases_with_adj(sub, &adj);
+        let mut aliases = build_passthrough_aliases_with_adj(sub, &adj);
+        let duplicates = self.plan_common_subexpressions(sub, sched, &back_edges, &aliases, &adj);
+        for (&dup, &original) in &duplicates {
+            let (Some(dup_out), Some(orig_out)) =
+                (adj.outgoing(dup).first(), adj.outgoing(original).first())
+            else {
+                continue;
+            };
+            aliases.insert(
+                (dup_out.source, dup_out.target),
+                (orig_out.source, orig_out.target),
+            );
+        }
+        resolve_transitive_aliases(&mut aliases);
         let (edge_buffers, edge_buf_names) =
             self.build_edge_buffers_and_names(sub, sched, &back_edges, &aliases);
 
-        let firings =
-            self.build_firing_groups(task_name, sub, sched, &edge_buf_names, &back_edges, &adj);
+        let firings = self.build_firing_groups(
+            task_name,
+            sub,
+            sched,
+            &edge_buf_names,
+            &back_edges,
+            &adj,
+            &duplicates,
+        );
+        let startup_nodes = self.plan_startup_nodes(sub, &firings);
+        let parallel_nodes = self.plan_parallel_nodes(sub, &firings, &startup_nodes);
 
         LirSubgraph {
             edge_buffers,
             firings,
+            startup_nodes,
+            parallel_nodes,
         }
     }
 
+    /// Whether an actor node carries the `PURE` marker (no state across firings).
+    fn is_pure_actor(&self, sub: &Subgraph, node_id: NodeId) -> bool {
+        match self.gqctx().node_in_subgraph(sub, node_id).map(|n| &n.kind) {
+            Some(NodeKind::Actor { name, call_id, .. }) => self
+                .thir
+                .concrete_actor(name, *call_id)
+                .is_some_and(|meta| meta.pure),
+            _ => false,
+        }
+    }
+
+    /// Common-subexpression elimination: map each duplicate `PURE` actor
+    /// firing to the earliest structurally identical one (same actor and
+    /// resolved arguments, same repetition, same input buffer after fork and
+    /// probe aliasing). The duplicate is not fired and its output edge aliases
+    /// the original's buffer. Runtime-param, tap-ref, or scratch arguments,
+    /// feedback cycles, fused chains, and void outputs are never merged.
+    fn plan_common_subexpressions(
+        &self,
+        sub: &Subgraph,
+        sched: &SubgraphSchedule,
+        back_edges: &HashSet<(NodeId, NodeId)>,
+        aliases: &HashMap<(NodeId, NodeId), (NodeId, NodeId)>,
+        adj: &EdgeAdjacency<'_>,
+    ) -> HashMap<NodeId, NodeId> {
+        let mut duplicates = HashMap::new();
+        let fused: HashSet<NodeId> = self
+            .plan_fusion_candidates(sub, sched, back_edges)
+            .values()
+            .flat_map(|c| c.node_ids.iter().copied())
+            .collect();
+        let firing_reps: HashMap<NodeId, u32> = sched
+            .firings
+            .iter()
+            .map(|f| (f.node_id, f.repetition_count))
+            .collect();
+        let edge_key = |e: &Edge| {
+            let key = (e.source, e.target);
+            aliases.get(&key).copied().unwrap_or(key)
+        };
+
+        // (node, actor, args, repetition, input buffers, output tokens)
+        type Signature = (
+            NodeId,
+            String,
+            Vec<LirActorArg>,
+            u32,
+            Vec<(NodeId, NodeId)>,
+            u32,
+        );
+        let mut seen: Vec<Signature> = Vec::new();
+        for entry in &sched.firings {
+            let Some(node) = self.gqctx().node_in_subgraph(sub, entry.node_id) else {
+                continue;
+            };
+            let NodeKind::Actor {
+                name,
+                call_id,
+                args,
+                shape_constraint,
+                ..
+            } = &node.kind
+            else {
+                continue;
+            };
+            let Some(meta) = self.thir.concrete_actor(name, *call_id) else {
+                continue;
+            };
+            let incoming = adj.incoming(node.id);
+            let outgoing = adj.outgoing(node.id);
+            let eligible = meta.pure
+                && meta.scratch.is_none()
+                && meta.out_type.as_concrete() != Some(PipitType::Void)
+                && is_actor_hoistable(args, false)
+                && !fused.contains(&node.id)
+                && !self.graph.cycles.iter().any(|c| c.contains(&node.id))
+                && !incoming.is_empty()
+                && outgoing.len() == 1
+                && !back_edges.contains(&(outgoing[0].source, outgoing[0].target));
+            if !eligible {
+                continue;
+            }
+
+            let overrides = self.build_schedule_dim_overrides(
+                meta,
+                args,
+                shape_constraint.as_ref(),
+                sched,
+                node.id,
+                adj,
+                &firing_reps,
+            );
+            let params =
+                self.resolve_actor_args(meta, args, shape_constraint.as_ref(), &overrides, node.id);
+            let inputs: Vec<(NodeId, NodeId)> = incoming.iter().map(|e| edge_key(e)).collect();
+            let out_tokens = sched
+                .edge_buffers
+                .get(&(outgoing[0].source, outgoing[0].target))
+                .copied()
+                .unwrap_or(1);
+            let cpp_name = self.actor_cpp_name(name, *call_id);
+            let original = seen.iter().find(|(_, n, p, r, i, t)| {
+                *n == cpp_name
+                    && *p == params
+                    && *r == entry.repetition_count
+                    && *i == inputs
+                    && *t == out_tokens
+            });
+            match original {
+                Some(&(original, ..)) => {
+                    duplicates.insert(node.id, original);
+                }
+                None => seen.push((
+                    node.id,
+                    cpp_name,
+                    params,
+                    entry.repetition_count,
+                    inputs,
+                    out_tokens,
+                )),
+            }
+        }
+        duplicates
+    }
+
+    /// Find repetition loops safe to split across threads: unfused, looped
+    /// `PURE` actor firings with no scratch, off every feedback cycle, and
+    /// with at least `OPENMP_MIN_REPETITION` iterations.
+    fn plan_parallel_nodes(
+        &self,
+        sub: &Subgraph,
+        firings: &[LirFiringGroup],
+        startup: &[NodeId],
+    ) -> Vec<NodeId> {
+        firings
+            .iter()
+            .filter_map(|group| match group {
+                LirFiringGroup::Single(LirFiring {
+                    kind: LirFiringKind::Actor(actor),
+                    repetition,
+                    needs_loop: true,
+                }) if *repetition >= OPENMP_MIN_REPETITION => Some(actor),
+                _ => None,
+            })
+            .filter(|actor| {
+                !startup.contains(&actor.node_id)
+                    && self.is_pure_actor(sub, actor.node_id)
+                    && !actor
+                        .params
+                        .iter()
+                        .any(|p| matches!(p, LirActorArg::Scratch { .. }))
+                    && !self.graph.cycles.iter().any(|c| c.contains(&actor.node_id))
+            })
+            .map(|actor| actor.node_id)
+            .collect()
+    }
+
+    /// Find the constant prefix of a subgraph: unfused `PURE` actor firings
+    /// with no runtime-param or scratch arguments, fed only by earlier
+    /// startup firings. Firings are in topological order, so one pass suffices.
+    fn plan_startup_nodes(&self, sub: &Subgraph, firings: &[LirFiringGroup]) -> Vec<NodeId> {
+        let mut startup: Vec<NodeId> = Vec::new();
+        for group in firings {
+            let LirFiringGroup::Single(LirFiring {
+                kind: LirFiringKind::Actor(actor),
+                ..
+            }) = group
+            else {
+                continue;
+            };
+            let pure = self.is_pure_actor(sub, actor.node_id);
+            let constant_args = actor.tick_hoistable
+                && !actor
+                    .params
+                    .iter()
+                    .any(|p| matches!(p, LirActorArg::Scratch { .. }));
+            let constant_inputs = actor
+                .inputs
+                .iter()
+                .all(|input| startup.contains(&input.peer_node_id));
+            if pure && constant_args && constant_inputs && !actor.void_output {
+                startup.push(actor.node_id);
+            }
+        }
+        startup
+    }
+
     /// Build edge buffer declarations AND the (src,tgt)→var_name map in a single
     /// sorted pass over `sched.edge_buffers`, eliminating the duplicate sort+iterate
     /// that the old separate `build_edge_buffers` / `build_edge_buf_name_map` pair did.
@@ -1539,6 +2163,7 @@ impl<'a> LirBuilder<'a> {
 
     // ── Firing groups ──────────────────────────────────────────────────
 
+    #[allow(clippy::too_many_arguments)]
     fn build_firing_groups(
         &self,
         task_name: &str,
@@ -1547,6 +2172,7 @@ impl<'a> LirBuilder<'a> {
         edge_bufs: &HashMap<(NodeId, NodeId), String>,
         back_edges: &HashSet<(NodeId, NodeId)>,
         adj: &EdgeAdjacency<'_>,
+        duplicates: &HashMap<NodeId, NodeId>,
     ) -> Vec<LirFiringGroup> {
         let fused = self.plan_fusion_candidates(sub, sched, back_edges);
         // Precompute node_id → repetition_count for O(1) lookup
@@ -1576,6 +2202,11 @@ impl<'a> LirBuilder<'a> {
             }
 
             let entry = &sched.firings[idx];
+            if duplicates.contains_key(&entry.node_id) {
+                // Output aliases the original firing's buffer (CSE)
+                idx += 1;
+                continue;
+            }
             let gq = self.gqctx();
             if let Some(node) = gq.node_in_subgraph(sub, entry.node_id) {
                 let firing = self.build_single_firing(
@@ -1759,7 +2390,7 @@ impl<'a> LirBuilder<'a> {
             HashMap::new()
         };
 
-        let params = if let Some(meta) = meta {
+        let mut params = if let Some(meta) = meta {
             self.resolve_actor_args(
                 meta,
                 args,
@@ -1771,6 +2402,16 @@ impl<'a> LirBuilder<'a> {
             Vec::new()
         };
 
+        let scratch = meta.and_then(|m| m.scratch.as_ref()).and_then(|sc| {
+            let elements = *self.analysis.scratch_sizes.get(&node_id)?;
+            Some(LirActorArg::Scratch {
+                var_name: format!("_scratch_{}", node_id.0),
+                cpp_type: pipit_type_to_cpp(sc.elem_type),
+                elements,
+            })
+        });
+        params.extend(scratch);
+
         let (in_type, out_type) = if let Some(meta) = meta {
             (
                 pipit_type_to_cpp(meta.in_type.as_concrete().unwrap_or(PipitType::Float)),
@@ -1828,6 +2469,7 @@ impl<'a> LirBuilder<'a> {
             node_id,
             void_output,
             tick_hoistable,
+            inline: meta.is_some_and(|m| m.inline),
         }
     }
 
@@ -1862,6 +2504,12 @@ impl<'a> LirBuilder<'a> {
     }
 
     fn actor_cpp_name(&self, actor_name: &str, call_id: crate::id::CallId) -> String {
+        // An ALIAS() name resolves to the struct of the canonical actor.
+        let actor_name = self
+            .thir
+            .registry
+            .lookup(actor_name)
+            .map_or(actor_name, |meta| meta.name.as_str());
         if let Some(types) = self.thir.lowered.type_instantiations.get(&call_id) {
             if !types.is_empty() {
                 let type_args: Vec<&str> = types.iter().map(|t| pipit_type_to_cpp(*t)).collect();
@@ -1979,12 +2627,14 @@ impl<'a> LirBuilder<'a> {
     }
 
     fn is_const_array_ref(&self, arg: &Arg) -> bool {
-        if let Arg::ConstRef(ident) = arg {
-            if let Some(c) = self.thir.const_info(&ident.name) {
-                return matches!(&c.value, Value::Array(_, _));
-            }
-        }
-        false
+        let name = match arg {
+            Arg::ConstRef(ident) => &ident.name,
+            Arg::ConstSlice(slice) => &slice.name.name,
+            _ => return false,
+        };
+        self.thir
+            .const_info(name)
+            .is_some_and(|c| matches!(&c.value, Value::Array(_, _)))
     }
 
     fn arg_to_lir_literal(&self, arg: &Arg) -> LirActorArg {
@@ -2004,6 +2654,9 @@ impl<'a> LirBuilder<'a> {
                     LirActorArg::Literal(format!("_const_{}", ident.name))
                 }
             }
+            Arg::ConstSlice(slice) => {
+                LirActorArg::Literal(format!("_const_{} + {}", slice.name.name, slice.start))
+            }
             Arg::TapRef(_) => LirActorArg::Literal("/* tap */".to_string()),
         }
     }
@@ -2025,6 +2678,7 @@ impl<'a> LirBuilder<'a> {
                             ) {
                                 LirActorArg::ConstSpan {
                                     name: ident.name.clone(),
+                                    offset: 0,
                                     len: elems.len() as u32,
                                 }
                             } else {
@@ -2036,6 +2690,20 @@ impl<'a> LirBuilder<'a> {
                     LirActorArg::Literal(format!("_const_{}", ident.name))
                 }
             }
+            Arg::ConstSlice(slice) => {
+                if matches!(
+                    param_type,
+                    ParamType::SpanFloat | ParamType::SpanChar | ParamType::SpanTypeParam(_)
+                ) {
+                    LirActorArg::ConstSpan {
+                        name: slice.name.name.clone(),
+                        offset: slice.start,
+                        len: slice.len(),
+                    }
+                } else {
+                    LirActorArg::ConstArrayLen(slice.len())
+                }
+            }
             Arg::ParamRef(ident) => LirActorArg::ParamRef(ident.name.clone()),
             Arg::TapRef(_) => LirActorArg::Literal("/* tap */".to_string()),
         }
@@ -2320,6 +2988,10 @@ impl<'a> LirBuilder<'a> {
             })
             .collect();
 
+        let coalesced_reader_idx = self
+            .family_reuse(family_name)
+            .and_then(|f| f.reader_tasks.iter().position(|t| t == task_name));
+
         LirGatherIo {
             family_name: family_name.to_string(),
             element_count,
@@ -2327,6 +2999,7 @@ impl<'a> LirBuilder<'a> {
             output_edge_var,
             total_output_tokens,
             elements,
+            coalesced_reader_idx,
         }
     }
 
@@ -2390,6 +3063,7 @@ impl<'a> LirBuilder<'a> {
             input_edge_var,
             total_input_tokens,
             elements,
+            coalesced: self.family_reuse(family_name).is_some(),
         }
     }
 
@@ -2412,7 +3086,10 @@ impl<'a> LirBuilder<'a> {
             let start = i;
             let start_entry = &sched.firings[start];
             let rep = start_entry.repetition_count;
-            if rep <= 1 || !is_fusion_entry_eligible(&gq, sub, start_entry, back_edges) {
+            if rep <= 1
+                || !(is_fusion_entry_eligible(&gq, sub, start_entry, back_edges)
+                    || is_fusion_anchor_eligible(&gq, sub, start_entry, back_edges))
+            {
                 i += 1;
                 continue;
             }
@@ -2469,7 +3146,14 @@ impl<'a> LirBuilder<'a> {
         }
 
         let gq = self.gqctx();
-        if !candidate.node_ids.iter().all(|&nid| {
+        // A leading BufferRead anchors the chain; the rest must be loop-able.
+        let anchored = matches!(
+            gq.node_in_subgraph(sub, candidate.node_ids[0])
+                .map(|n| &n.kind),
+            Some(NodeKind::BufferRead { .. })
+        );
+        let loop_ids = &candidate.node_ids[usize::from(anchored)..];
+        if !loop_ids.iter().all(|&nid| {
             matches!(
                 gq.node_in_subgraph(sub, nid).map(|n| &n.kind),
                 Some(NodeKind::Actor { .. } | NodeKind::Fork { .. } | NodeKind::Probe { .. })
@@ -2522,7 +3206,7 @@ impl<'a> LirBuilder<'a> {
             }
         }
 
-        // Build body firings
+        // Build body firings (an anchor BufferRead keeps its block transfer)
         let mut body = Vec::new();
         for &node_id in &candidate.node_ids {
             let node = gq.node_in_subgraph(sub, node_id)?;
@@ -2625,7 +3309,7 @@ impl<'a> LirBuilder<'a> {
             .inter_task_buffers
             .get(buf_name)
             .copied()
-            .unwrap_or(1024);
+            .unwrap_or(crate::analyze::DEFAULT_INTER_TASK_BUFFER_BYTES);
         let elem_size = pipit_type_size(wire_type);
         if elem_size == 0 {
             return 1024;
@@ -2653,6 +3337,7 @@ impl<'a> LirBuilder<'a> {
                 }
                 format!("_const_{}", ident.name)
             }
+            Arg::ConstSlice(slice) => format!("_const_{} + {}", slice.name.name, slice.start),
             Arg::TapRef(_) => "/* tap */".to_string(),
         }
     }
@@ -2660,6 +3345,31 @@ impl<'a> LirBuilder<'a> {
 
 // ── Free helpers ────────────────────────────────────────────────────────────
 
+/// Mark tasks whose timer frequency is commensurate with another task's
+/// (one is an integer multiple of the other), so their ticks coincide
+/// periodically once started from a common epoch. Tasks with no such
+/// partner keep an independent start.
+fn mark_tick_aligned_tasks(tasks: &mut [LirTask]) {
+    let timer_hz: Vec<f64> = tasks
+        .iter()
+        .map(|t| t.freq_hz / t.k_factor as f64)
+        .collect();
+    for i in 0..tasks.len() {
+        tasks[i].tick_aligned =
+            (0..tasks.len()).any(|j| j != i && commensurate_hz(timer_hz[i], timer_hz[j]));
+    }
+}
+
+/// Whether the larger of two frequencies is an integer multiple of the smaller.
+fn commensurate_hz(a: f64, b: f64) -> bool {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    if lo <= 0.0 {
+        return false;
+    }
+    let ratio = hi / lo;
+    (ratio - ratio.round()).abs() < 1e-9 * ratio
+}
+
 fn scalar_literal(scalar: &Scalar) -> String {
     match scalar {
         Scalar::Number(n, _, _) => {
@@ -2669,7 +3379,7 @@ fn scalar_literal(scalar: &Scalar) -> String {
                 format!("{}f", n)
             }
         }
-        Scalar::Freq(f, _) => format!("{:.1}", f),
+        Scalar::Freq(f, _) => format!("{:?}", f),
         Scalar::Size(s, _) => format!("{}", s),
         Scalar::StringLit(s, _) => format!("\"{}\"", s),
         Scalar::Ident(ident) => format!("_const_{}", ident.name),
@@ -2702,13 +3412,14 @@ fn pipit_type_to_cpp(t: PipitType) -> &'static str {
         PipitType::Int32 => "int32_t",
         PipitType::Cfloat => "cfloat",
         PipitType::Cdouble => "cdouble",
+        PipitType::Bool => "bool",
         PipitType::Void => "void",
     }
 }
 
 fn pipit_type_size(t: PipitType) -> usize {
     match t {
-        PipitType::Int8 => 1,
+        PipitType::Int8 | PipitType::Bool => 1,
         PipitType::Int16 => 2,
         PipitType::Int32 | PipitType::Float => 4,
         PipitType::Double => 8,
@@ -2786,7 +3497,12 @@ fn build_passthrough_aliases_with_adj(
             }
         }
     }
-    // Resolve transitive aliases
+    resolve_transitive_aliases(&mut aliases);
+    aliases
+}
+
+/// Point every alias at the end of its chain (fork → probe → actor edge).
+fn resolve_transitive_aliases(aliases: &mut HashMap<(NodeId, NodeId), (NodeId, NodeId)>) {
     let mut changed = true;
     while changed {
         changed = false;
@@ -2800,12 +3516,11 @@ fn build_passthrough_aliases_with_adj(
             }
         }
     }
-    aliases
 }
 
 fn is_actor_hoistable(args: &[Arg], allow_param_ref: bool) -> bool {
     args.iter().all(|arg| match arg {
-        Arg::Value(_) | Arg::ConstRef(_) => true,
+        Arg::Value(_) | Arg::ConstRef(_) | Arg::ConstSlice(_) => true,
         Arg::ParamRef(_) => allow_param_ref,
         Arg::TapRef(_) => false,
     })
@@ -2911,6 +3626,37 @@ fn is_fusion_entry_eligible(
     }
 }
 
+/// A `BufferRead` may anchor a fused chain when it feeds exactly one actor:
+/// the block read runs once before the loop, and the fused actors consume
+/// its output buffer iteration by iteration.
+fn is_fusion_anchor_eligible(
+    gq: &GraphQueryCtx<'_>,
+    sub: &Subgraph,
+    entry: &FiringEntry,
+    back_edges: &HashSet<(NodeId, NodeId)>,
+) -> bool {
+    let Some(node) = gq.node_in_subgraph(sub, entry.node_id) else {
+        return false;
+    };
+    if !matches!(node.kind, NodeKind::BufferRead { .. })
+        || back_edges
+            .iter()
+            .any(|(src, tgt)| *src == entry.node_id || *tgt == entry.node_id)
+        || gq.outgoing_edge_count(sub, entry.node_id) != 1
+    {
+        return false;
+    }
+    sub.edges
+        .iter()
+        .filter(|e| e.source == entry.node_id)
+        .all(|e| {
+            matches!(
+                gq.node_in_subgraph(sub, e.target).map(|n| &n.kind),
+                Some(NodeKind::Actor { .. })
+            )
+        })
+}
+
 fn can_append_to_fusion_chain(
     gq: &GraphQueryCtx<'_>,
     sub: &Subgraph,
@@ -2952,6 +3698,69 @@ mod tests {
         crate::ast::Span::new((), 0..0)
     }
 
+    fn iface_entry(name: &str, stable_id: &str, rate_hz: f64) -> InterfaceBindEntry {
+        InterfaceBindEntry {
+            stable_id: stable_id.to_string(),
+            name: name.to_string(),
+            direction: "out".to_string(),
+            dtype: Some("float".to_string()),
+            shape: vec![1],
+            rate_hz: Some(rate_hz),
+            version: None,
+            latency_ms: None,
+            endpoint: InterfaceEndpoint {
+                transport: "udp".to_string(),
+                args: Vec::new(),
+            },
+            endpoint_override: None,
+        }
+    }
+
+    #[test]
+    fn interface_diff_classifies_breaking_changes() {
+        let baseline = InterfaceManifest {
+            schema: 1,
+            binds: vec![
+                iface_entry("iq", "aaaa", 1000.0),
+                iface_entry("gone", "bbbb", 1000.0),
+            ],
+            sizing: None,
+        };
+        let current = InterfaceManifest {
+            schema: 1,
+            binds: vec![
+                iface_entry("iq", "cccc", 500.0),
+                iface_entry("fresh", "dddd", 1000.0),
+            ],
+            sizing: None,
+        };
+        let changes = diff_interface_manifests(&baseline, &current);
+        assert_eq!(changes.len(), 4, "changes: {:?}", changes);
+        assert!(
+            matches!(&changes[0], InterfaceChange::StableIdChanged { name, .. } if name == "iq")
+        );
+        assert!(matches!(&changes[1], InterfaceChange::RateChanged { name, .. } if name == "iq"));
+        assert_eq!(
+            changes[2],
+            InterfaceChange::Removed {
+                name: "gone".to_string()
+            }
+        );
+        assert_eq!(
+            changes[3],
+            InterfaceChange::Added {
+                name: "fresh".to_string()
+            }
+        );
+        assert!(changes[..3].iter().all(InterfaceChange::is_breaking));
+        assert!(!changes[3].is_breaking());
+        assert_eq!(
+            changes[0].to_string(),
+            "bind 'iq' stable_id changed: aaaa -> cccc"
+        );
+        assert!(diff_interface_manifests(&baseline, &baseline).is_empty());
+    }
+
     #[test]
     fn scalar_literal_integer() {
         let s = Scalar::Number(42.0, span(), false);
@@ -3084,6 +3893,52 @@ mod tests {
         (lir, sched_result.schedule)
     }
 
+    #[test]
+    fn buffer_read_anchors_fusion_with_single_consumer() {
+        let (lir, _) = build_lir_and_schedule(concat!(
+            "clock 1kHz a { constant(-4.0, 8) -> sig }\n",
+            "clock 1kHz b { @sig | abs() | sqrt() | decimate(8) | stdout() }\n",
+        ));
+        let task = lir.tasks.iter().find(|t| t.name == "b").unwrap();
+        let LirTaskBody::Pipeline(sub) = &task.body else {
+            panic!("expected pipeline body");
+        };
+        let LirFiringGroup::Fused(chain) = &sub.firings[0] else {
+            panic!("BufferRead should anchor a fused chain");
+        };
+        assert_eq!(chain.repetition, 8);
+        assert_eq!(chain.body.len(), 3);
+        assert!(matches!(
+            &chain.body[0].kind,
+            LirFiringKind::BufferRead(io) if io.total_tokens == 8
+        ));
+        assert!(chain.body[1..]
+            .iter()
+            .all(|f| matches!(f.kind, LirFiringKind::Actor(_))));
+    }
+
+    #[test]
+    fn buffer_read_feeding_fork_is_not_an_anchor() {
+        let (lir, _) = build_lir_and_schedule(concat!(
+            "clock 1kHz a { constant(-4.0, 8) -> sig }\n",
+            "clock 1kHz b {\n",
+            "    @sig | :raw | abs() | decimate(8) | stdout()\n",
+            "    :raw | sqrt() | decimate(8) | stdout()\n",
+            "}\n",
+        ));
+        let task = lir.tasks.iter().find(|t| t.name == "b").unwrap();
+        let LirTaskBody::Pipeline(sub) = &task.body else {
+            panic!("expected pipeline body");
+        };
+        assert!(matches!(
+            &sub.firings[0],
+            LirFiringGroup::Single(LirFiring {
+                kind: LirFiringKind::BufferRead(_),
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn verify_lir_passing() {
         use crate::pass::StageCert;
@@ -12,7 +12,7 @@
 
 use std::collections::HashMap;
 
-use crate::ast::Span;
+use crate::ast::{Arg, Scalar, Span, Value};
 use crate::diag::codes;
 use crate::diag::{DiagLevel, Diagnostic};
 use crate::hir::{
@@ -152,6 +152,304 @@ pub fn lower_and_verify(
     }
 }
 
+// ── Constant mul fusion (--release) ─────────────────────────────────────────
+
+/// Two adjacent `mul` calls folded into one by [`fuse_constant_muls`].
+#[derive(Debug, Clone)]
+pub struct MulFusion {
+    /// Task containing the pipe.
+    pub task: String,
+    /// Call that now carries the product.
+    pub kept: CallId,
+    /// Call removed from the pipe.
+    pub removed: CallId,
+    /// Original scales of `kept` and `removed`.
+    pub scales: (f64, f64),
+    /// Folded scale.
+    pub product: f64,
+}
+
+/// Fold `mul(a) | mul(b)` into `mul(a * b)` when both scales are
+/// compile-time constants (literals or `const` refs).
+///
+/// Only direct 1:1 edges are folded: both calls take the scale as their sole
+/// argument, carry no shape constraint or annotation, and have the same
+/// concrete type with no widening between them. A tap or probe on either
+/// call's output, or a `$param` scale, keeps the pair as written. Runs on
+/// HIR before lowering so the graph never sees the removed call.
+pub fn fuse_constant_muls(
+    hir: &mut HirProgram,
+    typed: &TypedProgram,
+    registry: &Registry,
+) -> Vec<MulFusion> {
+    let is_std_mul = registry
+        .lookup("mul")
+        .and_then(|meta| meta.params.first())
+        .is_some_and(|p| p.name == "gain");
+    if !is_std_mul {
+        return Vec::new();
+    }
+    let consts: HashMap<String, (f64, bool)> = hir
+        .consts
+        .iter()
+        .filter_map(|c| match &c.value {
+            Value::Scalar(Scalar::Number(n, _, is_int)) => Some((c.name.clone(), (*n, *is_int))),
+            _ => None,
+        })
+        .collect();
+
+    let mut fusions = Vec::new();
+    for task in &mut hir.tasks {
+        let pipelines: Vec<&mut HirPipeline> = match &mut task.body {
+            HirTaskBody::Pipeline(p) => vec![p],
+            HirTaskBody::Modal(modal) => std::iter::once(&mut modal.control)
+                .chain(modal.modes.iter_mut().map(|(_, p)| p))
+                .collect(),
+        };
+        for pipeline in pipelines {
+            for pipe in &mut pipeline.pipes {
+                fuse_in_pipe(pipe, &task.name, &consts, typed, &mut fusions);
+            }
+        }
+    }
+    fusions
+}
+
+fn fuse_in_pipe(
+    pipe: &mut HirPipeExpr,
+    task: &str,
+    consts: &HashMap<String, (f64, bool)>,
+    typed: &TypedProgram,
+    fusions: &mut Vec<MulFusion>,
+) {
+    let mut i = 0;
+    while i + 1 < pipe.elements.len() {
+        let observed_after = matches!(
+            pipe.elements.get(i + 2),
+            Some(HirPipeElem::Tap(..) | HirPipeElem::Probe(..))
+        );
+        let (HirPipeElem::ActorCall(a), HirPipeElem::ActorCall(b)) =
+            (&pipe.elements[i], &pipe.elements[i + 1])
+        else {
+            i += 1;
+            continue;
+        };
+        let fold = match (constant_scale(a, consts), constant_scale(b, consts)) {
+            (Some((x, x_int)), Some((y, y_int)))
+                if !observed_after && fusable_pair(a, b, typed) =>
+            {
+                Some((x, y, x_int && y_int))
+            }
+            _ => None,
+        };
+        let Some((x, y, is_int)) = fold else {
+            i += 1;
+            continue;
+        };
+        let removed = b.call_id;
+        pipe.elements.remove(i + 1);
+        let HirPipeElem::ActorCall(a) = &mut pipe.elements[i] else {
+            unreachable!("checked above")
+        };
+        let span = arg_span(&a.args[0]).unwrap_or(a.call_span);
+        a.args[0] = Arg::Value(Value::Scalar(Scalar::Number(x * y, span, is_int)));
+        fusions.push(MulFusion {
+            task: task.to_string(),
+            kept: a.call_id,
+            removed,
+            scales: (x, y),
+            product: x * y,
+        });
+        // Stay on `i`: the folded call may fuse with the next `mul` too.
+    }
+}
+
+/// Scale of a single-argument `mul` call, if it is a compile-time constant.
+fn constant_scale(
+    call: &HirActorCall,
+    consts: &HashMap<String, (f64, bool)>,
+) -> Option<(f64, bool)> {
+    if call.name != "mul" || call.args.len() != 1 {
+        return None;
+    }
+    match &call.args[0] {
+        Arg::Value(Value::Scalar(Scalar::Number(n, _, is_int))) => Some((*n, *is_int)),
+        Arg::Value(Value::Scalar(Scalar::Ident(id))) | Arg::ConstRef(id) => {
+            consts.get(&id.name).copied()
+        }
+        _ => None,
+    }
+}
+
+fn fusable_pair(a: &HirActorCall, b: &HirActorCall, typed: &TypedProgram) -> bool {
+    let plain = |c: &HirActorCall| {
+        c.shape_constraint.is_none()
+            && c.atomic_group.is_none()
+            && !c.persist
+            && !c.allow_combinational_loop
+    };
+    plain(a)
+        && plain(b)
+        && typed.type_assignments.get(&a.call_id) == typed.type_assignments.get(&b.call_id)
+        && !typed
+            .widenings
+            .iter()
+            .any(|w| w.target_call_id == b.call_id)
+}
+
+fn arg_span(arg: &Arg) -> Option<Span> {
+    match arg {
+        Arg::Value(Value::Scalar(Scalar::Number(_, span, _))) => Some(*span),
+        Arg::Value(Value::Scalar(Scalar::Ident(id))) | Arg::ConstRef(id) => Some(id.span),
+        _ => None,
+    }
+}
+
+// ── Peephole rewrites ───────────────────────────────────────────────────────
+
+/// A run of adjacent actors that a single registry actor computes directly.
+#[derive(Debug)]
+pub struct PeepholeRule {
+    /// Actor names of the run, upstream first.
+    pub pattern: &'static [&'static str],
+    /// Actor replacing the run. It takes the first call's arguments and
+    /// shape constraint; the rule is skipped when it is not registered.
+    pub replacement: &'static str,
+}
+
+/// Peephole table consulted by [`apply_peepholes`], in priority order.
+pub const PEEPHOLE_RULES: &[PeepholeRule] = &[
+    // |x|^2 without the sqrt inside `mag` and the square after it.
+    PeepholeRule {
+        pattern: &["mag", "sqr"],
+        replacement: "magsq",
+    },
+];
+
+/// A run rewritten by [`apply_peepholes`].
+#[derive(Debug, Clone)]
+pub struct PeepholeRewrite {
+    /// Task containing the pipe.
+    pub task: String,
+    pub rule: &'static PeepholeRule,
+    /// First call of the run, now calling the replacement actor.
+    pub kept: CallId,
+    /// Remaining calls of the run, removed from the pipe.
+    pub removed: Vec<CallId>,
+}
+
+/// Rewrite adjacent actor runs listed in [`PEEPHOLE_RULES`].
+///
+/// A run matches when its calls are adjacent in one pipe (no tap or probe
+/// between them), every call after the first takes no arguments, shape
+/// constraint, or annotation, and no widening enters them. The replacement
+/// must be a concrete registry actor whose input type matches the first
+/// call's and whose output type matches the last call's. Runs on HIR
+/// before lowering, like [`fuse_constant_muls`].
+pub fn apply_peepholes(
+    hir: &mut HirProgram,
+    typed: &TypedProgram,
+    registry: &Registry,
+) -> Vec<PeepholeRewrite> {
+    let rules: Vec<(&'static PeepholeRule, &ActorMeta)> = PEEPHOLE_RULES
+        .iter()
+        .filter_map(|rule| {
+            let meta = registry.lookup(rule.replacement)?;
+            meta.type_params.is_empty().then_some((rule, meta))
+        })
+        .collect();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+
+    let mut rewrites = Vec::new();
+    for task in &mut hir.tasks {
+        let pipelines: Vec<&mut HirPipeline> = match &mut task.body {
+            HirTaskBody::Pipeline(p) => vec![p],
+            HirTaskBody::Modal(modal) => std::iter::once(&mut modal.control)
+                .chain(modal.modes.iter_mut().map(|(_, p)| p))
+                .collect(),
+        };
+        for pipeline in pipelines {
+            for pipe in &mut pipeline.pipes {
+                let mut i = 0;
+                while i < pipe.elements.len() {
+                    let matched = rules.iter().find(|(rule, meta)| {
+                        peephole_matches(&pipe.elements[i..], rule, meta, typed, registry)
+                    });
+                    let Some(&(rule, _)) = matched else {
+                        i += 1;
+                        continue;
+                    };
+                    let removed: Vec<CallId> = pipe
+                        .elements
+                        .drain(i + 1..i + rule.pattern.len())
+                        .filter_map(|e| match e {
+                            HirPipeElem::ActorCall(c) => Some(c.call_id),
+                            _ => None,
+                        })
+                        .collect();
+                    let HirPipeElem::ActorCall(first) = &mut pipe.elements[i] else {
+                        unreachable!("peephole_matches checked the run")
+                    };
+                    first.name = rule.replacement.to_string();
+                    rewrites.push(PeepholeRewrite {
+                        task: task.name.clone(),
+                        rule,
+                        kept: first.call_id,
+                        removed,
+                    });
+                    i += 1;
+                }
+            }
+        }
+    }
+    rewrites
+}
+
+fn peephole_matches(
+    elems: &[HirPipeElem],
+    rule: &PeepholeRule,
+    replacement: &ActorMeta,
+    typed: &TypedProgram,
+    registry: &Registry,
+) -> bool {
+    if elems.len() < rule.pattern.len() {
+        return false;
+    }
+    let mut calls = Vec::with_capacity(rule.pattern.len());
+    for (elem, name) in elems.iter().zip(rule.pattern) {
+        match elem {
+            HirPipeElem::ActorCall(c) if c.name == *name => calls.push(c),
+            _ => return false,
+        }
+    }
+    let plain = |c: &HirActorCall| {
+        c.args.is_empty()
+            && c.shape_constraint.is_none()
+            && c.atomic_group.is_none()
+            && !c.persist
+            && !c.allow_combinational_loop
+            && !typed
+                .widenings
+                .iter()
+                .any(|w| w.target_call_id == c.call_id)
+    };
+    let concrete = |c: &HirActorCall| {
+        typed
+            .mono_actors
+            .get(&c.call_id)
+            .or_else(|| registry.lookup(&c.name))
+    };
+    let (first, last) = (calls[0], calls[calls.len() - 1]);
+    calls[1..].iter().all(|c| plain(c))
+        && first.type_args.is_empty()
+        && !first.persist
+        && !first.allow_combinational_loop
+        && concrete(first).is_some_and(|m| m.in_type == replacement.in_type)
+        && concrete(last).is_some_and(|m| m.out_type == replacement.out_type)
+}
+
 // ── Lowering engine ─────────────────────────────────────────────────────────
 
 struct LowerEngine<'a> {
@@ -702,6 +1000,13 @@ mod tests {
             out_count: TokenCount::Literal(1),
             out_shape: PortShape::rank1(TokenCount::Literal(1)),
             params: Vec::new(),
+            terminates: false,
+            pure: false,
+            inline: false,
+            scratch: None,
+            rate_change: None,
+            aliases: Vec::new(),
+            doc: None,
         }
     }
 
@@ -716,6 +1021,13 @@ mod tests {
             out_count: TokenCount::Literal(1),
             out_shape: PortShape::rank1(TokenCount::Literal(1)),
             params: Vec::new(),
+            terminates: false,
+            pure: false,
+            inline: false,
+            scratch: None,
+            rate_change: None,
+            aliases: Vec::new(),
+            doc: None,
         }
     }
 
@@ -727,6 +1039,9 @@ mod tests {
             args: Vec::new(),
             type_args: Vec::new(),
             shape_constraint: None,
+            atomic_group: None,
+            persist: false,
+            allow_combinational_loop: false,
         }
     }
 
@@ -825,6 +1140,7 @@ mod tests {
             call_resolutions: HashMap::new(),
             task_resolutions: HashMap::new(),
             probes: Vec::new(),
+            default_args: HashMap::new(),
             call_ids,
             call_spans,
             def_ids: HashMap::new(),
@@ -1431,6 +1747,141 @@ mod tests {
         lower_and_verify(&hir, &rr.resolved, &tr.typed, &registry)
     }
 
+    fn fuse_source(source: &str) -> (HirProgram, Vec<MulFusion>) {
+        use crate::registry::Registry;
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let mut registry = Registry::new();
+        registry
+            .load_header(&root.join("runtime/libpipit/include/std_actors.h"))
+            .expect("load std_actors.h");
+        registry
+            .load_header(&root.join("runtime/libpipit/include/std_math.h"))
+            .expect("load std_math.h");
+        let program = crate::parser::parse(source).program.expect("parse failed");
+        let mut rr = crate::resolve::resolve(&program, &registry);
+        let mut hir = crate::hir::build_hir(&program, &rr.resolved, &mut rr.id_alloc);
+        let tr = crate::type_infer::type_infer(&hir, &rr.resolved, &registry);
+        let fusions = fuse_constant_muls(&mut hir, &tr.typed, &registry);
+        (hir, fusions)
+    }
+
+    fn pipe_calls(hir: &HirProgram) -> Vec<String> {
+        let HirTaskBody::Pipeline(p) = &hir.tasks[0].body else {
+            panic!("expected pipeline task")
+        };
+        p.pipes[0]
+            .elements
+            .iter()
+            .map(|e| match e {
+                HirPipeElem::ActorCall(c) => match &c.args[..] {
+                    [Arg::Value(Value::Scalar(Scalar::Number(n, _, _)))] => {
+                        format!("{}({})", c.name, n)
+                    }
+                    _ => c.name.clone(),
+                },
+                HirPipeElem::Tap(name, _) => format!(":{}", name),
+                HirPipeElem::Probe(name, _) => format!("?{}", name),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fuse_constant_muls_folds_literals_and_consts() {
+        let (hir, fusions) = fuse_source(
+            "const k = 4.0\nclock 1kHz t { constant(1.0) | mul(2.0) | mul(0.5) | mul(k) | stdout() }",
+        );
+        assert_eq!(pipe_calls(&hir), vec!["mul(4)", "stdout"]);
+        assert_eq!(fusions.len(), 2);
+        assert_eq!(fusions[0].scales, (2.0, 0.5));
+        assert_eq!(fusions[1].product, 4.0);
+        assert_eq!(fusions[0].task, "t");
+    }
+
+    #[test]
+    fn fuse_constant_muls_bails_on_observed_or_runtime_scale() {
+        // Tap between the calls: intermediate is forked.
+        let (hir, fusions) =
+            fuse_source("clock 1kHz t { constant(1.0) | mul(2.0) | :m | mul(0.5) | stdout() }");
+        assert!(fusions.is_empty());
+        assert_eq!(pipe_calls(&hir), vec!["mul(2)", ":m", "mul(0.5)", "stdout"]);
+
+        // Probe on the second call's output.
+        let (_, fusions) =
+            fuse_source("clock 1kHz t { constant(1.0) | mul(2.0) | mul(0.5) | ?p | stdout() }");
+        assert!(fusions.is_empty());
+
+        // Runtime param scale.
+        let (_, fusions) = fuse_source(
+            "param g = 2.0\nclock 1kHz t { constant(1.0) | mul($g) | mul(0.5) | stdout() }",
+        );
+        assert!(fusions.is_empty());
+    }
+
+    fn peephole_source(source: &str, with_magsq: bool) -> (HirProgram, Vec<PeepholeRewrite>) {
+        use crate::registry::Registry;
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let mut registry = Registry::new();
+        registry
+            .load_header(&root.join("runtime/libpipit/include/std_actors.h"))
+            .expect("load std_actors.h");
+        registry
+            .load_header(&root.join("runtime/libpipit/include/std_math.h"))
+            .expect("load std_math.h");
+        if !with_magsq {
+            let mut trimmed = Registry::empty();
+            for meta in registry.actors().filter(|m| m.name != "magsq") {
+                trimmed.insert(meta.clone());
+            }
+            registry = trimmed;
+        }
+        let program = crate::parser::parse(source).program.expect("parse failed");
+        let mut rr = crate::resolve::resolve(&program, &registry);
+        let mut hir = crate::hir::build_hir(&program, &rr.resolved, &mut rr.id_alloc);
+        let tr = crate::type_infer::type_infer(&hir, &rr.resolved, &registry);
+        let rewrites = apply_peepholes(&mut hir, &tr.typed, &registry);
+        (hir, rewrites)
+    }
+
+    #[test]
+    fn peephole_rewrites_mag_sqr_to_magsq() {
+        let (hir, rewrites) = peephole_source(
+            "clock 1kHz t { constant(0.0) | fft(256) | mag() | sqr() | stdout() }",
+            true,
+        );
+        assert_eq!(pipe_calls(&hir), vec!["fft(256)", "magsq", "stdout"]);
+        assert_eq!(rewrites.len(), 1);
+        assert_eq!(rewrites[0].rule.replacement, "magsq");
+        assert_eq!(rewrites[0].removed.len(), 1);
+    }
+
+    #[test]
+    fn peephole_skips_observed_run_and_missing_replacement() {
+        // Probe on mag's output keeps both calls.
+        let (hir, rewrites) = peephole_source(
+            "clock 1kHz t { constant(0.0) | fft(256) | mag() | ?m | sqr() | stdout() }",
+            true,
+        );
+        assert!(rewrites.is_empty());
+        assert_eq!(
+            pipe_calls(&hir),
+            vec!["fft(256)", "mag", "?m", "sqr", "stdout"]
+        );
+
+        // No `magsq` in the registry: skipped silently.
+        let (hir, rewrites) = peephole_source(
+            "clock 1kHz t { constant(0.0) | fft(256) | mag() | sqr() | stdout() }",
+            false,
+        );
+        assert!(rewrites.is_empty());
+        assert_eq!(pipe_calls(&hir), vec!["fft(256)", "mag", "sqr", "stdout"]);
+    }
+
     #[test]
     fn define_expanded_calls_lowered() {
         // Define-expanded actor calls are visible to lower (all HIR calls are actors).
@@ -1,5 +1,5 @@
 use clap::Parser;
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 
@@ -8,6 +8,744 @@ const EXIT_COMPILE_ERROR: i32 = 1;
 const EXIT_USAGE_ERROR: i32 = 2;
 const EXIT_SYSTEM_ERROR: i32 = 3;
 
+/// Display name of a source read from stdin (`pcc -`).
+const STDIN_SOURCE_NAME: &str = "<stdin>";
+
+/// Whether human diagnostics carry ANSI colors; resolved once from
+/// `--color` in `main` before any diagnostic is printed.
+static DIAGNOSTIC_COLOR: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Wrap `text` in the ANSI SGR sequence `sgr` when diagnostics are colored.
+fn paint(sgr: &str, text: &str) -> String {
+    if DIAGNOSTIC_COLOR.load(std::sync::atomic::Ordering::Relaxed) {
+        format!("\x1b[{}m{}\x1b[0m", sgr, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// SGR for a diagnostic label: red errors, yellow warnings, cyan notes.
+fn level_sgr(level: &str) -> &'static str {
+    if level.starts_with("error") {
+        "1;31"
+    } else if level.starts_with("warning") {
+        "1;33"
+    } else {
+        "1;36"
+    }
+}
+
+/// SGR for carets and hints.
+const ACCENT_SGR: &str = "36";
+
+/// Print `pcc <version>`; under `--verbose`, also report the runtime ABI the
+/// compiler targets and the ABI declared by the first `pipit.h` found in the
+/// `-I` / `--actor-path` locations, warning on mismatch.
+fn print_version(cli: &Cli) {
+    println!("pcc {}", env!("CARGO_PKG_VERSION"));
+    if !cli.verbose {
+        return;
+    }
+    let expected = pcc::pipeline::RUNTIME_ABI_VERSION;
+    println!("runtime ABI: {}", expected);
+
+    let header = cli
+        .include
+        .iter()
+        .chain(cli.actor_path.iter())
+        .map(|p| {
+            if p.is_dir() {
+                p.join("pipit.h")
+            } else {
+                p.clone()
+            }
+        })
+        .find(|p| p.file_name().is_some_and(|n| n == "pipit.h") && p.is_file());
+    let Some(header) = header else {
+        println!("runtime header: not found (pass -I <runtime include dir> to check)");
+        return;
+    };
+    match read_runtime_abi_version(&header) {
+        Some(found) => {
+            println!("runtime header: {} (ABI {})", header.display(), found);
+            if found != expected {
+                eprintln!(
+                    "warning: runtime header ABI {} does not match compiler runtime ABI {}; \
+                     generated code may misbehave when linked against this runtime",
+                    found, expected
+                );
+            }
+        }
+        None => {
+            println!("runtime header: {} (ABI unknown)", header.display());
+            eprintln!(
+                "warning: {} does not declare PIPIT_RUNTIME_ABI_VERSION (runtime predates ABI versioning)",
+                header.display()
+            );
+        }
+    }
+}
+
+/// Diff `current` against the baseline manifest at `path`, printing one line
+/// per change to stderr. Returns true if any change is breaking.
+fn report_interface_diff(path: &Path, current: &pcc::lir::InterfaceManifest) -> bool {
+    let baseline: pcc::lir::InterfaceManifest = match std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+    {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!(
+                "error: failed to read baseline manifest {}: {}",
+                path.display(),
+                e
+            );
+            std::process::exit(EXIT_SYSTEM_ERROR);
+        }
+    };
+    let changes = pcc::lir::diff_interface_manifests(&baseline, current);
+    let mut breaking = false;
+    for change in &changes {
+        if change.is_breaking() {
+            breaking = true;
+            eprintln!("breaking: {}", change);
+        } else {
+            eprintln!("compatible: {}", change);
+        }
+    }
+    if changes.is_empty() {
+        eprintln!("pcc: interface unchanged from {}", path.display());
+    }
+    breaking
+}
+
+/// Render a Make/Ninja depfile: `target: dep...`, one dependency per line.
+/// Spaces, `#`, and `$` are escaped as GNU Make and Ninja expect.
+fn format_depfile(target: &Path, deps: &[PathBuf]) -> String {
+    fn escape(path: &Path) -> String {
+        let mut out = String::new();
+        for c in path.to_string_lossy().chars() {
+            match c {
+                ' ' => out.push_str("\\ "),
+                '#' => out.push_str("\\#"),
+                '$' => out.push_str("$$"),
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    let mut out = format!("{}:", escape(target));
+    let mut seen = HashSet::new();
+    for dep in deps {
+        if seen.insert(dep) {
+            out.push_str(" \\\n  ");
+            out.push_str(&escape(dep));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+// ── pcc repl ────────────────────────────────────────────────────────────────
+
+/// Task that wraps a bare pipeline line entered in `pcc repl`.
+const REPL_TASK: &str = "repl";
+
+const REPL_HELP: &str = "\
+enter a statement (const, param, define, clock ..., shared, bind) to keep it,
+or a bare pipeline line to analyze it in a throwaway `clock 1kHz repl { ... }`
+  :source  print the statements kept so far
+  :reset   forget all kept statements
+  :quit    exit";
+
+/// `pcc repl`: read entries from stdin and, after each one, run the pipeline
+/// through scheduling on the kept statements plus the new entry.
+///
+/// Entries continue across lines while braces are open. Statements that
+/// compile without errors are kept for later entries; bare pipeline lines
+/// are analyzed once and discarded.
+fn run_repl(cli: &Cli, registry: &pcc::registry::Registry) {
+    use std::io::{BufRead, IsTerminal, Write as _};
+
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        println!(
+            "pcc {} repl (:help for commands)",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    let mut kept: Vec<String> = Vec::new();
+    let mut pending = String::new();
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        if interactive {
+            print!("{}", if pending.is_empty() { "pcc> " } else { "...> " });
+            let _ = std::io::stdout().flush();
+        }
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        if pending.is_empty() {
+            match line.trim() {
+                "" => continue,
+                ":q" | ":quit" => break,
+                ":help" => {
+                    println!("{}", REPL_HELP);
+                    continue;
+                }
+                ":source" => {
+                    for stmt in &kept {
+                        println!("{}", stmt);
+                    }
+                    continue;
+                }
+                ":reset" => {
+                    kept.clear();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        pending.push_str(&line);
+        pending.push('\n');
+        if open_brace_depth(&pending) > 0 {
+            continue;
+        }
+        let entry = std::mem::take(&mut pending);
+        if let Some(stmt) = repl_evaluate(cli, registry, &kept, entry.trim_end()) {
+            kept.push(stmt);
+        }
+    }
+}
+
+/// Net count of unclosed `{` outside string literals and `#` comments.
+fn open_brace_depth(text: &str) -> i32 {
+    let mut depth = 0;
+    for line in text.lines() {
+        let mut in_string = false;
+        for c in line.chars() {
+            match c {
+                '"' => in_string = !in_string,
+                '#' if !in_string => break,
+                '{' if !in_string => depth += 1,
+                '}' if !in_string => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    depth
+}
+
+/// Compile the kept statements plus `entry`, printing diagnostics to stderr
+/// and a summary of the entry's tasks to stdout. Returns the entry's source
+/// text if it is a statement that should be kept.
+fn repl_evaluate(
+    cli: &Cli,
+    registry: &pcc::registry::Registry,
+    kept: &[String],
+    entry: &str,
+) -> Option<String> {
+    let path = Path::new("<repl>");
+    let as_stmt = pcc::parser::parse(entry);
+    let (candidate, program_tasks) = match &as_stmt.program {
+        Some(p) if as_stmt.errors.is_empty() => {
+            let tasks = p
+                .statements
+                .iter()
+                .filter_map(|s| match &s.kind {
+                    pcc::ast::StatementKind::Task(t) => Some(t.name.name.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            (entry.to_string(), Some(tasks))
+        }
+        _ => {
+            let wrapped = format!("clock 1kHz {} {{\n    {}\n}}", REPL_TASK, entry);
+            if !pcc::parser::parse(&wrapped).errors.is_empty() {
+                let entry_source = pcc::include::ComposedSource::single(path, entry);
+                for err in &as_stmt.errors {
+                    let span = err.span();
+                    print_span_diagnostic(
+                        "error",
+                        &format!("{}", err),
+                        &entry_source,
+                        span.start,
+                        span.end,
+                        None,
+                        cli.diagnostic_context,
+                    );
+                }
+                return None;
+            }
+            (wrapped, None)
+        }
+    };
+
+    let source = kept
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(candidate.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Includes resolve relative to the working directory, then -I.
+    let included = pcc::include::compose_includes(path, &source, &cli.include);
+    let sources = included.source;
+    if print_pipeline_diags(
+        &sources,
+        &included.diagnostics,
+        cli.diagnostic_format,
+        cli.diagnostic_context,
+    ) {
+        return None;
+    }
+    let program = pcc::parser::parse(&sources.text).program?;
+    let options = pcc::codegen::CodegenOptions {
+        release: cli.release,
+        experimental: cli.experimental,
+        reuse_family_buffers: cli.reuse_family_buffers,
+        ..Default::default()
+    };
+    let mut state = pcc::pipeline::CompilationState::new(program, registry.clone());
+    let mut has_errors = false;
+    let result = pcc::pipeline::run_pipeline(
+        &mut state,
+        pcc::pass::PassId::Schedule,
+        &options,
+        false,
+        |_pass_id, diags| {
+            has_errors |= print_pipeline_diags(
+                &sources,
+                diags,
+                cli.diagnostic_format,
+                cli.diagnostic_context,
+            );
+        },
+    );
+    if has_errors || result.is_err() {
+        return None;
+    }
+
+    let entry_tasks = program_tasks
+        .clone()
+        .unwrap_or_else(|| vec![REPL_TASK.to_string()]);
+    let summary = format_repl_summary(
+        state.upstream.graph.as_ref().unwrap(),
+        state.downstream.analysis.as_ref().unwrap(),
+        &entry_tasks,
+    );
+    if summary.is_empty() {
+        println!("ok");
+    } else {
+        print!("{}", summary);
+    }
+    program_tasks.map(|_| candidate)
+}
+
+/// One line per subgraph of the named tasks (including their spawned
+/// copies): node and edge counts, then each node with its repetition count.
+fn format_repl_summary(
+    graph: &pcc::graph::ProgramGraph,
+    analysis: &pcc::analyze::AnalyzedProgram,
+    task_names: &[String],
+) -> String {
+    use pcc::graph::TaskGraph;
+
+    let mut out = String::new();
+    let mut tasks: Vec<_> = graph
+        .tasks
+        .iter()
+        .filter(|(name, _)| {
+            task_names.iter().any(|t| {
+                *name == t
+                    || name
+                        .strip_prefix(t.as_str())
+                        .is_some_and(|rest| rest.starts_with("__spawn_"))
+            })
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.0.cmp(b.0));
+    for (task_name, task_graph) in tasks {
+        let subs: Vec<(String, &str, &pcc::graph::Subgraph)> = match task_graph {
+            TaskGraph::Pipeline(sub) => vec![(String::new(), "pipeline", sub)],
+            TaskGraph::Modal { control, modes } => {
+                std::iter::once((" control".to_string(), "control", control))
+                    .chain(
+                        modes
+                            .iter()
+                            .map(|(m, sub)| (format!(" mode '{}'", m), m.as_str(), sub)),
+                    )
+                    .collect()
+            }
+        };
+        for (heading, label, sub) in subs {
+            let rv = analysis
+                .repetition_vectors
+                .get(&(task_name.clone(), label.to_string()));
+            let nodes = sub
+                .nodes
+                .iter()
+                .map(|n| {
+                    let reps = rv.and_then(|rv| rv.get(&n.id)).copied().unwrap_or(0);
+                    format!("{} x{}", pcc::dot::node_label(&n.kind), reps)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                out,
+                "task '{}'{}: {} nodes, {} edges: {}",
+                task_name,
+                heading,
+                sub.nodes.len(),
+                sub.edges.len(),
+                nodes
+            );
+        }
+    }
+    out
+}
+
+/// How often `--watch` polls its inputs for changes.
+const WATCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// `--watch`: compile once, then recompile whenever an input changes.
+///
+/// Each build re-runs this executable with the same arguments minus
+/// `--watch`, so a failing build (including one that exits from deep in the
+/// pipeline) only ends that iteration. Inputs are the source files, the
+/// files they `include`, `--actor-meta`, and the actor headers; they are
+/// polled by size and modification time. Runs until interrupted (Ctrl-C).
+fn run_watch(cli: &Cli) -> ! {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("error: --watch cannot locate the pcc executable: {}", e);
+            std::process::exit(EXIT_SYSTEM_ERROR);
+        }
+    };
+    let args: Vec<std::ffi::OsString> = std::env::args_os()
+        .skip(1)
+        .filter(|arg| arg != "--watch")
+        .collect();
+    loop {
+        let inputs = watch_inputs(cli);
+        match std::process::Command::new(&exe).args(&args).status() {
+            Ok(status) if status.success() => eprintln!("pcc: watch: build succeeded"),
+            Ok(status) => match status.code() {
+                Some(code) => eprintln!("pcc: watch: build failed (exit {})", code),
+                None => eprintln!("pcc: watch: build terminated by signal"),
+            },
+            Err(e) => {
+                eprintln!("error: --watch failed to run {}: {}", exe.display(), e);
+                std::process::exit(EXIT_SYSTEM_ERROR);
+            }
+        }
+        eprintln!(
+            "pcc: watch: waiting for changes to {} file(s) (Ctrl-C to exit)",
+            inputs.len()
+        );
+        let changed = loop {
+            std::thread::sleep(WATCH_POLL_INTERVAL);
+            if let Some(path) = inputs
+                .iter()
+                .find(|(path, stamp)| watch_stamp(path) != **stamp)
+                .map(|(path, _)| path.clone())
+            {
+                break path;
+            }
+        };
+        eprintln!("pcc: watch: {} changed; recompiling", changed.display());
+    }
+}
+
+/// Inputs of the next `--watch` build with their current stamps. Include
+/// files are re-resolved each build, since edits can add or remove them.
+fn watch_inputs(cli: &Cli) -> BTreeMap<PathBuf, Option<(std::time::SystemTime, u64)>> {
+    let roots: Vec<(PathBuf, String)> = cli
+        .source
+        .iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(path).unwrap_or_default();
+            (path.clone(), text)
+        })
+        .collect();
+    let mut paths: Vec<PathBuf> = cli.source.clone();
+    let composed = pcc::include::compose_sources(&roots, &cli.include);
+    paths.extend(composed.source.included_paths().map(Path::to_path_buf));
+    paths.extend(cli.actor_meta.iter().cloned());
+    paths.extend(collect_all_headers(cli).unwrap_or_default());
+    paths
+        .into_iter()
+        .map(|path| {
+            let stamp = watch_stamp(&path);
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// Modification time and size of `path`, or `None` when it is unreadable.
+fn watch_stamp(path: &Path) -> Option<(std::time::SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Last pass the requested output needs, or `None` for stages that exit
+/// before the pass runner. `--interface-out` promotes the terminal to
+/// `BuildLir` so the interface manifest can be written.
+fn terminal_pass(cli: &Cli) -> Option<pcc::pass::PassId> {
+    use pcc::pass::PassId;
+    let terminal = match cli.emit {
+        _ if cli.preprocess_only || matches!(cli.command, Some(Command::PipelineHash { .. })) => {
+            PassId::BuildHir
+        }
+        _ if matches!(cli.command, Some(Command::GraphQuery { .. })) => PassId::Analyze,
+        EmitStage::Ast | EmitStage::Manifest | EmitStage::ManifestDiff => return None,
+        EmitStage::Interface | EmitStage::Header => PassId::BuildLir,
+        // Graph structure plus analysis port rates
+        EmitStage::GraphJson | EmitStage::Callgraph => PassId::Analyze,
+        EmitStage::GraphDot | EmitStage::Mermaid | EmitStage::Deps => PassId::BuildGraph,
+        EmitStage::Graph
+        | EmitStage::Schedule
+        | EmitStage::ScheduleJson
+        | EmitStage::TimingChart
+        | EmitStage::TimingJson
+        | EmitStage::Report
+        | EmitStage::BuildInfo => PassId::Schedule,
+        EmitStage::Cpp | EmitStage::Exe => PassId::Codegen,
+    };
+    if cli.interface_out.is_some()
+        && !pcc::pass::required_passes(terminal).contains(&PassId::BuildLir)
+    {
+        return Some(PassId::BuildLir);
+    }
+    Some(terminal)
+}
+
+/// Render `--dump-passes` output: the passes `terminal_pass` requires in
+/// execution order with the passes each one runs after, then the skipped
+/// passes. Parse is listed first when the output parses the source.
+fn format_pass_plan(cli: &Cli) -> String {
+    use pcc::pass::{descriptor, required_passes, ALL_PASSES};
+    let target = match cli.command {
+        _ if cli.preprocess_only => "--preprocess-only".to_string(),
+        Some(Command::PipelineHash { .. }) => "pipeline-hash".to_string(),
+        Some(Command::GraphQuery { .. }) => "graph-query".to_string(),
+        _ => format!("--emit {}", cli.emit.cli_name()),
+    };
+    let terminal = terminal_pass(cli);
+    let mut out = match terminal {
+        Some(t) => format!(
+            "pass plan for {} (terminal: {})\n",
+            target,
+            descriptor(t).name
+        ),
+        None => format!("pass plan for {} (no semantic passes)\n", target),
+    };
+    let parses = !matches!(cli.emit, EmitStage::Manifest | EmitStage::ManifestDiff)
+        || cli.preprocess_only
+        || cli.command.is_some();
+    if parses {
+        out.push_str("  parse        run\n");
+    }
+    let required = terminal.map(required_passes).unwrap_or_default();
+    for &id in &required {
+        let inputs: Vec<&str> = descriptor(id)
+            .inputs
+            .iter()
+            .map(|&i| descriptor(i).name)
+            .collect();
+        if inputs.is_empty() {
+            out.push_str(&format!("  {:<12} run\n", descriptor(id).name));
+        } else {
+            out.push_str(&format!(
+                "  {:<12} run      after {}\n",
+                descriptor(id).name,
+                inputs.join(", ")
+            ));
+        }
+    }
+    for id in ALL_PASSES.iter().filter(|id| !required.contains(id)) {
+        out.push_str(&format!("  {:<12} skipped\n", descriptor(*id).name));
+    }
+    out
+}
+
+/// Render `pcc --explain` output: title, the paragraph wrapped to 78
+/// columns, and the example indented as a code block.
+fn format_explanation(explanation: &pcc::diag::Explanation) -> String {
+    let mut out = format!("{}: {}\n\n", explanation.code, explanation.title);
+    let mut line = String::new();
+    for word in explanation.text.split_whitespace() {
+        if !line.is_empty() && line.len() + 1 + word.len() > 78 {
+            out.push_str(&line);
+            out.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    out.push_str(&line);
+    out.push('\n');
+    if let Some(example) = explanation.example {
+        out.push_str("\nExample:\n\n");
+        for line in example.lines() {
+            out.push_str("    ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Render `pcc actor-help` output: signature, port shapes, doc comment,
+/// parameter descriptions (`@param`), and deprecation status (`@deprecated`).
+fn format_actor_help(meta: &pcc::registry::ActorMeta) -> String {
+    use pcc::registry::{ParamKind, PortShape, TokenCount};
+
+    fn shape_desc(shape: &PortShape) -> (String, String) {
+        let dims: Vec<String> = shape
+            .dims
+            .iter()
+            .map(|d| match d {
+                TokenCount::Literal(n) => n.to_string(),
+                TokenCount::Symbolic(s) => s.clone(),
+            })
+            .collect();
+        (format!("[{}]", dims.join(", ")), dims.join("×"))
+    }
+
+    let mut out = String::new();
+    let type_params = if meta.type_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", meta.type_params.join(", "))
+    };
+    let params: Vec<String> = meta
+        .params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, p.param_type))
+        .collect();
+    let _ = writeln!(
+        out,
+        "actor {}{}({})",
+        meta.name,
+        type_params,
+        params.join(", ")
+    );
+
+    for (label, ty, shape) in [
+        ("in", &meta.in_type, &meta.in_shape),
+        ("out", &meta.out_type, &meta.out_shape),
+    ] {
+        let (dims, rate) = shape_desc(shape);
+        if matches!(
+            ty,
+            pcc::registry::TypeExpr::Concrete(pcc::registry::PipitType::Void)
+        ) {
+            let _ = writeln!(out, "  {:<4} void", format!("{}:", label));
+        } else {
+            let _ = writeln!(
+                out,
+                "  {:<4} {}{}  ({} token(s) per firing)",
+                format!("{}:", label),
+                ty,
+                dims,
+                rate
+            );
+        }
+    }
+    if !meta.aliases.is_empty() {
+        let _ = writeln!(out, "  aliases: {}", meta.aliases.join(", "));
+    }
+    if let Some(ref scratch) = meta.scratch {
+        let count = match &scratch.count {
+            TokenCount::Literal(n) => n.to_string(),
+            TokenCount::Symbolic(s) => s.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "  scratch: {}[{}]  (per instance)",
+            scratch.elem_type, count
+        );
+    }
+    if let Some(ref rc) = meta.rate_change {
+        let factor = |c: &TokenCount| match c {
+            TokenCount::Literal(n) => n.to_string(),
+            TokenCount::Symbolic(s) => s.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "  rate change: {} out per {} in",
+            factor(&rc.up),
+            factor(&rc.down)
+        );
+    }
+
+    let description: Vec<&str> = meta
+        .doc
+        .iter()
+        .flat_map(|d| d.lines())
+        .filter(|l| {
+            let t = l.trim_start();
+            !(t.starts_with("@param")
+                || t.starts_with("@deprecated")
+                || t.starts_with("@{")
+                || t.starts_with("@}")
+                || t.starts_with("@code")
+                || t.starts_with("@endcode"))
+        })
+        .map(|l| l.strip_prefix("@brief ").unwrap_or(l))
+        .collect();
+    if !description.is_empty() {
+        out.push('\n');
+        for line in description {
+            if line.is_empty() {
+                out.push('\n');
+            } else {
+                let _ = writeln!(out, "  {}", line);
+            }
+        }
+    }
+
+    if !meta.params.is_empty() {
+        out.push_str("\nparameters:\n");
+        for p in &meta.params {
+            let kind = match p.kind {
+                ParamKind::Param => "compile-time",
+                ParamKind::RuntimeParam => "runtime",
+            };
+            let _ = write!(out, "  {} ({}, {})", p.name, p.param_type, kind);
+            match meta.doc_param(&p.name) {
+                Some(desc) if !desc.is_empty() => {
+                    let _ = writeln!(out, ": {}", desc);
+                }
+                _ => out.push('\n'),
+            }
+        }
+    }
+
+    match meta.deprecation() {
+        Some("") => out.push_str("\ndeprecated: yes\n"),
+        Some(reason) => {
+            let _ = writeln!(out, "\ndeprecated: {}", reason);
+        }
+        None => out.push_str("\ndeprecated: no\n"),
+    }
+    out
+}
+
+/// Read `#define PIPIT_RUNTIME_ABI_VERSION <n>` from a runtime header.
+fn read_runtime_abi_version(path: &Path) -> Option<u32> {
+    let text = std::fs::read_to_string(path).ok()?;
+    text.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("#define")?.trim_start();
+        let value = rest.strip_prefix("PIPIT_RUNTIME_ABI_VERSION")?;
+        value.trim().parse().ok()
+    })
+}
+
 /// Emit a usage error respecting --diagnostic-format, then exit.
 fn emit_usage_error(
     diagnostic_format: DiagnosticFormat,
@@ -50,6 +788,52 @@ enum DiagnosticFormat {
     Json,
 }
 
+/// When human diagnostics are colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum ColorChoice {
+    /// Color when stderr is a terminal and `NO_COLOR` is unset
+    Auto,
+    Always,
+    Never,
+}
+
+/// Serialization of the interface manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum InterfaceFormat {
+    Json,
+    Yaml,
+}
+
+/// C++ language standard passed to the compiler as `-std=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum CppStd {
+    #[value(name = "c++17")]
+    Cpp17,
+    #[value(name = "c++20")]
+    Cpp20,
+    #[value(name = "c++23")]
+    Cpp23,
+}
+
+impl CppStd {
+    fn flag(self) -> &'static str {
+        match self {
+            CppStd::Cpp17 => "-std=c++17",
+            CppStd::Cpp20 => "-std=c++20",
+            CppStd::Cpp23 => "-std=c++23",
+        }
+    }
+}
+
+/// Task execution model of the generated program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum RuntimeModel {
+    /// One thread per task
+    Threaded,
+    /// All tasks in one cooperative earliest-deadline-first loop
+    SingleThread,
+}
+
 #[derive(Debug, Clone, clap::ValueEnum)]
 enum EmitStage {
     Exe,
@@ -57,11 +841,20 @@ enum EmitStage {
     Ast,
     Graph,
     GraphDot,
+    GraphJson,
+    Callgraph,
+    Mermaid,
+    Deps,
     Schedule,
+    ScheduleJson,
     TimingChart,
+    TimingJson,
+    Report,
     Manifest,
+    ManifestDiff,
     BuildInfo,
     Interface,
+    Header,
 }
 
 impl EmitStage {
@@ -72,11 +865,20 @@ impl EmitStage {
             EmitStage::Ast => "ast",
             EmitStage::Graph => "graph",
             EmitStage::GraphDot => "graph-dot",
+            EmitStage::GraphJson => "graph-json",
+            EmitStage::Callgraph => "callgraph",
+            EmitStage::Mermaid => "mermaid",
+            EmitStage::Deps => "deps",
             EmitStage::Schedule => "schedule",
+            EmitStage::ScheduleJson => "schedule-json",
             EmitStage::TimingChart => "timing-chart",
+            EmitStage::TimingJson => "timing-json",
+            EmitStage::Report => "report",
             EmitStage::Manifest => "manifest",
+            EmitStage::ManifestDiff => "manifest-diff",
             EmitStage::BuildInfo => "build-info",
             EmitStage::Interface => "interface",
+            EmitStage::Header => "header",
         }
     }
 }
@@ -85,26 +887,35 @@ impl EmitStage {
 #[command(
     name = "pcc",
     version,
+    disable_version_flag = true,
     about = "Pipit Compiler Collection — compiles .pdl pipeline definitions to native executables"
 )]
 struct Cli {
-    /// Input .pdl source file (not required for --emit manifest)
-    source: Option<PathBuf>,
+    /// Print version (with --verbose: also the runtime ABI version and header check)
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Input .pdl source files, merged into one program in the order given,
+    /// or `-` to read stdin (not required for --emit manifest)
+    source: Vec<PathBuf>,
 
     /// Output file path (default: stdout for text stages, a.out for exe)
     #[arg(short, long)]
     output: Option<PathBuf>,
 
     /// Actor header file or search directory (repeatable)
-    #[arg(short = 'I', long = "include")]
+    #[arg(short = 'I', long = "include", global = true)]
     include: Vec<PathBuf>,
 
     /// Actor search directory (repeatable)
-    #[arg(long)]
+    #[arg(long, global = true)]
     actor_path: Vec<PathBuf>,
 
     /// Actor metadata manifest file (actors.meta.json)
-    #[arg(long)]
+    #[arg(long, global = true)]
     actor_meta: Option<PathBuf>,
 
     /// Output stage
@@ -112,43 +923,305 @@ struct Cli {
     emit: EmitStage,
 
     /// Release build: strip probes, enable optimizations
-    #[arg(long)]
+    #[arg(long, global = true)]
     release: bool,
 
     /// C++ compiler command
-    #[arg(long, default_value = "g++")]
+    #[arg(long, default_value = "g++", global = true)]
     cc: String,
 
+    /// C++ standard for the compiler command (--emit exe, --compdb)
+    #[arg(long, value_enum, value_name = "STD", default_value_t = CppStd::Cpp20)]
+    cpp_std: CppStd,
+
     /// Additional C++ compiler flags (overrides default optimization flags)
     #[arg(long)]
     cflags: Option<String>,
 
+    /// With --emit exe, target ISA features as a comma-separated list
+    /// (e.g. "avx2,fma"), each passed as -m<feature>
+    #[arg(long, value_name = "LIST")]
+    target_features: Option<String>,
+
+    /// With --emit exe, pass -march=VALUE (e.g. native, x86-64-v3)
+    #[arg(long, value_name = "VALUE")]
+    march: Option<String>,
+
     /// Print compiler phases and timing
-    #[arg(long)]
+    #[arg(long, global = true)]
     verbose: bool,
 
     /// Diagnostic output format
     #[arg(long, value_enum, default_value_t = DiagnosticFormat::Human)]
     diagnostic_format: DiagnosticFormat,
 
+    /// With human diagnostics, show a line-numbered source excerpt with N
+    /// context lines around each span
+    #[arg(long, value_name = "N")]
+    diagnostic_context: Option<usize>,
+
+    /// Color human diagnostics (JSON diagnostics are never colored)
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Same as --color never
+    #[arg(long, conflicts_with = "color")]
+    no_color: bool,
+
     /// Enable experimental codegen features (no effect currently)
-    #[arg(long)]
+    #[arg(long, global = true)]
     experimental: bool,
 
-    /// Write interface manifest JSON to this path (orthogonal to --emit)
+    /// Coalesce shared array families whose elements are written by one
+    /// scatter and read only by gathers into a single ring buffer
+    #[arg(long, global = true)]
+    reuse_family_buffers: bool,
+
+    /// With --emit cpp/exe, emit only the standard includes the generated
+    /// code needs beyond those the runtime headers already provide
+    #[arg(long, global = true)]
+    minimal_includes: bool,
+
+    /// With --emit cpp/exe, parallelize repetition loops of stateless
+    /// (PURE) actors with OpenMP; --emit exe adds -fopenmp
+    #[arg(long, global = true)]
+    openmp: bool,
+
+    /// With --emit cpp/exe, time every actor firing and print per-actor
+    /// totals, sorted descending, at shutdown (not with --release)
+    #[arg(long, global = true)]
+    profile_actors: bool,
+
+    /// Build without C++ exceptions: generated glue avoids throwing library
+    /// calls, and --emit exe adds -fno-exceptions
+    #[arg(long, global = true)]
+    no_exceptions: bool,
+
+    /// With --emit cpp, build an embeddable library: omit main() and signal
+    /// handling, and define the pipit_start/pipit_stop/bind accessor entry
+    /// points declared by --emit header
+    #[arg(long, global = true)]
+    library: bool,
+
+    /// Build without RTTI: --emit exe adds -fno-rtti (generated code uses no
+    /// dynamic_cast or typeid)
+    #[arg(long)]
+    no_rtti: bool,
+
+    /// With --emit manifest, compile each actor body in isolation and
+    /// report which actors fail (exit 1 if any do)
+    #[arg(long)]
+    validate: bool,
+
+    /// Normalize build-dependent output: header paths relative to the
+    /// working directory, a build_epoch stamp from SOURCE_DATE_EPOCH (or 0),
+    /// and --emit exe maps temp and working-directory paths out of the binary
+    #[arg(long)]
+    reproducible: bool,
+
+    /// Task execution model: a thread per task, or one cooperative loop
+    /// for targets without threads
+    #[arg(long, value_enum, default_value_t = RuntimeModel::Threaded, global = true)]
+    runtime: RuntimeModel,
+
+    /// Print the source after define inlining and spawn expansion, in
+    /// canonical .pdl form, and stop before graph building (overrides --emit)
+    #[arg(long)]
+    preprocess_only: bool,
+
+    /// Print the compiler passes the requested output runs, in order, and
+    /// the ones it skips, then exit without compiling (no source needed)
+    #[arg(long)]
+    dump_passes: bool,
+
+    /// Write the interface manifest to this path (orthogonal to --emit)
     #[arg(long)]
     interface_out: Option<PathBuf>,
 
-    /// Bind endpoint override: name=endpoint (repeatable)
+    /// Serialization of --emit interface and --interface-out; both formats
+    /// list the same keys in the same order
+    #[arg(long, value_enum, default_value_t = InterfaceFormat::Json)]
+    interface_format: InterfaceFormat,
+
+    /// With --emit graph/graph-dot, hide forks and probes: draw direct
+    /// actor-to-actor edges labeled with the tap/probe names they cross
+    #[arg(long)]
+    collapse_passthrough: bool,
+
+    /// With --emit schedule, group tasks by the core `set affinity` pins
+    /// them to and sum each core's estimated utilization
+    #[arg(long)]
+    per_core: bool,
+
+    /// Compare the interface manifest against a prior one and report
+    /// added/removed binds and changed contracts to stderr (--emit interface)
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// With --baseline, exit with an error if any change is breaking
+    #[arg(long)]
+    fail_on_interface_break: bool,
+
+    /// With --emit manifest-diff, the newer actor manifest to compare
+    /// against --actor-meta
+    #[arg(long)]
+    compare: Option<PathBuf>,
+
+    /// Describe a diagnostic code (e.g. E0304) with an example, then exit
+    #[arg(long, value_name = "CODE")]
+    explain: Option<String>,
+
+    /// Check --actor-meta against the actors extracted from -I/--actor-path
+    /// headers and report every differing field (exit 1 on any mismatch)
     #[arg(long)]
+    verify_meta: bool,
+
+    /// Bind endpoint override: name=endpoint (repeatable)
+    #[arg(long, global = true)]
     bind: Vec<String>,
+
+    /// Memory pool limit for the shared-buffer check, in bytes or with a
+    /// KB/MB/GB suffix (e.g. 64MB); supersedes `set mem`
+    #[arg(long, value_name = "SIZE", global = true)]
+    max_memory: Option<String>,
+
+    /// Platform memory budget in bytes or with a KB/MB/GB suffix; resolves
+    /// `set mem = N%` to a concrete pool limit
+    #[arg(long, value_name = "SIZE", global = true)]
+    platform_memory: Option<String>,
+
+    /// Write a Make/Ninja depfile for the -o target (--emit cpp/exe)
+    #[arg(long)]
+    depfile: Option<PathBuf>,
+
+    /// With --emit cpp -o <file>, write a compile_commands.json entry for
+    /// the file using the compiler command --emit exe would run
+    #[arg(long, value_name = "PATH")]
+    compdb: Option<PathBuf>,
+
+    /// Print every buffer the generated code declares, with byte sizes
+    /// totaled per task, to stderr (--emit cpp/exe)
+    #[arg(long)]
+    trace_allocations: bool,
+
+    /// After compiling, recompile whenever a source file, included file,
+    /// actor header, or --actor-meta manifest changes (Ctrl-C exits)
+    #[arg(long)]
+    watch: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Print an actor's signature, documentation, and port shapes
+    ActorHelp {
+        /// Actor name
+        actor: String,
+    },
+    /// Interactive mode: read statements or bare pipeline lines from stdin
+    /// and show diagnostics and a graph summary after each one
+    Repl,
+    /// Print a stable hash of the compiled pipeline (insensitive to comments
+    /// and whitespace; honors every codegen flag, e.g. --release, --runtime)
+    PipelineHash {
+        /// Input .pdl source file
+        source: PathBuf,
+    },
+    /// Check structural assertions against the analyzed graph, e.g.
+    /// 'count(actor=fft, task=audio) == 1 && !exists_cycle' (exit 1 if any fail)
+    GraphQuery {
+        /// Input .pdl source file
+        source: PathBuf,
+        /// Query: clauses over count(...), max_repetition(...), exists_cycle
+        query: String,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
 
+    let color = match cli.color {
+        _ if cli.no_color => false,
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            use std::io::IsTerminal;
+            std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+        }
+    };
+    DIAGNOSTIC_COLOR.store(color, std::sync::atomic::Ordering::Relaxed);
+
+    if cli.version {
+        print_version(&cli);
+        std::process::exit(EXIT_OK);
+    }
+
+    if let Some(ref code) = cli.explain {
+        let Some(explanation) = pcc::diag::explain(code) else {
+            let available: Vec<&str> = pcc::diag::EXPLANATIONS.iter().map(|e| e.code.0).collect();
+            eprintln!("error: no explanation for '{}'", code);
+            eprintln!("  available: {}", available.join(", "));
+            std::process::exit(EXIT_USAGE_ERROR);
+        };
+        print!("{}", format_explanation(explanation));
+        std::process::exit(EXIT_OK);
+    }
+
+    if let Some(Command::ActorHelp { ref actor }) = cli.command {
+        let registry = if cli.actor_meta.is_some() {
+            load_actor_registry(&cli).map(|(r, _)| r)
+        } else {
+            load_actor_registry_from_headers(&cli).map(|(r, _)| r)
+        };
+        let registry = match registry {
+            Ok(r) => r,
+            Err((msg, code)) => {
+                eprintln!("error: {}", msg);
+                std::process::exit(code);
+            }
+        };
+        let Some(meta) = registry.lookup(actor) else {
+            eprintln!("error: unknown actor '{}'", actor);
+            eprintln!("  hint: check actor header includes (-I flag)");
+            std::process::exit(EXIT_USAGE_ERROR);
+        };
+        print!("{}", format_actor_help(meta));
+        std::process::exit(EXIT_OK);
+    }
+
+    if let Some(Command::Repl) = cli.command {
+        let registry = if cli.actor_meta.is_some() {
+            load_actor_registry(&cli).map(|(r, _)| r)
+        } else {
+            load_actor_registry_from_headers(&cli).map(|(r, _)| r)
+        };
+        match registry {
+            Ok(r) => run_repl(&cli, &r),
+            Err((msg, code)) => {
+                eprintln!("error: {}", msg);
+                std::process::exit(code);
+            }
+        }
+        std::process::exit(EXIT_OK);
+    }
+
+    if cli.watch {
+        if cli.command.is_some() {
+            eprintln!("error: --watch cannot be combined with a subcommand");
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+        if cli.source.is_empty() {
+            eprintln!("error: --watch requires a source file");
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+        if cli.source.iter().any(|p| p == Path::new("-")) {
+            eprintln!("error: --watch cannot read the source from stdin");
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+        run_watch(&cli);
+    }
+
     if cli.verbose {
-        if let Some(ref src) = cli.source {
+        for src in &cli.source {
             eprintln!("pcc: source = {}", src.display());
         }
         if let Some(ref out) = cli.output {
@@ -158,7 +1231,11 @@ fn main() {
     }
 
     // ── --interface-out guard: reject with non-pipeline early-exit stages ──
-    if cli.interface_out.is_some() && matches!(cli.emit, EmitStage::Manifest | EmitStage::BuildInfo)
+    if cli.interface_out.is_some()
+        && matches!(
+            cli.emit,
+            EmitStage::Manifest | EmitStage::ManifestDiff | EmitStage::BuildInfo
+        )
     {
         eprintln!(
             "error: --interface-out requires source compilation; incompatible with --emit {:?}",
@@ -167,6 +1244,113 @@ fn main() {
         std::process::exit(EXIT_USAGE_ERROR);
     }
 
+    if cli.interface_format != InterfaceFormat::Json
+        && !matches!(cli.emit, EmitStage::Interface)
+        && cli.interface_out.is_none()
+    {
+        eprintln!("error: --interface-format requires --emit interface or --interface-out");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    // ── --depfile guard: needs an artifact target ──
+    if cli.depfile.is_some() {
+        if !matches!(cli.emit, EmitStage::Cpp | EmitStage::Exe) || cli.command.is_some() {
+            eprintln!("error: --depfile requires --emit cpp or exe");
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+        if matches!(cli.emit, EmitStage::Cpp) && cli.output.is_none() {
+            eprintln!("error: --depfile with --emit cpp requires -o (the depfile target)");
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+    }
+
+    if cli.compdb.is_some()
+        && (!matches!(cli.emit, EmitStage::Cpp)
+            || cli.output.as_deref().is_none_or(|o| o == Path::new("-")))
+    {
+        eprintln!("error: --compdb requires --emit cpp with -o <file.cpp>");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.collapse_passthrough && !matches!(cli.emit, EmitStage::Graph | EmitStage::GraphDot) {
+        eprintln!("error: --collapse-passthrough requires --emit graph or graph-dot");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.per_core && !matches!(cli.emit, EmitStage::Schedule) {
+        eprintln!("error: --per-core requires --emit schedule");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.compare.is_some() != matches!(cli.emit, EmitStage::ManifestDiff) {
+        eprintln!("error: --emit manifest-diff requires --compare <new.json> (and vice versa)");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.baseline.is_some() && !matches!(cli.emit, EmitStage::Interface) {
+        eprintln!("error: --baseline requires --emit interface");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+    if cli.fail_on_interface_break && cli.baseline.is_none() {
+        eprintln!("error: --fail-on-interface-break requires --baseline");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.trace_allocations && !matches!(cli.emit, EmitStage::Cpp | EmitStage::Exe) {
+        eprintln!("error: --trace-allocations requires --emit cpp or exe");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.profile_actors && cli.release {
+        eprintln!("error: --profile-actors is debug instrumentation; incompatible with --release");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.library && !matches!(cli.emit, EmitStage::Cpp) {
+        eprintln!("error: --library requires --emit cpp");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+    if cli.library && cli.runtime == RuntimeModel::SingleThread {
+        eprintln!(
+            "error: --library runs a thread per task; incompatible with --runtime single-thread"
+        );
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if cli.validate && !matches!(cli.emit, EmitStage::Manifest) {
+        eprintln!("error: --validate requires --emit manifest");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
+    if let Some(list) = &cli.target_features {
+        match target_feature_flags(list) {
+            Ok((_, unknown)) => {
+                for feature in unknown {
+                    eprintln!(
+                        "warning: unknown target feature '{}', passing -m{} through",
+                        feature, feature
+                    );
+                }
+            }
+            Err(msg) => {
+                eprintln!("error: {}", msg);
+                std::process::exit(EXIT_USAGE_ERROR);
+            }
+        }
+    }
+
+    if cli.reproducible {
+        if let Err(msg) = source_date_epoch() {
+            eprintln!("error: {}", msg);
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+    }
+
+    if cli.preprocess_only && (cli.interface_out.is_some() || !cli.bind.is_empty()) {
+        eprintln!("error: --preprocess-only stops before codegen; incompatible with --interface-out and --bind");
+        std::process::exit(EXIT_USAGE_ERROR);
+    }
+
     // ── --bind stage guard: reject for stages where it has no observable effect ──
     if !cli.bind.is_empty()
         && !matches!(
@@ -198,6 +1382,136 @@ fn main() {
         }
     }
 
+    let max_memory = match cli.max_memory.as_deref().map(parse_mem_size) {
+        Some(Ok(bytes)) => Some(bytes),
+        Some(Err(msg)) => {
+            eprintln!("error: --max-memory: {}", msg);
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+        None => None,
+    };
+    let platform_memory = match cli.platform_memory.as_deref().map(parse_mem_size) {
+        Some(Ok(bytes)) => Some(bytes),
+        Some(Err(msg)) => {
+            eprintln!("error: --platform-memory: {}", msg);
+            std::process::exit(EXIT_USAGE_ERROR);
+        }
+        None => None,
+    };
+
+    // ── --dump-passes: print the pass plan, no source needed ──
+    if cli.dump_passes {
+        print!("{}", format_pass_plan(&cli));
+        std::process::exit(EXIT_OK);
+    }
+
+    // ── --emit manifest-diff: compare two manifests, no source needed ──
+    if let Some(new_path) = cli
+        .compare
+        .as_ref()
+        .filter(|_| matches!(cli.emit, EmitStage::ManifestDiff))
+    {
+        let Some(old_path) = cli.actor_meta.as_ref() else {
+            emit_usage_error(
+                cli.diagnostic_format,
+                Some(pcc::diag::codes::E0700),
+                "--actor-meta is required for --emit manifest-diff",
+                Some("pass the older manifest with --actor-meta and the newer with --compare"),
+            );
+        };
+        let load = |path: &Path| {
+            let mut registry = pcc::registry::Registry::new();
+            registry
+                .load_manifest(path)
+                .map_err(map_registry_error)
+                .map(|_| registry)
+        };
+        let (old, new) = match (load(old_path), load(new_path)) {
+            (Ok(old), Ok(new)) => (old, new),
+            (Err((msg, code)), _) | (_, Err((msg, code))) => {
+                eprintln!("error: {}", msg);
+                std::process::exit(code);
+            }
+        };
+        let diff = old.diff(&new);
+        if diff.is_empty() {
+            emit_output(&cli.output, "no actor changes\n");
+        } else {
+            emit_output(&cli.output, &diff.to_string());
+        }
+        if diff.is_breaking() {
+            std::process::exit(EXIT_COMPILE_ERROR);
+        }
+        std::process::exit(EXIT_OK);
+    }
+
+    // ── --verify-meta: check the manifest against headers, no source needed ──
+    if cli.verify_meta {
+        let Some(meta_path) = cli.actor_meta.as_ref() else {
+            emit_usage_error(
+                cli.diagnostic_format,
+                Some(pcc::diag::codes::E0700),
+                "--actor-meta is required for --verify-meta",
+                Some("pass the manifest to check with --actor-meta"),
+            );
+        };
+        if cli.include.is_empty() && cli.actor_path.is_empty() {
+            emit_usage_error(
+                cli.diagnostic_format,
+                None,
+                "--verify-meta requires actor headers",
+                Some("pass the headers to check against with -I or --actor-path"),
+            );
+        }
+        let mut manifest = pcc::registry::Registry::new();
+        if let Err((msg, code)) = manifest
+            .load_manifest(meta_path)
+            .map_err(map_registry_error)
+        {
+            eprintln!("error: {}", msg);
+            std::process::exit(code);
+        }
+        let headers = match load_actor_registry_from_headers(&cli) {
+            Ok((registry, _)) => registry,
+            Err((msg, code)) => {
+                eprintln!("error: {}", msg);
+                std::process::exit(code);
+            }
+        };
+        let diff = manifest.diff(&headers);
+        for name in &diff.removed {
+            eprintln!(
+                "error: actor '{}' is in the manifest but not in the headers",
+                name
+            );
+        }
+        for name in &diff.added {
+            eprintln!(
+                "error: actor '{}' is in the headers but not in the manifest",
+                name
+            );
+        }
+        for change in &diff.changed {
+            let (in_manifest, in_headers) = change.values();
+            eprintln!(
+                "error: actor '{}' {} differs: manifest {}, headers {}",
+                change.actor(),
+                change.field(),
+                in_manifest,
+                in_headers
+            );
+        }
+        if !diff.is_empty() {
+            eprintln!(
+                "pcc: {} does not match the headers; regenerate it with --emit manifest",
+                meta_path.display()
+            );
+            std::process::exit(EXIT_COMPILE_ERROR);
+        }
+        eprintln!("pcc: {} matches the headers", meta_path.display());
+        std::process::exit(EXIT_OK);
+    }
+
     // ── --emit manifest: early exit before source reading ──
     if matches!(cli.emit, EmitStage::Manifest) {
         if cli.actor_meta.is_some() {
@@ -208,7 +1522,7 @@ fn main() {
                 None,
             );
         }
-        let (registry, _headers) = match load_actor_registry_from_headers(&cli) {
+        let (registry, headers) = match load_actor_registry_from_headers(&cli) {
             Ok(v) => v,
             Err((msg, code)) => {
                 eprintln!("error: {}", msg);
@@ -217,32 +1531,90 @@ fn main() {
         };
         let manifest_json = registry.generate_manifest();
         emit_output(&cli.output, &manifest_json);
+        if cli.validate && !report_actor_validation(&cli, &registry, &headers) {
+            std::process::exit(EXIT_COMPILE_ERROR);
+        }
         std::process::exit(EXIT_OK);
     }
 
+    // ── pcc graph-query: reject malformed queries before compiling ──
+    let graph_query = match cli.command {
+        Some(Command::GraphQuery { ref query, .. }) => match pcc::graph_query::parse_query(query) {
+            Ok(q) => Some(q),
+            Err(msg) => {
+                eprintln!("error: invalid graph query: {}", msg);
+                std::process::exit(EXIT_USAGE_ERROR);
+            }
+        },
+        _ => None,
+    };
+
     // ── Validate source is provided for all other stages ──
-    let source_path = match cli.source {
-        Some(ref p) => p.clone(),
-        None => {
-            emit_usage_error(
-                cli.diagnostic_format,
-                None,
-                &format!("source file is required for --emit {}", cli.emit.cli_name()),
-                None,
-            );
-        }
+    let hash_source = match cli.command {
+        Some(Command::PipelineHash { ref source }) => Some(source),
+        _ => None,
     };
+    let query_source = match cli.command {
+        Some(Command::GraphQuery { ref source, .. }) => Some(source),
+        _ => None,
+    };
+    let source_paths = match hash_source.or(query_source) {
+        Some(p) => vec![p.clone()],
+        None => cli.source.clone(),
+    };
+    if source_paths.is_empty() {
+        emit_usage_error(
+            cli.diagnostic_format,
+            None,
+            &format!("source file is required for --emit {}", cli.emit.cli_name()),
+            None,
+        );
+    }
+    let from_stdin = source_paths.iter().any(|p| p == Path::new("-"));
+    if from_stdin && source_paths.len() > 1 {
+        emit_usage_error(
+            cli.diagnostic_format,
+            None,
+            "`-` (stdin) cannot be combined with other source files",
+            None,
+        );
+    }
 
-    // ── Read source ──
-    let source = match std::fs::read_to_string(&source_path) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("error: {}: {}", source_path.display(), e);
-            std::process::exit(EXIT_USAGE_ERROR);
+    // ── Read sources (`-` reads stdin, reported as `<stdin>`) ──
+    let mut root_sources = Vec::with_capacity(source_paths.len());
+    for path in source_paths {
+        let (path, text) = if path == Path::new("-") {
+            let mut text = String::new();
+            let read = std::io::Read::read_to_string(&mut std::io::stdin(), &mut text);
+            (PathBuf::from(STDIN_SOURCE_NAME), read.map(|_| text))
+        } else {
+            let read = std::fs::read_to_string(&path);
+            (path, read)
+        };
+        match text {
+            Ok(text) => root_sources.push((path, text)),
+            Err(e) => {
+                eprintln!("error: {}: {}", path.display(), e);
+                std::process::exit(EXIT_USAGE_ERROR);
+            }
         }
-    };
+    }
+    let source_path = root_sources[0].0.clone();
+
+    // ── Compose the sources and their `include "..."` files into one text ──
+    let include_result = pcc::include::compose_sources(&root_sources, &cli.include);
+    let sources = include_result.source;
+    if print_pipeline_diags(
+        &sources,
+        &include_result.diagnostics,
+        cli.diagnostic_format,
+        cli.diagnostic_context,
+    ) {
+        std::process::exit(EXIT_COMPILE_ERROR);
+    }
+    let source = &sources.text;
 
-    // ── --emit build-info: early exit before parsing ──
+    // ── --emit build-info: early exit; provenance does not require a valid program ──
     if matches!(cli.emit, EmitStage::BuildInfo) {
         let (registry, _headers) = match load_actor_registry(&cli) {
             Ok(v) => v,
@@ -251,14 +1623,54 @@ fn main() {
                 std::process::exit(code);
             }
         };
-        let provenance = pcc::pipeline::compute_provenance(&source, &registry);
+        let mut provenance = pcc::pipeline::compute_provenance(source, &registry);
+        provenance.build_epoch = build_epoch(&cli);
+        // Memory footprint needs the schedule; reported as `null`, with the
+        // reason on stderr, when the source does not compile that far.
+        let mut errors = Vec::new();
+        if let Some(program) = pcc::parser::parse(source).program {
+            let options = pcc::codegen::CodegenOptions {
+                release: cli.release,
+                experimental: cli.experimental,
+                reuse_family_buffers: cli.reuse_family_buffers,
+                max_memory,
+                platform_memory,
+                ..Default::default()
+            };
+            let mut state = pcc::pipeline::CompilationState::new(program, registry);
+            let result = pcc::pipeline::run_pipeline(
+                &mut state,
+                pcc::pass::PassId::Schedule,
+                &options,
+                false,
+                |_, diags| {
+                    errors.extend(
+                        diags
+                            .iter()
+                            .filter(|d| d.level == pcc::diag::DiagLevel::Error)
+                            .map(|d| d.to_string()),
+                    );
+                },
+            );
+            if result.is_ok() && errors.is_empty() {
+                provenance.memory = state.memory_footprint();
+            }
+        } else {
+            errors.push("error: source does not parse".to_string());
+        }
+        if provenance.memory.is_none() {
+            eprintln!("warning: --emit build-info: program does not compile through scheduling; \"memory\" is null");
+            for err in &errors {
+                eprintln!("{}", err);
+            }
+        }
         emit_output(&cli.output, &provenance.to_json());
         std::process::exit(EXIT_OK);
     }
 
     // ── Parse source ──
     let diag_format = cli.diagnostic_format;
-    let parse_result = pcc::parser::parse(&source);
+    let parse_result = pcc::parser::parse(source);
     if !parse_result.errors.is_empty() {
         for err in &parse_result.errors {
             let span = err.span();
@@ -273,11 +1685,11 @@ fn main() {
                 print_span_diagnostic(
                     "error",
                     &format!("{}", err),
-                    &source_path,
-                    &source,
+                    &sources,
                     span.start,
                     span.end,
                     None,
+                    cli.diagnostic_context,
                 );
             }
         }
@@ -321,32 +1733,39 @@ fn main() {
     }
 
     // ── Map EmitStage to terminal PassId ──
-    let mut terminal = match cli.emit {
-        EmitStage::Ast | EmitStage::Manifest | EmitStage::BuildInfo => unreachable!(),
-        EmitStage::Interface => pcc::pass::PassId::BuildLir,
-        EmitStage::GraphDot => pcc::pass::PassId::BuildGraph,
-        EmitStage::Graph | EmitStage::Schedule | EmitStage::TimingChart => {
-            pcc::pass::PassId::Schedule
-        }
-        EmitStage::Cpp | EmitStage::Exe => pcc::pass::PassId::Codegen,
-    };
-
-    // Promote terminal if --interface-out requires BuildLir
-    if cli.interface_out.is_some()
-        && !pcc::pass::required_passes(terminal).contains(&pcc::pass::PassId::BuildLir)
-    {
-        terminal = pcc::pass::PassId::BuildLir;
-    }
+    let terminal = terminal_pass(&cli).expect("stages without semantic passes exit earlier");
 
     // ── Run pipeline ──
-    let provenance = pcc::pipeline::compute_provenance(&source, &registry);
+    let mut provenance = pcc::pipeline::compute_provenance(source, &registry);
+    provenance.build_epoch = build_epoch(&cli);
+    let include_paths = if cli.reproducible {
+        loaded_headers.iter().map(|p| relative_to_cwd(p)).collect()
+    } else {
+        loaded_headers.clone()
+    };
     let codegen_options = pcc::codegen::CodegenOptions {
         release: cli.release,
-        include_paths: loaded_headers.clone(),
+        include_paths,
         provenance: Some(provenance),
         experimental: cli.experimental,
+        reuse_family_buffers: cli.reuse_family_buffers,
+        minimal_includes: cli.minimal_includes,
+        single_thread: cli.runtime == RuntimeModel::SingleThread,
+        openmp: cli.openmp,
+        profile_actors: cli.profile_actors,
+        no_exceptions: cli.no_exceptions,
+        library: cli.library,
         bind_overrides: bind_overrides.clone(),
+        max_memory,
+        platform_memory,
     };
+    if max_memory.is_some()
+        && program.statements.iter().any(|stmt| {
+            matches!(&stmt.kind, pcc::ast::StatementKind::Set(set) if set.name.name == "mem")
+        })
+    {
+        eprintln!("pcc: note: --max-memory overrides `set mem` for the memory pool check");
+    }
     let mut state = pcc::pipeline::CompilationState::new(program, registry);
     let mut has_errors = false;
     let result = pcc::pipeline::run_pipeline(
@@ -355,7 +1774,8 @@ fn main() {
         &codegen_options,
         cli.verbose,
         |_pass_id, diags| {
-            has_errors |= print_pipeline_diags(&source_path, &source, diags, diag_format);
+            has_errors |=
+                print_pipeline_diags(&sources, diags, diag_format, cli.diagnostic_context);
         },
     );
 
@@ -363,6 +1783,42 @@ fn main() {
         std::process::exit(EXIT_COMPILE_ERROR);
     }
 
+    // ── --preprocess-only: print expanded source, then exit ──
+    if cli.preprocess_only {
+        let hir = state.upstream.hir.as_ref().unwrap();
+        let resolved = state.upstream.resolved.as_ref().unwrap();
+        emit_output(&cli.output, &hir.to_pdl(resolved));
+        std::process::exit(EXIT_OK);
+    }
+
+    // ── pcc graph-query: report each clause, then exit ──
+    if let Some(query) = &graph_query {
+        let graph = state.upstream.graph.as_ref().unwrap();
+        let analysis = state.downstream.analysis.as_ref().unwrap();
+        let results = pcc::graph_query::evaluate(query, graph, analysis);
+        let mut out = String::new();
+        for r in &results {
+            out.push_str(&format!(
+                "{}  {}  (actual {})\n",
+                if r.passed { "ok  " } else { "FAIL" },
+                r.clause,
+                r.actual
+            ));
+        }
+        emit_output(&cli.output, &out);
+        if results.iter().any(|r| !r.passed) {
+            std::process::exit(EXIT_COMPILE_ERROR);
+        }
+        std::process::exit(EXIT_OK);
+    }
+
+    // ── pcc pipeline-hash: print the pipeline content hash, then exit ──
+    if hash_source.is_some() {
+        let hash = state.pipeline_hash(&codegen_options).unwrap();
+        emit_output(&cli.output, &format!("{}\n", hash));
+        std::process::exit(EXIT_OK);
+    }
+
     // ── Validate --bind names against LIR ──
     if !bind_overrides.is_empty() {
         let lir = state.downstream.lir.as_ref().unwrap();
@@ -375,9 +1831,14 @@ fn main() {
     }
 
     // ── Write interface manifest side-effect (before emit match exits) ──
+    let manifest_format = match cli.interface_format {
+        InterfaceFormat::Json => pcc::lir::ManifestFormat::Json,
+        InterfaceFormat::Yaml => pcc::lir::ManifestFormat::Yaml,
+    };
     if let Some(ref path) = cli.interface_out {
         let lir = state.downstream.lir.as_ref().unwrap();
-        let manifest = lir.generate_interface_manifest(&bind_overrides);
+        let analysis = state.downstream.analysis.as_ref().unwrap();
+        let manifest = lir.generate_interface_manifest(&bind_overrides, analysis, manifest_format);
         if let Err(e) = std::fs::write(path, &manifest) {
             eprintln!(
                 "error: failed to write interface manifest {}: {}",
@@ -394,23 +1855,134 @@ fn main() {
     // ── --emit interface: write to stdout/--output, then exit ──
     if matches!(cli.emit, EmitStage::Interface) {
         let lir = state.downstream.lir.as_ref().unwrap();
-        let manifest = lir.generate_interface_manifest(&bind_overrides);
+        let analysis = state.downstream.analysis.as_ref().unwrap();
+        let manifest = lir.generate_interface_manifest(&bind_overrides, analysis, manifest_format);
         emit_output(&cli.output, &manifest);
+        if let Some(ref path) = cli.baseline {
+            let current = lir.interface_manifest(&bind_overrides, analysis);
+            if report_interface_diff(path, &current) && cli.fail_on_interface_break {
+                std::process::exit(EXIT_COMPILE_ERROR);
+            }
+        }
+        std::process::exit(EXIT_OK);
+    }
+
+    // ── --emit header: write the --library C interface, then exit ──
+    if matches!(cli.emit, EmitStage::Header) {
+        let lir = state.downstream.lir.as_ref().unwrap();
+        emit_output(&cli.output, &pcc::codegen::generate_library_header(lir));
         std::process::exit(EXIT_OK);
     }
 
+    // ── Write depfile side-effect (inputs of the -o target) ──
+    if let Some(ref path) = cli.depfile {
+        let target = cli.output.clone().unwrap_or_else(|| PathBuf::from("a.out"));
+        let mut deps = Vec::new();
+        if !from_stdin {
+            for root in sources.files.iter().filter(|f| f.root) {
+                deps.push(std::fs::canonicalize(&root.path).unwrap_or(root.path.clone()));
+            }
+        }
+        deps.extend(sources.included_paths().map(Path::to_path_buf));
+        if let Some(ref meta) = cli.actor_meta {
+            deps.push(std::fs::canonicalize(meta).unwrap_or(meta.clone()));
+        }
+        deps.extend(loaded_headers.iter().cloned());
+        if let Err(e) = std::fs::write(path, format_depfile(&target, &deps)) {
+            eprintln!("error: failed to write depfile {}: {}", path.display(), e);
+            std::process::exit(EXIT_SYSTEM_ERROR);
+        }
+        if cli.verbose {
+            eprintln!("pcc: wrote depfile {}", path.display());
+        }
+    }
+
+    // ── --trace-allocations: report declared buffers (stderr) ──
+    if cli.trace_allocations {
+        if let Some(generated) = state.downstream.generated.as_ref() {
+            eprint!(
+                "{}",
+                pcc::codegen::format_allocation_report(&generated.allocations)
+            );
+        }
+    }
+
     // ── Emit-specific output ──
     match cli.emit {
-        EmitStage::Ast | EmitStage::Manifest | EmitStage::BuildInfo | EmitStage::Interface => {
-            unreachable!()
-        }
+        EmitStage::Ast
+        | EmitStage::Manifest
+        | EmitStage::ManifestDiff
+        | EmitStage::BuildInfo
+        | EmitStage::Interface
+        | EmitStage::Header => unreachable!(),
         EmitStage::GraphDot => {
+            let graph = state.upstream.graph.as_ref().unwrap();
+            if cli.collapse_passthrough {
+                print!("{}", pcc::dot::emit_dot_collapsed(graph));
+            } else {
+                print!("{}", pcc::dot::emit_dot(graph));
+            }
+            std::process::exit(EXIT_OK);
+        }
+        EmitStage::GraphJson => {
+            let rates = state
+                .downstream
+                .analysis
+                .as_ref()
+                .map(|a| &a.node_port_rates);
+            let json = pcc::dot::emit_graph_json(state.upstream.graph.as_ref().unwrap(), rates);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).expect("graph JSON serialization")
+            );
+            std::process::exit(EXIT_OK);
+        }
+        EmitStage::Callgraph => {
+            let bytes = state
+                .downstream
+                .analysis
+                .as_ref()
+                .map(|a| &a.inter_task_buffers);
+            let binds: Vec<(&str, &str)> = state
+                .upstream
+                .hir
+                .as_ref()
+                .map(|hir| {
+                    hir.binds
+                        .iter()
+                        .map(|b| (b.name.as_str(), b.endpoint.transport.name.as_str()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            print!(
+                "{}",
+                pcc::dot::emit_callgraph(state.upstream.graph.as_ref().unwrap(), bytes, &binds)
+            );
+            std::process::exit(EXIT_OK);
+        }
+        EmitStage::Mermaid => {
+            let rates = state
+                .downstream
+                .analysis
+                .as_ref()
+                .map(|a| &a.node_port_rates);
             print!(
                 "{}",
-                pcc::dot::emit_dot(state.upstream.graph.as_ref().unwrap())
+                pcc::dot::emit_mermaid(state.upstream.graph.as_ref().unwrap(), rates)
             );
             std::process::exit(EXIT_OK);
         }
+        EmitStage::Deps => {
+            let graph = state.upstream.graph.as_ref().unwrap();
+            match emit_actor_deps(graph, &loaded_headers) {
+                Ok(deps) => emit_output(&cli.output, &deps),
+                Err((msg, code)) => {
+                    eprintln!("error: {}", msg);
+                    std::process::exit(code);
+                }
+            }
+            std::process::exit(EXIT_OK);
+        }
         EmitStage::Graph => {
             print!(
                 "{}",
@@ -418,12 +1990,34 @@ fn main() {
                     state.upstream.graph.as_ref().unwrap(),
                     state.downstream.analysis.as_ref().unwrap(),
                     state.downstream.schedule.as_ref().unwrap(),
+                    cli.collapse_passthrough,
                 )
             );
             std::process::exit(EXIT_OK);
         }
         EmitStage::Schedule => {
-            print!("{}", state.downstream.schedule.as_ref().unwrap());
+            let schedule = state.downstream.schedule.as_ref().unwrap();
+            if cli.per_core {
+                let model =
+                    pcc::timing::timing_model(schedule, state.upstream.graph.as_ref().unwrap());
+                let affinity = pcc::thir::set_affinity(state.upstream.hir.as_ref().unwrap());
+                print!("{}", pcc::timing::emit_per_core(&model, &affinity));
+            } else {
+                print!("{}", schedule);
+            }
+            std::process::exit(EXIT_OK);
+        }
+        EmitStage::ScheduleJson => {
+            let json = state
+                .downstream
+                .schedule
+                .as_ref()
+                .unwrap()
+                .to_json(state.upstream.graph.as_ref().unwrap());
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).expect("schedule JSON serialization")
+            );
             std::process::exit(EXIT_OK);
         }
         EmitStage::TimingChart => {
@@ -436,6 +2030,36 @@ fn main() {
             );
             std::process::exit(EXIT_OK);
         }
+        EmitStage::TimingJson => {
+            let json = pcc::timing::emit_timing_json(
+                state.downstream.schedule.as_ref().unwrap(),
+                state.upstream.graph.as_ref().unwrap(),
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).expect("timing JSON serialization")
+            );
+            std::process::exit(EXIT_OK);
+        }
+        EmitStage::Report => {
+            let title = source_path.file_name().map_or_else(
+                || source_path.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            let html = pcc::report::emit_report(
+                &title,
+                state.upstream.graph.as_ref().unwrap(),
+                state.downstream.analysis.as_ref().unwrap(),
+                state.downstream.schedule.as_ref().unwrap(),
+            );
+            emit_output(&cli.output, &html);
+            if cli.verbose {
+                if let Some(ref out) = cli.output {
+                    eprintln!("pcc: wrote {}", out.display());
+                }
+            }
+            std::process::exit(EXIT_OK);
+        }
         EmitStage::Cpp => {
             let cpp_source = &state.downstream.generated.as_ref().unwrap().cpp_source;
             emit_output(&cli.output, cpp_source);
@@ -444,6 +2068,23 @@ fn main() {
                     eprintln!("pcc: wrote {}", out.display());
                 }
             }
+            if let (Some(compdb), Some(cpp_path)) = (&cli.compdb, &cli.output) {
+                // The executable `--emit exe` would build from this file.
+                let cmd = build_cpp_command(
+                    &cli,
+                    &loaded_headers,
+                    codegen_options.provenance.as_ref(),
+                    cpp_path,
+                    &cpp_path.with_extension(""),
+                );
+                if let Err(e) = write_compdb(compdb, &cmd, cpp_path) {
+                    eprintln!("error: failed to write {}: {}", compdb.display(), e);
+                    std::process::exit(EXIT_SYSTEM_ERROR);
+                }
+                if cli.verbose {
+                    eprintln!("pcc: wrote compilation database {}", compdb.display());
+                }
+            }
             std::process::exit(EXIT_OK);
         }
         EmitStage::Exe => {
@@ -462,53 +2103,13 @@ fn main() {
                 std::process::exit(EXIT_SYSTEM_ERROR);
             }
 
-            // Build compiler command
-            let mut cmd = std::process::Command::new(&cli.cc);
-            cmd.arg("-std=c++20");
-
-            if let Some(flags) = &cli.cflags {
-                for flag in flags.split_whitespace() {
-                    cmd.arg(flag);
-                }
-            } else if cli.release {
-                cmd.arg("-O2");
-            } else {
-                cmd.arg("-O0").arg("-g");
-            }
-
-            if cli.release {
-                cmd.arg("-DNDEBUG");
-            }
-
-            // Runtime headers live at workspace/runtime/libpipit/include.
-            let runtime_include = Path::new(env!("CARGO_MANIFEST_DIR"))
-                .join("..")
-                .join("runtime")
-                .join("libpipit")
-                .join("include");
-            if runtime_include.exists() {
-                cmd.arg("-I").arg(&runtime_include);
-            }
-
-            // Include directories for actor headers (needed for emitted #include "..." lines).
-            let mut include_dirs = BTreeSet::new();
-            for path in &loaded_headers {
-                if let Some(dir) = path.parent() {
-                    include_dirs.insert(dir.to_path_buf());
-                }
-            }
-            for dir in include_dirs {
-                cmd.arg("-I").arg(dir);
-            }
-
-            // Force-include actor headers discovered from both -I and --actor-path.
-            for path in &loaded_headers {
-                cmd.arg("-include").arg(path);
-            }
-
-            cmd.arg("-lpthread");
-            cmd.arg("-o").arg(&exe_output);
-            cmd.arg(&tmp_cpp);
+            let mut cmd = build_cpp_command(
+                &cli,
+                &loaded_headers,
+                codegen_options.provenance.as_ref(),
+                &tmp_cpp,
+                &exe_output,
+            );
 
             if cli.verbose {
                 eprintln!("pcc: running {:?}", cmd);
@@ -540,6 +2141,124 @@ fn main() {
     }
 }
 
+/// The C++ compiler invocation `--emit exe` runs on `cpp_path`; `--compdb`
+/// records the same command.
+fn build_cpp_command(
+    cli: &Cli,
+    loaded_headers: &[PathBuf],
+    provenance: Option<&pcc::pipeline::Provenance>,
+    cpp_path: &Path,
+    exe_output: &Path,
+) -> std::process::Command {
+    let mut cmd = std::process::Command::new(&cli.cc);
+    cmd.arg(cli.cpp_std.flag());
+
+    if let Some(flags) = &cli.cflags {
+        for flag in flags.split_whitespace() {
+            cmd.arg(flag);
+        }
+    } else if cli.release {
+        cmd.arg("-O2");
+    } else {
+        cmd.arg("-O0").arg("-g");
+    }
+
+    if cli.release {
+        cmd.arg("-DNDEBUG");
+    }
+
+    if let Some(march) = &cli.march {
+        cmd.arg(format!("-march={}", march));
+    }
+
+    if let Some(list) = &cli.target_features {
+        // Validated at startup.
+        let (flags, _) = target_feature_flags(list).unwrap_or_default();
+        cmd.args(flags);
+    }
+
+    if cli.openmp {
+        cmd.arg("-fopenmp");
+    }
+
+    if cli.no_exceptions {
+        cmd.arg("-fno-exceptions");
+    }
+
+    if cli.no_rtti {
+        cmd.arg("-fno-rtti");
+    }
+
+    if cli.reproducible {
+        // Keep the per-process temp path and the working directory
+        // out of debug info and symbol seeds.
+        let mut temp_map = std::ffi::OsString::from("-ffile-prefix-map=");
+        temp_map.push(cpp_path);
+        temp_map.push("=pcc_generated.cpp");
+        cmd.arg(temp_map);
+        if let Ok(cwd) = std::env::current_dir() {
+            // Header #includes are emitted relative to the cwd.
+            cmd.arg("-iquote").arg(&cwd);
+            let mut cwd_map = std::ffi::OsString::from("-ffile-prefix-map=");
+            cwd_map.push(cwd);
+            cwd_map.push("=.");
+            cmd.arg(cwd_map);
+        }
+        if let Some(prov) = provenance {
+            cmd.arg(format!("-frandom-seed={}", &prov.source_hash_hex()[..16]));
+        }
+    }
+
+    // Runtime headers live at workspace/runtime/libpipit/include.
+    let runtime_include = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("runtime")
+        .join("libpipit")
+        .join("include");
+    if runtime_include.exists() {
+        cmd.arg("-I").arg(&runtime_include);
+    }
+
+    // Include directories for actor headers (needed for emitted #include "..." lines).
+    let mut include_dirs = BTreeSet::new();
+    for path in loaded_headers {
+        if let Some(dir) = path.parent() {
+            include_dirs.insert(dir.to_path_buf());
+        }
+    }
+    for dir in include_dirs {
+        cmd.arg("-I").arg(dir);
+    }
+
+    // Force-include actor headers discovered from both -I and --actor-path.
+    for path in loaded_headers {
+        cmd.arg("-include").arg(path);
+    }
+
+    cmd.arg("-lpthread");
+    cmd.arg("-o").arg(exe_output);
+    cmd.arg(cpp_path);
+    cmd
+}
+
+/// Write a single-entry `compile_commands.json` for `cpp_path`.
+fn write_compdb(path: &Path, cmd: &std::process::Command, cpp_path: &Path) -> std::io::Result<()> {
+    let directory = std::env::current_dir()?;
+    let file = std::fs::canonicalize(cpp_path)?;
+    let arguments: Vec<String> = std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+    let db = serde_json::json!([{
+        "directory": directory.to_string_lossy(),
+        "file": file.to_string_lossy(),
+        "arguments": arguments,
+    }]);
+    let mut json = serde_json::to_string_pretty(&db).expect("compdb serialization");
+    json.push('\n');
+    std::fs::write(path, json)
+}
+
 /// Write content to the specified output path, or stdout if None / "-".
 fn emit_output(output: &Option<PathBuf>, content: &str) {
     match output {
@@ -690,7 +2409,9 @@ fn load_actor_registry_from_headers(
 
     // --actor-path is base registry; -I overlays with precedence.
     let mut merged = actor_path_registry;
-    merged.overlay_from(&include_registry);
+    merged
+        .overlay_from(&include_registry)
+        .map_err(map_registry_error)?;
 
     let mut all_headers = Vec::new();
     all_headers.extend(actor_path_headers);
@@ -702,6 +2423,127 @@ fn load_actor_registry_from_headers(
     Ok((merged, all_headers))
 }
 
+/// Compile each actor in isolation and print a pass/fail summary to stderr.
+/// Returns false if any actor failed.
+fn report_actor_validation(
+    cli: &Cli,
+    registry: &pcc::registry::Registry,
+    headers: &[PathBuf],
+) -> bool {
+    let mut include_dirs: Vec<PathBuf> = Vec::new();
+    for dir in headers.iter().filter_map(|h| h.parent()) {
+        if !include_dirs.iter().any(|d| d == dir) {
+            include_dirs.push(dir.to_path_buf());
+        }
+    }
+    let results = registry.validate_actors(&cli.cc, &include_dirs);
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    for result in &results {
+        match &result.error {
+            None => eprintln!("  ok    {}", result.name),
+            Some(error) => {
+                eprintln!("  FAIL  {} ({})", result.name, result.header.display());
+                let errors: Vec<&str> = error.lines().filter(|l| l.contains("error")).collect();
+                let shown = if errors.is_empty() {
+                    error.lines().take(3).collect()
+                } else {
+                    errors
+                };
+                for line in shown.iter().take(3) {
+                    eprintln!("          {}", line);
+                }
+            }
+        }
+    }
+    eprintln!(
+        "actor validation: {} passed, {} failed",
+        results.len() - failed,
+        failed
+    );
+    failed == 0
+}
+
+/// ISA features `--target-features` recognizes without a warning.
+const KNOWN_TARGET_FEATURES: &[&str] = &[
+    "sse2", "sse3", "ssse3", "sse4.1", "sse4.2", "popcnt", "avx", "avx2", "fma", "f16c", "bmi",
+    "bmi2", "lzcnt", "avx512f", "avx512cd", "avx512bw", "avx512dq", "avx512vl",
+];
+
+/// `-m<feature>` flags for a comma-separated feature list, plus the
+/// features not in `KNOWN_TARGET_FEATURES` (still passed through).
+fn target_feature_flags(list: &str) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut flags = Vec::new();
+    let mut unknown = Vec::new();
+    for feature in list.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        let valid = feature.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && feature
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if !valid {
+            return Err(format!("invalid target feature '{}'", feature));
+        }
+        if !KNOWN_TARGET_FEATURES.contains(&feature) {
+            unknown.push(feature.to_string());
+        }
+        flags.push(format!("-m{}", feature));
+    }
+    Ok((flags, unknown))
+}
+
+/// Byte count for a `--max-memory` value: a plain integer or one with a
+/// `KB`/`MB`/`GB` suffix (binary multiples, as for `set mem`).
+fn parse_mem_size(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let unit_start = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (num_str, unit) = text.split_at(unit_start);
+    let multiplier: u64 = match unit {
+        "" => 1,
+        "KB" => 1_024,
+        "MB" => 1_024 * 1_024,
+        "GB" => 1_024 * 1_024 * 1_024,
+        _ => {
+            return Err(format!(
+                "invalid size '{}' (expected e.g. 65536, 1KB, 64MB)",
+                text
+            ))
+        }
+    };
+    num_str
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size '{}' (expected e.g. 65536, 1KB, 64MB)", text))
+}
+
+/// `SOURCE_DATE_EPOCH` as seconds, or 0 when unset.
+fn source_date_epoch() -> Result<u64, String> {
+    match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(v) => v.trim().parse().map_err(|_| {
+            format!(
+                "SOURCE_DATE_EPOCH must be a non-negative integer, got '{}'",
+                v
+            )
+        }),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Normalized build stamp for `--reproducible`; validated at startup.
+fn build_epoch(cli: &Cli) -> Option<u64> {
+    cli.reproducible.then(|| source_date_epoch().unwrap_or(0))
+}
+
+/// `path` relative to the working directory when it lies beneath it.
+fn relative_to_cwd(path: &Path) -> PathBuf {
+    std::env::current_dir()
+        .and_then(std::fs::canonicalize)
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
 fn canonicalize_all(paths: &[PathBuf], err_code: i32) -> Result<Vec<PathBuf>, (String, i32)> {
     let mut out = Vec::new();
     for path in paths {
@@ -788,17 +2630,18 @@ fn map_registry_error(e: pcc::registry::RegistryError) -> (String, i32) {
             (format!("{}", e), EXIT_SYSTEM_ERROR)
         }
         pcc::registry::RegistryError::ParseError { .. }
-        | pcc::registry::RegistryError::DuplicateActor { .. } => {
+        | pcc::registry::RegistryError::DuplicateActor { .. }
+        | pcc::registry::RegistryError::AliasCollision { .. } => {
             (format!("{}", e), EXIT_COMPILE_ERROR)
         }
     }
 }
 
 fn print_pipeline_diags(
-    source_path: &Path,
-    source: &str,
+    sources: &pcc::include::ComposedSource,
     diags: &[pcc::diag::Diagnostic],
     format: DiagnosticFormat,
+    context: Option<usize>,
 ) -> bool {
     let mut has_error = false;
 
@@ -822,11 +2665,11 @@ fn print_pipeline_diags(
             print_span_diagnostic(
                 &level,
                 &diag.message,
-                source_path,
-                source,
+                sources,
                 diag.span.start,
                 diag.span.end,
                 diag.hint.as_deref(),
+                context,
             );
 
             // Display related spans
@@ -834,11 +2677,11 @@ fn print_pipeline_diags(
                 print_span_diagnostic(
                     "note",
                     &rel.label,
-                    source_path,
-                    source,
+                    sources,
                     rel.span.start,
                     rel.span.end,
                     None,
+                    context,
                 );
             }
 
@@ -848,14 +2691,18 @@ fn print_pipeline_diags(
                     print_span_diagnostic(
                         "cause",
                         &cause.message,
-                        source_path,
-                        source,
+                        sources,
                         span.start,
                         span.end,
                         None,
+                        context,
                     );
                 } else {
-                    eprintln!("  cause: {}", cause.message);
+                    eprintln!(
+                        "  {}: {}",
+                        paint(level_sgr("cause"), "cause"),
+                        cause.message
+                    );
                 }
             }
         }
@@ -866,17 +2713,21 @@ fn print_pipeline_diags(
     has_error
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_span_diagnostic(
     level: &str,
     message: &str,
-    source_path: &Path,
-    source: &str,
+    sources: &pcc::include::ComposedSource,
     span_start: usize,
     span_end: usize,
     hint: Option<&str>,
+    context: Option<usize>,
 ) {
-    let start = span_start.min(source.len());
-    let end = span_end.min(source.len());
+    // Report against the file the span falls in, with file-local offsets.
+    let (file, source) = sources.locate(span_start);
+    let source_path = &file.path;
+    let start = span_start.saturating_sub(file.start).min(source.len());
+    let end = span_end.saturating_sub(file.start).min(source.len());
 
     let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
     let line_end = source[start..]
@@ -887,6 +2738,16 @@ fn print_span_diagnostic(
     let line_no = source[..line_start].bytes().filter(|b| *b == b'\n').count() + 1;
     let col_no = source[line_start..start].chars().count() + 1;
 
+    eprintln!("{}: {}", paint(level_sgr(level), level), message);
+    eprintln!("  at {}:{}:{}", source_path.display(), line_no, col_no);
+    if let Some(context) = context {
+        eprint!("{}", format_source_excerpt(source, start, end, context));
+        if let Some(h) = hint {
+            eprintln!("  {}: {}", paint(ACCENT_SGR, "hint"), h);
+        }
+        return;
+    }
+
     let mut caret_width = if end > start {
         let caret_end = end.min(line_end);
         source[start..caret_end].chars().count().max(1)
@@ -898,27 +2759,129 @@ fn print_span_diagnostic(
         caret_width = 1;
     }
 
-    eprintln!("{}: {}", level, message);
-    eprintln!("  at {}:{}:{}", source_path.display(), line_no, col_no);
     eprintln!("  {}", line_text);
     eprintln!(
         "  {}{}",
         " ".repeat(col_no.saturating_sub(1)),
-        "^".repeat(caret_width)
+        paint(ACCENT_SGR, &"^".repeat(caret_width))
     );
     if let Some(h) = hint {
-        eprintln!("  hint: {}", h);
+        eprintln!("  {}: {}", paint(ACCENT_SGR, "hint"), h);
+    }
+}
+
+/// Render the lines covered by `start..end` plus `context` lines either
+/// side, with a line-number gutter and carets under the spanned text
+/// (`--diagnostic-context`).
+fn format_source_excerpt(source: &str, start: usize, end: usize, context: usize) -> String {
+    let lines: Vec<(usize, &str)> = source
+        .split('\n')
+        .scan(0, |offset, text| {
+            let line = (*offset, text);
+            *offset += text.len() + 1;
+            Some(line)
+        })
+        .collect();
+    let line_of = |pos: usize| {
+        lines
+            .iter()
+            .rposition(|&(offset, _)| offset <= pos)
+            .unwrap_or(0)
+    };
+    let first = line_of(start);
+    let last = line_of(end.saturating_sub(1).max(start));
+    let shown_first = first.saturating_sub(context);
+    let shown_last = (last + context).min(lines.len() - 1);
+    let width = (shown_last + 1).to_string().len();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "  {} |", " ".repeat(width));
+    for (idx, &(offset, text)) in lines
+        .iter()
+        .enumerate()
+        .take(shown_last + 1)
+        .skip(shown_first)
+    {
+        let _ = writeln!(out, "  {:>width$} | {}", idx + 1, text);
+        if idx < first || idx > last {
+            continue;
+        }
+        let from = start.max(offset) - offset;
+        let to = end.min(offset + text.len()).max(start.max(offset)) - offset;
+        let pad = text[..from].chars().count();
+        let carets = text[from..to].chars().count().max(1);
+        let _ = writeln!(
+            out,
+            "  {} | {}{}",
+            " ".repeat(width),
+            " ".repeat(pad),
+            paint(ACCENT_SGR, &"^".repeat(carets))
+        );
+    }
+    out
+}
+
+/// `--emit deps`: the headers defining actors the graph actually uses, one
+/// per line. Headers are attributed by scanning `headers` (from `-I` and
+/// `--actor-path`; `-I` wins on conflict), since a manifest-loaded registry
+/// does not record them.
+fn emit_actor_deps(
+    graph: &pcc::graph::ProgramGraph,
+    headers: &[PathBuf],
+) -> Result<String, (String, i32)> {
+    let mut by_header = pcc::registry::Registry::new();
+    for header in headers {
+        let mut one = pcc::registry::Registry::new();
+        one.load_header(header).map_err(map_registry_error)?;
+        by_header.overlay_from(&one).map_err(map_registry_error)?;
+    }
+
+    let mut deps = BTreeSet::new();
+    let mut missing = BTreeSet::new();
+    for task_graph in graph.tasks.values() {
+        for sub in pcc::subgraph_index::subgraphs_of(task_graph) {
+            for node in &sub.nodes {
+                let pcc::graph::NodeKind::Actor { name, .. } = &node.kind else {
+                    continue;
+                };
+                match by_header.source_header(name) {
+                    Some(path) => {
+                        deps.insert(path.to_path_buf());
+                    }
+                    None => {
+                        missing.insert(name.as_str());
+                    }
+                }
+            }
+        }
+    }
+    for name in missing {
+        eprintln!(
+            "pcc: warning: no header under -I/--actor-path defines actor '{}'",
+            name
+        );
+    }
+
+    let mut out = String::new();
+    for path in deps {
+        let _ = writeln!(out, "{}", path.display());
     }
+    Ok(out)
 }
 
 fn emit_graph_dump(
     graph: &pcc::graph::ProgramGraph,
     analysis: &pcc::analyze::AnalyzedProgram,
     schedule: &pcc::schedule::ScheduledProgram,
+    collapse_passthrough: bool,
 ) -> String {
     let mut out = String::new();
 
-    let _ = writeln!(out, "{}", graph);
+    if collapse_passthrough {
+        let _ = writeln!(out, "{}", pcc::graph::fmt_collapsed(graph));
+    } else {
+        let _ = writeln!(out, "{}", graph);
+    }
 
     // repetition_vector entries
     let mut rv = BTreeMap::new();
@@ -940,6 +2903,20 @@ fn emit_graph_dump(
         }
     }
 
+    // longest dependency chain per subgraph
+    if !analysis.critical_path.is_empty() {
+        let paths: BTreeMap<_, _> = analysis.critical_path.iter().collect();
+        let _ = writeln!(out, "critical_paths:");
+        for ((task, label), path) in paths {
+            let chain = path
+                .iter()
+                .map(|id| format!("n{}", id.0))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let _ = writeln!(out, "  critical_path {}.{}: {}", task, label, chain);
+        }
+    }
+
     // inter-task buffer sizes (bytes)
     if !analysis.inter_task_buffers.is_empty() {
         let mut inter: Vec<_> = analysis.inter_task_buffers.iter().collect();
@@ -951,6 +2928,23 @@ fn emit_graph_dump(
         }
     }
 
+    // family buffer reuse opportunities (bytes incl. ring buffer headers)
+    if !analysis.family_reuse.is_empty() {
+        let _ = writeln!(out, "family_reuse:");
+        for fam in &analysis.family_reuse {
+            let _ = writeln!(
+                out,
+                "  family_reuse {}: elements={} buffers={}->1 bytes={}->{} saved={}",
+                fam.family_name,
+                fam.element_count,
+                fam.element_count,
+                fam.separate_bytes,
+                fam.coalesced_bytes,
+                fam.saved_bytes()
+            );
+        }
+    }
+
     // intra-task edge buffer sizes (tokens)
     let mut task_names: Vec<_> = schedule.tasks.keys().cloned().collect();
     task_names.sort();
@@ -57,6 +57,12 @@ pub fn parse(source: &str) -> ParseResult {
     }
 }
 
+/// Combine two shape-dimension operands; the span covers both.
+fn shape_binop(lhs: ShapeDim, (op, rhs): (ShapeOp, ShapeDim)) -> ShapeDim {
+    let span: SimpleSpan = (lhs.span().start()..rhs.span().end()).into();
+    ShapeDim::BinOp(Box::new(lhs), op, Box::new(rhs), span)
+}
+
 // ── Main parser builder ──
 //
 // All grammar rules are built inside `program_parser` so that the `source`
@@ -72,8 +78,10 @@ where
 {
     let classify_number = |span: SimpleSpan| {
         let lexeme = &source[span.start()..span.end()];
-        let is_int_literal =
-            !lexeme.contains('.') && !lexeme.contains('e') && !lexeme.contains('E');
+        let digits = lexeme.trim_start_matches('-');
+        let is_int_literal = digits.starts_with("0x")
+            || digits.starts_with("0b")
+            || (!lexeme.contains('.') && !lexeme.contains('e') && !lexeme.contains('E'));
         (span, is_int_literal)
     };
 
@@ -150,12 +158,34 @@ where
             .ignore_then(ident.clone())
             .map(Arg::TapRef);
 
+        let slice_bound = select! {
+            Token::Number(n) if n >= 0.0 && n.fract() == 0.0 && n <= u32::MAX as f64 => n as u32,
+        };
+
+        let const_slice = ident
+            .clone()
+            .then(
+                slice_bound
+                    .then_ignore(just(Token::Colon))
+                    .then(slice_bound)
+                    .delimited_by(just(Token::LBracket), just(Token::RBracket)),
+            )
+            .map_with(|(name, (start, end)), e| {
+                Arg::ConstSlice(ConstSlice {
+                    name,
+                    start,
+                    end,
+                    span: e.span(),
+                })
+            });
+
         let const_ref = ident.clone().map(Arg::ConstRef);
 
         param_ref
             .or(tap_ref)
             .or(array_arg)
             .or(literal_arg)
+            .or(const_slice)
             .or(const_ref)
     };
 
@@ -174,12 +204,51 @@ where
         });
 
     // ── Shape constraint: '[' shape_dim (',' shape_dim)* ']' ──
-
-    let shape_dim = select! {
-        Token::Number(n) if n > 0.0 && n.fract() == 0.0 && n <= u32::MAX as f64 => n,
-    }
-    .map_with(|n, e| ShapeDim::Literal(n as u32, e.span()))
-    .or(ident.clone().map(ShapeDim::ConstRef));
+    //
+    // shape_dim  ::= shape_term (('+' | '-') shape_term)*
+    // shape_term ::= shape_atom (('*' | '/') shape_atom)*
+    // shape_atom ::= NUMBER | IDENT | '(' shape_dim ')'
+    //
+    // `N-1` lexes as IDENT followed by the literal `-1`, so a negative
+    // literal after an operand reads as a subtraction. Folding happens in
+    // resolve/THIR once const values are known.
+
+    let shape_dim = recursive(|shape_dim| {
+        let atom = select! {
+            Token::Number(n) if n >= 0.0 && n.fract() == 0.0 && n <= u32::MAX as f64 => n,
+        }
+        .map_with(|n, e| ShapeDim::Literal(n as u32, e.span()))
+        .or(ident.clone().map(ShapeDim::ConstRef))
+        .or(shape_dim.delimited_by(just(Token::LParen), just(Token::RParen)));
+        let term_tail = just(Token::Star)
+            .to(ShapeOp::Mul)
+            .or(just(Token::Slash).to(ShapeOp::Div))
+            .then(atom.clone())
+            .repeated();
+        let term = atom.foldl(term_tail.clone(), shape_binop);
+        let negative_term = select! {
+            Token::Number(n) if n < 0.0 && n.fract() == 0.0 && -n <= u32::MAX as f64 => n,
+        }
+        .map_with(|n, e| {
+            let span: SimpleSpan = e.span();
+            ShapeDim::Literal(-n as u32, (span.start() + 1..span.end()).into())
+        })
+        .foldl(term_tail, shape_binop);
+        term.clone().foldl(
+            just(Token::Plus)
+                .to(ShapeOp::Add)
+                .or(just(Token::Minus).to(ShapeOp::Sub))
+                .then(term)
+                .or(negative_term.map(|t| (ShapeOp::Sub, t)))
+                .repeated(),
+            shape_binop,
+        )
+    })
+    .try_map(|dim, span| match dim {
+        // A zero operand is fine (`N/1 - 0`); a bare zero dimension is not.
+        ShapeDim::Literal(0, _) => Err(Rich::custom(span, "shape dimension must be > 0")),
+        dim => Ok(dim),
+    });
 
     let shape_constraint = shape_dim
         .clone()
@@ -204,13 +273,14 @@ where
         })
         .try_map(|id, span| {
             match id.name.as_str() {
-                "int8" | "int16" | "int32" | "float" | "double" | "cfloat" | "cdouble" => Ok(id),
-                _ => Err(chumsky::error::Rich::custom(span, format!("expected pipit type (int8, int16, int32, float, double, cfloat, cdouble), found '{}'", id.name))),
+                "int8" | "int16" | "int32" | "float" | "double" | "cfloat" | "cdouble" | "bool" => Ok(id),
+                _ => Err(chumsky::error::Rich::custom(span, format!("expected pipit type (int8, int16, int32, float, double, cfloat, cdouble, bool), found '{}'", id.name))),
             }
         });
 
     // ── Type args: '<' type (',' type)* '>' (optional) ──
     let type_args = type_arg
+        .clone()
         .separated_by(just(Token::Comma))
         .at_least(1)
         .collect::<Vec<_>>()
@@ -224,15 +294,81 @@ where
                 .collect::<Vec<_>>()
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
-        .then(shape_constraint.or_not())
+        .then(shape_constraint.clone().or_not())
         .map_with(|(((name, type_args), args), shape), e| ActorCall {
             name,
             type_args: type_args.unwrap_or_default(),
             args,
             shape_constraint: shape,
+            atomic_group: None,
+            persist: false,
+            allow_combinational_loop: false,
             span: e.span(),
         });
 
+    // ── Call annotations: '@' 'atomic_group' '(' IDENT ')' | '@' 'persist'
+    //                     | '@' 'allow_combinational_loop' ──
+    // Postfix so they cannot be confused with a `@name` buffer read source.
+    enum CallAnnotation {
+        AtomicGroup(Ident),
+        Persist,
+        AllowCombinationalLoop,
+    }
+    let annotation_name = ident.clone().try_map(|id, span| {
+        if matches!(
+            id.name.as_str(),
+            "atomic_group" | "persist" | "allow_combinational_loop"
+        ) {
+            Ok(id)
+        } else {
+            Err(Rich::custom(
+                span,
+                format!(
+                    "unknown annotation '@{}' \
+                     (expected @atomic_group, @persist or @allow_combinational_loop)",
+                    id.name
+                ),
+            ))
+        }
+    });
+    let group_name = ident
+        .clone()
+        .delimited_by(just(Token::LParen), just(Token::RParen));
+    let annotation = just(Token::At)
+        .ignore_then(annotation_name)
+        .then(group_name.or_not())
+        .try_map(|(name, group), span| match (name.name.as_str(), group) {
+            ("atomic_group", Some(group)) => Ok(CallAnnotation::AtomicGroup(group)),
+            ("persist", None) => Ok(CallAnnotation::Persist),
+            ("allow_combinational_loop", None) => Ok(CallAnnotation::AllowCombinationalLoop),
+            ("atomic_group", None) => Err(Rich::custom(
+                span,
+                "@atomic_group requires a group name: @atomic_group(name)".to_string(),
+            )),
+            (name, _) => Err(Rich::custom(span, format!("@{} takes no arguments", name))),
+        });
+
+    let actor_call = actor_call
+        .then(annotation.repeated().collect::<Vec<_>>())
+        .map(|(call, annotations)| {
+            annotations
+                .into_iter()
+                .fold(call, |call, annotation| match annotation {
+                    CallAnnotation::AtomicGroup(group) => ActorCall {
+                        atomic_group: Some(group),
+                        ..call
+                    },
+                    CallAnnotation::Persist => ActorCall {
+                        persist: true,
+                        ..call
+                    },
+                    CallAnnotation::AllowCombinationalLoop => ActorCall {
+                        allow_combinational_loop: true,
+                        ..call
+                    },
+                })
+        });
+
     // ── Buffer reference: ident ('[' (Star | Number | ident) ']')? ──
 
     let buffer_index = just(Token::Star)
@@ -264,8 +400,31 @@ where
         let tap_ref = just(Token::Colon)
             .ignore_then(ident.clone())
             .map(PipeSource::TapRef);
+        // gather(:a, :b, ...) — tap fan-in; tried before the generic actor call
+        let gather_kw = ident.clone().try_map(|id, span| {
+            if id.name == "gather" {
+                Ok(id)
+            } else {
+                Err(Rich::custom(span, "expected gather"))
+            }
+        });
+        let tap_gather = gather_kw
+            .ignore_then(
+                just(Token::Colon)
+                    .ignore_then(ident.clone())
+                    .separated_by(just(Token::Comma))
+                    .at_least(1)
+                    .collect::<Vec<_>>()
+                    .delimited_by(just(Token::LParen), just(Token::RParen)),
+            )
+            .map_with(|taps, e| {
+                PipeSource::TapGather(TapGather {
+                    taps,
+                    span: e.span(),
+                })
+            });
         let actor_src = actor_call.clone().map(PipeSource::ActorCall);
-        buffer_read.or(tap_ref).or(actor_src)
+        buffer_read.or(tap_ref).or(tap_gather).or(actor_src)
     };
 
     let pipe_elem = {
@@ -324,9 +483,22 @@ where
         Token::Number(n) = e => SetValue::Number(n, e.span()),
         Token::Freq(f) = e => SetValue::Freq(f, e.span()),
         Token::Size(s) = e => SetValue::Size(s, e.span()),
+        Token::Percent(p) = e => SetValue::Percent(p, e.span()),
         Token::StringLit(s) = e => SetValue::StringLit(s, e.span()),
     }
     .or(ident.clone().map(SetValue::Ident));
+    let set_map = ident
+        .clone()
+        .then_ignore(just(Token::Colon))
+        .then(set_value.clone())
+        .padded_by(nl.clone())
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .collect::<Vec<_>>()
+        .then_ignore(nl.clone())
+        .delimited_by(just(Token::LBrace), just(Token::RBrace))
+        .map_with(|entries, e| SetValue::Map(entries, e.span()));
+    let set_value = set_map.or(set_value);
 
     // ── Statements ──
 
@@ -388,11 +560,54 @@ where
                 span: e.span(),
             });
 
-        just(Token::Bind)
-            .ignore_then(ident.clone())
+        // Optional build-mode guard: '@' 'when' '(' ('debug' | 'release') ')'
+        let when_name = ident.clone().try_map(|id, span| {
+            if id.name == "when" {
+                Ok(id)
+            } else {
+                Err(Rich::custom(
+                    span,
+                    format!("unknown annotation '@{}' (expected @when)", id.name),
+                ))
+            }
+        });
+        let build_mode = ident.clone().try_map(|id, span| match id.name.as_str() {
+            "debug" => Ok(BuildMode::Debug),
+            "release" => Ok(BuildMode::Release),
+            other => Err(Rich::custom(
+                span,
+                format!("unknown build mode '{}' (expected debug or release)", other),
+            )),
+        });
+        let when_guard = just(Token::At)
+            .ignore_then(when_name)
+            .ignore_then(build_mode.delimited_by(just(Token::LParen), just(Token::RParen)));
+
+        // Optional declared wire type: ':' type_arg shape_constraint?
+        let bind_type = just(Token::Colon)
+            .ignore_then(type_arg)
+            .then(shape_constraint.or_not())
+            .map_with(|(dtype, shape), e| BindType {
+                dtype,
+                shape,
+                span: e.span(),
+            });
+
+        when_guard
+            .or_not()
+            .then_ignore(just(Token::Bind))
+            .then(ident.clone())
+            .then(bind_type.or_not())
             .then_ignore(just(Token::Equals))
             .then(bind_endpoint)
-            .map(|(name, endpoint)| StatementKind::Bind(BindStmt { name, endpoint }))
+            .map(|(((when, name), ty), endpoint)| {
+                StatementKind::Bind(BindStmt {
+                    name,
+                    ty,
+                    endpoint,
+                    when,
+                })
+            })
     };
 
     let define_stmt = just(Token::Define)
@@ -441,8 +656,11 @@ where
         let param = just(Token::Dollar)
             .ignore_then(ident.clone())
             .map(SwitchSource::Param);
+        let bind = just(Token::At)
+            .ignore_then(ident.clone())
+            .map(SwitchSource::Bind);
         let buffer = ident.clone().map(SwitchSource::Buffer);
-        param.or(buffer)
+        param.or(bind).or(buffer)
     };
 
     let switch_stmt = just(Token::Switch)
@@ -534,6 +752,12 @@ where
             })
         });
 
+    // ── Include statement: 'include' STRING ──
+
+    let include_stmt = just(Token::Include)
+        .ignore_then(select! { Token::StringLit(s) = e => (s, e.span()) })
+        .map(|(path, path_span)| StatementKind::Include(IncludeStmt { path, path_span }));
+
     // ── Task statement ──
 
     let freq = select! {
@@ -569,6 +793,7 @@ where
         bind_stmt,
         define_stmt,
         shared_stmt,
+        include_stmt,
         task_stmt,
     ))
     .map_with(|kind, e| Statement {
@@ -633,6 +858,18 @@ mod tests {
         assert!(prog.statements.is_empty());
     }
 
+    // ── include_stmt ──
+
+    #[test]
+    fn include_path() {
+        let s = parse_one_stmt(r#"include "lib/common.pdl""#);
+        let StatementKind::Include(inc) = &s.kind else {
+            panic!("expected Include")
+        };
+        assert_eq!(inc.path, "lib/common.pdl");
+        assert_eq!((inc.path_span.start, inc.path_span.end), (8, 24));
+    }
+
     // ── set_stmt ──
 
     #[test]
@@ -655,6 +892,15 @@ mod tests {
         assert!(matches!(set.value, SetValue::Size(v, _) if v == 64 * 1024 * 1024));
     }
 
+    #[test]
+    fn set_percent() {
+        let s = parse_one_stmt("set mem = 50%");
+        let StatementKind::Set(set) = &s.kind else {
+            panic!("expected Set")
+        };
+        assert!(matches!(set.value, SetValue::Percent(v, _) if v == 50.0));
+    }
+
     #[test]
     fn set_freq() {
         let s = parse_one_stmt("set rate = 48kHz");
@@ -682,6 +928,22 @@ mod tests {
         assert!(matches!(&set.value, SetValue::Ident(id) if id.name == "round_robin"));
     }
 
+    #[test]
+    fn set_map() {
+        let s = parse_one_stmt("set affinity = { rx: 2,\n  tx: 3 }");
+        let StatementKind::Set(set) = &s.kind else {
+            panic!("expected Set")
+        };
+        let SetValue::Map(entries, _) = &set.value else {
+            panic!("expected Map, got {:?}", set.value)
+        };
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0.name, "rx");
+        assert!(matches!(entries[0].1, SetValue::Number(v, _) if v == 2.0));
+        assert_eq!(entries[1].0.name, "tx");
+        assert!(matches!(entries[1].1, SetValue::Number(v, _) if v == 3.0));
+    }
+
     // ── const_stmt ──
 
     #[test]
@@ -694,6 +956,21 @@ mod tests {
         assert!(matches!(c.value, Value::Scalar(Scalar::Number(v, _, _)) if v == 0.5));
     }
 
+    #[test]
+    fn const_hex_and_binary_are_int_literals() {
+        for (src, expected) in [("const mask = 0xFE", 254.0), ("const bits = 0b1010", 10.0)] {
+            let s = parse_one_stmt(src);
+            let StatementKind::Const(c) = &s.kind else {
+                panic!("expected Const")
+            };
+            assert!(
+                matches!(c.value, Value::Scalar(Scalar::Number(v, _, true)) if v == expected),
+                "{src}: {:?}",
+                c.value
+            );
+        }
+    }
+
     #[test]
     fn const_array() {
         let s = parse_one_stmt("const c = [0.1, 0.4, 0.1]");
@@ -799,6 +1076,35 @@ mod tests {
         assert!(matches!(&p.lines[0].source, PipeSource::TapRef(id) if id.name == "raw"));
     }
 
+    #[test]
+    fn pipe_with_tap_gather_source() {
+        let s = parse_one_stmt("clock 1kHz t {\n  gather(:a, :b, :c) | add()\n}");
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Pipeline(p) = &t.body else {
+            panic!("expected Pipeline")
+        };
+        let PipeSource::TapGather(g) = &p.lines[0].source else {
+            panic!("expected TapGather, got {:?}", p.lines[0].source)
+        };
+        let names: Vec<&str> = g.taps.iter().map(|id| id.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn gather_without_taps_is_actor_call() {
+        // `gather` is not a keyword: with non-tap args it is an ordinary call.
+        let s = parse_one_stmt("clock 1kHz t {\n  gather(1) | add()\n}");
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Pipeline(p) = &t.body else {
+            panic!("expected Pipeline")
+        };
+        assert!(matches!(&p.lines[0].source, PipeSource::ActorCall(c) if c.name.name == "gather"));
+    }
+
     #[test]
     fn pipe_with_probe() {
         let s = parse_one_stmt("clock 1kHz t {\n  adc(0) | ?debug | fir(c)\n}");
@@ -875,6 +1181,25 @@ mod tests {
         assert!(matches!(&a.args[2], Arg::ConstRef(id) if id.name == "c"));
     }
 
+    #[test]
+    fn actor_call_with_const_slice() {
+        let s = parse_one_stmt("clock 1kHz t {\n  fir(coeff[4:20])\n}");
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Pipeline(p) = &t.body else {
+            panic!("expected Pipeline")
+        };
+        let PipeSource::ActorCall(a) = &p.lines[0].source else {
+            panic!("expected ActorCall")
+        };
+        let Arg::ConstSlice(slice) = &a.args[0] else {
+            panic!("expected ConstSlice, got {:?}", a.args[0])
+        };
+        assert_eq!(slice.name.name, "coeff");
+        assert_eq!((slice.start, slice.end, slice.len()), (4, 20, 16));
+    }
+
     #[test]
     fn actor_call_with_tap_ref() {
         let s = parse_one_stmt("clock 1kHz t {\n  add(:fb)\n}");
@@ -968,6 +1293,19 @@ mod tests {
         assert!(matches!(&m.switch.source, SwitchSource::Param(id) if id.name == "sel"));
     }
 
+    #[test]
+    fn switch_bind_source() {
+        let src = "clock 10MHz rx {\n  mode a {\n    foo()\n  }\n  mode b {\n    bar()\n  }\n  switch(@sel, a, b)\n}";
+        let s = parse_one_stmt(src);
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Modal(m) = &t.body else {
+            panic!("expected Modal")
+        };
+        assert!(matches!(&m.switch.source, SwitchSource::Bind(id) if id.name == "sel"));
+    }
+
     // ── Multiple statements ──
 
     #[test]
@@ -1130,6 +1468,37 @@ mod tests {
         assert!(matches!(&sc.dims[0], ShapeDim::ConstRef(id) if id.name == "N"));
     }
 
+    #[test]
+    fn actor_call_with_arithmetic_shape() {
+        let s = parse_one_stmt("clock 1kHz t {\n  img()[N*2+1, (N - 1)/2, N-1]\n}");
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Pipeline(p) = &t.body else {
+            panic!("expected Pipeline")
+        };
+        let PipeSource::ActorCall(a) = &p.lines[0].source else {
+            panic!("expected ActorCall")
+        };
+        let sc = a
+            .shape_constraint
+            .as_ref()
+            .expect("expected shape constraint");
+        let dims: Vec<String> = sc.dims.iter().map(|d| d.to_string()).collect();
+        assert_eq!(dims, vec!["N*2+1", "(N-1)/2", "N-1"]);
+        // `*` binds tighter than `+`.
+        let ShapeDim::BinOp(lhs, ShapeOp::Add, rhs, _) = &sc.dims[0] else {
+            panic!("expected Add, got {:?}", sc.dims[0])
+        };
+        assert!(matches!(**lhs, ShapeDim::BinOp(_, ShapeOp::Mul, _, _)));
+        assert!(matches!(**rhs, ShapeDim::Literal(1, _)));
+        // `N-1` (lexed as `N` and `-1`) is a subtraction of 1.
+        assert!(matches!(
+            &sc.dims[2],
+            ShapeDim::BinOp(_, ShapeOp::Sub, rhs, _) if matches!(**rhs, ShapeDim::Literal(1, _))
+        ));
+    }
+
     #[test]
     fn actor_call_without_shape() {
         let s = parse_one_stmt("clock 1kHz t {\n  fft(256)\n}");
@@ -1206,6 +1575,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn actor_call_with_atomic_group() {
+        let s =
+            parse_one_stmt("clock 1kHz t {\n  adc(0) | fir(c)[64] @atomic_group(st) | stdout()\n}");
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Pipeline(p) = &t.body else {
+            panic!("expected Pipeline")
+        };
+        let PipeElem::ActorCall(a) = &p.lines[0].elements[0] else {
+            panic!("expected ActorCall in pipe element")
+        };
+        assert_eq!(a.atomic_group.as_ref().map(|g| g.name.as_str()), Some("st"));
+        assert!(a.shape_constraint.is_some());
+        assert_eq!(
+            &"clock 1kHz t {\n  adc(0) | fir(c)[64] @atomic_group(st) | stdout()\n}"
+                [a.span.start..a.span.end],
+            "fir(c)[64]"
+        );
+    }
+
+    #[test]
+    fn actor_call_with_persist() {
+        let prog =
+            parse_ok("clock 1kHz t {\n  :out | delay(1, 0.0) @persist @atomic_group(g) | :fb\n}");
+        let StatementKind::Task(t) = &prog.statements[0].kind else {
+            panic!("expected task")
+        };
+        let TaskBody::Pipeline(body) = &t.body else {
+            panic!("expected pipeline")
+        };
+        let PipeElem::ActorCall(a) = &body.lines[0].elements[0] else {
+            panic!("expected actor call")
+        };
+        assert!(a.persist);
+        assert_eq!(a.atomic_group.as_ref().unwrap().name, "g");
+
+        let (_, errs) = parse_all("clock 1kHz t {\n  :out | delay(1, 0.0) @persist(x) | :fb\n}");
+        assert!(!errs.is_empty(), "@persist takes no arguments");
+    }
+
+    #[test]
+    fn actor_call_with_allow_combinational_loop() {
+        let prog = parse_ok(
+            "clock 1kHz t {\n  constant(0.0) | add(:fb) @allow_combinational_loop | :out\n}",
+        );
+        let StatementKind::Task(t) = &prog.statements[0].kind else {
+            panic!("expected task")
+        };
+        let TaskBody::Pipeline(body) = &t.body else {
+            panic!("expected pipeline")
+        };
+        let PipeElem::ActorCall(a) = &body.lines[0].elements[0] else {
+            panic!("expected actor call")
+        };
+        assert!(a.allow_combinational_loop);
+        assert!(!a.persist);
+
+        let (_, errs) = parse_all(
+            "clock 1kHz t {\n  constant(0.0) | add(:fb) @allow_combinational_loop(x) | :out\n}",
+        );
+        assert!(
+            !errs.is_empty(),
+            "@allow_combinational_loop takes no arguments"
+        );
+    }
+
+    #[test]
+    fn unknown_call_annotation_rejected() {
+        let (_, errs) = parse_all("clock 1kHz t {\n  adc(0) @bogus(g) | stdout()\n}");
+        assert!(
+            !errs.is_empty(),
+            "unknown annotation should be a parse error"
+        );
+    }
+
     // ── Polymorphic actor calls (v0.3.0) ────────────────────────────────
 
     #[test]
@@ -1293,6 +1739,21 @@ mod tests {
         assert_eq!(a.type_args[1].name, "double");
     }
 
+    #[test]
+    fn actor_call_with_bool_type_arg() {
+        let s = parse_one_stmt("clock 1kHz t {\n  stdout<bool>()\n}");
+        let StatementKind::Task(t) = &s.kind else {
+            panic!("expected Task")
+        };
+        let TaskBody::Pipeline(p) = &t.body else {
+            panic!("expected Pipeline")
+        };
+        let PipeSource::ActorCall(a) = &p.lines[0].source else {
+            panic!("expected ActorCall")
+        };
+        assert_eq!(a.type_args[0].name, "bool");
+    }
+
     // ── bind_stmt ──
 
     #[test]
@@ -1330,6 +1791,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bind_stmt_tcp() {
+        let s = parse_one_stmt(r#"bind iq = tcp("10.0.0.2:9200", backlog=4, reconnect=500)"#);
+        let StatementKind::Bind(b) = &s.kind else {
+            panic!("expected Bind")
+        };
+        assert_eq!(b.endpoint.transport.name, "tcp");
+        assert_eq!(b.endpoint.args.len(), 3);
+        assert!(
+            matches!(&b.endpoint.args[0], BindArg::Positional(Scalar::StringLit(s, _)) if s == "10.0.0.2:9200")
+        );
+        assert!(
+            matches!(&b.endpoint.args[1], BindArg::Named(ident, Scalar::Number(n, _, true)) if ident.name == "backlog" && *n == 4.0)
+        );
+        assert!(
+            matches!(&b.endpoint.args[2], BindArg::Named(ident, _) if ident.name == "reconnect")
+        );
+    }
+
     #[test]
     fn bind_stmt_positional_only() {
         let s = parse_one_stmt(r#"bind x = udp("host:port")"#);
@@ -1358,6 +1838,59 @@ mod tests {
         assert!(matches!(&b.endpoint.args[1], BindArg::Named(ident, _) if ident.name == "chan"));
     }
 
+    #[test]
+    fn bind_stmt_when_guard() {
+        let s = parse_one_stmt(r#"@when(debug) bind dbg = udp("127.0.0.1:9200")"#);
+        let StatementKind::Bind(b) = &s.kind else {
+            panic!("expected Bind")
+        };
+        assert_eq!(b.name.name, "dbg");
+        assert_eq!(b.when, Some(BuildMode::Debug));
+
+        let s = parse_one_stmt(r#"bind x = udp("host:port")"#);
+        let StatementKind::Bind(b) = &s.kind else {
+            panic!("expected Bind")
+        };
+        assert_eq!(b.when, None);
+    }
+
+    #[test]
+    fn bind_stmt_when_unknown_mode() {
+        let (_, errs) = parse_all(r#"@when(profile) bind dbg = udp("127.0.0.1:9200")"#);
+        assert!(
+            !errs.is_empty(),
+            "unknown build mode should be a parse error"
+        );
+    }
+
+    #[test]
+    fn bind_stmt_declared_type() {
+        let s = parse_one_stmt(r#"bind iq: cfloat[N, 2] = udp("127.0.0.1:9100")"#);
+        let StatementKind::Bind(b) = &s.kind else {
+            panic!("expected Bind")
+        };
+        assert_eq!(b.name.name, "iq");
+        let ty = b.ty.as_ref().expect("declared type");
+        assert_eq!(ty.dtype.name, "cfloat");
+        let dims = &ty.shape.as_ref().expect("declared shape").dims;
+        assert!(matches!(&dims[0], ShapeDim::ConstRef(id) if id.name == "N"));
+        assert!(matches!(&dims[1], ShapeDim::Literal(2, _)));
+
+        let s = parse_one_stmt(r#"bind iq: int16 = udp("127.0.0.1:9100")"#);
+        let StatementKind::Bind(b) = &s.kind else {
+            panic!("expected Bind")
+        };
+        let ty = b.ty.as_ref().expect("declared type");
+        assert_eq!(ty.dtype.name, "int16");
+        assert!(ty.shape.is_none());
+    }
+
+    #[test]
+    fn bind_stmt_declared_type_rejects_unknown_dtype() {
+        let (_, errs) = parse_all(r#"bind iq: complex[4] = udp("127.0.0.1:9100")"#);
+        assert!(!errs.is_empty(), "unknown dtype should be a parse error");
+    }
+
     // ── shared_stmt (v0.4.8) ──
 
     #[test]
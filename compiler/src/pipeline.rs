@@ -13,7 +13,7 @@
 use std::time::Instant;
 
 use crate::analyze::AnalyzedProgram;
-use crate::ast::Program;
+use crate::ast::{Program, StatementKind};
 use crate::codegen::{CodegenOptions, GeneratedCode};
 use crate::diag::codes;
 use crate::diag::{DiagLevel, Diagnostic};
@@ -51,16 +51,51 @@ pub struct DownstreamArtifacts {
     pub generated: Option<GeneratedCode>,
 }
 
+/// Runtime ABI version the generated code is written against.
+///
+/// Must match `PIPIT_RUNTIME_ABI_VERSION` in `runtime/libpipit/include/pipit.h`;
+/// bump both together when generated code relies on a changed runtime layout
+/// or API.
+pub const RUNTIME_ABI_VERSION: u32 = 3;
+
 /// Provenance metadata for hermetic builds and cache-key use.
 ///
 /// `source_hash`: SHA-256 of the raw `.pdl` source text.
 /// `registry_fingerprint`: SHA-256 of canonical compact JSON from `Registry::canonical_json()`.
 /// `compiler_version`: crate version from `Cargo.toml`.
+/// `runtime_abi_version`: runtime ABI the generated code expects.
+/// `build_epoch`: normalized build stamp (seconds since the Unix epoch) for
+/// `--reproducible` builds; never wall-clock time.
+/// `memory`: static memory footprint for `--emit build-info`, when the
+/// program compiled through scheduling.
 #[derive(Debug, Clone)]
 pub struct Provenance {
     pub source_hash: [u8; 32],
     pub registry_fingerprint: [u8; 32],
     pub compiler_version: &'static str,
+    pub runtime_abi_version: u32,
+    pub build_epoch: Option<u64>,
+    pub memory: Option<MemoryFootprint>,
+}
+
+/// Static memory footprint of a compiled program, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// Inter-task ring buffers, from analysis.
+    pub inter_task_buffer_bytes: u64,
+    /// Intra-task edge buffers: scheduled tokens × element size. Element
+    /// types come from the producing actor; unknown ones count as `float`.
+    pub edge_buffer_bytes: u64,
+    /// Const arrays (emitted as `float` arrays).
+    pub const_bytes: u64,
+    /// Runtime param values.
+    pub param_bytes: u64,
+}
+
+impl MemoryFootprint {
+    pub fn total_bytes(&self) -> u64 {
+        self.inter_task_buffer_bytes + self.edge_buffer_bytes + self.const_bytes + self.param_bytes
+    }
 }
 
 impl Provenance {
@@ -76,11 +111,29 @@ impl Provenance {
 
     /// Serialize provenance as a JSON string for `--emit build-info`.
     pub fn to_json(&self) -> String {
+        let build_epoch = match self.build_epoch {
+            Some(epoch) => format!(",\n  \"build_epoch\": {}", epoch),
+            None => String::new(),
+        };
+        let memory = match &self.memory {
+            Some(m) => format!(
+                ",\n  \"memory\": {{\n    \"inter_task_buffer_bytes\": {},\n    \"edge_buffer_bytes\": {},\n    \"const_bytes\": {},\n    \"param_bytes\": {},\n    \"total_bytes\": {}\n  }}",
+                m.inter_task_buffer_bytes,
+                m.edge_buffer_bytes,
+                m.const_bytes,
+                m.param_bytes,
+                m.total_bytes(),
+            ),
+            None => ",\n  \"memory\": null".to_string(),
+        };
         format!(
-            "{{\n  \"source_hash\": \"{}\",\n  \"registry_fingerprint\": \"{}\",\n  \"manifest_schema_version\": 1,\n  \"compiler_version\": \"{}\"\n}}\n",
+            "{{\n  \"source_hash\": \"{}\",\n  \"registry_fingerprint\": \"{}\",\n  \"manifest_schema_version\": 1,\n  \"compiler_version\": \"{}\",\n  \"runtime_abi_version\": {}{}{}\n}}\n",
             self.source_hash_hex(),
             self.registry_fingerprint_hex(),
             self.compiler_version,
+            self.runtime_abi_version,
+            build_epoch,
+            memory,
         )
     }
 }
@@ -125,6 +178,9 @@ pub fn compute_provenance(source: &str, registry: &Registry) -> Provenance {
         source_hash,
         registry_fingerprint,
         compiler_version: env!("CARGO_PKG_VERSION"),
+        runtime_abi_version: RUNTIME_ABI_VERSION,
+        build_epoch: None,
+        memory: None,
     }
 }
 
@@ -162,6 +218,112 @@ impl CompilationState {
             provenance: None,
         }
     }
+
+    /// Stable content hash of the compiled pipeline (64 hex characters).
+    ///
+    /// Hashes the canonical `.pdl` form of the HIR (defines inlined, `spawn`
+    /// expanded, comments and whitespace normalized away), the registry
+    /// fingerprint, and `CodegenOptions::canonical_json`, so every flag that
+    /// changes the generated C++ changes the hash. Unlike `Provenance::source_hash`,
+    /// cosmetic source edits leave it unchanged. Returns `None` until
+    /// `BuildHir` has run.
+    pub fn pipeline_hash(&self, options: &CodegenOptions) -> Option<String> {
+        use sha2::{Digest, Sha256};
+
+        let hir = self.upstream.hir.as_ref()?;
+        let resolved = self.upstream.resolved.as_ref()?;
+        let mut hasher = Sha256::new();
+        hasher.update(hir.to_pdl(resolved).as_bytes());
+        hasher.update([0]);
+        hasher.update(self.upstream.registry.canonical_json().as_bytes());
+        hasher.update([0]);
+        hasher.update(options.canonical_json().as_bytes());
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&hasher.finalize());
+        Some(bytes_to_hex(&hash))
+    }
+
+    /// Static memory footprint: inter-task buffers from analysis, intra-task
+    /// edge buffers from the schedule, const arrays, and runtime params.
+    /// Returns `None` until `Schedule` has run.
+    pub fn memory_footprint(&self) -> Option<MemoryFootprint> {
+        use crate::ast::Value;
+        use crate::graph::{NodeKind, Subgraph};
+        use crate::schedule::TaskSchedule;
+
+        let analysis = self.downstream.analysis.as_ref()?;
+        let schedule = self.downstream.schedule.as_ref()?;
+        let graph = self.upstream.graph.as_ref()?;
+        let hir = self.upstream.hir.as_ref()?;
+        let lowered = self.upstream.lowered.as_ref()?;
+        let registry = &self.upstream.registry;
+
+        // Element size of the tokens a node emits, through forks and probes.
+        let elem_bytes = |sub: &Subgraph, mut id: crate::graph::NodeId| -> u64 {
+            for _ in 0..=sub.nodes.len() {
+                let Some(node) = sub.nodes.iter().find(|n| n.id == id) else {
+                    break;
+                };
+                match &node.kind {
+                    NodeKind::Actor { name, call_id, .. } => {
+                        return lowered
+                            .concrete_actors
+                            .get(call_id)
+                            .or_else(|| registry.lookup(name))
+                            .and_then(|m| m.out_type.as_concrete())
+                            .map_or(4, crate::analyze::type_size_bytes);
+                    }
+                    NodeKind::Fork { .. } | NodeKind::Probe { .. } => {
+                        match sub.edges.iter().find(|e| e.target == id) {
+                            Some(edge) => id = edge.source,
+                            None => break,
+                        }
+                    }
+                    _ => break,
+                }
+            }
+            4
+        };
+
+        let mut edge_buffer_bytes = 0;
+        for (task_name, meta) in &schedule.tasks {
+            let Some(task_graph) = graph.tasks.get(task_name) else {
+                continue;
+            };
+            let scheds: Vec<_> = match &meta.schedule {
+                TaskSchedule::Pipeline(sched) => vec![sched],
+                TaskSchedule::Modal { control, modes } => std::iter::once(control)
+                    .chain(modes.iter().map(|(_, sched)| sched))
+                    .collect(),
+            };
+            let subs = crate::subgraph_index::subgraphs_of(task_graph);
+            for (sub, sched) in subs.into_iter().zip(scheds) {
+                for (&(src, _), &tokens) in &sched.edge_buffers {
+                    edge_buffer_bytes += tokens as u64 * elem_bytes(sub, src);
+                }
+            }
+        }
+
+        let const_bytes = hir
+            .consts
+            .iter()
+            .map(|c| match &c.value {
+                Value::Array(elems, _) => elems.len() as u64 * 4,
+                Value::Scalar(_) => 0,
+            })
+            .sum();
+        let param_bytes = crate::thir::resolve_param_cpp_types(hir, lowered, registry, graph)
+            .values()
+            .map(|t| if *t == "double" { 8 } else { 4 })
+            .sum();
+
+        Some(MemoryFootprint {
+            inter_task_buffer_bytes: analysis.inter_task_buffers.values().sum(),
+            edge_buffer_bytes,
+            const_bytes,
+            param_bytes,
+        })
+    }
 }
 
 // ── Error type ─────────────────────────────────────────────────────────────
@@ -268,6 +430,22 @@ pub fn run_pipeline(
 ) -> Result<(), PipelineError> {
     let passes = required_passes(terminal);
 
+    // Unused const/param lint sees the source as written, before `@when`
+    // filtering and spawn expansion drop references; reported with Resolve.
+    let mut unused_decls = crate::resolve::unused_decl_warnings(&state.upstream.program);
+
+    // Build-mode guards: drop `@when(...)` binds inactive in this build so
+    // later passes (and the interface manifest) never see them.
+    let release = codegen_options.release;
+    state
+        .upstream
+        .program
+        .statements
+        .retain(|stmt| match &stmt.kind {
+            StatementKind::Bind(b) => b.when.is_none_or(|mode| mode.is_active(release)),
+            _ => true,
+        });
+
     // Spawn expansion: AST → AST pre-pass (before name resolution).
     let spawn_result = crate::spawn::expand_spawns(&state.upstream.program);
     state.upstream.program = spawn_result.program;
@@ -302,7 +480,8 @@ pub fn run_pipeline(
                 let result =
                     crate::resolve::resolve(&state.upstream.program, &state.upstream.registry);
                 let elapsed = t.elapsed();
-                let diags = result.diagnostics;
+                let mut diags = result.diagnostics;
+                diags.append(&mut unused_decls);
                 state.upstream.resolved = Some(result.resolved);
                 state.upstream.id_alloc = Some(result.id_alloc);
                 finish_pass(
@@ -374,6 +553,36 @@ pub fn run_pipeline(
             }
             PassId::Lower => {
                 let t = Instant::now();
+                let rewrites = crate::lower::apply_peepholes(
+                    state.upstream.hir.as_mut().unwrap(),
+                    state.upstream.typed.as_ref().unwrap(),
+                    &state.upstream.registry,
+                );
+                if verbose {
+                    for r in &rewrites {
+                        eprintln!(
+                            "pcc: note: rewrote {} into {} in task '{}'",
+                            r.rule.pattern.join(" | "),
+                            r.rule.replacement,
+                            r.task
+                        );
+                    }
+                }
+                if release {
+                    let fusions = crate::lower::fuse_constant_muls(
+                        state.upstream.hir.as_mut().unwrap(),
+                        state.upstream.typed.as_ref().unwrap(),
+                        &state.upstream.registry,
+                    );
+                    if verbose {
+                        for f in &fusions {
+                            eprintln!(
+                                "pcc: note: fused mul({}) | mul({}) into mul({}) in task '{}'",
+                                f.scales.0, f.scales.1, f.product, f.task
+                            );
+                        }
+                    }
+                }
                 let result = crate::lower::lower_and_verify(
                     state.upstream.hir.as_ref().unwrap(),
                     state.upstream.resolved.as_ref().unwrap(),
@@ -441,7 +650,7 @@ fn run_thir_and_downstream(
     on_pass_complete: &mut impl FnMut(PassId, &[Diagnostic]),
 ) -> Result<(), PipelineError> {
     // Build ThirContext — borrows from upstream (immutable).
-    let thir = crate::thir::build_thir_context(
+    let mut thir = crate::thir::build_thir_context(
         state.upstream.hir.as_ref().unwrap(),
         state.upstream.resolved.as_ref().unwrap(),
         state.upstream.typed.as_ref().unwrap(),
@@ -449,6 +658,12 @@ fn run_thir_and_downstream(
         &state.upstream.registry,
         state.upstream.graph.as_ref().unwrap(),
     );
+    if let Some(bytes) = codegen_options.platform_memory {
+        thir.set_platform_memory(bytes);
+    }
+    if let Some(bytes) = codegen_options.max_memory {
+        thir.override_mem_bytes(bytes);
+    }
 
     if passes.contains(&PassId::Analyze) {
         let t = Instant::now();
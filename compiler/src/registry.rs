@@ -22,6 +22,9 @@ pub enum PipitType {
     Double,
     Cfloat,
     Cdouble,
+    /// One-byte truth value (`bool` in C++); not part of the numeric
+    /// widening chain.
+    Bool,
     Void,
 }
 
@@ -93,6 +96,7 @@ impl fmt::Display for PipitType {
             PipitType::Double => write!(f, "double"),
             PipitType::Cfloat => write!(f, "cfloat"),
             PipitType::Cdouble => write!(f, "cdouble"),
+            PipitType::Bool => write!(f, "bool"),
             PipitType::Void => write!(f, "void"),
         }
     }
@@ -188,12 +192,49 @@ pub enum ParamType {
     SpanTypeParam(String),
 }
 
+/// PDL-facing type names, as shown by `pcc actor-help` and diagnostics.
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Int => write!(f, "int"),
+            ParamType::Float => write!(f, "float"),
+            ParamType::Double => write!(f, "double"),
+            ParamType::SpanFloat => write!(f, "float[]"),
+            ParamType::SpanChar => write!(f, "string"),
+            ParamType::TypeParam(t) => write!(f, "{}", t),
+            ParamType::SpanTypeParam(t) => write!(f, "{}[]", t),
+        }
+    }
+}
+
 /// A single actor parameter declaration.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActorParam {
     pub kind: ParamKind,
     pub param_type: ParamType,
     pub name: String,
+    /// Scalar literal from `PARAM(int, N = 256)`, used when a call omits the
+    /// argument. Stored without a C++ `f` suffix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// Per-instance scratch memory declared with `SCRATCH(type, count)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActorScratch {
+    pub elem_type: PipitType,
+    /// Element count: a literal, or the name of one of the actor's PARAMs.
+    pub count: TokenCount,
+}
+
+/// Fixed sample-rate ratio declared with `RATE_CHANGE(up, down)`: each
+/// firing consumes `down` tokens and produces `up`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateChange {
+    /// Tokens produced per firing: a literal, or the name of a PARAM.
+    pub up: TokenCount,
+    /// Tokens consumed per firing: a literal, or the name of a PARAM.
+    pub down: TokenCount,
 }
 
 /// Metadata extracted from one ACTOR() macro invocation.
@@ -209,6 +250,39 @@ pub struct ActorMeta {
     pub out_count: TokenCount,
     pub out_shape: PortShape,
     pub params: Vec<ActorParam>,
+    /// Declared with `TERMINATES`: a source that ends the pipeline when its
+    /// input is exhausted (e.g. `binread` at EOF). Omitted from manifests
+    /// when false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub terminates: bool,
+    /// Declared with `PURE`: output depends only on input and arguments, so
+    /// a firing with constant inputs may be evaluated once and cached.
+    /// Omitted from manifests when false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pure: bool,
+    /// Declared with `INLINE`: a tiny actor whose call overhead dominates;
+    /// codegen fires it through a forced-inline wrapper. Omitted from
+    /// manifests when false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub inline: bool,
+    /// `SCRATCH(type, count)`: per-instance persistent work memory, allocated
+    /// by codegen and passed as the last aggregate member. Omitted from
+    /// manifests when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scratch: Option<ActorScratch>,
+    /// `RATE_CHANGE(up, down)`: per-firing port rates that analysis uses in
+    /// place of the port shapes. Omitted from manifests when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_change: Option<RateChange>,
+    /// `ALIAS(old_name)` entries: additional names that `Registry::lookup`
+    /// resolves to this actor. Omitted from manifests when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Leading `///` or `/** */` doc comment above the ACTOR() definition.
+    /// Not serialized: manifests and registry fingerprints must not change
+    /// when only documentation is edited.
+    #[serde(skip)]
+    pub doc: Option<String>,
 }
 
 impl ActorMeta {
@@ -216,6 +290,40 @@ impl ActorMeta {
     pub fn is_polymorphic(&self) -> bool {
         !self.type_params.is_empty()
     }
+
+    /// `@param <name> <description>` entries from the doc comment.
+    pub fn doc_param(&self, name: &str) -> Option<&str> {
+        self.doc_tags("@param").find_map(|text| {
+            let (param, desc) = text.split_once(char::is_whitespace)?;
+            (param == name).then(|| desc.trim())
+        })
+    }
+
+    /// Text of the `@deprecated` tag, if the actor is marked deprecated.
+    /// An empty string means deprecated without a reason.
+    pub fn deprecation(&self) -> Option<&str> {
+        self.doc_tags("@deprecated").next()
+    }
+
+    /// Iterate over the text following each occurrence of a doc tag.
+    fn doc_tags<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.doc
+            .iter()
+            .flat_map(|d| d.lines())
+            .filter_map(move |line| {
+                let rest = line.trim().strip_prefix(tag)?;
+                (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim())
+            })
+    }
+}
+
+/// Outcome of compiling one actor body in isolation.
+#[derive(Debug, Clone)]
+pub struct ActorValidation {
+    pub name: String,
+    pub header: PathBuf,
+    /// Compiler diagnostics when the actor failed to compile.
+    pub error: Option<String>,
 }
 
 /// Errors that can occur during registry loading.
@@ -235,6 +343,13 @@ pub enum RegistryError {
         first: PathBuf,
         second: PathBuf,
     },
+    /// An `ALIAS()` name equal to another actor's name or alias.
+    AliasCollision {
+        alias: String,
+        actor: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
     PreprocessorError {
         message: String,
         stderr: String,
@@ -267,6 +382,22 @@ impl fmt::Display for RegistryError {
                     second.display()
                 )
             }
+            RegistryError::AliasCollision {
+                alias,
+                actor,
+                first,
+                second,
+            } => {
+                write!(
+                    f,
+                    "alias '{}' of actor '{}' collides with an existing actor or alias: \
+                     first defined in {}, redefined in {}",
+                    alias,
+                    actor,
+                    first.display(),
+                    second.display()
+                )
+            }
             RegistryError::PreprocessorError { message, stderr } => {
                 if stderr.is_empty() {
                     write!(f, "preprocessor: {}", message)
@@ -286,6 +417,8 @@ impl std::error::Error for RegistryError {}
 #[derive(Clone)]
 pub struct Registry {
     actors: HashMap<String, (ActorMeta, PathBuf)>,
+    /// Alias name → canonical actor name, from `ALIAS()` clauses.
+    aliases: HashMap<String, String>,
 }
 
 impl Default for Registry {
@@ -298,6 +431,7 @@ impl Registry {
     pub fn new() -> Self {
         Registry {
             actors: HashMap::new(),
+            aliases: HashMap::new(),
         }
     }
 
@@ -312,15 +446,7 @@ impl Registry {
         let count = actors.len();
 
         for actor in actors {
-            if let Some((_, first_path)) = self.actors.get(&actor.name) {
-                return Err(RegistryError::DuplicateActor {
-                    name: actor.name.clone(),
-                    first: first_path.clone(),
-                    second: path.to_path_buf(),
-                });
-            }
-            self.actors
-                .insert(actor.name.clone(), (actor, path.to_path_buf()));
+            self.register(actor, path)?;
         }
 
         Ok(count)
@@ -334,17 +460,84 @@ impl Registry {
     /// Insert an actor directly (for tests). Uses a synthetic path.
     pub fn insert(&mut self, meta: ActorMeta) {
         let name = meta.name.clone();
+        for alias in &meta.aliases {
+            self.aliases.insert(alias.clone(), name.clone());
+        }
         self.actors.insert(name, (meta, PathBuf::from("<test>")));
     }
 
+    /// Look up an actor by name or by one of its `ALIAS()` names.
     pub fn lookup(&self, name: &str) -> Option<&ActorMeta> {
+        let name = self.aliases.get(name).map_or(name, String::as_str);
         self.actors.get(name).map(|(meta, _)| meta)
     }
 
+    /// Registered actor names within edit distance 2 of `name`, for
+    /// "did you mean" hints. At most 3, nearest first, ties by name.
+    pub fn lookup_fuzzy(&self, name: &str) -> Vec<&str> {
+        let mut candidates: Vec<(usize, &str)> = self
+            .actors
+            .keys()
+            .map(|actor| (levenshtein(name, actor), actor.as_str()))
+            .filter(|&(d, _)| d <= 2)
+            .collect();
+        candidates.sort_unstable();
+        candidates.into_iter().take(3).map(|(_, n)| n).collect()
+    }
+
     pub fn actors(&self) -> impl Iterator<Item = &ActorMeta> {
         self.actors.values().map(|(meta, _)| meta)
     }
 
+    /// File an actor (or alias) was loaded from: its header, or the
+    /// manifest for registries built with `load_manifest`.
+    pub fn source_header(&self, name: &str) -> Option<&Path> {
+        let name = self.aliases.get(name).map_or(name, String::as_str);
+        self.actors.get(name).map(|(_, path)| path.as_path())
+    }
+
+    /// Compile every actor body in isolation (`--emit manifest --validate`).
+    ///
+    /// Each actor gets its own translation unit that includes only its
+    /// header and instantiates `Actor_<name>` (type parameters as `float`)
+    /// with a call on typed null buffers, checked with `-fsyntax-only`.
+    /// Results are sorted by actor name.
+    pub fn validate_actors(&self, cc: &str, include_dirs: &[PathBuf]) -> Vec<ActorValidation> {
+        let mut names: Vec<&String> = self.actors.keys().collect();
+        names.sort();
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut results: Vec<ActorValidation> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers.min(names.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                            let Some(name) = names.get(i) else {
+                                break;
+                            };
+                            let (meta, header) = &self.actors[*name];
+                            let tu = build_validation_tu(meta, header);
+                            done.push(ActorValidation {
+                                name: meta.name.clone(),
+                                header: header.clone(),
+                                error: compile_syntax_only(cc, &tu, include_dirs).err(),
+                            });
+                        }
+                        done
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("validation worker panicked"))
+                .collect()
+        });
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        results
+    }
+
     pub fn len(&self) -> usize {
         self.actors.len()
     }
@@ -354,12 +547,62 @@ impl Registry {
     }
 
     /// Overlay entries from another registry.
-    /// Existing names are replaced by entries from `other`.
-    pub fn overlay_from(&mut self, other: &Registry) {
-        for (name, (meta, path)) in &other.actors {
-            self.actors
-                .insert(name.clone(), (meta.clone(), path.clone()));
+    /// Existing names are replaced by entries from `other`; an alias that
+    /// collides with a surviving actor name or alias is an error.
+    pub fn overlay_from(&mut self, other: &Registry) -> Result<(), RegistryError> {
+        let mut names: Vec<&String> = other.actors.keys().collect();
+        names.sort();
+        for name in &names {
+            if let Some((replaced, _)) = self.actors.remove(*name) {
+                for alias in &replaced.aliases {
+                    self.aliases.remove(alias);
+                }
+            }
+        }
+        for name in names {
+            let (meta, path) = &other.actors[name];
+            self.register(meta.clone(), path)?;
+        }
+        Ok(())
+    }
+
+    /// Add an actor, rejecting a name or alias already taken by another entry.
+    fn register(&mut self, meta: ActorMeta, path: &Path) -> Result<(), RegistryError> {
+        if let Some((_, first_path)) = self.actors.get(&meta.name) {
+            return Err(RegistryError::DuplicateActor {
+                name: meta.name.clone(),
+                first: first_path.clone(),
+                second: path.to_path_buf(),
+            });
         }
+        if let Some(owner) = self.aliases.get(&meta.name) {
+            return Err(RegistryError::AliasCollision {
+                alias: meta.name.clone(),
+                actor: owner.clone(),
+                first: self.actors[owner].1.clone(),
+                second: path.to_path_buf(),
+            });
+        }
+        for alias in &meta.aliases {
+            let taken_by = self
+                .actors
+                .get(alias)
+                .or_else(|| self.aliases.get(alias).map(|owner| &self.actors[owner]));
+            if let Some((_, first_path)) = taken_by {
+                return Err(RegistryError::AliasCollision {
+                    alias: alias.clone(),
+                    actor: meta.name.clone(),
+                    first: first_path.clone(),
+                    second: path.to_path_buf(),
+                });
+            }
+        }
+        for alias in &meta.aliases {
+            self.aliases.insert(alias.clone(), meta.name.clone());
+        }
+        self.actors
+            .insert(meta.name.clone(), (meta, path.to_path_buf()));
+        Ok(())
     }
     /// Load actors from a JSON manifest file (`actors.meta.json` schema v1).
     pub fn load_manifest(&mut self, path: &Path) -> Result<usize, RegistryError> {
@@ -388,15 +631,7 @@ impl Registry {
 
         let count = manifest.actors.len();
         for actor in manifest.actors {
-            if self.actors.contains_key(&actor.name) {
-                return Err(RegistryError::DuplicateActor {
-                    name: actor.name.clone(),
-                    first: path.to_path_buf(),
-                    second: path.to_path_buf(),
-                });
-            }
-            self.actors
-                .insert(actor.name.clone(), (actor, path.to_path_buf()));
+            self.register(actor, path)?;
         }
 
         Ok(count)
@@ -431,6 +666,264 @@ impl Registry {
     }
 }
 
+/// Levenshtein edit distance between two names (single-row DP).
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let next = (diag + usize::from(ca != cb))
+                .min(row[j] + 1)
+                .min(row[j + 1] + 1);
+            diag = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+// ── Registry diff ──────────────────────────────────────────────────────────
+
+/// One field change of an actor present in both registries.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActorChange {
+    InType {
+        actor: String,
+        old: TypeExpr,
+        new: TypeExpr,
+    },
+    OutType {
+        actor: String,
+        old: TypeExpr,
+        new: TypeExpr,
+    },
+    InShape {
+        actor: String,
+        old: PortShape,
+        new: PortShape,
+    },
+    OutShape {
+        actor: String,
+        old: PortShape,
+        new: PortShape,
+    },
+    Params {
+        actor: String,
+        old: Vec<ActorParam>,
+        new: Vec<ActorParam>,
+    },
+}
+
+impl ActorChange {
+    /// Whether `.pdl` sources written against the old actor may stop
+    /// compiling: a port type change, or a param list change other than
+    /// adding or removing trailing params.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            ActorChange::InType { .. } | ActorChange::OutType { .. } => true,
+            ActorChange::InShape { .. } | ActorChange::OutShape { .. } => false,
+            ActorChange::Params { old, new, .. } => {
+                let common = old.len().min(new.len());
+                old[..common] != new[..common]
+            }
+        }
+    }
+
+    /// Name of the actor whose field changed.
+    pub fn actor(&self) -> &str {
+        match self {
+            ActorChange::InType { actor, .. }
+            | ActorChange::OutType { actor, .. }
+            | ActorChange::InShape { actor, .. }
+            | ActorChange::OutShape { actor, .. }
+            | ActorChange::Params { actor, .. } => actor,
+        }
+    }
+
+    /// Manifest name of the changed field.
+    pub fn field(&self) -> &'static str {
+        match self {
+            ActorChange::InType { .. } => "in_type",
+            ActorChange::OutType { .. } => "out_type",
+            ActorChange::InShape { .. } => "in_shape",
+            ActorChange::OutShape { .. } => "out_shape",
+            ActorChange::Params { .. } => "params",
+        }
+    }
+
+    /// The old and new field values, rendered in header notation.
+    pub fn values(&self) -> (String, String) {
+        fn shape(s: &PortShape) -> String {
+            let dims: Vec<String> = s
+                .dims
+                .iter()
+                .map(|d| match d {
+                    TokenCount::Literal(n) => n.to_string(),
+                    TokenCount::Symbolic(sym) => sym.clone(),
+                })
+                .collect();
+            format!("[{}]", dims.join(", "))
+        }
+        fn params(ps: &[ActorParam]) -> String {
+            let items: Vec<String> = ps
+                .iter()
+                .map(|p| {
+                    let kind = match p.kind {
+                        ParamKind::Param => "PARAM",
+                        ParamKind::RuntimeParam => "RUNTIME_PARAM",
+                    };
+                    let ty = match &p.param_type {
+                        ParamType::Int => "int".to_string(),
+                        ParamType::Float => "float".to_string(),
+                        ParamType::Double => "double".to_string(),
+                        ParamType::SpanFloat => "std::span<const float>".to_string(),
+                        ParamType::SpanChar => "std::span<const char>".to_string(),
+                        ParamType::TypeParam(t) => t.clone(),
+                        ParamType::SpanTypeParam(t) => format!("std::span<const {}>", t),
+                    };
+                    format!("{}({}, {})", kind, ty, p.name)
+                })
+                .collect();
+            format!("({})", items.join(", "))
+        }
+        match self {
+            ActorChange::InType { old, new, .. } | ActorChange::OutType { old, new, .. } => {
+                (old.to_string(), new.to_string())
+            }
+            ActorChange::InShape { old, new, .. } | ActorChange::OutShape { old, new, .. } => {
+                (shape(old), shape(new))
+            }
+            ActorChange::Params { old, new, .. } => (params(old), params(new)),
+        }
+    }
+}
+
+impl fmt::Display for ActorChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (old, new) = self.values();
+        write!(
+            f,
+            "actor '{}' {} changed: {} -> {}",
+            self.actor(),
+            self.field(),
+            old,
+            new
+        )
+    }
+}
+
+/// Differences from one registry to another (`Registry::diff`).
+///
+/// Actor names are sorted; `changed` follows actor name order, then field
+/// order (in_type, out_type, in_shape, out_shape, params).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegistryDiff {
+    /// Actors only in the newer registry.
+    pub added: Vec<String>,
+    /// Actors only in the older registry.
+    pub removed: Vec<String>,
+    /// Field changes of actors present in both.
+    pub changed: Vec<ActorChange>,
+}
+
+impl RegistryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// True when an actor was removed or any field change is breaking
+    /// (see `ActorChange::is_breaking`).
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || self.changed.iter().any(ActorChange::is_breaking)
+    }
+}
+
+impl fmt::Display for RegistryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in &self.removed {
+            writeln!(f, "breaking: actor '{}' removed", name)?;
+        }
+        for change in &self.changed {
+            let class = if change.is_breaking() {
+                "breaking"
+            } else {
+                "compatible"
+            };
+            writeln!(f, "{}: {}", class, change)?;
+        }
+        for name in &self.added {
+            writeln!(f, "compatible: actor '{}' added", name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Registry {
+    /// Compare this (older) registry against `other` (newer) actor-by-name.
+    ///
+    /// Aliases, documentation, and flags such as `PURE` are not compared.
+    pub fn diff(&self, other: &Registry) -> RegistryDiff {
+        let mut names: Vec<&String> = self.actors.keys().chain(other.actors.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        let mut diff = RegistryDiff::default();
+        for name in names {
+            let (old, new) = match (self.actors.get(name), other.actors.get(name)) {
+                (Some((old, _)), Some((new, _))) => (old, new),
+                (Some(_), None) => {
+                    diff.removed.push(name.clone());
+                    continue;
+                }
+                (None, Some(_)) => {
+                    diff.added.push(name.clone());
+                    continue;
+                }
+                (None, None) => unreachable!(),
+            };
+            let actor = name.clone();
+            if old.in_type != new.in_type {
+                diff.changed.push(ActorChange::InType {
+                    actor: actor.clone(),
+                    old: old.in_type.clone(),
+                    new: new.in_type.clone(),
+                });
+            }
+            if old.out_type != new.out_type {
+                diff.changed.push(ActorChange::OutType {
+                    actor: actor.clone(),
+                    old: old.out_type.clone(),
+                    new: new.out_type.clone(),
+                });
+            }
+            if old.in_shape != new.in_shape {
+                diff.changed.push(ActorChange::InShape {
+                    actor: actor.clone(),
+                    old: old.in_shape.clone(),
+                    new: new.in_shape.clone(),
+                });
+            }
+            if old.out_shape != new.out_shape {
+                diff.changed.push(ActorChange::OutShape {
+                    actor: actor.clone(),
+                    old: old.out_shape.clone(),
+                    new: new.out_shape.clone(),
+                });
+            }
+            if old.params != new.params {
+                diff.changed.push(ActorChange::Params {
+                    actor,
+                    old: old.params.clone(),
+                    new: new.params.clone(),
+                });
+            }
+        }
+        diff
+    }
+}
+
 // ── Manifest (actors.meta.json) ──────────────────────────────────────────────
 
 /// Top-level structure of `actors.meta.json` (schema v1).
@@ -511,9 +1004,116 @@ fn scan_actors(source: &str, file: &Path) -> Result<Vec<ActorMeta>, RegistryErro
         }
     }
 
+    let docs = extract_doc_comments(source);
+    for actor in &mut results {
+        actor.doc = docs.get(&actor.name).cloned();
+    }
+
     Ok(results)
 }
 
+/// Collect leading doc comments for every `ACTOR(name, ...)` in a header.
+///
+/// A doc comment is the contiguous run of `///` lines, or a `/** ... */`
+/// block, directly above the ACTOR line (or above its `template <...>` line).
+/// Returns actor name → comment text with comment markers removed.
+fn extract_doc_comments(source: &str) -> HashMap<String, String> {
+    let stripped = strip_comments(source);
+    let lines: Vec<&str> = source.lines().collect();
+    let mut docs = HashMap::new();
+
+    for (idx, line) in stripped.lines().enumerate() {
+        let Some(pos) = line.find("ACTOR(") else {
+            continue;
+        };
+        if pos > 0 {
+            let prev = line.as_bytes()[pos - 1];
+            if prev.is_ascii_alphanumeric() || prev == b'_' {
+                continue;
+            }
+        }
+        let prefix = line[..pos].trim();
+        if prefix.starts_with('#') {
+            continue;
+        }
+        let name: String = line[pos + 6..]
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        if name.is_empty() {
+            continue;
+        }
+
+        // Skip a `template <...>` line directly above the ACTOR line.
+        let mut end = idx;
+        if prefix.is_empty() && end > 0 && stripped_line_is_template(lines[end - 1]) {
+            end -= 1;
+        }
+        if let Some(doc) = doc_comment_above(&lines, end) {
+            docs.insert(name, doc);
+        }
+    }
+    docs
+}
+
+fn stripped_line_is_template(line: &str) -> bool {
+    let t = line.trim();
+    t.starts_with("template") && t.ends_with('>')
+}
+
+/// Doc comment ending on the line just above `lines[end]`, if any.
+fn doc_comment_above(lines: &[&str], end: usize) -> Option<String> {
+    let mut doc: Vec<String> = Vec::new();
+    let mut i = end;
+    let last = lines.get(end.checked_sub(1)?)?.trim();
+
+    if last.starts_with("///") {
+        while i > 0 {
+            let Some(text) = lines[i - 1].trim().strip_prefix("///") else {
+                break;
+            };
+            doc.push(
+                text.strip_prefix(' ')
+                    .unwrap_or(text)
+                    .trim_end()
+                    .to_string(),
+            );
+            i -= 1;
+        }
+    } else if last.ends_with("*/") {
+        loop {
+            if i == 0 {
+                return None;
+            }
+            i -= 1;
+            let raw = lines[i].trim();
+            let text = raw.trim_end_matches("*/").trim_end();
+            if let Some(first) = text.strip_prefix("/**") {
+                doc.push(first.trim().to_string());
+                break;
+            }
+            if raw.starts_with("/*") {
+                // Plain block comment, not documentation.
+                return None;
+            }
+            let text = text.strip_prefix('*').unwrap_or(text);
+            doc.push(text.strip_prefix(' ').unwrap_or(text).to_string());
+        }
+    } else {
+        return None;
+    }
+
+    doc.reverse();
+    while doc.first().is_some_and(|l| l.is_empty()) {
+        doc.remove(0);
+    }
+    while doc.last().is_some_and(|l| l.is_empty()) {
+        doc.pop();
+    }
+    (!doc.is_empty()).then(|| doc.join("\n"))
+}
+
 /// Extract template type parameters from text preceding an ACTOR() call.
 /// Matches patterns like `template <typename T>` or `template <typename T, typename U>`.
 /// Returns an empty vec if no template declaration is found.
@@ -668,6 +1268,253 @@ fn split_top_level_commas(s: &str) -> Vec<&str> {
     parts
 }
 
+/// Actor property marking a source that terminates the pipeline on exhaustion.
+const TERMINATES_SPEC: &str = "TERMINATES";
+
+/// Actor property marking a stateless actor (no state, I/O, or clock reads).
+const PURE_SPEC: &str = "PURE";
+
+/// Actor property asking codegen to force-inline the actor's firings.
+const INLINE_SPEC: &str = "INLINE";
+
+/// Trailing ACTOR() specs: parameters and actor properties, in declaration order.
+#[derive(Default)]
+struct ActorSpecs {
+    params: Vec<ActorParam>,
+    terminates: bool,
+    pure: bool,
+    inline: bool,
+    scratch: Option<ActorScratch>,
+    rate_change: Option<RateChange>,
+    aliases: Vec<String>,
+}
+
+impl ActorSpecs {
+    fn push(
+        &mut self,
+        spec: &str,
+        type_params: &[String],
+        file: &Path,
+        line: usize,
+    ) -> Result<(), RegistryError> {
+        let error = |message: String| RegistryError::ParseError {
+            file: file.to_path_buf(),
+            line,
+            message,
+        };
+        if spec == TERMINATES_SPEC {
+            self.terminates = true;
+            return Ok(());
+        }
+        if spec == PURE_SPEC {
+            self.pure = true;
+            return Ok(());
+        }
+        if spec == INLINE_SPEC {
+            self.inline = true;
+            return Ok(());
+        }
+        if let Some(rest) = spec.strip_prefix("ALIAS(") {
+            // Aliases add no struct member, so they may appear anywhere.
+            let alias = rest
+                .strip_suffix(')')
+                .map(str::trim)
+                .filter(|a| {
+                    !a.is_empty() && a.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                })
+                .ok_or_else(|| error(format!("expected ALIAS(name), found '{}'", spec)))?;
+            if self.aliases.iter().any(|a| a == alias) {
+                return Err(error(format!("duplicate ALIAS({})", alias)));
+            }
+            self.aliases.push(alias.to_string());
+            return Ok(());
+        }
+        if let Some(rest) = spec.strip_prefix("RATE_CHANGE(") {
+            // Like ALIAS, adds no struct member and may appear anywhere.
+            if self.rate_change.is_some() {
+                return Err(error("at most one RATE_CHANGE() per actor".to_string()));
+            }
+            let parts = rest
+                .strip_suffix(')')
+                .map(split_top_level_commas)
+                .filter(|parts| parts.len() == 2)
+                .ok_or_else(|| {
+                    error(format!("expected RATE_CHANGE(up, down), found '{}'", spec))
+                })?;
+            let [up, down] = [parts[0], parts[1]].map(|f| parse_token_count(f.trim()));
+            if [&up, &down].contains(&&TokenCount::Literal(0)) {
+                return Err(error(format!(
+                    "RATE_CHANGE() factors must be positive, found '{}'",
+                    spec
+                )));
+            }
+            self.rate_change = Some(RateChange { up, down });
+            return Ok(());
+        }
+        if let Some(rest) = spec.strip_prefix("SCRATCH(") {
+            if self.scratch.is_some() {
+                return Err(error("at most one SCRATCH() per actor".to_string()));
+            }
+            let parts = rest
+                .strip_suffix(')')
+                .map(split_top_level_commas)
+                .filter(|parts| parts.len() == 2)
+                .ok_or_else(|| error(format!("expected SCRATCH(type, count), found '{}'", spec)))?;
+            self.scratch = Some(ActorScratch {
+                elem_type: parse_pipit_type(parts[0].trim(), file, line)?,
+                count: parse_token_count(parts[1].trim()),
+            });
+            return Ok(());
+        }
+        if self.scratch.is_some() {
+            // The scratch pointer is initialized after all params.
+            return Err(error(format!(
+                "'{}' must precede SCRATCH() (scratch is the last member)",
+                spec
+            )));
+        }
+        let param = parse_param_spec(spec, type_params, file, line)?;
+        if param.default.is_none() {
+            if let Some(prev) = self.params.iter().rev().find(|p| p.default.is_some()) {
+                return Err(error(format!(
+                    "parameter '{}' without a default follows defaulted parameter '{}' \
+                     (defaults must be trailing)",
+                    param.name, prev.name
+                )));
+            }
+        }
+        self.params.push(param);
+        Ok(())
+    }
+
+    /// An alias must differ from the actor's own name.
+    fn validate_aliases(&self, name: &str, file: &Path, line: usize) -> Result<(), RegistryError> {
+        if !self.aliases.iter().any(|a| a == name) {
+            return Ok(());
+        }
+        Err(RegistryError::ParseError {
+            file: file.to_path_buf(),
+            line,
+            message: format!("ALIAS({}) repeats the actor's own name", name),
+        })
+    }
+
+    /// Every symbolic port dimension must name a compile-time `PARAM(int, ...)`.
+    /// Span-derived dims (`fir(coeff)` → N) are inferred into such a PARAM too,
+    /// so an undeclared dim can never be resolved at a use site.
+    fn validate_shape_dims(
+        &self,
+        ports: [(&str, &PortShape); 2],
+        file: &Path,
+        line: usize,
+    ) -> Result<(), RegistryError> {
+        for (port, shape) in ports {
+            for dim in &shape.dims {
+                let TokenCount::Symbolic(sym) = dim else {
+                    continue;
+                };
+                if self.params.iter().any(|p| {
+                    p.kind == ParamKind::Param && p.param_type == ParamType::Int && &p.name == sym
+                }) {
+                    continue;
+                }
+                return Err(RegistryError::ParseError {
+                    file: file.to_path_buf(),
+                    line,
+                    message: format!(
+                        "{} dimension '{}' is not a PARAM(int, {}) of this actor",
+                        port, sym, sym
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Symbolic RATE_CHANGE factors must name a compile-time `PARAM(int, ...)`,
+    /// and literal factors must agree with fully literal port shapes.
+    fn validate_rate_change(
+        &self,
+        ports: [(&str, &PortShape); 2],
+        file: &Path,
+        line: usize,
+    ) -> Result<(), RegistryError> {
+        let Some(rc) = &self.rate_change else {
+            return Ok(());
+        };
+        let error = |message: String| RegistryError::ParseError {
+            file: file.to_path_buf(),
+            line,
+            message,
+        };
+        let show = |count: &TokenCount| match count {
+            TokenCount::Literal(n) => n.to_string(),
+            TokenCount::Symbolic(sym) => sym.clone(),
+        };
+        for ((port, shape), factor) in ports.into_iter().zip([&rc.down, &rc.up]) {
+            if let TokenCount::Symbolic(sym) = factor {
+                if !self.params.iter().any(|p| {
+                    p.kind == ParamKind::Param && p.param_type == ParamType::Int && &p.name == sym
+                }) {
+                    return Err(error(format!(
+                        "RATE_CHANGE() factor '{}' is not a PARAM(int, {}) of this actor",
+                        sym, sym
+                    )));
+                }
+            }
+            // The factor restates the port's token count and must name the
+            // same literal or PARAM; it never overrides the port shape.
+            let literal: Option<u32> = shape
+                .dims
+                .iter()
+                .map(|d| match d {
+                    TokenCount::Literal(v) => Some(*v),
+                    TokenCount::Symbolic(_) => None,
+                })
+                .product();
+            let count = match (literal, shape.dims.as_slice()) {
+                (Some(n), _) => Some(TokenCount::Literal(n)),
+                (None, [dim]) => Some(dim.clone()),
+                (None, _) => None,
+            };
+            if count.as_ref() != Some(factor) {
+                let dims: Vec<String> = shape.dims.iter().map(show).collect();
+                return Err(error(format!(
+                    "RATE_CHANGE() {} rate {} contradicts the {} port's {} token(s)",
+                    port,
+                    show(factor),
+                    port,
+                    dims.join(" x ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// A symbolic scratch count must name a compile-time PARAM.
+    fn validate_scratch(&self, file: &Path, line: usize) -> Result<(), RegistryError> {
+        let Some(ActorScratch {
+            count: TokenCount::Symbolic(sym),
+            ..
+        }) = &self.scratch
+        else {
+            return Ok(());
+        };
+        if self
+            .params
+            .iter()
+            .any(|p| p.kind == ParamKind::Param && &p.name == sym)
+        {
+            return Ok(());
+        }
+        Err(RegistryError::ParseError {
+            file: file.to_path_buf(),
+            line,
+            message: format!("SCRATCH() count '{}' is not a PARAM of this actor", sym),
+        })
+    }
+}
+
 /// Split a string that may contain multiple space-separated PARAM()/RUNTIME_PARAM() specs.
 /// Returns references into the original string for each individual spec.
 /// Handles both single specs ("PARAM(int, N)") and multiple ("PARAM(int, N) PARAM(float, init)").
@@ -686,6 +1533,15 @@ fn split_param_specs(s: &str) -> Vec<&str> {
         }
 
         let start = pos;
+        // Bare-word specs (`TERMINATES`) end at the next whitespace
+        let word_end = s[pos..]
+            .find(|c: char| c.is_ascii_whitespace() || c == '(')
+            .map_or(bytes.len(), |rel| pos + rel);
+        if word_end == bytes.len() || bytes[word_end] != b'(' {
+            specs.push(&s[start..word_end]);
+            pos = word_end;
+            continue;
+        }
         // Find the opening paren
         if let Some(rel_paren) = s[pos..].find('(') {
             let paren_pos = pos + rel_paren;
@@ -745,19 +1601,21 @@ fn parse_actor_macro(
 
     // Collect remaining fields (params). Fields may be comma-separated (old style)
     // or space-separated within a single field (new style). Handle both.
-    let mut params = Vec::new();
+    let mut specs = ActorSpecs::default();
     for field in &fields[3..] {
         let trimmed = field.trim();
         if trimmed.is_empty() {
             continue;
         }
         // A field may contain multiple space-separated PARAM/RUNTIME_PARAM specs
-        let specs = split_param_specs(trimmed);
-        for spec in specs {
-            let param = parse_param_spec(spec, type_params, file, line)?;
-            params.push(param);
+        for spec in split_param_specs(trimmed) {
+            specs.push(spec, type_params, file, line)?;
         }
     }
+    specs.validate_shape_dims([("IN", &in_shape), ("OUT", &out_shape)], file, line)?;
+    specs.validate_scratch(file, line)?;
+    specs.validate_rate_change([("IN", &in_shape), ("OUT", &out_shape)], file, line)?;
+    specs.validate_aliases(&name, file, line)?;
 
     Ok(ActorMeta {
         name,
@@ -768,7 +1626,14 @@ fn parse_actor_macro(
         out_type,
         out_count,
         out_shape,
-        params,
+        params: specs.params,
+        terminates: specs.terminates,
+        pure: specs.pure,
+        inline: specs.inline,
+        scratch: specs.scratch,
+        rate_change: specs.rate_change,
+        aliases: specs.aliases,
+        doc: None,
     })
 }
 
@@ -880,15 +1745,53 @@ fn parse_param_spec(
     }
 
     let param_type = parse_param_type(parts[0].trim(), type_params, file, line)?;
-    let name = parts[1].trim().to_string();
+    let (name, default) = match parts[1].split_once('=') {
+        Some((name, value)) => (
+            name.trim().to_string(),
+            Some(parse_param_default(value.trim(), &param_type, file, line)?),
+        ),
+        None => (parts[1].trim().to_string(), None),
+    };
 
     Ok(ActorParam {
         kind,
         param_type,
         name,
+        default,
     })
 }
 
+/// Validate a `PARAM(type, name = value)` default: a numeric literal for a
+/// scalar parameter type. Returns the literal without a trailing `f`.
+fn parse_param_default(
+    value: &str,
+    param_type: &ParamType,
+    file: &Path,
+    line: usize,
+) -> Result<String, RegistryError> {
+    let error = |message: String| RegistryError::ParseError {
+        file: file.to_path_buf(),
+        line,
+        message,
+    };
+    let literal = match param_type {
+        ParamType::Int => value.parse::<i64>().ok().map(|_| value),
+        ParamType::Float | ParamType::Double | ParamType::TypeParam(_) => {
+            let digits = value.strip_suffix(['f', 'F']).unwrap_or(value);
+            digits.parse::<f64>().ok().map(|_| digits)
+        }
+        _ => {
+            return Err(error(format!(
+                "default value '{}' is only allowed on scalar parameters",
+                value
+            )))
+        }
+    };
+    literal
+        .map(str::to_string)
+        .ok_or_else(|| error(format!("invalid default value '{}' for parameter", value)))
+}
+
 /// Map a type string to PipitType.
 fn parse_pipit_type(s: &str, file: &Path, line: usize) -> Result<PipitType, RegistryError> {
     match s {
@@ -899,6 +1802,7 @@ fn parse_pipit_type(s: &str, file: &Path, line: usize) -> Result<PipitType, Regi
         "double" => Ok(PipitType::Double),
         "cfloat" | "std::complex<float>" => Ok(PipitType::Cfloat),
         "cdouble" | "std::complex<double>" => Ok(PipitType::Cdouble),
+        "bool" => Ok(PipitType::Bool),
         "void" => Ok(PipitType::Void),
         _ => Err(RegistryError::ParseError {
             file: file.to_path_buf(),
@@ -980,7 +1884,18 @@ pub fn scan_actors_pp(
 
     let probe_tu = build_probe_tu(&all_headers);
     let pp_output = invoke_preprocessor(cc, &probe_tu, extra_include_dirs)?;
-    let records = parse_pp_records(&pp_output)?;
+    let mut records = parse_pp_records(&pp_output)?;
+
+    // Comments are gone after preprocessing; recover docs from the headers.
+    let mut docs_by_file: HashMap<String, HashMap<String, String>> = HashMap::new();
+    for (meta, source_path) in &mut records {
+        let docs = docs_by_file.entry(source_path.clone()).or_insert_with(|| {
+            std::fs::read_to_string(source_path.as_str())
+                .map(|src| extract_doc_comments(&src))
+                .unwrap_or_default()
+        });
+        meta.doc = docs.get(&meta.name).cloned();
+    }
 
     // Split records into two registries by source path
     let mut include_registry = Registry::new();
@@ -1021,15 +1936,8 @@ pub fn scan_actors_pp(
             &mut include_registry
         };
 
-        // Enforce same-group duplicate check
-        if let Some((_, first_path)) = registry.actors.get(&meta.name) {
-            return Err(RegistryError::DuplicateActor {
-                name: meta.name.clone(),
-                first: first_path.clone(),
-                second: source.clone(),
-            });
-        }
-        registry.actors.insert(meta.name.clone(), (meta, source));
+        // Enforce same-group duplicate and alias collision checks
+        registry.register(meta, &source)?;
     }
 
     Ok((include_registry, actor_path_registry))
@@ -1048,6 +1956,12 @@ fn build_probe_tu(headers: &[&Path]) -> String {
     tu.push_str("#undef OUT\n");
     tu.push_str("#undef PARAM\n");
     tu.push_str("#undef RUNTIME_PARAM\n");
+    tu.push_str("#undef TERMINATES\n");
+    tu.push_str("#undef PURE\n");
+    tu.push_str("#undef INLINE\n");
+    tu.push_str("#undef SCRATCH\n");
+    tu.push_str("#undef ALIAS\n");
+    tu.push_str("#undef RATE_CHANGE\n");
     tu.push_str("#undef _PIPIT_FIRST\n\n");
 
     // Self-referential macros: prevent expansion, preserve text
@@ -1055,6 +1969,12 @@ fn build_probe_tu(headers: &[&Path]) -> String {
     tu.push_str("#define OUT(type, count) OUT(type, count)\n");
     tu.push_str("#define PARAM(type, name) PARAM(type, name)\n");
     tu.push_str("#define RUNTIME_PARAM(type, name) RUNTIME_PARAM(type, name)\n");
+    tu.push_str("#define TERMINATES TERMINATES\n");
+    tu.push_str("#define PURE PURE\n");
+    tu.push_str("#define INLINE INLINE\n");
+    tu.push_str("#define SCRATCH(type, count) SCRATCH(type, count)\n");
+    tu.push_str("#define ALIAS(name) ALIAS(name)\n");
+    tu.push_str("#define RATE_CHANGE(up, down) RATE_CHANGE(up, down)\n");
     tu.push_str("#define _PIPIT_FIRST(a, ...) void\n\n");
 
     // Record emission macro
@@ -1079,24 +1999,76 @@ fn build_probe_tu(headers: &[&Path]) -> String {
     tu
 }
 
-/// Invoke the C++ preprocessor and capture stdout.
-fn invoke_preprocessor(
-    cc: &str,
-    source: &str,
-    include_dirs: &[PathBuf],
-) -> Result<String, RegistryError> {
+/// Translation unit instantiating one actor and calling it on null buffers.
+fn build_validation_tu(meta: &ActorMeta, header: &Path) -> String {
+    let mut tu = String::with_capacity(512);
+    tu.push_str("#include \"pipit.h\"\n");
+    tu.push_str(&format!(
+        "#include \"{}\"\n\n",
+        header.display().to_string().replace('\\', "\\\\")
+    ));
+    tu.push_str("template <typename F> struct PipitFireSig;\n");
+    tu.push_str("template <typename A, typename I, typename O>\n");
+    tu.push_str("struct PipitFireSig<int (A::*)(const I *, O *) noexcept> {\n");
+    tu.push_str("    using In = I;\n");
+    tu.push_str("    using Out = O;\n");
+    tu.push_str("};\n\n");
+    let targs = if meta.is_polymorphic() {
+        format!("<{}>", vec!["float"; meta.type_params.len()].join(", "))
+    } else {
+        String::new()
+    };
+    tu.push_str(&format!(
+        "using PipitActor = Actor_{}{};\n",
+        meta.name, targs
+    ));
+    tu.push_str("using PipitSig = PipitFireSig<decltype(&PipitActor::operator()<>)>;\n\n");
+    tu.push_str("int pipit_validate_actor() {\n");
+    tu.push_str("    PipitActor actor{};\n");
+    tu.push_str("    const PipitSig::In *in = nullptr;\n");
+    tu.push_str("    PipitSig::Out *out = nullptr;\n");
+    tu.push_str("    return actor(in, out);\n");
+    tu.push_str("}\n");
+    tu
+}
+
+/// Compile `source` with `-fsyntax-only`; `Err` carries the compiler output.
+fn compile_syntax_only(cc: &str, source: &str, include_dirs: &[PathBuf]) -> Result<(), String> {
     use std::io::Write;
     use std::process::{Command, Stdio};
 
     let mut cmd = Command::new(cc);
-    cmd.arg("-E")
-        .arg("-P")
+    cmd.arg("-fsyntax-only")
         .arg("-x")
         .arg("c++")
         .arg("-std=c++20")
         .arg("-");
+    add_include_args(&mut cmd, include_dirs);
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
 
-    // Auto-add runtime include paths
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("failed to launch '{}': {}", cc, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(source.as_bytes())
+            .map_err(|e| format!("failed to write to compiler stdin: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to wait for compiler: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+/// Add `-I` flags for the runtime headers (and their third-party
+/// dependencies), then for `include_dirs`.
+fn add_include_args(cmd: &mut std::process::Command, include_dirs: &[PathBuf]) {
     let runtime_include = Path::new(env!("CARGO_MANIFEST_DIR"))
         .join("..")
         .join("runtime")
@@ -1110,9 +2082,29 @@ fn invoke_preprocessor(
         }
     }
 
-    for dir in include_dirs {
-        cmd.arg("-I").arg(dir);
-    }
+    for dir in include_dirs {
+        cmd.arg("-I").arg(dir);
+    }
+}
+
+/// Invoke the C++ preprocessor and capture stdout.
+fn invoke_preprocessor(
+    cc: &str,
+    source: &str,
+    include_dirs: &[PathBuf],
+) -> Result<String, RegistryError> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut cmd = Command::new(cc);
+    cmd.arg("-E")
+        .arg("-P")
+        .arg("-x")
+        .arg("c++")
+        .arg("-std=c++20")
+        .arg("-");
+
+    add_include_args(&mut cmd, include_dirs);
 
     cmd.stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -1195,14 +2187,16 @@ fn parse_pp_records(output: &str) -> Result<Vec<(ActorMeta, String)>, RegistryEr
         let (out_type, out_count, out_shape) =
             parse_port_spec(&out_spec, "OUT", &type_params, &file_path, line)?;
 
-        let mut actor_params = Vec::new();
+        let mut specs = ActorSpecs::default();
         if !params.is_empty() {
-            let specs = split_param_specs(&params);
-            for spec in specs {
-                let param = parse_param_spec(spec, &type_params, &file_path, line)?;
-                actor_params.push(param);
+            for spec in split_param_specs(&params) {
+                specs.push(spec, &type_params, &file_path, line)?;
             }
         }
+        specs.validate_shape_dims([("IN", &in_shape), ("OUT", &out_shape)], &file_path, line)?;
+        specs.validate_scratch(&file_path, line)?;
+        specs.validate_rate_change([("IN", &in_shape), ("OUT", &out_shape)], &file_path, line)?;
+        specs.validate_aliases(&name, &file_path, line)?;
 
         results.push((
             ActorMeta {
@@ -1214,7 +2208,14 @@ fn parse_pp_records(output: &str) -> Result<Vec<(ActorMeta, String)>, RegistryEr
                 out_type,
                 out_count,
                 out_shape,
-                params: actor_params,
+                params: specs.params,
+                terminates: specs.terminates,
+                pure: specs.pure,
+                inline: specs.inline,
+                scratch: specs.scratch,
+                rate_change: specs.rate_change,
+                aliases: specs.aliases,
+                doc: None,
             },
             file,
         ));
@@ -1401,6 +2402,208 @@ mod tests {
         assert_eq!(a.params[0].name, "channel");
     }
 
+    #[test]
+    fn parse_actor_terminates() {
+        let a = scan_one(
+            "ACTOR(replay, IN(void, 0), OUT(float, 1), TERMINATES PARAM(int, channel)) { return ACTOR_OK; }",
+        );
+        assert!(a.terminates);
+        assert_eq!(a.params.len(), 1);
+        assert_eq!(a.params[0].name, "channel");
+
+        let a = scan_one(
+            "ACTOR(adc, IN(void, 0), OUT(float, 1), PARAM(int, channel)) { return ACTOR_OK; }",
+        );
+        assert!(!a.terminates);
+        assert!(
+            !serde_json::to_string(&a).unwrap().contains("terminates"),
+            "non-terminating actors keep the existing manifest shape"
+        );
+    }
+
+    #[test]
+    fn parse_actor_pure() {
+        let a = scan_one(
+            "ACTOR(gain, IN(float, 1), OUT(float, 1), RUNTIME_PARAM(float, g) PURE) { return ACTOR_OK; }",
+        );
+        assert!(a.pure);
+        assert_eq!(a.params.len(), 1, "PURE is not a parameter");
+        assert!(serde_json::to_string(&a).unwrap().contains("\"pure\":true"));
+
+        let a = scan_one("ACTOR(adc, IN(void, 0), OUT(float, 1)) { return ACTOR_OK; }");
+        assert!(!a.pure);
+        assert!(
+            !serde_json::to_string(&a).unwrap().contains("pure"),
+            "impure actors keep the existing manifest shape"
+        );
+    }
+
+    #[test]
+    fn parse_actor_inline() {
+        let a =
+            scan_one("ACTOR(add, IN(float, 2), OUT(float, 1), PURE INLINE) { return ACTOR_OK; }");
+        assert!(a.inline && a.pure);
+        assert!(a.params.is_empty(), "INLINE is not a parameter");
+        assert!(serde_json::to_string(&a)
+            .unwrap()
+            .contains("\"inline\":true"));
+
+        let a = scan_one("ACTOR(adc, IN(void, 0), OUT(float, 1)) { return ACTOR_OK; }");
+        assert!(!a.inline);
+        assert!(!serde_json::to_string(&a).unwrap().contains("inline"));
+    }
+
+    #[test]
+    fn parse_actor_scratch() {
+        let a = scan_one(
+            "ACTOR(hist, IN(float, 1), OUT(float, 1), PARAM(int, N) SCRATCH(float, N)) { return ACTOR_OK; }",
+        );
+        assert_eq!(a.params.len(), 1, "SCRATCH is not a parameter");
+        assert_eq!(
+            a.scratch,
+            Some(ActorScratch {
+                elem_type: PipitType::Float,
+                count: TokenCount::Symbolic("N".to_string()),
+            })
+        );
+
+        let a = scan_one("ACTOR(plain, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }");
+        assert!(
+            !serde_json::to_string(&a).unwrap().contains("scratch"),
+            "actors without scratch keep the existing manifest shape"
+        );
+
+        let path = PathBuf::from("test.h");
+        for (src, expected) in [
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), SCRATCH(float, 4) PARAM(int, N)) { return ACTOR_OK; }",
+                "must precede SCRATCH()",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), RUNTIME_PARAM(int, N) SCRATCH(float, N)) { return ACTOR_OK; }",
+                "is not a PARAM of this actor",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), SCRATCH(float, 4) SCRATCH(int32, 2)) { return ACTOR_OK; }",
+                "at most one SCRATCH()",
+            ),
+        ] {
+            match scan_actors(src, &path) {
+                Err(RegistryError::ParseError { message, .. }) => {
+                    assert!(message.contains(expected), "got: {}", message)
+                }
+                other => panic!("expected ParseError for {}, got: {:?}", src, other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_actor_rate_change() {
+        let a = scan_one(
+            "ACTOR(dec, IN(float, N), OUT(float, 1), PARAM(int, N) RATE_CHANGE(1, N)) { return ACTOR_OK; }",
+        );
+        assert_eq!(a.params.len(), 1, "RATE_CHANGE is not a parameter");
+        assert_eq!(
+            a.rate_change,
+            Some(RateChange {
+                up: TokenCount::Literal(1),
+                down: TokenCount::Symbolic("N".to_string()),
+            })
+        );
+
+        let a = scan_one("ACTOR(plain, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }");
+        assert!(
+            !serde_json::to_string(&a).unwrap().contains("rate_change"),
+            "actors without a rate change keep the existing manifest shape"
+        );
+
+        let path = PathBuf::from("test.h");
+        for (src, expected) in [
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), RUNTIME_PARAM(int, N) RATE_CHANGE(N, 1)) { return ACTOR_OK; }",
+                "is not a PARAM(int, N) of this actor",
+            ),
+            (
+                "ACTOR(a, IN(float, 4), OUT(float, 1), RATE_CHANGE(1, 2)) { return ACTOR_OK; }",
+                "contradicts the IN port's 4 token(s)",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), PARAM(int, U) PARAM(int, D) RATE_CHANGE(U, D)) { return ACTOR_OK; }",
+                "RATE_CHANGE() IN rate D contradicts the IN port's 1 token(s)",
+            ),
+            (
+                "ACTOR(a, IN(float, N), OUT(float, 1), PARAM(int, N) PARAM(int, M) RATE_CHANGE(1, M)) { return ACTOR_OK; }",
+                "RATE_CHANGE() IN rate M contradicts the IN port's N token(s)",
+            ),
+            (
+                "ACTOR(a, IN(float, N), OUT(float, 1), PARAM(int, N) RATE_CHANGE(1, 4)) { return ACTOR_OK; }",
+                "RATE_CHANGE() IN rate 4 contradicts the IN port's N token(s)",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), RATE_CHANGE(0, 1)) { return ACTOR_OK; }",
+                "must be positive",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), RATE_CHANGE(1, 1) RATE_CHANGE(1, 1)) { return ACTOR_OK; }",
+                "at most one RATE_CHANGE()",
+            ),
+        ] {
+            match scan_actors(src, &path) {
+                Err(RegistryError::ParseError { message, .. }) => {
+                    assert!(message.contains(expected), "got: {}", message)
+                }
+                other => panic!("expected ParseError for {}, got: {:?}", src, other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_actor_alias() {
+        let a = scan_one(
+            "ACTOR(gain, IN(float, 1), OUT(float, 1), ALIAS(scale) PARAM(float, g) ALIAS(amp)) { return ACTOR_OK; }",
+        );
+        assert_eq!(a.aliases, vec!["scale", "amp"]);
+        assert_eq!(a.params.len(), 1, "ALIAS is not a parameter");
+
+        let mut reg = Registry::new();
+        reg.insert(a);
+        let meta = reg.lookup("scale").expect("alias resolves");
+        assert_eq!(meta.name, "gain", "alias resolves to the canonical actor");
+        assert_eq!(reg.lookup("amp").map(|m| &m.name), Some(&meta.name));
+        assert_eq!(reg.len(), 1, "aliases are not separate actors");
+        assert!(
+            reg.generate_manifest().contains("\"aliases\""),
+            "manifest lists aliases"
+        );
+
+        let path = PathBuf::from("test.h");
+        for (src, expected) in [
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), ALIAS(a)) { return ACTOR_OK; }",
+                "repeats the actor's own name",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), ALIAS(b) ALIAS(b)) { return ACTOR_OK; }",
+                "duplicate ALIAS(b)",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), ALIAS(not-a-name)) { return ACTOR_OK; }",
+                "expected ALIAS(name)",
+            ),
+        ] {
+            match scan_actors(src, &path) {
+                Err(RegistryError::ParseError { message, .. }) => {
+                    assert!(message.contains(expected), "got: {}", message)
+                }
+                other => panic!(
+                    "expected ParseError for {}, got: {:?}",
+                    src,
+                    other.map(|_| ())
+                ),
+            }
+        }
+    }
+
     #[test]
     fn parse_actor_with_runtime_param() {
         let a = scan_one(
@@ -1497,6 +2700,47 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn alias_colliding_with_actor_name_error() {
+        let dir = std::env::temp_dir().join("pipit_test_alias_collision");
+        std::fs::create_dir_all(&dir).unwrap();
+        let f1 = dir.join("a.h");
+        let f2 = dir.join("b.h");
+        std::fs::write(
+            &f1,
+            "ACTOR(foo, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }",
+        )
+        .unwrap();
+        std::fs::write(
+            &f2,
+            "ACTOR(bar, IN(float, 1), OUT(float, 1), ALIAS(foo)) { return ACTOR_OK; }",
+        )
+        .unwrap();
+
+        let mut reg = Registry::new();
+        reg.load_header(&f1).unwrap();
+        let err = reg.load_header(&f2).unwrap_err();
+        match &err {
+            RegistryError::AliasCollision { alias, actor, .. } => {
+                assert_eq!(alias, "foo");
+                assert_eq!(actor, "bar");
+            }
+            _ => panic!("expected AliasCollision error, got: {}", err),
+        }
+
+        // Same collision in the other load order: the real name comes second.
+        let mut reg = Registry::new();
+        reg.load_header(&f2).unwrap();
+        let err = reg.load_header(&f1).unwrap_err();
+        assert!(
+            matches!(err, RegistryError::AliasCollision { ref alias, .. } if alias == "foo"),
+            "got: {}",
+            err
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn unknown_type_error() {
         let path = PathBuf::from("test.h");
@@ -1608,6 +2852,76 @@ ACTOR(real, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }
         assert_eq!(actors[0].name, "real");
     }
 
+    #[test]
+    fn doc_comment_captured() {
+        let src = r#"
+// ── Section ──
+
+/// @brief Scale input
+///
+/// @param gain Multiplier applied to each sample
+/// @deprecated use mul instead
+ACTOR(scale, IN(float, 1), OUT(float, 1), PARAM(float, gain)) { return ACTOR_OK; }
+
+/** Block-documented
+ * second line
+ */
+template <typename T>
+ACTOR(ident, IN(T, 1), OUT(T, 1)) { return ACTOR_OK; }
+
+// plain comment is not documentation
+ACTOR(plain, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }
+"#;
+        let actors = scan_actors(src, &PathBuf::from("test.h")).unwrap();
+        let scale = actors.iter().find(|a| a.name == "scale").unwrap();
+        assert_eq!(
+            scale.doc.as_deref(),
+            Some("@brief Scale input\n\n@param gain Multiplier applied to each sample\n@deprecated use mul instead")
+        );
+        assert_eq!(
+            scale.doc_param("gain"),
+            Some("Multiplier applied to each sample")
+        );
+        assert_eq!(scale.deprecation(), Some("use mul instead"));
+
+        let ident = actors.iter().find(|a| a.name == "ident").unwrap();
+        assert_eq!(ident.doc.as_deref(), Some("Block-documented\nsecond line"));
+        assert_eq!(ident.deprecation(), None);
+
+        let plain = actors.iter().find(|a| a.name == "plain").unwrap();
+        assert_eq!(plain.doc, None);
+    }
+
+    #[test]
+    fn lookup_fuzzy_ranks_near_names() {
+        let mut reg = Registry::empty();
+        for name in ["fft", "ifft", "fir", "mag", "map", "max", "mean"] {
+            reg.insert(simple_meta(name, PipitType::Float, PipitType::Float));
+        }
+        assert_eq!(reg.lookup_fuzzy("fftt"), vec!["fft", "ifft"]);
+        // mag/map/max at distance 1, mean at 2: capped at 3
+        assert_eq!(reg.lookup_fuzzy("mae"), vec!["mag", "map", "max"]);
+        assert!(reg.lookup_fuzzy("decimate").is_empty());
+    }
+
+    #[test]
+    fn doc_comment_not_in_manifest() {
+        let meta = scan_one("ACTOR(a, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }");
+        let mut reg = Registry::empty();
+        reg.insert(meta.clone());
+        let undocumented = reg.canonical_json();
+
+        let mut documented = meta;
+        documented.doc = Some("documented".to_string());
+        let mut reg = Registry::empty();
+        reg.insert(documented);
+        assert_eq!(
+            reg.canonical_json(),
+            undocumented,
+            "doc comments must not affect the manifest or registry fingerprint"
+        );
+    }
+
     #[test]
     fn skip_preprocessor_define() {
         // #define ACTOR(...) should not be parsed as an actor definition
@@ -1653,8 +2967,8 @@ ACTOR(real, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }
 
         let mut reg = Registry::new();
         let count = reg.load_header(&path).unwrap();
-        assert_eq!(count, 4, "expected 4 actors in examples/example_actors.h");
-        assert_eq!(reg.len(), 4);
+        assert_eq!(count, 6, "expected 6 actors in examples/example_actors.h");
+        assert_eq!(reg.len(), 6);
 
         // Spot-check a few example actors
         let correlate = reg.lookup("correlate").expect("correlate not found");
@@ -1686,6 +3000,65 @@ ACTOR(b, IN(int32, 2), OUT(double, 1)) { return ACTOR_OK; }
         assert_eq!(actors[1].name, "b");
     }
 
+    #[test]
+    fn parse_bool_port_type() {
+        let a = scan_one("ACTOR(is_pos, IN(float, 1), OUT(bool, 1)) { return ACTOR_OK; }");
+        assert_eq!(a.out_type, PipitType::Bool);
+        assert_eq!(a.out_type.to_string(), "bool");
+        let json = serde_json::to_string(&a).unwrap();
+        assert!(
+            json.contains("\"out_type\":{\"Concrete\":\"Bool\"}"),
+            "{}",
+            json
+        );
+        let back: ActorMeta = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, a);
+    }
+
+    #[test]
+    fn parse_param_defaults() {
+        let a = scan_one(
+            "ACTOR(win, IN(float, N), OUT(float, N), PARAM(float, gain = 0.5f) PARAM(int, N = 256)) { return ACTOR_OK; }",
+        );
+        assert_eq!(a.params[0].name, "gain");
+        assert_eq!(a.params[0].default.as_deref(), Some("0.5"));
+        assert_eq!(a.params[1].name, "N");
+        assert_eq!(a.params[1].default.as_deref(), Some("256"));
+        let back: ActorMeta = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+        assert_eq!(back, a);
+
+        let plain = scan_one(
+            "ACTOR(adc, IN(void, 0), OUT(float, 1), PARAM(int, channel)) { return ACTOR_OK; }",
+        );
+        assert!(
+            !serde_json::to_string(&plain).unwrap().contains("default"),
+            "params without defaults keep the existing manifest shape"
+        );
+
+        let path = PathBuf::from("test.h");
+        for (src, expected) in [
+            (
+                "ACTOR(a, IN(float, N), OUT(float, N), PARAM(int, N = 64) PARAM(float, gain)) { return ACTOR_OK; }",
+                "defaults must be trailing",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), PARAM(int, N = 1.5)) { return ACTOR_OK; }",
+                "invalid default value '1.5'",
+            ),
+            (
+                "ACTOR(a, IN(float, 1), OUT(float, 1), PARAM(std::span<const float>, taps = 0)) { return ACTOR_OK; }",
+                "only allowed on scalar parameters",
+            ),
+        ] {
+            match scan_actors(src, &path) {
+                Err(RegistryError::ParseError { message, .. }) => {
+                    assert!(message.contains(expected), "got: {}", message)
+                }
+                other => panic!("expected ParseError for {}, got: {:?}", src, other.map(|_| ())),
+            }
+        }
+    }
+
     // ── PortShape tests ─────────────────────────────────────────────────
 
     #[test]
@@ -1799,18 +3172,6 @@ ACTOR(b, IN(int32, 2), OUT(double, 1)) { return ACTOR_OK; }
 
     // ── Shape edge cases (documenting current permissive behavior) ──────
 
-    #[test]
-    fn shape_empty_becomes_symbolic() {
-        // SHAPE() → inner "" parsed as Symbolic("") — no validation in registry
-        let a = scan_one("ACTOR(x, IN(float, SHAPE()), OUT(float, 1)) { return ACTOR_OK; }");
-        assert_eq!(a.in_shape.rank(), 1);
-        assert_eq!(
-            a.in_shape.dims[0],
-            TokenCount::Symbolic("".into()),
-            "empty SHAPE() should produce Symbolic(\"\")"
-        );
-    }
-
     #[test]
     fn shape_zero_literal() {
         // SHAPE(0) is valid at registry level — semantics checked in analysis
@@ -1821,15 +3182,48 @@ ACTOR(b, IN(int32, 2), OUT(double, 1)) { return ACTOR_OK; }
     }
 
     #[test]
-    fn shape_negative_becomes_symbolic() {
-        // -1 fails u32::parse → treated as Symbolic("-1")
-        let a = scan_one("ACTOR(x, IN(float, SHAPE(-1)), OUT(float, 1)) { return ACTOR_OK; }");
-        assert_eq!(a.in_shape.rank(), 1);
-        assert_eq!(
-            a.in_shape.dims[0],
-            TokenCount::Symbolic("-1".into()),
-            "negative value should become Symbolic"
+    fn shape_dims_must_be_declared_int_params() {
+        // Orphan symbolic dims are rejected at load time, not at use sites.
+        let path = PathBuf::from("test.h");
+        for (src, expected) in [
+            (
+                "ACTOR(x, IN(float, 1), OUT(float, SHAPE(N)), PARAM(float, g)) { return ACTOR_OK; }",
+                "OUT dimension 'N' is not a PARAM(int, N) of this actor",
+            ),
+            (
+                "ACTOR(x, IN(float, SHAPE(H, W)), OUT(float, 1), PARAM(int, H)) { return ACTOR_OK; }",
+                "IN dimension 'W' is not a PARAM(int, W)",
+            ),
+            (
+                "ACTOR(x, IN(float, N), OUT(float, N), RUNTIME_PARAM(int, N)) { return ACTOR_OK; }",
+                "IN dimension 'N'",
+            ),
+            (
+                "ACTOR(x, IN(float, N), OUT(float, 1), PARAM(float, N)) { return ACTOR_OK; }",
+                "IN dimension 'N'",
+            ),
+            // SHAPE() and SHAPE(-1) parse as symbolic dims with no matching PARAM
+            (
+                "ACTOR(x, IN(float, SHAPE()), OUT(float, 1)) { return ACTOR_OK; }",
+                "IN dimension ''",
+            ),
+            (
+                "ACTOR(x, IN(float, SHAPE(-1)), OUT(float, 1)) { return ACTOR_OK; }",
+                "IN dimension '-1'",
+            ),
+        ] {
+            match scan_actors(src, &path) {
+                Err(RegistryError::ParseError { message, .. }) => {
+                    assert!(message.contains(expected), "got: {}", message)
+                }
+                other => panic!("expected ParseError for {}, got: {:?}", src, other),
+            }
+        }
+        // A span-derived dim is still declared as PARAM(int, N).
+        let a = scan_one(
+            "ACTOR(f, IN(float, N), OUT(float, 1), PARAM(std::span<const float>, coeff) PARAM(int, N)) { return ACTOR_OK; }",
         );
+        assert_eq!(a.in_shape.dims[0], TokenCount::Symbolic("N".into()));
     }
 
     // ── Registry API contract tests ─────────────────────────────────────
@@ -1894,7 +3288,7 @@ ACTOR(b, IN(float, 1), OUT(float, 1)) { return ACTOR_OK; }
         let mut overlay = Registry::new();
         overlay.load_header(&f2).unwrap();
 
-        base.overlay_from(&overlay);
+        base.overlay_from(&overlay).unwrap();
 
         // "a" should be overwritten by overlay's version
         assert_eq!(base.lookup("a").unwrap().in_type, PipitType::Int32);
@@ -2012,6 +3406,7 @@ ACTOR(concrete_c, IN(int32, 1), OUT(int32, 1)) { return ACTOR_OK; }
         let src = r#"
 ACTOR(mul, IN(float, 1), OUT(float, 1), RUNTIME_PARAM(float, gain)) { return ACTOR_OK; }
 ACTOR(fft, IN(float, N), OUT(cfloat, N), PARAM(int, N)) { return ACTOR_OK; }
+ACTOR(add, IN(float, 2), OUT(float, 1), INLINE) { return ACTOR_OK; }
 "#;
         let dir = std::env::temp_dir().join("pipit_test_manifest_rt");
         std::fs::create_dir_all(&dir).unwrap();
@@ -2040,6 +3435,7 @@ ACTOR(fft, IN(float, N), OUT(cfloat, N), PARAM(int, N)) { return ACTOR_OK; }
                 .expect("actor missing after roundtrip");
             assert_eq!(a1, a2, "mismatch for actor '{}'", a1.name);
         }
+        assert!(reg2.lookup("add").unwrap().inline);
 
         std::fs::remove_dir_all(&dir).ok();
     }
@@ -2135,6 +3531,13 @@ ACTOR(scale, IN(T, N), OUT(T, N), PARAM(T, gain) PARAM(int, N)) {
             out_count: TokenCount::Literal(1),
             out_shape: PortShape::rank1(TokenCount::Literal(1)),
             params: vec![],
+            terminates: false,
+            pure: false,
+            inline: false,
+            scratch: None,
+            rate_change: None,
+            aliases: Vec::new(),
+            doc: None,
         }
     }
 
@@ -2158,9 +3561,30 @@ ACTOR(scale, IN(T, N), OUT(T, N), PARAM(T, gain) PARAM(int, N)) {
             ),
         );
 
-        base.overlay_from(&overlay);
+        base.overlay_from(&overlay).unwrap();
         let meta = base.lookup("Gain").unwrap();
         assert_eq!(meta.in_type, PipitType::Cfloat, "overlay should replace");
+        assert_eq!(base.source_header("Gain"), Some(Path::new("new.h")));
+    }
+
+    #[test]
+    fn source_header_resolves_aliases() {
+        let dir = std::env::temp_dir().join("pipit_test_source_header");
+        std::fs::create_dir_all(&dir).unwrap();
+        let f = dir.join("gain.h");
+        std::fs::write(
+            &f,
+            "ACTOR(gain, IN(float, 1), OUT(float, 1), ALIAS(amp)) { return ACTOR_OK; }",
+        )
+        .unwrap();
+
+        let mut reg = Registry::new();
+        reg.load_header(&f).unwrap();
+        assert_eq!(reg.source_header("gain"), Some(f.as_path()));
+        assert_eq!(reg.source_header("amp"), Some(f.as_path()));
+        assert_eq!(reg.source_header("missing"), None);
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 
     #[test]
@@ -2183,12 +3607,112 @@ ACTOR(scale, IN(T, N), OUT(T, N), PARAM(T, gain) PARAM(int, N)) {
             ),
         );
 
-        base.overlay_from(&overlay);
+        base.overlay_from(&overlay).unwrap();
         assert!(base.lookup("A").is_some(), "base actor A preserved");
         assert!(base.lookup("B").is_some(), "overlay actor B added");
         assert_eq!(base.len(), 2);
     }
 
+    #[test]
+    fn overlay_from_rejects_alias_of_surviving_actor() {
+        let mut base = Registry::new();
+        base.insert(simple_meta("A", PipitType::Float, PipitType::Float));
+        let mut renamed = simple_meta("B", PipitType::Float, PipitType::Float);
+        renamed.aliases = vec!["old_b".into()];
+        base.insert(renamed);
+
+        // Replacing B drops its alias, so the overlay may reuse the name.
+        let mut overlay = Registry::new();
+        let mut c = simple_meta("C", PipitType::Float, PipitType::Float);
+        c.aliases = vec!["old_b".into()];
+        overlay.insert(c);
+        overlay.insert(simple_meta("B", PipitType::Float, PipitType::Float));
+        base.overlay_from(&overlay).unwrap();
+        assert_eq!(base.lookup("old_b").map(|m| m.name.as_str()), Some("C"));
+
+        let mut overlay = Registry::new();
+        let mut d = simple_meta("D", PipitType::Float, PipitType::Float);
+        d.aliases = vec!["A".into()];
+        overlay.insert(d);
+        match base.overlay_from(&overlay) {
+            Err(RegistryError::AliasCollision { alias, actor, .. }) => {
+                assert_eq!((alias.as_str(), actor.as_str()), ("A", "D"));
+            }
+            other => panic!("expected AliasCollision, got: {:?}", other),
+        }
+    }
+
+    // ── Registry diff tests ──────────────────────────────────────────────
+
+    fn param(kind: ParamKind, param_type: ParamType, name: &str) -> ActorParam {
+        ActorParam {
+            kind,
+            param_type,
+            name: name.to_string(),
+            default: None,
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_field_changes() {
+        let mut old = Registry::new();
+        old.insert(simple_meta("gain", PipitType::Float, PipitType::Float));
+        old.insert(simple_meta("gone", PipitType::Float, PipitType::Float));
+        old.insert(simple_meta("same", PipitType::Float, PipitType::Float));
+
+        let mut new = Registry::new();
+        new.insert(simple_meta("gain", PipitType::Cfloat, PipitType::Float));
+        new.insert(simple_meta("fresh", PipitType::Float, PipitType::Float));
+        new.insert(simple_meta("same", PipitType::Float, PipitType::Float));
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["fresh".to_string()]);
+        assert_eq!(diff.removed, vec!["gone".to_string()]);
+        assert_eq!(diff.changed.len(), 1, "{:?}", diff.changed);
+        assert_eq!(
+            diff.changed[0].to_string(),
+            "actor 'gain' in_type changed: float -> cfloat"
+        );
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.to_string(),
+            concat!(
+                "breaking: actor 'gone' removed\n",
+                "breaking: actor 'gain' in_type changed: float -> cfloat\n",
+                "compatible: actor 'fresh' added\n",
+            )
+        );
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn diff_params_trailing_changes_are_compatible() {
+        let n = param(ParamKind::Param, ParamType::Int, "n");
+        let gain = param(ParamKind::RuntimeParam, ParamType::Float, "gain");
+        let with_params = |params: Vec<ActorParam>, out_n: u32| {
+            let mut meta = simple_meta("fir", PipitType::Float, PipitType::Float);
+            meta.params = params;
+            meta.out_shape = PortShape::rank1(TokenCount::Literal(out_n));
+            let mut reg = Registry::new();
+            reg.insert(meta);
+            reg
+        };
+
+        // Trailing param added, output shape changed: compatible
+        let diff =
+            with_params(vec![n.clone()], 1).diff(&with_params(vec![n.clone(), gain.clone()], 2));
+        assert_eq!(diff.changed.len(), 2, "{:?}", diff.changed);
+        assert!(!diff.is_breaking(), "{}", diff);
+        assert_eq!(
+            diff.changed[1].to_string(),
+            "actor 'fir' params changed: (PARAM(int, n)) -> (PARAM(int, n), RUNTIME_PARAM(float, gain))"
+        );
+
+        // Leading param inserted: breaking
+        let diff = with_params(vec![n.clone()], 1).diff(&with_params(vec![gain, n], 1));
+        assert!(diff.is_breaking(), "{}", diff);
+    }
+
     // ── Canonical JSON tests ─────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,517 @@
+// report.rs — Self-contained HTML report for a compiled Pipit program
+//
+// Combines the graph diagram, PASS schedule, timing chart, memory breakdown,
+// and bind interface contracts into one HTML page. Diagrams are inline SVG
+// laid out here, so the report has no external scripts or stylesheets and
+// renders offline.
+//
+// Preconditions: `graph`, `analysis`, and `schedule` describe the same program.
+// Postconditions: returns a complete HTML document with the sections
+//   `graph`, `schedule`, `timing`, `memory`, and `interface`.
+// Failure modes: none (pure string formatting; unknown nodes get fallback labels).
+// Side effects: none.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::analyze::AnalyzedProgram;
+use crate::graph::*;
+use crate::schedule::*;
+use crate::timing::{asap_firings, format_freq};
+
+/// Report sections as (anchor id, heading), in page order.
+pub const REPORT_SECTIONS: &[(&str, &str)] = &[
+    ("graph", "Graph"),
+    ("schedule", "Schedule"),
+    ("timing", "Timing"),
+    ("memory", "Memory"),
+    ("interface", "Interface"),
+];
+
+const NODE_WIDTH: u32 = 132;
+const NODE_HEIGHT: u32 = 30;
+const LAYER_GAP: u32 = 48;
+const ROW_GAP: u32 = 18;
+const MARGIN: u32 = 12;
+
+const TIMING_LABEL_WIDTH: u32 = 160;
+const TIMING_BAR_HEIGHT: u32 = 18;
+const TIMING_ROW_GAP: u32 = 6;
+const TIMING_WIDTH: u32 = 560;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+nav a{margin-right:1em}\
+h2{border-bottom:1px solid #ccc;padding-bottom:.2em}\
+table{border-collapse:collapse}\
+td,th{border:1px solid #ccc;padding:.2em .6em;text-align:left}\
+pre{background:#f6f6f6;padding:1em;overflow:auto}\
+svg text{font-family:monospace;font-size:12px}";
+
+/// Emit the HTML report.
+///
+/// Preconditions: `graph`, `analysis`, and `schedule` correspond to the same program.
+/// Postconditions: returns a complete HTML document.
+/// Failure modes: none.
+/// Side effects: none.
+pub fn emit_report(
+    title: &str,
+    graph: &ProgramGraph,
+    analysis: &AnalyzedProgram,
+    schedule: &ScheduledProgram,
+) -> String {
+    let mut buf = String::new();
+    writeln!(buf, "<!DOCTYPE html>").unwrap();
+    writeln!(buf, "<html lang=\"en\">").unwrap();
+    writeln!(buf, "<head>").unwrap();
+    writeln!(buf, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(buf, "<title>Pipit report: {}</title>", escape(title)).unwrap();
+    writeln!(buf, "<style>{}</style>", STYLE).unwrap();
+    writeln!(buf, "</head>").unwrap();
+    writeln!(buf, "<body>").unwrap();
+    writeln!(buf, "<h1>Pipit report: {}</h1>", escape(title)).unwrap();
+    let links: Vec<String> = REPORT_SECTIONS
+        .iter()
+        .map(|(id, heading)| format!("<a href=\"#{id}\">{heading}</a>"))
+        .collect();
+    writeln!(buf, "<nav>{}</nav>", links.join("")).unwrap();
+
+    let subgraphs = ordered_subgraphs(graph, schedule);
+
+    section_start(&mut buf, 0);
+    for s in &subgraphs {
+        writeln!(buf, "<h3>{}</h3>", escape(&s.heading)).unwrap();
+        buf.push_str(&graph_svg(s.sub, s.sched));
+    }
+    writeln!(buf, "</section>").unwrap();
+
+    section_start(&mut buf, 1);
+    writeln!(buf, "<pre>{}</pre>", escape(&schedule.to_string())).unwrap();
+    writeln!(buf, "</section>").unwrap();
+
+    section_start(&mut buf, 2);
+    for s in &subgraphs {
+        writeln!(buf, "<h3>{}</h3>", escape(&s.heading)).unwrap();
+        buf.push_str(&timing_svg(s.sub, s.sched));
+    }
+    writeln!(buf, "</section>").unwrap();
+
+    section_start(&mut buf, 3);
+    write_memory(&mut buf, analysis, &subgraphs);
+    writeln!(buf, "</section>").unwrap();
+
+    section_start(&mut buf, 4);
+    write_interface(&mut buf, analysis);
+    writeln!(buf, "</section>").unwrap();
+
+    writeln!(buf, "</body>").unwrap();
+    writeln!(buf, "</html>").unwrap();
+    buf
+}
+
+fn section_start(buf: &mut String, index: usize) {
+    let (id, heading) = REPORT_SECTIONS[index];
+    writeln!(buf, "<section id=\"{id}\">").unwrap();
+    writeln!(buf, "<h2>{heading}</h2>").unwrap();
+}
+
+/// A scheduled subgraph with its display heading.
+struct ReportSubgraph<'a> {
+    heading: String,
+    sub: &'a Subgraph,
+    sched: &'a SubgraphSchedule,
+}
+
+/// Pair each task's subgraphs with their schedules, sorted by task name.
+fn ordered_subgraphs<'a>(
+    graph: &'a ProgramGraph,
+    schedule: &'a ScheduledProgram,
+) -> Vec<ReportSubgraph<'a>> {
+    let mut task_names: Vec<&String> = schedule.tasks.keys().collect();
+    task_names.sort();
+
+    let mut out = Vec::new();
+    for task_name in task_names {
+        let meta = &schedule.tasks[task_name];
+        let Some(task_graph) = graph.tasks.get(task_name) else {
+            continue;
+        };
+        let freq = format_freq(meta.freq_hz);
+        match (&meta.schedule, task_graph) {
+            (TaskSchedule::Pipeline(sched), TaskGraph::Pipeline(sub)) => {
+                out.push(ReportSubgraph {
+                    heading: format!("{task_name} ({freq})"),
+                    sub,
+                    sched,
+                });
+            }
+            (
+                TaskSchedule::Modal { control, modes },
+                TaskGraph::Modal {
+                    control: ctrl_sub,
+                    modes: mode_subs,
+                },
+            ) => {
+                out.push(ReportSubgraph {
+                    heading: format!("{task_name}.control ({freq})"),
+                    sub: ctrl_sub,
+                    sched: control,
+                });
+                for (mode_name, mode_sched) in modes {
+                    if let Some((_, sub)) = mode_subs.iter().find(|(n, _)| n == mode_name) {
+                        out.push(ReportSubgraph {
+                            heading: format!("{task_name}.{mode_name} ({freq})"),
+                            sub,
+                            sched: mode_sched,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+// ── Graph diagram ───────────────────────────────────────────────────────────
+
+/// Lay out a subgraph left-to-right and render it as inline SVG.
+///
+/// Nodes are placed in layers by longest forward path in firing order;
+/// feedback edges (target fires before source) are drawn dashed.
+fn graph_svg(sub: &Subgraph, sched: &SubgraphSchedule) -> String {
+    let position: HashMap<NodeId, usize> = sched
+        .firings
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.node_id, i))
+        .collect();
+
+    let mut layer: HashMap<NodeId, u32> = HashMap::new();
+    for entry in &sched.firings {
+        let l = sub
+            .edges
+            .iter()
+            .filter(|e| e.target == entry.node_id && is_forward(&position, e))
+            .filter_map(|e| layer.get(&e.source).map(|l| l + 1))
+            .max()
+            .unwrap_or(0);
+        layer.insert(entry.node_id, l);
+    }
+
+    let mut rows_used: HashMap<u32, u32> = HashMap::new();
+    let mut coords: HashMap<NodeId, (u32, u32)> = HashMap::new();
+    for entry in &sched.firings {
+        let l = layer[&entry.node_id];
+        let row = rows_used.entry(l).or_insert(0);
+        let x = MARGIN + l * (NODE_WIDTH + LAYER_GAP);
+        let y = MARGIN + *row * (NODE_HEIGHT + ROW_GAP);
+        *row += 1;
+        coords.insert(entry.node_id, (x, y));
+    }
+
+    let layers = layer.values().max().map_or(0, |m| m + 1);
+    let rows = rows_used.values().max().copied().unwrap_or(0);
+    let width = 2 * MARGIN + layers * NODE_WIDTH + layers.saturating_sub(1) * LAYER_GAP;
+    let height = 2 * MARGIN + rows * NODE_HEIGHT + rows.saturating_sub(1) * ROW_GAP;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg class=\"graph\" xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\">"
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<defs><marker id=\"arrow\" markerWidth=\"8\" markerHeight=\"8\" refX=\"8\" refY=\"4\" orient=\"auto\"><path d=\"M0,0 L8,4 L0,8 z\" fill=\"#555\"/></marker></defs>"
+    )
+    .unwrap();
+
+    for edge in &sub.edges {
+        let (Some(&(sx, sy)), Some(&(tx, ty))) =
+            (coords.get(&edge.source), coords.get(&edge.target))
+        else {
+            continue;
+        };
+        let dash = if is_forward(&position, edge) {
+            ""
+        } else {
+            " stroke-dasharray=\"4 3\""
+        };
+        writeln!(
+            svg,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#555\"{} marker-end=\"url(#arrow)\"/>",
+            sx + NODE_WIDTH,
+            sy + NODE_HEIGHT / 2,
+            tx,
+            ty + NODE_HEIGHT / 2,
+            dash
+        )
+        .unwrap();
+    }
+
+    for entry in &sched.firings {
+        let (x, y) = coords[&entry.node_id];
+        let label = sub
+            .nodes
+            .iter()
+            .find(|n| n.id == entry.node_id)
+            .map(|n| crate::dot::node_label(&n.kind))
+            .unwrap_or_else(|| format!("node_{}", entry.node_id.0));
+        writeln!(
+            svg,
+            "<g><rect x=\"{x}\" y=\"{y}\" width=\"{NODE_WIDTH}\" height=\"{NODE_HEIGHT}\" rx=\"4\" fill=\"#eef3fb\" stroke=\"#4a6fa5\"/><text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{} x{}</text></g>",
+            x + NODE_WIDTH / 2,
+            y + NODE_HEIGHT / 2 + 4,
+            escape(&label),
+            entry.repetition_count
+        )
+        .unwrap();
+    }
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+fn is_forward(position: &HashMap<NodeId, usize>, edge: &Edge) -> bool {
+    matches!(
+        (position.get(&edge.source), position.get(&edge.target)),
+        (Some(sp), Some(tp)) if sp < tp
+    )
+}
+
+// ── Timing chart ────────────────────────────────────────────────────────────
+
+/// Render ASAP firing intervals as an inline SVG Gantt chart.
+fn timing_svg(sub: &Subgraph, sched: &SubgraphSchedule) -> String {
+    let firings = asap_firings(sched, sub);
+    let span = firings.iter().map(|f| f.end).max().unwrap_or(0).max(1);
+    let rows = firings.len() as u32;
+    let width = 2 * MARGIN + TIMING_LABEL_WIDTH + TIMING_WIDTH;
+    let height = 2 * MARGIN + rows * (TIMING_BAR_HEIGHT + TIMING_ROW_GAP);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg class=\"timing\" xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\">"
+    )
+    .unwrap();
+    for (i, f) in firings.iter().enumerate() {
+        let y = MARGIN + i as u32 * (TIMING_BAR_HEIGHT + TIMING_ROW_GAP);
+        let x0 = MARGIN + TIMING_LABEL_WIDTH;
+        let bar_x = x0 + (f.start * TIMING_WIDTH as u64 / span) as u32;
+        let bar_w = ((f.end - f.start) * TIMING_WIDTH as u64 / span).max(1) as u32;
+        writeln!(
+            svg,
+            "<text x=\"{MARGIN}\" y=\"{}\">{} x{}</text><rect x=\"{bar_x}\" y=\"{y}\" width=\"{bar_w}\" height=\"{TIMING_BAR_HEIGHT}\" fill=\"#7fa7d9\"><title>{}..{}</title></rect>",
+            y + TIMING_BAR_HEIGHT - 5,
+            escape(&f.label),
+            f.repetition_count,
+            f.start,
+            f.end
+        )
+        .unwrap();
+    }
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+// ── Memory and interface tables ─────────────────────────────────────────────
+
+fn write_memory(buf: &mut String, analysis: &AnalyzedProgram, subgraphs: &[ReportSubgraph]) {
+    writeln!(
+        buf,
+        "<p>Total memory: <b>{} bytes</b></p>",
+        analysis.total_memory
+    )
+    .unwrap();
+
+    if !analysis.inter_task_buffers.is_empty() {
+        let mut inter: Vec<_> = analysis.inter_task_buffers.iter().collect();
+        inter.sort_by(|a, b| a.0.cmp(b.0));
+        writeln!(buf, "<h3>Inter-task buffers</h3>").unwrap();
+        writeln!(buf, "<table><tr><th>buffer</th><th>bytes</th></tr>").unwrap();
+        for (name, bytes) in inter {
+            writeln!(buf, "<tr><td>{}</td><td>{}</td></tr>", escape(name), bytes).unwrap();
+        }
+        writeln!(buf, "</table>").unwrap();
+    }
+
+    writeln!(buf, "<h3>Intra-task edge buffers</h3>").unwrap();
+    writeln!(
+        buf,
+        "<table><tr><th>subgraph</th><th>edge</th><th>tokens</th></tr>"
+    )
+    .unwrap();
+    for s in subgraphs {
+        let mut edges: Vec<_> = s.sched.edge_buffers.iter().collect();
+        edges.sort_by_key(|((src, tgt), _)| (src.0, tgt.0));
+        for ((src, tgt), tokens) in edges {
+            writeln!(
+                buf,
+                "<tr><td>{}</td><td>{} -&gt; {}</td><td>{}</td></tr>",
+                escape(&s.heading),
+                escape(&label_of(s.sub, *src)),
+                escape(&label_of(s.sub, *tgt)),
+                tokens
+            )
+            .unwrap();
+        }
+    }
+    writeln!(buf, "</table>").unwrap();
+}
+
+fn write_interface(buf: &mut String, analysis: &AnalyzedProgram) {
+    if analysis.bind_contracts.is_empty() {
+        writeln!(buf, "<p>No bind interfaces.</p>").unwrap();
+        return;
+    }
+    let mut binds: Vec<_> = analysis.bind_contracts.iter().collect();
+    binds.sort_by(|a, b| a.0.cmp(b.0));
+    writeln!(
+        buf,
+        "<table><tr><th>bind</th><th>direction</th><th>dtype</th><th>shape</th><th>rate</th><th>version</th><th>stable id</th></tr>"
+    )
+    .unwrap();
+    for (name, c) in binds {
+        let dtype = c
+            .dtype
+            .as_ref()
+            .map_or_else(|| "?".to_string(), |t| t.to_string());
+        let shape: Vec<String> = c.shape.iter().map(|d| d.to_string()).collect();
+        let rate = c.rate_hz.map_or_else(|| "?".to_string(), format_freq);
+        let version = c.version.map_or_else(|| "-".to_string(), |v| v.to_string());
+        writeln!(
+            buf,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>[{}]</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(name),
+            c.direction,
+            escape(&dtype),
+            shape.join(", "),
+            rate,
+            version,
+            c.stable_id
+        )
+        .unwrap();
+    }
+    writeln!(buf, "</table>").unwrap();
+}
+
+fn label_of(sub: &Subgraph, id: NodeId) -> String {
+    sub.nodes
+        .iter()
+        .find(|n| n.id == id)
+        .map(|n| crate::dot::node_label(&n.kind))
+        .unwrap_or_else(|| format!("node_{}", id.0))
+}
+
+/// Escape text for HTML element content and attribute values.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Registry;
+    use std::path::PathBuf;
+
+    fn test_registry() -> Registry {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let mut reg = Registry::new();
+        for header in [
+            "runtime/libpipit/include/std_actors.h",
+            "runtime/libpipit/include/std_math.h",
+            "runtime/libpipit/include/std_sink.h",
+            "runtime/libpipit/include/std_source.h",
+        ] {
+            reg.load_header(&root.join(header))
+                .unwrap_or_else(|e| panic!("failed to load {header}: {e:?}"));
+        }
+        reg
+    }
+
+    fn build_report(source: &str) -> String {
+        let registry = test_registry();
+        let program = crate::parser::parse(source).program.expect("parse failed");
+        let mut resolve_result = crate::resolve::resolve(&program, &registry);
+        let hir_program = crate::hir::build_hir(
+            &program,
+            &resolve_result.resolved,
+            &mut resolve_result.id_alloc,
+        );
+        let graph_result =
+            crate::graph::build_graph(&hir_program, &resolve_result.resolved, &registry);
+        let type_result =
+            crate::type_infer::type_infer(&hir_program, &resolve_result.resolved, &registry);
+        let lower_result = crate::lower::lower_and_verify(
+            &hir_program,
+            &resolve_result.resolved,
+            &type_result.typed,
+            &registry,
+        );
+        let thir = crate::thir::build_thir_context(
+            &hir_program,
+            &resolve_result.resolved,
+            &type_result.typed,
+            &lower_result.lowered,
+            &registry,
+            &graph_result.graph,
+        );
+        let analysis_result = crate::analyze::analyze(&thir, &graph_result.graph);
+        let schedule_result =
+            crate::schedule::schedule(&thir, &graph_result.graph, &analysis_result.analysis);
+        emit_report(
+            "sample.pdl",
+            &graph_result.graph,
+            &analysis_result.analysis,
+            &schedule_result.schedule,
+        )
+    }
+
+    #[test]
+    fn report_contains_all_sections() {
+        let html = build_report(
+            "shared buf[256]\n\
+             clock 1kHz producer { constant(0.0) | fft(256) -> buf }\n\
+             clock 1kHz consumer { @buf | c2r() | stdout() }\n",
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        for (id, heading) in REPORT_SECTIONS {
+            assert!(
+                html.contains(&format!("<section id=\"{id}\">\n<h2>{heading}</h2>")),
+                "missing section '{id}':\n{html}"
+            );
+        }
+        assert_eq!(html.matches("<svg class=\"graph\"").count(), 2);
+        assert_eq!(html.matches("<svg class=\"timing\"").count(), 2);
+        assert!(html.contains(">fft x1</text>"), "{html}");
+        assert!(html.contains("<td>buf</td>"), "{html}");
+        assert!(!html.contains("<script"), "report must be self-contained");
+    }
+
+    #[test]
+    fn report_escapes_text_and_lists_binds() {
+        let html = build_report(
+            "bind iq = udp(\"127.0.0.1:9100\")\n\
+             clock 1kHz t { constant(0.0) -> iq }\n",
+        );
+        assert!(
+            html.contains("<td>iq</td><td>out</td><td>float</td>"),
+            "{html}"
+        );
+        assert_eq!(escape("a<b & \"c\">"), "a&lt;b &amp; &quot;c&quot;&gt;");
+    }
+}
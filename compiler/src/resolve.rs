@@ -11,13 +11,14 @@
 //                produce `Diagnostic` entries. Resolution continues past errors.
 // Side effects: none.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use crate::ast::*;
 use crate::diag::codes;
 use crate::diag::{DiagCode, DiagLevel, Diagnostic};
 use crate::id::{CallId, DefId, IdAllocator, TaskId};
-use crate::registry::Registry;
+use crate::registry::{ActorMeta, ParamKind, ParamType, Registry, TokenCount};
 
 // ── Public types ────────────────────────────────────────────────────────────
 
@@ -44,6 +45,9 @@ pub struct ResolvedProgram {
     pub call_resolutions: HashMap<CallId, CallResolution>,
     pub task_resolutions: HashMap<String, TaskResolution>,
     pub probes: Vec<ProbeEntry>,
+    /// Call span → trailing arguments filled from the actor's PARAM defaults,
+    /// appended to the call's own arguments when HIR is built.
+    pub default_args: HashMap<Span, Vec<Arg>>,
 
     // ── Stable IDs (ADR-021) ──────────────────────────────────────────────
     /// Span → CallId lookup for actor call sites.
@@ -101,6 +105,7 @@ pub struct TaskEntry {
 pub struct BindEntry {
     pub stmt_index: usize,
     pub name_span: Span,
+    pub ty: Option<BindType>,
     pub endpoint: BindEndpoint,
 }
 
@@ -144,6 +149,50 @@ pub struct ProbeEntry {
     pub context: String,
 }
 
+/// Inter-task buffer depth selected by `set buffering`.
+///
+/// Each shared ring holds `depth()` frames of the writer's per-iteration
+/// output. Writers already spin on a full ring, so a single frame trades
+/// latency (the writer waits for every reader) for space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Buffering {
+    Single,
+    #[default]
+    Double,
+    Triple,
+}
+
+impl Buffering {
+    /// Parse a `set buffering` value; None for unknown names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "single" => Some(Buffering::Single),
+            "double" => Some(Buffering::Double),
+            "triple" => Some(Buffering::Triple),
+            _ => None,
+        }
+    }
+
+    /// Number of writer frames each ring holds.
+    pub fn depth(self) -> u64 {
+        match self {
+            Buffering::Single => 1,
+            Buffering::Double => 2,
+            Buffering::Triple => 3,
+        }
+    }
+}
+
+impl fmt::Display for Buffering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Buffering::Single => "single",
+            Buffering::Double => "double",
+            Buffering::Triple => "triple",
+        })
+    }
+}
+
 // ── Public entry point ──────────────────────────────────────────────────────
 
 /// Resolve all names in a parsed Pipit program.
@@ -167,6 +216,150 @@ pub fn resolve(program: &Program, registry: &Registry) -> ResolveResult {
     }
 }
 
+/// Warn (W0003) about `const` and `param` declarations that nothing references.
+///
+/// Runs on the program *before* spawn expansion so consts used only as spawn
+/// bounds still count. References are collected syntactically from actor
+/// arguments (including array elements), shape constraints, buffer indices,
+/// shared-array sizes, switch sources, and bind endpoint arguments. Names
+/// shadowed by a define's formal parameters do not count as uses.
+pub fn unused_decl_warnings(program: &Program) -> Vec<Diagnostic> {
+    let mut used: HashSet<&str> = HashSet::new();
+    for stmt in &program.statements {
+        match &stmt.kind {
+            StatementKind::Const(c) => collect_value_refs(&c.value, &mut used),
+            StatementKind::Param(p) => collect_scalar_ref(&p.value, &mut used),
+            StatementKind::Define(d) => {
+                let formals: Vec<&str> = d.params.iter().map(|p| p.name.as_str()).collect();
+                let mut body_refs = HashSet::new();
+                collect_body_refs(&d.body, &mut body_refs);
+                used.extend(body_refs.into_iter().filter(|n| !formals.contains(n)));
+            }
+            StatementKind::Task(t) => {
+                if let Some(spawn) = &t.spawn {
+                    for bound in [&spawn.begin, &spawn.end] {
+                        if let SpawnBound::ConstRef(ident) = bound {
+                            used.insert(&ident.name);
+                        }
+                    }
+                }
+                match &t.body {
+                    TaskBody::Pipeline(body) => collect_body_refs(body, &mut used),
+                    TaskBody::Modal(modal) => {
+                        collect_body_refs(&modal.control.body, &mut used);
+                        for mode in &modal.modes {
+                            collect_body_refs(&mode.body, &mut used);
+                        }
+                        if let SwitchSource::Param(ident) = &modal.switch.source {
+                            used.insert(&ident.name);
+                        }
+                    }
+                }
+            }
+            StatementKind::Bind(b) => {
+                for arg in &b.endpoint.args {
+                    match arg {
+                        BindArg::Positional(scalar) | BindArg::Named(_, scalar) => {
+                            collect_scalar_ref(scalar, &mut used)
+                        }
+                    }
+                }
+            }
+            StatementKind::Shared(decl) => {
+                for ident in decl.size.const_refs() {
+                    used.insert(&ident.name);
+                }
+            }
+            StatementKind::Set(_) | StatementKind::Include(_) => {}
+        }
+    }
+
+    let mut diags = Vec::new();
+    for stmt in &program.statements {
+        let (kind, sigil, name) = match &stmt.kind {
+            StatementKind::Const(c) => ("const", "", &c.name),
+            StatementKind::Param(p) => ("param", "$", &p.name),
+            _ => continue,
+        };
+        if !used.contains(name.name.as_str()) {
+            diags.push(
+                Diagnostic::new(
+                    DiagLevel::Warning,
+                    name.span,
+                    format!(
+                        "{} '{}{}' is declared but never used",
+                        kind, sigil, name.name
+                    ),
+                )
+                .with_code(codes::W0003)
+                .with_hint(format!("remove the unused {} declaration", kind)),
+            );
+        }
+    }
+    diags
+}
+
+fn collect_body_refs<'p>(body: &'p PipelineBody, used: &mut HashSet<&'p str>) {
+    for line in &body.lines {
+        match &line.source {
+            PipeSource::ActorCall(call) => collect_call_refs(call, used),
+            PipeSource::BufferRead(buffer_ref) => collect_buffer_ref(buffer_ref, used),
+            PipeSource::TapRef(_) | PipeSource::TapGather(_) => {}
+        }
+        for elem in &line.elements {
+            if let PipeElem::ActorCall(call) = elem {
+                collect_call_refs(call, used);
+            }
+        }
+        if let Some(sink) = &line.sink {
+            collect_buffer_ref(&sink.buffer, used);
+        }
+    }
+}
+
+fn collect_call_refs<'p>(call: &'p ActorCall, used: &mut HashSet<&'p str>) {
+    for arg in &call.args {
+        match arg {
+            Arg::ParamRef(ident) | Arg::ConstRef(ident) => {
+                used.insert(&ident.name);
+            }
+            Arg::ConstSlice(slice) => {
+                used.insert(&slice.name.name);
+            }
+            Arg::Value(value) => collect_value_refs(value, used),
+            Arg::TapRef(_) => {}
+        }
+    }
+    if let Some(sc) = &call.shape_constraint {
+        for ident in sc.dims.iter().flat_map(ShapeDim::const_refs) {
+            used.insert(&ident.name);
+        }
+    }
+}
+
+fn collect_buffer_ref<'p>(buffer_ref: &'p BufferRef, used: &mut HashSet<&'p str>) {
+    if let BufferIndex::Ident(ident) = &buffer_ref.index {
+        used.insert(&ident.name);
+    }
+}
+
+fn collect_value_refs<'p>(value: &'p Value, used: &mut HashSet<&'p str>) {
+    match value {
+        Value::Scalar(scalar) => collect_scalar_ref(scalar, used),
+        Value::Array(elems, _) => {
+            for scalar in elems {
+                collect_scalar_ref(scalar, used);
+            }
+        }
+    }
+}
+
+fn collect_scalar_ref<'p>(scalar: &'p Scalar, used: &mut HashSet<&'p str>) {
+    if let Scalar::Ident(ident) = scalar {
+        used.insert(&ident.name);
+    }
+}
+
 // ── Internal context ────────────────────────────────────────────────────────
 
 struct PendingTapRef {
@@ -188,6 +381,11 @@ struct ResolveCtx<'a> {
     id_alloc: IdAllocator,
     /// Integer const values for buffer index resolution (v0.4.8).
     const_values: HashMap<String, u32>,
+    /// Element counts of array consts, for slice bounds checks.
+    const_array_lens: HashMap<String, usize>,
+    /// Writers of a buffer from tasks other than its first writer, in source
+    /// order, so each E0010 can point at every earlier writer.
+    extra_writers: HashMap<String, Vec<(String, Span)>>,
 }
 
 impl<'a> ResolveCtx<'a> {
@@ -205,6 +403,7 @@ impl<'a> ResolveCtx<'a> {
                 call_resolutions: HashMap::new(),
                 task_resolutions: HashMap::new(),
                 probes: Vec::new(),
+                default_args: HashMap::new(),
                 call_ids: HashMap::new(),
                 call_spans: HashMap::new(),
                 def_ids: HashMap::new(),
@@ -215,6 +414,8 @@ impl<'a> ResolveCtx<'a> {
             pending_tap_refs: Vec::new(),
             id_alloc: IdAllocator::new(),
             const_values: HashMap::new(),
+            const_array_lens: HashMap::new(),
+            extra_writers: HashMap::new(),
         }
     }
 
@@ -223,30 +424,108 @@ impl<'a> ResolveCtx<'a> {
             .push(Diagnostic::new(DiagLevel::Error, span, message).with_code(code));
     }
 
+    /// Duplicate definition; the note points at the first one, which may be
+    /// in another source file.
+    fn duplicate_error(&mut self, code: DiagCode, kind: &str, name: &str, span: Span, first: Span) {
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagLevel::Error,
+                span,
+                format!("duplicate {} '{}'", kind, name),
+            )
+            .with_code(code)
+            .with_related(first, format!("'{}' first defined here", name)),
+        );
+    }
+
     fn warning(&mut self, code: DiagCode, span: Span, message: String) {
         self.diagnostics
             .push(Diagnostic::new(DiagLevel::Warning, span, message).with_code(code));
     }
 
+    /// Check set directives whose values come from a fixed vocabulary.
+    fn validate_set(&mut self, set: &SetStmt) {
+        match set.name.name.as_str() {
+            "buffering" => self.validate_buffering(set),
+            "tick_alignment" => self.validate_tick_alignment(set),
+            _ => {}
+        }
+    }
+
+    fn validate_tick_alignment(&mut self, set: &SetStmt) {
+        let (value, span) = set_ident_value(&set.value);
+        if matches!(value, Some("true" | "false")) {
+            return;
+        }
+        let shown = value.map_or_else(
+            || "a non-identifier value".to_string(),
+            |v| format!("'{}'", v),
+        );
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagLevel::Error,
+                span,
+                format!("invalid tick_alignment value {}", shown),
+            )
+            .with_code(codes::E0046)
+            .with_hint("expected true or false"),
+        );
+    }
+
+    fn validate_buffering(&mut self, set: &SetStmt) {
+        let (value, span) = set_ident_value(&set.value);
+        if value.and_then(Buffering::from_name).is_some() {
+            return;
+        }
+        let shown = value.map_or_else(
+            || "a non-identifier value".to_string(),
+            |v| format!("'{}'", v),
+        );
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagLevel::Error,
+                span,
+                format!("unknown buffering mode {}", shown),
+            )
+            .with_code(codes::E0041)
+            .with_hint(
+                "expected single, double, or triple: single holds one writer frame per \
+                 ring (least memory, but the writer waits for readers to drain it, adding \
+                 latency), double (default) overlaps writer and reader, triple absorbs \
+                 reader jitter for throughput at 3x the memory",
+            ),
+        );
+    }
+
     // ── Pass 1: collect globals ─────────────────────────────────────────
 
     fn collect_globals(&mut self, program: &Program) {
         // Pre-scan: collect integer const values for shared-array size and buffer index resolution.
         self.const_values = crate::spawn::collect_integer_consts_from(&program.statements);
         let const_values = self.const_values.clone();
+        self.const_array_lens = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match &stmt.kind {
+                StatementKind::Const(c) => match &c.value {
+                    Value::Array(elems, _) => Some((c.name.name.clone(), elems.len())),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
 
         for (i, stmt) in program.statements.iter().enumerate() {
             match &stmt.kind {
                 StatementKind::Const(c) => {
                     let name = &c.name.name;
                     if let Some(existing) = self.resolved.consts.get(name) {
-                        self.error(
+                        self.duplicate_error(
                             codes::E0001,
+                            "const",
+                            name,
                             c.name.span,
-                            format!(
-                                "duplicate const '{}' (first defined at offset {})",
-                                name, existing.name_span.start
-                            ),
+                            existing.name_span,
                         );
                     } else {
                         let def_id = self.id_alloc.alloc_def();
@@ -263,13 +542,12 @@ impl<'a> ResolveCtx<'a> {
                 StatementKind::Param(p) => {
                     let name = &p.name.name;
                     if let Some(existing) = self.resolved.params.get(name) {
-                        self.error(
+                        self.duplicate_error(
                             codes::E0002,
+                            "param",
+                            name,
                             p.name.span,
-                            format!(
-                                "duplicate param '{}' (first defined at offset {})",
-                                name, existing.name_span.start
-                            ),
+                            existing.name_span,
                         );
                     } else {
                         let def_id = self.id_alloc.alloc_def();
@@ -286,13 +564,12 @@ impl<'a> ResolveCtx<'a> {
                 StatementKind::Define(d) => {
                     let name = &d.name.name;
                     if let Some(existing) = self.resolved.defines.get(name) {
-                        self.error(
+                        self.duplicate_error(
                             codes::E0003,
+                            "define",
+                            name,
                             d.name.span,
-                            format!(
-                                "duplicate define '{}' (first defined at offset {})",
-                                name, existing.name_span.start
-                            ),
+                            existing.name_span,
                         );
                     } else {
                         let def_id = self.id_alloc.alloc_def();
@@ -310,13 +587,12 @@ impl<'a> ResolveCtx<'a> {
                 StatementKind::Task(t) => {
                     let name = &t.name.name;
                     if let Some(existing) = self.resolved.tasks.get(name) {
-                        self.error(
+                        self.duplicate_error(
                             codes::E0004,
+                            "task",
+                            name,
                             t.name.span,
-                            format!(
-                                "duplicate task '{}' (first defined at offset {})",
-                                name, existing.name_span.start
-                            ),
+                            existing.name_span,
                         );
                     } else {
                         let task_id = self.id_alloc.alloc_task();
@@ -333,13 +609,12 @@ impl<'a> ResolveCtx<'a> {
                 StatementKind::Bind(b) => {
                     let name = &b.name.name;
                     if let Some(existing) = self.resolved.binds.get(name) {
-                        self.error(
+                        self.duplicate_error(
                             codes::E0024,
+                            "bind",
+                            name,
                             b.name.span,
-                            format!(
-                                "duplicate bind '{}' (first defined at offset {})",
-                                name, existing.name_span.start
-                            ),
+                            existing.name_span,
                         );
                     } else {
                         self.resolved.binds.insert(
@@ -347,6 +622,7 @@ impl<'a> ResolveCtx<'a> {
                             BindEntry {
                                 stmt_index: i,
                                 name_span: b.name.span,
+                                ty: b.ty.clone(),
                                 endpoint: b.endpoint.clone(),
                             },
                         );
@@ -376,7 +652,9 @@ impl<'a> ResolveCtx<'a> {
                         }
                     }
                 }
-                StatementKind::Set(_) => {}
+                StatementKind::Set(set) => self.validate_set(set),
+                // Included files are already part of the composed source.
+                StatementKind::Include(_) => {}
             }
         }
 
@@ -456,6 +734,85 @@ impl<'a> ResolveCtx<'a> {
         for (span, message) in collision_errors {
             self.error(codes::E0005, span, message);
         }
+
+        // `set affinity` names tasks, so it is checked once all are known.
+        for stmt in &program.statements {
+            if let StatementKind::Set(set) = &stmt.kind {
+                if set.name.name == "affinity" {
+                    self.validate_affinity(set);
+                }
+            }
+        }
+    }
+
+    /// Check `set affinity = { task: core, ... }`: a map from declared task
+    /// names to non-negative integer core indices.
+    fn validate_affinity(&mut self, set: &SetStmt) {
+        let SetValue::Map(entries, _) = &set.value else {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagLevel::Error,
+                    set.name.span,
+                    "set affinity expects a map of task names to core indices".to_string(),
+                )
+                .with_code(codes::E0044)
+                .with_hint("e.g. set affinity = { capture: 2, process: 3 }"),
+            );
+            return;
+        };
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+        for (task, value) in entries {
+            if let Some(&first) = seen.get(task.name.as_str()) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagLevel::Error,
+                        task.span,
+                        format!("task '{}' is pinned more than once", task.name),
+                    )
+                    .with_code(codes::E0044)
+                    .with_related(first, "first pinned here"),
+                );
+                continue;
+            }
+            seen.insert(&task.name, task.span);
+            if !self.resolved.tasks.contains_key(&task.name) {
+                let mut declared: Vec<&str> =
+                    self.resolved.tasks.keys().map(String::as_str).collect();
+                declared.sort_unstable();
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagLevel::Error,
+                        task.span,
+                        format!("unknown task '{}' in set affinity", task.name),
+                    )
+                    .with_code(codes::E0044)
+                    .with_hint(format!("declared tasks: {}", declared.join(", "))),
+                );
+            }
+            let core = match value {
+                SetValue::Number(n, _) => Some(*n).filter(|n| *n >= 0.0 && n.fract() == 0.0),
+                _ => None,
+            };
+            if core.is_none() {
+                let span = match value {
+                    SetValue::Ident(ident) => ident.span,
+                    SetValue::Number(_, s)
+                    | SetValue::Size(_, s)
+                    | SetValue::Percent(_, s)
+                    | SetValue::Freq(_, s)
+                    | SetValue::StringLit(_, s)
+                    | SetValue::Map(_, s) => *s,
+                };
+                self.error(
+                    codes::E0044,
+                    span,
+                    format!(
+                        "core index for task '{}' must be a non-negative integer",
+                        task.name
+                    ),
+                );
+            }
+        }
     }
 
     /// Resolve a `ShapeDim` to a concrete u32 size using the const value map.
@@ -463,10 +820,7 @@ impl<'a> ResolveCtx<'a> {
         dim: &ShapeDim,
         const_values: &HashMap<String, u32>,
     ) -> Option<u32> {
-        match dim {
-            ShapeDim::Literal(n, _) => Some(*n),
-            ShapeDim::ConstRef(ident) => const_values.get(&ident.name).copied(),
-        }
+        dim.fold(&|name| const_values.get(name).copied()).ok()
     }
 
     // ── Pass 2: resolve references ──────────────────────────────────────
@@ -601,19 +955,10 @@ impl<'a> ResolveCtx<'a> {
                     }
                 }
                 PipeSource::TapRef(ident) => {
-                    if let Some(info) = taps.get_mut(&ident.name) {
-                        info.consumed = true;
-                    } else {
-                        self.error(
-                            codes::E0008,
-                            ident.span,
-                            format!(
-                                "undefined tap ':{name}' in {ctx}",
-                                name = ident.name,
-                                ctx = scope.description()
-                            ),
-                        );
-                    }
+                    self.consume_tap_source(ident, scope, taps);
+                }
+                PipeSource::TapGather(gather) => {
+                    self.resolve_tap_gather(gather, line, scope, taps);
                 }
             }
 
@@ -645,6 +990,27 @@ impl<'a> ResolveCtx<'a> {
                         }
                     }
                     PipeElem::Probe(ident) => {
+                        // Probe names share one program-wide namespace (the
+                        // runtime `--probe` flags); taps are per-scope.
+                        if let Some(first) =
+                            self.resolved.probes.iter().find(|p| p.name == ident.name)
+                        {
+                            let diag = Diagnostic::new(
+                                DiagLevel::Error,
+                                ident.span,
+                                format!(
+                                    "duplicate probe '?{}' in task '{}' (first used in task '{}')",
+                                    ident.name, task_name, first.context
+                                ),
+                            )
+                            .with_code(codes::E0038)
+                            .with_related(first.span, format!("'?{}' first used here", ident.name))
+                            .with_hint(
+                                "probe names must be unique across the program; rename one of them",
+                            );
+                            self.diagnostics.push(diag);
+                            continue;
+                        }
                         self.resolved.probes.push(ProbeEntry {
                             name: ident.name.clone(),
                             span: ident.span,
@@ -665,14 +1031,32 @@ impl<'a> ResolveCtx<'a> {
                         let buf_span = sink.buffer.name.span;
                         if let Some(existing) = self.resolved.buffers.get(&buf_name) {
                             if existing.writer_task != task_name {
-                                self.error(
-                                    codes::E0010,
+                                let mut diag = Diagnostic::new(
+                                    DiagLevel::Error,
                                     buf_span,
                                     format!(
                                         "multiple writers to shared buffer '{}': first written by task '{}' (offset {})",
                                         buf_name, existing.writer_task, existing.writer_span.start
                                     ),
-                                );
+                                )
+                                .with_code(codes::E0010)
+                                .with_related(
+                                    existing.writer_span,
+                                    format!("first written by task '{}'", existing.writer_task),
+                                )
+                                .with_hint(format!(
+                                    "a shared buffer has exactly one writer task; rename one of the '-> {}' sinks",
+                                    buf_name
+                                ));
+                                let extra = self.extra_writers.entry(buf_name.clone()).or_default();
+                                for (task, span) in extra.iter() {
+                                    diag = diag.with_related(
+                                        *span,
+                                        format!("also written by task '{}'", task),
+                                    );
+                                }
+                                extra.push((task_name.clone(), buf_span));
+                                self.diagnostics.push(diag);
                             }
                             // Same task writing same buffer from multiple lines is OK
                         } else {
@@ -863,6 +1247,69 @@ impl<'a> ResolveCtx<'a> {
         }
     }
 
+    fn consume_tap_source(
+        &mut self,
+        ident: &Ident,
+        scope: &Scope,
+        taps: &mut HashMap<String, TapInfo>,
+    ) {
+        if let Some(info) = taps.get_mut(&ident.name) {
+            info.consumed = true;
+        } else {
+            self.error(
+                codes::E0008,
+                ident.span,
+                format!(
+                    "undefined tap ':{name}' in {ctx}",
+                    name = ident.name,
+                    ctx = scope.description()
+                ),
+            );
+        }
+    }
+
+    /// `gather(:a, :b, ...)` consumes each tap once and must feed an actor,
+    /// whose input concatenates the taps (E0043).
+    fn resolve_tap_gather(
+        &mut self,
+        gather: &TapGather,
+        line: &PipeExpr,
+        scope: &Scope,
+        taps: &mut HashMap<String, TapInfo>,
+    ) {
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+        for ident in &gather.taps {
+            if let Some(&first) = seen.get(ident.name.as_str()) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagLevel::Error,
+                        ident.span,
+                        format!("tap ':{}' is gathered more than once", ident.name),
+                    )
+                    .with_code(codes::E0043)
+                    .with_related(first, "first gathered here"),
+                );
+                continue;
+            }
+            seen.insert(&ident.name, ident.span);
+            self.consume_tap_source(ident, scope, taps);
+        }
+        if !matches!(line.elements.first(), Some(PipeElem::ActorCall(_))) {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagLevel::Error,
+                    gather.span,
+                    "gather(...) must be followed by an actor".to_string(),
+                )
+                .with_code(codes::E0043)
+                .with_hint(
+                    "the gathered taps are concatenated into the next actor's input, \
+                     e.g. gather(:a, :b) | add()",
+                ),
+            );
+        }
+    }
+
     fn resolve_actor_call(
         &mut self,
         call: &ActorCall,
@@ -910,9 +1357,16 @@ impl<'a> ResolveCtx<'a> {
                 .call_resolutions
                 .insert(call_id, CallResolution::Actor);
             self.validate_actor_type_args(call, name, meta.type_params.len());
+            self.validate_actor_arity(call, name, meta);
+            self.fill_default_args(call, meta);
             return;
         }
 
+        let hint = match &self.registry.lookup_fuzzy(name)[..] {
+            [] => "check actor header includes (-I flag)".to_string(),
+            [only] => format!("did you mean '{}'?", only),
+            several => format!("did you mean one of '{}'?", several.join("', '")),
+        };
         self.diagnostics.push(
             Diagnostic::new(
                 DiagLevel::Error,
@@ -920,10 +1374,96 @@ impl<'a> ResolveCtx<'a> {
                 format!("unknown actor or define '{}'", name),
             )
             .with_code(codes::E0011)
-            .with_hint("check actor header includes (-I flag)"),
+            .with_hint(hint),
+        );
+    }
+
+    /// Check the positional argument count against the actor's PARAMs (E0042).
+    /// Tap-ref inputs (`:name`) are not parameters and are not counted.
+    fn validate_actor_arity(&mut self, call: &ActorCall, name: &str, meta: &ActorMeta) {
+        let given = call
+            .args
+            .iter()
+            .filter(|a| !matches!(a, Arg::TapRef(_)))
+            .count();
+        let max = meta.params.len();
+        let required = (0..max)
+            .rev()
+            .find(|&i| !param_may_be_omitted(meta, i))
+            .map_or(0, |i| i + 1);
+        if (required..=max).contains(&given) {
+            return;
+        }
+        let expected = if required == max {
+            max.to_string()
+        } else if given > max {
+            format!("at most {}", max)
+        } else {
+            format!("at least {}", required)
+        };
+        let signature = if meta.params.is_empty() {
+            "no parameters".to_string()
+        } else {
+            meta.params
+                .iter()
+                .map(|p| format!("{}: {}", p.name, p.param_type))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        // Canonical call form, optional trailing params in brackets:
+        // `sine(freq, amp[, N])`.
+        let mut form = String::new();
+        for (i, p) in meta.params.iter().enumerate() {
+            let sep = if i == 0 { "" } else { ", " };
+            if i == required {
+                form.push('[');
+            }
+            form.push_str(sep);
+            form.push_str(&p.name);
+        }
+        if required < max {
+            form.push(']');
+        }
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagLevel::Error,
+                call.span,
+                format!(
+                    "actor '{}' expects {} argument(s) ({}), found {}",
+                    name, expected, signature, given
+                ),
+            )
+            .with_code(codes::E0042)
+            .with_hint(format!("call as {}({})", name, form)),
         );
     }
 
+    /// Record defaults for the trailing PARAMs a call omits. Filling stops at
+    /// the first omitted PARAM without a default, and — when the call has a
+    /// shape constraint — at the first port dimension, which the constraint
+    /// supplies instead.
+    fn fill_default_args(&mut self, call: &ActorCall, meta: &ActorMeta) {
+        let mut filled = Vec::new();
+        for param in meta.params.iter().skip(call.args.len()) {
+            let Some(value) = &param.default else {
+                break;
+            };
+            if call.shape_constraint.is_some() && is_port_dim(meta, &param.name) {
+                break;
+            }
+            let Ok(number) = value.parse::<f64>() else {
+                break;
+            };
+            let is_int = param.param_type == ParamType::Int;
+            filled.push(Arg::Value(Value::Scalar(Scalar::Number(
+                number, call.span, is_int,
+            ))));
+        }
+        if !filled.is_empty() {
+            self.resolved.default_args.insert(call.span, filled);
+        }
+    }
+
     fn validate_actor_type_args(&mut self, call: &ActorCall, actor_name: &str, expected: usize) {
         if call.type_args.is_empty() {
             return;
@@ -975,6 +1515,7 @@ impl<'a> ResolveCtx<'a> {
                     );
                 }
             }
+            Arg::ConstSlice(slice) => self.resolve_const_slice(slice, scope),
             Arg::Value(_) => {}
             Arg::TapRef(ident) => {
                 if let Some(info) = taps.get_mut(&ident.name) {
@@ -991,16 +1532,63 @@ impl<'a> ResolveCtx<'a> {
         }
     }
 
+    /// Check `name[start:end]`: `name` must be a global const array (not a
+    /// define formal) and the range must be non-empty and within bounds.
+    fn resolve_const_slice(&mut self, slice: &ConstSlice, scope: &Scope) {
+        let name = &slice.name.name;
+        if scope_has_formal_param(scope, name) {
+            self.error(
+                codes::E0039,
+                slice.span,
+                format!(
+                    "cannot slice define parameter '{}'; slice the const at the call site",
+                    name
+                ),
+            );
+            return;
+        }
+        if !self.resolved.consts.contains_key(name) {
+            self.error(
+                codes::E0015,
+                slice.name.span,
+                format!("undefined const '{}'", name),
+            );
+            return;
+        }
+        let Some(&len) = self.const_array_lens.get(name) else {
+            self.error(
+                codes::E0039,
+                slice.span,
+                format!("cannot slice const '{}': not an array", name),
+            );
+            return;
+        };
+        if slice.is_empty() || slice.end as usize > len {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagLevel::Error,
+                    slice.span,
+                    format!(
+                        "slice [{}:{}] out of bounds for const '{}' of length {}",
+                        slice.start, slice.end, name, len
+                    ),
+                )
+                .with_code(codes::E0039)
+                .with_hint("slice bounds are half-open: 0 <= start < end <= length"),
+            );
+        }
+    }
+
     /// Validate that all dimensions in a shape constraint are compile-time
-    /// constants (integer literals or const references). Runtime params are
-    /// forbidden in shape constraints.
+    /// constants (integer literals, const references, or arithmetic over
+    /// them). Runtime params are forbidden in shape constraints.
     fn validate_shape_constraint(
         &mut self,
         constraint: &crate::ast::ShapeConstraint,
         scope: &Scope,
     ) {
         for dim in &constraint.dims {
-            if let crate::ast::ShapeDim::ConstRef(ident) = dim {
+            for ident in dim.const_refs() {
                 if !scope_has_formal_param(scope, &ident.name) {
                     if self.resolved.params.contains_key(&ident.name) {
                         self.diagnostics.push(
@@ -1024,6 +1612,33 @@ impl<'a> ResolveCtx<'a> {
                     }
                 }
             }
+            self.validate_shape_dim_value(dim, scope);
+        }
+    }
+
+    /// Fold a shape dimension over integer consts and report (E0045) a
+    /// division by zero, a negative or overflowing step, or a zero result.
+    /// Dimensions naming a define's formal parameter are left to analysis.
+    fn validate_shape_dim_value(&mut self, dim: &ShapeDim, scope: &Scope) {
+        let lookup = |name: &str| {
+            if scope_has_formal_param(scope, name) {
+                None
+            } else {
+                self.const_values.get(name).copied()
+            }
+        };
+        match dim.fold(&lookup) {
+            Ok(0) => self.error(
+                codes::E0045,
+                dim.span(),
+                format!("shape dimension '{}' evaluates to 0; must be > 0", dim),
+            ),
+            Ok(_) => {}
+            Err(err) => {
+                if let Some(diag) = crate::spawn::shape_fold_diagnostic(dim, &err) {
+                    self.diagnostics.push(diag);
+                }
+            }
         }
     }
 
@@ -1056,6 +1671,15 @@ impl<'a> ResolveCtx<'a> {
                     );
                 }
             }
+            SwitchSource::Bind(ident) => {
+                if !self.resolved.binds.contains_key(&ident.name) {
+                    self.error(
+                        codes::E0040,
+                        ident.span,
+                        format!("undefined bind '@{}' in switch source", ident.name),
+                    );
+                }
+            }
         }
 
         // Validate mode references
@@ -1231,8 +1855,47 @@ fn scope_has_formal_param(scope: &Scope, name: &str) -> bool {
     }
 }
 
+/// Whether `name` is a symbolic dimension of the actor's input or output port.
+fn is_port_dim(meta: &ActorMeta, name: &str) -> bool {
+    meta.in_shape
+        .dims
+        .iter()
+        .chain(meta.out_shape.dims.iter())
+        .any(|d| matches!(d, TokenCount::Symbolic(sym) if sym == name))
+}
+
+/// A call may omit PARAM `i` if it has a default, is a port dimension
+/// (inferred from shape constraints, SDF balance, or span arguments), or is
+/// a span that reuses the const array passed to the preceding int PARAM.
+fn param_may_be_omitted(meta: &ActorMeta, i: usize) -> bool {
+    let param = &meta.params[i];
+    if param.default.is_some() {
+        return true;
+    }
+    match &param.param_type {
+        ParamType::Int => param.kind == ParamKind::Param && is_port_dim(meta, &param.name),
+        ParamType::SpanFloat | ParamType::SpanChar | ParamType::SpanTypeParam(_) => {
+            i > 0 && meta.params[i - 1].param_type == ParamType::Int
+        }
+        _ => false,
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
+/// Identifier text of a `set` value (None for non-identifiers) and its span.
+fn set_ident_value(value: &SetValue) -> (Option<&str>, Span) {
+    match value {
+        SetValue::Ident(ident) => (Some(ident.name.as_str()), ident.span),
+        SetValue::Number(_, s)
+        | SetValue::Size(_, s)
+        | SetValue::Percent(_, s)
+        | SetValue::Freq(_, s)
+        | SetValue::StringLit(_, s)
+        | SetValue::Map(_, s) => (None, *s),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1415,28 +2078,92 @@ mod tests {
     }
 
     #[test]
-    fn define_call_resolved() {
+    fn unknown_actor_suggests_near_names() {
         let reg = test_registry();
-        let r = resolve_ok_with(
-            "define foo() {\n    constant(0.0)\n}\nclock 1kHz t {\n    foo()\n}",
-            &reg,
+        let result = resolve_source("clock 1kHz t {\n    fftt(256)\n}", &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1);
+        let hint = errs[0].hint.as_deref().unwrap();
+        assert!(hint.starts_with("did you mean"), "{}", hint);
+        assert!(hint.contains("'fft'"), "{}", hint);
+
+        let result = resolve_source("clock 1kHz t {\n    zzzzqqq(0)\n}", &reg);
+        let errs = errors(&result);
+        assert_eq!(
+            errs[0].hint.as_deref(),
+            Some("check actor header includes (-I flag)")
         );
-        assert!(r
-            .call_resolutions
-            .values()
-            .any(|v| *v == CallResolution::Define));
     }
 
     #[test]
-    fn define_shadows_actor_warning() {
+    fn const_slice_bounds_checked() {
         let reg = test_registry();
-        let result = resolve_source(
-            "define constant() {\n    mag()\n}\nclock 1kHz t {\n    constant()\n}",
+        let table = "const coeff = [0.1, 0.2, 0.3, 0.4]\nconst g = 2\n";
+        resolve_ok_with(
+            &format!("{table}clock 1kHz t {{\n    constant(0.0) | fir(coeff[1:3]) | stdout()\n}}"),
             &reg,
         );
-        // No errors, but should have a warning
-        assert!(errors(&result).is_empty());
-        let warnings: Vec<_> = result
+        // A slice passed into a define substitutes like any other argument.
+        resolve_ok_with(
+            &format!(
+                "{table}define lp(c) {{\n    fir(c)\n}}\n\
+                 clock 1kHz t {{\n    constant(0.0) | lp(coeff[0:4]) | stdout()\n}}"
+            ),
+            &reg,
+        );
+
+        for (arg, expected) in [
+            (
+                "coeff[2:5]",
+                "slice [2:5] out of bounds for const 'coeff' of length 4",
+            ),
+            ("coeff[2:2]", "slice [2:2] out of bounds"),
+            ("g[0:1]", "cannot slice const 'g': not an array"),
+        ] {
+            let result = resolve_source(
+                &format!("{table}clock 1kHz t {{\n    constant(0.0) | fir({arg}) | stdout()\n}}"),
+                &reg,
+            );
+            let errs = errors(&result);
+            assert_eq!(errs.len(), 1, "{arg}: {:#?}", errs);
+            assert_eq!(errs[0].code, Some(codes::E0039));
+            assert!(errs[0].message.contains(expected), "{}", errs[0].message);
+        }
+
+        let result = resolve_source(
+            &format!("{table}define lp(c) {{\n    fir(c[0:2])\n}}\nclock 1kHz t {{\n    constant(0.0) | lp(coeff) | stdout()\n}}"),
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs[0].code, Some(codes::E0039));
+        assert!(errs[0]
+            .message
+            .contains("cannot slice define parameter 'c'"));
+    }
+
+    #[test]
+    fn define_call_resolved() {
+        let reg = test_registry();
+        let r = resolve_ok_with(
+            "define foo() {\n    constant(0.0)\n}\nclock 1kHz t {\n    foo()\n}",
+            &reg,
+        );
+        assert!(r
+            .call_resolutions
+            .values()
+            .any(|v| *v == CallResolution::Define));
+    }
+
+    #[test]
+    fn define_shadows_actor_warning() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "define constant() {\n    mag()\n}\nclock 1kHz t {\n    constant()\n}",
+            &reg,
+        );
+        // No errors, but should have a warning
+        assert!(errors(&result).is_empty());
+        let warnings: Vec<_> = result
             .diagnostics
             .iter()
             .filter(|d| d.level == DiagLevel::Warning)
@@ -1445,6 +2172,142 @@ mod tests {
         assert!(warnings[0].message.contains("shadows actor"));
     }
 
+    #[test]
+    fn omitted_trailing_args_filled_from_defaults() {
+        let mut reg = test_registry();
+        let mut meta = reg.lookup("fft").unwrap().clone();
+        meta.name = "blk".to_string();
+        meta.params[0].default = Some("256".to_string());
+        meta.params.push(crate::registry::ActorParam {
+            kind: crate::registry::ParamKind::Param,
+            param_type: ParamType::Float,
+            name: "gain".to_string(),
+            default: Some("0.5".to_string()),
+        });
+        reg.insert(meta);
+
+        let filled = |call: &str| -> Vec<f64> {
+            let r = resolve_ok_with(
+                &format!(
+                    "clock 1kHz t {{\n    constant(0.0) | {} | stdout()\n}}",
+                    call
+                ),
+                &reg,
+            );
+            r.default_args
+                .values()
+                .flatten()
+                .map(|arg| match arg {
+                    Arg::Value(Value::Scalar(Scalar::Number(n, _, _))) => *n,
+                    other => panic!("unexpected default arg {:?}", other),
+                })
+                .collect()
+        };
+        assert_eq!(filled("blk()"), vec![256.0, 0.5]);
+        assert_eq!(filled("blk(64)"), vec![0.5]);
+        assert_eq!(filled("blk(64, 2.0)"), Vec::<f64>::new());
+        // The shape constraint supplies N, so nothing past it is filled.
+        assert_eq!(filled("blk()[64]"), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn tap_gather_consumes_taps() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "clock 1kHz t {\n    constant(1.0) | :a | stdout()\n    \
+             constant(2.0) | :b | stdout()\n    gather(:a, :b) | add() | stdout()\n}",
+            &reg,
+        );
+        assert!(errors(&result).is_empty(), "{:#?}", errors(&result));
+    }
+
+    #[test]
+    fn tap_gather_duplicate_tap_error() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "clock 1kHz t {\n    constant(1.0) | :a | stdout()\n    \
+             gather(:a, :a) | add() | stdout()\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0043));
+        assert_eq!(errs[0].message, "tap ':a' is gathered more than once");
+        assert_eq!(errs[0].related_spans[0].label, "first gathered here");
+    }
+
+    #[test]
+    fn tap_gather_requires_following_actor() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "clock 1kHz t {\n    constant(1.0) | :a | stdout()\n    \
+             constant(2.0) | :b | stdout()\n    gather(:a, :b) -> out\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0043));
+        assert_eq!(errs[0].message, "gather(...) must be followed by an actor");
+    }
+
+    #[test]
+    fn tap_gather_undefined_tap_error() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "clock 1kHz t {\n    constant(1.0) | :a | stdout()\n    \
+             gather(:a, :nope) | add() | stdout()\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0008));
+    }
+
+    #[test]
+    fn too_many_actor_args_error() {
+        let reg = test_registry();
+        let result = resolve_source("clock 1kHz t {\n    constant(0.0) | fft(256, 3)\n}", &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0042));
+        assert_eq!(
+            errs[0].message,
+            "actor 'fft' expects at most 1 argument(s) (N: int), found 2"
+        );
+        assert_eq!(errs[0].hint.as_deref(), Some("call as fft([N])"));
+    }
+
+    #[test]
+    fn too_few_actor_args_error() {
+        let reg = test_registry();
+        let result = resolve_source("clock 1kHz t {\n    sine(100.0) | stdout()\n}", &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0042));
+        assert_eq!(
+            errs[0].message,
+            "actor 'sine' expects at least 2 argument(s) (freq: float, amp: float, N: int), found 1"
+        );
+        assert_eq!(
+            errs[0].hint.as_deref(),
+            Some("call as sine(freq, amp[, N])")
+        );
+    }
+
+    #[test]
+    fn omittable_params_not_counted_as_required() {
+        let reg = test_registry();
+        // Port dims (fft's N, sine's N), span-inferred dims (fir's N), and
+        // tap-ref inputs do not count toward the positional arity.
+        resolve_ok_with(
+            "const coeff = [0.5, 0.5]\n\
+             clock 1kHz t {\n    \
+             sine(100.0, 1.0) | fft()[256] | mag() | stdout()\n    \
+             constant(0.0) | fir(coeff) | :a | add(:a) | stdout()\n}",
+            &reg,
+        );
+    }
+
     // ── Param/const refs ────────────────────────────────────────────────
 
     #[test]
@@ -1462,6 +2325,42 @@ mod tests {
         assert!(errs[0].message.contains("undefined param '$unknown'"));
     }
 
+    #[test]
+    fn hex_and_binary_literals_resolve_as_integers() {
+        let reg = test_registry();
+        resolve_ok_with(
+            concat!(
+                "const coeff = [0.1, 0.2, 0.3, 0.4]\n",
+                "set affinity = { t: 0b1010 }\n",
+                "clock 1kHz t {\n    constant(0.0) | fir(coeff[0b1:0x3]) | stdout()\n}",
+            ),
+            &reg,
+        );
+
+        let result = resolve_source(
+            concat!(
+                "const coeff = [0.1, 0.2, 0.3, 0.4]\n",
+                "clock 1kHz t {\n    constant(0.0) | fir(coeff[0x2:0b101]) | stdout()\n}",
+            ),
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert!(errs[0].message.contains("slice [2:5] out of bounds"));
+    }
+
+    #[test]
+    fn invalid_hex_literal_rejected_before_resolve() {
+        let result = crate::parser::parse("const mask = 0xG\n");
+        assert!(
+            result.errors.iter().any(|e| e
+                .to_string()
+                .contains("invalid hexadecimal literal: \"0xG\"")),
+            "{:?}",
+            result.errors
+        );
+    }
+
     #[test]
     fn const_ref_resolved() {
         let reg = test_registry();
@@ -1523,6 +2422,55 @@ mod tests {
         assert!(errs[0].message.contains("multiple writers"));
     }
 
+    #[test]
+    fn multiple_writers_error_relates_every_writer() {
+        let reg = test_registry();
+        let src = concat!(
+            "clock 1kHz a {\n    constant(0.0) -> sig\n}\n",
+            "clock 1kHz b {\n    constant(0.0) -> sig\n}\n",
+            "clock 1kHz c {\n    constant(0.0) -> sig\n}\n",
+            "clock 1kHz d {\n    @sig | stdout()\n}",
+        );
+        let result = resolve_source(src, &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 2, "{:#?}", errs);
+        let writer_offsets: Vec<usize> = src.match_indices("-> sig").map(|(i, _)| i + 3).collect();
+        assert_eq!(errs[0].code, Some(codes::E0010));
+        assert_eq!(errs[0].span.start, writer_offsets[1]);
+        let related: Vec<(usize, &str)> = errs[0]
+            .related_spans
+            .iter()
+            .map(|r| (r.span.start, r.label.as_str()))
+            .collect();
+        assert_eq!(related, [(writer_offsets[0], "first written by task 'a'")]);
+
+        assert_eq!(errs[1].span.start, writer_offsets[2]);
+        let related: Vec<(usize, &str)> = errs[1]
+            .related_spans
+            .iter()
+            .map(|r| (r.span.start, r.label.as_str()))
+            .collect();
+        assert_eq!(
+            related,
+            [
+                (writer_offsets[0], "first written by task 'a'"),
+                (writer_offsets[1], "also written by task 'b'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_writer_task_with_several_sinks_ok() {
+        let reg = test_registry();
+        let _ = resolve_ok_with(
+            concat!(
+                "clock 1kHz a {\n    constant(0.0) -> sig\n    constant(1.0) -> sig\n}\n",
+                "clock 1kHz b {\n    @sig | stdout()\n}",
+            ),
+            &reg,
+        );
+    }
+
     // ── Taps ────────────────────────────────────────────────────────────
 
     #[test]
@@ -1571,6 +2519,37 @@ mod tests {
         assert!(errs[0].message.contains("declared but never consumed"));
     }
 
+    // ── Probes ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn probe_duplicate_across_tasks_error() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "clock 1kHz a {\n    constant(0.0) | ?mon | stdout()\n}\nclock 1kHz b {\n    constant(0.0) | ?mon | stdout()\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "got: {:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0038));
+        assert!(errs[0]
+            .message
+            .contains("duplicate probe '?mon' in task 'b' (first used in task 'a')"));
+        let related = &errs[0].related_spans;
+        assert_eq!(related.len(), 1);
+        assert!(related[0].span.start < errs[0].span.start);
+        assert_eq!(result.resolved.probes.len(), 1);
+    }
+
+    #[test]
+    fn probe_and_tap_may_share_a_name() {
+        let reg = test_registry();
+        let resolved = resolve_ok_with(
+            "clock 1kHz t {\n    constant(0.0) | :mon | ?mon | stdout()\n    :mon | stdout()\n}",
+            &reg,
+        );
+        assert_eq!(resolved.probes.len(), 1);
+    }
+
     // ── Tap-ref as actor arg ───────────────────────────────────────────
 
     #[test]
@@ -1786,6 +2765,180 @@ mod tests {
             .any(|e| e.message.contains("undefined param '$missing'")));
     }
 
+    #[test]
+    fn switch_bind_source() {
+        let reg = test_registry();
+        let _ = resolve_ok_with(
+            concat!(
+                "bind sel = udp(\"127.0.0.1:9200\")\n",
+                "clock 1kHz t {\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(@sel, a, b)\n",
+                "}"
+            ),
+            &reg,
+        );
+    }
+
+    #[test]
+    fn switch_bind_source_undefined() {
+        let reg = test_registry();
+        let result = resolve_source(
+            concat!(
+                "clock 1kHz t {\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(@sel, a, b)\n",
+                "}"
+            ),
+            &reg,
+        );
+        let errs = errors(&result);
+        assert!(errs
+            .iter()
+            .any(|e| e.code == Some(codes::E0040) && e.message.contains("undefined bind '@sel'")));
+    }
+
+    // ── Set directive checks ─────────────────────────────────────────
+
+    #[test]
+    fn set_buffering_known_modes_ok() {
+        let reg = test_registry();
+        for mode in ["single", "double", "triple"] {
+            let src = format!(
+                "set buffering = {}\nclock 1kHz t {{\n    constant(0.0) | stdout()\n}}",
+                mode
+            );
+            let result = resolve_source(&src, &reg);
+            assert!(
+                errors(&result).is_empty(),
+                "{}: {:#?}",
+                mode,
+                errors(&result)
+            );
+        }
+    }
+
+    #[test]
+    fn set_buffering_unknown_mode_error() {
+        let reg = test_registry();
+        let src = "set buffering = quad\nclock 1kHz t {\n    constant(0.0) | stdout()\n}";
+        let result = resolve_source(src, &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0041));
+        assert_eq!(errs[0].span.start, src.find("quad").unwrap());
+        assert!(errs[0].message.contains("'quad'"));
+        let hint = errs[0].hint.as_deref().unwrap();
+        assert!(hint.contains("latency") && hint.contains("throughput"));
+    }
+
+    #[test]
+    fn set_buffering_number_error() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "set buffering = 2\nclock 1kHz t {\n    constant(0.0) | stdout()\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0041));
+    }
+
+    #[test]
+    fn set_tick_alignment_bool_ok() {
+        let reg = test_registry();
+        for value in ["true", "false"] {
+            let src = format!(
+                "set tick_alignment = {}\nclock 1kHz t {{\n    constant(0.0) | stdout()\n}}",
+                value
+            );
+            let result = resolve_source(&src, &reg);
+            assert!(
+                errors(&result).is_empty(),
+                "{}: {:#?}",
+                value,
+                errors(&result)
+            );
+        }
+    }
+
+    #[test]
+    fn set_tick_alignment_invalid_value_error() {
+        let reg = test_registry();
+        for value in ["yes", "1"] {
+            let src = format!(
+                "set tick_alignment = {}\nclock 1kHz t {{\n    constant(0.0) | stdout()\n}}",
+                value
+            );
+            let result = resolve_source(&src, &reg);
+            let errs = errors(&result);
+            assert_eq!(errs.len(), 1, "{}: {:#?}", value, errs);
+            assert_eq!(errs[0].code, Some(codes::E0046));
+            assert_eq!(errs[0].span.start, src.find(value).unwrap());
+        }
+    }
+
+    #[test]
+    fn set_affinity_ok() {
+        let reg = test_registry();
+        let result = resolve_source(
+            concat!(
+                "set affinity = { rx: 2, tx: 0 }\n",
+                "clock 1kHz rx {\n    constant(0.0) | stdout()\n}\n",
+                "clock 1kHz tx {\n    constant(0.0) | stdout()\n}",
+            ),
+            &reg,
+        );
+        assert!(errors(&result).is_empty(), "{:#?}", errors(&result));
+    }
+
+    #[test]
+    fn set_affinity_unknown_task_error() {
+        let reg = test_registry();
+        let src =
+            "set affinity = { rx: 2, nope: 3 }\nclock 1kHz rx {\n    constant(0.0) | stdout()\n}";
+        let result = resolve_source(src, &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0044));
+        assert_eq!(errs[0].message, "unknown task 'nope' in set affinity");
+        assert_eq!(errs[0].span.start, src.find("nope").unwrap());
+        assert_eq!(errs[0].hint.as_deref(), Some("declared tasks: rx"));
+    }
+
+    #[test]
+    fn set_affinity_core_must_be_non_negative_integer() {
+        let reg = test_registry();
+        for core in ["-1", "1.5", "fast"] {
+            let src = format!(
+                "set affinity = {{ rx: {} }}\nclock 1kHz rx {{\n    constant(0.0) | stdout()\n}}",
+                core
+            );
+            let result = resolve_source(&src, &reg);
+            let errs = errors(&result);
+            assert_eq!(errs.len(), 1, "{}: {:#?}", core, errs);
+            assert_eq!(errs[0].code, Some(codes::E0044));
+            assert_eq!(
+                errs[0].message,
+                "core index for task 'rx' must be a non-negative integer"
+            );
+        }
+    }
+
+    #[test]
+    fn set_affinity_requires_map() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "set affinity = 2\nclock 1kHz rx {\n    constant(0.0) | stdout()\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0044));
+    }
+
     // ── Mode coverage checks ─────────────────────────────────────────
 
     #[test]
@@ -1850,6 +3003,53 @@ mod tests {
         let _ = resolve_ok_with("const N = 256\nclock 1kHz t {\n    fft()[N]\n}", &reg);
     }
 
+    #[test]
+    fn shape_constraint_arithmetic_ok() {
+        let reg = test_registry();
+        let _ = resolve_ok_with(
+            "const N = 256\nclock 1kHz t {\n    fft()[N*2]\n    fft()[N/2]\n}",
+            &reg,
+        );
+    }
+
+    #[test]
+    fn shape_constraint_division_by_zero_error() {
+        let reg = test_registry();
+        let result = resolve_source("clock 1kHz t {\n    fft()[0/0]\n}", &reg);
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 1, "{:#?}", errs);
+        assert_eq!(errs[0].code, Some(codes::E0045));
+        assert!(
+            errs[0]
+                .message
+                .contains("division by zero in shape dimension '0/0'"),
+            "{}",
+            errs[0].message
+        );
+    }
+
+    #[test]
+    fn shape_constraint_negative_or_zero_result_error() {
+        let reg = test_registry();
+        let result = resolve_source(
+            "const N = 4\nclock 1kHz t {\n    fft()[N-8+16]\n    fft()[N-N]\n}",
+            &reg,
+        );
+        let errs = errors(&result);
+        assert_eq!(errs.len(), 2, "{:#?}", errs);
+        assert!(errs.iter().all(|e| e.code == Some(codes::E0045)));
+        assert!(
+            errs[0].message.contains("goes negative (-4)"),
+            "{}",
+            errs[0].message
+        );
+        assert!(
+            errs[1].message.contains("evaluates to 0"),
+            "{}",
+            errs[1].message
+        );
+    }
+
     #[test]
     fn shape_constraint_param_ref_error() {
         let reg = test_registry();
@@ -2139,4 +3339,72 @@ clock 1kHz t {
             errs
         );
     }
+
+    // ── Unused const/param lint (W0003) ─────────────────────────────────
+
+    fn unused_names(source: &str) -> Vec<String> {
+        let result = crate::parser::parse(source);
+        let program = result.program.expect("parse failed in test");
+        unused_decl_warnings(&program)
+            .into_iter()
+            .inspect(|d| assert_eq!(d.code, Some(codes::W0003)))
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn unused_const_and_param_warned() {
+        let msgs = unused_names(concat!(
+            "const N = 256\nconst DEAD = 3\n",
+            "param gain = 1.0\nparam old = 2.0\n",
+            "clock 1kHz t {\n    constant(0.0) | mul($gain) | fft(N) | c2r(N) | stdout()\n}\n",
+        ));
+        assert_eq!(
+            msgs,
+            vec![
+                "const 'DEAD' is declared but never used".to_string(),
+                "param '$old' is declared but never used".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_lint_counts_shape_constraint_and_switch_param() {
+        let msgs = unused_names(concat!(
+            "const N = 64\nparam sel = 0\n",
+            "clock 1kHz t {\n",
+            "    control {\n        constant(0.0) | stdout()\n    }\n",
+            "    mode a {\n        constant(0.0) | fft()[N] | stdout()\n    }\n",
+            "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+            "    switch($sel, a, b)\n",
+            "}\n",
+        ));
+        assert!(msgs.is_empty(), "{msgs:?}");
+    }
+
+    #[test]
+    fn unused_lint_counts_spawn_bounds_shared_sizes_and_binds() {
+        let msgs = unused_names(concat!(
+            "const CH = 2\nconst ADDR = \"127.0.0.1:9100\"\nconst IDX = 1\n",
+            "shared buf[CH]\n",
+            "bind out = udp(ADDR)\n",
+            "clock 1kHz w[ch=0..CH] {\n    constant(0.0) -> buf[ch]\n}\n",
+            "clock 1kHz r {\n    @buf[IDX] -> out\n}\n",
+        ));
+        assert!(msgs.is_empty(), "{msgs:?}");
+    }
+
+    #[test]
+    fn unused_lint_ignores_define_formal_shadowing() {
+        // `N` inside the define is the formal parameter, not the const
+        let msgs = unused_names(concat!(
+            "const N = 8\n",
+            "define d(N) {\n    constant(0.0) | fft(N) | c2r(N) | stdout()\n}\n",
+            "clock 1kHz t {\n    d(16)\n}\n",
+        ));
+        assert_eq!(
+            msgs,
+            vec!["const 'N' is declared but never used".to_string()]
+        );
+    }
 }
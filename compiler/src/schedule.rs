@@ -2,17 +2,19 @@
 //
 // Generates a Periodic Asynchronous Static Schedule (PASS) for each task.
 // Per-task topological sort of actors in dependency order, with each actor
-// firing repetition_vector[node] times per PASS cycle.
+// firing repetition_vector[node] times per PASS cycle. Actors annotated with
+// `@atomic_group(name)` are ordered as one indivisible unit.
 //
 // Preconditions: `thir` is a ThirContext wrapping HIR + resolved/typed/lowered;
 //                `graph` is a valid ProgramGraph with detected cycles;
 //                `analysis` has computed repetition vectors.
 // Postconditions: returns `ScheduleResult` with per-task schedules, K factors,
 //                 and intra-task buffer sizes.
-// Failure modes: unsortable subgraphs produce `Diagnostic` entries.
+// Failure modes: unsortable subgraphs and invalid atomic groups produce
+//                `Diagnostic` entries.
 // Side effects: none.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fmt;
 
 use chumsky::span::Span as _;
@@ -265,6 +267,13 @@ impl<'a> ScheduleCtx<'a> {
             None => return,
         };
 
+        if !self.check_atomic_group_subgraphs(task_name, task_graph) {
+            return;
+        }
+        if !self.check_persist_annotations(task_name, task_graph) {
+            return;
+        }
+
         let task_schedule = match task_graph {
             TaskGraph::Pipeline(sub) => {
                 let rv_key = (task_name.to_string(), "pipeline".to_string());
@@ -278,10 +287,14 @@ impl<'a> ScheduleCtx<'a> {
             }
             TaskGraph::Modal { control, modes } => {
                 let ctrl_rv_key = (task_name.to_string(), "control".to_string());
+                // `switch($param, ...)` / `switch(@bind, ...)` may omit the
+                // control block; its empty subgraph has no repetition vector.
+                let empty_rv = HashMap::new();
                 let ctrl_sched = self
                     .analysis
                     .repetition_vectors
                     .get(&ctrl_rv_key)
+                    .or(control.nodes.is_empty().then_some(&empty_rv))
                     .and_then(|rv| self.sort_subgraph(task_name, "control", control, rv));
 
                 let ctrl_sched = match ctrl_sched {
@@ -324,6 +337,27 @@ impl<'a> ScheduleCtx<'a> {
             );
         }
 
+        // Batching K firings per wake delays the first of them by up to K
+        // clock periods.
+        if let Some(max_ms) = self.thir.max_latency_ms {
+            let batch_ms = k as f64 * 1000.0 / freq_hz;
+            if batch_ms > max_ms {
+                self.warning(
+                    codes::W0401,
+                    freq_span,
+                    format!(
+                        "task '{}' batches K={} iterations per tick: batched latency {:.3}ms \
+                         (clock period {:.3}ms) exceeds max_latency_ms = {}",
+                        task_name,
+                        k,
+                        batch_ms,
+                        1000.0 / freq_hz,
+                        max_ms
+                    ),
+                );
+            }
+        }
+
         self.task_schedules.insert(
             task_name.to_string(),
             TaskMeta {
@@ -353,21 +387,39 @@ impl<'a> ScheduleCtx<'a> {
         // Identify back-edges from feedback cycles (delay actors break cycles)
         let back_edges = self.identify_back_edges(sub);
 
-        // Build in-degree map and adjacency list (excluding back-edges)
+        // Atomic groups collapse into a single scheduling unit keyed by their
+        // first member; every other node is its own unit.
+        let groups = self.collect_atomic_groups(task_name, label, sub, &back_edges)?;
+        let mut unit_of: HashMap<NodeId, NodeId> = HashMap::new();
+        let mut unit_members: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for members in groups {
+            for &m in &members {
+                unit_of.insert(m, members[0]);
+            }
+            unit_members.insert(members[0], members);
+        }
+        let unit = |id: NodeId| unit_of.get(&id).copied().unwrap_or(id);
+
+        // Build in-degree map and adjacency list over units (excluding back-edges
+        // and edges internal to a group)
         let mut in_degree: HashMap<NodeId, u32> = HashMap::new();
         let mut adj: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
 
         for node in &sub.nodes {
-            in_degree.entry(node.id).or_insert(0);
-            adj.entry(node.id).or_default();
+            in_degree.entry(unit(node.id)).or_insert(0);
+            adj.entry(unit(node.id)).or_default();
         }
 
         for edge in &sub.edges {
             if back_edges.contains(&(edge.source, edge.target)) {
                 continue;
             }
-            *in_degree.entry(edge.target).or_insert(0) += 1;
-            adj.entry(edge.source).or_default().push(edge.target);
+            let (src, tgt) = (unit(edge.source), unit(edge.target));
+            if src == tgt {
+                continue;
+            }
+            *in_degree.entry(tgt).or_insert(0) += 1;
+            adj.entry(src).or_default().push(tgt);
         }
 
         // Pre-sort each adjacency list once for deterministic traversal order.
@@ -375,7 +427,14 @@ impl<'a> ScheduleCtx<'a> {
             neighbors.sort_by_key(|id| id.0);
         }
 
-        // Kahn's algorithm with deterministic ordering (sort by NodeId)
+        // Kahn's algorithm with deterministic ordering (sort by NodeId).
+        //
+        // Invariant: the firing order depends only on the graph, never on
+        // HashMap iteration. `in_degree`, `adj` and `unit_members` are
+        // HashMaps, so only keyed lookups are allowed below; the ready queue
+        // starts sorted by NodeId and successors are released in sorted
+        // adjacency order. Two builds of one program fire actors in the same
+        // order, which A/B comparisons of numerical output rely on.
         let mut queue: Vec<NodeId> = in_degree
             .iter()
             .filter(|(_, &deg)| deg == 0)
@@ -386,14 +445,23 @@ impl<'a> ScheduleCtx<'a> {
 
         let mut firings = Vec::new();
 
-        while let Some(node_id) = queue.pop_front() {
-            let count = rv.get(&node_id).copied().unwrap_or(1);
-            firings.push(FiringEntry {
-                node_id,
-                repetition_count: count,
-            });
+        while let Some(unit_id) = queue.pop_front() {
+            match unit_members.get(&unit_id) {
+                Some(members) => {
+                    for &node_id in members {
+                        firings.push(FiringEntry {
+                            node_id,
+                            repetition_count: rv.get(&node_id).copied().unwrap_or(1),
+                        });
+                    }
+                }
+                None => firings.push(FiringEntry {
+                    node_id: unit_id,
+                    repetition_count: rv.get(&unit_id).copied().unwrap_or(1),
+                }),
+            }
 
-            if let Some(neighbors) = adj.get(&node_id) {
+            if let Some(neighbors) = adj.get(&unit_id) {
                 for &next in neighbors {
                     if let Some(deg) = in_degree.get_mut(&next) {
                         *deg -= 1;
@@ -437,6 +505,167 @@ impl<'a> ScheduleCtx<'a> {
         })
     }
 
+    // ── Atomic groups ───────────────────────────────────────────────────
+
+    /// Members of each `@atomic_group` in a subgraph, in declared order.
+    ///
+    /// Consecutive members must be joined by a direct forward edge so the
+    /// group can fire as one contiguous block. Returns `None` (after
+    /// reporting E0402) if any group is not such a chain.
+    fn collect_atomic_groups(
+        &mut self,
+        task_name: &str,
+        label: &str,
+        sub: &Subgraph,
+        back_edges: &HashSet<(NodeId, NodeId)>,
+    ) -> Option<Vec<Vec<NodeId>>> {
+        let mut by_name: BTreeMap<&str, Vec<&Node>> = BTreeMap::new();
+        for node in &sub.nodes {
+            if let NodeKind::Actor {
+                atomic_group: Some(group),
+                ..
+            } = &node.kind
+            {
+                by_name.entry(group.as_str()).or_default().push(node);
+            }
+        }
+
+        let mut groups = Vec::new();
+        let mut ok = true;
+        for (group, mut members) in by_name {
+            members.sort_by_key(|n| (n.span.start, n.id.0));
+            for pair in members.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let linked = sub.edges.iter().any(|e| {
+                    e.source == a.id
+                        && e.target == b.id
+                        && !back_edges.contains(&(e.source, e.target))
+                });
+                if !linked {
+                    self.diagnostics.push(
+                        Diagnostic::new(
+                            DiagLevel::Error,
+                            b.span,
+                            format!(
+                                "atomic group '{}' in {} of task '{}' is not a connected \
+                                 chain: '{}' is not fed directly by '{}'",
+                                group,
+                                label,
+                                task_name,
+                                node_display_name(b),
+                                node_display_name(a)
+                            ),
+                        )
+                        .with_code(codes::E0402)
+                        .with_related(a.span, "previous group member")
+                        .with_hint(
+                            "annotate consecutive actors of a single pipe, in dataflow order",
+                        ),
+                    );
+                    ok = false;
+                    break;
+                }
+            }
+            groups.push(members.iter().map(|n| n.id).collect());
+        }
+
+        ok.then_some(groups)
+    }
+
+    /// Report E0401 for any atomic group whose members are spread across
+    /// the control and mode subgraphs of a modal task.
+    fn check_atomic_group_subgraphs(&mut self, task_name: &str, task_graph: &TaskGraph) -> bool {
+        let subgraphs: Vec<(&str, &Subgraph)> = match task_graph {
+            TaskGraph::Pipeline(_) => return true,
+            TaskGraph::Modal { control, modes } => std::iter::once(("control", control))
+                .chain(modes.iter().map(|(name, sub)| (name.as_str(), sub)))
+                .collect(),
+        };
+
+        let mut home: BTreeMap<&str, (&str, Span)> = BTreeMap::new();
+        let mut ok = true;
+        for (label, sub) in subgraphs {
+            for node in &sub.nodes {
+                let NodeKind::Actor {
+                    atomic_group: Some(group),
+                    ..
+                } = &node.kind
+                else {
+                    continue;
+                };
+                match home.get(group.as_str()) {
+                    None => {
+                        home.insert(group.as_str(), (label, node.span));
+                    }
+                    Some(&(first_label, first_span)) if first_label != label => {
+                        self.diagnostics.push(
+                            Diagnostic::new(
+                                DiagLevel::Error,
+                                node.span,
+                                format!(
+                                    "atomic group '{}' of task '{}' spans subgraphs '{}' and \
+                                     '{}'; all members must be in the same subgraph",
+                                    group, task_name, first_label, label
+                                ),
+                            )
+                            .with_code(codes::E0401)
+                            .with_related(first_span, "first group member"),
+                        );
+                        ok = false;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        ok
+    }
+
+    /// Report E0403 for `@persist` on anything but a `delay` inside a mode:
+    /// only mode subgraphs have their feedback state reset on a switch.
+    fn check_persist_annotations(&mut self, task_name: &str, task_graph: &TaskGraph) -> bool {
+        let subgraphs: Vec<(&str, &Subgraph, bool)> = match task_graph {
+            TaskGraph::Pipeline(sub) => vec![("pipeline", sub, false)],
+            TaskGraph::Modal { control, modes } => std::iter::once(("control", control, false))
+                .chain(modes.iter().map(|(name, sub)| (name.as_str(), sub, true)))
+                .collect(),
+        };
+
+        let mut ok = true;
+        for (label, sub, in_mode) in subgraphs {
+            for node in &sub.nodes {
+                let NodeKind::Actor {
+                    name,
+                    persist: true,
+                    ..
+                } = &node.kind
+                else {
+                    continue;
+                };
+                let message = if name != "delay" {
+                    format!("@persist applies only to delay, not '{}'", name)
+                } else if !in_mode {
+                    let place = if label == "pipeline" {
+                        "the pipeline"
+                    } else {
+                        "the control subgraph"
+                    };
+                    format!(
+                        "@persist delay in {} of task '{}' is never reset; \
+                         @persist applies only to delays inside a mode",
+                        place, task_name
+                    )
+                } else {
+                    continue;
+                };
+                self.diagnostics.push(
+                    Diagnostic::new(DiagLevel::Error, node.span, message).with_code(codes::E0403),
+                );
+                ok = false;
+            }
+        }
+        ok
+    }
+
     // ── Back-edge identification ────────────────────────────────────────
 
     fn identify_back_edges(&self, sub: &Subgraph) -> HashSet<(NodeId, NodeId)> {
@@ -456,9 +685,12 @@ impl<'a> ScheduleCtx<'a> {
         for edge in &sub.edges {
             if back_edges.contains(&(edge.source, edge.target)) {
                 // Back-edge: buffer holds initial tokens from delay actor
-                let tokens = self.delay_initial_tokens(sub, edge.source);
-                buffers.insert((edge.source, edge.target), tokens);
-                continue;
+                if let Some(tokens) = self.delay_initial_tokens(sub, edge.source) {
+                    buffers.insert((edge.source, edge.target), tokens);
+                    continue;
+                }
+                // `@allow_combinational_loop` back-edge: sized like a forward
+                // edge, read one PASS cycle late
             }
 
             let p = self.node_out_rate(edge.source).unwrap_or(1);
@@ -469,18 +701,20 @@ impl<'a> ScheduleCtx<'a> {
         buffers
     }
 
-    /// Get the initial token count from a delay actor (first arg).
-    fn delay_initial_tokens(&self, sub: &Subgraph, node_id: NodeId) -> u32 {
-        if let Some(node) = find_node(sub, node_id) {
-            if let NodeKind::Actor { args, name, .. } = &node.kind {
-                if name == "delay" {
-                    if let Some(Arg::Value(Value::Scalar(Scalar::Number(n, _, _)))) = args.first() {
-                        return *n as u32;
-                    }
+    /// Get the initial token count from a delay actor (first arg), or
+    /// `None` if the node is not a delay.
+    fn delay_initial_tokens(&self, sub: &Subgraph, node_id: NodeId) -> Option<u32> {
+        let node = find_node(sub, node_id)?;
+        match &node.kind {
+            NodeKind::Actor { args, name, .. } if name == "delay" => {
+                if let Some(Arg::Value(Value::Scalar(Scalar::Number(n, _, _)))) = args.first() {
+                    Some(*n as u32)
+                } else {
+                    Some(1)
                 }
             }
+            _ => None,
         }
-        1
     }
 
     fn node_out_rate(&self, node_id: NodeId) -> Option<u32> {
@@ -495,6 +729,13 @@ impl<'a> ScheduleCtx<'a> {
 
 use crate::subgraph_index::find_node;
 
+fn node_display_name(node: &Node) -> &str {
+    match &node.kind {
+        NodeKind::Actor { name, .. } => name,
+        _ => "<non-actor>",
+    }
+}
+
 /// K factor: iterations per tick (compile-time heuristic).
 /// K = ceil(freq / tick_rate), capped at MAX_K to prevent UDP buffer
 /// overflow when tasks contain network actors (socket_write).
@@ -555,6 +796,16 @@ fn write_subgraph_schedule(
             entry.node_id.0, entry.repetition_count
         )?;
     }
+    let total: u64 = sched
+        .firings
+        .iter()
+        .map(|e| u64::from(e.repetition_count))
+        .sum();
+    writeln!(
+        f,
+        "{indent}  total: {total} firings ({} nodes)",
+        sched.firings.len()
+    )?;
     if !sched.edge_buffers.is_empty() {
         writeln!(f, "{indent}  buffers:")?;
         let mut edges: Vec<_> = sched.edge_buffers.iter().collect();
@@ -566,6 +817,97 @@ fn write_subgraph_schedule(
     Ok(())
 }
 
+// ── JSON ────────────────────────────────────────────────────────────────────
+
+impl ScheduledProgram {
+    /// Serialize the schedule for `--emit schedule-json`.
+    ///
+    /// Tasks are keyed by name and edge buffers sorted by (src, dst), so the
+    /// output is byte-stable across runs. `graph` supplies each firing's
+    /// node kind and name.
+    pub fn to_json(&self, graph: &ProgramGraph) -> serde_json::Value {
+        let mut task_names: Vec<&String> = self.tasks.keys().collect();
+        task_names.sort();
+        let mut tasks = serde_json::Map::new();
+        for task_name in task_names {
+            let meta = &self.tasks[task_name];
+            let task_graph = graph.tasks.get(task_name);
+            let subgraphs = match &meta.schedule {
+                TaskSchedule::Pipeline(sched) => {
+                    let sub = match task_graph {
+                        Some(TaskGraph::Pipeline(sub)) => Some(sub),
+                        _ => None,
+                    };
+                    vec![subgraph_schedule_json("pipeline", sched, sub)]
+                }
+                TaskSchedule::Modal { control, modes } => {
+                    let (control_sub, mode_subs) = match task_graph {
+                        Some(TaskGraph::Modal { control, modes }) => (Some(control), Some(modes)),
+                        _ => (None, None),
+                    };
+                    let mut subgraphs =
+                        vec![subgraph_schedule_json("control", control, control_sub)];
+                    for (mode_name, sched) in modes {
+                        let sub = mode_subs
+                            .and_then(|m| m.iter().find(|(n, _)| n == mode_name))
+                            .map(|(_, sub)| sub);
+                        subgraphs.push(subgraph_schedule_json(
+                            &format!("mode:{mode_name}"),
+                            sched,
+                            sub,
+                        ));
+                    }
+                    subgraphs
+                }
+            };
+            tasks.insert(
+                task_name.clone(),
+                serde_json::json!({
+                    "k_factor": meta.k_factor,
+                    "freq_hz": meta.freq_hz,
+                    "subgraphs": subgraphs,
+                }),
+            );
+        }
+        serde_json::json!({ "tasks": tasks })
+    }
+}
+
+fn subgraph_schedule_json(
+    label: &str,
+    sched: &SubgraphSchedule,
+    sub: Option<&Subgraph>,
+) -> serde_json::Value {
+    let firings: Vec<serde_json::Value> = sched
+        .firings
+        .iter()
+        .map(|entry| {
+            let (kind, name) = sub
+                .and_then(|s| find_node(s, entry.node_id))
+                .map_or(("unknown", ""), |n| {
+                    crate::graph_query::kind_and_name(&n.kind)
+                });
+            serde_json::json!({
+                "node_id": entry.node_id.0,
+                "repetition_count": entry.repetition_count,
+                "kind": kind,
+                "name": name,
+            })
+        })
+        .collect();
+    let mut edges: Vec<_> = sched.edge_buffers.iter().collect();
+    edges.sort_by_key(|((a, b), _)| (a.0, b.0));
+    let edge_buffers: Vec<serde_json::Value> = edges
+        .into_iter()
+        .map(|((src, dst), tokens)| serde_json::json!({ "src": src.0, "dst": dst.0, "tokens": tokens }))
+        .collect();
+    serde_json::json!({
+        "label": label,
+        "firings": firings,
+        "edge_buffers": edge_buffers,
+    })
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -600,6 +942,13 @@ mod tests {
     }
 
     fn schedule_source(source: &str, registry: &Registry) -> ScheduleResult {
+        schedule_source_with_graph(source, registry).1
+    }
+
+    fn schedule_source_with_graph(
+        source: &str,
+        registry: &Registry,
+    ) -> (ProgramGraph, ScheduleResult) {
         let parse_result = crate::parser::parse(source);
         assert!(
             parse_result.errors.is_empty(),
@@ -656,7 +1005,8 @@ mod tests {
             "analysis errors: {:#?}",
             analysis_result.diagnostics
         );
-        schedule(&thir, &graph_result.graph, &analysis_result.analysis)
+        let result = schedule(&thir, &graph_result.graph, &analysis_result.analysis);
+        (graph_result.graph, result)
     }
 
     fn schedule_ok(source: &str, registry: &Registry) -> ScheduleResult {
@@ -925,6 +1275,141 @@ mod tests {
         }
     }
 
+    // ── Atomic group tests ──────────────────────────────────────────────
+
+    /// Source text of each fired actor in task `t`, in firing order.
+    fn fired_calls(source: &str, registry: &Registry) -> Vec<String> {
+        let (result, graph_result, _) = build_schedule_and_graph(source, registry);
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert!(errors.is_empty(), "schedule errors: {:#?}", errors);
+        let TaskGraph::Pipeline(sub) = &graph_result.graph.tasks["t"] else {
+            panic!("expected Pipeline graph")
+        };
+        let sched = get_pipeline_schedule(&result.schedule.tasks["t"]);
+        sched
+            .firings
+            .iter()
+            .map(|f| {
+                let node = find_node(sub, f.node_id).unwrap();
+                source[node.span.start..node.span.end].to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn atomic_group_fires_contiguously() {
+        let reg = test_registry();
+        let src = |g: &str| {
+            format!(
+                "clock 1kHz t {{\n    constant(0.0) | mul(2.0){g} | mul(3.0){g} | stdout()\n    \
+                 constant(1.0) | mul(4.0) | stdout()\n}}"
+            )
+        };
+
+        // Without the group, breadth-first ordering interleaves the two chains.
+        let plain = fired_calls(&src(""), &reg);
+        let pos = |calls: &[String], c: &str| calls.iter().position(|x| x == c).unwrap();
+        assert_ne!(
+            pos(&plain, "mul(3.0)"),
+            pos(&plain, "mul(2.0)") + 1,
+            "baseline should interleave: {:?}",
+            plain
+        );
+
+        let grouped = fired_calls(&src(" @atomic_group(st)"), &reg);
+        assert_eq!(
+            pos(&grouped, "mul(3.0)"),
+            pos(&grouped, "mul(2.0)") + 1,
+            "grouped actors must fire back-to-back: {:?}",
+            grouped
+        );
+        assert_eq!(grouped.len(), plain.len());
+    }
+
+    #[test]
+    fn atomic_group_must_be_connected_chain() {
+        let reg = test_registry();
+        let result = schedule_source(
+            "clock 1kHz t {\n    constant(0.0) | mul(2.0) @atomic_group(st) | stdout()\n    \
+             constant(1.0) | mul(4.0) @atomic_group(st) | stdout()\n}",
+            &reg,
+        );
+        let err = result
+            .diagnostics
+            .iter()
+            .find(|d| d.code == Some(codes::E0402))
+            .expect("expected E0402 for disconnected group");
+        assert!(err.message.contains("atomic group 'st'"), "{}", err.message);
+        assert!(!result.schedule.tasks.contains_key("t"));
+    }
+
+    #[test]
+    fn atomic_group_must_stay_in_one_subgraph() {
+        let reg = test_registry();
+        let result = schedule_source(
+            concat!(
+                "clock 1kHz t {\n",
+                "    control {\n        constant(0.0) | detect() @atomic_group(st) -> ctrl\n    }\n",
+                "    mode sync {\n        constant(0.0) | mul(2.0) @atomic_group(st) | stdout()\n    }\n",
+                "    mode data {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(ctrl, sync, data) default sync\n",
+                "}",
+            ),
+            &reg,
+        );
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.code == Some(codes::E0401)),
+            "expected E0401, got {:#?}",
+            result.diagnostics
+        );
+    }
+
+    #[test]
+    fn persist_requires_delay_inside_mode() {
+        let reg = test_registry();
+        let result = schedule_source(
+            concat!(
+                "clock 1kHz t {\n",
+                "    constant(0.0) | add(:fb) | :out | stdout()\n",
+                "    :out | delay(1, 0.0) @persist | :fb\n",
+                "}",
+            ),
+            &reg,
+        );
+        let err = result
+            .diagnostics
+            .iter()
+            .find(|d| d.code == Some(codes::E0403))
+            .expect("expected E0403 for @persist outside a mode");
+        assert!(err.message.contains("inside a mode"), "{}", err.message);
+
+        let result = schedule_source(
+            concat!(
+                "param sel = 0\n",
+                "clock 1kHz t {\n",
+                "    control {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode a {\n        constant(0.0) | mul(2.0) @persist | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch($sel, a, b)\n",
+                "}",
+            ),
+            &reg,
+        );
+        let err = result
+            .diagnostics
+            .iter()
+            .find(|d| d.code == Some(codes::E0403))
+            .expect("expected E0403 for @persist on a non-delay actor");
+        assert!(err.message.contains("not 'mul'"), "{}", err.message);
+    }
+
     // ── Buffer sizing tests ─────────────────────────────────────────────
 
     #[test]
@@ -1094,6 +1579,73 @@ mod tests {
         assert!(output.contains("[pipeline]"));
     }
 
+    #[test]
+    fn display_firing_counts_and_total() {
+        let reg = test_registry();
+        // fft(256) fires once, stdout fires 256 times per PASS cycle
+        let result = schedule_ok(
+            "clock 1kHz t {\n    constant(0.0) | fft(256) | c2r() | stdout()\n}",
+            &reg,
+        );
+        let output = format!("{}", result.schedule);
+        assert!(output.contains("x256"), "{output}");
+        assert!(
+            output.contains("  total: 259 firings (4 nodes)\n"),
+            "{output}"
+        );
+
+        // A tap adds a fork node: three actors, four scheduled nodes.
+        let result = schedule_ok(
+            "clock 1kHz t {\n    constant(1.0) | :a | stdout()\n    :a | stdout()\n}",
+            &reg,
+        );
+        let output = format!("{}", result.schedule);
+        assert!(
+            output.contains("  total: 4 firings (4 nodes)\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn to_json_lists_firings_and_sorted_buffers() {
+        let reg = test_registry();
+        let (graph, result) = schedule_source_with_graph(
+            "clock 1kHz b {\n    constant(0.0) | stdout()\n}\n\
+             clock 2kHz a {\n    constant(0.0) | fft(256) | c2r() | stdout()\n}",
+            &reg,
+        );
+        let json = result.schedule.to_json(&graph);
+        let names: Vec<&String> = json["tasks"].as_object().unwrap().keys().collect();
+        assert_eq!(names, ["a", "b"]);
+
+        let task = &json["tasks"]["a"];
+        assert_eq!(task["k_factor"], 1);
+        assert_eq!(task["freq_hz"], 2000.0);
+        let sub = &task["subgraphs"][0];
+        assert_eq!(sub["label"], "pipeline");
+        let fft = sub["firings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["name"] == "fft")
+            .expect("fft firing");
+        assert_eq!(fft["kind"], "actor");
+        assert_eq!(fft["repetition_count"], 1);
+
+        let edges = sub["edge_buffers"].as_array().unwrap();
+        let keys: Vec<(u64, u64)> = edges
+            .iter()
+            .map(|e| (e["src"].as_u64().unwrap(), e["dst"].as_u64().unwrap()))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert_eq!(
+            serde_json::to_string(&json).unwrap(),
+            serde_json::to_string(&result.schedule.to_json(&graph)).unwrap()
+        );
+    }
+
     // ── Integration tests ───────────────────────────────────────────────
 
     #[test]
@@ -1230,6 +1782,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn max_latency_warns_when_batch_exceeds_budget() {
+        let reg = test_registry();
+        // tick_rate = 1kHz with 10kHz task → K=10, batched latency = 1ms
+        let src = |budget: &str| {
+            format!(
+                "set tick_rate = 1kHz\nset max_latency_ms = {}\n\
+                 clock 10kHz t {{\n    constant(0.0) | stdout()\n}}",
+                budget
+            )
+        };
+        let result = schedule_ok(&src("0.5"), &reg);
+        let warnings: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == Some(codes::W0401))
+            .collect();
+        assert_eq!(warnings.len(), 1, "{:?}", result.diagnostics);
+        let msg = &warnings[0].message;
+        assert!(msg.contains("task 't'"), "{}", msg);
+        assert!(msg.contains("K=10"), "{}", msg);
+        assert!(msg.contains("batched latency 1.000ms"), "{}", msg);
+        assert!(msg.contains("clock period 0.100ms"), "{}", msg);
+
+        let result = schedule_ok(&src("2"), &reg);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .all(|d| d.code != Some(codes::W0401)),
+            "{:?}",
+            result.diagnostics
+        );
+    }
+
     // ── verify_schedule tests ───────────────────────────────────────────
 
     /// Helper: build schedule and graph from source for verification tests.
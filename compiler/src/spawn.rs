@@ -8,7 +8,7 @@
 //   `TaskStmt` entries that reference their values.
 // Postconditions: no `TaskStmt` with a `SpawnClause` remains in the output.
 //   Index variable is substituted in `Arg::ConstRef`, `BufferIndex::Ident`,
-//   and `ShapeDim::ConstRef` positions.
+//   and `ShapeDim::ConstRef` positions (including inside `ShapeDim::BinOp`).
 // Failure modes: unresolvable spawn bounds produce diagnostics.
 // Side effects: none (pure function).
 
@@ -131,9 +131,65 @@ fn validate_shared_size(
                 Some(_) => { /* valid */ }
             }
         }
+        ShapeDim::BinOp(..) => match decl.size.fold(&|name| consts.get(name).copied()) {
+            Ok(0) => {
+                diags.push(
+                    Diagnostic::new(
+                        DiagLevel::Error,
+                        decl.size.span(),
+                        format!(
+                            "'{}' evaluates to 0; shared array size must be > 0",
+                            decl.size
+                        ),
+                    )
+                    .with_code(codes::E0028),
+                );
+            }
+            Ok(_) => { /* valid */ }
+            Err(ShapeFoldError::Unresolved(ident)) => {
+                diags.push(
+                    Diagnostic::new(
+                        DiagLevel::Error,
+                        ident.span,
+                        format!("unknown const '{}' in shared array size", ident.name),
+                    )
+                    .with_code(codes::E0030),
+                );
+            }
+            Err(err) => diags.extend(shape_fold_diagnostic(&decl.size, &err)),
+        },
     }
 }
 
+/// Diagnostic (E0045) for a shape dimension whose arithmetic divides by zero,
+/// goes negative, or overflows. `None` for an unresolved const reference,
+/// which callers report under their own code.
+pub fn shape_fold_diagnostic(dim: &ShapeDim, err: &ShapeFoldError) -> Option<Diagnostic> {
+    let (span, message, hint) = match err {
+        ShapeFoldError::Unresolved(_) => return None,
+        ShapeFoldError::DivisionByZero(span) => (
+            *span,
+            format!("division by zero in shape dimension '{}'", dim),
+            "the divisor must not evaluate to 0",
+        ),
+        ShapeFoldError::Negative(value, span) => (
+            *span,
+            format!("shape dimension '{}' goes negative ({})", dim, value),
+            "every step of a dimension expression must be non-negative",
+        ),
+        ShapeFoldError::Overflow(span) => (
+            *span,
+            format!("shape dimension '{}' exceeds {}", dim, u32::MAX),
+            "every step of a dimension expression must fit in 32 bits",
+        ),
+    };
+    Some(
+        Diagnostic::new(DiagLevel::Error, span, message)
+            .with_code(codes::E0045)
+            .with_hint(hint),
+    )
+}
+
 // ── Spawn bound resolution ──────────────────────────────────────────
 
 fn resolve_bound(
@@ -278,7 +334,7 @@ fn substitute_pipe_source(src: &PipeSource, idx_var: &str, idx_val: u32) -> Pipe
         PipeSource::BufferRead(br) => {
             PipeSource::BufferRead(substitute_buffer_ref(br, idx_var, idx_val))
         }
-        PipeSource::TapRef(_) => src.clone(),
+        PipeSource::TapRef(_) | PipeSource::TapGather(_) => src.clone(),
         PipeSource::ActorCall(ac) => {
             PipeSource::ActorCall(substitute_actor_call(ac, idx_var, idx_val))
         }
@@ -325,6 +381,9 @@ fn substitute_actor_call(ac: &ActorCall, idx_var: &str, idx_val: u32) -> ActorCa
             .shape_constraint
             .as_ref()
             .map(|sc| substitute_shape_constraint(sc, idx_var, idx_val)),
+        atomic_group: ac.atomic_group.clone(),
+        persist: ac.persist,
+        allow_combinational_loop: ac.allow_combinational_loop,
         span: ac.span,
     }
 }
@@ -349,17 +408,27 @@ fn substitute_shape_constraint(
         dims: sc
             .dims
             .iter()
-            .map(|d| match d {
-                ShapeDim::ConstRef(ident) if ident.name == idx_var => {
-                    ShapeDim::Literal(idx_val, ident.span)
-                }
-                other => other.clone(),
-            })
+            .map(|d| substitute_shape_dim(d, idx_var, idx_val))
             .collect(),
         span: sc.span,
     }
 }
 
+fn substitute_shape_dim(dim: &ShapeDim, idx_var: &str, idx_val: u32) -> ShapeDim {
+    match dim {
+        ShapeDim::ConstRef(ident) if ident.name == idx_var => {
+            ShapeDim::Literal(idx_val, ident.span)
+        }
+        ShapeDim::BinOp(lhs, op, rhs, span) => ShapeDim::BinOp(
+            Box::new(substitute_shape_dim(lhs, idx_var, idx_val)),
+            *op,
+            Box::new(substitute_shape_dim(rhs, idx_var, idx_val)),
+            *span,
+        ),
+        other => other.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -567,6 +636,16 @@ mod tests {
         assert_eq!(result.diagnostics[0].code, Some(codes::E0028));
     }
 
+    #[test]
+    fn shared_size_arithmetic() {
+        let ok = expand_spawns(&parse_program("const CH = 4\nshared buf[CH*2]"));
+        assert!(ok.diagnostics.is_empty(), "{:?}", ok.diagnostics);
+
+        let result = expand_spawns(&parse_program("const CH = 4\nshared buf[CH/(CH-4)]"));
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].code, Some(codes::E0045));
+    }
+
     // ── Mixed statements preserved ───────────────────────────────────
 
     #[test]
@@ -142,7 +142,8 @@ pub fn subgraphs_of(task_graph: &TaskGraph) -> Vec<&Subgraph> {
 ///
 /// For each detected cycle, the outgoing edge from the `delay` actor is
 /// treated as the back-edge (delay provides initial tokens, breaking the
-/// data-flow dependency for topological sorting).
+/// data-flow dependency for topological sorting). A cycle with no delay is
+/// broken at the first actor annotated `@allow_combinational_loop`.
 pub fn identify_back_edges(sub: &Subgraph, cycles: &[Vec<NodeId>]) -> HashSet<(NodeId, NodeId)> {
     let mut back_edges = HashSet::new();
     let node_ids: HashSet<u32> = sub.nodes.iter().map(|n| n.id.0).collect();
@@ -151,19 +152,31 @@ pub fn identify_back_edges(sub: &Subgraph, cycles: &[Vec<NodeId>]) -> HashSet<(N
         if !cycle.iter().all(|id| node_ids.contains(&id.0)) {
             continue;
         }
-        for (i, &nid) in cycle.iter().enumerate() {
-            if let Some(node) = find_node(sub, nid) {
-                if matches!(&node.kind, NodeKind::Actor { name, .. } if name == "delay") {
-                    let next_nid = cycle[(i + 1) % cycle.len()];
-                    if sub
-                        .edges
-                        .iter()
-                        .any(|e| e.source == nid && e.target == next_nid)
-                    {
-                        back_edges.insert((nid, next_nid));
-                    }
-                    break;
-                }
+        let breaks_at = |pred: fn(&NodeKind) -> bool| {
+            cycle
+                .iter()
+                .position(|&nid| find_node(sub, nid).is_some_and(|n| pred(&n.kind)))
+        };
+        let pos = breaks_at(|k| matches!(k, NodeKind::Actor { name, .. } if name == "delay"))
+            .or_else(|| {
+                breaks_at(|k| {
+                    matches!(
+                        k,
+                        NodeKind::Actor {
+                            allow_combinational_loop: true,
+                            ..
+                        }
+                    )
+                })
+            });
+        if let Some(i) = pos {
+            let (nid, next_nid) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            if sub
+                .edges
+                .iter()
+                .any(|e| e.source == nid && e.target == next_nid)
+            {
+                back_edges.insert((nid, next_nid));
             }
         }
     }
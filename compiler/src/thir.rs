@@ -20,7 +20,7 @@ use crate::hir::{HirBind, HirConst, HirParam, HirProgram, HirSetDirective, HirTa
 use crate::id::CallId;
 use crate::lower::LoweredProgram;
 use crate::registry::{ActorMeta, ParamKind, ParamType, PortShape, Registry, TokenCount};
-use crate::resolve::ResolvedProgram;
+use crate::resolve::{Buffering, ResolvedProgram};
 use crate::subgraph_index::subgraphs_of;
 use crate::type_infer::TypedProgram;
 
@@ -47,12 +47,28 @@ pub struct ThirContext<'a> {
 
     // ── Precomputed set-directive values ──
     pub mem_bytes: u64,
-    /// Span of the `set mem` directive (None if using default 64MB).
+    /// Span of the `set mem` directive (None if using default 64MB or
+    /// `--max-memory`).
     pub mem_span: Option<Span>,
+    /// `mem_bytes` comes from the `--max-memory` CLI override.
+    pub mem_from_cli: bool,
+    /// `set mem = N%`: the pool limit is a share of the platform budget.
+    pub mem_percent: Option<f64>,
+    /// Platform memory budget from `--platform-memory`, in bytes.
+    pub platform_mem_bytes: Option<u64>,
     pub tick_rate_hz: f64,
     pub timer_spin: Option<f64>,
     pub overrun_policy: String,
+    /// `set buffering`: inter-task ring depth in writer frames.
+    pub buffering: Buffering,
     pub wait_timeout_ms: u64,
+    /// `set max_latency_ms`: batched-latency budget per task (None = no check).
+    pub max_latency_ms: Option<f64>,
+    /// `set warn_buffer_tokens`: W0315 edge-buffer threshold (None = no check).
+    pub warn_buffer_tokens: Option<u64>,
+    /// `set affinity`: task name → CPU core its thread is pinned to.
+    /// Tasks not listed keep OS default placement.
+    pub affinity: HashMap<String, u32>,
     /// Span of the original program (fallback for diagnostics).
     pub program_span: Span,
 
@@ -110,15 +126,26 @@ pub fn build_thir_context<'a>(
     let (mem_bytes, mem_span) = find_set_size_with_span(&hir.set_directives, &set_index, "mem")
         .map(|(v, s)| (v, Some(s)))
         .unwrap_or((64 * 1024 * 1024, None));
+    let mem_percent = find_set_percent_with_span(&hir.set_directives, &set_index, "mem");
+    let mem_span = mem_span.or(mem_percent.map(|(_, s)| s));
+    let mem_percent = mem_percent.map(|(p, _)| p);
     let tick_rate_hz =
         find_set_freq(&hir.set_directives, &set_index, "tick_rate").unwrap_or(10_000.0);
     let timer_spin = find_set_number(&hir.set_directives, &set_index, "timer_spin");
     let overrun_policy = find_set_ident(&hir.set_directives, &set_index, "overrun")
         .unwrap_or("drop")
         .to_string();
+    let buffering = find_set_ident(&hir.set_directives, &set_index, "buffering")
+        .and_then(Buffering::from_name)
+        .unwrap_or_default();
     let wait_timeout_ms = find_set_number(&hir.set_directives, &set_index, "wait_timeout")
         .map(|n| (n as u64).clamp(1, 60000))
         .unwrap_or(50);
+    let max_latency_ms = find_set_number(&hir.set_directives, &set_index, "max_latency_ms");
+    let warn_buffer_tokens = find_set_number(&hir.set_directives, &set_index, "warn_buffer_tokens")
+        .filter(|n| *n >= 1.0)
+        .map(|n| n as u64);
+    let affinity = set_affinity(hir);
 
     // Resolve param C++ types by scanning graph nodes
     let param_cpp_types = resolve_param_cpp_types(hir, lowered, registry, graph);
@@ -136,18 +163,67 @@ pub fn build_thir_context<'a>(
         bind_index,
         mem_bytes,
         mem_span,
+        mem_from_cli: false,
+        mem_percent,
+        platform_mem_bytes: None,
         tick_rate_hz,
         timer_spin,
         overrun_policy,
+        buffering,
         wait_timeout_ms,
+        max_latency_ms,
+        warn_buffer_tokens,
+        affinity,
         program_span: hir.program_span,
         param_cpp_types,
     }
 }
 
+/// `set affinity` as task name → CPU core (the last `set affinity` wins).
+pub fn set_affinity(hir: &HirProgram) -> HashMap<String, u32> {
+    let Some(directive) = hir
+        .set_directives
+        .iter()
+        .rev()
+        .find(|d| d.name == "affinity")
+    else {
+        return HashMap::new();
+    };
+    let SetValue::Map(entries, _) = &directive.value else {
+        return HashMap::new();
+    };
+    entries
+        .iter()
+        .filter_map(|(task, value)| match value {
+            // Validated in resolve: non-negative integers only.
+            SetValue::Number(n, _) => Some((task.name.clone(), *n as u32)),
+            _ => None,
+        })
+        .collect()
+}
+
 // ── Query methods ───────────────────────────────────────────────────────────
 
 impl<'a> ThirContext<'a> {
+    /// Replace the memory pool limit with a `--max-memory` override,
+    /// superseding `set mem`.
+    pub fn override_mem_bytes(&mut self, bytes: u64) {
+        self.mem_bytes = bytes;
+        self.mem_span = None;
+        self.mem_from_cli = true;
+        self.mem_percent = None;
+    }
+
+    /// Record the `--platform-memory` budget. A `set mem = N%` limit of at
+    /// most 100% becomes that share of the budget; analysis reports the
+    /// rest.
+    pub fn set_platform_memory(&mut self, bytes: u64) {
+        self.platform_mem_bytes = Some(bytes);
+        if let Some(pct) = self.mem_percent.filter(|&p| p <= 100.0) {
+            self.mem_bytes = (bytes as f64 * pct / 100.0) as u64;
+        }
+    }
+
     /// Look up a task by name.
     pub fn task_info(&self, name: &str) -> Option<&HirTask> {
         self.task_index.get(name).map(|&i| &self.hir.tasks[i])
@@ -223,19 +299,20 @@ impl<'a> ThirContext<'a> {
     // ── Dimension resolution (replaces dim_resolve.rs Program access) ───
 
     /// Resolve a ShapeDim to a concrete u32 value.
+    /// Arithmetic dimensions (`N*2`) are folded; a fold error yields `None`
+    /// (resolve already reported it).
     pub fn resolve_shape_dim(&self, dim: &ShapeDim) -> Option<u32> {
-        match dim {
-            ShapeDim::Literal(n, _) => Some(*n),
-            ShapeDim::ConstRef(ident) => self.resolve_const_to_u32(&ident.name),
-        }
+        dim.fold(&|name| self.resolve_const_to_u32(name)).ok()
     }
 
-    /// Resolve an Arg to a u32 value (number, array length, or const ref).
+    /// Resolve an Arg to a u32 value (number, array length, slice length, or
+    /// const ref).
     pub fn resolve_arg_to_u32(&self, arg: &Arg) -> Option<u32> {
         match arg {
             Arg::Value(Value::Scalar(Scalar::Number(n, _, _))) => Some(*n as u32),
             Arg::Value(Value::Array(elems, _)) => Some(elems.len() as u32),
             Arg::ConstRef(ident) => self.resolve_const_array_len(&ident.name),
+            Arg::ConstSlice(slice) => Some(slice.len()),
             _ => None,
         }
     }
@@ -274,12 +351,8 @@ impl<'a> ThirContext<'a> {
                         .and_then(|sd| self.resolve_shape_dim(sd))
                     {
                         v
-                    } else if let Some(v) =
-                        self.infer_dim_param_from_span_args(name, actor_meta, actor_args)
-                    {
-                        v
                     } else {
-                        return None;
+                        self.infer_dim_param_from_span_args(name, actor_meta, actor_args)?
                     }
                 }
             };
@@ -288,6 +361,25 @@ impl<'a> ThirContext<'a> {
         Some(rate)
     }
 
+    /// Resolve one `RATE_CHANGE(up, down)` factor: a literal, or the call's
+    /// argument for the named PARAM.
+    pub fn resolve_rate_factor(
+        &self,
+        factor: &TokenCount,
+        actor_meta: &ActorMeta,
+        actor_args: &[Arg],
+    ) -> Option<u32> {
+        match factor {
+            TokenCount::Literal(n) => Some(*n),
+            TokenCount::Symbolic(name) => actor_meta
+                .params
+                .iter()
+                .position(|p| p.name == *name)
+                .and_then(|idx| actor_args.get(idx))
+                .and_then(|arg| self.resolve_arg_to_u32(arg)),
+        }
+    }
+
     /// Like `resolve_port_rate()` but returns individual resolved dim values
     /// instead of their product. Returns `None` if any dim is unresolvable.
     ///
@@ -321,12 +413,8 @@ impl<'a> ThirContext<'a> {
                         .and_then(|sd| self.resolve_shape_dim(sd))
                     {
                         v
-                    } else if let Some(v) =
-                        self.infer_dim_param_from_span_args(name, actor_meta, actor_args)
-                    {
-                        v
                     } else {
-                        return None;
+                        self.infer_dim_param_from_span_args(name, actor_meta, actor_args)?
                     }
                 }
             };
@@ -402,7 +490,8 @@ impl<'a> ThirContext<'a> {
     /// Produce a deterministic summary of THIR precomputed metadata for snapshot tests.
     ///
     /// Shows the three unique THIR contributions not captured by HIR or LIR:
-    /// 1. Extracted set-directive values (mem_bytes, tick_rate_hz, timer_spin, overrun_policy)
+    /// 1. Extracted set-directive values (mem_bytes, tick_rate_hz, timer_spin, overrun_policy,
+    ///    buffering)
     /// 2. Resolved param C++ types (from graph-based scanning)
     /// 3. Indexed entry keys (derived from HIR items)
     ///
@@ -421,6 +510,7 @@ impl<'a> ThirContext<'a> {
             None => writeln!(out, "    timer_spin: None").unwrap(),
         }
         writeln!(out, "    overrun_policy: {}", self.overrun_policy).unwrap();
+        writeln!(out, "    buffering: {}", self.buffering).unwrap();
         writeln!(out, "    wait_timeout_ms: {}", self.wait_timeout_ms).unwrap();
 
         // 2. Param C++ types (sorted)
@@ -539,6 +629,18 @@ fn find_set_size_with_span(
     }
 }
 
+fn find_set_percent_with_span(
+    directives: &[HirSetDirective],
+    index: &HashMap<String, usize>,
+    name: &str,
+) -> Option<(f64, Span)> {
+    let &i = index.get(name)?;
+    match &directives[i].value {
+        SetValue::Percent(p, _) => Some((*p, directives[i].span)),
+        _ => None,
+    }
+}
+
 fn find_set_freq(
     directives: &[HirSetDirective],
     index: &HashMap<String, usize>,
@@ -596,7 +698,7 @@ fn scalar_cpp_type(s: &Scalar) -> &'static str {
 
 /// Resolve param C++ types by scanning graph nodes for actor calls that
 /// reference each param, then looking up the actor's parameter type.
-fn resolve_param_cpp_types(
+pub(crate) fn resolve_param_cpp_types(
     hir: &HirProgram,
     lowered: &LoweredProgram,
     registry: &Registry,
@@ -700,6 +802,7 @@ mod tests {
             call_resolutions: HashMap::new(),
             task_resolutions: HashMap::new(),
             probes: Vec::new(),
+            default_args: HashMap::new(),
             call_ids: HashMap::new(),
             call_spans: HashMap::new(),
             def_ids: HashMap::new(),
@@ -751,6 +854,9 @@ mod tests {
                             )))],
                             type_args: vec![],
                             shape_constraint: None,
+                            atomic_group: None,
+                            persist: false,
+                            allow_combinational_loop: false,
                         }),
                         elements: vec![HirPipeElem::ActorCall(HirActorCall {
                             name: "stdout".to_string(),
@@ -759,6 +865,9 @@ mod tests {
                             args: vec![],
                             type_args: vec![],
                             shape_constraint: None,
+                            atomic_group: None,
+                            persist: false,
+                            allow_combinational_loop: false,
                         })],
                         sink: None,
                         span: sp(20, 41),
@@ -853,6 +962,7 @@ mod tests {
         assert_eq!(thir.tick_rate_hz, 1000.0);
         assert!(thir.timer_spin.is_none());
         assert_eq!(thir.overrun_policy, "drop");
+        assert_eq!(thir.buffering, Buffering::Double);
         assert_eq!(thir.wait_timeout_ms, 50);
     }
 
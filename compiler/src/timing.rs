@@ -1,20 +1,142 @@
-// timing.rs — Mermaid Gantt timing chart output for Pipit PASS schedules
+// timing.rs — Timing chart output for Pipit PASS schedules
 //
-// Transforms a ScheduledProgram + ProgramGraph into a Mermaid Gantt chart
-// showing actor firing order and durations per task.
+// Places a ScheduledProgram + ProgramGraph on an ASAP timeline (the timing
+// model) and renders it as a Mermaid Gantt chart or as JSON, showing actor
+// firing order and durations per task, or as a per-core utilization summary.
 //
 // Preconditions: `schedule` is a computed ScheduledProgram;
 //                `graph` is the corresponding ProgramGraph.
-// Postconditions: returns a valid Mermaid Gantt chart string.
-// Failure modes: none (pure string formatting).
+// Postconditions: returns a valid Mermaid Gantt chart string, a JSON value,
+//                 or the per-core text view.
+// Failure modes: none (pure formatting).
 // Side effects: none.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 
 use crate::graph::*;
 use crate::schedule::*;
 
+// ── Timing model ────────────────────────────────────────────────────────────
+
+/// ASAP timelines of every scheduled task, sorted by task name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingModel {
+    pub tasks: Vec<TaskTiming>,
+}
+
+/// Timeline of one task: its clock plus one section per subgraph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskTiming {
+    pub name: String,
+    pub freq_hz: f64,
+    pub k_factor: u32,
+    /// `pipeline`, or `control` followed by the modes in schedule order.
+    pub sections: Vec<TimingSection>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SectionKind {
+    Pipeline,
+    Control,
+    Mode(String),
+}
+
+/// ASAP timeline of one subgraph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingSection {
+    pub kind: SectionKind,
+    pub timing: SubgraphTiming,
+}
+
+/// Firings of one subgraph on the ASAP timeline.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SubgraphTiming {
+    pub firings: Vec<TimedFiring>,
+    /// Chain of firings ending last, first to last. Probes are omitted.
+    pub critical_path: Vec<NodeId>,
+}
+
+impl TaskTiming {
+    /// Nominal task period in nanoseconds (`1 / freq_hz`).
+    pub fn period_ns(&self) -> f64 {
+        1_000_000_000.0 / self.freq_hz
+    }
+}
+
+impl SubgraphTiming {
+    /// End of the last firing, in firing units.
+    pub fn makespan(&self) -> u64 {
+        self.firings.iter().map(|f| f.end).max().unwrap_or(0)
+    }
+}
+
+/// Build the timing model shared by the Mermaid and JSON renderers.
+///
+/// Preconditions: `schedule` and `graph` correspond to the same program.
+/// Postconditions: tasks are sorted by name; a task whose schedule and graph
+///   kinds disagree (pipeline vs modal) is omitted.
+/// Failure modes: none.
+/// Side effects: none.
+pub fn timing_model(schedule: &ScheduledProgram, graph: &ProgramGraph) -> TimingModel {
+    // Sort task names for deterministic output
+    let mut task_names: Vec<&String> = schedule.tasks.keys().collect();
+    task_names.sort();
+
+    let tasks = task_names
+        .into_iter()
+        .filter_map(|task_name| {
+            let meta = &schedule.tasks[task_name];
+            let task_graph = graph.tasks.get(task_name)?;
+            let sections = task_sections(meta, task_graph)?;
+            Some(TaskTiming {
+                name: task_name.clone(),
+                freq_hz: meta.freq_hz,
+                k_factor: meta.k_factor,
+                sections,
+            })
+        })
+        .collect();
+    TimingModel { tasks }
+}
+
+fn task_sections(meta: &TaskMeta, task_graph: &TaskGraph) -> Option<Vec<TimingSection>> {
+    match (&meta.schedule, task_graph) {
+        (TaskSchedule::Pipeline(sched), TaskGraph::Pipeline(sub)) => Some(vec![TimingSection {
+            kind: SectionKind::Pipeline,
+            timing: asap_timing(sched, sub),
+        }]),
+        (
+            TaskSchedule::Modal { control, modes },
+            TaskGraph::Modal {
+                control: ctrl_sub,
+                modes: mode_subs,
+            },
+        ) => {
+            let mut sections = vec![TimingSection {
+                kind: SectionKind::Control,
+                timing: asap_timing(control, ctrl_sub),
+            }];
+            for (mode_name, mode_sched) in modes {
+                let timing = mode_subs
+                    .iter()
+                    .find(|(n, _)| n == mode_name)
+                    .map(|(_, sub)| asap_timing(mode_sched, sub))
+                    .unwrap_or_default();
+                sections.push(TimingSection {
+                    kind: SectionKind::Mode(mode_name.clone()),
+                    timing,
+                });
+            }
+            Some(sections)
+        }
+        // Schedule/graph type mismatch — skip silently
+        _ => None,
+    }
+}
+
+// ── Mermaid renderer ────────────────────────────────────────────────────────
+
 /// Emit the PASS schedule as a Mermaid Gantt chart string.
 ///
 /// Preconditions: `schedule` and `graph` correspond to the same program.
@@ -28,87 +150,206 @@ pub fn emit_timing_chart(schedule: &ScheduledProgram, graph: &ProgramGraph) -> S
     writeln!(buf, "    dateFormat x").unwrap();
     writeln!(buf, "    axisFormat %Q").unwrap();
 
-    // Sort task names for deterministic output
-    let mut task_names: Vec<&String> = schedule.tasks.keys().collect();
-    task_names.sort();
-
-    for task_name in &task_names {
-        let meta = &schedule.tasks[*task_name];
-        let task_graph = match graph.tasks.get(*task_name) {
-            Some(g) => g,
-            None => continue,
-        };
-        emit_task_section(&mut buf, task_name, meta, task_graph);
+    for task in &timing_model(schedule, graph).tasks {
+        emit_task_section(&mut buf, task);
     }
 
     buf
 }
 
-// ── Helpers ─────────────────────────────────────────────────────────────────
+fn emit_task_section(buf: &mut String, task: &TaskTiming) {
+    let freq = format_freq(task.freq_hz);
+    let prefix = sanitize(&task.name);
+    for section in &task.sections {
+        writeln!(buf).unwrap();
+        let id_prefix = match &section.kind {
+            SectionKind::Pipeline => {
+                writeln!(
+                    buf,
+                    "    section {} [pipeline] (K={}, {})",
+                    task.name, task.k_factor, freq
+                )
+                .unwrap();
+                prefix.clone()
+            }
+            SectionKind::Control => {
+                writeln!(
+                    buf,
+                    "    section {} [control] (K={}, {})",
+                    task.name, task.k_factor, freq
+                )
+                .unwrap();
+                format!("{prefix}_ctrl")
+            }
+            SectionKind::Mode(mode_name) => {
+                writeln!(buf, "    section {} [mode: {}]", task.name, mode_name).unwrap();
+                format!("{}_{}", prefix, sanitize(mode_name))
+            }
+        };
+        emit_subgraph_firings(buf, &section.timing.firings, &id_prefix);
+    }
+}
 
-fn emit_task_section(buf: &mut String, task_name: &str, meta: &TaskMeta, task_graph: &TaskGraph) {
-    let freq = format_freq(meta.freq_hz);
-    let prefix = sanitize(task_name);
-    match (&meta.schedule, task_graph) {
-        (TaskSchedule::Pipeline(sched), TaskGraph::Pipeline(sub)) => {
-            writeln!(buf).unwrap();
-            writeln!(
-                buf,
-                "    section {} [pipeline] (K={}, {})",
-                task_name, meta.k_factor, freq
-            )
-            .unwrap();
-            emit_subgraph_firings(buf, sched, sub, &prefix);
-        }
-        (
-            TaskSchedule::Modal { control, modes },
-            TaskGraph::Modal {
-                control: ctrl_sub,
-                modes: mode_subs,
-            },
-        ) => {
-            writeln!(buf).unwrap();
-            writeln!(
-                buf,
-                "    section {} [control] (K={}, {})",
-                task_name, meta.k_factor, freq
-            )
-            .unwrap();
-            emit_subgraph_firings(buf, control, ctrl_sub, &format!("{prefix}_ctrl"));
+// ── JSON renderer ───────────────────────────────────────────────────────────
 
-            for (mode_name, mode_sched) in modes {
-                let mode_sub = mode_subs
+/// Serialize the timing model for `--emit timing-json`.
+///
+/// Tasks are keyed by name and sections keep schedule order, so the output
+/// is byte-stable across runs. `start`/`end` are in firing units, as in the
+/// Mermaid chart; `duration_estimate_ns` is `null` until the scheduler
+/// provides per-actor cost estimates.
+pub fn emit_timing_json(schedule: &ScheduledProgram, graph: &ProgramGraph) -> serde_json::Value {
+    let mut tasks = serde_json::Map::new();
+    for task in timing_model(schedule, graph).tasks {
+        let sections: Vec<serde_json::Value> = task
+            .sections
+            .iter()
+            .map(|section| {
+                let label = match &section.kind {
+                    SectionKind::Pipeline => "pipeline".to_string(),
+                    SectionKind::Control => "control".to_string(),
+                    SectionKind::Mode(name) => format!("mode:{name}"),
+                };
+                let firings: Vec<serde_json::Value> = section
+                    .timing
+                    .firings
                     .iter()
-                    .find(|(n, _)| n == mode_name)
-                    .map(|(_, s)| s);
-                writeln!(buf).unwrap();
-                writeln!(buf, "    section {} [mode: {}]", task_name, mode_name).unwrap();
-                if let Some(sub) = mode_sub {
-                    let mode_prefix = format!("{}_{}", prefix, sanitize(mode_name));
-                    emit_subgraph_firings(buf, mode_sched, sub, &mode_prefix);
-                }
+                    .map(|f| {
+                        serde_json::json!({
+                            "node_id": f.node_id.0,
+                            "label": f.label,
+                            "repetition_count": f.repetition_count,
+                            "start": f.start,
+                            "end": f.end,
+                            "duration_estimate_ns": f.duration_estimate_ns,
+                        })
+                    })
+                    .collect();
+                let critical_path: Vec<u32> =
+                    section.timing.critical_path.iter().map(|id| id.0).collect();
+                serde_json::json!({
+                    "label": label,
+                    "makespan": section.timing.makespan(),
+                    "critical_path": critical_path,
+                    "firings": firings,
+                })
+            })
+            .collect();
+        tasks.insert(
+            task.name.clone(),
+            serde_json::json!({
+                "freq_hz": task.freq_hz,
+                "k_factor": task.k_factor,
+                "period_ns": task.period_ns(),
+                "sections": sections,
+            }),
+        );
+    }
+    serde_json::json!({ "tasks": tasks })
+}
+
+// ── Per-core view ───────────────────────────────────────────────────────────
+
+impl TaskTiming {
+    /// Estimated share of one core the task needs: the duration estimates of
+    /// one iteration over the task period, counting the control subgraph
+    /// plus the costliest mode. `None` while any firing lacks an estimate.
+    pub fn utilization(&self) -> Option<f64> {
+        let mut busy_ns = 0.0;
+        let mut costliest_mode_ns = 0.0_f64;
+        for section in &self.sections {
+            let section_ns = section
+                .timing
+                .firings
+                .iter()
+                .map(|f| f.duration_estimate_ns)
+                .sum::<Option<f64>>()?;
+            match section.kind {
+                SectionKind::Mode(_) => costliest_mode_ns = costliest_mode_ns.max(section_ns),
+                SectionKind::Pipeline | SectionKind::Control => busy_ns += section_ns,
             }
         }
-        _ => {
-            // Schedule/graph type mismatch — skip silently
+        Some((busy_ns + costliest_mode_ns) / self.period_ns())
+    }
+}
+
+/// Render `--emit schedule --per-core`: tasks grouped by the core `set
+/// affinity` pins them to, with each core's summed utilization
+/// (`core 0: audio(40%), ctrl(15%) = 55%`). Unpinned tasks follow under
+/// `floating`. Utilizations print as `?` until the scheduler provides
+/// per-actor cost estimates.
+pub fn emit_per_core(model: &TimingModel, affinity: &HashMap<String, u32>) -> String {
+    let mut cores: BTreeMap<u32, Vec<&TaskTiming>> = BTreeMap::new();
+    let mut floating = Vec::new();
+    for task in &model.tasks {
+        match affinity.get(&task.name) {
+            Some(core) => cores.entry(*core).or_default().push(task),
+            None => floating.push(task),
         }
     }
+
+    let mut buf = String::new();
+    let groups = cores
+        .into_iter()
+        .map(|(core, tasks)| (format!("core {core}"), tasks))
+        .chain((!floating.is_empty()).then(|| ("floating".to_string(), floating)));
+    for (group, tasks) in groups {
+        let entries: Vec<String> = tasks
+            .iter()
+            .map(|t| format!("{}({})", t.name, format_utilization(t.utilization())))
+            .collect();
+        let total = tasks.iter().map(|t| t.utilization()).sum::<Option<f64>>();
+        writeln!(
+            buf,
+            "{}: {} = {}",
+            group,
+            entries.join(", "),
+            format_utilization(total)
+        )
+        .unwrap();
+    }
+    buf
+}
+
+fn format_utilization(utilization: Option<f64>) -> String {
+    match utilization {
+        Some(u) => format!("{:.0}%", u * 100.0),
+        None => "?".to_string(),
+    }
+}
+
+// ── ASAP placement ──────────────────────────────────────────────────────────
+
+/// One firing entry placed on the ASAP timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedFiring {
+    pub node_id: NodeId,
+    /// Mermaid-safe node label (`fft`, `fork(raw)`, ...).
+    pub label: String,
+    pub repetition_count: u32,
+    pub start: u64,
+    pub end: u64,
+    /// Scheduler cost estimate for the firing, when one exists.
+    pub duration_estimate_ns: Option<f64>,
 }
 
-/// Emit firing entries as Mermaid Gantt task lines using ASAP scheduling.
+/// Place a subgraph's firings on a timeline using ASAP scheduling.
 ///
-/// Uses `dateFormat x` with numeric start/end values.  Each node starts
-/// at the earliest possible time: `max(end_time of predecessors)`.
-/// Independent branches (e.g. after a fork) run in parallel.
-/// Probes are zero-duration observation points and are omitted from output.
-fn emit_subgraph_firings(
-    buf: &mut String,
-    sched: &SubgraphSchedule,
-    sub: &Subgraph,
-    id_prefix: &str,
-) {
+/// Each node starts at the earliest possible time: `max(end_time of
+/// predecessors)`, and lasts its repetition count. Independent branches
+/// (e.g. after a fork) run in parallel. Probes are zero-duration
+/// observation points and are omitted.
+pub fn asap_firings(sched: &SubgraphSchedule, sub: &Subgraph) -> Vec<TimedFiring> {
+    asap_timing(sched, sub).firings
+}
+
+/// `asap_firings` plus the critical path: the chain of forward predecessors
+/// that determines when the last firing ends (earliest in schedule order on
+/// ties).
+pub fn asap_timing(sched: &SubgraphSchedule, sub: &Subgraph) -> SubgraphTiming {
+    let mut timed = Vec::new();
     if sched.firings.is_empty() {
-        return;
+        return SubgraphTiming::default();
     }
 
     // Build position map: node_id -> index in topological order
@@ -138,24 +379,25 @@ fn emit_subgraph_firings(
 
     // Compute ASAP start/end times
     let mut end_time: HashMap<NodeId, u64> = HashMap::new();
-    let mut task_index = 0usize;
+    // Predecessor that released each node (the last to finish)
+    let mut critical_pred: HashMap<NodeId, NodeId> = HashMap::new();
+    let mut probes: Vec<NodeId> = Vec::new();
 
     for entry in &sched.firings {
         let node = find_node(sub, entry.node_id);
         let is_probe = node.is_some_and(|n| matches!(n.kind, NodeKind::Probe { .. }));
 
         // start = max(end_time of forward predecessors), or 0 if none
-        let start = predecessors
-            .get(&entry.node_id)
-            .map(|preds| {
-                preds
-                    .iter()
-                    .filter_map(|p| end_time.get(p))
-                    .max()
-                    .copied()
-                    .unwrap_or(0)
-            })
-            .unwrap_or(0);
+        let mut start = 0;
+        for pred in predecessors.get(&entry.node_id).into_iter().flatten() {
+            let Some(&pred_end) = end_time.get(pred) else {
+                continue;
+            };
+            if pred_end > start || !critical_pred.contains_key(&entry.node_id) {
+                start = start.max(pred_end);
+                critical_pred.insert(entry.node_id, *pred);
+            }
+        }
 
         let duration = if is_probe {
             0
@@ -167,21 +409,60 @@ fn emit_subgraph_firings(
 
         // Skip probes in output (zero-duration observation points)
         if is_probe {
+            probes.push(entry.node_id);
             continue;
         }
 
         let label = node
             .map(|n| node_label(&n.kind))
             .unwrap_or_else(|| format!("node_{}", entry.node_id.0));
+        timed.push(TimedFiring {
+            node_id: entry.node_id,
+            label,
+            repetition_count: entry.repetition_count,
+            start,
+            end,
+            // The scheduler carries no per-actor cost model yet.
+            duration_estimate_ns: None,
+        });
+    }
 
+    // Walk back from the first firing that ends last.
+    let mut critical_path = Vec::new();
+    let last = timed
+        .iter()
+        .fold(None::<&TimedFiring>, |best, f| match best {
+            Some(b) if b.end >= f.end => Some(b),
+            _ => Some(f),
+        })
+        .map(|f| f.node_id);
+    let mut cursor = last;
+    while let Some(id) = cursor {
+        if !probes.contains(&id) {
+            critical_path.push(id);
+        }
+        cursor = critical_pred.get(&id).copied();
+    }
+    critical_path.reverse();
+
+    SubgraphTiming {
+        firings: timed,
+        critical_path,
+    }
+}
+
+/// Emit firing entries as Mermaid Gantt task lines (see `asap_firings`).
+///
+/// Uses `dateFormat x` with numeric start/end values.
+fn emit_subgraph_firings(buf: &mut String, firings: &[TimedFiring], id_prefix: &str) {
+    for (task_index, f) in firings.iter().enumerate() {
         let id = format!("{id_prefix}_{task_index}");
         writeln!(
             buf,
             "    {} x{} :{}, {}, {}",
-            label, entry.repetition_count, id, start, end
+            f.label, f.repetition_count, id, f.start, f.end
         )
         .unwrap();
-        task_index += 1;
     }
 }
 
@@ -203,7 +484,7 @@ fn node_label(kind: &NodeKind) -> String {
 }
 
 /// Format frequency in engineering notation.
-fn format_freq(freq_hz: f64) -> String {
+pub(crate) fn format_freq(freq_hz: f64) -> String {
     if freq_hz >= 1_000_000.0 {
         let mhz = freq_hz / 1_000_000.0;
         if mhz == mhz.floor() {
@@ -276,6 +557,16 @@ mod tests {
 
     /// Full pipeline: parse -> resolve -> graph -> analyze -> schedule -> timing chart
     fn build_and_emit(source: &str, registry: &Registry) -> String {
+        let (schedule, graph) = build_schedule(source, registry);
+        emit_timing_chart(&schedule, &graph)
+    }
+
+    fn build_and_emit_json(source: &str, registry: &Registry) -> serde_json::Value {
+        let (schedule, graph) = build_schedule(source, registry);
+        emit_timing_json(&schedule, &graph)
+    }
+
+    fn build_schedule(source: &str, registry: &Registry) -> (ScheduledProgram, ProgramGraph) {
         let parse_result = crate::parser::parse(source);
         assert!(
             parse_result.errors.is_empty(),
@@ -342,7 +633,7 @@ mod tests {
             "schedule errors: {:?}",
             schedule_result.diagnostics
         );
-        emit_timing_chart(&schedule_result.schedule, &graph_result.graph)
+        (schedule_result.schedule, graph_result.graph)
     }
 
     /// Parse a task line like "    adc x256 :t_0, 0, 256" into (label, id, start, end).
@@ -898,10 +1189,158 @@ mod tests {
         }
     }
 
+    // ══════════════════════════════════════════════════════════════════════
+    // JSON Output
+    // ══════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn json_matches_chart_timeline() {
+        let reg = test_registry();
+        let source = "clock 1kHz t {\n    constant(0.0) | fft(256) | c2r() | stdout()\n}";
+        let chart = build_and_emit(source, &reg);
+        let json = build_and_emit_json(source, &reg);
+        let task = &json["tasks"]["t"];
+        assert_eq!(task["freq_hz"], 1000.0);
+        assert_eq!(task["k_factor"], 1);
+        assert_eq!(task["period_ns"], 1_000_000.0);
+        let section = &task["sections"][0];
+        assert_eq!(section["label"], "pipeline");
+
+        let lines: Vec<_> = chart.lines().filter_map(parse_task_line).collect();
+        let firings = section["firings"].as_array().unwrap();
+        assert_eq!(firings.len(), lines.len());
+        for (f, (label, _, start, end)) in firings.iter().zip(&lines) {
+            assert!(label.starts_with(f["label"].as_str().unwrap()));
+            assert_eq!(f["start"], *start);
+            assert_eq!(f["end"], *end);
+            assert!(f["duration_estimate_ns"].is_null());
+        }
+        assert_eq!(section["makespan"], lines.last().unwrap().3);
+    }
+
+    #[test]
+    fn json_critical_path_follows_longest_branch() {
+        let reg = test_registry();
+        let json = build_and_emit_json(
+            concat!(
+                "const lp_coeff = [0.25, 0.5, 0.25]\n",
+                "clock 1kHz analyzer {\n",
+                "    constant(0.0) | :sig | fir(lp_coeff) | stdout()\n",
+                "    :sig | fft(64) | mag() | stdout()\n",
+                "}",
+            ),
+            &reg,
+        );
+        let section = &json["tasks"]["analyzer"]["sections"][0];
+        let label_of = |id: &serde_json::Value| {
+            section["firings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|f| f["node_id"] == *id)
+                .map(|f| f["label"].as_str().unwrap().to_string())
+                .unwrap()
+        };
+        let path: Vec<String> = section["critical_path"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(label_of)
+            .collect();
+        assert_eq!(
+            path,
+            ["constant", "fork(sig)", "fft", "mag", "stdout"],
+            "critical path should run through the fft branch: {}",
+            section
+        );
+    }
+
+    #[test]
+    fn json_modal_sections_in_schedule_order() {
+        let reg = test_registry();
+        let json = build_and_emit_json(
+            concat!(
+                "clock 1kHz t {\n",
+                "    control {\n        constant(0.0) | detect() -> ctrl\n    }\n",
+                "    mode a {\n        constant(0.0) | stdout()\n    }\n",
+                "    mode b {\n        constant(0.0) | stdout()\n    }\n",
+                "    switch(ctrl, a, b) default a\n",
+                "}",
+            ),
+            &reg,
+        );
+        let labels: Vec<&str> = json["tasks"]["t"]["sections"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, ["control", "mode:a", "mode:b"]);
+    }
+
+    #[test]
+    fn json_round_trips_deterministically() {
+        let reg = test_registry();
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("examples/receiver.pdl");
+        let source = std::fs::read_to_string(&path).expect("failed to read receiver.pdl");
+        let first = serde_json::to_string_pretty(&build_and_emit_json(&source, &reg)).unwrap();
+        let second = serde_json::to_string_pretty(&build_and_emit_json(&source, &reg)).unwrap();
+        assert_eq!(first, second, "timing JSON must be deterministic");
+        let reparsed: serde_json::Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(serde_json::to_string_pretty(&reparsed).unwrap(), first);
+    }
+
     // ══════════════════════════════════════════════════════════════════════
     // Integration Tests
     // ══════════════════════════════════════════════════════════════════════
 
+    #[test]
+    fn per_core_groups_pinned_tasks_and_sums_utilization() {
+        let reg = test_registry();
+        let (schedule, graph) = build_schedule(
+            concat!(
+                "clock 1kHz audio { constant(0.0) | mul(2.0) | stdout() }\n",
+                "clock 1kHz ctrl { constant(0.0) | stdout() }\n",
+                "clock 1kHz rx { constant(0.0) | stdout() }\n",
+                "clock 1kHz ui { constant(0.0) | stdout() }\n",
+            ),
+            &reg,
+        );
+        let mut model = timing_model(&schedule, &graph);
+        let affinity: HashMap<String, u32> = [("audio", 0), ("ctrl", 0), ("rx", 2)]
+            .into_iter()
+            .map(|(task, core)| (task.to_string(), core))
+            .collect();
+
+        // No cost estimates yet: grouping only.
+        assert_eq!(
+            emit_per_core(&model, &affinity),
+            "core 0: audio(?), ctrl(?) = ?\ncore 2: rx(?) = ?\nfloating: ui(?) = ?\n"
+        );
+
+        // Spread each task's share of its 1 ms period over its firings.
+        for task in &mut model.tasks {
+            let share = match task.name.as_str() {
+                "audio" => 0.40,
+                "ctrl" => 0.15,
+                "rx" => 0.70,
+                _ => 0.05,
+            };
+            let firings = &mut task.sections[0].timing.firings;
+            let each_ns = share * 1_000_000.0 / firings.len() as f64;
+            for f in firings.iter_mut() {
+                f.duration_estimate_ns = Some(each_ns);
+            }
+        }
+        assert_eq!(
+            emit_per_core(&model, &affinity),
+            "core 0: audio(40%), ctrl(15%) = 55%\ncore 2: rx(70%) = 70%\nfloating: ui(5%) = 5%\n"
+        );
+    }
+
     #[test]
     fn example_pdl_timing_chart() {
         let reg = test_registry();
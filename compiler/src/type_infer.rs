@@ -31,6 +31,7 @@ use crate::resolve::ResolvedProgram;
 /// Real: int8 → int16 → int32 → float → double
 /// Complex: cfloat → cdouble
 /// Cross-family (real ↔ complex): NEVER implicit.
+/// `bool` belongs to no chain: it never widens and nothing widens to it.
 pub fn widening_rank(t: PipitType) -> Option<(u8, u8)> {
     // (family, rank) — family 0 = real, family 1 = complex
     match t {
@@ -41,7 +42,7 @@ pub fn widening_rank(t: PipitType) -> Option<(u8, u8)> {
         PipitType::Double => Some((0, 4)),
         PipitType::Cfloat => Some((1, 0)),
         PipitType::Cdouble => Some((1, 1)),
-        PipitType::Void => None,
+        PipitType::Bool | PipitType::Void => None,
     }
 }
 
@@ -177,8 +178,18 @@ pub fn type_infer(
             mono_actors: HashMap::new(),
         },
         diagnostics: Vec::new(),
-        buffer_types: HashMap::new(),
+        // Typed binds (`bind iq: cfloat = ...`) give `@iq` readers their
+        // upstream type; a pipeline writer's output type replaces it.
+        buffer_types: hir
+            .binds
+            .iter()
+            .filter_map(|b| {
+                let dtype = parse_type_name(&b.ty.as_ref()?.dtype.name)?;
+                Some((b.name.clone(), dtype))
+            })
+            .collect(),
         tap_types: HashMap::new(),
+        void_taps: HashMap::new(),
         effective_registry_meta_cache: RefCell::new(HashMap::new()),
         param_types,
         const_types,
@@ -201,6 +212,9 @@ struct TypeInferEngine<'a> {
     buffer_types: HashMap<String, PipitType>,
     /// Output types of tap/fork points, keyed by tap label name.
     tap_types: HashMap<String, PipitType>,
+    /// Taps of the current pipeline placed after a void-output sink, keyed by
+    /// tap label name: the sink's name and call span.
+    void_taps: HashMap<String, (String, Span)>,
     /// Per-call cached registry metadata for non-monomorphized actor calls.
     effective_registry_meta_cache: RefCell<HashMap<CallId, Option<&'a ActorMeta>>>,
     /// Name→type lookup maps for O(1) access.
@@ -251,6 +265,7 @@ impl<'a> TypeInferEngine<'a> {
     }
 
     fn infer_pipeline(&mut self, pipeline: &HirPipeline) {
+        self.collect_void_taps(pipeline);
         for pipe in &pipeline.pipes {
             self.infer_pipe_expr(pipe);
         }
@@ -288,6 +303,10 @@ impl<'a> TypeInferEngine<'a> {
             HirPipeSource::TapRef(name, _span) => {
                 upstream_type.or_else(|| self.tap_types.get(name.as_str()).copied())
             }
+            // The first gathered tap types the fan-in; the rest must match.
+            HirPipeSource::TapGather(taps) => {
+                upstream_type.or_else(|| self.check_gather_types(taps))
+            }
         };
 
         // Track tap element positions so we can record their types after inference
@@ -311,6 +330,12 @@ impl<'a> TypeInferEngine<'a> {
             }
         }
 
+        // Phase 0: Nothing flows out of a void-output sink; stop before
+        // inference reports the downstream calls as ambiguous.
+        if !self.check_calls_after_sink(&pipe.source, &calls) {
+            return;
+        }
+
         // Phase 1: Resolve explicit type arguments for polymorphic calls
         for call in &calls {
             self.resolve_explicit_type_args(call);
@@ -343,6 +368,42 @@ impl<'a> TypeInferEngine<'a> {
         }
     }
 
+    /// Check that all taps of a `gather(...)` carry one wire type: the taps
+    /// are concatenated into a single input buffer. Returns the type of the
+    /// first tap whose type is known.
+    fn check_gather_types(&mut self, taps: &[(String, Span)]) -> Option<PipitType> {
+        let typed: Vec<(&str, Span, PipitType)> = taps
+            .iter()
+            .filter_map(|(name, span)| {
+                let t = self.tap_types.get(name.as_str()).copied()?;
+                Some((name.as_str(), *span, t))
+            })
+            .collect();
+        let &(first_name, _, first_type) = typed.first()?;
+        if let Some(&(name, span, t)) = typed.iter().find(|(_, _, t)| *t != first_type) {
+            let mut diag = Diagnostic::new(
+                DiagLevel::Error,
+                span,
+                format!(
+                    "gathered tap ':{}' carries {}, but ':{}' carries {}",
+                    name, t, first_name, first_type
+                ),
+            )
+            .with_code(codes::E0104)
+            .with_hint(
+                "gathered taps are concatenated into one buffer; convert them to a common \
+                 type before tapping",
+            );
+            for &(other, other_span, t) in &typed {
+                if other_span != span {
+                    diag = diag.with_related(other_span, format!("':{}' is {}", other, t));
+                }
+            }
+            self.diagnostics.push(diag);
+        }
+        Some(first_type)
+    }
+
     /// Resolve explicit type arguments (e.g., `fir<float>(coeff)`).
     fn resolve_explicit_type_args(&mut self, call: &HirActorCall) {
         if call.type_args.is_empty() {
@@ -377,7 +438,7 @@ impl<'a> TypeInferEngine<'a> {
                         )
                         .with_code(codes::E0100)
                         .with_hint(
-                            "valid types: int8, int16, int32, float, double, cfloat, cdouble",
+                            "valid types: int8, int16, int32, float, double, cfloat, cdouble, bool",
                         )
                         .with_related(
                             call.call_span,
@@ -401,6 +462,119 @@ impl<'a> TypeInferEngine<'a> {
         self.store_monomorphized_actor(call.call_id, concrete_types, mono);
     }
 
+    /// True if `call` is a void-output actor (a sink).
+    fn is_void_sink(&self, call: &HirActorCall) -> bool {
+        self.registry
+            .lookup(&call.name)
+            .is_some_and(|meta| meta.out_type == TypeExpr::Concrete(PipitType::Void))
+    }
+
+    /// Record which taps of `pipeline` carry a sink's (void) output, directly
+    /// (`stdout() | :s`) or through other taps (`:s | :t`).
+    fn collect_void_taps(&mut self, pipeline: &HirPipeline) {
+        self.void_taps.clear();
+        loop {
+            let before = self.void_taps.len();
+            for pipe in &pipeline.pipes {
+                let mut origin = match &pipe.source {
+                    HirPipeSource::ActorCall(call) if self.is_void_sink(call) => {
+                        Some((call.name.clone(), call.call_span))
+                    }
+                    HirPipeSource::TapRef(name, _) => self.void_taps.get(name).cloned(),
+                    HirPipeSource::TapGather(taps) => taps
+                        .iter()
+                        .find_map(|(name, _)| self.void_taps.get(name).cloned()),
+                    _ => None,
+                };
+                for elem in &pipe.elements {
+                    match elem {
+                        HirPipeElem::ActorCall(call) => {
+                            origin = self
+                                .is_void_sink(call)
+                                .then(|| (call.name.clone(), call.call_span));
+                        }
+                        HirPipeElem::Tap(name, _) => {
+                            if let Some(origin) = &origin {
+                                self.void_taps
+                                    .entry(name.clone())
+                                    .or_insert_with(|| origin.clone());
+                            }
+                        }
+                        HirPipeElem::Probe(_, _) => {}
+                    }
+                }
+            }
+            if self.void_taps.len() == before {
+                break;
+            }
+        }
+    }
+
+    /// E0103: reject an actor whose upstream is a void-output sink, in the
+    /// same pipe (`a | stdout() | mul(2.0)`) or through a tap placed after
+    /// the sink (`a | stdout() | :s` then `:s | mul(2.0)`). Returns false if
+    /// one was found.
+    fn check_calls_after_sink(&mut self, source: &HirPipeSource, calls: &[&HirActorCall]) -> bool {
+        let tap_sink = match source {
+            HirPipeSource::TapRef(name, _) => self.void_taps.get(name).cloned(),
+            HirPipeSource::TapGather(taps) => taps
+                .iter()
+                .find_map(|(name, _)| self.void_taps.get(name).cloned()),
+            _ => None,
+        };
+        if let (Some((sink_name, sink_span)), Some(next)) = (tap_sink, calls.first()) {
+            self.push_call_after_sink(
+                next,
+                &sink_name,
+                sink_span,
+                format!(
+                    "tap the signal before '{}' and read that tap instead",
+                    sink_name
+                ),
+            );
+            return false;
+        }
+        for pair in calls.windows(2) {
+            let (sink, next) = (pair[0], pair[1]);
+            if !self.is_void_sink(sink) {
+                continue;
+            }
+            self.push_call_after_sink(
+                next,
+                &sink.name,
+                sink.call_span,
+                format!(
+                    "move '{}' before '{}', or end the pipeline at the sink",
+                    next.name, sink.name
+                ),
+            );
+            return false;
+        }
+        true
+    }
+
+    fn push_call_after_sink(
+        &mut self,
+        next: &HirActorCall,
+        sink_name: &str,
+        sink_span: Span,
+        hint: String,
+    ) {
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagLevel::Error,
+                next.call_span,
+                format!(
+                    "actor '{}' is placed after sink '{}', which produces no output",
+                    next.name, sink_name
+                ),
+            )
+            .with_code(codes::E0103)
+            .with_related(sink_span, format!("'{}' outputs void", sink_name))
+            .with_hint(hint),
+        );
+    }
+
     /// Infer type arguments from pipe context, with an optional initial upstream type.
     /// Returns the output type of the last actor in the chain.
     ///
@@ -680,6 +854,7 @@ impl<'a> TypeInferEngine<'a> {
                 // Look up const's declared value type
                 self.infer_const_type(&ident.name)
             }
+            Arg::ConstSlice(slice) => self.infer_const_type(&slice.name.name),
             _ => None,
         }
     }
@@ -771,7 +946,7 @@ impl<'a> TypeInferEngine<'a> {
 }
 
 /// Parse a type name string to PipitType.
-fn parse_type_name(name: &str) -> Option<PipitType> {
+pub(crate) fn parse_type_name(name: &str) -> Option<PipitType> {
     match name {
         "int8" => Some(PipitType::Int8),
         "int16" => Some(PipitType::Int16),
@@ -780,6 +955,7 @@ fn parse_type_name(name: &str) -> Option<PipitType> {
         "double" => Some(PipitType::Double),
         "cfloat" => Some(PipitType::Cfloat),
         "cdouble" => Some(PipitType::Cdouble),
+        "bool" => Some(PipitType::Bool),
         _ => None,
     }
 }
@@ -846,8 +1022,16 @@ fn monomorphize_actor(meta: &ActorMeta, concrete_types: &[PipitType]) -> ActorMe
                 kind: p.kind,
                 param_type: substitute_param_type(&p.param_type),
                 name: p.name.clone(),
+                default: p.default.clone(),
             })
             .collect(),
+        terminates: meta.terminates,
+        pure: meta.pure,
+        inline: meta.inline,
+        scratch: meta.scratch.clone(),
+        rate_change: meta.rate_change.clone(),
+        aliases: meta.aliases.clone(),
+        doc: meta.doc.clone(),
     }
 }
 
@@ -898,6 +1082,14 @@ mod tests {
         assert!(!can_widen(PipitType::Float, PipitType::Void));
     }
 
+    #[test]
+    fn bool_not_widenable() {
+        assert!(can_widen(PipitType::Bool, PipitType::Bool));
+        assert!(!can_widen(PipitType::Bool, PipitType::Int8));
+        assert!(!can_widen(PipitType::Bool, PipitType::Float));
+        assert!(!can_widen(PipitType::Int8, PipitType::Bool));
+    }
+
     #[test]
     fn common_widening_type_same() {
         assert_eq!(
@@ -941,7 +1133,15 @@ mod tests {
                 kind: crate::registry::ParamKind::Param,
                 param_type: crate::registry::ParamType::TypeParam("T".to_string()),
                 name: "gain".to_string(),
+                default: None,
             }],
+            terminates: false,
+            pure: false,
+            inline: false,
+            scratch: None,
+            rate_change: None,
+            aliases: Vec::new(),
+            doc: None,
         };
 
         let mono = monomorphize_actor(&meta, &[PipitType::Float]);
@@ -1010,6 +1210,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn actor_after_sink_e0103() {
+        let result =
+            infer_source("clock 1kHz t {\n    constant(0.0) | stdout() | mul(2.0) | stdout()\n}");
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert_eq!(errors.len(), 1, "only E0103 expected, got: {:#?}", errors);
+        assert_eq!(errors[0].code, Some(codes::E0103));
+        assert_eq!(
+            errors[0].message,
+            "actor 'mul' is placed after sink 'stdout', which produces no output"
+        );
+        assert_eq!(errors[0].related_spans.len(), 1);
+    }
+
+    #[test]
+    fn actor_after_sink_through_tap_e0103() {
+        let result = infer_source(concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | stdout() | :s\n",
+            "    :s | :u\n",
+            "    :u | mag() | stdout()\n",
+            "}\n",
+        ));
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert_eq!(errors.len(), 1, "only E0103 expected, got: {:#?}", errors);
+        assert_eq!(errors[0].code, Some(codes::E0103));
+        assert_eq!(
+            errors[0].message,
+            "actor 'mag' is placed after sink 'stdout', which produces no output"
+        );
+        assert!(errors[0]
+            .hint
+            .as_deref()
+            .is_some_and(|h| h.contains("tap the signal before 'stdout'")));
+    }
+
+    #[test]
+    fn tap_before_sink_is_not_e0103() {
+        let result = infer_source(
+            "clock 1kHz t {\n    constant(0.0) | :s | stdout()\n    :s | mag() | stdout()\n}",
+        );
+        assert!(
+            !result
+                .diagnostics
+                .iter()
+                .any(|d| d.code == Some(codes::E0103)),
+            "got: {:#?}",
+            result.diagnostics
+        );
+    }
+
+    #[test]
+    fn gather_tap_type_mismatch_e0104() {
+        let result = infer_source(
+            "clock 1kHz t {\n    constant<float>(1.0) | :a | stdout()\n    \
+             constant<int32>(2) | :b | stdout()\n    gather(:a, :b) | add() | stdout()\n}",
+        );
+        let errors: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == DiagLevel::Error)
+            .collect();
+        assert_eq!(errors.len(), 1, "only E0104 expected, got: {:#?}", errors);
+        assert_eq!(errors[0].code, Some(codes::E0104));
+        assert_eq!(
+            errors[0].message,
+            "gathered tap ':b' carries int32, but ':a' carries float"
+        );
+        assert_eq!(errors[0].related_spans.len(), 1);
+        assert_eq!(errors[0].related_spans[0].label, "':a' is float");
+    }
+
+    #[test]
+    fn gather_types_fan_in_from_taps() {
+        let result = infer_source(
+            "clock 1kHz t {\n    constant(1.0) | :a | stdout()\n    \
+             constant(2.0) | :b | stdout()\n    gather(:a, :b) | add() | stdout()\n}",
+        );
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .all(|d| d.level != DiagLevel::Error),
+            "unexpected errors: {:#?}",
+            result.diagnostics
+        );
+    }
+
     #[test]
     fn ambiguous_poly_with_upstream_context_e0101() {
         // fft(N) has type params <T> but input type is T and output is cfloat (fixed).
@@ -1073,6 +1369,9 @@ mod tests {
             args: vec![],
             type_args: vec![("badtype".to_string(), Span::new((), 16..23))],
             shape_constraint: None,
+            atomic_group: None,
+            persist: false,
+            allow_combinational_loop: false,
         };
         let hir = HirProgram {
             tasks: vec![HirTask {
@@ -1112,7 +1411,15 @@ mod tests {
                 kind: ParamKind::Param,
                 param_type: ParamType::TypeParam("T".to_string()),
                 name: "gain".to_string(),
+                default: None,
             }],
+            terminates: false,
+            pure: false,
+            inline: false,
+            scratch: None,
+            rate_change: None,
+            aliases: Vec::new(),
+            doc: None,
         });
         let resolved = crate::resolve::ResolvedProgram {
             consts: HashMap::new(),
@@ -1124,6 +1431,7 @@ mod tests {
             call_resolutions: HashMap::new(),
             task_resolutions: HashMap::new(),
             probes: vec![],
+            default_args: HashMap::new(),
             call_ids: HashMap::new(),
             call_spans: HashMap::new(),
             def_ids: HashMap::new(),
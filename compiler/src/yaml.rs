@@ -0,0 +1,246 @@
+// yaml.rs — Minimal YAML emitter for serializable compiler artifacts
+//
+// Renders any `Serialize` value as block-style YAML with the same keys, in
+// the same order, as its serde_json form, so JSON and YAML outputs of one
+// artifact diff line-for-line in content. Strings are always double-quoted
+// (JSON string escapes are valid YAML), which avoids YAML's implicit typing
+// of bare words like `yes` or `1e3`.
+//
+// Preconditions: `value` serializes to JSON (no non-string map keys).
+// Postconditions: returns a YAML document ending in a newline.
+// Failure modes: none beyond serialization errors, which panic as with
+//   `serde_json::to_string` on the same value.
+// Side effects: none.
+
+use std::fmt::{self, Write};
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::Serialize;
+
+/// Serialize `value` to a YAML document.
+pub fn to_string<T: Serialize>(value: &T) -> String {
+    let json = serde_json::to_string(value).expect("YAML source serialization");
+    // Round-trip through JSON into an order-preserving tree: serde_json's own
+    // `Value` sorts object keys, which would reorder struct fields.
+    let node: Node = serde_json::from_str(&json).expect("YAML source tree");
+    let mut out = String::new();
+    match &node {
+        Node::Map(entries) if !entries.is_empty() => write_map(&mut out, entries, 0, false),
+        Node::Seq(items) if !items.is_empty() => write_seq(&mut out, items, 0),
+        scalar => writeln!(out, "{}", inline(scalar)).unwrap(),
+    }
+    out
+}
+
+/// JSON value with object keys kept in document order.
+enum Node {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Seq(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Node, E> {
+        Ok(Node::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Node, E> {
+        Ok(Node::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Node, E> {
+        Ok(Node::Number(v.into()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Node, E> {
+        Ok(Node::Number(v.into()))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Node, E> {
+        serde_json::Number::from_f64(v)
+            .map(Node::Number)
+            .ok_or_else(|| E::custom("non-finite number"))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Node, E> {
+        Ok(Node::String(v.to_string()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Node::Seq(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Node::Map(entries))
+    }
+}
+
+/// Flow-style rendering for scalars and empty collections.
+fn inline(node: &Node) -> String {
+    match node {
+        Node::Null => "null".to_string(),
+        Node::Bool(b) => b.to_string(),
+        Node::Number(n) => n.to_string(),
+        Node::String(s) => quote(s),
+        Node::Seq(_) => "[]".to_string(),
+        Node::Map(_) => "{}".to_string(),
+    }
+}
+
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).expect("string serialization")
+}
+
+/// Keys that read as identifiers stay bare; anything YAML could take for
+/// another type or for syntax is quoted.
+fn key(k: &str) -> String {
+    let plain = k
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && k.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(
+            k.to_ascii_lowercase().as_str(),
+            "true" | "false" | "null" | "yes" | "no" | "on" | "off" | "y" | "n"
+        );
+    if plain {
+        k.to_string()
+    } else {
+        quote(k)
+    }
+}
+
+fn is_block(node: &Node) -> bool {
+    match node {
+        Node::Seq(items) => !items.is_empty(),
+        Node::Map(entries) => !entries.is_empty(),
+        _ => false,
+    }
+}
+
+/// Write `entries` at `indent`. With `first_inline`, the first key continues
+/// the current line (after a sequence dash).
+fn write_map(out: &mut String, entries: &[(String, Node)], indent: usize, first_inline: bool) {
+    for (i, (k, v)) in entries.iter().enumerate() {
+        if i > 0 || !first_inline {
+            out.push_str(&" ".repeat(indent));
+        }
+        out.push_str(&key(k));
+        out.push(':');
+        write_value(out, v, indent + 2);
+    }
+}
+
+fn write_seq(out: &mut String, items: &[Node], indent: usize) {
+    for item in items {
+        out.push_str(&" ".repeat(indent));
+        out.push('-');
+        match item {
+            Node::Map(entries) if !entries.is_empty() => {
+                out.push(' ');
+                write_map(out, entries, indent + 2, true);
+            }
+            _ => write_value(out, item, indent + 2),
+        }
+    }
+}
+
+/// Write the value following `key:` or `-`: inline for scalars, else a
+/// nested block at `indent`.
+fn write_value(out: &mut String, node: &Node, indent: usize) {
+    if !is_block(node) {
+        writeln!(out, " {}", inline(node)).unwrap();
+        return;
+    }
+    out.push('\n');
+    match node {
+        Node::Map(entries) => write_map(out, entries, indent, false),
+        Node::Seq(items) => write_seq(out, items, indent),
+        _ => unreachable!("scalars are written inline"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Entry {
+        name: String,
+        shape: Vec<u32>,
+        rate_hz: Option<f64>,
+        tags: Vec<Vec<u32>>,
+    }
+
+    #[derive(Serialize)]
+    struct Doc {
+        schema: u32,
+        entries: Vec<Entry>,
+        empty: std::collections::BTreeMap<String, u32>,
+        counts: std::collections::BTreeMap<String, u32>,
+    }
+
+    #[test]
+    fn block_yaml_keeps_field_order() {
+        let doc = Doc {
+            schema: 1,
+            entries: vec![Entry {
+                name: "yes".to_string(),
+                shape: vec![],
+                rate_hz: Some(48000.0),
+                tags: vec![vec![1, 2]],
+            }],
+            empty: Default::default(),
+            counts: [("b".to_string(), 2), ("a x".to_string(), 1)]
+                .into_iter()
+                .collect(),
+        };
+        assert_eq!(
+            to_string(&doc),
+            "schema: 1\n\
+             entries:\n  \
+               - name: \"yes\"\n    \
+                 shape: []\n    \
+                 rate_hz: 48000.0\n    \
+                 tags:\n      \
+                   -\n        \
+                     - 1\n        \
+                     - 2\n\
+             empty: {}\n\
+             counts:\n  \
+               \"a x\": 1\n  \
+               b: 2\n"
+        );
+    }
+
+    #[test]
+    fn scalar_document() {
+        assert_eq!(to_string(&Option::<u32>::None), "null\n");
+        assert_eq!(to_string(&"a\"b"), "\"a\\\"b\"\n");
+    }
+}
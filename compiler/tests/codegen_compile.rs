@@ -90,11 +90,21 @@ fn shared_manifest() -> &'static Path {
 /// Run pcc on a PDL input file with given include paths.
 /// Returns the pcc Output and the path to the generated .cpp file.
 fn run_pcc(pdl_input: &Path, include_paths: &[&Path]) -> (std::process::Output, PathBuf) {
+    run_pcc_with_args(pdl_input, include_paths, &[])
+}
+
+/// Run pcc with extra compiler flags (e.g. `--runtime single-thread`).
+fn run_pcc_with_args(
+    pdl_input: &Path,
+    include_paths: &[&Path],
+    pcc_args: &[&str],
+) -> (std::process::Output, PathBuf) {
     let cpp_out = temp_path("pipit_gen", "cpp");
     let pcc = pcc_binary();
     let mut cmd = Command::new(&pcc);
     cmd.arg(pdl_input.to_str().unwrap());
     cmd.arg("--actor-meta").arg(shared_manifest());
+    cmd.args(pcc_args);
     for path in include_paths {
         cmd.arg("-I").arg(path.to_str().unwrap());
     }
@@ -216,6 +226,18 @@ fn compile_cpp_to_binary(
     label: &str,
     runtime_include: &Path,
     examples_dir: &Path,
+) -> PathBuf {
+    compile_cpp_to_binary_with_flags(cxx, cpp_file, label, runtime_include, examples_dir, &[])
+}
+
+/// Like `compile_cpp_to_binary`, with extra C++ compiler flags.
+fn compile_cpp_to_binary_with_flags(
+    cxx: &str,
+    cpp_file: &Path,
+    label: &str,
+    runtime_include: &Path,
+    examples_dir: &Path,
+    cxx_flags: &[&str],
 ) -> PathBuf {
     let cpp = std::fs::read_to_string(cpp_file).expect("read cpp source");
     let bin_file = temp_path("pipit_bin", "");
@@ -223,6 +245,7 @@ fn compile_cpp_to_binary(
     let compile = Command::new(cxx)
         .arg("-std=c++20")
         .arg("-O0")
+        .args(cxx_flags)
         .arg("-I")
         .arg(runtime_include.to_str().unwrap())
         .arg("-I")
@@ -405,6 +428,38 @@ fn example_file_bind() {
     assert_pdl_file_compiles("bind.pdl");
 }
 
+#[test]
+fn example_file_bind_library() {
+    let cxx = match find_cxx_compiler() {
+        Some(c) => c,
+        None => {
+            eprintln!("SKIP: no C++ compiler found");
+            return;
+        }
+    };
+    let root = project_root();
+    let runtime_include = runtime_include_dir();
+    let pdl_path = root.join("examples").join("bind.pdl");
+
+    let (gen, cpp_out) = run_pcc_with_args(&pdl_path, &[&runtime_include], &["--library"]);
+    assert!(
+        gen.status.success(),
+        "pcc --library failed:\n{}",
+        String::from_utf8_lossy(&gen.stderr)
+    );
+
+    let cpp = std::fs::read_to_string(&cpp_out).expect("failed to read generated cpp");
+    let _ = std::fs::remove_file(&cpp_out);
+    assert!(cpp.contains("extern \"C\" int pipit_read_iq(float* out)"));
+    compile_cpp(
+        &cxx,
+        &cpp,
+        "bind.pdl --library",
+        &runtime_include,
+        &root.join("examples"),
+    );
+}
+
 // ── -I with directory path ──────────────────────────────────────────────
 
 #[test]
@@ -601,6 +656,14 @@ fn feedback_with_delay() {
     );
 }
 
+#[test]
+fn feedback_allow_combinational_loop() {
+    assert_inline_compiles(
+        "clock 1kHz t {\n  constant(0.0) | add(:fb) @allow_combinational_loop | :out | mul(0.5) | :fb\n  :out | stdout()\n}",
+        "feedback_allow_combinational_loop",
+    );
+}
+
 // ── Define (macro) ─────────────────────────────────────────────────────
 
 #[test]
@@ -671,6 +734,21 @@ fn modal_switch_default() {
     );
 }
 
+#[test]
+fn modal_switch_bind() {
+    assert_inline_compiles(
+        concat!(
+            "bind sel = udp(\"127.0.0.1:9300\")\n",
+            "clock 1kHz t {\n",
+            "  mode idle { constant(0.0) | stdout() }\n",
+            "  mode active { constant(0.0) | mul(2.0) | stdout() }\n",
+            "  switch(@sel, idle, active)\n",
+            "}\n",
+        ),
+        "modal_switch_bind",
+    );
+}
+
 // ── Core stdlib actors ─────────────────────────────────────────────────
 
 #[test]
@@ -796,6 +874,22 @@ fn actor_decimate() {
     );
 }
 
+#[test]
+fn actor_interpolate_then_decimate_runs() {
+    // interpolate(3) zero-stuffs 1.0 → [1, 0, 0]; decimate(3) keeps the first
+    // token of each 3, so the round trip reproduces the input.
+    if let Some((code, stdout, stderr)) = compile_and_run_inline(
+        "clock 1kHz t { constant(1.0) | interpolate(3) | decimate(3) | stdout() }",
+        "actor_interpolate_decimate",
+        &["--duration", "0.01"],
+    ) {
+        assert_eq!(code, 0, "interpolate/decimate run failed: {}", stderr);
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        assert!(!lines.is_empty(), "expected output, got: {}", stdout);
+        assert!(lines.iter().all(|l| *l == "1.000000"), "got: {}", stdout);
+    }
+}
+
 #[test]
 fn actor_stdout() {
     assert_inline_compiles("clock 1kHz t { constant(0.0) | stdout() }", "actor_stdout");
@@ -1099,6 +1193,24 @@ fn shape_constraint_const_ref() {
     );
 }
 
+#[test]
+fn shape_constraint_const_arithmetic_infers_param_value() {
+    // fft()[N*2], fft()[N/2] — the folded dimension reaches the C++ actor
+    let cpp = generate_inline_cpp(
+        "const N = 256\nclock 1kHz t { constant(0.0) | fft()[N*2] | mag() | stdout() }",
+        "shape_const_mul_infers_param",
+    );
+    assert!(
+        cpp.contains("Actor_fft{512}"),
+        "expected Actor_fft{{512}} but got:\n{}",
+        cpp
+    );
+    assert_inline_compiles(
+        "const N = 256\nclock 1kHz t { constant(0.0) | fft()[N/2] | mag() | stdout() }",
+        "shape_const_div",
+    );
+}
+
 #[test]
 fn shape_constraint_with_arg() {
     // fft(256) with explicit arg — backward-compat, no shape constraint needed
@@ -1317,6 +1429,17 @@ fn fir_span_inference_compiles() {
     );
 }
 
+#[test]
+fn fir_const_slice_compiles() {
+    assert_inline_compiles(
+        concat!(
+            "const coeff = [0.1, 0.2, 0.4, 0.2, 0.1, 0.0, 0.0]\n",
+            "clock 1kHz t { constant(0.0) | fir(coeff[2:5]) | stdout() }",
+        ),
+        "fir_const_slice",
+    );
+}
+
 #[test]
 fn fir_span_after_fft_compiles() {
     assert_inline_compiles(
@@ -1340,6 +1463,19 @@ fn shared_buffer_block_transfer_compiles() {
     );
 }
 
+#[test]
+fn pure_actor_cse_compiles() {
+    assert_inline_compiles(
+        concat!(
+            "clock 1kHz t {\n",
+            "    constant(0.0) | :raw | fft(256) | c2r() | stdout()\n",
+            "    :raw | fft(256) | ?spec | mag() | stdout()\n",
+            "}\n",
+        ),
+        "pure_actor_cse",
+    );
+}
+
 #[test]
 fn actor_hoisting_compiles() {
     assert_inline_compiles(
@@ -1400,6 +1536,27 @@ fn compile_and_run_inline(
     pdl_source: &str,
     test_name: &str,
     run_args: &[&str],
+) -> Option<(i32, String, String)> {
+    compile_and_run_inline_with_pcc_args(pdl_source, test_name, &[], run_args)
+}
+
+/// Compile inline PDL with extra pcc flags to a binary and run it.
+fn compile_and_run_inline_with_pcc_args(
+    pdl_source: &str,
+    test_name: &str,
+    pcc_args: &[&str],
+    run_args: &[&str],
+) -> Option<(i32, String, String)> {
+    compile_and_run_inline_with_flags(pdl_source, test_name, pcc_args, &[], run_args)
+}
+
+/// Like `compile_and_run_inline_with_pcc_args`, with extra C++ compiler flags.
+fn compile_and_run_inline_with_flags(
+    pdl_source: &str,
+    test_name: &str,
+    pcc_args: &[&str],
+    cxx_flags: &[&str],
+    run_args: &[&str],
 ) -> Option<(i32, String, String)> {
     let cxx = find_cxx_compiler()?;
     let root = project_root();
@@ -1408,7 +1565,7 @@ fn compile_and_run_inline(
     let pdl_file = temp_path("pipit_pdl", "pdl");
     std::fs::write(&pdl_file, pdl_source).expect("write pdl");
 
-    let (gen, cpp_file) = run_pcc(&pdl_file, &[&runtime_include]);
+    let (gen, cpp_file) = run_pcc_with_args(&pdl_file, &[&runtime_include], pcc_args);
     let _ = std::fs::remove_file(&pdl_file);
 
     if !gen.status.success() {
@@ -1419,12 +1576,13 @@ fn compile_and_run_inline(
         );
     }
 
-    let bin_file = compile_cpp_to_binary(
+    let bin_file = compile_cpp_to_binary_with_flags(
         &cxx,
         &cpp_file,
         test_name,
         &runtime_include,
         &root.join("examples"),
+        cxx_flags,
     );
 
     let run = Command::new("timeout")
@@ -1511,6 +1669,162 @@ fn duration_with_suffix() {
     }
 }
 
+#[test]
+fn scratch_is_private_per_actor_instance() {
+    let example_actors_h = project_root().join("examples").join("example_actors.h");
+    if let Some((code, stdout, stderr)) = compile_and_run_inline_with_pcc_args(
+        concat!(
+            "clock 1kHz t {\n",
+            "    constant(1.0) | sample_delay(2) | stdout()\n",
+            "    constant(2.0) | sample_delay(2) | stdout()\n",
+            "}\n",
+        ),
+        "scratch_per_instance",
+        &["-I", example_actors_h.to_str().unwrap()],
+        &["--duration", "0.02"],
+    ) {
+        assert_eq!(code, 0, "scratch run failed: {}", stderr);
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        assert!(
+            lines.len() >= 6,
+            "expected at least 3 ticks, got: {}",
+            stdout
+        );
+        // Each instance delays its own input by 2 firings; a shared delay
+        // line would leak 1.0 into the second instance's first outputs.
+        assert!(
+            lines[..4].iter().all(|l| *l == "0.000000"),
+            "delay lines should start empty, got: {}",
+            stdout
+        );
+        let mut third_tick = lines[4..6].to_vec();
+        third_tick.sort();
+        assert_eq!(third_tick, ["1.000000", "2.000000"], "got: {}", stdout);
+    }
+}
+
+#[test]
+fn buffer_read_anchored_fusion_runs() {
+    if let Some((code, stdout, stderr)) = compile_and_run_inline(
+        concat!(
+            "clock 1kHz a { constant(-4.0, 8) -> sig }\n",
+            "clock 1kHz b { @sig | abs() | sqrt() | decimate(8) | stdout() }\n",
+        ),
+        "buffer_read_anchored_fusion",
+        &["--duration", "0.02"],
+    ) {
+        assert_eq!(code, 0, "anchored fusion run failed: {}", stderr);
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        assert!(!lines.is_empty(), "expected output, got: {}", stdout);
+        // Every tick reads 8 tokens, takes sqrt(|x|) of each, keeps the first.
+        assert!(lines.iter().all(|l| *l == "2.000000"), "got: {}", stdout);
+    }
+}
+
+#[test]
+fn actor_body_uses_compile_time_token_counts() {
+    // frame_reverse sizes a stack array with IN_COUNT; impulse(4) marks the
+    // first sample of each 4-sample frame, so reversed frames end in 1.
+    let example_actors_h = project_root().join("examples").join("example_actors.h");
+    if let Some((code, stdout, stderr)) = compile_and_run_inline_with_pcc_args(
+        "clock 1kHz t { impulse<float>(4, 4) | frame_reverse(4) | stdout() }\n",
+        "actor_token_counts",
+        &["-I", example_actors_h.to_str().unwrap()],
+        &["--duration", "0.01"],
+    ) {
+        assert_eq!(code, 0, "frame_reverse run failed: {}", stderr);
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        assert!(lines.len() >= 8, "expected two frames, got: {}", stdout);
+        assert_eq!(
+            &lines[..8],
+            [
+                "0.000000", "0.000000", "0.000000", "1.000000", "0.000000", "0.000000", "0.000000",
+                "1.000000"
+            ],
+            "got: {}",
+            stdout
+        );
+    }
+}
+
+#[test]
+fn single_thread_runtime_runs_two_tasks() {
+    if let Some((code, stdout, stderr)) = compile_and_run_inline_with_pcc_args(
+        concat!(
+            "clock 1kHz producer { constant(1.0) | mul(2.0) -> sig }\n",
+            "clock 1kHz consumer { @sig | stdout() }\n",
+        ),
+        "single_thread_runtime",
+        &["--runtime", "single-thread"],
+        &["--duration", "0.05", "--stats"],
+    ) {
+        assert_eq!(code, 0, "single-thread run failed: {}", stderr);
+        assert!(
+            stdout.lines().any(|l| l.trim() == "2.000000"),
+            "consumer should print producer output, got: {}",
+            stdout
+        );
+        for task in ["producer", "consumer"] {
+            assert!(
+                stderr.contains(&format!("[stats] task '{}'", task)),
+                "expected stats for task '{}', got: {}",
+                task,
+                stderr
+            );
+        }
+    }
+}
+
+#[test]
+fn single_thread_writer_outrunning_reader_loses_no_tokens() {
+    // The writer counts 1, 2, 3, ... into a 2-token ring; the slower stdout
+    // reader leaves it full, so writer ticks must be skipped whole instead of
+    // firing the counter and dropping its output.
+    if let Some((code, stdout, stderr)) = compile_and_run_inline_with_pcc_args(
+        concat!(
+            "clock 1kHz writer {\n",
+            "    constant(1.0) | add(:fb) | :out | delay(1, 0.0) | :fb\n",
+            "    :out -> sig\n",
+            "}\n",
+            "clock 1kHz reader { @sig | stdout() }\n",
+        ),
+        "single_thread_no_token_loss",
+        &["--runtime", "single-thread"],
+        &["--duration", "0.1"],
+    ) {
+        assert_eq!(code, 0, "single-thread run failed: {}", stderr);
+        let values: Vec<f64> = stdout
+            .lines()
+            .map(|l| l.trim().parse().expect("numeric output"))
+            .collect();
+        assert!(!values.is_empty(), "expected output, got: {}", stdout);
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(*v, (i + 1) as f64, "token lost or reordered: {}", stdout);
+        }
+    }
+}
+
+#[test]
+fn no_exceptions_no_rtti_build_runs() {
+    if let Some((code, stdout, stderr)) = compile_and_run_inline_with_flags(
+        concat!(
+            "param g = 0.5\n",
+            "clock 1kHz t { constant(1.0) | fft(4) | c2r() | mul($g) | stdout() }\n",
+        ),
+        "no_exceptions_no_rtti",
+        &["--no-exceptions", "--no-rtti"],
+        &["-fno-exceptions", "-fno-rtti"],
+        &["--duration", "0.01", "--param", "g=2"],
+    ) {
+        assert_eq!(code, 0, "-fno-exceptions build failed: {}", stderr);
+        assert!(
+            stdout.lines().any(|l| l.trim() == "8.000000"),
+            "--param should be parsed without exceptions, got: {}",
+            stdout
+        );
+    }
+}
+
 // ── Probe Tests ─────────────────────────────────────────────────────────────
 
 #[test]
@@ -1863,6 +2177,34 @@ clock 48kHz audio {
     );
 }
 
+#[test]
+fn bind_in_declared_type_compiles() {
+    let cxx = match find_cxx_compiler() {
+        Some(c) => c,
+        None => return,
+    };
+    let runtime_include = runtime_include_dir();
+    let cpp = generate_cpp_from_inline(
+        r#"bind iq: int16[1] = udp("127.0.0.1:9100", chan=10)
+clock 48kHz audio {
+    @iq | stdout()
+}"#,
+        "bind_in_declared_type",
+        &[&runtime_include],
+    );
+    assert!(
+        cpp.contains("pipit::net::DTYPE_I16"),
+        "declared int16 bind should get an int16 adapter"
+    );
+    compile_cpp(
+        &cxx,
+        &cpp,
+        "bind_in_declared_type",
+        &runtime_include,
+        &project_root().join("examples"),
+    );
+}
+
 #[test]
 fn bind_in_compiles() {
     let cxx = match find_cxx_compiler() {
@@ -1873,7 +2215,7 @@ fn bind_in_compiles() {
     let cpp = generate_cpp_from_inline(
         r#"bind iq = udp("127.0.0.1:9100", chan=10)
 clock 48kHz audio {
-    @iq | binwrite("/dev/null")
+    @iq | binwrite("/dev/null", "float")
 }"#,
         "bind_in",
         &[&runtime_include],
@@ -1891,6 +2233,62 @@ clock 48kHz audio {
     );
 }
 
+#[test]
+fn bind_tcp_out_compiles() {
+    let cxx = match find_cxx_compiler() {
+        Some(c) => c,
+        None => return,
+    };
+    let runtime_include = runtime_include_dir();
+    let cpp = generate_cpp_from_inline(
+        r#"bind iq = tcp("127.0.0.1:9100", reconnect=500)
+clock 48kHz audio {
+    constant(0) -> iq
+}"#,
+        "bind_tcp_out",
+        &[&runtime_include],
+    );
+    assert!(
+        cpp.contains("TcpIoAdapter"),
+        "tcp bind OUT program should contain TcpIoAdapter"
+    );
+    compile_cpp(
+        &cxx,
+        &cpp,
+        "bind_tcp_out",
+        &runtime_include,
+        &project_root().join("examples"),
+    );
+}
+
+#[test]
+fn bind_tcp_in_compiles() {
+    let cxx = match find_cxx_compiler() {
+        Some(c) => c,
+        None => return,
+    };
+    let runtime_include = runtime_include_dir();
+    let cpp = generate_cpp_from_inline(
+        r#"bind iq = tcp("127.0.0.1:9100", backlog=2)
+clock 48kHz audio {
+    @iq | binwrite("/dev/null", "float")
+}"#,
+        "bind_tcp_in",
+        &[&runtime_include],
+    );
+    assert!(
+        cpp.contains("_bind_io_iq.recv("),
+        "tcp bind IN program should contain recv() call"
+    );
+    compile_cpp(
+        &cxx,
+        &cpp,
+        "bind_tcp_in",
+        &runtime_include,
+        &project_root().join("examples"),
+    );
+}
+
 #[test]
 fn bind_mixed_with_socket_actors_compiles() {
     let cxx = match find_cxx_compiler() {
@@ -1996,7 +2394,7 @@ fn bind_shm_in_compiles() {
     let cpp = generate_cpp_from_inline(
         r#"bind iq = shm("test_ring", slots=1024, slot_bytes=4096)
 clock 48kHz audio {
-    @iq | binwrite("/dev/null")
+    @iq | binwrite("/dev/null", "float")
 }"#,
         "bind_shm_in",
         &[&runtime_include],
@@ -8,6 +8,7 @@
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::OnceLock;
 
 fn pcc_binary() -> PathBuf {
@@ -56,6 +57,33 @@ fn shared_manifest() -> &'static Path {
     })
 }
 
+/// Unique counter for temp dir names (avoids collisions in parallel tests).
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Scratch directory for one test, removed on drop so a failing assertion
+/// does not leave it behind.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create a fresh `<tmp>/<prefix>_<pid>_<n>` directory.
+    fn new(prefix: &str) -> Self {
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), n));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        TempDir(dir)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
 /// `--emit ast` succeeds without any -I or --actor-path flags.
 /// This locks the invariant that `--emit ast` is a parse-only path
 /// with no registry dependency.
@@ -183,6 +211,49 @@ fn emit_manifest_rejects_actor_meta() {
     );
 }
 
+/// `--emit manifest --validate` compiles each actor alone and reports the
+/// broken one without failing the good one.
+#[test]
+fn emit_manifest_validate_reports_broken_actor() {
+    let tmp = TempDir::new("pcc_validate");
+    let dir = tmp.path();
+    std::fs::write(
+        dir.join("qa_actors.h"),
+        concat!(
+            "#pragma once\n",
+            "#include <pipit.h>\n\n",
+            "ACTOR(good_gain, IN(float, 1), OUT(float, 1), PARAM(float, gain)) {\n",
+            "    out[0] = in[0] * gain;\n",
+            "    return ACTOR_OK;\n",
+            "}\n};\n\n",
+            "template <typename T> ACTOR(broken_pass, IN(T, 1), OUT(T, 1)) {\n",
+            "    out[0] = in[0].no_such_member;\n",
+            "    return ACTOR_OK;\n",
+            "}\n};\n",
+        ),
+    )
+    .expect("write header");
+    let output = Command::new(pcc_binary())
+        .args(["--emit", "manifest", "--validate", "-I"])
+        .arg(dir)
+        .output()
+        .expect("failed to run pcc");
+
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("manifest is still emitted");
+    assert_eq!(manifest["actors"].as_array().unwrap().len(), 2);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("  ok    good_gain"), "got:\n{}", stderr);
+    assert!(stderr.contains("  FAIL  broken_pass"), "got:\n{}", stderr);
+    assert!(stderr.contains("no_such_member"), "got:\n{}", stderr);
+    assert!(
+        stderr.contains("actor validation: 1 passed, 1 failed"),
+        "got:\n{}",
+        stderr
+    );
+}
+
 // ── --emit build-info tests ────────────────────────────────────────────────
 
 /// `--emit build-info` generates valid provenance JSON.
@@ -222,6 +293,11 @@ fn emit_build_info_generates_valid_json() {
         json["compiler_version"].is_string(),
         "should have compiler_version"
     );
+    assert_eq!(
+        json["runtime_abi_version"],
+        pcc::pipeline::RUNTIME_ABI_VERSION,
+        "should have runtime_abi_version"
+    );
 
     // Hashes should be 64-char hex strings
     let source_hash = json["source_hash"].as_str().unwrap();
@@ -305,6 +381,86 @@ fn emit_build_info_succeeds_with_parse_invalid_source() {
     let json: serde_json::Value =
         serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("should be valid JSON: {}", e));
     assert!(json["source_hash"].is_string());
+    assert!(
+        json["memory"].is_null(),
+        "memory needs a compiled program: {}",
+        stdout
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("\"memory\" is null") && stderr.contains("source does not parse"),
+        "stderr should say why memory is missing: {}",
+        stderr
+    );
+}
+
+/// `--emit build-info` on a program that parses but fails to schedule
+/// reports `"memory": null` and prints the pipeline errors to stderr.
+#[test]
+fn emit_build_info_reports_pipeline_errors() {
+    let output = pcc_from_stdin(
+        "clock 1kHz t {\n    constant(0.0) | no_such_actor() | stdout()\n}\n",
+        &["--emit", "build-info"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("should be valid JSON: {}", e));
+    assert!(json["memory"].is_null(), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("\"memory\" is null") && stderr.contains("no_such_actor"),
+        "stderr should carry the pipeline errors: {}",
+        stderr
+    );
+}
+
+/// `--emit build-info` reports the static memory footprint of a program that
+/// compiles through scheduling.
+#[test]
+fn emit_build_info_reports_memory_footprint() {
+    let output = pcc_from_stdin(
+        concat!(
+            "const coeffs = [0.1, 0.2, 0.3, 0.4]\n",
+            "param g = 2.0\n",
+            "clock 1kHz a {\n",
+            "    constant(0.0) | fir(coeffs) | mul($g) -> sig\n",
+            "}\n",
+            "clock 1kHz b {\n",
+            "    @sig | stdout()\n",
+            "}\n",
+        ),
+        &["--emit", "build-info"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("should be valid JSON: {}", e));
+    let memory = &json["memory"];
+    let field = |name: &str| {
+        memory[name]
+            .as_u64()
+            .unwrap_or_else(|| panic!("missing memory.{}: {}", name, stdout))
+    };
+    assert!(field("inter_task_buffer_bytes") > 0, "{}", stdout);
+    assert!(field("edge_buffer_bytes") > 0, "{}", stdout);
+    assert_eq!(field("const_bytes"), 16, "four float taps");
+    assert_eq!(field("param_bytes"), 4, "one float param");
+    assert_eq!(
+        field("total_bytes"),
+        field("inter_task_buffer_bytes")
+            + field("edge_buffer_bytes")
+            + field("const_bytes")
+            + field("param_bytes")
+    );
 }
 
 // ── Manifest round-trip tests ─────────────────────────────────────────────
@@ -486,3 +642,1994 @@ fn emit_manifest_does_not_require_actor_meta() {
         String::from_utf8_lossy(&output.stderr)
     );
 }
+
+// ── Family buffer reuse ───────────────────────────────────────────────────
+
+/// `--emit graph` reports the savings of coalescing a scatter/gather family,
+/// and `--reuse-family-buffers` applies it in the generated C++.
+#[test]
+fn family_buffer_reuse_reported_and_applied() {
+    let pdl = std::env::temp_dir().join("pcc_test_family_reuse.pdl");
+    std::fs::write(
+        &pdl,
+        concat!(
+            "shared sig[4]\n",
+            "clock 1kHz writer { constant(0.0) -> sig[*] }\n",
+            "clock 1kHz reader { @sig[*] | stdout<float>() }\n",
+        ),
+    )
+    .expect("write pdl");
+
+    let graph = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .arg("--emit")
+        .arg("graph")
+        .output()
+        .expect("failed to run pcc --emit graph");
+    assert!(
+        graph.status.success(),
+        "--emit graph should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&graph.stderr)
+    );
+    let graph = String::from_utf8_lossy(&graph.stdout);
+    assert!(
+        graph.contains("family_reuse sig: elements=4 buffers=4->1 bytes=4864->4288 saved=576"),
+        "graph dump should report family reuse savings, got:\n{}",
+        graph
+    );
+
+    let cpp = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .arg("-I")
+        .arg(runtime_include_dir())
+        .arg("--emit")
+        .arg("cpp")
+        .arg("--reuse-family-buffers")
+        .output()
+        .expect("failed to run pcc --emit cpp");
+    let _ = std::fs::remove_file(&pdl);
+    assert!(
+        cpp.status.success(),
+        "--emit cpp should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&cpp.stderr)
+    );
+    let cpp = String::from_utf8_lossy(&cpp.stdout);
+    assert!(cpp.contains("_ringbuf_sig;") && !cpp.contains("_ringbuf_sig__0"));
+}
+
+// ── --version --verbose ───────────────────────────────────────────────────
+
+/// `--version --verbose` reports the runtime ABI and the ABI declared by the
+/// runtime header found via `-I`; plain `--version` stays a single line.
+#[test]
+fn version_verbose_reports_runtime_abi() {
+    let output = Command::new(pcc_binary())
+        .arg("--version")
+        .arg("--verbose")
+        .arg("-I")
+        .arg(runtime_include_dir())
+        .output()
+        .expect("failed to run pcc --version --verbose");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let expected_abi = format!("runtime ABI: {}", pcc::pipeline::RUNTIME_ABI_VERSION);
+    assert!(
+        stdout.contains(&expected_abi),
+        "verbose version should report ABI, got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!("(ABI {})", pcc::pipeline::RUNTIME_ABI_VERSION)),
+        "verbose version should report the header ABI, got:\n{}",
+        stdout
+    );
+    assert!(
+        !String::from_utf8_lossy(&output.stderr).contains("warning"),
+        "bundled runtime header should match the compiler ABI"
+    );
+
+    let plain = Command::new(pcc_binary())
+        .arg("--version")
+        .output()
+        .expect("failed to run pcc --version");
+    assert!(plain.status.success());
+    let stdout = String::from_utf8_lossy(&plain.stdout);
+    assert_eq!(stdout.trim(), format!("pcc {}", env!("CARGO_PKG_VERSION")));
+}
+
+// ── actor-help ────────────────────────────────────────────────────────────
+
+/// `pcc actor-help <actor>` prints the signature, the header doc comment,
+/// `@param` descriptions, and deprecation status.
+#[test]
+fn actor_help_prints_doc_comment() {
+    let tmp = TempDir::new("pcc_actor_help");
+    let dir = tmp.path();
+    let header = dir.join("documented.h");
+    std::fs::write(
+        &header,
+        concat!(
+            "#include <pipit.h>\n",
+            "/// @brief Scale each sample\n",
+            "///\n",
+            "/// Multiplies the input by a runtime gain.\n",
+            "/// @param gain Multiplier applied to each sample\n",
+            "/// @deprecated use mul instead\n",
+            "ACTOR(doc_scale, IN(float, N), OUT(float, N), RUNTIME_PARAM(float, gain) PARAM(int, N)) {\n",
+            "    for (int i = 0; i < N; ++i) out[i] = in[i] * gain;\n",
+            "    return ACTOR_OK;\n",
+            "}\n",
+        ),
+    )
+    .expect("write header");
+
+    let output = Command::new(pcc_binary())
+        .arg("actor-help")
+        .arg("doc_scale")
+        .arg("-I")
+        .arg(&header)
+        .arg("-I")
+        .arg(runtime_include_dir().join("pipit.h"))
+        .output()
+        .expect("failed to run pcc actor-help");
+
+    assert!(
+        output.status.success(),
+        "actor-help should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for expected in [
+        "actor doc_scale(gain: float, N: int)",
+        "in:  float[N]  (N token(s) per firing)",
+        "Scale each sample",
+        "Multiplies the input by a runtime gain.",
+        "gain (float, runtime): Multiplier applied to each sample",
+        "N (int, compile-time)",
+        "deprecated: use mul instead",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "actor-help output should contain '{}', got:\n{}",
+            expected,
+            stdout
+        );
+    }
+}
+
+/// Unknown actors are a usage error.
+#[test]
+fn actor_help_unknown_actor() {
+    let output = Command::new(pcc_binary())
+        .arg("actor-help")
+        .arg("no_such_actor")
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .output()
+        .expect("failed to run pcc actor-help");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown actor 'no_such_actor'"));
+}
+
+// ── --preprocess-only ─────────────────────────────────────────────────────
+
+/// `--preprocess-only` prints canonical source with defines inlined and
+/// spawn clauses expanded, and the output compiles like the original.
+#[test]
+fn preprocess_only_expands_define() {
+    let tmp = TempDir::new("pcc_preprocess");
+    let dir = tmp.path();
+    let pdl = dir.join("pp.pdl");
+    std::fs::write(
+        &pdl,
+        concat!(
+            "param gain = 2.0\n",
+            "define amp(g) {\n",
+            "    mul(g) | mul($gain)\n",
+            "}\n",
+            "clock 1kHz t {\n",
+            "    constant(0.0) | amp(3.0) | stdout()\n",
+            "}\n",
+        ),
+    )
+    .expect("write pdl");
+
+    let output = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--preprocess-only")
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .output()
+        .expect("failed to run pcc --preprocess-only");
+    assert!(
+        output.status.success(),
+        "--preprocess-only should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(
+        stdout.contains("constant(0.0) | mul(3.0) | mul($gain) | stdout()"),
+        "define should be inlined, got:\n{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("amp"),
+        "no define should remain:\n{}",
+        stdout
+    );
+
+    // The preprocessed source is itself valid input.
+    let expanded = dir.join("expanded.pdl");
+    std::fs::write(&expanded, &stdout).expect("write expanded");
+    let recompiled = Command::new(pcc_binary())
+        .arg(&expanded)
+        .arg("--emit")
+        .arg("cpp")
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .output()
+        .expect("failed to run pcc on preprocessed source");
+    assert!(
+        recompiled.status.success(),
+        "preprocessed source should compile.\nstderr: {}",
+        String::from_utf8_lossy(&recompiled.stderr)
+    );
+}
+
+// ── pcc graph-query ────────────────────────────────────────────────────────
+
+#[test]
+fn graph_query_reports_passing_and_failing_clauses() {
+    let tmp = TempDir::new("pcc_graph_query");
+    let dir = tmp.path();
+    let pdl = dir.join("q.pdl");
+    std::fs::write(
+        &pdl,
+        concat!(
+            "clock 1kHz audio {\n",
+            "    constant(0.0) | fft(256) | :spec | mag() | stdout()\n",
+            "    :spec | c2r() | stdout()\n",
+            "}\n",
+        ),
+    )
+    .expect("write pdl");
+    let query = |q: &str| {
+        Command::new(pcc_binary())
+            .arg("graph-query")
+            .arg(&pdl)
+            .arg(q)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .output()
+            .expect("failed to run pcc graph-query")
+    };
+
+    let pass = query(
+        "count(actor=fft, task=audio) == 1 && count(kind=fork) == 1 \
+         && !exists_cycle && max_repetition(actor=stdout) <= 256",
+    );
+    let fail = query("count(actor=fft) == 2 && count(actor=mag) == 1");
+    let malformed = query("count(actor=fft) = 1");
+
+    let stdout = String::from_utf8_lossy(&pass.stdout);
+    assert!(pass.status.success(), "query should pass:\n{}", stdout);
+    assert!(
+        stdout.contains("ok    count(actor=fft, task=audio) == 1  (actual 1)"),
+        "got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("ok    max_repetition(actor=stdout) <= 256  (actual 256)"),
+        "got:\n{}",
+        stdout
+    );
+
+    let stdout = String::from_utf8_lossy(&fail.stdout);
+    assert_eq!(fail.status.code(), Some(1), "got:\n{}", stdout);
+    assert!(
+        stdout.contains("FAIL  count(actor=fft) == 2  (actual 1)"),
+        "got:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("ok    count(actor=mag) == 1  (actual 1)"),
+        "got:\n{}",
+        stdout
+    );
+
+    assert_eq!(malformed.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&malformed.stderr)
+        .contains("error: invalid graph query: expected a comparison, found '='"));
+}
+
+// ── --emit report ───────────────────────────────────────────────────────────
+
+#[test]
+fn emit_report_writes_self_contained_html() {
+    let tmp = TempDir::new("pcc_emit_report");
+    let dir = tmp.path();
+    let pdl = dir.join("sample.pdl");
+    std::fs::write(
+        &pdl,
+        "clock 1kHz audio {\n    constant(0.0) | fft(256) | c2r() | stdout()\n}\n",
+    )
+    .expect("write pdl");
+    let html_path = dir.join("report.html");
+    let out = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .arg("--emit")
+        .arg("report")
+        .arg("-o")
+        .arg(&html_path)
+        .output()
+        .expect("failed to run pcc --emit report");
+    let html = std::fs::read_to_string(&html_path).unwrap_or_default();
+
+    assert!(
+        out.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(html.contains("<title>Pipit report: sample.pdl</title>"));
+    for id in ["graph", "schedule", "timing", "memory", "interface"] {
+        assert!(
+            html.contains(&format!("<section id=\"{id}\">")),
+            "missing section '{id}':\n{html}"
+        );
+    }
+    assert!(html.contains("<svg class=\"graph\""));
+    assert!(html.contains("<svg class=\"timing\""));
+}
+
+// ── pcc pipeline-hash ───────────────────────────────────────────────────────
+
+#[test]
+fn pipeline_hash_ignores_whitespace_but_tracks_topology() {
+    let tmp = TempDir::new("pcc_pipeline_hash");
+    let dir = tmp.path();
+    let hash_of = |name: &str, source: &str, extra: &[&str]| {
+        let pdl = dir.join(name);
+        std::fs::write(&pdl, source).expect("write pdl");
+        let output = Command::new(pcc_binary())
+            .arg("pipeline-hash")
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(extra)
+            .output()
+            .expect("failed to run pcc pipeline-hash");
+        assert!(
+            output.status.success(),
+            "pipeline-hash should succeed.\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    let base = hash_of(
+        "base.pdl",
+        "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}\n",
+        &[],
+    );
+    let reformatted = hash_of(
+        "reformatted.pdl",
+        "# gain stage\nclock 1kHz t {\n  constant(0.0)|mul(2.0)  |  stdout()  # sink\n}\n\n",
+        &[],
+    );
+    let rewired = hash_of(
+        "rewired.pdl",
+        "clock 1kHz t {\n    constant(0.0) | mul(2.0) | mul(2.0) | stdout()\n}\n",
+        &[],
+    );
+    let release = hash_of(
+        "release.pdl",
+        "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}\n",
+        &["--release"],
+    );
+    let single_thread = hash_of(
+        "single_thread.pdl",
+        "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}\n",
+        &["--runtime", "single-thread"],
+    );
+    let max_memory = hash_of(
+        "max_memory.pdl",
+        "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}\n",
+        &["--max-memory", "64MB"],
+    );
+    // The same headers under another directory: only the -I path differs.
+    let moved_include = dir.join("include");
+    std::fs::create_dir_all(&moved_include).expect("create include copy");
+    for entry in std::fs::read_dir(runtime_include_dir()).expect("read runtime include") {
+        let path = entry.expect("dir entry").path();
+        if path.is_file() {
+            std::fs::copy(&path, moved_include.join(path.file_name().unwrap()))
+                .expect("copy header");
+        }
+    }
+    let runtime_include = runtime_include_dir();
+    let base_source = "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}\n";
+    let with_include = hash_of(
+        "with_include.pdl",
+        base_source,
+        &["-I", runtime_include.to_str().unwrap()],
+    );
+    let with_moved_include = hash_of(
+        "with_moved_include.pdl",
+        base_source,
+        &["-I", moved_include.to_str().unwrap()],
+    );
+
+    assert_eq!(base.len(), 64, "expected a SHA-256 hex digest: {}", base);
+    assert_eq!(
+        with_include, with_moved_include,
+        "moving the -I directory must not change the hash"
+    );
+    assert_eq!(base, with_include, "-I paths must not change the hash");
+    assert_eq!(
+        base, reformatted,
+        "whitespace/comment edits must not change the hash"
+    );
+    assert_ne!(base, rewired, "a topology change must change the hash");
+    assert_ne!(base, release, "--release must change the hash");
+    assert_ne!(
+        base, single_thread,
+        "--runtime single-thread must change the hash"
+    );
+    assert_ne!(base, max_memory, "--max-memory must change the hash");
+}
+
+// ── --depfile ───────────────────────────────────────────────────────────────
+
+#[test]
+fn depfile_lists_source_manifest_and_headers() {
+    let tmp = TempDir::new("pcc_depfile");
+    let dir = tmp.path();
+    let target = dir.join("gain.cpp");
+    let depfile = dir.join("gain.cpp.d");
+    let pdl = project_root().join("examples/gain.pdl");
+
+    let output = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--emit")
+        .arg("cpp")
+        .arg("-o")
+        .arg(&target)
+        .arg("--depfile")
+        .arg(&depfile)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .arg("-I")
+        .arg(runtime_include_dir())
+        .output()
+        .expect("failed to run pcc --depfile");
+    assert!(
+        output.status.success(),
+        "--depfile should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let deps = std::fs::read_to_string(&depfile).expect("depfile written");
+
+    let (head, rest) = deps.split_once(':').expect("depfile has a rule");
+    assert_eq!(
+        head,
+        target.display().to_string(),
+        "rule target is the -o path"
+    );
+    let inputs: Vec<&str> = rest
+        .split("\\\n")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap().display().to_string();
+    assert!(
+        inputs.contains(&canonical(&pdl).as_str()),
+        "source missing: {}",
+        deps
+    );
+    assert!(
+        inputs.contains(&canonical(shared_manifest()).as_str()),
+        "manifest missing: {}",
+        deps
+    );
+    for entry in std::fs::read_dir(runtime_include_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|e| e == "h") {
+            assert!(
+                inputs.contains(&canonical(&path).as_str()),
+                "header {} missing: {}",
+                path.display(),
+                deps
+            );
+        }
+    }
+}
+
+#[test]
+fn depfile_requires_artifact_target() {
+    let output = Command::new(pcc_binary())
+        .arg(project_root().join("examples/gain.pdl"))
+        .arg("--emit")
+        .arg("cpp")
+        .arg("--depfile")
+        .arg(std::env::temp_dir().join("pcc_depfile_no_target.d"))
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .output()
+        .expect("failed to run pcc --depfile");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires -o"));
+}
+
+// ── --watch ────────────────────────────────────────────────────────────────
+
+/// `--watch` rebuilds on each source change, and a failing build does not
+/// end the watch loop.
+#[test]
+fn watch_recompiles_on_change_and_survives_errors() {
+    let tmp = TempDir::new("pcc_watch");
+    let dir = tmp.path();
+    let pdl = dir.join("watch.pdl");
+    let cpp = dir.join("watch.cpp");
+    let log = dir.join("watch.log");
+    let good = "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n}\n";
+    std::fs::write(&pdl, good).unwrap();
+
+    let mut child = Command::new(pcc_binary())
+        .arg("--watch")
+        .arg(&pdl)
+        .arg("--emit")
+        .arg("cpp")
+        .arg("-o")
+        .arg(&cpp)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .stderr(std::fs::File::create(&log).unwrap())
+        .spawn()
+        .expect("failed to run pcc --watch");
+    // Waits until the log holds `count` finished builds; returns the log.
+    let wait_for_builds = |count: usize| {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        loop {
+            let text = std::fs::read_to_string(&log).unwrap_or_default();
+            if text.matches("pcc: watch: build ").count() >= count {
+                return text;
+            }
+            if std::time::Instant::now() > deadline {
+                return format!("timed out waiting for build {}:\n{}", count, text);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    };
+
+    let first = wait_for_builds(1);
+    std::fs::write(&pdl, "clock 1kHz t {\n    constant(0.0) | nope()\n}\n").unwrap();
+    let second = wait_for_builds(2);
+    std::fs::write(&pdl, good.replace("2.0", "3.0")).unwrap();
+    let third = wait_for_builds(3);
+    let _ = child.kill();
+    let _ = child.wait();
+    let cpp_text = std::fs::read_to_string(&cpp).unwrap_or_default();
+
+    assert!(first.contains("pcc: watch: build succeeded"), "{}", first);
+    assert!(
+        second.contains("unknown actor or define 'nope'"),
+        "{}",
+        second
+    );
+    assert!(
+        second.contains("pcc: watch: build failed (exit 1)"),
+        "{}",
+        second
+    );
+    assert!(
+        third.matches("pcc: watch: build succeeded").count() == 2,
+        "{}",
+        third
+    );
+    assert!(
+        cpp_text.contains("Actor_mul<float>{3, 1}"),
+        "rebuilt output:\n{}",
+        cpp_text
+    );
+}
+
+#[test]
+fn watch_rejects_stdin_source() {
+    let output = Command::new(pcc_binary())
+        .arg("--watch")
+        .arg("-")
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .output()
+        .expect("failed to run pcc --watch");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("stdin"));
+}
+
+#[test]
+fn interface_baseline_reports_topology_change_as_breaking() {
+    let tmp = TempDir::new("pcc_iface_baseline");
+    let dir = tmp.path();
+    let pdl = dir.join("iface.pdl");
+    let baseline = dir.join("prev.json");
+    let write_pdl = |body: &str| {
+        let src = format!(
+            "bind iq = udp(\"127.0.0.1:9100\")\nclock 1kHz t {{\n    {} -> iq\n}}\n",
+            body
+        );
+        std::fs::write(&pdl, src).expect("write pdl");
+    };
+    let interface = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--emit")
+            .arg("interface")
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(extra)
+            .output()
+            .expect("failed to run pcc --emit interface")
+    };
+
+    write_pdl("constant(1.0) | mul(2.0)");
+    let out = interface(&["-o", baseline.to_str().unwrap()]);
+    assert!(out.status.success(), "baseline emit should succeed");
+
+    // Same topology: no changes, and the flag does not trip.
+    let same = interface(&[
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--fail-on-interface-break",
+    ]);
+    assert!(
+        same.status.success(),
+        "unchanged interface should pass.\nstderr: {}",
+        String::from_utf8_lossy(&same.stderr)
+    );
+
+    // Inserting an actor before the sink changes the bind's lineage.
+    write_pdl("constant(1.0) | mul(2.0) | mul(3.0)");
+    let report = interface(&["--baseline", baseline.to_str().unwrap()]);
+    let stderr = String::from_utf8_lossy(&report.stderr).to_string();
+    assert!(
+        report.status.success(),
+        "without --fail-on-interface-break the diff is informational.\nstderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("breaking: bind 'iq' stable_id changed"),
+        "stable_id change should be reported as breaking: {}",
+        stderr
+    );
+
+    let strict = interface(&[
+        "--baseline",
+        baseline.to_str().unwrap(),
+        "--fail-on-interface-break",
+    ]);
+    assert_eq!(
+        strict.status.code(),
+        Some(1),
+        "breaking change should fail with --fail-on-interface-break.\nstderr: {}",
+        String::from_utf8_lossy(&strict.stderr)
+    );
+}
+
+#[test]
+fn interface_format_yaml_for_emit_and_interface_out() {
+    let tmp = TempDir::new("pcc_iface_yaml");
+    let dir = tmp.path();
+    let pdl = dir.join("iface.pdl");
+    let side = dir.join("iface.yaml");
+    std::fs::write(
+        &pdl,
+        "bind iq = udp(\"127.0.0.1:9100\")\nclock 1kHz t {\n    constant(1.0) -> iq\n}\n",
+    )
+    .expect("write pdl");
+    let run = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .arg("--interface-format")
+            .arg("yaml")
+            .args(extra)
+            .output()
+            .expect("failed to run pcc --interface-format yaml")
+    };
+
+    let out = run(&["--emit", "interface"]);
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let yaml = String::from_utf8_lossy(&out.stdout).into_owned();
+    assert!(
+        yaml.starts_with("schema: 1\nbinds:\n  - stable_id: "),
+        "{}",
+        yaml
+    );
+
+    let out = run(&["--emit", "cpp", "--interface-out", side.to_str().unwrap()]);
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let written = std::fs::read_to_string(&side).expect("read --interface-out");
+    assert_eq!(written, yaml);
+
+    // Without an interface output the flag has no effect, so it is rejected.
+    let out = run(&["--emit", "cpp"]);
+    assert_eq!(out.status.code(), Some(2));
+    let manifest = Command::new(pcc_binary())
+        .args(["--emit", "manifest", "--interface-format", "yaml"])
+        .arg("-I")
+        .arg(runtime_include_dir())
+        .output()
+        .expect("failed to run pcc --emit manifest");
+    assert_eq!(manifest.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&manifest.stderr).contains("--interface-format requires"));
+}
+
+#[test]
+fn emit_header_declares_typed_bind_accessors() {
+    let tmp = TempDir::new("pcc_emit_header");
+    let dir = tmp.path();
+    let pdl = dir.join("header.pdl");
+    std::fs::write(
+        &pdl,
+        r#"bind spec = udp("127.0.0.1:9100")
+bind rf = udp("127.0.0.1:9200")
+clock 1kHz t {
+    @rf | fft(256) -> spec
+}
+"#,
+    )
+    .expect("write pdl");
+    let run = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(extra)
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    let header = run(&["--emit", "header"]);
+    let misuse = run(&["--emit", "header", "--library"]);
+
+    assert!(
+        header.status.success(),
+        "--emit header should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&header.stderr)
+    );
+    let text = String::from_utf8_lossy(&header.stdout);
+    for expected in [
+        "int pipit_start(void);",
+        "int pipit_stop(void);",
+        "typedef struct { float re; float im; } pipit_cfloat;",
+        "#define PIPIT_BIND_SPEC_TOKENS 256",
+        "int pipit_read_spec(pipit_cfloat* out);",
+        "int pipit_write_rf(const float* in);",
+    ] {
+        assert!(
+            text.contains(expected),
+            "missing '{}' in:\n{}",
+            expected,
+            text
+        );
+    }
+
+    assert_eq!(misuse.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&misuse.stderr).contains("--library requires --emit cpp"),
+        "stderr: {}",
+        String::from_utf8_lossy(&misuse.stderr)
+    );
+}
+
+#[test]
+fn when_debug_bind_absent_from_release_interface() {
+    let tmp = TempDir::new("pcc_when_bind");
+    let dir = tmp.path();
+    let pdl = dir.join("when.pdl");
+    std::fs::write(
+        &pdl,
+        r#"@when(debug) bind dbg = udp("127.0.0.1:9200")
+bind out = udp("127.0.0.1:9100")
+clock 1kHz t {
+    constant(1.0) | mul(2.0) -> dbg
+    constant(2.0) | mul(3.0) -> out
+}
+"#,
+    )
+    .expect("write pdl");
+    let interface = |extra: &[&str]| {
+        let output = Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--emit")
+            .arg("interface")
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(extra)
+            .output()
+            .expect("failed to run pcc --emit interface");
+        assert!(
+            output.status.success(),
+            "--emit interface should succeed.\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let debug = interface(&[]);
+    let release = interface(&["--release"]);
+
+    assert!(
+        debug.contains("\"dbg\""),
+        "debug build lists dbg: {}",
+        debug
+    );
+    assert!(
+        debug.contains("\"out\""),
+        "debug build lists out: {}",
+        debug
+    );
+    assert!(
+        !release.contains("\"dbg\""),
+        "release build omits dbg: {}",
+        release
+    );
+    assert!(
+        release.contains("\"out\""),
+        "release build lists out: {}",
+        release
+    );
+}
+
+#[test]
+fn diagnostic_context_renders_line_numbered_gutter() {
+    let tmp = TempDir::new("pcc_diag_ctx");
+    let dir = tmp.path();
+    let pdl = dir.join("bad.pdl");
+    std::fs::write(
+        &pdl,
+        "set mem = 64MB\nclock 1kHz t {\n    constant(0.0) | nosuch_actor() | stdout()\n}\n",
+    )
+    .expect("write pdl");
+    let run = |extra: &[&str]| {
+        let output = Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .arg("-o")
+            .arg(dir.join("bad.cpp"))
+            .args(extra)
+            .output()
+            .expect("failed to run pcc");
+        assert!(!output.status.success(), "unknown actor must fail");
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    let plain = run(&[]);
+    let gutter = run(&["--diagnostic-context", "1"]);
+
+    assert!(
+        !plain.contains("  3 | "),
+        "default output has no gutter: {}",
+        plain
+    );
+    assert!(
+        gutter.contains("  2 | clock 1kHz t {"),
+        "preceding context line is numbered: {}",
+        gutter
+    );
+    assert!(
+        gutter.contains("  3 |     constant(0.0) | nosuch_actor() | stdout()"),
+        "error line is numbered: {}",
+        gutter
+    );
+    assert!(
+        gutter.contains("    |                     ^^^^^^^^^^^^\n"),
+        "carets sit under the span: {}",
+        gutter
+    );
+    assert!(
+        gutter.contains("  4 | }"),
+        "following context line is numbered: {}",
+        gutter
+    );
+    assert!(
+        !gutter.contains("set mem"),
+        "lines outside the context window are omitted: {}",
+        gutter
+    );
+}
+
+#[test]
+fn color_only_adds_escapes_to_human_diagnostics() {
+    let tmp = TempDir::new("pcc_color");
+    let dir = tmp.path();
+    let pdl = dir.join("bad.pdl");
+    std::fs::write(
+        &pdl,
+        "clock 1kHz t {\n    constant(0.0) | nosuch_actor() | stdout()\n}\n",
+    )
+    .expect("write pdl");
+    let run = |extra: &[&str]| {
+        let output = Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .arg("-o")
+            .arg(dir.join("bad.cpp"))
+            .args(extra)
+            .env_remove("NO_COLOR")
+            .output()
+            .expect("failed to run pcc");
+        assert!(!output.status.success(), "unknown actor must fail");
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    // stderr is a pipe here, so `auto` (the default) stays plain.
+    let default = run(&[]);
+    let never = run(&["--color", "never"]);
+    let no_color = run(&["--no-color"]);
+    let always = run(&["--color", "always"]);
+    let always_ctx = run(&["--color", "always", "--diagnostic-context", "1"]);
+    let plain_ctx = run(&["--diagnostic-context", "1"]);
+    let json = run(&["--color", "always", "--diagnostic-format", "json"]);
+
+    assert_eq!(never, default, "--color never matches uncolored output");
+    assert_eq!(no_color, default, "--no-color matches uncolored output");
+    assert!(!default.contains('\x1b'), "{}", default);
+    assert!(
+        always.starts_with("\x1b[1;31merror[E0011]\x1b[0m: "),
+        "{:?}",
+        always
+    );
+    assert!(
+        always.contains("\n                      \x1b[36m^^^^^^^^^^^^\x1b[0m\n"),
+        "carets keep their column: {:?}",
+        always
+    );
+    let strip = |text: &str| {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(at) = rest.find('\x1b') {
+            out.push_str(&rest[..at]);
+            let end = rest[at..].find('m').expect("SGR terminator");
+            rest = &rest[at + end + 1..];
+        }
+        out.push_str(rest);
+        out
+    };
+    assert_eq!(strip(&always), default, "color only adds escapes");
+    assert_eq!(strip(&always_ctx), plain_ctx, "color only adds escapes");
+    assert!(!json.contains('\x1b'), "JSON is never colored: {}", json);
+}
+
+// ── --openmp ───────────────────────────────────────────────────────────────
+
+#[test]
+fn openmp_adds_fopenmp_to_compile_command() {
+    let tmp = TempDir::new("pcc_openmp");
+    let dir = tmp.path();
+    let pdl = dir.join("omp.pdl");
+    std::fs::write(
+        &pdl,
+        "clock 1kHz t {\n    constant(0.0) | fft(256) | c2r() | abs() | mean(256) | stdout()\n}\n",
+    )
+    .expect("write pdl");
+    // `true` stands in for the C++ compiler; --verbose echoes its command line.
+    let run = |extra: &[&str]| {
+        let output = Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "exe", "--cc", "true", "--verbose", "-o"])
+            .arg(dir.join("omp"))
+            .args(extra)
+            .output()
+            .expect("failed to run pcc");
+        assert!(output.status.success(), "pcc failed: {:?}", output);
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    let with = run(&["--openmp"]);
+    let without = run(&[]);
+
+    assert!(with.contains("\"-fopenmp\""), "got:\n{}", with);
+    assert!(!without.contains("-fopenmp"), "got:\n{}", without);
+}
+
+#[test]
+fn no_exceptions_no_rtti_add_compile_flags() {
+    let tmp = TempDir::new("pcc_noexc");
+    let dir = tmp.path();
+    let pdl = dir.join("noexc.pdl");
+    std::fs::write(&pdl, "clock 1kHz t {\n    constant(0.0) | stdout()\n}\n").expect("write pdl");
+    let output = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .args(["--emit", "exe", "--cc", "true", "--verbose", "-o"])
+        .arg(dir.join("noexc"))
+        .args(["--no-exceptions", "--no-rtti"])
+        .output()
+        .expect("failed to run pcc");
+    assert!(output.status.success(), "pcc failed: {:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("\"-fno-exceptions\""), "got:\n{}", stderr);
+    assert!(stderr.contains("\"-fno-rtti\""), "got:\n{}", stderr);
+}
+
+// ── --max-memory ──────────────────────────────────────────────────────────
+
+#[test]
+fn max_memory_overrides_set_mem() {
+    let tmp = TempDir::new("pcc_max_mem");
+    let dir = tmp.path();
+    let run = |set_mem: &str, extra: &[&str]| {
+        // fft(256) -> sig needs a 4096-byte inter-task buffer
+        let pdl = dir.join("mem.pdl");
+        std::fs::write(
+            &pdl,
+            format!(
+                "{}clock 1kHz a {{ constant(0.0) | fft(256) -> sig }}\nclock 1kHz b {{ @sig | c2r(256) | stdout() }}\n",
+                set_mem
+            ),
+        )
+        .expect("write pdl");
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "schedule"])
+            .args(extra)
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    let raised = run("set mem = 1KB\n", &["--max-memory", "64MB"]);
+    let lowered = run("", &["--max-memory", "1KB"]);
+    let plain = run("", &["--max-memory", "8192"]);
+    let invalid = run("", &["--max-memory", "64XB"]);
+
+    assert!(raised.status.success(), "pcc failed: {:?}", raised);
+    let stderr = String::from_utf8_lossy(&raised.stderr);
+    assert!(
+        stderr.contains("note: --max-memory overrides `set mem`"),
+        "got:\n{}",
+        stderr
+    );
+
+    assert_eq!(lowered.status.code(), Some(1), "{:?}", lowered);
+    let stderr = String::from_utf8_lossy(&lowered.stderr);
+    assert!(
+        stderr.contains("available 1024 bytes (--max-memory)"),
+        "got:\n{}",
+        stderr
+    );
+    assert!(!stderr.contains("overrides `set mem`"), "got:\n{}", stderr);
+
+    assert!(plain.status.success(), "pcc failed: {:?}", plain);
+
+    assert_eq!(invalid.status.code(), Some(2), "{:?}", invalid);
+    assert!(String::from_utf8_lossy(&invalid.stderr).contains("invalid size '64XB'"));
+}
+
+#[test]
+fn platform_memory_resolves_set_mem_percent() {
+    let tmp = TempDir::new("pcc_platform_mem");
+    let dir = tmp.path();
+    let pdl = dir.join("mem.pdl");
+    // fft(256) -> sig needs a 4096-byte inter-task buffer
+    std::fs::write(
+        &pdl,
+        "set mem = 50%\nclock 1kHz a { constant(0.0) | fft(256) -> sig }\nclock 1kHz b { @sig | c2r(256) | stdout() }\n",
+    )
+    .expect("write pdl");
+    let run = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "schedule"])
+            .args(extra)
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    let fits = run(&["--platform-memory", "8KB"]);
+    let tight = run(&["--platform-memory", "4KB"]);
+    let missing = run(&[]);
+
+    assert!(fits.status.success(), "pcc failed: {:?}", fits);
+
+    assert_eq!(tight.status.code(), Some(1), "{:?}", tight);
+    let stderr = String::from_utf8_lossy(&tight.stderr);
+    assert!(
+        stderr.contains("available 2048 bytes (set mem = 50% of --platform-memory)"),
+        "got:\n{}",
+        stderr
+    );
+
+    assert_eq!(missing.status.code(), Some(1), "{:?}", missing);
+    let stderr = String::from_utf8_lossy(&missing.stderr);
+    assert!(stderr.contains("E0315"), "got:\n{}", stderr);
+}
+
+// ── Deterministic schedule ────────────────────────────────────────────────
+
+#[test]
+fn schedule_output_is_byte_identical_across_runs() {
+    let tmp = TempDir::new("pcc_det_schedule");
+    let dir = tmp.path();
+    let pdl = dir.join("det.pdl");
+    // Fork, feedback, multi-task and modal subgraphs all have several valid
+    // firing orders; each pcc process seeds its HashMaps differently.
+    std::fs::write(
+        &pdl,
+        concat!(
+            "const coeff = [0.1, 0.2, 0.4, 0.2, 0.1]\n",
+            "param gain = 0.5\n",
+            "clock 10kHz fork {\n",
+            "    constant(0.0) | :raw | fir(coeff) | mul($gain) -> filtered\n",
+            "    :raw | mag() | stdout()\n",
+            "    :raw | mul(2.0) | stdout()\n",
+            "}\n",
+            "clock 1kHz loop {\n",
+            "    constant(1.0) | add(:fb) | :out | stdout()\n",
+            "    :out | delay(1, 0.0) | :fb\n",
+            "}\n",
+            "clock 1kHz sel {\n",
+            "    control {\n        constant(0.0) -> ctrl\n    }\n",
+            "    mode a {\n        @filtered | decimate(10) | stdout()\n    }\n",
+            "    mode b {\n        @filtered | decimate(10) | mul(3.0) | stdout()\n    }\n",
+            "    switch(ctrl, a, b)\n",
+            "}\n",
+        ),
+    )
+    .expect("write pdl");
+    let run = || {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "schedule"])
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    let first = run();
+    let rest: Vec<_> = (0..4).map(|_| run()).collect();
+
+    assert!(first.status.success(), "pcc failed: {:?}", first);
+    for other in &rest {
+        assert!(other.status.success(), "pcc failed: {:?}", other);
+        assert_eq!(
+            other.stdout,
+            first.stdout,
+            "schedule differs between runs:\n{}\n---\n{}",
+            String::from_utf8_lossy(&first.stdout),
+            String::from_utf8_lossy(&other.stdout)
+        );
+    }
+}
+
+// ── --release mul fusion ──────────────────────────────────────────────────
+
+#[test]
+fn release_fuses_constant_muls() {
+    let tmp = TempDir::new("pcc_mul_fusion");
+    let dir = tmp.path();
+    let pdl = dir.join("fuse.pdl");
+    std::fs::write(
+        &pdl,
+        "clock 1kHz t { constant(1.0) | mul(2.0) | mul(0.5) | stdout() }\n",
+    )
+    .expect("write pdl");
+    let run = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "cpp"])
+            .args(extra)
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    let release = run(&["--release", "--verbose"]);
+    let debug = run(&[]);
+
+    assert!(release.status.success(), "pcc failed: {:?}", release);
+    let stderr = String::from_utf8_lossy(&release.stderr);
+    assert!(
+        stderr.contains("pcc: note: fused mul(2) | mul(0.5) into mul(1) in task 't'"),
+        "got:\n{}",
+        stderr
+    );
+    let cpp = String::from_utf8_lossy(&release.stdout);
+    assert_eq!(cpp.matches("Actor_mul<float>{").count(), 1, "got:\n{}", cpp);
+
+    assert!(debug.status.success(), "pcc failed: {:?}", debug);
+    let cpp = String::from_utf8_lossy(&debug.stdout);
+    assert_eq!(cpp.matches("Actor_mul<float>{").count(), 2, "got:\n{}", cpp);
+}
+
+#[test]
+fn peephole_rewrites_mag_sqr_into_magsq() {
+    let tmp = TempDir::new("pcc_peephole");
+    let dir = tmp.path();
+    let pdl = dir.join("magsq.pdl");
+    std::fs::write(
+        &pdl,
+        "clock 1kHz t { constant(0.0) | fft(64) | mag() | sqr() | stdout() }\n",
+    )
+    .expect("write pdl");
+    let out = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .args(["--emit", "cpp", "--verbose"])
+        .output()
+        .expect("failed to run pcc");
+
+    assert!(out.status.success(), "pcc failed: {:?}", out);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("pcc: note: rewrote mag | sqr into magsq in task 't'"),
+        "got:\n{}",
+        stderr
+    );
+    let cpp = String::from_utf8_lossy(&out.stdout);
+    assert!(cpp.contains("Actor_magsq"), "got:\n{}", cpp);
+    assert!(!cpp.contains("Actor_mag{"), "got:\n{}", cpp);
+    assert!(!cpp.contains("Actor_sqr"), "got:\n{}", cpp);
+}
+
+// ── --compdb ──────────────────────────────────────────────────────────────
+
+#[test]
+fn compdb_records_exe_compile_command() {
+    let tmp = TempDir::new("pcc_compdb");
+    let dir = tmp.path();
+    let pdl = dir.join("gain.pdl");
+    std::fs::write(&pdl, "clock 1kHz t { constant(0.0) | stdout() }\n").expect("write pdl");
+    let cpp = dir.join("gain.cpp");
+    let compdb = dir.join("compile_commands.json");
+    let run = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .arg("-I")
+            .arg(runtime_include_dir())
+            .args(["--emit", "cpp", "--release", "--compdb"])
+            .arg(&compdb)
+            .args(extra)
+            .output()
+            .expect("failed to run pcc")
+    };
+    let output = run(&["-o", cpp.to_str().unwrap()]);
+    let db = std::fs::read_to_string(&compdb);
+    let no_output = run(&[]);
+
+    assert!(output.status.success(), "pcc failed: {:?}", output);
+    let json: serde_json::Value =
+        serde_json::from_str(&db.expect("compdb written")).expect("compdb is JSON");
+    let entries = json.as_array().expect("compdb is an array");
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert!(
+        entry["file"].as_str().unwrap().ends_with("gain.cpp"),
+        "{}",
+        entry
+    );
+    let args: Vec<&str> = entry["arguments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a.as_str().unwrap())
+        .collect();
+    assert_eq!(args[0], "g++");
+    for flag in ["-std=c++20", "-O2", "-DNDEBUG", "-include", "-lpthread"] {
+        assert!(args.contains(&flag), "missing {} in {:?}", flag, args);
+    }
+    assert!(args.last().unwrap().ends_with("gain.cpp"), "{:?}", args);
+
+    assert_eq!(no_output.status.code(), Some(2), "{:?}", no_output);
+}
+
+#[test]
+fn cpp_std_selects_standard_in_compdb() {
+    let tmp = TempDir::new("pcc_cpp_std");
+    let dir = tmp.path();
+    let pdl = dir.join("gain.pdl");
+    std::fs::write(&pdl, "clock 1kHz t { constant(0.0) | stdout() }\n").expect("write pdl");
+    let cpp = dir.join("gain.cpp");
+    let compdb = dir.join("compile_commands.json");
+    let run = |std: &str| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "cpp", "--cpp-std", std, "--compdb"])
+            .arg(&compdb)
+            .arg("-o")
+            .arg(&cpp)
+            .output()
+            .expect("failed to run pcc")
+    };
+    let output = run("c++23");
+    let db = std::fs::read_to_string(&compdb);
+    let rejected = run("c++14");
+
+    assert!(output.status.success(), "pcc failed: {:?}", output);
+    let json: serde_json::Value =
+        serde_json::from_str(&db.expect("compdb written")).expect("compdb is JSON");
+    let args: Vec<&str> = json[0]["arguments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a.as_str().unwrap())
+        .collect();
+    assert!(args.contains(&"-std=c++23"), "{:?}", args);
+    assert!(!args.contains(&"-std=c++20"), "{:?}", args);
+
+    assert_eq!(rejected.status.code(), Some(2), "{:?}", rejected);
+    let stderr = String::from_utf8_lossy(&rejected.stderr);
+    assert!(stderr.contains("c++17, c++20, c++23"), "got:\n{}", stderr);
+}
+
+// ── --emit deps ───────────────────────────────────────────────────────────
+
+#[test]
+fn emit_deps_lists_only_used_headers() {
+    let tmp = TempDir::new("pcc_emit_deps");
+    let dir = tmp.path();
+    let pdl = dir.join("deps.pdl");
+    std::fs::write(
+        &pdl,
+        "clock 1kHz t {\n    constant(0.0) | mul(2.0) | stdout()\n    constant(0.0) | mul(3.0) | stdout()\n}\n",
+    )
+    .expect("write pdl");
+    let output = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .arg("-I")
+        .arg(runtime_include_dir())
+        .arg("-I")
+        .arg(examples_dir())
+        .args(["--emit", "deps"])
+        .output()
+        .expect("failed to run pcc");
+
+    assert!(output.status.success(), "pcc failed: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    let mut sorted = lines.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(lines, sorted, "deps must be sorted and deduplicated");
+    assert!(
+        lines.iter().any(|l| l.ends_with("std_actors.h")),
+        "got:\n{}",
+        stdout
+    );
+    assert!(
+        !lines.iter().any(|l| l.ends_with("example_actors.h")),
+        "unused headers must be excluded, got:\n{}",
+        stdout
+    );
+}
+
+// ── --emit manifest-diff ──────────────────────────────────────────────────
+
+#[test]
+fn manifest_diff_reports_breaking_changes() {
+    let tmp = TempDir::new("pcc_manifest_diff");
+    let dir = tmp.path();
+    let old = shared_manifest();
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(old).expect("read manifest"))
+            .expect("manifest is JSON");
+    let actors = json["actors"].as_array_mut().expect("actors array");
+    actors.retain(|a| a["name"] != "stdout");
+    let new = dir.join("new.json");
+    std::fs::write(&new, serde_json::to_string_pretty(&json).unwrap()).expect("write manifest");
+
+    let run = |compare: &Path| {
+        Command::new(pcc_binary())
+            .arg("--actor-meta")
+            .arg(old)
+            .args(["--emit", "manifest-diff", "--compare"])
+            .arg(compare)
+            .output()
+            .expect("failed to run pcc")
+    };
+    let changed = run(&new);
+    let same = run(old);
+    let missing = Command::new(pcc_binary())
+        .arg("--actor-meta")
+        .arg(old)
+        .args(["--emit", "manifest-diff"])
+        .output()
+        .expect("failed to run pcc");
+
+    assert_eq!(changed.status.code(), Some(1), "{:?}", changed);
+    let stdout = String::from_utf8_lossy(&changed.stdout);
+    assert!(
+        stdout.contains("breaking: actor 'stdout' removed"),
+        "got:\n{}",
+        stdout
+    );
+
+    assert!(same.status.success(), "pcc failed: {:?}", same);
+    assert_eq!(String::from_utf8_lossy(&same.stdout), "no actor changes\n");
+
+    assert_eq!(missing.status.code(), Some(2), "{:?}", missing);
+}
+
+// ── --dump-passes ─────────────────────────────────────────────────────────
+
+#[test]
+fn dump_passes_lists_plan_for_emit_stage() {
+    let run = |args: &[&str]| {
+        let output = Command::new(pcc_binary())
+            .arg("--dump-passes")
+            .args(args)
+            .output()
+            .expect("failed to run pcc");
+        assert!(output.status.success(), "pcc failed: {:?}", output);
+        String::from_utf8(output.stdout).expect("utf-8 output")
+    };
+
+    let graph_dot = run(&["--emit", "graph-dot"]);
+    assert_eq!(
+        graph_dot.lines().take(5).collect::<Vec<_>>(),
+        vec![
+            "pass plan for --emit graph-dot (terminal: build_graph)",
+            "  parse        run",
+            "  resolve      run",
+            "  build_hir    run      after resolve",
+            "  build_graph  run      after build_hir",
+        ]
+    );
+    assert!(
+        graph_dot.contains("  type_infer   skipped\n"),
+        "{}",
+        graph_dot
+    );
+    assert!(
+        graph_dot.contains("  codegen      skipped\n"),
+        "{}",
+        graph_dot
+    );
+
+    // --interface-out promotes the terminal so the LIR is built.
+    let promoted = run(&["--emit", "graph-dot", "--interface-out", "unused.json"]);
+    assert!(
+        promoted.starts_with("pass plan for --emit graph-dot (terminal: build_lir)\n"),
+        "{}",
+        promoted
+    );
+    assert!(
+        promoted.contains("  analyze      run      after lower, build_graph\n"),
+        "{}",
+        promoted
+    );
+
+    let manifest = run(&["--emit", "manifest"]);
+    assert!(
+        manifest.starts_with("pass plan for --emit manifest (no semantic passes)\n"),
+        "{}",
+        manifest
+    );
+    assert!(!manifest.contains(" run"), "{}", manifest);
+}
+
+// ── --verify-meta ─────────────────────────────────────────────────────────
+
+#[test]
+fn verify_meta_reports_each_differing_field() {
+    let tmp = TempDir::new("pcc_verify_meta");
+    let dir = tmp.path();
+    std::fs::write(
+        dir.join("meta_actors.h"),
+        concat!(
+            "#pragma once\n",
+            "#include <pipit.h>\n\n",
+            "ACTOR(vm_gain, IN(float, 1), OUT(float, 1), PARAM(float, gain)) {\n",
+            "    out[0] = in[0] * gain;\n",
+            "    return ACTOR_OK;\n",
+            "}\n};\n\n",
+            "ACTOR(vm_dup, IN(float, 1), OUT(float, 2)) {\n",
+            "    out[0] = in[0];\n",
+            "    out[1] = in[0];\n",
+            "    return ACTOR_OK;\n",
+            "}\n};\n",
+        ),
+    )
+    .expect("write header");
+    let generated = Command::new(pcc_binary())
+        .args(["--emit", "manifest", "-I"])
+        .arg(dir)
+        .output()
+        .expect("failed to run pcc");
+    assert!(generated.status.success(), "pcc failed: {:?}", generated);
+    let mut json: serde_json::Value =
+        serde_json::from_slice(&generated.stdout).expect("manifest is JSON");
+    let fresh = dir.join("fresh.json");
+    std::fs::write(&fresh, &generated.stdout).expect("write manifest");
+    let actors = json["actors"].as_array_mut().expect("actors array");
+    actors.retain(|a| a["name"] != "vm_dup");
+    let gain = actors
+        .iter_mut()
+        .find(|a| a["name"] == "vm_gain")
+        .expect("vm_gain in manifest");
+    gain["in_type"] = serde_json::json!({ "Concrete": "Double" });
+    gain["params"] = serde_json::json!([]);
+    let stale = dir.join("stale.json");
+    std::fs::write(&stale, serde_json::to_string_pretty(&json).unwrap()).expect("write manifest");
+
+    let run = |meta: &Path| {
+        Command::new(pcc_binary())
+            .arg("--verify-meta")
+            .arg("--actor-meta")
+            .arg(meta)
+            .arg("-I")
+            .arg(dir)
+            .output()
+            .expect("failed to run pcc")
+    };
+    let mismatched = run(&stale);
+    let matching = run(&fresh);
+
+    assert_eq!(mismatched.status.code(), Some(1), "{:?}", mismatched);
+    let stderr = String::from_utf8_lossy(&mismatched.stderr);
+    for expected in [
+        "error: actor 'vm_dup' is in the headers but not in the manifest",
+        "error: actor 'vm_gain' in_type differs: manifest double, headers float",
+        "error: actor 'vm_gain' params differs: manifest (), headers (PARAM(float, gain))",
+    ] {
+        assert!(stderr.contains(expected), "got:\n{}", stderr);
+    }
+
+    assert!(matching.status.success(), "pcc failed: {:?}", matching);
+}
+
+// ── --target-features / --march ───────────────────────────────────────────
+
+#[test]
+fn target_features_add_m_flags_to_compile_command() {
+    let tmp = TempDir::new("pcc_target_feat");
+    let dir = tmp.path();
+    let pdl = dir.join("simd.pdl");
+    std::fs::write(&pdl, "clock 1kHz t {\n    constant(0.0) | stdout()\n}\n").expect("write pdl");
+    let run = |extra: &[&str]| {
+        Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "exe", "--cc", "true", "--verbose", "-o"])
+            .arg(dir.join("simd"))
+            .args(extra)
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    let known = run(&["--target-features", "avx2,fma", "--march", "x86-64-v3"]);
+    let unknown = run(&["--target-features", "avx2,foo"]);
+    let invalid = run(&["--target-features", "avx2,-o/tmp/x"]);
+
+    assert!(known.status.success(), "pcc failed: {:?}", known);
+    let stderr = String::from_utf8_lossy(&known.stderr);
+    assert!(stderr.contains("\"-mavx2\" \"-mfma\""), "got:\n{}", stderr);
+    assert!(stderr.contains("\"-march=x86-64-v3\""), "got:\n{}", stderr);
+    assert!(!stderr.contains("warning:"), "got:\n{}", stderr);
+
+    assert!(unknown.status.success(), "pcc failed: {:?}", unknown);
+    let stderr = String::from_utf8_lossy(&unknown.stderr);
+    assert!(
+        stderr.contains("warning: unknown target feature 'foo', passing -mfoo through"),
+        "got:\n{}",
+        stderr
+    );
+    assert!(stderr.contains("\"-mfoo\""), "got:\n{}", stderr);
+
+    assert_eq!(invalid.status.code(), Some(2), "got: {:?}", invalid);
+    assert!(String::from_utf8_lossy(&invalid.stderr)
+        .contains("error: invalid target feature '-o/tmp/x'"));
+}
+
+// ── include composition ────────────────────────────────────────────────────
+
+#[test]
+fn include_composes_definitions_from_other_files() {
+    let tmp = TempDir::new("pcc_include_ok");
+    let dir = tmp.path();
+    let lib = dir.join("lib");
+    std::fs::create_dir_all(&lib).expect("create temp dir");
+    std::fs::write(
+        lib.join("common.pdl"),
+        "const gain = 2.0\ndefine amp() {\n    mul(gain)\n}\n",
+    )
+    .expect("write common.pdl");
+    let pdl = dir.join("main.pdl");
+    std::fs::write(
+        &pdl,
+        "include \"common.pdl\"\nclock 1kHz t {\n    constant(gain) | amp() | stdout()\n}\n",
+    )
+    .expect("write main.pdl");
+    let output = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("-I")
+        .arg(&lib)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .args(["--emit", "cpp", "-o", "-"])
+        .output()
+        .expect("failed to run pcc");
+    assert!(output.status.success(), "pcc failed: {:?}", output);
+    let cpp = String::from_utf8_lossy(&output.stdout);
+    assert!(cpp.contains("Actor_mul"), "got:\n{}", cpp);
+}
+
+#[test]
+fn include_cycle_is_an_error() {
+    let tmp = TempDir::new("pcc_include_cycle");
+    let dir = tmp.path();
+    std::fs::write(dir.join("a.pdl"), "include \"b.pdl\"\n").expect("write a.pdl");
+    std::fs::write(dir.join("b.pdl"), "include \"a.pdl\"\n").expect("write b.pdl");
+    let pdl = dir.join("main.pdl");
+    std::fs::write(
+        &pdl,
+        "include \"a.pdl\"\nclock 1kHz t {\n    constant(0.0) | stdout()\n}\n",
+    )
+    .expect("write main.pdl");
+    let output = Command::new(pcc_binary())
+        .arg(&pdl)
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .args(["--emit", "cpp", "-o", "-"])
+        .output()
+        .expect("failed to run pcc");
+    assert!(!output.status.success(), "pcc should fail: {:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("error[E0037]: include cycle:"),
+        "got:\n{}",
+        stderr
+    );
+    assert!(stderr.contains("b.pdl:1:9"), "got:\n{}", stderr);
+}
+
+// ── pcc repl ───────────────────────────────────────────────────────────────
+
+#[test]
+fn repl_keeps_declarations_and_summarizes_each_entry() {
+    use std::io::Write as _;
+
+    let mut child = Command::new(pcc_binary())
+        .arg("repl")
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to run pcc repl");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(
+            concat!(
+                "param g = 2.0\n",
+                "constant($g) | fft(256) | c2r() | stdout()\n",
+                "constant(0.0) | nosuch_actor()\n",
+                "clock 1kHz prod {\n",
+                "    constant($g) -> sig\n",
+                "}\n",
+                ":source\n",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+    let output = child.wait_with_output().expect("repl did not exit");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(
+        stdout,
+        concat!(
+            "ok\n",
+            "task 'repl': 4 nodes, 3 edges: constant x1, fft x1, c2r x1, stdout x256\n",
+            "task 'prod': 2 nodes, 1 edges: constant x1, ->sig x1\n",
+            "param g = 2.0\n",
+            "clock 1kHz prod {\n",
+            "    constant($g) -> sig\n",
+            "}\n",
+        ),
+        "stderr:\n{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("unknown actor or define 'nosuch_actor'"),
+        "got:\n{}",
+        stderr
+    );
+}
+
+// ── stdin source (`-`) ──────────────────────────────────────────────────────
+
+fn pcc_from_stdin(source: &str, extra_args: &[&str]) -> std::process::Output {
+    use std::io::Write as _;
+
+    let mut child = Command::new(pcc_binary())
+        .arg("-")
+        .arg("--actor-meta")
+        .arg(shared_manifest())
+        .arg("-I")
+        .arg(runtime_include_dir())
+        .args(extra_args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to run pcc -");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    child.wait_with_output().expect("pcc - did not exit")
+}
+
+#[test]
+fn stdin_source_compiles_to_cpp() {
+    let output = pcc_from_stdin(
+        "clock 1kHz t {\n    constant(1.0) | stdout()\n}\n",
+        &["--emit", "cpp"],
+    );
+    assert!(
+        output.status.success(),
+        "pcc - --emit cpp should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let cpp = String::from_utf8_lossy(&output.stdout);
+    assert!(cpp.contains("Actor_constant"), "got:\n{}", cpp);
+    assert!(cpp.contains("Actor_stdout"), "got:\n{}", cpp);
+}
+
+#[test]
+fn stdin_source_diagnostics_name_stdin() {
+    let output = pcc_from_stdin(
+        "clock 1kHz t {\n    constant(1.0) | nosuch_actor()\n}\n",
+        &["--emit", "cpp"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("  at <stdin>:2:21"), "got:\n{}", stderr);
+}
+
+#[test]
+fn stdin_source_is_omitted_from_depfile() {
+    let tmp = TempDir::new("pcc_stdin_depfile");
+    let dir = tmp.path();
+    let target = dir.join("t.cpp");
+    let depfile = dir.join("t.cpp.d");
+    let output = pcc_from_stdin(
+        "clock 1kHz t {\n    constant(1.0) | stdout()\n}\n",
+        &[
+            "--emit",
+            "cpp",
+            "-o",
+            target.to_str().unwrap(),
+            "--depfile",
+            depfile.to_str().unwrap(),
+        ],
+    );
+    let deps = std::fs::read_to_string(&depfile).unwrap_or_default();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!deps.contains("<stdin>"), "got:\n{}", deps);
+    assert!(
+        deps.contains("actor") || deps.contains(".json"),
+        "got:\n{}",
+        deps
+    );
+}
+
+// ── Multiple source files ───────────────────────────────────────────────────
+
+#[test]
+fn multiple_sources_merge_into_one_program() {
+    let tmp = TempDir::new("pcc_multi_source");
+    let dir = tmp.path();
+    let lib = dir.join("lib.pdl");
+    let main = dir.join("main.pdl");
+    let clash = dir.join("clash.pdl");
+    std::fs::write(
+        &lib,
+        "const g = 2.0\nclock 1kHz a {\n    constant(0.0) | mul(g) -> sig\n}\n",
+    )
+    .expect("write lib.pdl");
+    std::fs::write(&main, "clock 1kHz b {\n    @sig | stdout()\n}\n").expect("write main.pdl");
+    std::fs::write(&clash, "clock 1kHz a {\n    constant(1.0) | stdout()\n}\n")
+        .expect("write clash.pdl");
+    let run = |second: &Path| {
+        Command::new(pcc_binary())
+            .arg(&lib)
+            .arg(second)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .arg("--emit")
+            .arg("cpp")
+            .output()
+            .expect("failed to run pcc")
+    };
+
+    // `main.pdl` reads a buffer written by a task in `lib.pdl`.
+    let merged = run(&main);
+    // Both roots define task `a`; the error points at each file.
+    let clashed = run(&clash);
+
+    assert!(
+        merged.status.success(),
+        "merged sources should compile.\nstderr: {}",
+        String::from_utf8_lossy(&merged.stderr)
+    );
+    let cpp = String::from_utf8_lossy(&merged.stdout);
+    assert!(cpp.contains("task_a"), "missing task a:\n{}", cpp);
+    assert!(cpp.contains("task_b"), "missing task b:\n{}", cpp);
+
+    assert_eq!(clashed.status.code(), Some(1), "{:?}", clashed);
+    let stderr = String::from_utf8_lossy(&clashed.stderr);
+    assert!(stderr.contains("duplicate task 'a'"), "{}", stderr);
+    assert!(
+        stderr.contains(&format!("at {}:1:12", clash.display())),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains(&format!("at {}:2:12", lib.display())),
+        "{}",
+        stderr
+    );
+}
+
+// ── --explain ───────────────────────────────────────────────────────────────
+
+#[test]
+fn explain_prints_code_description_and_lists_unknown() {
+    let output = Command::new(pcc_binary())
+        .args(["--explain", "E0304"])
+        .output()
+        .expect("failed to run pcc");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("E0304: SDF balance equation unsolvable\n\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("\nExample:\n\n    clock 1kHz t {\n"),
+        "{}",
+        stdout
+    );
+
+    let output = Command::new(pcc_binary())
+        .args(["--explain", "E9999"])
+        .output()
+        .expect("failed to run pcc");
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no explanation for 'E9999'"), "{}", stderr);
+    assert!(stderr.contains("available: E0300, E0301,"), "{}", stderr);
+}
+
+#[test]
+fn explain_examples_trigger_their_code() {
+    let tmp = TempDir::new("pcc_explain");
+    let dir = tmp.path();
+    let mut failures = Vec::new();
+    for explanation in pcc::diag::EXPLANATIONS {
+        // Examples needing actors outside the standard library say so.
+        let Some(example) = explanation.example.filter(|e| !e.starts_with('#')) else {
+            continue;
+        };
+        let pdl = dir.join(format!("{}.pdl", explanation.code));
+        std::fs::write(&pdl, example).expect("write example");
+        let output = Command::new(pcc_binary())
+            .arg(&pdl)
+            .arg("--actor-meta")
+            .arg(shared_manifest())
+            .args(["--emit", "schedule"])
+            .output()
+            .expect("failed to run pcc");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.contains(&format!("[{}]", explanation.code)) {
+            failures.push(format!("{}:\n{}", explanation.code, stderr));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn emit_callgraph_links_tasks_through_buffers_and_binds() {
+    let output = pcc_from_stdin(
+        concat!(
+            "bind rf = udp(\"127.0.0.1:9200\", chan=20)\n",
+            "clock 1kHz capture {\n    constant(0.0) -> sig\n}\n",
+            "clock 1kHz drain {\n    @sig | stdout()\n}\n",
+            "clock 1kHz rx {\n    @rf | stdout<float>()\n}\n",
+        ),
+        &["--emit", "callgraph"],
+    );
+    assert!(
+        output.status.success(),
+        "pcc --emit callgraph should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let dot = String::from_utf8_lossy(&output.stdout);
+    // Buffer bytes come from static analysis (double buffering of one float).
+    assert!(
+        dot.contains("task_capture -> task_drain [label=\"sig\\n8 B\"];"),
+        "got:\n{}",
+        dot
+    );
+    assert!(
+        dot.contains("bind_rf [label=\"rf\\nudp\", shape=cds, style=dashed];"),
+        "got:\n{}",
+        dot
+    );
+    assert!(
+        dot.contains("bind_rf -> task_rx [label=\"rf\"];"),
+        "got:\n{}",
+        dot
+    );
+}
+
+#[test]
+fn emit_schedule_per_core_groups_tasks_by_affinity() {
+    let source = concat!(
+        "set affinity = { rx: 2, audio: 0 }\n",
+        "clock 48kHz audio {\n    constant(0.0) | stdout()\n}\n",
+        "clock 1kHz rx {\n    constant(0.0) | stdout()\n}\n",
+        "clock 10Hz ui {\n    constant(0.0) | stdout()\n}\n",
+    );
+    let output = pcc_from_stdin(source, &["--emit", "schedule", "--per-core"]);
+    assert!(
+        output.status.success(),
+        "pcc --emit schedule --per-core should succeed.\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "core 0: audio(?) = ?\ncore 2: rx(?) = ?\nfloating: ui(?) = ?\n"
+    );
+
+    let misuse = pcc_from_stdin(source, &["--emit", "cpp", "--per-core"]);
+    assert_eq!(misuse.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&misuse.stderr).contains("--per-core requires --emit schedule"));
+}
@@ -50,11 +50,9 @@ fn build_codegen_options(
     let root = project_root();
     let provenance = pcc::pipeline::compute_provenance(source, registry);
     pcc::codegen::CodegenOptions {
-        release: false,
         include_paths: vec![root.join("runtime/libpipit/include"), root.join("examples")],
         provenance: Some(provenance),
-        experimental: false,
-        bind_overrides: std::collections::HashMap::new(),
+        ..Default::default()
     }
 }
 
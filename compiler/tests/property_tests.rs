@@ -46,7 +46,8 @@ fn load_full_registry() -> pcc::registry::Registry {
 // ── PDL generator ───────────────────────────────────────────────────────────
 
 /// Generate a small valid PDL program using only actors known to resolve.
-/// Grammar: constant(<f64>) (| (mul|add)(<f64>))* (| stdout())?
+/// Grammar: constant(<f64>) (| (mul(<f64>)|add()))* (| stdout())?
+/// (`add` takes no parameters; the generated value is unused for it).
 /// stdout() is terminal-only (void output cannot feed downstream actors).
 fn arb_pdl_program() -> impl Strategy<Value = String> {
     // PDL syntax: `clock <freq_with_unit> <name> { ... }`
@@ -68,7 +69,12 @@ fn arb_pdl_program() -> impl Strategy<Value = String> {
         .prop_map(|(init_val, mid_actors, has_stdout)| {
             let mut chain = format!("constant({})", format_f64(init_val));
             for (actor, val) in &mid_actors {
-                chain.push_str(&format!(" | {}({})", actor, format_f64(*val)));
+                let arg = if *actor == "mul" {
+                    format_f64(*val)
+                } else {
+                    String::new()
+                };
+                chain.push_str(&format!(" | {}({})", actor, arg));
             }
             if has_stdout {
                 chain.push_str(" | stdout()");
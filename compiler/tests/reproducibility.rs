@@ -107,6 +107,54 @@ fn same_source_same_manifest_identical_cpp() {
     );
 }
 
+/// `--reproducible` C++ is byte-identical across runs, carries a
+/// `SOURCE_DATE_EPOCH` build stamp, and embeds no absolute paths.
+#[test]
+fn reproducible_cpp_identical_with_normalized_stamp() {
+    let root = project_root();
+    let meta = shared_manifest().to_str().unwrap();
+    let run = |epoch: Option<&str>| {
+        let mut cmd = Command::new(pcc_binary());
+        cmd.current_dir(&root)
+            .args(["--emit", "cpp", "examples/gain.pdl", "--actor-meta", meta])
+            .args(["-I", "runtime/libpipit/include", "-I", "examples"])
+            .arg("--reproducible");
+        match epoch {
+            Some(epoch) => cmd.env("SOURCE_DATE_EPOCH", epoch),
+            None => cmd.env_remove("SOURCE_DATE_EPOCH"),
+        };
+        let output = cmd.output().expect("failed to run pcc");
+        assert!(
+            output.status.success(),
+            "pcc --reproducible failed\nstderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).expect("non-UTF8 output")
+    };
+
+    let first = run(Some("1700000000"));
+    let second = run(Some("1700000000"));
+    assert_eq!(
+        first, second,
+        "--reproducible C++ should be byte-identical across runs"
+    );
+    let stamp = first.lines().next().unwrap();
+    assert!(
+        stamp.starts_with("// pcc provenance: source_hash=")
+            && stamp.ends_with(" build_epoch=1700000000"),
+        "got: {}",
+        stamp
+    );
+    assert!(
+        !first.contains(root.to_str().unwrap()),
+        "--reproducible C++ should not embed absolute project paths"
+    );
+    assert!(first.contains("#include \"runtime/libpipit/include/std_actors.h\""));
+
+    let unset = run(None);
+    assert!(unset.lines().next().unwrap().ends_with(" build_epoch=0"));
+}
+
 /// Two independently-generated manifests from the same headers produce
 /// identical registry fingerprints in build-info.
 #[test]
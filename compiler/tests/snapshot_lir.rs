@@ -206,7 +206,7 @@ fn snapshot_lir_bind_in() {
     let (registry, _) = load_full_registry();
     let source = r#"bind iq = udp("127.0.0.1:9100", chan=10)
 clock 48kHz audio {
-    @iq | binwrite("/dev/null")
+    @iq | binwrite("/dev/null", "float")
 }
 "#;
     let output = lir_snapshot(source, &registry);
@@ -215,6 +215,14 @@ clock 48kHz audio {
 
 /// Build an LirProgram from source (same pipeline as lir_snapshot but returns the struct).
 fn build_lir(source: &str, registry: &pcc::registry::Registry) -> pcc::lir::LirProgram {
+    build_lir_with_analysis(source, registry).0
+}
+
+/// Like `build_lir`, also returning the analysis the LIR was built from.
+fn build_lir_with_analysis(
+    source: &str,
+    registry: &pcc::registry::Registry,
+) -> (pcc::lir::LirProgram, pcc::analyze::AnalyzedProgram) {
     let parse_result = pcc::parser::parse(source);
     assert!(
         parse_result.errors.is_empty(),
@@ -288,12 +296,13 @@ fn build_lir(source: &str, registry: &pcc::registry::Registry) -> pcc::lir::LirP
         "schedule errors: {:?}",
         schedule_result.diagnostics
     );
-    pcc::lir::build_lir(
+    let lir = pcc::lir::build_lir(
         &thir,
         &graph_result.graph,
         &analysis_result.analysis,
         &schedule_result.schedule,
-    )
+    );
+    (lir, analysis_result.analysis)
 }
 
 #[test]
@@ -304,11 +313,46 @@ clock 48kHz audio {
     constant(0) -> iq
 }
 "#;
-    let lir = build_lir(source, &registry);
-    let manifest = lir.generate_interface_manifest(&std::collections::HashMap::new());
+    let (lir, analysis) = build_lir_with_analysis(source, &registry);
+    let manifest = lir.generate_interface_manifest(
+        &std::collections::HashMap::new(),
+        &analysis,
+        pcc::lir::ManifestFormat::Json,
+    );
     insta::assert_snapshot!("lir_bind_interface_manifest", manifest);
 }
 
+#[test]
+fn snapshot_lir_bind_interface_manifest_yaml() {
+    let (registry, _) = load_full_registry();
+    let source = r#"bind iq = udp("127.0.0.1:9100", chan=10)
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+    let (lir, analysis) = build_lir_with_analysis(source, &registry);
+    let overrides = std::collections::HashMap::new();
+    let yaml =
+        lir.generate_interface_manifest(&overrides, &analysis, pcc::lir::ManifestFormat::Yaml);
+    insta::assert_snapshot!("lir_bind_interface_manifest_yaml", yaml);
+
+    // Same keys in the same order as the JSON form.
+    let json =
+        lir.generate_interface_manifest(&overrides, &analysis, pcc::lir::ManifestFormat::Json);
+    // A YAML key with a block value ends its line (`binds:`).
+    let keys = |text: &str, sep: &str| -> Vec<String> {
+        text.lines()
+            .map(|l| l.trim_start_matches([' ', '-']))
+            .filter_map(|l| {
+                l.split_once(sep)
+                    .or_else(|| Some((l.strip_suffix(':')?, "")))
+            })
+            .map(|(k, _)| k.trim_matches('"').to_string())
+            .collect()
+    };
+    assert_eq!(keys(&yaml, ": "), keys(&json, "\": "));
+}
+
 #[test]
 fn lir_bind_format_endpoint_spec() {
     let (registry, _) = load_full_registry();
@@ -323,6 +367,92 @@ clock 48kHz audio {
     assert_eq!(spec, r#"udp("127.0.0.1:9100", chan=10)"#);
 }
 
+#[test]
+fn lir_bind_manifest_with_version() {
+    let (registry, _) = load_full_registry();
+    let source = r#"bind iq = udp("127.0.0.1:9100", chan=10, version=2)
+clock 48kHz audio {
+    constant(0) -> iq
+}
+"#;
+    let (lir, analysis) = build_lir_with_analysis(source, &registry);
+    let manifest = lir.generate_interface_manifest(
+        &std::collections::HashMap::new(),
+        &analysis,
+        pcc::lir::ManifestFormat::Json,
+    );
+    assert!(manifest.contains("\"version\": 2"), "got:\n{}", manifest);
+}
+
+#[test]
+fn lir_bind_manifest_cross_clock_latency() {
+    let (registry, _) = load_full_registry();
+    // raw: 1 token/tick at 10kHz, 2-token ring → 2 / 10000 Hz = 0.2 ms.
+    let source = r#"bind out = udp("127.0.0.1:9100")
+clock 10kHz fast {
+    constant(0.0) -> raw
+}
+clock 1kHz slow {
+    @raw | mean(10) -> out
+}
+"#;
+    let (lir, analysis) = build_lir_with_analysis(source, &registry);
+    let manifest = lir.interface_manifest(&std::collections::HashMap::new(), &analysis);
+    let latency = manifest.binds[0].latency_ms.expect("cross-clock latency");
+    assert!((latency - 0.2).abs() < 1e-9, "got {}", latency);
+    let json = lir.generate_interface_manifest(
+        &std::collections::HashMap::new(),
+        &analysis,
+        pcc::lir::ManifestFormat::Json,
+    );
+    assert!(json.contains("\"latency_ms\": 0.2"), "got:\n{}", json);
+
+    // Same-clock writer: no buffer-depth delay is reported.
+    let (direct, analysis) = build_lir_with_analysis(
+        r#"bind out = udp("127.0.0.1:9100")
+clock 1kHz t {
+    constant(0.0) -> out
+}
+"#,
+        &registry,
+    );
+    let manifest = direct.interface_manifest(&std::collections::HashMap::new(), &analysis);
+    assert_eq!(manifest.binds[0].latency_ms, None);
+}
+
+#[test]
+fn lir_bind_manifest_sizing() {
+    let (registry, _) = load_full_registry();
+    let source = r#"bind out = udp("127.0.0.1:9100")
+clock 10kHz fast {
+    constant(0.0) -> raw
+}
+clock 1kHz slow {
+    @raw | mean(10) -> out
+}
+"#;
+    let (lir, analysis) = build_lir_with_analysis(source, &registry);
+    let manifest = lir.interface_manifest(&std::collections::HashMap::new(), &analysis);
+    let sizing = manifest.sizing.expect("sizing");
+    assert_eq!(
+        sizing.tasks.keys().collect::<Vec<_>>(),
+        ["fast", "slow"],
+        "tasks are keyed by name"
+    );
+    let slow = &sizing.tasks["slow"].repetition_vectors["pipeline"];
+    assert!(slow.windows(2).all(|w| w[0].node_id < w[1].node_id));
+    assert!(slow.iter().any(|e| e.count == 10), "got {:?}", slow);
+    assert_eq!(
+        sizing.inter_task_buffers.get("raw").copied(),
+        analysis.inter_task_buffers.get("raw").copied()
+    );
+
+    // Baselines written before `sizing` existed still load.
+    let old: pcc::lir::InterfaceManifest =
+        serde_json::from_str(r#"{"schema": 1, "binds": []}"#).expect("old manifest");
+    assert!(old.sizing.is_none());
+}
+
 #[test]
 fn lir_bind_manifest_with_override() {
     let (registry, _) = load_full_registry();
@@ -331,10 +461,11 @@ clock 48kHz audio {
     constant(0) -> iq
 }
 "#;
-    let lir = build_lir(source, &registry);
+    let (lir, analysis) = build_lir_with_analysis(source, &registry);
     let mut overrides = std::collections::HashMap::new();
     overrides.insert("iq".to_string(), "shm(\"/dev/shm/iq\")".to_string());
-    let manifest = lir.generate_interface_manifest(&overrides);
+    let manifest =
+        lir.generate_interface_manifest(&overrides, &analysis, pcc::lir::ManifestFormat::Json);
     assert!(manifest.contains("endpoint_override"));
     assert!(manifest.contains("shm(\\\"/dev/shm/iq\\\")"));
 }
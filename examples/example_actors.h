@@ -42,6 +42,23 @@ ACTOR(detect, IN(float, 1), OUT(int32, 1)) {
 }
 ;
 
+// ── sample_delay: N-firing sample delay (example) ──
+//
+// Outputs the input from N firings earlier (zeros until the line fills).
+// The delay line lives in per-instance SCRATCH memory.
+//
+// Example: sample_delay(4)
+//
+ACTOR(sample_delay, IN(float, 1), OUT(float, 1), PARAM(int, N) SCRATCH(float, N)) {
+    out[0] = scratch[N - 1];
+    for (int i = N - 1; i > 0; --i)
+        scratch[i] = scratch[i - 1];
+    scratch[0] = in[0];
+    return ACTOR_OK;
+}
+}
+;
+
 // ── sync_process: Signal processing example ──
 //
 // Sums 256 input samples (example processing).
@@ -58,6 +75,24 @@ ACTOR(sync_process, IN(float, 256), OUT(float, 1)) {
 }
 ;
 
+// ── frame_reverse: Reverse each N-sample frame (example) ──
+//
+// Copies the frame into a stack array sized by the compile-time IN_COUNT
+// that generated code passes to every firing, then writes it out reversed.
+//
+// Example: frame_reverse(8)
+//
+ACTOR(frame_reverse, IN(float, N), OUT(float, N), PARAM(int, N)) {
+    float tmp[IN_COUNT > 0 ? IN_COUNT : 1];
+    for (int i = 0; i < IN_COUNT; ++i)
+        tmp[i] = in[i];
+    for (int i = 0; i < OUT_COUNT; ++i)
+        out[i] = tmp[IN_COUNT - 1 - i];
+    return ACTOR_OK;
+}
+}
+;
+
 // ── Sink actors (examples) ──
 
 // ── csvwrite: CSV file writer (placeholder) ──
@@ -6,6 +6,7 @@
 // pipelines. Used by both hand-written actor headers and generated code.
 
 #include <atomic>
+#include <cassert>
 #include <chrono>
 #include <complex>
 #include <cstdint>
@@ -13,6 +14,10 @@
 #include <span>
 #include <thread>
 
+// Runtime ABI version. Must match pcc's RUNTIME_ABI_VERSION (pipeline.rs);
+// reported by `pcc --version --verbose`.
+#define PIPIT_RUNTIME_ABI_VERSION 3
+
 // Actor return codes
 constexpr int ACTOR_OK = 0;
 constexpr int ACTOR_ERROR = 1;
@@ -24,11 +29,35 @@ using int32 = std::int32_t;
 
 // ── Actor registration macros ───────────────────────────────────────────────
 //
-// ACTOR(name, IN(type, count), OUT(type, count), [PARAM|RUNTIME_PARAM]...)
+// ACTOR(name, IN(type, count), OUT(type, count),
+//       [PARAM|RUNTIME_PARAM|TERMINATES|PURE|INLINE|ALIAS|RATE_CHANGE]...
+//       [SCRATCH(type, count)])
 //
 // IN/OUT expand to (type, count) pairs — consumed by the compiler scanner.
 // PARAM/RUNTIME_PARAM declare member variables in the actor struct.
+// TERMINATES marks a source that ends the pipeline when its input runs out
+// (returns ACTOR_ERROR at EOF); it expands to nothing.
+// PURE marks an actor whose output depends only on its input and arguments
+// (no state, I/O, or clock reads), letting the compiler evaluate a constant
+// chain once before the tick loop and fire identical calls on the same input
+// only once; it expands to nothing.
+// INLINE marks a tiny actor whose call overhead dominates its work: generated
+// code fires it through pipit::fire_inline, which forces the body into the
+// task loop; it expands to nothing.
+// ALIAS(old_name) registers an extra lookup name for the actor (e.g. the
+// name before a rename); it expands to nothing and may appear anywhere.
+// RATE_CHANGE(up, down) declares a sample-rate changer that consumes `down`
+// tokens and produces `up` per firing. Each factor must restate the IN/OUT
+// token count (the same literal or PARAM name). It expands to nothing and may
+// appear anywhere.
+// SCRATCH declares per-instance work memory that persists across firings:
+// the generated code allocates `count` elements per call site and passes
+// them as the `scratch` member, so it must come after every PARAM.
 // The actor body becomes the operator() of the generated struct.
+// operator() is a template over IN_COUNT / OUT_COUNT, the resolved per-firing
+// input/output token counts: generated code passes them at every call site,
+// so a body may use them as compile-time loop bounds or stack array sizes.
+// Direct calls without template arguments see 0 for both.
 //
 // The _PIPIT_FIRST helper extracts the type from the expanded IN/OUT pair
 // to produce typed `in`/`out` pointers in operator().
@@ -37,6 +66,12 @@ using int32 = std::int32_t;
 #define OUT(type, count) type, count
 #define PARAM(type, name) type name;
 #define RUNTIME_PARAM(type, name) type name;
+#define TERMINATES
+#define PURE
+#define INLINE
+#define ALIAS(name)
+#define RATE_CHANGE(up, down)
+#define SCRATCH(type, count) type *scratch;
 
 // Helper: extract the first element from a comma-separated pair
 #define _PIPIT_FIRST(a, ...) a
@@ -47,6 +82,7 @@ using int32 = std::int32_t;
 #define ACTOR(name, in_spec, out_spec, ...)                                                        \
     struct Actor_##name {                                                                          \
         __VA_ARGS__                                                                                \
+        template <int IN_COUNT = 0, int OUT_COUNT = 0>                                             \
         int operator()(const _PIPIT_FIRST(in_spec) * in, _PIPIT_FIRST(out_spec) * out) noexcept
 
 // ── Ring buffer (lock-free single-writer, multi-reader) ─────────────────────
@@ -75,6 +111,17 @@ inline void set_actor_task_rate_hz(double task_rate_hz) {
 
 } // namespace detail
 
+// ── Forced-inline firing (INLINE actors) ────────────────────────────────────
+
+// Fire `actor` with the given per-firing token counts. `flatten` inlines the
+// actor's operator() into this wrapper and `always_inline` splices the wrapper
+// into the caller, so the firing compiles to the body itself.
+template <int IN_COUNT, int OUT_COUNT, typename Actor, typename In, typename Out>
+[[gnu::always_inline, gnu::flatten]] inline int fire_inline(Actor &&actor, In in,
+                                                             Out out) noexcept {
+    return actor.template operator()<IN_COUNT, OUT_COUNT>(in, out);
+}
+
 // ── Wait result for blocking ring buffer operations ─────────────────────────
 
 enum class WaitResult { ready, timeout, stopped };
@@ -155,6 +202,24 @@ template <typename T, std::size_t Capacity, std::size_t Readers = 1> class RingB
         return h - t;
     }
 
+    /// Tokens held for the slowest reader (the writer-side occupancy).
+    std::size_t fill() const {
+        std::size_t h = head_.load(std::memory_order_acquire);
+        std::size_t mt = tails_[0].value.load(std::memory_order_acquire);
+        for (std::size_t i = 1; i < Readers; ++i) {
+            std::size_t t = tails_[i].value.load(std::memory_order_acquire);
+            if (t < mt)
+                mt = t;
+        }
+        return h - mt;
+    }
+
+    /// Tokens of write space left before the slowest reader would be overrun.
+    std::size_t writable() const {
+        std::size_t used = fill();
+        return used <= Capacity ? Capacity - used : 0;
+    }
+
     /// Block until `count` tokens are readable, or until stopped/timed out.
     /// Hybrid polling: spin ~100 → yield ~100 → sleep 1ms until deadline.
     ///
@@ -317,6 +382,15 @@ template <typename T, std::size_t Capacity> class RingBuffer<T, Capacity, 1> {
         return h - t;
     }
 
+    /// Tokens held for the reader (the writer-side occupancy).
+    std::size_t fill() const { return available(); }
+
+    /// Tokens of write space left before the reader would be overrun.
+    std::size_t writable() const {
+        std::size_t used = fill();
+        return used <= Capacity ? Capacity - used : 0;
+    }
+
     /// Hybrid polling: spin ~100 → yield ~100 → sleep 1ms until deadline.
     WaitResult wait_readable(std::size_t reader_idx, std::size_t count,
                              const std::atomic<bool> &stop, std::chrono::milliseconds timeout) {
@@ -413,10 +487,17 @@ class Timer {
     static constexpr int64_t kMaxSpinNs = 100'000; // ceiling: 100us
     static constexpr int64_t kInitSpinNs = 10'000; // bootstrap: 10us
 
+    // Round (not truncate) so sub-Hz and fractional rates keep their exact
+    // period; clamp to 1ns so absurd rates never yield a zero period.
+    static Nanos period_for(double freq_hz) {
+        auto p = std::chrono::round<Nanos>(std::chrono::duration<double>(1.0 / freq_hz));
+        return p.count() > 0 ? p : Nanos{1};
+    }
+
   public:
     explicit Timer(double freq_hz, bool measure_latency = true, int64_t spin_ns = 0)
-        : period_(std::chrono::duration_cast<Nanos>(std::chrono::duration<double>(1.0 / freq_hz))),
-          next_(Clock::now() + period_), measure_latency_(measure_latency) {
+        : period_(period_for(freq_hz)), next_(Clock::now() + period_),
+          measure_latency_(measure_latency) {
         if (spin_ns < 0) {
             // Adaptive mode: EWMA calibration (sentinel -1)
             adaptive_ = true;
@@ -426,13 +507,22 @@ class Timer {
         }
     }
 
+    /// Start ticking one period after `epoch` instead of now, so timers
+    /// constructed from the same epoch share a phase reference.
+    Timer(double freq_hz, bool measure_latency, int64_t spin_ns, Clock::time_point epoch)
+        : Timer(freq_hz, measure_latency, spin_ns) {
+        next_ = epoch + period_;
+    }
+
     void wait() {
         auto now = Clock::now();
         if (now < next_) {
             if (spin_threshold_.count() > 0) {
                 // Hybrid: sleep for bulk of the period, spin for the final portion
                 auto sleep_target = next_ - spin_threshold_;
-                if (now < sleep_target) {
+                // Re-sleep on early wake-ups so a long (low-rate) period is
+                // never finished by busy-spinning; only the threshold spins.
+                while (Clock::now() < sleep_target) {
                     std::this_thread::sleep_until(sleep_target);
                 }
                 // Record wake point for adaptive calibration
@@ -459,7 +549,9 @@ class Timer {
                     spin_threshold_ = Nanos{new_spin};
                 }
             } else {
-                std::this_thread::sleep_until(next_);
+                while (Clock::now() < next_) {
+                    std::this_thread::sleep_until(next_);
+                }
             }
             overrun_ = false;
             if (measure_latency_) {
@@ -476,7 +568,13 @@ class Timer {
 
     bool overrun() const { return overrun_; }
 
+    // Next deadline as steady_clock nanoseconds (single-thread runtime ordering)
+    int64_t deadline_ns() const {
+        return std::chrono::duration_cast<Nanos>(next_.time_since_epoch()).count();
+    }
+
     Nanos last_latency() const { return last_latency_; }
+    Nanos period() const { return period_; }
 
     // Adaptive spin observability
     bool is_adaptive() const { return adaptive_; }
@@ -497,6 +595,31 @@ class Timer {
     }
 };
 
+// ── Edge buffer guard (debug codegen) ───────────────────────────────────────
+//
+// Non-release codegen declares each static edge buffer with EDGE_GUARD_TOKENS
+// extra tokens, fills them with EDGE_GUARD_BYTE at task start, and asserts
+// they are intact after every firing that writes the buffer.  An actor that
+// writes past its declared output count clobbers the guard.
+
+inline constexpr std::size_t EDGE_GUARD_TOKENS = 4;
+inline constexpr unsigned char EDGE_GUARD_BYTE = 0xA5;
+
+template <typename T, std::size_t N> inline void edge_guard_fill(T (&buf)[N]) {
+    static_assert(N > EDGE_GUARD_TOKENS, "edge buffer declared without guard tokens");
+    std::memset(&buf[N - EDGE_GUARD_TOKENS], EDGE_GUARD_BYTE, EDGE_GUARD_TOKENS * sizeof(T));
+}
+
+template <typename T, std::size_t N> inline bool edge_guard_intact(const T (&buf)[N]) {
+    static_assert(N > EDGE_GUARD_TOKENS, "edge buffer declared without guard tokens");
+    const auto *guard = reinterpret_cast<const unsigned char *>(&buf[N - EDGE_GUARD_TOKENS]);
+    for (std::size_t i = 0; i < EDGE_GUARD_TOKENS * sizeof(T); ++i) {
+        if (guard[i] != EDGE_GUARD_BYTE)
+            return false;
+    }
+    return true;
+}
+
 // ── Statistics collection ────────────────────────────────────────────────────
 
 struct TaskStats {
@@ -520,6 +643,29 @@ struct TaskStats {
     }
 };
 
+/// Failed reads (underruns) and writes (overruns) on one shared ring buffer.
+/// Readers in several tasks may fail on the same buffer, so the counters are
+/// atomic; read at shutdown after tasks join.
+struct BufferStats {
+    std::atomic<uint64_t> read_fails{0};
+    std::atomic<uint64_t> write_fails{0};
+
+    void record_read_fail() { read_fails.fetch_add(1, std::memory_order_relaxed); }
+    void record_write_fail() { write_fails.fetch_add(1, std::memory_order_relaxed); }
+};
+
+/// Accumulated wall time of one actor node (`pcc --profile-actors`).
+/// Written only by the owning task; read at shutdown after tasks join.
+struct ActorProfile {
+    uint64_t firings = 0;
+    uint64_t total_ns = 0;
+
+    void record(uint64_t ns, uint64_t n = 1) {
+        firings += n;
+        total_ns += ns;
+    }
+};
+
 } // namespace pipit
 
 // ── Actor runtime context API ────────────────────────────────────────────────
@@ -63,10 +63,12 @@ class BindIoAdapter {
 
   public:
     BindIoAdapter(const char *name, bool is_out, pipit::net::DType dtype, uint16_t chan_id,
-                  double rate_hz, const char *transport, BindState *state)
+                  double rate_hz, const char *transport, BindState *state,
+                  uint16_t contract_version = 0)
         : name_(name), dtype_(dtype), chan_id_(chan_id), rate_hz_(rate_hz), is_out_(is_out),
           transport_(transport), state_(state) {
         hdr_ = pipit::net::ppkt_make_header(dtype, chan_id);
+        hdr_.contract_version = contract_version;
         hdr_.flags = pipit::net::FLAG_FIRST_FRAME;
         std::memset(recv_buf_, 0, sizeof(recv_buf_));
     }
@@ -91,8 +93,8 @@ class BindIoAdapter {
     }
 
     /// Receive data from the bind endpoint via PPKT.
-    /// Zero-fills output if no valid data is available.
-    void recv(void *out, uint32_t n_tokens) {
+    /// Zero-fills output and returns false if no valid data is available.
+    bool recv(void *out, uint32_t n_tokens) {
         std::lock_guard<std::mutex> lk(io_mtx_);
         size_t fill_bytes = n_tokens * pipit::net::dtype_size(dtype_);
         std::memset(out, 0, fill_bytes);
@@ -100,7 +102,7 @@ class BindIoAdapter {
         if (!initialized_)
             lazy_init();
         if (!receiver_ || !receiver_->is_valid())
-            return;
+            return false;
 
         // Drain all available packets, keep latest valid one
         ssize_t latest_len = 0;
@@ -112,19 +114,20 @@ class BindIoAdapter {
         }
 
         if (latest_len < static_cast<ssize_t>(sizeof(pipit::net::PpktHeader)))
-            return;
+            return false;
 
         const auto *pkt_hdr = reinterpret_cast<const pipit::net::PpktHeader *>(recv_buf_);
         if (!pipit::net::ppkt_validate(*pkt_hdr))
-            return;
+            return false;
         if (pkt_hdr->dtype != static_cast<uint8_t>(dtype_))
-            return;
+            return false;
 
         size_t header_size = sizeof(pipit::net::PpktHeader);
         size_t available_bytes = std::min(static_cast<size_t>(pkt_hdr->payload_bytes),
                                           static_cast<size_t>(latest_len) - header_size);
         size_t copy_bytes = std::min(available_bytes, fill_bytes);
         std::memcpy(out, recv_buf_ + header_size, copy_bytes);
+        return true;
     }
 
     /// Reconnect to a new endpoint. Called after rebind.
@@ -0,0 +1,113 @@
+#pragma once
+/// @file pipit_library.h
+/// @brief Embedding support for pipelines built with `pcc --library`
+///
+/// Provides:
+///   - LibraryBindPort: in-process bind adapter; the host application is the
+///     transport, exchanging frames through the generated `pipit_read_<bind>`
+///     / `pipit_write_<bind>` accessors
+///   - LibraryRuntime: starts and stops the task threads on behalf of the
+///     generated `pipit_start()` / `pipit_stop()` entry points
+///
+/// Library builds have no `main()`: CLI parsing, the SIGINT handler and the
+/// statistics report of shell_main() are left to the host.
+///
+/// See doc/spec/pcc-spec-v0.4.0.md §6.13 for the generated C interface.
+
+#include <pipit.h>
+#include <pipit_shell.h>
+
+#include <atomic>
+#include <cstring>
+#include <mutex>
+#include <string>
+#include <thread>
+#include <vector>
+
+namespace pipit {
+
+// ── Bind port ───────────────────────────────────────────────────────────────
+
+/// Single-producer/single-consumer queue between a task and the host.
+///
+/// Exposes the same `send` / `recv` / `reconnect` surface as BindIoAdapter so
+/// generated task code is identical in both modes.  Task-side I/O never
+/// blocks: `send` drops the firing when the host has not drained the port,
+/// and `recv` zero-fills when the host has not supplied enough tokens.
+/// Each accessor must be called from one host thread at a time.
+template <typename T, std::size_t Capacity> class LibraryBindPort {
+    RingBuffer<T, Capacity, 1> ring_;
+
+  public:
+    /// Task side (OUT bind): queue one firing for the host.
+    void send(const void *data, uint32_t n_tokens) {
+        (void)ring_.write(static_cast<const T *>(data), n_tokens);
+    }
+
+    /// Task side (IN bind): take one firing from the host, or zeros.
+    /// Returns false when the host supplied nothing.
+    bool recv(void *out, uint32_t n_tokens) {
+        if (ring_.read(static_cast<T *>(out), n_tokens))
+            return true;
+        std::memset(out, 0, n_tokens * sizeof(T));
+        return false;
+    }
+
+    /// Rebinding has no effect: the host is the only endpoint.
+    void reconnect(const std::string &) {}
+
+    /// Host side (IN bind): false when the port is full.
+    bool push(const T *src, std::size_t n_tokens) { return ring_.write(src, n_tokens); }
+
+    /// Host side (OUT bind): false when fewer than `n_tokens` are queued.
+    bool pop(T *dst, std::size_t n_tokens) { return ring_.read(dst, n_tokens); }
+};
+
+// ── Runtime control ─────────────────────────────────────────────────────────
+
+/// Thread lifecycle for a library build: the part of shell_main() that
+/// launches and joins the task threads, split into start and stop calls.
+class LibraryRuntime {
+    std::mutex mtx_;
+    std::vector<std::thread> threads_;
+    bool running_ = false;
+
+  public:
+    /// Launch one thread per task and release the start barrier.
+    /// Returns false if the pipeline is already running.
+    bool start(const ProgramDesc &desc) {
+        std::lock_guard<std::mutex> lk(mtx_);
+        if (running_)
+            return false;
+        desc.state.stop->store(false, std::memory_order_release);
+        desc.state.start->store(false, std::memory_order_release);
+        desc.state.exit_code->store(0, std::memory_order_release);
+        threads_.reserve(desc.tasks.size());
+        for (const auto &t : desc.tasks) {
+            threads_.emplace_back([&t] {
+                set_task_thread_name(t.name);
+                set_task_thread_affinity(t.name, t.cpu);
+                t.entry();
+            });
+        }
+        desc.state.start->store(true, std::memory_order_release);
+        running_ = true;
+        return true;
+    }
+
+    /// Signal the tasks to stop, join them, and return the exit code.
+    /// Returns -1 if the pipeline is not running.
+    int stop(const ProgramDesc &desc) {
+        std::lock_guard<std::mutex> lk(mtx_);
+        if (!running_)
+            return -1;
+        desc.state.stop->store(true, std::memory_order_release);
+        for (auto &t : threads_)
+            t.join();
+        threads_.clear();
+        running_ = false;
+        return desc.state.exit_code->load(std::memory_order_acquire);
+    }
+};
+
+} // namespace pipit
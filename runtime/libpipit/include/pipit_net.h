@@ -63,19 +63,19 @@ enum Flags : uint8_t {
 
 #pragma pack(push, 1)
 struct PpktHeader {
-    uint8_t magic[4];         //  0: "PPKT"
-    uint8_t version;          //  4: protocol version (1)
-    uint8_t header_len;       //  5: total header size (48)
-    uint8_t dtype;            //  6: sample data type
-    uint8_t flags;            //  7: bitfield
-    uint16_t chan_id;         //  8: channel identifier
-    uint16_t reserved;        // 10: must be 0
-    uint32_t sequence;        // 12: per-channel seq number
-    uint32_t sample_count;    // 16: number of samples
-    uint32_t payload_bytes;   // 20: payload size in bytes
-    double sample_rate_hz;    // 24: task rate (Hz)
-    uint64_t timestamp_ns;    // 32: steady_clock nanoseconds
-    uint64_t iteration_index; // 40: logical iteration counter
+    uint8_t magic[4];          //  0: "PPKT"
+    uint8_t version;           //  4: protocol version (1)
+    uint8_t header_len;        //  5: total header size (48)
+    uint8_t dtype;             //  6: sample data type
+    uint8_t flags;             //  7: bitfield
+    uint16_t chan_id;          //  8: channel identifier
+    uint16_t contract_version; // 10: bind version= (0 = unversioned)
+    uint32_t sequence;         // 12: per-channel seq number
+    uint32_t sample_count;     // 16: number of samples
+    uint32_t payload_bytes;    // 20: payload size in bytes
+    double sample_rate_hz;     // 24: task rate (Hz)
+    uint64_t timestamp_ns;     // 32: steady_clock nanoseconds
+    uint64_t iteration_index;  // 40: logical iteration counter
 };
 #pragma pack(pop)
 
@@ -90,7 +90,7 @@ inline PpktHeader ppkt_make_header(DType dtype, uint16_t chan_id) {
     h.dtype = static_cast<uint8_t>(dtype);
     h.flags = 0;
     h.chan_id = chan_id;
-    h.reserved = 0;
+    h.contract_version = 0;
     h.sequence = 0;
     h.sample_count = 0;
     h.payload_bytes = 0;
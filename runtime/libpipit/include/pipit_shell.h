@@ -11,21 +11,33 @@
 
 #include <pipit.h>
 
+#include <algorithm>
 #include <atomic>
 #include <cerrno>
 #include <chrono>
 #include <cmath>
 #include <csignal>
 #include <cstdio>
+#include <cstdlib>
 #include <cstring>
 #include <limits>
 #include <mutex>
 #include <span>
 #include <string>
 #include <thread>
+#include <type_traits>
 #include <unordered_set>
+#include <utility>
 #include <vector>
 
+#if defined(__linux__) || defined(__APPLE__)
+#include <pthread.h>
+#define PIPIT_HAS_PTHREAD_SETNAME 1
+#endif
+#if defined(__linux__)
+#include <sched.h>
+#endif
+
 namespace pipit {
 
 // ── Descriptor types ────────────────────────────────────────────────────────
@@ -37,14 +49,26 @@ struct ParamDesc {
 
 struct TaskDesc {
     const char *name;
-    void (*entry)();  // task function pointer
-    TaskStats *stats; // per-task stats accumulator
+    void (*entry)();                      // task function pointer
+    TaskStats *stats;                     // per-task stats accumulator
+    const int64_t *deadline_ns = nullptr; // next tick deadline (single-thread runtime only)
+    int cpu = -1;                         // `set affinity` core, or -1 for OS placement
 };
 
 struct BufferStatsDesc {
     const char *name;
-    size_t (*available)(); // returns available token count
-    size_t elem_size;      // sizeof element type
+    size_t (*available)();               // returns available token count
+    size_t elem_size;                    // sizeof element type
+    const size_t *peak = nullptr;        // high-water mark in tokens (debug builds only)
+    size_t capacity = 0;                 // ring capacity in tokens (with peak)
+    const BufferStats *faults = nullptr; // underrun/overrun counters
+};
+
+struct ActorProfileDesc {
+    const char *task;
+    const char *actor;
+    uint32_t node_id;             // graph node (separates repeated actors)
+    const ActorProfile *profile;  // accumulated wall time
 };
 
 struct ProbeDesc {
@@ -86,11 +110,108 @@ struct ProgramDesc {
     std::span<const BufferStatsDesc> buffers;
     std::span<const ProbeDesc> probes;
     std::span<const BindDesc> binds;
+    std::span<const ActorProfileDesc> actor_profiles; // --profile-actors builds only
     const char *overrun_policy;
     size_t mem_allocated;
     size_t mem_used;
+    bool single_thread = false; // entries run one tick per call from one cooperative loop
 };
 
+// ── Param value parsing ─────────────────────────────────────────────────────
+
+/// Parse a `--param` value into `*out` without exceptions (for setters
+/// generated with `pcc --no-exceptions`). Like `std::stoi`/`stof`/`stod`, a
+/// numeric prefix is accepted; returns false if nothing converts or the
+/// value is out of range for `T`.
+template <typename T> inline bool parse_param(const char *v, T *out) {
+    char *end = nullptr;
+    errno = 0;
+    if constexpr (std::is_integral_v<T>) {
+        long long x = std::strtoll(v, &end, 10);
+        if (end == v || errno == ERANGE || !std::in_range<T>(x))
+            return false;
+        *out = static_cast<T>(x);
+    } else if constexpr (std::is_same_v<T, float>) {
+        float x = std::strtof(v, &end);
+        if (end == v || errno == ERANGE)
+            return false;
+        *out = x;
+    } else {
+        double x = std::strtod(v, &end);
+        if (end == v || errno == ERANGE)
+            return false;
+        *out = static_cast<T>(x);
+    }
+    return true;
+}
+
+// ── Host param control ──────────────────────────────────────────────────────
+
+/// Status returned by the generated `pipit_set_param(name, value)`.
+enum ParamStatus : int {
+    PARAM_OK = 0,      // value stored
+    PARAM_UNKNOWN = 1, // no param with that name
+    PARAM_INVALID = 2, // value not representable in the param's type
+};
+
+/// Coerce a host-supplied value into `*out`. Integer params accept only
+/// finite, integral values within range; floating params reject NaN and
+/// finite values that overflow `T`.
+template <typename T> inline bool coerce_param(double v, T *out) {
+    if constexpr (std::is_integral_v<T>) {
+        if (!std::isfinite(v) || v != std::trunc(v) ||
+            v < static_cast<double>(std::numeric_limits<T>::min()) ||
+            v >= static_cast<double>(std::numeric_limits<T>::max()) + 1.0)
+            return false;
+    } else {
+        if (std::isnan(v) ||
+            (std::isfinite(v) && std::fabs(v) > static_cast<double>(std::numeric_limits<T>::max())))
+            return false;
+    }
+    *out = static_cast<T>(v);
+    return true;
+}
+
+/// Name the calling thread `task_<name>` for profilers (`perf`, `top -H`).
+/// Truncated to 15 characters, the Linux limit. No-op on platforms
+/// without `pthread_setname_np`.
+inline void set_task_thread_name(const char *task_name) {
+#if defined(PIPIT_HAS_PTHREAD_SETNAME)
+    char buf[16];
+    std::snprintf(buf, sizeof(buf), "task_%s", task_name);
+#if defined(__APPLE__)
+    pthread_setname_np(buf); // macOS only names the calling thread
+#else
+    pthread_setname_np(pthread_self(), buf);
+#endif
+#else
+    (void)task_name;
+#endif
+}
+
+/// Pin the calling thread to CPU core `cpu` (`set affinity`). A negative
+/// `cpu` keeps OS placement. Failure (e.g. a core the machine lacks) is
+/// reported and the thread keeps running unpinned. No-op on platforms
+/// without `pthread_setaffinity_np`.
+inline void set_task_thread_affinity(const char *task_name, int cpu) {
+    if (cpu < 0)
+        return;
+#if defined(__linux__)
+    int rc = EINVAL;
+    if (cpu < CPU_SETSIZE) {
+        cpu_set_t set;
+        CPU_ZERO(&set);
+        CPU_SET(cpu, &set);
+        rc = pthread_setaffinity_np(pthread_self(), sizeof(set), &set);
+    }
+    if (rc != 0)
+        std::fprintf(stderr, "startup warning: cannot pin task '%s' to core %d: %s\n", task_name,
+                     cpu, std::strerror(rc));
+#else
+    (void)task_name;
+#endif
+}
+
 // ── Shell entry point ───────────────────────────────────────────────────────
 
 namespace detail {
@@ -100,13 +221,15 @@ inline bool parse_duration(const std::string &s, double *out) {
         *out = std::numeric_limits<double>::infinity();
         return true;
     }
-    std::size_t pos = 0;
-    double base = 0.0;
-    try {
-        base = std::stod(s, &pos);
-    } catch (...) {
+    // strtod rather than std::stod: the shell must build with -fno-exceptions.
+    const char *begin = s.c_str();
+    char *end = nullptr;
+    errno = 0;
+    double base = std::strtod(begin, &end);
+    if (end == begin || errno == ERANGE) {
         return false;
     }
+    std::size_t pos = static_cast<std::size_t>(end - begin);
     std::string unit = s.substr(pos);
     if (unit.empty() || unit == "s") {
         *out = base;
@@ -226,12 +349,16 @@ inline int shell_main(int argc, char *argv[], const ProgramDesc &desc) {
                 std::fprintf(stderr, "startup error: --threads requires a positive integer\n");
                 return 2;
             }
-            try {
-                threads = std::stoi(std::string(argv[++i]));
-            } catch (...) {
+            const char *arg = argv[++i];
+            char *end = nullptr;
+            errno = 0;
+            long value = std::strtol(arg, &end, 10);
+            if (end == arg || errno == ERANGE || value > std::numeric_limits<int>::max() ||
+                value < std::numeric_limits<int>::min()) {
                 std::fprintf(stderr, "startup error: --threads requires a positive integer\n");
                 return 2;
             }
+            threads = static_cast<int>(value);
             if (threads <= 0) {
                 std::fprintf(stderr, "startup error: --threads requires a positive integer\n");
                 return 2;
@@ -365,27 +492,55 @@ inline int shell_main(int argc, char *argv[], const ProgramDesc &desc) {
     s_stop = desc.state.stop;
     std::signal(SIGINT, [](int) { s_stop->store(true, std::memory_order_release); });
 
-    // ── Launch task threads ─────────────────────────────────────────────
-    std::vector<std::thread> task_threads;
-    task_threads.reserve(desc.tasks.size());
-    for (const auto &t : desc.tasks) {
-        task_threads.emplace_back(t.entry);
-    }
-    desc.state.start->store(true, std::memory_order_release);
-
-    // ── Duration wait ───────────────────────────────────────────────────
-    if (std::isfinite(duration_seconds)) {
-        std::this_thread::sleep_for(std::chrono::duration<double>(duration_seconds));
-        desc.state.stop->store(true, std::memory_order_release);
+    // ── Cooperative single-thread loop ──────────────────────────────────
+    // Earliest deadline first: each pass runs one tick of the task whose
+    // timer fires next, so faster tasks get proportionally more ticks.
+    // Tasks that have not ticked yet (deadline 0) run first, in order.
+    if (desc.single_thread) {
+        desc.state.start->store(true, std::memory_order_release);
+        auto end = std::chrono::steady_clock::time_point::max();
+        if (std::isfinite(duration_seconds)) {
+            end = std::chrono::steady_clock::now() +
+                  std::chrono::duration_cast<std::chrono::steady_clock::duration>(
+                      std::chrono::duration<double>(duration_seconds));
+        }
+        while (!desc.tasks.empty() && !desc.state.stop->load(std::memory_order_acquire)) {
+            const TaskDesc *next = &desc.tasks.front();
+            for (const auto &t : desc.tasks) {
+                if (*t.deadline_ns < *next->deadline_ns)
+                    next = &t;
+            }
+            next->entry();
+            if (std::chrono::steady_clock::now() >= end)
+                desc.state.stop->store(true, std::memory_order_release);
+        }
     } else {
-        // Run until SIGINT
-        while (!desc.state.stop->load(std::memory_order_acquire))
-            std::this_thread::sleep_for(std::chrono::milliseconds(100));
-    }
+        // ── Launch task threads ─────────────────────────────────────────
+        std::vector<std::thread> task_threads;
+        task_threads.reserve(desc.tasks.size());
+        for (const auto &t : desc.tasks) {
+            task_threads.emplace_back([&t] {
+                set_task_thread_name(t.name);
+                set_task_thread_affinity(t.name, t.cpu);
+                t.entry();
+            });
+        }
+        desc.state.start->store(true, std::memory_order_release);
+
+        // ── Duration wait ───────────────────────────────────────────────
+        if (std::isfinite(duration_seconds)) {
+            std::this_thread::sleep_for(std::chrono::duration<double>(duration_seconds));
+            desc.state.stop->store(true, std::memory_order_release);
+        } else {
+            // Run until SIGINT
+            while (!desc.state.stop->load(std::memory_order_acquire))
+                std::this_thread::sleep_for(std::chrono::milliseconds(100));
+        }
 
-    // ── Join threads ────────────────────────────────────────────────────
-    for (auto &t : task_threads) {
-        t.join();
+        // ── Join threads ────────────────────────────────────────────────
+        for (auto &t : task_threads) {
+            t.join();
+        }
     }
 
     // ── Advisory --threads warning ──────────────────────────────────────
@@ -407,11 +562,42 @@ inline int shell_main(int argc, char *argv[], const ProgramDesc &desc) {
             size_t avail = b.available();
             std::fprintf(stderr, "[stats] shared buffer '%s': %zu tokens (%zuB)\n", b.name, avail,
                          avail * b.elem_size);
+            if (b.peak)
+                std::fprintf(stderr, "[stats] buffer %s: peak %zu/%zu tokens\n", b.name, *b.peak,
+                             b.capacity);
+            if (b.faults)
+                std::fprintf(stderr, "[stats] buffer %s: read_fails=%lu, write_fails=%lu\n", b.name,
+                             (unsigned long)b.faults->read_fails.load(std::memory_order_relaxed),
+                             (unsigned long)b.faults->write_fails.load(std::memory_order_relaxed));
         }
         std::fprintf(stderr, "[stats] memory pool: %zuB allocated, %zuB used\n", desc.mem_allocated,
                      desc.mem_used);
     }
 
+    // ── Per-actor profile (--profile-actors builds) ─────────────────────
+    if (!desc.actor_profiles.empty()) {
+        std::vector<const ActorProfileDesc *> ranked;
+        uint64_t total_ns = 0;
+        for (const auto &a : desc.actor_profiles) {
+            ranked.push_back(&a);
+            total_ns += a.profile->total_ns;
+        }
+        std::stable_sort(ranked.begin(), ranked.end(), [](const auto *x, const auto *y) {
+            return x->profile->total_ns > y->profile->total_ns;
+        });
+        std::fprintf(stderr, "[profile] actor wall time, %lluns total:\n",
+                     (unsigned long long)total_ns);
+        for (const auto *a : ranked) {
+            const auto &p = *a->profile;
+            double pct = total_ns > 0 ? 100.0 * static_cast<double>(p.total_ns) / total_ns : 0.0;
+            std::fprintf(stderr,
+                         "[profile] %5.1f%% %lluns firings=%llu avg=%lluns  %s (node %u, task '%s')\n",
+                         pct, (unsigned long long)p.total_ns, (unsigned long long)p.firings,
+                         (unsigned long long)(p.firings > 0 ? p.total_ns / p.firings : 0),
+                         a->actor, a->node_id, a->task);
+        }
+    }
+
     return desc.state.exit_code->load(std::memory_order_acquire);
 }
 
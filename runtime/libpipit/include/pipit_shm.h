@@ -25,8 +25,10 @@
 #include <pipit_net.h>
 #include <pipit_shell.h>
 
+#include <cerrno>
 #include <cstdint>
 #include <cstdio>
+#include <cstdlib>
 #include <cstring>
 #include <mutex>
 #include <string>
@@ -601,11 +603,14 @@ inline ShmEndpointArgs parse_shm_endpoint(const std::string &ep) {
             if (pos == std::string::npos)
                 return -1;
             pos += search.size();
-            try {
-                return std::stoll(ep.substr(pos));
-            } catch (...) {
+            const char *begin = ep.c_str() + pos;
+            char *end = nullptr;
+            errno = 0;
+            long long value = std::strtoll(begin, &end, 10);
+            if (end == begin || errno == ERANGE) {
                 return -1;
             }
+            return value;
         };
         args.slots = extract_named("slots");
         args.slot_bytes = extract_named("slot_bytes");
@@ -669,8 +674,8 @@ class ShmIoAdapter {
     }
 
     /// Receive data from the SHM ring.
-    /// Zero-fills output if no valid data is available.
-    void recv(void *out, uint32_t n_tokens) {
+    /// Zero-fills output and returns false if no valid data is available.
+    bool recv(void *out, uint32_t n_tokens) {
         std::lock_guard<std::mutex> lk(io_mtx_);
         size_t fill_bytes = n_tokens * pipit::net::dtype_size(dtype_);
         std::memset(out, 0, fill_bytes);
@@ -678,9 +683,9 @@ class ShmIoAdapter {
         if (!initialized_)
             lazy_init();
         if (!reader_.is_valid())
-            return;
+            return false;
 
-        reader_.consume(out, fill_bytes);
+        return reader_.consume(out, fill_bytes) > 0;
     }
 
     /// Validate geometry and reconnect to a new endpoint.
@@ -799,7 +804,7 @@ class ShmIoAdapter {
         }
     }
 
-    void recv(void *out, uint32_t n_tokens) {
+    bool recv(void *out, uint32_t n_tokens) {
         if (!warned_) {
             std::fprintf(stderr,
                          "pshm bind '%s': shared memory transport not supported on this platform\n",
@@ -808,6 +813,7 @@ class ShmIoAdapter {
         }
         // Zero-fill to maintain non-blocking contract
         std::memset(out, 0, n_tokens * 4); // approximate
+        return false;
     }
 
     bool try_reconnect(const std::string &) {
@@ -0,0 +1,492 @@
+#pragma once
+/// @file pipit_tcp.h
+/// @brief TCP bind transport — PPKT packets over a stream socket
+///
+/// Provides:
+///   - TcpSender: client connection for OUT binds (connects to the endpoint)
+///   - TcpReceiver: listening server for IN binds (accepts one peer at a time)
+///   - ppkt_send_stream: write one firing as a single PPKT packet
+///   - TcpIoAdapter: high-level adapter for generated code (lazy init, rebind,
+///     timed reconnect)
+///
+/// Framing: packets are written back to back on the stream, each a 48-byte
+/// PpktHeader followed by `payload_bytes` of samples.  Unlike the datagram
+/// transports there is no MTU chunking: one firing is one packet, flagged
+/// FLAG_FRAME_START | FLAG_FRAME_END.
+///
+/// See doc/spec/pcc-spec-v0.4.0.md §5.5 for the bind compilation contract.
+
+#include <pipit.h>
+#include <pipit_bind_io.h>
+#include <pipit_net.h>
+#include <pipit_shell.h>
+
+#include <cerrno>
+#include <chrono>
+#include <cstdio>
+#include <cstring>
+#include <memory>
+#include <mutex>
+#include <string>
+#include <vector>
+
+#include <netinet/tcp.h>
+#include <poll.h>
+
+namespace pipit {
+namespace net {
+
+/// Default `backlog=` for IN binds.
+static constexpr int TCP_DEFAULT_BACKLOG = 1;
+/// Default `reconnect=` interval for OUT binds (milliseconds).
+static constexpr int TCP_DEFAULT_RECONNECT_MS = 1000;
+
+// A peer that closes mid-write must not raise SIGPIPE: Linux suppresses it per
+// call, macOS per socket (SO_NOSIGPIPE, set in TcpSender::open).
+#ifdef MSG_NOSIGNAL
+static constexpr int TCP_SEND_FLAGS = MSG_NOSIGNAL;
+#else
+static constexpr int TCP_SEND_FLAGS = 0;
+#endif
+
+inline bool tcp_set_nonblocking(int fd) {
+    int flags = fcntl(fd, F_GETFL, 0);
+    return flags >= 0 && fcntl(fd, F_SETFL, flags | O_NONBLOCK) >= 0;
+}
+
+// ── TcpSender (client) ──────────────────────────────────────────────────────
+
+/// Non-blocking client.  Neither connecting nor sending ever waits: the
+/// connect completes in the background (see connected()), and a packet the
+/// socket cannot take right away is kept in `pending_` and flushed by later
+/// calls.  While a packet is still pending, further packets are dropped whole,
+/// so the stream never carries a partial packet.
+class TcpSender {
+    int fd_ = -1;
+    bool valid_ = false;
+    bool connecting_ = false;
+    std::vector<uint8_t> pending_;
+    size_t pending_off_ = 0;
+
+  public:
+    TcpSender() = default;
+
+    /// Start connecting to "host:port".  Returns false if the address is
+    /// invalid or the connect fails immediately; otherwise the connection may
+    /// still be in progress.
+    bool open(const char *addr, size_t addr_len) {
+        ParsedAddr pa = parse_address(addr, addr_len);
+        if (pa.kind != AddrKind::INET)
+            return false;
+
+        fd_ = ::socket(AF_INET, SOCK_STREAM, 0);
+        if (fd_ < 0)
+            return false;
+        if (!tcp_set_nonblocking(fd_)) {
+            close();
+            return false;
+        }
+        int one = 1;
+#ifdef SO_NOSIGPIPE
+        setsockopt(fd_, SOL_SOCKET, SO_NOSIGPIPE, &one, sizeof(one));
+#endif
+        setsockopt(fd_, IPPROTO_TCP, TCP_NODELAY, &one, sizeof(one));
+
+        if (::connect(fd_, reinterpret_cast<const struct sockaddr *>(&pa.storage), pa.len) < 0) {
+            if (errno != EINPROGRESS) {
+                close();
+                return false;
+            }
+            connecting_ = true;
+        }
+        valid_ = true;
+        return true;
+    }
+
+    /// True once the connection is established.  Polls an in-progress connect
+    /// without waiting; a failed connect closes the sender.
+    bool connected() {
+        if (!valid_)
+            return false;
+        if (!connecting_)
+            return true;
+        struct pollfd pfd = {fd_, POLLOUT, 0};
+        if (::poll(&pfd, 1, 0) == 0)
+            return false;
+        int err = 0;
+        socklen_t err_len = sizeof(err);
+        if (getsockopt(fd_, SOL_SOCKET, SO_ERROR, &err, &err_len) < 0 || err != 0) {
+            close();
+            return false;
+        }
+        connecting_ = false;
+        return true;
+    }
+
+    /// Queue one packet made of `head` followed by `body`.  Returns false if
+    /// the packet was dropped: not connected yet, an earlier packet is still
+    /// pending, or the connection failed (which closes it).
+    bool send(const void *head, size_t head_len, const void *body, size_t body_len) {
+        if (!connected() || !flush() || pending_off_ < pending_.size())
+            return false;
+        const auto *h = static_cast<const uint8_t *>(head);
+        const auto *b = static_cast<const uint8_t *>(body);
+        pending_.clear();
+        pending_.insert(pending_.end(), h, h + head_len);
+        pending_.insert(pending_.end(), b, b + body_len);
+        pending_off_ = 0;
+        return flush();
+    }
+
+    bool is_valid() const { return valid_; }
+
+    void close() {
+        if (fd_ >= 0)
+            ::close(fd_);
+        fd_ = -1;
+        valid_ = false;
+        connecting_ = false;
+        pending_.clear();
+        pending_off_ = 0;
+    }
+
+    ~TcpSender() { close(); }
+
+    // Non-copyable
+    TcpSender(const TcpSender &) = delete;
+    TcpSender &operator=(const TcpSender &) = delete;
+
+  private:
+    /// Write as much of the pending packet as the socket takes now.  Returns
+    /// false (and closes) only on a connection error.
+    bool flush() {
+        while (pending_off_ < pending_.size()) {
+            ssize_t r = ::send(fd_, pending_.data() + pending_off_, pending_.size() - pending_off_,
+                               TCP_SEND_FLAGS);
+            if (r > 0) {
+                pending_off_ += static_cast<size_t>(r);
+            } else if (r < 0 && errno == EINTR) {
+                continue;
+            } else if (r < 0 && (errno == EAGAIN || errno == EWOULDBLOCK)) {
+                return true;
+            } else {
+                close();
+                return false;
+            }
+        }
+        return true;
+    }
+};
+
+// ── TcpReceiver (listening server) ──────────────────────────────────────────
+
+class TcpReceiver {
+    int listen_fd_ = -1;
+    int conn_fd_ = -1;
+    bool valid_ = false;
+    std::vector<uint8_t> pending_;
+
+  public:
+    TcpReceiver() = default;
+
+    /// Bind and listen on "host:port" (non-blocking).
+    bool open(const char *addr, size_t addr_len, int backlog = TCP_DEFAULT_BACKLOG) {
+        ParsedAddr pa = parse_address(addr, addr_len);
+        if (pa.kind != AddrKind::INET)
+            return false;
+
+        listen_fd_ = ::socket(AF_INET, SOCK_STREAM, 0);
+        if (listen_fd_ < 0)
+            return false;
+        int optval = 1;
+        setsockopt(listen_fd_, SOL_SOCKET, SO_REUSEADDR, &optval, sizeof(optval));
+        const auto *sa = reinterpret_cast<const struct sockaddr *>(&pa.storage);
+        if (::bind(listen_fd_, sa, pa.len) < 0 || ::listen(listen_fd_, backlog) < 0 ||
+            !tcp_set_nonblocking(listen_fd_)) {
+            close();
+            return false;
+        }
+        valid_ = true;
+        return true;
+    }
+
+    /// Copy the next complete PPKT packet into `buf`.
+    /// Returns its length, 0 if no complete packet is buffered yet, or -1 on
+    /// lost framing.  After a peer disconnects the next one is accepted.
+    ssize_t recv(void *buf, size_t max_len) {
+        if (!valid_)
+            return -1;
+        if (conn_fd_ < 0) {
+            int fd = ::accept(listen_fd_, nullptr, nullptr);
+            if (fd >= 0) {
+                // A new peer starts a new stream; a partial packet left by
+                // the previous one can never complete.
+                pending_.clear();
+                conn_fd_ = fd;
+                if (!tcp_set_nonblocking(conn_fd_))
+                    drop_peer();
+            }
+        }
+        fill();
+        return take_packet(buf, max_len);
+    }
+
+    bool is_valid() const { return valid_; }
+    bool has_peer() const { return conn_fd_ >= 0; }
+
+    void close() {
+        drop_peer();
+        if (listen_fd_ >= 0)
+            ::close(listen_fd_);
+        listen_fd_ = -1;
+        valid_ = false;
+    }
+
+    ~TcpReceiver() { close(); }
+
+    // Non-copyable
+    TcpReceiver(const TcpReceiver &) = delete;
+    TcpReceiver &operator=(const TcpReceiver &) = delete;
+
+  private:
+    /// Read everything currently available on the connection.
+    void fill() {
+        uint8_t chunk[4096];
+        while (conn_fd_ >= 0) {
+            ssize_t r = ::recv(conn_fd_, chunk, sizeof(chunk), 0);
+            if (r > 0) {
+                pending_.insert(pending_.end(), chunk, chunk + r);
+            } else if (r < 0 && errno == EINTR) {
+                continue;
+            } else if (r < 0 && (errno == EAGAIN || errno == EWOULDBLOCK)) {
+                return;
+            } else {
+                // Orderly shutdown (0) or error: keep complete packets
+                // already buffered, re-arm accept.
+                ::close(conn_fd_);
+                conn_fd_ = -1;
+            }
+        }
+    }
+
+    ssize_t take_packet(void *buf, size_t max_len) {
+        if (pending_.size() < sizeof(PpktHeader))
+            return 0;
+        PpktHeader hdr;
+        std::memcpy(&hdr, pending_.data(), sizeof(hdr));
+        if (!ppkt_validate(hdr)) {
+            // Lost framing: discard the stream and wait for the next peer.
+            drop_peer();
+            return -1;
+        }
+        size_t total = sizeof(PpktHeader) + hdr.payload_bytes;
+        if (pending_.size() < total)
+            return 0;
+        size_t n = total < max_len ? total : max_len;
+        std::memcpy(buf, pending_.data(), n);
+        pending_.erase(pending_.begin(), pending_.begin() + static_cast<ptrdiff_t>(total));
+        return static_cast<ssize_t>(n);
+    }
+
+    void drop_peer() {
+        if (conn_fd_ >= 0)
+            ::close(conn_fd_);
+        conn_fd_ = -1;
+        pending_.clear();
+    }
+};
+
+// ── Stream send helper ──────────────────────────────────────────────────────
+
+/// Send N samples as one PPKT packet (no chunking on a stream transport).
+/// Returns true if the packet was queued whole; false if it was dropped.
+inline bool ppkt_send_stream(TcpSender &sender, PpktHeader &hdr, const void *data, uint32_t n) {
+    size_t dsz = dtype_size(static_cast<DType>(hdr.dtype));
+    if (dsz == 0)
+        return false;
+    hdr.sample_count = n;
+    hdr.payload_bytes = static_cast<uint32_t>(n * dsz);
+    hdr.flags |= FLAG_FRAME_START | FLAG_FRAME_END;
+    bool ok = sender.send(&hdr, sizeof(PpktHeader), data, hdr.payload_bytes);
+    hdr.sequence++;
+    return ok;
+}
+
+} // namespace net
+
+// ── TCP bind I/O adapter ────────────────────────────────────────────────────
+
+/// Stream counterpart of BindIoAdapter.  OUT binds connect as a client and
+/// retry every `reconnect_ms` after a failed connect or a dropped connection
+/// (0 disables retries after MAX_INIT_RETRIES).  Sending never blocks the task:
+/// firings are dropped while connecting or while the peer lags.  IN binds
+/// listen with `backlog` and keep the latest complete packet, like the datagram
+/// adapter.
+class TcpIoAdapter {
+    const char *name_;
+    pipit::net::DType dtype_;
+    double rate_hz_;
+    bool is_out_;
+    int backlog_;
+    int reconnect_ms_;
+    BindState *state_;
+
+    std::unique_ptr<pipit::net::TcpSender> sender_;
+    std::unique_ptr<pipit::net::TcpReceiver> receiver_;
+    pipit::net::PpktHeader hdr_;
+    bool initialized_ = false;
+    int init_fail_count_ = 0;
+    static constexpr int MAX_INIT_RETRIES = 3;
+    std::chrono::steady_clock::time_point next_attempt_{};
+    std::string endpoint_;
+    std::mutex io_mtx_;
+    std::vector<uint8_t> recv_buf_;
+
+  public:
+    TcpIoAdapter(const char *name, bool is_out, pipit::net::DType dtype, uint16_t chan_id,
+                 double rate_hz, BindState *state, int backlog = pipit::net::TCP_DEFAULT_BACKLOG,
+                 int reconnect_ms = pipit::net::TCP_DEFAULT_RECONNECT_MS,
+                 uint16_t contract_version = 0)
+        : name_(name), dtype_(dtype), rate_hz_(rate_hz), is_out_(is_out), backlog_(backlog),
+          reconnect_ms_(reconnect_ms), state_(state) {
+        hdr_ = pipit::net::ppkt_make_header(dtype, chan_id);
+        hdr_.contract_version = contract_version;
+        hdr_.flags = pipit::net::FLAG_FIRST_FRAME;
+    }
+
+    /// Send one firing to the peer as a PPKT packet.
+    void send(const void *data, uint32_t n_tokens) {
+        std::lock_guard<std::mutex> lk(io_mtx_);
+        bool dropped = sender_ && !sender_->is_valid();
+        if (!initialized_ || (dropped && reconnect_ms_ > 0))
+            lazy_init();
+        if (!sender_ || !sender_->is_valid())
+            return;
+        if (!sender_->connected()) {
+            if (!sender_->is_valid())
+                open_failed(std::chrono::steady_clock::now());
+            return; // still connecting: drop this firing
+        }
+        init_fail_count_ = 0;
+
+        hdr_.sample_rate_hz = pipit_task_rate_hz();
+        hdr_.timestamp_ns = pipit_now_ns();
+        hdr_.iteration_index = pipit_iteration_index();
+        if (pipit::net::ppkt_send_stream(*sender_, hdr_, data, n_tokens))
+            hdr_.flags = 0;
+        else if (!sender_->is_valid())
+            hdr_.flags = pipit::net::FLAG_FIRST_FRAME; // next connection starts a new stream
+    }
+
+    /// Receive the latest complete packet.  Zero-fills output and returns
+    /// false if none.
+    bool recv(void *out, uint32_t n_tokens) {
+        std::lock_guard<std::mutex> lk(io_mtx_);
+        size_t fill_bytes = n_tokens * pipit::net::dtype_size(dtype_);
+        std::memset(out, 0, fill_bytes);
+
+        if (!initialized_)
+            lazy_init();
+        if (!receiver_ || !receiver_->is_valid())
+            return false;
+
+        size_t cap = sizeof(pipit::net::PpktHeader) + fill_bytes;
+        if (recv_buf_.size() < cap)
+            recv_buf_.resize(cap);
+        ssize_t latest_len = 0;
+        for (;;) {
+            ssize_t r = receiver_->recv(recv_buf_.data(), recv_buf_.size());
+            if (r <= 0)
+                break;
+            latest_len = r;
+        }
+        if (latest_len < static_cast<ssize_t>(sizeof(pipit::net::PpktHeader)))
+            return false;
+
+        const auto *pkt_hdr = reinterpret_cast<const pipit::net::PpktHeader *>(recv_buf_.data());
+        if (pkt_hdr->dtype != static_cast<uint8_t>(dtype_))
+            return false;
+        size_t header_size = sizeof(pipit::net::PpktHeader);
+        size_t available_bytes = std::min(static_cast<size_t>(pkt_hdr->payload_bytes),
+                                          static_cast<size_t>(latest_len) - header_size);
+        std::memcpy(out, recv_buf_.data() + header_size, std::min(available_bytes, fill_bytes));
+        return true;
+    }
+
+    /// Reconnect to a new endpoint.  Called after rebind.
+    /// Empty string disconnects (next I/O becomes no-op).
+    void reconnect(const std::string &new_endpoint) {
+        std::lock_guard<std::mutex> lk(io_mtx_);
+        sender_.reset();
+        receiver_.reset();
+        endpoint_ = extract_address(new_endpoint);
+        initialized_ = false;
+        init_fail_count_ = 0;
+        next_attempt_ = {};
+        hdr_.flags = pipit::net::FLAG_FIRST_FRAME;
+    }
+
+  private:
+    void lazy_init() {
+        // Already holding io_mtx_
+        auto now = std::chrono::steady_clock::now();
+        if (init_fail_count_ >= MAX_INIT_RETRIES && reconnect_ms_ <= 0)
+            return; // permanent no-op, already warned
+        if (now < next_attempt_)
+            return; // waiting out the reconnect interval
+
+        std::string ep;
+        {
+            std::lock_guard<std::mutex> lk(state_->mtx);
+            ep = state_->current_endpoint;
+        }
+        endpoint_ = extract_address(ep);
+
+        if (endpoint_.empty()) {
+            initialized_ = true; // intentional no-op mode
+            return;
+        }
+
+        bool ok = false;
+        if (is_out_) {
+            sender_ = std::make_unique<pipit::net::TcpSender>();
+            ok = sender_->open(endpoint_.c_str(), endpoint_.size());
+        } else {
+            receiver_ = std::make_unique<pipit::net::TcpReceiver>();
+            ok = receiver_->open(endpoint_.c_str(), endpoint_.size(), backlog_);
+        }
+
+        if (ok) {
+            // An OUT connect may still be in progress; send() resets the
+            // failure count once it completes.
+            initialized_ = true;
+            if (!is_out_)
+                init_fail_count_ = 0;
+            return;
+        }
+        open_failed(now);
+    }
+
+    /// Count a failed open or connect, and schedule the next attempt.
+    void open_failed(std::chrono::steady_clock::time_point now) {
+        initialized_ = false;
+        init_fail_count_++;
+        if (reconnect_ms_ > 0) {
+            if (init_fail_count_ == 1)
+                std::fprintf(stderr,
+                             "bind '%s': failed to open tcp endpoint '%s', retrying every %d ms\n",
+                             name_, endpoint_.c_str(), reconnect_ms_);
+            next_attempt_ = now + std::chrono::milliseconds(reconnect_ms_);
+            return;
+        }
+        std::fprintf(stderr, "bind '%s': failed to open tcp endpoint '%s' (attempt %d/%d)\n", name_,
+                     endpoint_.c_str(), init_fail_count_, MAX_INIT_RETRIES);
+        if (init_fail_count_ >= MAX_INIT_RETRIES) {
+            std::fprintf(stderr, "bind '%s': giving up after %d attempts\n", name_,
+                         MAX_INIT_RETRIES);
+            initialized_ = true; // permanent no-op
+        }
+    }
+};
+
+} // namespace pipit
@@ -21,7 +21,26 @@
 #ifndef POCKETFFT_CACHE_SIZE
 #define POCKETFFT_CACHE_SIZE 16
 #endif
+#if defined(__cpp_exceptions)
 #include <third_party/pocketfft_hdronly.h>
+#else
+// -fno-exceptions: PocketFFT's (statement-form) throws become std::abort().
+// Its standard headers are included first so the shim only sees PocketFFT.
+#include <algorithm>
+#include <array>
+#include <cstddef>
+#include <cstdint>
+#include <cstdlib>
+#include <exception>
+#include <memory>
+#include <mutex>
+#include <new>
+#include <stdexcept>
+#include <vector>
+#define throw std::abort(), (void)
+#include <third_party/pocketfft_hdronly.h>
+#undef throw
+#endif
 
 /// @defgroup source_actors Source Actors
 /// @{
@@ -40,7 +59,7 @@
 /// clock 1kHz t { constant(1.0) | stdout() }
 /// @endcode
 template <typename T>
-ACTOR(constant, IN(void, 0), OUT(T, N), RUNTIME_PARAM(T, value) PARAM(int, N)) {
+ACTOR(constant, IN(void, 0), OUT(T, N), RUNTIME_PARAM(T, value) PARAM(int, N) PURE) {
     (void)in;
     for (int i = 0; i < N; ++i) {
         out[i] = value;
@@ -255,7 +274,7 @@ template <typename T> ACTOR(impulse, IN(void, 0), OUT(T, N), PARAM(int, period)
 /// @code{.pdl}
 /// fft(256)
 /// @endcode
-ACTOR(fft, IN(float, N), OUT(cfloat, N), PARAM(int, N)) {
+ACTOR(fft, IN(float, N), OUT(cfloat, N), PARAM(int, N) PURE) {
     // Verify N is power of 2
     if (N <= 0 || (N & (N - 1)) != 0) {
         return ACTOR_ERROR;
@@ -288,7 +307,7 @@ ACTOR(fft, IN(float, N), OUT(cfloat, N), PARAM(int, N)) {
 /// @code{.pdl}
 /// c2r()
 /// @endcode
-ACTOR(c2r, IN(cfloat, N), OUT(float, N), PARAM(int, N)) {
+ACTOR(c2r, IN(cfloat, N), OUT(float, N), PARAM(int, N) PURE) {
     using cbatch = xsimd::batch<std::complex<float>>;
     constexpr int S = static_cast<int>(cbatch::size);
     int i = 0;
@@ -313,7 +332,7 @@ ACTOR(c2r, IN(cfloat, N), OUT(float, N), PARAM(int, N)) {
 /// @code{.pdl}
 /// mag()
 /// @endcode
-ACTOR(mag, IN(cfloat, SHAPE(N)), OUT(float, SHAPE(N)), PARAM(int, N)) {
+ACTOR(mag, IN(cfloat, SHAPE(N)), OUT(float, SHAPE(N)), PARAM(int, N) PURE) {
     using cbatch = xsimd::batch<std::complex<float>>;
     constexpr int S = static_cast<int>(cbatch::size);
     int i = 0;
@@ -328,6 +347,25 @@ ACTOR(mag, IN(cfloat, SHAPE(N)), OUT(float, SHAPE(N)), PARAM(int, N)) {
 }
 ;
 
+/// @brief Complex magnitude squared
+///
+/// Computes |x|^2 = re^2 + im^2 of complex signal without the square root.
+/// `pcc` rewrites `mag() | sqr()` into this actor.
+///
+/// @return ACTOR_OK on success
+///
+/// Example usage:
+/// @code{.pdl}
+/// magsq()
+/// @endcode
+ACTOR(magsq, IN(cfloat, SHAPE(N)), OUT(float, SHAPE(N)), PARAM(int, N) PURE) {
+    for (int i = 0; i < N; ++i)
+        out[i] = std::norm(in[i]);
+    return ACTOR_OK;
+}
+}
+;
+
 /// @brief Finite Impulse Response filter
 ///
 /// Applies FIR filter with given coefficients.
@@ -342,7 +380,7 @@ ACTOR(mag, IN(cfloat, SHAPE(N)), OUT(float, SHAPE(N)), PARAM(int, N)) {
 /// fir([0.1, 0.2, 0.4, 0.2, 0.1])
 /// @endcode
 template <typename T>
-ACTOR(fir, IN(T, N), OUT(T, 1), PARAM(std::span<const T>, coeff) PARAM(int, N)) {
+ACTOR(fir, IN(T, N), OUT(T, 1), PARAM(std::span<const T>, coeff) PARAM(int, N) PURE) {
     using batch = xsimd::batch<T>;
     constexpr int S = static_cast<int>(batch::size);
     batch acc(T{});
@@ -379,7 +417,7 @@ ACTOR(fir, IN(T, N), OUT(T, 1), PARAM(std::span<const T>, coeff) PARAM(int, N))
 /// @code{.pdl}
 /// mean(10)
 /// @endcode
-template <typename T> ACTOR(mean, IN(T, N), OUT(T, 1), PARAM(int, N)) {
+template <typename T> ACTOR(mean, IN(T, N), OUT(T, 1), PARAM(int, N) PURE) {
     using batch = xsimd::batch<T>;
     constexpr int S = static_cast<int>(batch::size);
     batch acc(T{});
@@ -408,7 +446,7 @@ template <typename T> ACTOR(mean, IN(T, N), OUT(T, 1), PARAM(int, N)) {
 /// @code{.pdl}
 /// rms(10)
 /// @endcode
-template <typename T> ACTOR(rms, IN(T, N), OUT(T, 1), PARAM(int, N)) {
+template <typename T> ACTOR(rms, IN(T, N), OUT(T, 1), PARAM(int, N) PURE) {
     using batch = xsimd::batch<T>;
     constexpr int S = static_cast<int>(batch::size);
     batch acc(T{});
@@ -439,7 +477,7 @@ template <typename T> ACTOR(rms, IN(T, N), OUT(T, 1), PARAM(int, N)) {
 /// @code{.pdl}
 /// min(10)
 /// @endcode
-template <typename T> ACTOR(min, IN(T, N), OUT(T, 1), PARAM(int, N)) {
+template <typename T> ACTOR(min, IN(T, N), OUT(T, 1), PARAM(int, N) PURE) {
     using batch = xsimd::batch<T>;
     constexpr int S = static_cast<int>(batch::size);
     int i = 0;
@@ -473,7 +511,7 @@ template <typename T> ACTOR(min, IN(T, N), OUT(T, 1), PARAM(int, N)) {
 /// @code{.pdl}
 /// max(10)
 /// @endcode
-template <typename T> ACTOR(max, IN(T, N), OUT(T, 1), PARAM(int, N)) {
+template <typename T> ACTOR(max, IN(T, N), OUT(T, 1), PARAM(int, N) PURE) {
     using batch = xsimd::batch<T>;
     constexpr int S = static_cast<int>(batch::size);
     int i = 0;
@@ -546,7 +584,8 @@ template <typename T> ACTOR(delay, IN(T, 1), OUT(T, 1), PARAM(int, N) PARAM(T, i
 /// binread("data.bin", "int16")
 /// @endcode
 ACTOR(binread, IN(void, 0), OUT(float, 1),
-      RUNTIME_PARAM(std::span<const char>, path) RUNTIME_PARAM(std::span<const char>, dtype)) {
+      RUNTIME_PARAM(std::span<const char>, path) RUNTIME_PARAM(std::span<const char>, dtype)
+          TERMINATES) {
     (void)in;
     static FILE *fp = nullptr;
     static bool initialized = false;
@@ -675,8 +714,31 @@ ACTOR(binwrite, IN(float, 1), OUT(void, 0),
 /// @code{.pdl}
 /// decimate(10)
 /// @endcode
-template <typename T> ACTOR(decimate, IN(T, N), OUT(T, 1), PARAM(int, N)) {
+template <typename T> ACTOR(decimate, IN(T, N), OUT(T, 1), PARAM(int, N) RATE_CHANGE(1, N) PURE) {
+    out[0] = in[0];
+    return ACTOR_OK;
+}
+}
+;
+
+/// @brief Upsampling
+///
+/// Consumes 1 token, outputs it followed by N-1 zeros (rate increase by N).
+/// Follow with a low-pass `fir` to interpolate between the samples.
+/// Polymorphic: works with any wire type.
+///
+/// @param N Interpolation factor
+/// @return ACTOR_OK on success
+///
+/// Example usage:
+/// @code{.pdl}
+/// interpolate(4)
+/// @endcode
+template <typename T>
+ACTOR(interpolate, IN(T, 1), OUT(T, N), PARAM(int, N) RATE_CHANGE(N, 1) PURE) {
     out[0] = in[0];
+    for (int i = 1; i < N; ++i)
+        out[i] = T{};
     return ACTOR_OK;
 }
 }
@@ -738,7 +800,7 @@ template <typename T> ACTOR(stderr, IN(T, 1), OUT(void, 0)) {
 /// @code{.pdl}
 /// stdin()
 /// @endcode
-template <typename T> ACTOR(stdin, IN(void, 0), OUT(T, 1)) {
+template <typename T> ACTOR(stdin, IN(void, 0), OUT(T, 1), TERMINATES) {
     (void)in;
     T value;
     float temp;
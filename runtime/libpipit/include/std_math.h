@@ -26,7 +26,7 @@
 /// mul($gain)
 /// mul(2.5)
 /// @endcode
-template <typename T> ACTOR(mul, IN(T, N), OUT(T, N), RUNTIME_PARAM(T, gain) PARAM(int, N)) {
+template <typename T> ACTOR(mul, IN(T, N), OUT(T, N), RUNTIME_PARAM(T, gain) PARAM(int, N) PURE) {
     using batch = xsimd::batch<T>;
     constexpr int S = static_cast<int>(batch::size);
     auto vgain = batch(gain);
@@ -53,7 +53,7 @@ template <typename T> ACTOR(mul, IN(T, N), OUT(T, N), RUNTIME_PARAM(T, gain) PAR
 /// @code{.pdl}
 /// :a | add(:b)
 /// @endcode
-template <typename T> ACTOR(add, IN(T, 2), OUT(T, 1)) {
+template <typename T> ACTOR(add, IN(T, 2), OUT(T, 1), PURE INLINE) {
     out[0] = in[0] + in[1];
     return ACTOR_OK;
 }
@@ -71,7 +71,7 @@ template <typename T> ACTOR(add, IN(T, 2), OUT(T, 1)) {
 /// @code{.pdl}
 /// :a | sub(:b)
 /// @endcode
-template <typename T> ACTOR(sub, IN(T, 2), OUT(T, 1)) {
+template <typename T> ACTOR(sub, IN(T, 2), OUT(T, 1), PURE INLINE) {
     out[0] = in[0] - in[1];
     return ACTOR_OK;
 }
@@ -91,7 +91,7 @@ template <typename T> ACTOR(sub, IN(T, 2), OUT(T, 1)) {
 /// @code{.pdl}
 /// :a | div(:b)
 /// @endcode
-template <typename T> ACTOR(div, IN(T, 2), OUT(T, 1)) {
+template <typename T> ACTOR(div, IN(T, 2), OUT(T, 1), PURE INLINE) {
     if (in[1] == T{}) {
         out[0] = std::numeric_limits<T>::quiet_NaN();
     } else {
@@ -113,7 +113,7 @@ template <typename T> ACTOR(div, IN(T, 2), OUT(T, 1)) {
 /// @code{.pdl}
 /// abs()
 /// @endcode
-template <typename T> ACTOR(abs, IN(T, 1), OUT(T, 1)) {
+template <typename T> ACTOR(abs, IN(T, 1), OUT(T, 1), PURE INLINE) {
     out[0] = std::abs(in[0]);
     return ACTOR_OK;
 }
@@ -132,13 +132,31 @@ template <typename T> ACTOR(abs, IN(T, 1), OUT(T, 1)) {
 /// @code{.pdl}
 /// sqrt()
 /// @endcode
-template <typename T> ACTOR(sqrt, IN(T, 1), OUT(T, 1)) {
+template <typename T> ACTOR(sqrt, IN(T, 1), OUT(T, 1), PURE INLINE) {
     out[0] = std::sqrt(in[0]);
     return ACTOR_OK;
 }
 }
 ;
 
+/// @brief Square
+///
+/// Computes x * x of signal.
+/// Polymorphic: works with any numeric wire type.
+///
+/// @return ACTOR_OK on success
+///
+/// Example usage:
+/// @code{.pdl}
+/// sqr()
+/// @endcode
+template <typename T> ACTOR(sqr, IN(T, 1), OUT(T, 1), PURE INLINE) {
+    out[0] = in[0] * in[0];
+    return ACTOR_OK;
+}
+}
+;
+
 /// @brief Threshold detector
 ///
 /// Converts signal to int32 based on threshold.
@@ -153,7 +171,7 @@ template <typename T> ACTOR(sqrt, IN(T, 1), OUT(T, 1)) {
 /// @code{.pdl}
 /// threshold(0.5)
 /// @endcode
-template <typename T> ACTOR(threshold, IN(T, 1), OUT(int32, 1), RUNTIME_PARAM(T, value)) {
+template <typename T> ACTOR(threshold, IN(T, 1), OUT(int32, 1), RUNTIME_PARAM(T, value) PURE) {
     out[0] = (in[0] > value) ? 1 : 0;
     return ACTOR_OK;
 }
@@ -180,7 +198,7 @@ template <typename T> ACTOR(threshold, IN(T, 1), OUT(int32, 1), RUNTIME_PARAM(T,
 /// convolve([0.2, 0.6, 0.2])
 /// @endcode
 template <typename T>
-ACTOR(convolve, IN(T, N), OUT(T, N), PARAM(std::span<const T>, kernel) PARAM(int, N)) {
+ACTOR(convolve, IN(T, N), OUT(T, N), PARAM(std::span<const T>, kernel) PARAM(int, N) PURE) {
     const int M = static_cast<int>(kernel.size());
     // Partial-kernel region (boundary): out[i] where i < M-1
     int i = 0;
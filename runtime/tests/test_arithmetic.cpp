@@ -1,7 +1,7 @@
 //
 // test_arithmetic.cpp — Runtime tests for arithmetic actors
 //
-// Tests basic arithmetic operations: add, sub, mul, div, abs, sqrt, threshold
+// Tests basic arithmetic operations: add, sub, mul, div, abs, sqrt, sqr, threshold
 //
 
 #include <cmath>
@@ -171,6 +171,17 @@ TEST(abs_zero) {
     ASSERT_EQ(out[0], 0.0f);
 }
 
+// ── Test: sqr actor ──
+TEST(sqr_basic) {
+    Actor_sqr<float> actor;
+    float in[1] = {-3.0f};
+    float out[1];
+
+    int result = actor(in, out);
+    ASSERT_EQ(result, ACTOR_OK);
+    ASSERT_EQ(out[0], 9.0f);
+}
+
 // ── Test: sqrt actor ──
 TEST(sqrt_basic) {
     Actor_sqrt<float> actor;
@@ -126,7 +126,7 @@ TEST(adapter_loopback_send_recv) {
 
     // Receive data
     int32_t recv_data[2] = {0, 0};
-    in_adapter.recv(recv_data, 2);
+    ASSERT_TRUE(in_adapter.recv(recv_data, 2));
     ASSERT_TRUE(recv_data[0] == 42);
     ASSERT_TRUE(recv_data[1] == 99);
 }
@@ -139,7 +139,7 @@ TEST(adapter_recv_zero_fill) {
     pipit::BindIoAdapter adapter("zero_fill", false, pipit::net::DTYPE_F32, 0, 1000.0, "udp",
                                  &state);
     float data[4] = {1.0f, 2.0f, 3.0f, 4.0f};
-    adapter.recv(data, 4);
+    ASSERT_TRUE(!adapter.recv(data, 4));
     for (int i = 0; i < 4; ++i) {
         ASSERT_TRUE(data[i] == 0.0f);
     }
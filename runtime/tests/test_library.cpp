@@ -0,0 +1,126 @@
+//
+// test_library.cpp — Unit tests for pipit_library.h (LibraryBindPort + LibraryRuntime)
+//
+
+#include <atomic>
+#include <chrono>
+#include <cstdio>
+#include <cstdlib>
+#include <thread>
+
+#include <pipit_library.h>
+
+#define TEST(name)                                                                                 \
+    static void test_##name();                                                                     \
+    static struct TestRunner_##name {                                                              \
+        TestRunner_##name() {                                                                      \
+            printf("Running test: %s\n", #name);                                                   \
+            test_##name();                                                                         \
+            printf("  PASS: %s\n", #name);                                                         \
+        }                                                                                          \
+    } runner_##name;                                                                               \
+    static void test_##name()
+
+
+#define ASSERT_TRUE(cond)                                                                          \
+    do {                                                                                           \
+        if (!(cond)) {                                                                             \
+            fprintf(stderr, "FAIL: %s:%d: condition false: %s\n", __FILE__, __LINE__, #cond);      \
+            exit(1);                                                                               \
+        }                                                                                          \
+    } while (0)
+
+// ── LibraryBindPort tests ───────────────────────────────────────────────────
+
+TEST(port_host_push_task_recv) {
+    pipit::LibraryBindPort<float, 8> port;
+    float frame[4] = {1.0f, 2.0f, 3.0f, 4.0f};
+    ASSERT_TRUE(port.push(frame, 4));
+    float out[4] = {};
+    ASSERT_TRUE(port.recv(out, 4));
+    for (int i = 0; i < 4; ++i) {
+        ASSERT_TRUE(out[i] == frame[i]);
+    }
+}
+
+TEST(port_recv_zero_fills_when_empty) {
+    pipit::LibraryBindPort<float, 8> port;
+    float out[4] = {9.0f, 9.0f, 9.0f, 9.0f};
+    ASSERT_TRUE(!port.recv(out, 4));
+    for (int i = 0; i < 4; ++i) {
+        ASSERT_TRUE(out[i] == 0.0f);
+    }
+}
+
+TEST(port_task_send_host_pop) {
+    pipit::LibraryBindPort<int32_t, 4> port;
+    int32_t data[2] = {42, 99};
+    port.send(data, 2);
+    int32_t out[2] = {};
+    ASSERT_TRUE(port.pop(out, 2));
+    ASSERT_TRUE(out[0] == 42);
+    ASSERT_TRUE(out[1] == 99);
+    ASSERT_TRUE(!port.pop(out, 2));
+}
+
+TEST(port_send_drops_when_full) {
+    pipit::LibraryBindPort<int32_t, 4> port;
+    int32_t first[4] = {1, 2, 3, 4};
+    int32_t second[4] = {5, 6, 7, 8};
+    port.send(first, 4);
+    port.send(second, 4); // host has not drained: dropped, not blocked
+    int32_t out[4] = {};
+    ASSERT_TRUE(port.pop(out, 4));
+    ASSERT_TRUE(out[0] == 1);
+    ASSERT_TRUE(!port.pop(out, 4));
+    ASSERT_TRUE(!port.push(first, 5));
+}
+
+// ── LibraryRuntime tests ────────────────────────────────────────────────────
+
+static std::atomic<bool> g_stop{false};
+static std::atomic<int> g_exit_code{0};
+static std::atomic<bool> g_start{false};
+static bool g_stats = false;
+static FILE *g_probe_output = nullptr;
+static std::atomic<int> g_ticks{0};
+
+static void task_counter() {
+    while (!g_start.load(std::memory_order_acquire)) {
+        std::this_thread::yield();
+    }
+    while (!g_stop.load(std::memory_order_acquire)) {
+        g_ticks.fetch_add(1, std::memory_order_relaxed);
+        std::this_thread::sleep_for(std::chrono::milliseconds(1));
+    }
+}
+
+static pipit::ProgramDesc make_desc() {
+    static pipit::TaskStats stats;
+    static const pipit::TaskDesc tasks[] = {{"counter", task_counter, &stats}};
+    pipit::ProgramDesc desc{};
+    desc.state = {&g_stop, &g_exit_code, &g_start, &g_stats, &g_probe_output};
+    desc.tasks = tasks;
+    desc.overrun_policy = "drop";
+    return desc;
+}
+
+TEST(runtime_start_stop_restart) {
+    static const pipit::ProgramDesc desc = make_desc();
+    pipit::LibraryRuntime runtime;
+    ASSERT_TRUE(runtime.stop(desc) == -1);
+    for (int round = 0; round < 2; ++round) {
+        g_ticks.store(0);
+        ASSERT_TRUE(runtime.start(desc));
+        ASSERT_TRUE(!runtime.start(desc));
+        std::this_thread::sleep_for(std::chrono::milliseconds(20));
+        ASSERT_TRUE(runtime.stop(desc) == 0);
+        ASSERT_TRUE(g_ticks.load() > 0);
+        ASSERT_TRUE(g_stop.load());
+    }
+}
+
+int main() {
+    printf("All library tests passed.\n");
+    return 0;
+}
@@ -55,7 +55,7 @@ TEST(ppkt_header_offsets) {
     ASSERT_EQ(reinterpret_cast<const char *>(&h.dtype) - base, 6);
     ASSERT_EQ(reinterpret_cast<const char *>(&h.flags) - base, 7);
     ASSERT_EQ(reinterpret_cast<const char *>(&h.chan_id) - base, 8);
-    ASSERT_EQ(reinterpret_cast<const char *>(&h.reserved) - base, 10);
+    ASSERT_EQ(reinterpret_cast<const char *>(&h.contract_version) - base, 10);
     ASSERT_EQ(reinterpret_cast<const char *>(&h.sequence) - base, 12);
     ASSERT_EQ(reinterpret_cast<const char *>(&h.sample_count) - base, 16);
     ASSERT_EQ(reinterpret_cast<const char *>(&h.payload_bytes) - base, 20);
@@ -71,7 +71,7 @@ TEST(ppkt_make_header) {
     ASSERT_EQ(h.header_len, PPKT_HEADER_LEN);
     ASSERT_EQ(h.dtype, DTYPE_F32);
     ASSERT_EQ(h.chan_id, 7);
-    ASSERT_EQ(h.reserved, 0);
+    ASSERT_EQ(h.contract_version, 0);
     ASSERT_EQ(h.sequence, 0);
     ASSERT_EQ(h.sample_count, 0);
     ASSERT_EQ(h.payload_bytes, 0);
@@ -2,7 +2,7 @@
 // test_ringbuf.cpp — Runtime tests for SPSC RingBuffer partial specialization
 //
 // Tests: SPSC correctness (single-threaded), SPSC concurrent stress test,
-//        API compatibility with generic class
+//        API compatibility with generic class, debug edge buffer guard
 //
 
 #include <atomic>
@@ -139,6 +139,24 @@ TEST(spsc_full_buffer_fails) {
     ASSERT_TRUE(!rb.write(&extra, 1));
 }
 
+// ── Write space tracks the slowest reader ──
+
+TEST(writable_tracks_slowest_reader) {
+    pipit::RingBuffer<float, 4, 1> spsc;
+    float src[3] = {1, 2, 3};
+    ASSERT_EQ(spsc.writable(), 4u);
+    ASSERT_TRUE(spsc.write(src, 3));
+    ASSERT_EQ(spsc.writable(), 1u);
+
+    pipit::RingBuffer<float, 4, 2> multi;
+    float out[2];
+    ASSERT_TRUE(multi.write(src, 3));
+    ASSERT_TRUE(multi.read(0, out, 2));
+    ASSERT_EQ(multi.writable(), 1u);
+    ASSERT_TRUE(multi.read(1, out, 1));
+    ASSERT_EQ(multi.writable(), 2u);
+}
+
 // ── Empty buffer: read should fail when buffer is empty ──
 
 TEST(spsc_empty_read_fails) {
@@ -349,6 +367,21 @@ TEST(wait_writable_unblocked_by_reader) {
     ASSERT_TRUE(wait_result.load() == static_cast<int>(pipit::WaitResult::ready));
 }
 
+// ── Edge buffer guard (debug codegen) ───────────────────────────────────────
+
+TEST(edge_guard_detects_overrun) {
+    alignas(64) static float buf[8 + pipit::EDGE_GUARD_TOKENS];
+    pipit::edge_guard_fill(buf);
+    ASSERT_TRUE(pipit::edge_guard_intact(buf));
+    for (int i = 0; i < 8; ++i)
+        buf[i] = 1.0f; // in bounds: guard untouched
+    ASSERT_TRUE(pipit::edge_guard_intact(buf));
+    buf[8] = 0.0f; // one token past the payload
+    ASSERT_TRUE(!pipit::edge_guard_intact(buf));
+    pipit::edge_guard_fill(buf);
+    ASSERT_TRUE(pipit::edge_guard_intact(buf));
+}
+
 int main() {
     printf("All RingBuffer tests passed.\n");
     return 0;
@@ -100,6 +100,61 @@ TEST(shell_finite_duration) {
     ASSERT_EQ(rc, 0);
 }
 
+static char g_thread_name[16] = {};
+
+static void naming_task() {
+#if defined(__linux__)
+    pthread_getname_np(pthread_self(), g_thread_name, sizeof(g_thread_name));
+#endif
+    mock_task();
+}
+
+TEST(shell_names_task_threads) {
+    reset_state();
+    static const pipit::TaskDesc tasks[] = {{"decimate_stage", naming_task, &g_task_stats}};
+    auto desc = make_empty_desc();
+    desc.tasks = tasks;
+
+    const char *args[] = {"prog", "--duration", "0"};
+    int rc = call_shell(args, desc);
+    ASSERT_EQ(rc, 0);
+#if defined(__linux__)
+    // "task_decimate_stage" truncated to 15 characters
+    ASSERT_TRUE(std::strcmp(g_thread_name, "task_decimate_s") == 0);
+#endif
+}
+
+static int g_pinned_cpu_count = -1;
+static bool g_pinned_to_core0 = false;
+
+static void affinity_task() {
+#if defined(__linux__)
+    cpu_set_t set;
+    CPU_ZERO(&set);
+    if (pthread_getaffinity_np(pthread_self(), sizeof(set), &set) == 0) {
+        g_pinned_cpu_count = CPU_COUNT(&set);
+        g_pinned_to_core0 = CPU_ISSET(0, &set);
+    }
+#endif
+    mock_task();
+}
+
+TEST(shell_pins_task_threads) {
+    reset_state();
+    static const pipit::TaskDesc tasks[] = {
+        {"pinned", affinity_task, &g_task_stats, nullptr, 0}};
+    auto desc = make_empty_desc();
+    desc.tasks = tasks;
+
+    const char *args[] = {"prog", "--duration", "0"};
+    int rc = call_shell(args, desc);
+    ASSERT_EQ(rc, 0);
+#if defined(__linux__)
+    ASSERT_EQ(g_pinned_cpu_count, 1);
+    ASSERT_TRUE(g_pinned_to_core0);
+#endif
+}
+
 TEST(shell_duration_invalid) {
     reset_state();
     auto desc = make_empty_desc();
@@ -127,6 +182,26 @@ TEST(shell_unknown_param) {
     ASSERT_EQ(rc, 2);
 }
 
+TEST(shell_coerce_param) {
+    int i = 0;
+    ASSERT_TRUE(pipit::coerce_param(42.0, &i));
+    ASSERT_EQ(i, 42);
+    ASSERT_TRUE(!pipit::coerce_param(1.5, &i));
+    ASSERT_TRUE(!pipit::coerce_param(3e9, &i));
+    ASSERT_TRUE(!pipit::coerce_param(std::numeric_limits<double>::quiet_NaN(), &i));
+    ASSERT_EQ(i, 42);
+
+    float f = 0.0f;
+    ASSERT_TRUE(pipit::coerce_param(0.25, &f));
+    ASSERT_TRUE(f == 0.25f);
+    ASSERT_TRUE(!pipit::coerce_param(1e300, &f));
+    ASSERT_TRUE(!pipit::coerce_param(std::numeric_limits<double>::quiet_NaN(), &f));
+
+    double d = 0.0;
+    ASSERT_TRUE(pipit::coerce_param(1e300, &d));
+    ASSERT_TRUE(d == 1e300);
+}
+
 TEST(shell_stats_flag) {
     reset_state();
     static const pipit::TaskDesc tasks[] = {{"mock", mock_task, &g_task_stats}};
@@ -139,6 +214,26 @@ TEST(shell_stats_flag) {
     ASSERT_TRUE(g_stats);
 }
 
+TEST(shell_actor_profile_report) {
+    reset_state();
+    static const pipit::TaskDesc tasks[] = {{"mock", mock_task, &g_task_stats}};
+    static pipit::ActorProfile fast{}, slow{}, idle{};
+    fast.record(100);
+    slow.record(900, 3);
+    static const pipit::ActorProfileDesc profiles[] = {
+        {"mock", "fast", 0, &fast}, {"mock", "slow", 1, &slow}, {"mock", "idle", 2, &idle}};
+    auto desc = make_empty_desc();
+    desc.tasks = tasks;
+    desc.actor_profiles = profiles;
+
+    const char *args[] = {"prog", "--duration", "0"};
+    int rc = call_shell(args, desc);
+    ASSERT_EQ(rc, 0);
+    ASSERT_EQ(slow.firings, 3u);
+    ASSERT_EQ(slow.total_ns, 900u);
+    ASSERT_EQ(idle.firings, 0u);
+}
+
 TEST(shell_probe_known) {
     reset_state();
     static bool probe_enabled = false;
@@ -0,0 +1,225 @@
+//
+// test_tcp.cpp — Unit tests for pipit_tcp.h (TcpSender/TcpReceiver + TcpIoAdapter)
+//
+
+#include <chrono>
+#include <cstdio>
+#include <cstdlib>
+#include <cstring>
+#include <string>
+#include <thread>
+
+#include <pipit_tcp.h>
+
+#define TEST(name)                                                                                 \
+    static void test_##name();                                                                     \
+    static struct TestRunner_##name {                                                              \
+        TestRunner_##name() {                                                                      \
+            printf("Running test: %s\n", #name);                                                   \
+            test_##name();                                                                         \
+            printf("  PASS: %s\n", #name);                                                         \
+        }                                                                                          \
+    } runner_##name;                                                                               \
+    static void test_##name()
+
+#define ASSERT_TRUE(cond)                                                                          \
+    do {                                                                                           \
+        if (!(cond)) {                                                                             \
+            fprintf(stderr, "FAIL: %s:%d: condition false: %s\n", __FILE__, __LINE__, #cond);      \
+            exit(1);                                                                               \
+        }                                                                                          \
+    } while (0)
+
+/// Poll an in-progress connect; open() itself never waits.
+static bool wait_connected(pipit::net::TcpSender &sender) {
+    for (int i = 0; i < 1000 && sender.is_valid(); i++) {
+        if (sender.connected())
+            return true;
+        std::this_thread::sleep_for(std::chrono::milliseconds(1));
+    }
+    return false;
+}
+
+// ── Socket-level tests ──────────────────────────────────────────────────────
+
+TEST(sender_rejects_unix_address) {
+    pipit::net::TcpSender sender;
+    const char *addr = "unix:///tmp/pipit_tcp_test.sock";
+    ASSERT_TRUE(!sender.open(addr, std::strlen(addr)));
+}
+
+TEST(sender_refused_without_listener) {
+    pipit::net::TcpSender sender;
+    const char *addr = "127.0.0.1:19301";
+    if (sender.open(addr, std::strlen(addr)))
+        ASSERT_TRUE(!wait_connected(sender));
+    ASSERT_TRUE(!sender.is_valid());
+}
+
+TEST(stream_reassembles_packets) {
+    const char *addr = "127.0.0.1:19302";
+    pipit::net::TcpReceiver receiver;
+    ASSERT_TRUE(receiver.open(addr, std::strlen(addr), 4));
+
+    pipit::net::TcpSender sender;
+    ASSERT_TRUE(sender.open(addr, std::strlen(addr)));
+    ASSERT_TRUE(wait_connected(sender));
+
+    auto hdr = pipit::net::ppkt_make_header(pipit::net::DTYPE_I32, 3);
+    int32_t a[3] = {1, 2, 3};
+    int32_t b[2] = {7, 8};
+    ASSERT_TRUE(pipit::net::ppkt_send_stream(sender, hdr, a, 3));
+    ASSERT_TRUE(pipit::net::ppkt_send_stream(sender, hdr, b, 2));
+    std::this_thread::sleep_for(std::chrono::milliseconds(10));
+
+    uint8_t buf[256];
+    ssize_t n1 = receiver.recv(buf, sizeof(buf));
+    ASSERT_TRUE(n1 == static_cast<ssize_t>(sizeof(pipit::net::PpktHeader) + 3 * 4));
+    pipit::net::PpktHeader got;
+    std::memcpy(&got, buf, sizeof(got));
+    ASSERT_TRUE(got.sample_count == 3 && got.chan_id == 3 && got.sequence == 0);
+    ASSERT_TRUE(got.flags & pipit::net::FLAG_FRAME_START);
+    ASSERT_TRUE(got.flags & pipit::net::FLAG_FRAME_END);
+
+    ssize_t n2 = receiver.recv(buf, sizeof(buf));
+    ASSERT_TRUE(n2 == static_cast<ssize_t>(sizeof(pipit::net::PpktHeader) + 2 * 4));
+    std::memcpy(&got, buf, sizeof(got));
+    ASSERT_TRUE(got.sequence == 1);
+    int32_t payload[2];
+    std::memcpy(payload, buf + sizeof(got), sizeof(payload));
+    ASSERT_TRUE(payload[0] == 7 && payload[1] == 8);
+
+    ASSERT_TRUE(receiver.recv(buf, sizeof(buf)) == 0);
+}
+
+TEST(receiver_keeps_packets_after_peer_closes) {
+    const char *addr = "127.0.0.1:19303";
+    pipit::net::TcpReceiver receiver;
+    ASSERT_TRUE(receiver.open(addr, std::strlen(addr)));
+    uint8_t buf[256];
+    {
+        pipit::net::TcpSender sender;
+        ASSERT_TRUE(sender.open(addr, std::strlen(addr)));
+        ASSERT_TRUE(wait_connected(sender));
+        ASSERT_TRUE(receiver.recv(buf, sizeof(buf)) == 0); // accept
+        auto hdr = pipit::net::ppkt_make_header(pipit::net::DTYPE_F32, 0);
+        float x[1] = {1.5f};
+        ASSERT_TRUE(pipit::net::ppkt_send_stream(sender, hdr, x, 1));
+    }
+    std::this_thread::sleep_for(std::chrono::milliseconds(10));
+    ASSERT_TRUE(receiver.recv(buf, sizeof(buf)) > 0);
+    ASSERT_TRUE(!receiver.has_peer());
+}
+
+TEST(sender_drops_whole_packets_while_peer_lags) {
+    const char *addr = "127.0.0.1:19306";
+    pipit::net::TcpReceiver receiver;
+    ASSERT_TRUE(receiver.open(addr, std::strlen(addr)));
+    pipit::net::TcpSender sender;
+    ASSERT_TRUE(sender.open(addr, std::strlen(addr)));
+    ASSERT_TRUE(wait_connected(sender));
+
+    // The receiver does not read, so the socket buffers fill up.  Sends must
+    // then drop firings instead of waiting for the peer.
+    static float samples[16384];
+    auto hdr = pipit::net::ppkt_make_header(pipit::net::DTYPE_F32, 0);
+    auto start = std::chrono::steady_clock::now();
+    int sent = 0;
+    bool dropped = false;
+    for (int i = 0; i < 4096 && !dropped; i++) {
+        if (pipit::net::ppkt_send_stream(sender, hdr, samples, 16384))
+            sent++;
+        else
+            dropped = true;
+    }
+    auto elapsed = std::chrono::steady_clock::now() - start;
+    ASSERT_TRUE(dropped);
+    ASSERT_TRUE(sender.is_valid());
+    ASSERT_TRUE(elapsed < std::chrono::seconds(1));
+
+    // Draining the receiver lets the pending packet through; every packet on
+    // the stream is complete and the dropped one leaves a sequence gap.
+    static uint8_t buf[sizeof(pipit::net::PpktHeader) + sizeof(samples)];
+    const float marker[1] = {-1.0f};
+    bool marker_queued = false;
+    int received = 0;
+    uint32_t last_seq = 0;
+    for (int i = 0; i < 5000; i++) {
+        if (!marker_queued)
+            marker_queued = pipit::net::ppkt_send_stream(sender, hdr, marker, 1);
+        ssize_t n = receiver.recv(buf, sizeof(buf));
+        ASSERT_TRUE(n >= 0);
+        if (n == 0) {
+            std::this_thread::sleep_for(std::chrono::milliseconds(1));
+            continue;
+        }
+        pipit::net::PpktHeader got;
+        std::memcpy(&got, buf, sizeof(got));
+        ASSERT_TRUE(n == static_cast<ssize_t>(sizeof(got) + got.payload_bytes));
+        if (got.sample_count == 1) {
+            ASSERT_TRUE(got.sequence > last_seq + 1);
+            break;
+        }
+        ASSERT_TRUE(got.sample_count == 16384);
+        last_seq = got.sequence;
+        received++;
+    }
+    ASSERT_TRUE(marker_queued);
+    ASSERT_TRUE(received == sent);
+}
+
+// ── Adapter tests ───────────────────────────────────────────────────────────
+
+TEST(adapter_no_endpoint_is_noop) {
+    pipit::BindState state;
+    state.current_endpoint = "";
+    pipit::TcpIoAdapter out("out", true, pipit::net::DTYPE_F32, 0, 1000.0, &state);
+    float data[2] = {1.0f, 2.0f};
+    out.send(data, 2);
+
+    pipit::TcpIoAdapter in("in", false, pipit::net::DTYPE_F32, 0, 1000.0, &state);
+    in.recv(data, 2);
+    ASSERT_TRUE(data[0] == 0.0f && data[1] == 0.0f);
+}
+
+TEST(adapter_loopback_send_recv) {
+    std::string spec = "tcp(\"127.0.0.1:19304\", backlog=2)";
+    pipit::BindState in_state;
+    in_state.current_endpoint = spec;
+    pipit::TcpIoAdapter in_adapter("in", false, pipit::net::DTYPE_I32, 0, 1000.0, &in_state, 2);
+    int32_t dummy[1] = {0};
+    in_adapter.recv(dummy, 1); // listen
+
+    pipit::BindState out_state;
+    out_state.current_endpoint = spec;
+    pipit::TcpIoAdapter out_adapter("out", true, pipit::net::DTYPE_I32, 0, 1000.0, &out_state);
+    int32_t first[2] = {1, 2};
+    int32_t second[2] = {42, 99};
+    out_adapter.send(first, 2); // start connecting
+    in_adapter.recv(dummy, 1);  // accept
+    std::this_thread::sleep_for(std::chrono::milliseconds(10));
+    out_adapter.send(second, 2);
+    std::this_thread::sleep_for(std::chrono::milliseconds(10));
+
+    // Latest complete packet wins, as with the datagram adapter
+    int32_t recv_data[2] = {0, 0};
+    in_adapter.recv(recv_data, 2);
+    ASSERT_TRUE(recv_data[0] == 42);
+    ASSERT_TRUE(recv_data[1] == 99);
+}
+
+TEST(adapter_reconnect_empty) {
+    pipit::BindState state;
+    state.current_endpoint = "127.0.0.1:19305";
+    pipit::TcpIoAdapter adapter("reconnect", true, pipit::net::DTYPE_F32, 0, 1000.0, &state, 1, 0);
+    // Generated code commits the new endpoint to BindState before reconnect()
+    state.current_endpoint = "";
+    adapter.reconnect("");
+    float data[1] = {1.0f};
+    adapter.send(data, 1);
+}
+
+int main() {
+    printf("All tcp tests passed.\n");
+    return 0;
+}
@@ -78,6 +78,28 @@ TEST(timer_default_spin_10us) {
     ASSERT_EQ(t.current_spin_threshold().count(), 0);
 }
 
+// -- Period math --
+
+TEST(timer_sub_hz_period) {
+    pipit::Timer half(0.5, true, -1); // 0.5Hz → 2s period
+    ASSERT_EQ(half.period().count(), 2'000'000'000LL);
+    ASSERT_TRUE(half.is_adaptive());
+    // Spin budget stays a tiny fraction of a long period
+    ASSERT_TRUE(half.current_spin_threshold() < half.period() / 1000);
+
+    pipit::Timer frac(1250.0); // 1.25kHz → 800us period
+    ASSERT_EQ(frac.period().count(), 800'000);
+
+    pipit::Timer third(3.0); // rounded, not truncated
+    ASSERT_EQ(third.period().count(), 333'333'333);
+}
+
+TEST(timer_period_never_zero) {
+    pipit::Timer t(1e12); // sub-nanosecond period clamps to 1ns
+    ASSERT_EQ(t.period().count(), 1);
+    ASSERT_TRUE(t.missed_count() >= 0);
+}
+
 int main() {
     printf("All timer tests passed.\n");
     return 0;
@@ -1,7 +1,7 @@
 //
 // test_transform.cpp — Runtime tests for transform actors
 //
-// Tests: c2r, mag, fir
+// Tests: c2r, mag, magsq, fir
 //
 
 #include <cmath>
@@ -102,6 +102,19 @@ TEST(mag_zero) {
     ASSERT_NEAR(out[0], 0.0f, 0.0001f);
 }
 
+// ── Test: magsq actor ──
+TEST(magsq_magnitude_squared) {
+    Actor_magsq actor;
+    actor.N = 2;
+    cfloat in[2] = {cfloat(3.0f, 4.0f), cfloat(0.0f, -2.0f)};
+    float out[2];
+
+    int result = actor(in, out);
+    ASSERT_EQ(result, ACTOR_OK);
+    ASSERT_NEAR(out[0], 25.0f, 0.0001f);
+    ASSERT_NEAR(out[1], 4.0f, 0.0001f);
+}
+
 // ── Test: fir actor ──
 TEST(fir_basic) {
     Actor_fir<float> actor;